@@ -7,6 +7,7 @@ fn bench_alert_creation(c: &mut Criterion) {
     c.bench_function("create_alert", |b| {
         b.iter(|| {
             let alert = Alert {
+                schema_version: SCHEMA_VERSION,
                 id: uuid::Uuid::new_v4().to_string(),
                 timestamp: chrono::Utc::now().timestamp() as u64,
                 chain: black_box("test-chain".to_string()),
@@ -17,6 +18,7 @@ fn bench_alert_creation(c: &mut Criterion) {
                 block_number: Some(1000),
                 metadata: std::collections::HashMap::new(),
                 recommended_actions: vec!["Action 1".to_string()],
+                acknowledged: false,
             };
             black_box(alert);
         });
@@ -0,0 +1,34 @@
+//! Embeds the git commit hash reported by `GET /api/info`
+//!
+//! `GIT_HASH` in the build environment takes precedence, for builds without
+//! a `.git` directory (e.g. Docker images).
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=GIT_HASH");
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    println!("cargo:rerun-if-changed=../../.git/refs/heads");
+
+    let hash = std::env::var("GIT_HASH")
+        .ok()
+        .filter(|hash| !hash.trim().is_empty())
+        .or_else(git_hash)
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=GIT_HASH={}", hash.trim());
+}
+
+fn git_hash() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+}
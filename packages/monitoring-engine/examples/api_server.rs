@@ -49,6 +49,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         min_alert_severity: AlertSeverity::Low,
         buffer_size: 1000,
         max_reconnect_attempts: 5,
+        ..MonitorConfig::default()
     };
 
     tracing::info!("Configuration:");
@@ -0,0 +1,15 @@
+-- ============================================
+-- Migration 1.1.0: detector version on detections
+-- ============================================
+-- Records which version of a detector's logic produced each detection.
+-- Existing rows predate versioning and are backfilled as version '1'.
+
+ALTER TABLE detections
+    ADD COLUMN IF NOT EXISTS detector_version TEXT NOT NULL DEFAULT '1';
+
+CREATE INDEX IF NOT EXISTS idx_detection_version
+    ON detections(detector_name, detector_version, timestamp DESC);
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.1.0', 'Add detector_version to detections')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,16 @@
+-- ============================================
+-- Migration 1.2.0: detector state snapshots
+-- ============================================
+-- Stores the latest state of detectors that learn across blocks so
+-- baselines survive restarts instead of re-warming from scratch.
+
+CREATE TABLE IF NOT EXISTS detector_state (
+    detector_name TEXT PRIMARY KEY,
+    detector_version TEXT NOT NULL,
+    state JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.2.0', 'Add detector_state table')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,20 @@
+-- ============================================
+-- Migration 1.3.0: backfill checkpoints
+-- ============================================
+-- Records the last fully processed block of each historical backfill so
+-- an interrupted backfill resumes where it stopped instead of restarting.
+
+CREATE TABLE IF NOT EXISTS backfill_checkpoints (
+    chain TEXT NOT NULL,
+    from_block BIGINT NOT NULL,
+    to_block BIGINT NOT NULL,
+    last_processed_block BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (chain, from_block, to_block)
+);
+
+CREATE INDEX IF NOT EXISTS idx_backfill_chain ON backfill_checkpoints(chain, updated_at DESC);
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.3.0', 'Add backfill_checkpoints table')
+ON CONFLICT (version) DO NOTHING;
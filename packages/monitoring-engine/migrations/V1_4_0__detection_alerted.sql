@@ -0,0 +1,15 @@
+-- ============================================
+-- Migration 1.4.0: near-miss detections
+-- ============================================
+-- Detections below the alert threshold can be stored for threshold tuning
+-- and ML labels. Existing rows all raised an alert.
+
+ALTER TABLE detections
+    ADD COLUMN IF NOT EXISTS alerted BOOLEAN NOT NULL DEFAULT TRUE;
+
+CREATE INDEX IF NOT EXISTS idx_detection_alerted
+    ON detections(alerted, timestamp DESC);
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.4.0', 'Add alerted flag to detections')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,15 @@
+-- ============================================
+-- Migration 1.5.0: transfer destination and value
+-- ============================================
+-- Balances transfers record who received how much, so transactions can be
+-- exported as a graph of addresses. Existing rows have neither.
+
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS destination TEXT,
+    ADD COLUMN IF NOT EXISTS value NUMERIC;
+
+CREATE INDEX IF NOT EXISTS idx_tx_destination ON transactions(destination);
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.5.0', 'Add transfer destination and value to transactions')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,18 @@
+-- ============================================
+-- Migration 1.6.0: detection feedback
+-- ============================================
+-- Stores analyst verdicts on detections so noisy detectors can be tuned
+-- and labels are available for offline retraining.
+
+CREATE TABLE IF NOT EXISTS detection_feedback (
+    detection_id TEXT PRIMARY KEY,
+    detector_name TEXT NOT NULL,
+    true_positive BOOLEAN NOT NULL,
+    labeled_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_feedback_detector ON detection_feedback(detector_name, labeled_at DESC);
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.6.0', 'Add detection_feedback table')
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,29 @@
+-- ============================================
+-- Migration 1.7.0: persistent alert history
+-- ============================================
+-- Persists composed alerts so history survives restarts.
+-- Regular table (not a hypertable): rows are updated on acknowledgment.
+
+CREATE TABLE IF NOT EXISTS alerts (
+    timestamp TIMESTAMPTZ NOT NULL,
+    alert_id TEXT PRIMARY KEY,
+    tx_hash TEXT,
+    block_number BIGINT,
+    chain TEXT NOT NULL,
+    severity TEXT NOT NULL CHECK (severity IN ('low', 'medium', 'high', 'critical')),
+    attack_pattern TEXT NOT NULL,
+    description TEXT NOT NULL,
+    metadata JSONB NOT NULL DEFAULT '{}'::jsonb,
+    recommended_actions TEXT[] NOT NULL DEFAULT '{}',
+    acknowledged BOOLEAN NOT NULL DEFAULT FALSE,
+    acknowledged_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_alert_time ON alerts(timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_alert_severity ON alerts(severity, timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_alert_ack ON alerts(acknowledged, timestamp DESC);
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.7.0', 'Add alerts table')
+ON CONFLICT (version) DO NOTHING;
@@ -33,6 +33,10 @@ CREATE TABLE IF NOT EXISTS transactions (
     -- Flexible storage for call arguments
     args JSONB,
 
+    -- Balances transfers: recipient (SS58) and amount (planck)
+    destination TEXT,
+    value NUMERIC,
+
     -- Gas/fees
     gas_used BIGINT,
     fee_paid NUMERIC,
@@ -48,6 +52,7 @@ SELECT create_hypertable('transactions', 'timestamp', if_not_exists => TRUE);
 CREATE INDEX IF NOT EXISTS idx_tx_chain_time ON transactions(chain, timestamp DESC);
 CREATE INDEX IF NOT EXISTS idx_tx_pallet_time ON transactions(pallet, timestamp DESC);
 CREATE INDEX IF NOT EXISTS idx_tx_caller ON transactions(caller);
+CREATE INDEX IF NOT EXISTS idx_tx_destination ON transactions(destination);
 CREATE INDEX IF NOT EXISTS idx_tx_block ON transactions(block_number);
 
 -- ============================================
@@ -105,6 +110,7 @@ CREATE TABLE IF NOT EXISTS detections (
 
     -- Detection details
     detector_name TEXT NOT NULL,
+    detector_version TEXT NOT NULL DEFAULT '1',
     attack_pattern TEXT NOT NULL,
     confidence DOUBLE PRECISION NOT NULL CHECK (confidence >= 0 AND confidence <= 1),
     severity TEXT NOT NULL CHECK (severity IN ('low', 'medium', 'high', 'critical')),
@@ -119,6 +125,8 @@ CREATE TABLE IF NOT EXISTS detections (
     acknowledged BOOLEAN DEFAULT FALSE,
     acknowledged_at TIMESTAMPTZ,
     acknowledged_by TEXT,
+    -- FALSE for near misses stored below the alert threshold
+    alerted BOOLEAN NOT NULL DEFAULT TRUE,
 
     -- Created timestamp
     created_at TIMESTAMPTZ DEFAULT NOW()
@@ -132,6 +140,8 @@ CREATE INDEX IF NOT EXISTS idx_detection_severity ON detections(severity, timest
 CREATE INDEX IF NOT EXISTS idx_detection_pattern ON detections(attack_pattern, timestamp DESC);
 CREATE INDEX IF NOT EXISTS idx_detection_chain ON detections(chain, timestamp DESC);
 CREATE INDEX IF NOT EXISTS idx_detection_ack ON detections(acknowledged, timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_detection_version ON detections(detector_name, detector_version, timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_detection_alerted ON detections(alerted, timestamp DESC);
 
 -- ============================================
 -- 4. ML FEATURES TABLE (Hypertable)
@@ -350,6 +360,85 @@ CREATE INDEX IF NOT EXISTS idx_liq_liquidator ON hydration_liquidations(liquidat
 CREATE INDEX IF NOT EXISTS idx_liq_borrower ON hydration_liquidations(borrower, timestamp DESC);
 CREATE INDEX IF NOT EXISTS idx_liq_profit ON hydration_liquidations(liquidator_profit DESC) WHERE liquidator_profit > 0;
 
+-- ============================================
+-- 8. ALERTS TABLE
+-- ============================================
+-- Persists composed alerts so history survives restarts.
+-- Regular table (not a hypertable): rows are updated on acknowledgment.
+CREATE TABLE IF NOT EXISTS alerts (
+    -- Temporal
+    timestamp TIMESTAMPTZ NOT NULL,
+
+    -- Alert identifiers
+    alert_id TEXT PRIMARY KEY,
+    tx_hash TEXT,
+    block_number BIGINT,
+
+    -- Chain info
+    chain TEXT NOT NULL,
+
+    -- Alert details
+    severity TEXT NOT NULL CHECK (severity IN ('low', 'medium', 'high', 'critical')),
+    attack_pattern TEXT NOT NULL,
+    description TEXT NOT NULL,
+    metadata JSONB NOT NULL DEFAULT '{}'::jsonb,
+    recommended_actions TEXT[] NOT NULL DEFAULT '{}',
+
+    -- Status
+    acknowledged BOOLEAN NOT NULL DEFAULT FALSE,
+    acknowledged_at TIMESTAMPTZ,
+
+    -- Created timestamp
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- Indexes
+CREATE INDEX IF NOT EXISTS idx_alert_time ON alerts(timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_alert_severity ON alerts(severity, timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_alert_ack ON alerts(acknowledged, timestamp DESC);
+
+-- ============================================
+-- 9. DETECTOR STATE TABLE
+-- ============================================
+-- Latest state snapshot of detectors that learn across blocks
+-- (baselines, per-caller histories), restored on engine startup.
+CREATE TABLE IF NOT EXISTS detector_state (
+    detector_name TEXT PRIMARY KEY,
+    detector_version TEXT NOT NULL,
+    state JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- ============================================
+-- 10. BACKFILL CHECKPOINT TABLE
+-- ============================================
+-- Last fully processed block of each historical backfill range,
+-- so an interrupted backfill resumes instead of starting over.
+CREATE TABLE IF NOT EXISTS backfill_checkpoints (
+    chain TEXT NOT NULL,
+    from_block BIGINT NOT NULL,
+    to_block BIGINT NOT NULL,
+    last_processed_block BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (chain, from_block, to_block)
+);
+
+CREATE INDEX IF NOT EXISTS idx_backfill_chain ON backfill_checkpoints(chain, updated_at DESC);
+
+-- ============================================
+-- 11. DETECTION FEEDBACK TABLE
+-- ============================================
+-- Analyst verdicts on detections (true or false positive), used to raise
+-- thresholds of noisy detectors and as labels for offline retraining.
+CREATE TABLE IF NOT EXISTS detection_feedback (
+    detection_id TEXT PRIMARY KEY,
+    detector_name TEXT NOT NULL,
+    true_positive BOOLEAN NOT NULL,
+    labeled_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_feedback_detector ON detection_feedback(detector_name, labeled_at DESC);
+
 -- ============================================
 -- CONTINUOUS AGGREGATES
 -- ============================================
@@ -616,3 +705,31 @@ CREATE TABLE IF NOT EXISTS schema_version (
 INSERT INTO schema_version (version, description)
 VALUES ('1.0.0', 'Initial schema with TimescaleDB hypertables, continuous aggregates, and compression policies')
 ON CONFLICT (version) DO NOTHING;
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.1.0', 'Add detector_version to detections')
+ON CONFLICT (version) DO NOTHING;
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.2.0', 'Add detector_state table')
+ON CONFLICT (version) DO NOTHING;
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.3.0', 'Add backfill_checkpoints table')
+ON CONFLICT (version) DO NOTHING;
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.4.0', 'Add alerted flag to detections')
+ON CONFLICT (version) DO NOTHING;
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.5.0', 'Add transfer destination and value to transactions')
+ON CONFLICT (version) DO NOTHING;
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.6.0', 'Add detection_feedback table')
+ON CONFLICT (version) DO NOTHING;
+
+INSERT INTO schema_version (version, description)
+VALUES ('1.7.0', 'Add alerts table')
+ON CONFLICT (version) DO NOTHING;
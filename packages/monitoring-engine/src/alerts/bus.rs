@@ -0,0 +1,117 @@
+//! Message bus sinks (Kafka and NATS)
+//!
+//! Both connect on first publish, so the engine starts even when the broker
+//! is down, and reconnect after a failed publish. Messages are encoded with
+//! [`encode_alert`].
+
+use super::sink::{encode_alert, Sink};
+use crate::types::Alert;
+use crate::{Error, Result};
+use rskafka::client::partition::{Compression, PartitionClient, UnknownTopicHandling};
+use rskafka::client::ClientBuilder;
+use rskafka::record::Record;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex, OnceCell};
+
+/// Publishes alerts to a Kafka topic, keyed by alert ID
+///
+/// Alerts are produced to partition 0, which keeps them in trigger order.
+pub struct KafkaSink {
+    brokers: Vec<String>,
+    topic: String,
+    client: Mutex<Option<Arc<PartitionClient>>>,
+}
+
+impl KafkaSink {
+    pub fn new(brokers: Vec<String>, topic: impl Into<String>) -> Self {
+        Self {
+            brokers,
+            topic: topic.into(),
+            client: Mutex::new(None),
+        }
+    }
+
+    /// Reuse the partition client, connecting first if needed
+    async fn partition_client(&self) -> Result<Arc<PartitionClient>> {
+        let mut client = self.client.lock().await;
+        if let Some(partition) = client.as_ref() {
+            return Ok(partition.clone());
+        }
+
+        let connection = ClientBuilder::new(self.brokers.clone())
+            .build()
+            .await
+            .map_err(|e| Error::ConnectionError(format!("Kafka brokers unreachable: {}", e)))?;
+        let partition = connection
+            .partition_client(self.topic.clone(), 0, UnknownTopicHandling::Retry)
+            .await
+            .map_err(|e| Error::ConnectionError(format!("Kafka topic {}: {}", self.topic, e)))?;
+
+        let partition = Arc::new(partition);
+        *client = Some(partition.clone());
+        Ok(partition)
+    }
+}
+
+#[async_trait::async_trait]
+impl Sink for KafkaSink {
+    fn name(&self) -> &str {
+        "kafka"
+    }
+
+    async fn publish(&self, alert: &Alert) -> Result<()> {
+        let partition = self.partition_client().await?;
+        let record = Record {
+            key: Some(alert.id.clone().into_bytes()),
+            value: Some(encode_alert(alert)),
+            headers: BTreeMap::new(),
+            timestamp: chrono::Utc::now(),
+        };
+
+        if let Err(e) = partition.produce(vec![record], Compression::NoCompression).await {
+            // Reconnect on the next attempt
+            *self.client.lock().await = None;
+            return Err(Error::AlertError(format!("Kafka publish failed: {}", e)));
+        }
+        Ok(())
+    }
+}
+
+/// Publishes alerts to a NATS subject
+pub struct NatsSink {
+    url: String,
+    subject: String,
+    client: OnceCell<async_nats::Client>,
+}
+
+impl NatsSink {
+    pub fn new(url: impl Into<String>, subject: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            subject: subject.into(),
+            client: OnceCell::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Sink for NatsSink {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn publish(&self, alert: &Alert) -> Result<()> {
+        // Once connected, the client reconnects on its own
+        let client = self
+            .client
+            .get_or_try_init(|| async_nats::connect(self.url.as_str()))
+            .await
+            .map_err(|e| Error::ConnectionError(format!("NATS server unreachable: {}", e)))?;
+
+        client
+            .publish(self.subject.clone(), encode_alert(alert).into())
+            .await
+            .map_err(|e| Error::AlertError(format!("NATS publish failed: {}", e)))
+    }
+}
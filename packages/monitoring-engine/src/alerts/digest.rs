@@ -0,0 +1,85 @@
+//! Periodic alert digests
+//!
+//! In digest mode the alert manager batches non-critical alerts and sends a
+//! single summary per interval instead of one webhook per alert.
+
+use super::AlertCounts;
+use crate::types::{Alert, AlertSeverity};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Number of callers listed in a digest
+const TOP_CALLERS: usize = 5;
+
+/// Summary of the alerts raised during one digest interval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertDigest {
+    /// Unix timestamp of the first alert in the digest
+    pub period_start: u64,
+    /// Unix timestamp when the digest was built
+    pub period_end: u64,
+    /// Total number of alerts
+    pub total: usize,
+    pub by_severity: AlertCounts,
+    /// Alert count per attack pattern
+    pub by_pattern: BTreeMap<String, usize>,
+    /// Callers with the most alerts, most frequent first
+    pub top_callers: Vec<CallerCount>,
+    /// IDs of the batched alerts, for lookup via `/api/alerts`
+    pub alert_ids: Vec<String>,
+    /// Transaction hashes referenced by the batched alerts
+    pub transactions: Vec<String>,
+}
+
+/// Alert count for a single caller
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CallerCount {
+    pub caller: String,
+    pub alerts: usize,
+}
+
+impl AlertDigest {
+    /// Aggregate a batch of alerts
+    pub fn from_alerts(alerts: &[Alert], period_end: u64) -> Self {
+        let mut by_severity = AlertCounts::default();
+        let mut by_pattern = BTreeMap::new();
+        let mut callers: HashMap<&str, usize> = HashMap::new();
+
+        for alert in alerts {
+            match alert.severity {
+                AlertSeverity::Critical => by_severity.critical += 1,
+                AlertSeverity::High => by_severity.high += 1,
+                AlertSeverity::Medium => by_severity.medium += 1,
+                AlertSeverity::Low => by_severity.low += 1,
+            }
+            *by_pattern.entry(alert.pattern.to_string()).or_insert(0) += 1;
+            if let Some(caller) = alert.metadata.get("caller") {
+                *callers.entry(caller.as_str()).or_insert(0) += 1;
+            }
+        }
+
+        let mut top_callers: Vec<CallerCount> = callers
+            .into_iter()
+            .map(|(caller, alerts)| CallerCount {
+                caller: caller.to_string(),
+                alerts,
+            })
+            .collect();
+        top_callers.sort_by(|a, b| b.alerts.cmp(&a.alerts).then_with(|| a.caller.cmp(&b.caller)));
+        top_callers.truncate(TOP_CALLERS);
+
+        Self {
+            period_start: alerts.iter().map(|a| a.timestamp).min().unwrap_or(period_end),
+            period_end,
+            total: alerts.len(),
+            by_severity,
+            by_pattern,
+            top_callers,
+            alert_ids: alerts.iter().map(|a| a.id.clone()).collect(),
+            transactions: alerts
+                .iter()
+                .filter_map(|a| a.transaction_hash.clone())
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Export of the in-memory alert history
+//!
+//! Without a database the alert history only lives in memory; exports let
+//! operators dump it for post-incident analysis. Metadata keys are sorted
+//! so exports of the same history are byte-for-byte identical.
+
+use crate::types::{Alert, AlertSeverity, AttackPattern};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Output format of an alert export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlertExportFormat {
+    #[default]
+    Json,
+    Csv,
+    /// SARIF 2.1.0, one rule per attack pattern (see [`super::sarif`])
+    Sarif,
+}
+
+impl std::str::FromStr for AlertExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(AlertExportFormat::Json),
+            "csv" => Ok(AlertExportFormat::Csv),
+            "sarif" => Ok(AlertExportFormat::Sarif),
+            other => Err(format!("unknown export format '{}'", other)),
+        }
+    }
+}
+
+impl AlertExportFormat {
+    /// MIME type of the exported document
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            AlertExportFormat::Json => "application/json",
+            AlertExportFormat::Csv => "text/csv",
+            AlertExportFormat::Sarif => "application/sarif+json",
+        }
+    }
+}
+
+/// An alert with its metadata in sorted key order
+#[derive(Serialize)]
+pub(super) struct ExportedAlert<'a> {
+    schema_version: u32,
+    id: &'a str,
+    timestamp: u64,
+    chain: &'a str,
+    severity: AlertSeverity,
+    pattern: &'a AttackPattern,
+    description: &'a str,
+    transaction_hash: Option<&'a str>,
+    block_number: Option<u64>,
+    metadata: BTreeMap<&'a str, &'a str>,
+    recommended_actions: &'a [String],
+    acknowledged: bool,
+}
+
+impl<'a> From<&'a Alert> for ExportedAlert<'a> {
+    fn from(alert: &'a Alert) -> Self {
+        Self {
+            schema_version: alert.schema_version,
+            id: &alert.id,
+            timestamp: alert.timestamp,
+            chain: &alert.chain,
+            severity: alert.severity,
+            pattern: &alert.pattern,
+            description: &alert.description,
+            transaction_hash: alert.transaction_hash.as_deref(),
+            block_number: alert.block_number,
+            metadata: alert
+                .metadata
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect(),
+            recommended_actions: &alert.recommended_actions,
+            acknowledged: alert.acknowledged,
+        }
+    }
+}
+
+/// Serialize alerts, oldest first
+pub fn export_alerts(alerts: &[Alert], format: AlertExportFormat) -> String {
+    match format {
+        AlertExportFormat::Json => {
+            let exported: Vec<ExportedAlert> = alerts.iter().map(ExportedAlert::from).collect();
+            serde_json::to_string_pretty(&exported).unwrap_or_else(|_| "[]".to_string())
+        }
+        AlertExportFormat::Csv => export_csv(alerts),
+        AlertExportFormat::Sarif => super::sarif::export_sarif(alerts),
+    }
+}
+
+const CSV_HEADER: &str = "id,timestamp,chain,severity,pattern,description,transaction_hash,block_number,acknowledged,metadata,recommended_actions";
+
+fn export_csv(alerts: &[Alert]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+
+    for alert in alerts {
+        let exported = ExportedAlert::from(alert);
+        let metadata = serde_json::to_string(&exported.metadata).unwrap_or_default();
+        let fields = [
+            csv_field(&alert.id),
+            alert.timestamp.to_string(),
+            csv_field(&alert.chain),
+            alert.severity.to_string().to_lowercase(),
+            csv_field(&alert.pattern.to_string()),
+            csv_field(&alert.description),
+            csv_field(alert.transaction_hash.as_deref().unwrap_or("")),
+            alert.block_number.map(|n| n.to_string()).unwrap_or_default(),
+            alert.acknowledged.to_string(),
+            csv_field(&metadata),
+            csv_field(&alert.recommended_actions.join("; ")),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Quote a CSV field when it contains separators, quotes or newlines
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SCHEMA_VERSION;
+    use std::collections::HashMap;
+
+    fn alert(id: &str, metadata: &[(&str, &str)]) -> Alert {
+        Alert {
+            schema_version: SCHEMA_VERSION,
+            id: id.to_string(),
+            timestamp: 1_700_000_000,
+            chain: "westend".to_string(),
+            severity: AlertSeverity::High,
+            pattern: AttackPattern::FlashLoan,
+            description: "Borrow, swap, repay".to_string(),
+            transaction_hash: Some("0xabc".to_string()),
+            block_number: Some(42),
+            metadata: metadata
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>(),
+            recommended_actions: vec!["Review transaction".to_string()],
+            acknowledged: false,
+        }
+    }
+
+    #[test]
+    fn test_json_metadata_keys_sorted() {
+        let alerts = vec![alert("a1", &[("zeta", "1"), ("alpha", "2"), ("mid", "3")])];
+        let json = export_alerts(&alerts, AlertExportFormat::Json);
+
+        let alpha = json.find("\"alpha\"").unwrap();
+        let mid = json.find("\"mid\"").unwrap();
+        let zeta = json.find("\"zeta\"").unwrap();
+        assert!(alpha < mid && mid < zeta);
+    }
+
+    #[test]
+    fn test_csv_quotes_fields() {
+        let mut quoted = alert("a2", &[("detector", "Flash Loan Detector")]);
+        quoted.description = "Borrowed, then \"repaid\"".to_string();
+
+        let csv = export_alerts(&[quoted], AlertExportFormat::Csv);
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.starts_with("a2,1700000000,westend,high,Flash Loan,"));
+        assert!(row.contains("\"Borrowed, then \"\"repaid\"\"\""));
+        assert!(row.contains("\"{\"\"detector\"\":\"\"Flash Loan Detector\"\"}\""));
+    }
+
+    #[test]
+    fn test_format_parsing() {
+        assert_eq!("CSV".parse::<AlertExportFormat>(), Ok(AlertExportFormat::Csv));
+        assert_eq!("json".parse::<AlertExportFormat>(), Ok(AlertExportFormat::Json));
+        assert_eq!("sarif".parse::<AlertExportFormat>(), Ok(AlertExportFormat::Sarif));
+        assert!("xml".parse::<AlertExportFormat>().is_err());
+    }
+}
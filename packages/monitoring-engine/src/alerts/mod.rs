@@ -1,9 +1,43 @@
 //! Alert management system
 
+pub mod bus;
+pub mod digest;
+pub mod export;
+pub mod sarif;
+pub mod sink;
+
+pub use bus::{KafkaSink, NatsSink};
+pub use digest::AlertDigest;
+pub use export::AlertExportFormat;
+pub use sink::{Sink, SinkStats};
+
+use crate::database::{models::AlertRecord, DatabaseClient};
 use crate::types::{Alert, AlertSeverity};
-use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
+use sink::SinkQueue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, RwLock, Semaphore};
+
+/// Digests buffered for subscribers that fall behind
+const DIGEST_CHANNEL_CAPACITY: usize = 16;
+
+/// Alerts buffered for subscribers that fall behind
+pub const ALERT_CHANNEL_CAPACITY: usize = 256;
+
+/// Default limit on webhook requests in flight at once
+pub const DEFAULT_WEBHOOK_CONCURRENCY: usize = 8;
+
+/// Alert metadata key counting hits suppressed by the caller cooldown
+pub const SUPPRESSED_COUNT_KEY: &str = "suppressed_count";
+
+/// Alert sent for a (caller, pattern) whose repeats are being suppressed
+struct CallerCooldown {
+    started: Instant,
+    alert_id: String,
+    suppressed: u64,
+}
 
 /// Alert manager handles alert creation, storage, and notifications
 pub struct AlertManager {
@@ -11,6 +45,22 @@ pub struct AlertManager {
     webhook_url: Option<String>,
     alert_history: Arc<RwLock<Vec<Alert>>>,
     max_webhook_retries: u32,
+    /// Bounds concurrent webhook requests; excess deliveries wait their turn
+    webhook_permits: Semaphore,
+    /// Database for persisting alert history (optional)
+    database: Option<Arc<DatabaseClient>>,
+    /// Digest interval; when set, non-critical webhooks are batched
+    digest_interval: Option<Duration>,
+    /// Alerts accumulated since the last digest
+    pending_digest: Arc<RwLock<Vec<Alert>>>,
+    digest_tx: broadcast::Sender<AlertDigest>,
+    alert_tx: broadcast::Sender<Alert>,
+    /// How long repeat alerts for the same caller and pattern are suppressed
+    cooldown: Option<Duration>,
+    /// Active cooldowns keyed by (caller, pattern)
+    cooldowns: Arc<RwLock<HashMap<(String, String), CallerCooldown>>>,
+    /// Sinks every triggered alert is published to, besides the webhook
+    sinks: Vec<SinkQueue>,
 }
 
 impl AlertManager {
@@ -21,6 +71,15 @@ impl AlertManager {
             webhook_url,
             alert_history: Arc::new(RwLock::new(Vec::new())),
             max_webhook_retries: 3,
+            webhook_permits: Semaphore::new(DEFAULT_WEBHOOK_CONCURRENCY),
+            database: None,
+            digest_interval: None,
+            pending_digest: Arc::new(RwLock::new(Vec::new())),
+            digest_tx: broadcast::channel(DIGEST_CHANNEL_CAPACITY).0,
+            alert_tx: broadcast::channel(ALERT_CHANNEL_CAPACITY).0,
+            cooldown: None,
+            cooldowns: Arc::new(RwLock::new(HashMap::new())),
+            sinks: Vec::new(),
         }
     }
 
@@ -35,9 +94,284 @@ impl AlertManager {
             webhook_url,
             alert_history: Arc::new(RwLock::new(Vec::new())),
             max_webhook_retries: max_retries,
+            webhook_permits: Semaphore::new(DEFAULT_WEBHOOK_CONCURRENCY),
+            database: None,
+            digest_interval: None,
+            pending_digest: Arc::new(RwLock::new(Vec::new())),
+            digest_tx: broadcast::channel(DIGEST_CHANNEL_CAPACITY).0,
+            alert_tx: broadcast::channel(ALERT_CHANNEL_CAPACITY).0,
+            cooldown: None,
+            cooldowns: Arc::new(RwLock::new(HashMap::new())),
+            sinks: Vec::new(),
+        }
+    }
+
+    /// Allow at most `limit` webhook requests in flight at once
+    ///
+    /// During an alert storm further deliveries queue for a permit, which
+    /// slows `trigger_alert` callers instead of flooding the receiver.
+    /// Permits are released while waiting to retry.
+    pub fn with_webhook_concurrency(mut self, limit: usize) -> Self {
+        self.webhook_permits = Semaphore::new(limit.max(1));
+        self
+    }
+
+    /// Persist alerts to the database in addition to the in-memory history
+    pub fn with_database(mut self, database: Arc<DatabaseClient>) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Batch non-critical alerts into one digest per interval
+    ///
+    /// Critical alerts still fire their webhook immediately. Call
+    /// `start_digest` to run the background task that sends digests.
+    /// Intervals shorter than one second are raised to one second.
+    pub fn with_digest(mut self, interval: Duration) -> Self {
+        self.digest_interval = Some(interval.max(Duration::from_secs(1)));
+        self
+    }
+
+    /// Suppress repeat alerts for the same caller and pattern for `cooldown`
+    ///
+    /// Only the first alert in each window is stored and notified; later hits
+    /// increment its `suppressed_count` metadata, which is written through to
+    /// the database. Once the window has closed, the next alert that checks
+    /// the cooldowns republishes the original with the final count to
+    /// subscribers, sinks and the webhook. Alerts without a `caller` in their
+    /// metadata and privileged patterns (see `AttackPattern::is_privileged`)
+    /// are never suppressed.
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
+    /// Also publish every triggered alert to `sink`
+    ///
+    /// Alerts are buffered in a queue of `capacity` until `start_sinks` runs
+    /// its worker; when the queue is full they are dropped and counted (see
+    /// `sink_stats`). Digest mode does not apply to sinks.
+    pub fn with_sink(mut self, sink: Arc<dyn Sink>, capacity: usize) -> Self {
+        self.sinks.push(SinkQueue::new(sink, capacity));
+        self
+    }
+
+    /// Spawn the workers publishing to the configured sinks
+    ///
+    /// Returns how many were started; sinks already running are skipped.
+    pub fn start_sinks(&self) -> usize {
+        self.sinks.iter().filter_map(SinkQueue::start).count()
+    }
+
+    /// Delivery counters of every configured sink
+    pub fn sink_stats(&self) -> Vec<SinkStats> {
+        self.sinks.iter().map(SinkQueue::stats).collect()
+    }
+
+    /// Receive every alert triggered from now on
+    ///
+    /// Alerts are delivered after the severity filter and cooldown, the same
+    /// ones stored in the history; an alert whose cooldown suppressed hits is
+    /// delivered again with its final `suppressed_count`. Each receiver buffers up to
+    /// `ALERT_CHANNEL_CAPACITY` alerts; a receiver that falls further behind
+    /// loses the oldest ones and its next `recv` returns
+    /// `RecvError::Lagged` with the number skipped, after which it resumes
+    /// with the oldest alert still buffered. Slow receivers never block
+    /// `trigger_alert`.
+    pub fn subscribe(&self) -> broadcast::Receiver<Alert> {
+        self.alert_tx.subscribe()
+    }
+
+    /// Receive every digest sent by this manager
+    pub fn subscribe_digests(&self) -> broadcast::Receiver<AlertDigest> {
+        self.digest_tx.subscribe()
+    }
+
+    /// Spawn the background task that sends digests on the configured interval
+    ///
+    /// Returns `None` when digest mode is disabled. The task ends once the
+    /// manager is dropped.
+    pub fn start_digest(self: &Arc<Self>) -> Option<tokio::task::JoinHandle<()>> {
+        let interval = self.digest_interval?;
+        let manager: Weak<Self> = Arc::downgrade(self);
+
+        Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // The first tick completes immediately
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.flush_digest().await;
+            }
+        }))
+    }
+
+    /// Send a digest of the alerts accumulated since the last one
+    ///
+    /// Returns `None` without notifying anyone when no alerts are pending.
+    pub async fn flush_digest(&self) -> Option<AlertDigest> {
+        let alerts = std::mem::take(&mut *self.pending_digest.write().await);
+        if alerts.is_empty() {
+            return None;
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let digest = AlertDigest::from_alerts(&alerts, now);
+
+        tracing::info!("Sending alert digest with {} alerts", digest.total);
+        // No subscribers is not an error
+        let _ = self.digest_tx.send(digest.clone());
+
+        if let Some(webhook_url) = &self.webhook_url {
+            self.send_webhook(webhook_url, &digest).await;
+        }
+
+        Some(digest)
+    }
+
+    /// Load the most recent persisted alerts into memory
+    ///
+    /// Alerts already present in memory are skipped. Returns the number of
+    /// alerts loaded; without a database this is a no-op.
+    pub async fn load_from_database(&self, limit: usize) -> usize {
+        let Some(db) = &self.database else {
+            return 0;
+        };
+
+        let records = match db.get_alerts(limit as i64).await {
+            Ok(records) => records,
+            Err(e) => {
+                tracing::warn!("Failed to load alert history from database: {}", e);
+                return 0;
+            }
+        };
+
+        let mut history = self.alert_history.write().await;
+        let mut loaded = 0;
+
+        // Records come newest first; history is kept oldest first
+        for record in records.iter().rev() {
+            if history.iter().any(|a| a.id == record.alert_id) {
+                continue;
+            }
+            match record.to_alert() {
+                Ok(alert) => {
+                    history.push(alert);
+                    loaded += 1;
+                }
+                Err(e) => tracing::warn!("Skipping unreadable alert {}: {}", record.alert_id, e),
+            }
+        }
+
+        history.sort_by_key(|a| a.timestamp);
+        tracing::info!("Loaded {} alerts from database", loaded);
+        loaded
+    }
+
+    /// Write an alert to the database if one is configured
+    async fn persist_alert(&self, alert: &Alert) {
+        let Some(db) = &self.database else {
+            return;
+        };
+
+        let result = match AlertRecord::from_alert(alert) {
+            Ok(record) => db.insert_alert(&record).await,
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to store alert {} in database: {}", alert.id, e);
         }
     }
 
+    /// Whether the alert falls in an active cooldown for its caller and pattern
+    ///
+    /// A suppressed hit is counted on the alert that started the cooldown;
+    /// otherwise a new cooldown starts with this alert, and the alerts of
+    /// windows that closed with suppressed hits are republished.
+    async fn suppressed_by_cooldown(&self, alert: &Alert) -> bool {
+        let Some(cooldown) = self.cooldown else {
+            return false;
+        };
+        if alert.pattern.is_privileged() {
+            return false;
+        }
+        let Some(caller) = alert.metadata.get("caller") else {
+            return false;
+        };
+
+        let key = (caller.clone(), alert.pattern.to_string());
+        let mut cooldowns = self.cooldowns.write().await;
+
+        if let Some(active) = cooldowns.get_mut(&key) {
+            if active.started.elapsed() < cooldown {
+                active.suppressed += 1;
+                let (alert_id, suppressed) = (active.alert_id.clone(), active.suppressed);
+                drop(cooldowns);
+
+                tracing::debug!(
+                    "Suppressed {} alert for {} during cooldown ({} so far)",
+                    alert.pattern,
+                    caller,
+                    suppressed
+                );
+                if let Some(original) = self.record_suppressed(&alert_id, suppressed).await {
+                    self.persist_alert(&original).await;
+                }
+                return true;
+            }
+        }
+
+        let mut closed = Vec::new();
+        cooldowns.retain(|_, active| {
+            let open = active.started.elapsed() < cooldown;
+            if !open && active.suppressed > 0 {
+                closed.push(active.alert_id.clone());
+            }
+            open
+        });
+        cooldowns.insert(
+            key,
+            CallerCooldown {
+                started: Instant::now(),
+                alert_id: alert.id.clone(),
+                suppressed: 0,
+            },
+        );
+        drop(cooldowns);
+
+        for alert_id in closed {
+            if let Some(original) = self.get_alert(&alert_id).await {
+                tracing::info!(
+                    "Cooldown for alert {} closed with {} suppressed hits",
+                    alert_id,
+                    original.metadata.get(SUPPRESSED_COUNT_KEY).map(String::as_str).unwrap_or("0")
+                );
+                self.notify(original).await;
+            }
+        }
+        false
+    }
+
+    /// Store the suppressed hit count on the alert that started a cooldown
+    ///
+    /// Returns the updated alert, or `None` when it is no longer in the history.
+    async fn record_suppressed(&self, alert_id: &str, suppressed: u64) -> Option<Alert> {
+        let mut history = self.alert_history.write().await;
+        let original = history.iter_mut().rev().find(|a| a.id == alert_id)?;
+        original
+            .metadata
+            .insert(SUPPRESSED_COUNT_KEY.to_string(), suppressed.to_string());
+        Some(original.clone())
+    }
+
     /// Trigger a new alert
     pub async fn trigger_alert(&self, alert: Alert) {
         if alert.severity < self.min_severity {
@@ -49,6 +383,10 @@ impl AlertManager {
             return;
         }
 
+        if self.suppressed_by_cooldown(&alert).await {
+            return;
+        }
+
         tracing::warn!(
             "ALERT: [{}] {} - {}",
             alert.severity,
@@ -59,6 +397,26 @@ impl AlertManager {
         // Store alert in history
         let mut history = self.alert_history.write().await;
         history.push(alert.clone());
+        drop(history);
+
+        self.persist_alert(&alert).await;
+        self.notify(alert).await;
+    }
+
+    /// Publish an alert to subscribers and sinks and send its webhook
+    async fn notify(&self, alert: Alert) {
+        // No subscribers is not an error
+        let _ = self.alert_tx.send(alert.clone());
+
+        for sink in &self.sinks {
+            sink.enqueue(&alert);
+        }
+
+        // In digest mode only critical alerts notify immediately
+        if self.digest_interval.is_some() && alert.severity != AlertSeverity::Critical {
+            self.pending_digest.write().await.push(alert);
+            return;
+        }
 
         // Send webhook notification if configured
         if let Some(webhook_url) = &self.webhook_url {
@@ -72,6 +430,18 @@ impl AlertManager {
         history.iter().rev().take(limit).cloned().collect()
     }
 
+    /// Get an alert from the in-memory history by ID
+    pub async fn get_alert(&self, alert_id: &str) -> Option<Alert> {
+        let history = self.alert_history.read().await;
+        history.iter().find(|a| a.id == alert_id).cloned()
+    }
+
+    /// Serialize the in-memory alert history, oldest first
+    pub async fn export_history(&self, format: AlertExportFormat) -> String {
+        let history = self.alert_history.read().await;
+        export::export_alerts(&history, format)
+    }
+
     /// Get alert count by severity
     pub async fn get_alert_counts(&self) -> AlertCounts {
         let history = self.alert_history.read().await;
@@ -89,17 +459,23 @@ impl AlertManager {
         counts
     }
 
-    /// Send alert to webhook with retry logic
+    /// Send an alert or digest to the webhook with retry logic
     ///
     /// Uses exponential backoff: 1s, 2s, 4s delays between retries
-    async fn send_webhook(&self, url: &str, alert: &Alert) {
+    async fn send_webhook<T: Serialize + ?Sized>(&self, url: &str, payload: &T) {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(10))
             .build()
             .expect("Failed to create HTTP client");
 
         for attempt in 0..=self.max_webhook_retries {
-            match client.post(url).json(alert).send().await {
+            let result = {
+                // The semaphore is never closed
+                let _permit = self.webhook_permits.acquire().await.ok();
+                client.post(url).json(payload).send().await
+            };
+
+            match result {
                 Ok(response) => {
                     if response.status().is_success() {
                         tracing::info!(
@@ -139,11 +515,31 @@ impl AlertManager {
     }
 
     /// Acknowledge an alert by ID
+    ///
+    /// The acknowledgment is written through to the database when configured.
     pub async fn acknowledge_alert(&self, alert_id: &str) -> bool {
         let mut history = self.alert_history.write().await;
+        let found_in_memory = match history.iter_mut().find(|a| a.id == alert_id) {
+            Some(alert) => {
+                alert.acknowledged = true;
+                true
+            }
+            None => false,
+        };
+        drop(history);
 
-        if let Some(alert) = history.iter_mut().find(|a| a.id == alert_id) {
-            alert.acknowledged = true;
+        let found_in_db = match &self.database {
+            Some(db) => match db.acknowledge_alert(alert_id).await {
+                Ok(updated) => updated,
+                Err(e) => {
+                    tracing::warn!("Failed to persist acknowledgment for alert {}: {}", alert_id, e);
+                    false
+                }
+            },
+            None => false,
+        };
+
+        if found_in_memory || found_in_db {
             tracing::info!("Alert {} acknowledged", alert_id);
             true
         } else {
@@ -171,7 +567,7 @@ impl AlertManager {
 }
 
 /// Alert count statistics
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct AlertCounts {
     pub critical: usize,
     pub high: usize,
@@ -188,8 +584,7 @@ impl AlertCounts {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::AttackPattern;
-    use std::collections::HashMap;
+    use crate::types::{AttackPattern, SCHEMA_VERSION};
 
     #[tokio::test]
     async fn test_alert_manager_creation() {
@@ -203,6 +598,7 @@ mod tests {
         let manager = AlertManager::new(AlertSeverity::Low, None);
 
         let alert = Alert {
+            schema_version: SCHEMA_VERSION,
             id: "test-1".to_string(),
             timestamp: 1234567890,
             chain: "test-chain".to_string(),
@@ -223,11 +619,40 @@ mod tests {
         assert_eq!(recent[0].id, "test-1");
     }
 
+    #[tokio::test]
+    async fn test_subscribers_receive_alerts() {
+        let manager = AlertManager::new(AlertSeverity::Medium, None);
+        let mut alerts = manager.subscribe();
+
+        let alert = |id: &str, severity| Alert {
+            schema_version: SCHEMA_VERSION,
+            id: id.to_string(),
+            timestamp: 1234567890,
+            chain: "test-chain".to_string(),
+            severity,
+            pattern: AttackPattern::FlashLoan,
+            description: "Test alert".to_string(),
+            transaction_hash: None,
+            block_number: Some(100),
+            metadata: HashMap::new(),
+            recommended_actions: vec![],
+            acknowledged: false,
+        };
+
+        manager.trigger_alert(alert("filtered", AlertSeverity::Low)).await;
+        manager.trigger_alert(alert("sent", AlertSeverity::High)).await;
+
+        let received = alerts.recv().await.unwrap();
+        assert_eq!(received.id, "sent");
+        assert!(alerts.try_recv().is_err());
+    }
+
     #[tokio::test]
     async fn test_alert_severity_filtering() {
         let manager = AlertManager::new(AlertSeverity::High, None);
 
         let low_alert = Alert {
+            schema_version: SCHEMA_VERSION,
             id: "low-1".to_string(),
             timestamp: 1234567890,
             chain: "test-chain".to_string(),
@@ -252,6 +677,7 @@ mod tests {
         let manager = AlertManager::new(AlertSeverity::Low, None);
 
         let alert = Alert {
+            schema_version: SCHEMA_VERSION,
             id: "test-1".to_string(),
             timestamp: 1234567890,
             chain: "test-chain".to_string(),
@@ -271,4 +697,300 @@ mod tests {
         manager.clear_history().await;
         assert_eq!(manager.get_alert_counts().await.total(), 0);
     }
+
+    fn alert_for(id: &str, severity: AlertSeverity, pattern: AttackPattern, caller: &str) -> Alert {
+        Alert {
+            schema_version: SCHEMA_VERSION,
+            id: id.to_string(),
+            timestamp: 1234567890,
+            chain: "test-chain".to_string(),
+            severity,
+            pattern,
+            description: "Test alert".to_string(),
+            transaction_hash: Some(format!("0x{}", id)),
+            block_number: Some(100),
+            metadata: HashMap::from([("caller".to_string(), caller.to_string())]),
+            recommended_actions: vec![],
+            acknowledged: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_digest_batches_alerts() {
+        let manager = Arc::new(
+            AlertManager::new(AlertSeverity::Low, None).with_digest(Duration::from_secs(1)),
+        );
+        let mut digests = manager.subscribe_digests();
+        manager.start_digest().expect("digest mode is enabled");
+
+        manager.trigger_alert(alert_for("a1", AlertSeverity::High, AttackPattern::FlashLoan, "alice")).await;
+        manager.trigger_alert(alert_for("a2", AlertSeverity::Medium, AttackPattern::FlashLoan, "alice")).await;
+        manager.trigger_alert(alert_for("a3", AlertSeverity::Medium, AttackPattern::Mev, "bob")).await;
+
+        let digest = tokio::time::timeout(Duration::from_secs(3), digests.recv())
+            .await
+            .expect("digest within the interval")
+            .unwrap();
+
+        assert_eq!(digest.total, 3);
+        assert_eq!(digest.by_severity.high, 1);
+        assert_eq!(digest.by_severity.medium, 2);
+        assert_eq!(digest.by_pattern.values().sum::<usize>(), 3);
+        assert_eq!(digest.by_pattern.len(), 2);
+        assert_eq!(digest.top_callers[0].caller, "alice");
+        assert_eq!(digest.top_callers[0].alerts, 2);
+
+        // Alerts are batched once; an empty interval sends nothing
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(digests.try_recv().is_err());
+
+        // Alert history is unaffected by digest mode
+        assert_eq!(manager.get_alert_counts().await.total(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_zero_digest_interval_is_raised() {
+        let manager = Arc::new(AlertManager::new(AlertSeverity::Low, None).with_digest(Duration::ZERO));
+        assert_eq!(manager.digest_interval, Some(Duration::from_secs(1)));
+
+        // tokio::time::interval panics on a zero period
+        let handle = manager.start_digest().expect("digest mode is enabled");
+        tokio::task::yield_now().await;
+        assert!(!handle.is_finished());
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_critical_alert_bypasses_digest() {
+        let manager = AlertManager::new(AlertSeverity::Low, None).with_digest(Duration::from_secs(60));
+
+        manager.trigger_alert(alert_for("c1", AlertSeverity::Critical, AttackPattern::FlashLoan, "eve")).await;
+        manager.trigger_alert(alert_for("m1", AlertSeverity::Medium, AttackPattern::Mev, "eve")).await;
+
+        let digest = manager.flush_digest().await.unwrap();
+        assert_eq!(digest.total, 1);
+        assert_eq!(digest.alert_ids, vec!["m1".to_string()]);
+        assert!(manager.flush_digest().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_suppresses_repeat_alerts() {
+        let manager = AlertManager::new(AlertSeverity::Low, None).with_cooldown(Duration::from_secs(60));
+
+        for i in 0..4 {
+            manager
+                .trigger_alert(alert_for(&format!("fl{}", i), AlertSeverity::High, AttackPattern::FlashLoan, "mallory"))
+                .await;
+        }
+        // Another pattern or caller is not in the cooldown
+        manager.trigger_alert(alert_for("mev", AlertSeverity::High, AttackPattern::Mev, "mallory")).await;
+        manager.trigger_alert(alert_for("fl-bob", AlertSeverity::High, AttackPattern::FlashLoan, "bob")).await;
+
+        let alerts = manager.get_recent_alerts(10).await;
+        assert_eq!(alerts.len(), 3);
+
+        let first = alerts.iter().find(|a| a.id == "fl0").unwrap();
+        assert_eq!(first.metadata.get(SUPPRESSED_COUNT_KEY).map(String::as_str), Some("3"));
+        assert!(alerts.iter().all(|a| a.id == "fl0" || !a.metadata.contains_key(SUPPRESSED_COUNT_KEY)));
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_expires() {
+        let manager = AlertManager::new(AlertSeverity::Low, None).with_cooldown(Duration::from_millis(50));
+
+        manager.trigger_alert(alert_for("x1", AlertSeverity::High, AttackPattern::FlashLoan, "mallory")).await;
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        manager.trigger_alert(alert_for("x2", AlertSeverity::High, AttackPattern::FlashLoan, "mallory")).await;
+
+        assert_eq!(manager.get_alert_counts().await.total(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_republishes_suppressed_count() {
+        let manager = AlertManager::new(AlertSeverity::Low, None).with_cooldown(Duration::from_millis(50));
+        let mut alerts = manager.subscribe();
+
+        for id in ["r1", "r2", "r3"] {
+            manager.trigger_alert(alert_for(id, AlertSeverity::High, AttackPattern::FlashLoan, "mallory")).await;
+        }
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        manager.trigger_alert(alert_for("r4", AlertSeverity::High, AttackPattern::FlashLoan, "mallory")).await;
+
+        let received: Vec<Alert> = std::iter::from_fn(|| alerts.try_recv().ok()).collect();
+        let ids: Vec<&str> = received.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["r1", "r1", "r4"]);
+        assert!(!received[0].metadata.contains_key(SUPPRESSED_COUNT_KEY));
+        assert_eq!(received[1].metadata.get(SUPPRESSED_COUNT_KEY).map(String::as_str), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_never_suppresses_privileged_alerts() {
+        let manager = AlertManager::new(AlertSeverity::Low, None).with_cooldown(Duration::from_secs(60));
+
+        for i in 0..3 {
+            manager
+                .trigger_alert(alert_for(&format!("ru{}", i), AlertSeverity::Critical, AttackPattern::RuntimeUpgrade, "root"))
+                .await;
+        }
+
+        let alerts = manager.get_recent_alerts(10).await;
+        assert_eq!(alerts.len(), 3);
+        assert!(alerts.iter().all(|a| !a.metadata.contains_key(SUPPRESSED_COUNT_KEY)));
+    }
+
+    #[tokio::test]
+    async fn test_export_history_formats() {
+        let manager = AlertManager::new(AlertSeverity::Low, None);
+
+        for (id, pattern) in [("exp-1", AttackPattern::FlashLoan), ("exp-2", AttackPattern::Mev)] {
+            manager
+                .trigger_alert(Alert {
+                    schema_version: SCHEMA_VERSION,
+                    id: id.to_string(),
+                    timestamp: 1234567890,
+                    chain: "test-chain".to_string(),
+                    severity: AlertSeverity::High,
+                    pattern,
+                    description: "Exported alert".to_string(),
+                    transaction_hash: Some("0x1234".to_string()),
+                    block_number: Some(100),
+                    metadata: HashMap::from([("detector".to_string(), "Test Detector".to_string())]),
+                    recommended_actions: vec![],
+                    acknowledged: false,
+                })
+                .await;
+        }
+
+        let json = manager.export_history(AlertExportFormat::Json).await;
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0]["id"], "exp-1");
+        assert_eq!(parsed[1]["pattern"], "mev");
+        assert_eq!(parsed[0]["metadata"]["detector"], "Test Detector");
+
+        let csv = manager.export_history(AlertExportFormat::Csv).await;
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("id,timestamp,chain,severity"));
+        assert!(lines[1].starts_with("exp-1,"));
+        assert!(lines[2].starts_with("exp-2,"));
+    }
+
+    /// Records every alert it is asked to publish
+    struct RecordingSink {
+        published: tokio::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Sink for RecordingSink {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn publish(&self, alert: &Alert) -> crate::Result<()> {
+            self.published.lock().await.push(alert.id.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sink_receives_each_triggered_alert() {
+        let sink = Arc::new(RecordingSink { published: Default::default() });
+        let manager = AlertManager::new(AlertSeverity::Medium, None)
+            .with_digest(Duration::from_secs(60))
+            .with_sink(sink.clone(), 10);
+        assert_eq!(manager.start_sinks(), 1);
+        assert_eq!(manager.start_sinks(), 0);
+
+        manager.trigger_alert(alert_for("s1", AlertSeverity::High, AttackPattern::FlashLoan, "alice")).await;
+        manager.trigger_alert(alert_for("s2", AlertSeverity::Low, AttackPattern::Mev, "bob")).await;
+        manager.trigger_alert(alert_for("s3", AlertSeverity::Critical, AttackPattern::Mev, "carol")).await;
+
+        // Published by the background worker; digest mode does not delay sinks
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while manager.sink_stats()[0].published < 2 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // The low severity alert is filtered before reaching any sink
+        assert_eq!(*sink.published.lock().await, vec!["s1".to_string(), "s3".to_string()]);
+        assert_eq!(
+            manager.sink_stats(),
+            vec![SinkStats { name: "recording".to_string(), published: 2, dropped: 0 }]
+        );
+    }
+
+    /// Serve webhooks slowly, tracking the most requests ever in flight
+    async fn slow_webhook_server(delay: Duration) -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/webhook", listener.local_addr().unwrap());
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let max = max_in_flight.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    break;
+                };
+                let (in_flight, max) = (in_flight.clone(), max.clone());
+                tokio::spawn(async move {
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max.fetch_max(current, Ordering::SeqCst);
+
+                    // Read the headers and the body they announce
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    loop {
+                        let Ok(n) = socket.read(&mut buf).await else { break };
+                        if n == 0 {
+                            break;
+                        }
+                        request.extend_from_slice(&buf[..n]);
+                        let text = String::from_utf8_lossy(&request).to_lowercase();
+                        if let Some(end) = text.find("\r\n\r\n") {
+                            let length = text
+                                .lines()
+                                .find_map(|l| l.strip_prefix("content-length:"))
+                                .and_then(|l| l.trim().parse::<usize>().ok())
+                                .unwrap_or(0);
+                            if request.len() >= end + 4 + length {
+                                break;
+                            }
+                        }
+                    }
+
+                    tokio::time::sleep(delay).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    let _ = socket
+                        .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                        .await;
+                });
+            }
+        });
+
+        (url, max_in_flight)
+    }
+
+    #[tokio::test]
+    async fn test_webhook_concurrency_is_bounded() {
+        let (url, max_in_flight) = slow_webhook_server(Duration::from_millis(20)).await;
+        let manager = AlertManager::new(AlertSeverity::Low, Some(url)).with_webhook_concurrency(4);
+
+        futures::future::join_all((0..100).map(|i| {
+            manager.trigger_alert(alert_for(&format!("w{}", i), AlertSeverity::High, AttackPattern::Mev, "alice"))
+        }))
+        .await;
+
+        let max = max_in_flight.load(std::sync::atomic::Ordering::SeqCst);
+        assert!(max <= 4, "{} webhook requests were in flight at once", max);
+        assert!(max > 1, "deliveries should still run concurrently");
+        assert_eq!(manager.get_alert_counts().await.total(), 100);
+    }
 }
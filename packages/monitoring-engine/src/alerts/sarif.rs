@@ -0,0 +1,144 @@
+//! Alerts as SAFT findings
+//!
+//! Runtime detections are converted to the static analyzer's
+//! `AnalysisResult`/`Vulnerability` shape, so SAFT's `Reporter` renders them
+//! (SARIF included) the same way as static findings. Each attack pattern is a
+//! rule. Alerts have no source location, so the chain stands in for the file
+//! and the block number for the line, and text reports read `chain:block`.
+
+use crate::types::{Alert, AttackPattern};
+use saft_enhanced::reporter::{ReportFormat, Reporter};
+use saft_enhanced::{
+    AnalysisMetadata, AnalysisResult, Location, Severity, SeverityCounts, Vulnerability,
+    VulnerabilityCategory,
+};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Rule ID prefix that sets runtime detections apart from `SAFT-*` static rules
+pub const RULE_PREFIX: &str = "NEXUS";
+
+/// Rule ID for an attack pattern, e.g. `NEXUS-FlashLoan`
+pub fn rule_id(pattern: &AttackPattern) -> String {
+    format!("{}-{:?}", RULE_PREFIX, pattern)
+}
+
+/// Finding for a single alert
+pub fn to_vulnerability(alert: &Alert) -> Vulnerability {
+    Vulnerability {
+        id: rule_id(&alert.pattern),
+        severity: Severity::from(alert.severity),
+        category: VulnerabilityCategory::RuntimeAttack,
+        message: alert.description.clone(),
+        description: format!("{} detected by runtime monitoring", alert.pattern),
+        location: Location {
+            file: PathBuf::from(&alert.chain),
+            line: alert.block_number.unwrap_or_default() as usize,
+            column: 0,
+            snippet: alert.transaction_hash.clone(),
+        },
+        remediation: (!alert.recommended_actions.is_empty())
+            .then(|| alert.recommended_actions.join("; ")),
+        references: alert.pattern.references(),
+    }
+}
+
+/// One analysis result per chain, alerts in their original order
+pub fn to_analysis_results(alerts: &[Alert]) -> Vec<AnalysisResult> {
+    let mut by_chain: BTreeMap<&str, Vec<Vulnerability>> = BTreeMap::new();
+    for alert in alerts {
+        by_chain
+            .entry(alert.chain.as_str())
+            .or_default()
+            .push(to_vulnerability(alert));
+    }
+
+    by_chain
+        .into_iter()
+        .map(|(chain, vulnerabilities)| {
+            let mut severity_counts = SeverityCounts::default();
+            for vuln in &vulnerabilities {
+                severity_counts.increment(vuln.severity);
+            }
+
+            AnalysisResult {
+                file: PathBuf::from(chain),
+                metadata: AnalysisMetadata {
+                    total_vulnerabilities: vulnerabilities.len(),
+                    risk_score: severity_counts.risk_score(),
+                    severity_counts,
+                    duration_ms: 0,
+                    analyzer_version: env!("CARGO_PKG_VERSION").to_string(),
+                    incomplete: None,
+                },
+                vulnerabilities,
+            }
+        })
+        .collect()
+}
+
+/// Render alerts as a SARIF 2.1.0 document
+pub fn export_sarif(alerts: &[Alert]) -> String {
+    let mut output = Vec::new();
+    if let Err(e) = Reporter::new(ReportFormat::Sarif).generate(&to_analysis_results(alerts), &mut output) {
+        tracing::error!("Failed to render SARIF export: {}", e);
+    }
+    String::from_utf8(output).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AlertSeverity, SCHEMA_VERSION};
+    use std::collections::HashMap;
+
+    fn alert(chain: &str, severity: AlertSeverity, block_number: u64) -> Alert {
+        Alert {
+            schema_version: SCHEMA_VERSION,
+            id: "alert-1".to_string(),
+            timestamp: 1_700_000_000,
+            chain: chain.to_string(),
+            severity,
+            pattern: AttackPattern::FlashLoan,
+            description: "Borrow, swap, repay in one transaction".to_string(),
+            transaction_hash: Some("0xabc".to_string()),
+            block_number: Some(block_number),
+            metadata: HashMap::new(),
+            recommended_actions: vec!["Review transaction".to_string()],
+            acknowledged: false,
+        }
+    }
+
+    #[test]
+    fn test_detection_exports_as_sarif_result() {
+        let sarif: serde_json::Value =
+            serde_json::from_str(&export_sarif(&[alert("westend", AlertSeverity::High, 42)])).unwrap();
+
+        let run = &sarif["runs"][0];
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], "NEXUS-FlashLoan");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[0]["message"]["text"], "Borrow, swap, repay in one transaction");
+
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "westend");
+        assert_eq!(location["region"]["startLine"], 42);
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "NEXUS-FlashLoan");
+    }
+
+    #[test]
+    fn test_analysis_results_grouped_by_chain() {
+        let results = to_analysis_results(&[
+            alert("westend", AlertSeverity::Low, 1),
+            alert("kusama", AlertSeverity::Critical, 2),
+            alert("westend", AlertSeverity::Medium, 3),
+        ]);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].file, PathBuf::from("kusama"));
+        assert_eq!(results[1].metadata.total_vulnerabilities, 2);
+        assert_eq!(results[1].metadata.severity_counts.medium, 1);
+        assert_eq!(results[1].vulnerabilities[1].location.line, 3);
+    }
+}
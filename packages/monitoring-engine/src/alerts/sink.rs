@@ -0,0 +1,227 @@
+//! Alert sinks beyond the webhook
+//!
+//! Every triggered alert is queued for each configured [`Sink`] (e.g. a
+//! message bus) and published by a background worker, so a slow or
+//! unavailable broker never blocks detection. Each sink has a bounded queue:
+//! when it is full, or an alert still fails after `MAX_PUBLISH_ATTEMPTS`,
+//! the alert is dropped and counted.
+
+use super::export::ExportedAlert;
+use crate::types::Alert;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Schema name carried by every published alert message
+pub const ALERT_SCHEMA: &str = "security-nexus.alert";
+
+/// Version of the published alert message; follows the alert's own schema version
+pub const ALERT_SCHEMA_VERSION: u32 = crate::types::SCHEMA_VERSION;
+
+/// Default number of alerts buffered per sink while its broker is unavailable
+pub const DEFAULT_QUEUE_CAPACITY: usize = 1000;
+
+/// Attempts to publish one alert before it is dropped
+const MAX_PUBLISH_ATTEMPTS: u32 = 4;
+
+/// Destination that triggered alerts are published to
+#[async_trait::async_trait]
+pub trait Sink: Send + Sync {
+    /// Short name used in logs and stats (e.g. `kafka`)
+    fn name(&self) -> &str;
+
+    /// Publish one alert; errors are retried by the queue
+    async fn publish(&self, alert: &Alert) -> Result<()>;
+}
+
+/// Published message: the alert wrapped with its schema and version
+#[derive(Serialize)]
+struct AlertMessage<'a> {
+    schema: &'static str,
+    version: u32,
+    alert: ExportedAlert<'a>,
+}
+
+/// Encode an alert as a versioned JSON message
+///
+/// Metadata keys are sorted, so the same alert always encodes to the same bytes.
+pub fn encode_alert(alert: &Alert) -> Vec<u8> {
+    serde_json::to_vec(&AlertMessage {
+        schema: ALERT_SCHEMA,
+        version: ALERT_SCHEMA_VERSION,
+        alert: ExportedAlert::from(alert),
+    })
+    .unwrap_or_default()
+}
+
+/// Delivery counters of one sink
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SinkStats {
+    pub name: String,
+    pub published: u64,
+    /// Alerts dropped because the queue was full or publishing kept failing
+    pub dropped: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    published: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// A sink with the bounded queue feeding it
+pub(crate) struct SinkQueue {
+    sink: Arc<dyn Sink>,
+    tx: mpsc::Sender<Alert>,
+    /// Taken by the worker once started
+    rx: Mutex<Option<mpsc::Receiver<Alert>>>,
+    counters: Arc<Counters>,
+}
+
+impl SinkQueue {
+    pub(crate) fn new(sink: Arc<dyn Sink>, capacity: usize) -> Self {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        Self {
+            sink,
+            tx,
+            rx: Mutex::new(Some(rx)),
+            counters: Arc::default(),
+        }
+    }
+
+    /// Queue an alert without waiting; it is dropped and counted when the queue is full
+    pub(crate) fn enqueue(&self, alert: &Alert) {
+        if self.tx.try_send(alert.clone()).is_err() {
+            let dropped = self.counters.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            tracing::warn!(
+                "{} sink queue full, dropped alert {} ({} dropped so far)",
+                self.sink.name(),
+                alert.id,
+                dropped
+            );
+        }
+    }
+
+    /// Spawn the worker publishing queued alerts; `None` if it already runs
+    ///
+    /// The worker ends once the queue is dropped and drained.
+    pub(crate) fn start(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let mut rx = self.rx.lock().ok()?.take()?;
+        let sink = self.sink.clone();
+        let counters = self.counters.clone();
+
+        Some(tokio::spawn(async move {
+            while let Some(alert) = rx.recv().await {
+                let counter = if deliver(sink.as_ref(), &alert).await {
+                    &counters.published
+                } else {
+                    &counters.dropped
+                };
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+        }))
+    }
+
+    pub(crate) fn stats(&self) -> SinkStats {
+        SinkStats {
+            name: self.sink.name().to_string(),
+            published: self.counters.published.load(Ordering::Relaxed),
+            dropped: self.counters.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Publish an alert, backing off 1s, 2s, 4s between attempts
+///
+/// Later alerts wait in the queue meanwhile, so a broker outage fills the
+/// queue instead of losing alerts right away.
+async fn deliver(sink: &dyn Sink, alert: &Alert) -> bool {
+    for attempt in 0..MAX_PUBLISH_ATTEMPTS {
+        match sink.publish(alert).await {
+            Ok(()) => return true,
+            Err(e) => tracing::warn!(
+                "Failed to publish alert {} to {}: {} (attempt {})",
+                alert.id,
+                sink.name(),
+                e,
+                attempt + 1
+            ),
+        }
+
+        if attempt + 1 < MAX_PUBLISH_ATTEMPTS {
+            tokio::time::sleep(Duration::from_secs(2u64.pow(attempt))).await;
+        }
+    }
+
+    tracing::error!(
+        "Dropped alert {} after {} failed publishes to {}",
+        alert.id,
+        MAX_PUBLISH_ATTEMPTS,
+        sink.name()
+    );
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AlertSeverity, AttackPattern, SCHEMA_VERSION};
+    use std::collections::HashMap;
+
+    struct NullSink;
+
+    #[async_trait::async_trait]
+    impl Sink for NullSink {
+        fn name(&self) -> &str {
+            "null"
+        }
+
+        async fn publish(&self, _alert: &Alert) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn alert(id: &str) -> Alert {
+        Alert {
+            schema_version: SCHEMA_VERSION,
+            id: id.to_string(),
+            timestamp: 1_700_000_000,
+            chain: "westend".to_string(),
+            severity: AlertSeverity::High,
+            pattern: AttackPattern::FlashLoan,
+            description: "Borrow, swap, repay".to_string(),
+            transaction_hash: Some("0xabc".to_string()),
+            block_number: Some(42),
+            metadata: HashMap::from([("caller".to_string(), "alice".to_string())]),
+            recommended_actions: vec![],
+            acknowledged: false,
+        }
+    }
+
+    #[test]
+    fn test_encoded_alert_carries_schema_version() {
+        let message: serde_json::Value = serde_json::from_slice(&encode_alert(&alert("a1"))).unwrap();
+
+        assert_eq!(message["schema"], ALERT_SCHEMA);
+        assert_eq!(message["version"], ALERT_SCHEMA_VERSION);
+        assert_eq!(message["alert"]["id"], "a1");
+        assert_eq!(message["alert"]["metadata"]["caller"], "alice");
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_drops_and_counts() {
+        // Not started, so nothing drains the queue
+        let queue = SinkQueue::new(Arc::new(NullSink), 2);
+        for id in ["a1", "a2", "a3", "a4"] {
+            queue.enqueue(&alert(id));
+        }
+
+        assert_eq!(
+            queue.stats(),
+            SinkStats { name: "null".to_string(), published: 0, dropped: 2 }
+        );
+    }
+}
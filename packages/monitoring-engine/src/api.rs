@@ -1,13 +1,59 @@
 //! REST API for monitoring engine
 //!
 //! Provides HTTP endpoints to access monitoring statistics and status
+//!
+//! Every handler runs under a per-route timeout (503 when exceeded),
+//! list endpoints are paginated with `limit`/`offset` and capped at
+//! `MAX_PAGE_SIZE` rows, and JSON request bodies are limited to
+//! `MAX_JSON_BODY_BYTES` (400 when exceeded).
+//!
+//! Errors use a uniform body, `{"error": {"code": ..., "message": ...}}`
+//! (see [`ApiError`]). Server-side failures are logged in full but only a
+//! generic message is returned, so connection strings and query details
+//! never reach clients.
+//!
+//! Admin endpoints (`/api/admin/*`), and acknowledgments carrying a
+//! `false_positive` verdict, require `Authorization: Bearer <token>`
+//! matching the configured `admin_token`, and are disabled (403) when no
+//! token is configured.
 
 use crate::{MonitoringEngine, MonitorConfig, ChainInfo, Result};
+use crate::alerts::AlertExportFormat;
 use crate::config;
-use actix_web::{http::header, web, App, HttpResponse, HttpServer, middleware};
+use actix_web::{http::{header, StatusCode}, web, App, HttpRequest, HttpResponse, HttpServer, ResponseError, middleware};
 use actix_cors::Cors;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Timeout for endpoints served from memory
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Timeout for database-backed analytics endpoints
+pub const QUERY_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Timeout for export endpoints
+pub const EXPORT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum number of rows returned by a single page of alerts or exports
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+/// Default page size for alert listings
+const DEFAULT_ALERTS_PAGE_SIZE: usize = 50;
+
+/// Maximum accepted JSON request body size
+pub const MAX_JSON_BODY_BYTES: usize = 16 * 1024;
+
+/// Widest window `GET /api/graph` exports, in hours
+pub const MAX_GRAPH_HOURS: i32 = 7 * 24;
+
+/// Most edges `GET /api/graph` returns
+pub const MAX_GRAPH_EDGES: usize = 10_000;
+
+/// Fewest days of data `POST /api/admin/purge` keeps
+pub const MIN_PURGE_DAYS: i32 = 7;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiStats {
@@ -15,6 +61,9 @@ pub struct ApiStats {
     pub blocks_processed: u64,
     pub transactions_analyzed: u64,
     pub alerts_triggered: u64,
+    pub decode_failures: u64,
+    /// Retried, deferred and dropped database inserts
+    pub db_writes: crate::database::retry::WriteStats,
     pub chain_name: String,
     pub endpoint: String,
     pub reconnect_attempts: u32,
@@ -43,25 +92,229 @@ pub struct SwitchChainResponse {
 /// State shared across API handlers
 struct ApiState {
     engine: Arc<MonitoringEngine>,
-    start_time: std::time::Instant,
+    start_time: Instant,
+}
+
+/// Page selection parsed from `limit`/`offset` query parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Page {
+    limit: usize,
+    offset: usize,
+}
+
+impl Page {
+    /// Parse pagination parameters, rejecting limits above `MAX_PAGE_SIZE`
+    /// and offsets the database cannot represent (above `i64::MAX`)
+    fn from_query(
+        query: &HashMap<String, String>,
+        default_limit: usize,
+    ) -> std::result::Result<Self, HttpResponse> {
+        let limit = match query.get("limit") {
+            Some(l) => l
+                .parse::<usize>()
+                .map_err(|_| bad_request(format!("Invalid limit: {}", l)))?,
+            None => default_limit,
+        };
+
+        if limit == 0 || limit > MAX_PAGE_SIZE {
+            return Err(bad_request(format!(
+                "limit must be between 1 and {}",
+                MAX_PAGE_SIZE
+            )));
+        }
+
+        let offset = match query.get("offset") {
+            Some(o) => o
+                .parse::<usize>()
+                .map_err(|_| bad_request(format!("Invalid offset: {}", o)))?,
+            None => 0,
+        };
+
+        if offset > i64::MAX as usize {
+            return Err(bad_request(format!("offset must be at most {}", i64::MAX)));
+        }
+
+        Ok(Self { limit, offset })
+    }
+}
+
+fn bad_request(message: String) -> HttpResponse {
+    ApiError::bad_request(message).error_response()
+}
+
+/// Error returned by API handlers, rendered as `{"error": {"code", "message"}}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    status: StatusCode,
+    /// Stable machine-readable code, e.g. `database_unavailable`
+    pub code: &'static str,
+    /// Message safe to show to clients
+    pub message: String,
+}
+
+/// Body of an error response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorBody {
+    pub error: ErrorDetail,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorDetail {
+    pub code: String,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "bad_request", message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "not_found", message)
+    }
+
+    /// Missing or wrong admin token
+    pub fn unauthorized() -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "unauthorized", "Admin token required")
+    }
+
+    /// No admin token is configured, so admin endpoints are off
+    pub fn admin_disabled() -> Self {
+        Self::new(StatusCode::FORBIDDEN, "admin_disabled", "Admin API is disabled")
+    }
+
+    /// Unexpected failure; the details are logged, not returned
+    pub fn internal(context: &str, error: impl std::fmt::Display) -> Self {
+        tracing::error!("{}: {}", context, error);
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", context)
+    }
+
+    /// No database is configured for this engine
+    pub fn database_unavailable() -> Self {
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "database_unavailable",
+            "Database not available",
+        )
+    }
+
+    /// Map a database client error: 503 when the database cannot be
+    /// reached, 500 for anything else
+    pub fn database(context: &str, error: anyhow::Error) -> Self {
+        let unreachable = error.downcast_ref::<deadpool_postgres::PoolError>().is_some()
+            || error
+                .downcast_ref::<tokio_postgres::Error>()
+                .is_some_and(|e| e.is_closed());
+
+        if unreachable {
+            tracing::error!("{}: {}", context, error);
+            Self::database_unavailable()
+        } else {
+            Self::internal(context, error)
+        }
+    }
+
+    pub fn timeout(timeout: Duration) -> Self {
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "timeout",
+            format!("Request timed out after {}ms", timeout.as_millis()),
+        )
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.status
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status).json(ErrorBody {
+            error: ErrorDetail {
+                code: self.code.to_string(),
+                message: self.message.clone(),
+            },
+        })
+    }
+}
+
+impl From<crate::Error> for ApiError {
+    fn from(error: crate::Error) -> Self {
+        use crate::{Error, ErrorKind};
+
+        if error.kind() == ErrorKind::NotFound {
+            return Self::not_found(error.to_string());
+        }
+
+        match error {
+            Error::ConnectionError(_) | Error::SubscriptionError(_) => {
+                tracing::error!("Node unavailable: {}", error);
+                Self::new(StatusCode::SERVICE_UNAVAILABLE, "node_unavailable", "Node not available")
+            }
+            Error::DatabaseError(_) => {
+                tracing::error!("Database error: {}", error);
+                Self::database_unavailable()
+            }
+            Error::ConfigError(message) => {
+                Self::new(StatusCode::BAD_REQUEST, "invalid_configuration", message)
+            }
+            Error::NotFound(_) => Self::not_found(error.to_string()),
+            Error::ParseError(_) | Error::AlertError(_) | Error::IoError(_) => {
+                Self::internal("Internal error", error)
+            }
+        }
+    }
+}
+
+/// Run a handler future, returning 503 if it does not finish within `timeout`
+pub async fn with_timeout<F>(timeout: Duration, handler: F) -> HttpResponse
+where
+    F: Future<Output = HttpResponse>,
+{
+    match tokio::time::timeout(timeout, handler).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!("API request timed out after {:?}", timeout);
+            ApiError::timeout(timeout).error_response()
+        }
+    }
 }
 
 /// GET /api/stats - Get monitoring engine statistics
 async fn get_stats(data: web::Data<ApiState>) -> HttpResponse {
-    let stats = data.engine.get_stats().await;
-    let config = &data.engine.config;
+    with_timeout(DEFAULT_TIMEOUT, async {
+        let stats = data.engine.get_stats().await;
+        let config = &data.engine.config;
 
-    let api_stats = ApiStats {
-        is_running: stats.is_running,
-        blocks_processed: stats.blocks_processed,
-        transactions_analyzed: stats.transactions_analyzed,
-        alerts_triggered: stats.alerts_triggered,
-        chain_name: config.chain_name.clone(),
-        endpoint: config.ws_endpoint.clone(),
-        reconnect_attempts: data.engine.connection.get_reconnect_attempts(),
-    };
+        let api_stats = ApiStats {
+            is_running: stats.is_running,
+            blocks_processed: stats.blocks_processed,
+            transactions_analyzed: stats.transactions_analyzed,
+            alerts_triggered: stats.alerts_triggered,
+            decode_failures: stats.decode_failures,
+            db_writes: stats.db_writes,
+            chain_name: config.chain_name.clone(),
+            endpoint: config.ws_endpoint.clone(),
+            reconnect_attempts: data.engine.connection.get_reconnect_attempts(),
+        };
 
-    HttpResponse::Ok().json(api_stats)
+        HttpResponse::Ok().json(api_stats)
+    })
+    .await
 }
 
 /// GET /api/health - Health check endpoint
@@ -75,42 +328,205 @@ async fn health_check(data: web::Data<ApiState>) -> HttpResponse {
     })
 }
 
-/// GET /api/alerts - Get recent alerts
-async fn get_alerts(data: web::Data<ApiState>) -> HttpResponse {
-    let alerts = data.engine.alert_manager.get_recent_alerts(50).await;
-    HttpResponse::Ok().json(alerts)
+/// GET /api/info - Engine version, build, uptime and connected chains
+async fn get_info(data: web::Data<ApiState>) -> HttpResponse {
+    with_timeout(DEFAULT_TIMEOUT, async {
+        HttpResponse::Ok().json(data.engine.get_info().await)
+    })
+    .await
+}
+
+/// GET /api/backfill/status - Progress of the latest historical backfill
+async fn get_backfill_status(data: web::Data<ApiState>) -> HttpResponse {
+    with_timeout(DEFAULT_TIMEOUT, async {
+        match data.engine.backfill_status().await {
+            Some(status) => HttpResponse::Ok().json(status),
+            None => ApiError::not_found("No backfill has been run").error_response(),
+        }
+    })
+    .await
+}
+
+/// GET /api/alerts - Get recent alerts (newest first, paginated)
+async fn get_alerts(
+    query: web::Query<HashMap<String, String>>,
+    data: web::Data<ApiState>,
+) -> HttpResponse {
+    let page = match Page::from_query(&query, DEFAULT_ALERTS_PAGE_SIZE) {
+        Ok(page) => page,
+        Err(response) => return response,
+    };
+
+    with_timeout(DEFAULT_TIMEOUT, async {
+        let alerts: Vec<_> = data
+            .engine
+            .alert_manager
+            .get_recent_alerts(page.offset.saturating_add(page.limit))
+            .await
+            .into_iter()
+            .skip(page.offset)
+            .take(page.limit)
+            .collect();
+        HttpResponse::Ok().json(alerts)
+    })
+    .await
+}
+
+/// GET /api/alerts/unacknowledged - Get unacknowledged alerts (paginated)
+async fn get_unacknowledged_alerts(
+    query: web::Query<HashMap<String, String>>,
+    data: web::Data<ApiState>,
+) -> HttpResponse {
+    let page = match Page::from_query(&query, MAX_PAGE_SIZE) {
+        Ok(page) => page,
+        Err(response) => return response,
+    };
+
+    with_timeout(DEFAULT_TIMEOUT, async {
+        let alerts: Vec<_> = data
+            .engine
+            .alert_manager
+            .get_unacknowledged_alerts()
+            .await
+            .into_iter()
+            .skip(page.offset)
+            .take(page.limit)
+            .collect();
+        HttpResponse::Ok().json(alerts)
+    })
+    .await
 }
 
-/// GET /api/alerts/unacknowledged - Get unacknowledged alerts
-async fn get_unacknowledged_alerts(data: web::Data<ApiState>) -> HttpResponse {
-    let alerts = data.engine.alert_manager.get_unacknowledged_alerts().await;
-    HttpResponse::Ok().json(alerts)
+/// GET /api/alerts/export?format=json|csv|sarif - Export the in-memory alert history
+async fn export_alerts(
+    query: web::Query<HashMap<String, String>>,
+    data: web::Data<ApiState>,
+) -> HttpResponse {
+    let format = match query.get("format").map(|f| f.parse::<AlertExportFormat>()) {
+        None => AlertExportFormat::Json,
+        Some(Ok(format)) => format,
+        Some(Err(e)) => return ApiError::bad_request(e).error_response(),
+    };
+
+    with_timeout(EXPORT_TIMEOUT, async {
+        let body = data.engine.alert_manager.export_history(format).await;
+        let filename = match format {
+            AlertExportFormat::Json => "alerts.json",
+            AlertExportFormat::Csv => "alerts.csv",
+            AlertExportFormat::Sarif => "alerts.sarif",
+        };
+
+        HttpResponse::Ok()
+            .insert_header((header::CONTENT_TYPE, format.content_type()))
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ))
+            .body(body)
+    })
+    .await
 }
 
 /// POST /api/alerts/{id}/acknowledge - Acknowledge an alert
+///
+/// `?false_positive=true|false` also records the analyst's verdict, which
+/// adjusts the detector's alert threshold, so it requires admin auth.
 async fn acknowledge_alert(
+    req: HttpRequest,
     path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
     data: web::Data<ApiState>,
 ) -> HttpResponse {
     let alert_id = path.into_inner();
-
-    if data.engine.alert_manager.acknowledge_alert(&alert_id).await {
-        HttpResponse::Ok().json(serde_json::json!({
-            "success": true,
-            "message": "Alert acknowledged"
-        }))
-    } else {
-        HttpResponse::NotFound().json(serde_json::json!({
-            "success": false,
-            "message": "Alert not found"
-        }))
+    let false_positive = match query.get("false_positive").map(|v| v.parse::<bool>()) {
+        None => None,
+        Some(Ok(value)) => Some(value),
+        Some(Err(_)) => {
+            return ApiError::bad_request("false_positive must be true or false").error_response();
+        }
+    };
+    if false_positive.is_some() {
+        if let Err(e) = authorize_admin(&req, data.engine.config.admin_token.as_deref()) {
+            return e.error_response();
+        }
     }
+
+    with_timeout(DEFAULT_TIMEOUT, async {
+        if !data.engine.alert_manager.acknowledge_alert(&alert_id).await {
+            return ApiError::not_found("Alert not found").error_response();
+        }
+
+        let Some(false_positive) = false_positive else {
+            return HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "message": "Alert acknowledged"
+            }));
+        };
+
+        match data.engine.record_feedback(&alert_id, !false_positive).await {
+            Ok(threshold) => HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "message": "Alert acknowledged and feedback recorded",
+                "threshold": threshold
+            })),
+            Err(e) => {
+                tracing::warn!("Failed to record feedback for alert {}: {}", alert_id, e);
+                HttpResponse::Ok().json(serde_json::json!({
+                    "success": true,
+                    "message": "Alert acknowledged; feedback not recorded"
+                }))
+            }
+        }
+    })
+    .await
 }
 
 /// GET /api/detectors - Get detector statistics
 async fn get_detectors(data: web::Data<ApiState>) -> HttpResponse {
-    let detector_stats = data.engine.get_detector_stats().await;
-    HttpResponse::Ok().json(detector_stats)
+    with_timeout(DEFAULT_TIMEOUT, async {
+        let detector_stats = data.engine.get_detector_stats().await;
+        HttpResponse::Ok().json(detector_stats)
+    })
+    .await
+}
+
+/// GET /api/detectors/manifest - What each registered detector emits and inspects
+async fn get_detector_manifests(data: web::Data<ApiState>) -> HttpResponse {
+    with_timeout(DEFAULT_TIMEOUT, async {
+        HttpResponse::Ok().json(data.engine.detector_manifests())
+    })
+    .await
+}
+
+/// GET /api/schema/alert - JSON Schema of serialized alerts
+async fn get_alert_schema() -> HttpResponse {
+    HttpResponse::Ok().json(crate::schema::alert_schema())
+}
+
+/// GET /api/schema/detection - JSON Schema of serialized detection results
+async fn get_detection_schema() -> HttpResponse {
+    HttpResponse::Ok().json(crate::schema::detection_result_schema())
+}
+
+/// GET /api/detectors/{name}/histogram - Confidence distribution of a detector
+async fn get_detector_histogram(
+    path: web::Path<String>,
+    data: web::Data<ApiState>,
+) -> HttpResponse {
+    let detector_name = path.into_inner();
+
+    with_timeout(DEFAULT_TIMEOUT, async {
+        match data.engine.get_confidence_histogram(&detector_name).await {
+            Some(histogram) => HttpResponse::Ok().json(serde_json::json!({
+                "detector": detector_name,
+                "threshold": data.engine.alert_thresholds().threshold_for(&detector_name),
+                "total": histogram.total,
+                "buckets": histogram.buckets(),
+            })),
+            None => ApiError::not_found(format!("Unknown detector '{}'", detector_name)).error_response(),
+        }
+    })
+    .await
 }
 
 /// GET /api/chains - Get available chain presets
@@ -143,11 +559,15 @@ async fn switch_chain(
 
     // Validate that the chain exists
     if MonitorConfig::from_chain_name(chain_name).is_none() {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "success": false,
-            "error": format!("Unknown chain: {}", chain_name),
-            "available_chains": ["westend", "asset-hub", "polkadot", "kusama"]
-        }));
+        let available: Vec<String> = MonitorConfig::available_chains()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        return bad_request(format!(
+            "Unknown chain: {} (available: {})",
+            chain_name,
+            available.join(", ")
+        ));
     }
 
     // Save the configuration
@@ -164,187 +584,324 @@ async fn switch_chain(
                 requires_restart: true,
             })
         }
-        Err(e) => {
-            tracing::error!("Failed to save chain configuration: {}", e);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "success": false,
-                "error": format!("Failed to save configuration: {}", e)
-            }))
-        }
+        Err(e) => ApiError::internal("Failed to save configuration", e).error_response(),
     }
 }
 
 /// GET /api/analytics/ml-features - Get ML feature statistics
 async fn get_ml_features(
-    query: web::Query<std::collections::HashMap<String, String>>,
+    query: web::Query<HashMap<String, String>>,
     data: web::Data<ApiState>,
 ) -> HttpResponse {
-    if let Some(db) = &data.engine.database {
-        let limit = query
-            .get("limit")
-            .and_then(|l| l.parse::<i64>().ok())
-            .unwrap_or(100);
-
-        match db.get_ml_feature_stats(limit).await {
-            Ok(features) => HttpResponse::Ok().json(features),
-            Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": format!("Failed to fetch ML features: {}", e)
-            })),
+    with_timeout(QUERY_TIMEOUT, async {
+        if let Some(db) = &data.engine.database {
+            let limit = query
+                .get("limit")
+                .and_then(|l| l.parse::<i64>().ok())
+                .unwrap_or(100)
+                .clamp(1, MAX_PAGE_SIZE as i64);
+
+            match db.get_ml_feature_stats(limit).await {
+                Ok(features) => HttpResponse::Ok().json(features),
+                Err(e) => ApiError::database("Failed to fetch ML features", e).error_response(),
+            }
+        } else {
+            ApiError::database_unavailable().error_response()
         }
-    } else {
-        HttpResponse::ServiceUnavailable().json(serde_json::json!({
-            "error": "Database not available"
-        }))
-    }
+    })
+    .await
 }
 
 /// GET /api/analytics/attack-trends - Get attack pattern trends
 async fn get_attack_trends(
-    query: web::Query<std::collections::HashMap<String, String>>,
+    query: web::Query<HashMap<String, String>>,
     data: web::Data<ApiState>,
 ) -> HttpResponse {
-    if let Some(db) = &data.engine.database {
-        let hours = query
-            .get("hours")
-            .and_then(|h| h.parse::<i32>().ok())
-            .unwrap_or(24);
-
-        match db.get_attack_trends(hours).await {
-            Ok(trends) => HttpResponse::Ok().json(trends),
-            Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": format!("Failed to fetch attack trends: {}", e)
-            })),
+    with_timeout(QUERY_TIMEOUT, async {
+        if let Some(db) = &data.engine.database {
+            let hours = query
+                .get("hours")
+                .and_then(|h| h.parse::<i32>().ok())
+                .unwrap_or(24);
+
+            match db.get_attack_trends(hours).await {
+                Ok(trends) => HttpResponse::Ok().json(trends),
+                Err(e) => ApiError::database("Failed to fetch attack trends", e).error_response(),
+            }
+        } else {
+            ApiError::database_unavailable().error_response()
         }
-    } else {
-        HttpResponse::ServiceUnavailable().json(serde_json::json!({
-            "error": "Database not available"
-        }))
-    }
+    })
+    .await
+}
+
+/// GET /api/analytics/correlated-trends - Get hourly transaction volume and detections
+async fn get_correlated_trends(
+    query: web::Query<HashMap<String, String>>,
+    data: web::Data<ApiState>,
+) -> HttpResponse {
+    with_timeout(QUERY_TIMEOUT, async {
+        if let Some(db) = &data.engine.database {
+            let hours = query
+                .get("hours")
+                .and_then(|h| h.parse::<i32>().ok())
+                .unwrap_or(24);
+
+            match db.get_correlated_trends(hours).await {
+                Ok(trends) => HttpResponse::Ok().json(trends),
+                Err(e) => ApiError::database("Failed to fetch correlated trends", e).error_response(),
+            }
+        } else {
+            ApiError::database_unavailable().error_response()
+        }
+    })
+    .await
 }
 
 /// GET /api/analytics/detector-stats - Get detector statistics
 async fn get_detector_stats(
-    query: web::Query<std::collections::HashMap<String, String>>,
+    query: web::Query<HashMap<String, String>>,
     data: web::Data<ApiState>,
 ) -> HttpResponse {
-    if let Some(db) = &data.engine.database {
-        let hours = query
-            .get("hours")
-            .and_then(|h| h.parse::<i32>().ok())
-            .unwrap_or(24);
-
-        match db.get_detector_stats(hours).await {
-            Ok(stats) => HttpResponse::Ok().json(stats),
-            Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": format!("Failed to fetch detector stats: {}", e)
-            })),
+    with_timeout(QUERY_TIMEOUT, async {
+        if let Some(db) = &data.engine.database {
+            let hours = query
+                .get("hours")
+                .and_then(|h| h.parse::<i32>().ok())
+                .unwrap_or(24);
+
+            match db.get_detector_stats(hours).await {
+                Ok(stats) => HttpResponse::Ok().json(stats),
+                Err(e) => ApiError::database("Failed to fetch detector stats", e).error_response(),
+            }
+        } else {
+            ApiError::database_unavailable().error_response()
         }
-    } else {
-        HttpResponse::ServiceUnavailable().json(serde_json::json!({
-            "error": "Database not available"
-        }))
+    })
+    .await
+}
+
+/// Check the request's bearer token against the configured admin token
+fn authorize_admin(req: &HttpRequest, admin_token: Option<&str>) -> std::result::Result<(), ApiError> {
+    let Some(expected) = admin_token else {
+        return Err(ApiError::admin_disabled());
+    };
+
+    let provided = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(()),
+        _ => Err(ApiError::unauthorized()),
     }
 }
 
-/// GET /api/export/json - Export detection data as JSON
-async fn export_json(
-    query: web::Query<std::collections::HashMap<String, String>>,
+/// Compare without returning early on the first mismatch, so response
+/// times don't reveal how much of a token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// POST /api/admin/purge?days=N - Remove data older than N days
+///
+/// Applies the same purge as the `retention_days` background task, on
+/// demand. Admin only; `days` must be at least [`MIN_PURGE_DAYS`].
+async fn purge_data(
+    req: HttpRequest,
+    query: web::Query<HashMap<String, String>>,
     data: web::Data<ApiState>,
 ) -> HttpResponse {
-    if let Some(db) = &data.engine.database {
-        let hours = query
-            .get("hours")
-            .and_then(|h| h.parse::<i32>().ok());
-
-        match db.get_export_data(hours).await {
-            Ok(export_data) => HttpResponse::Ok()
-                .insert_header((header::CONTENT_TYPE, "application/json"))
-                .insert_header((
-                    header::CONTENT_DISPOSITION,
-                    "attachment; filename=\"detections.json\"",
-                ))
-                .json(export_data),
-            Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": format!("Failed to export data: {}", e)
-            })),
-        }
-    } else {
-        HttpResponse::ServiceUnavailable().json(serde_json::json!({
-            "error": "Database not available"
-        }))
+    if let Err(e) = authorize_admin(&req, data.engine.config.admin_token.as_deref()) {
+        return e.error_response();
     }
+
+    let days = match query.get("days").map(|d| (d, d.parse::<i32>())) {
+        Some((_, Ok(days))) if days >= MIN_PURGE_DAYS => days,
+        Some((_, Ok(_))) => return bad_request(format!("days must be at least {}", MIN_PURGE_DAYS)),
+        Some((d, _)) => return bad_request(format!("Invalid days: {}", d)),
+        None => return bad_request("days is required".to_string()),
+    };
+
+    with_timeout(EXPORT_TIMEOUT, async {
+        if let Some(db) = &data.engine.database {
+            match db.apply_retention(days).await {
+                Ok(report) => HttpResponse::Ok().json(report),
+                Err(e) => ApiError::database("Failed to purge data", e).error_response(),
+            }
+        } else {
+            ApiError::database_unavailable().error_response()
+        }
+    })
+    .await
 }
 
-/// GET /api/export/csv - Export detection data as CSV
-async fn export_csv(
-    query: web::Query<std::collections::HashMap<String, String>>,
+/// GET /api/graph?hours=24 - Address graph of transfers and detections, for graph tools
+///
+/// `hours` must be between 1 and `MAX_GRAPH_HOURS` (400 otherwise). At most
+/// `MAX_GRAPH_EDGES` edges are returned, with `truncated` set when more exist.
+async fn get_transaction_graph(
+    query: web::Query<HashMap<String, String>>,
     data: web::Data<ApiState>,
 ) -> HttpResponse {
-    if let Some(db) = &data.engine.database {
-        let hours = query
-            .get("hours")
-            .and_then(|h| h.parse::<i32>().ok());
-
-        match db.get_export_data(hours).await {
-            Ok(export_data) => {
-                // Convert JSON to CSV
-                let mut csv_content = String::from("timestamp,detection_id,tx_hash,detector_name,attack_pattern,confidence,severity,description,caller,pallet,call_name,success,chain\n");
-
-                for row in export_data {
-                    csv_content.push_str(&format!(
-                        "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{},{},\"{}\",\"{}\",\"{}\",\"{}\",{},\"{}\"\n",
-                        row["timestamp"].as_str().unwrap_or(""),
-                        row["detection_id"].as_str().unwrap_or(""),
-                        row["tx_hash"].as_str().unwrap_or(""),
-                        row["detector_name"].as_str().unwrap_or(""),
-                        row["attack_pattern"].as_str().unwrap_or(""),
-                        row["confidence"].as_f64().unwrap_or(0.0),
-                        row["severity"].as_str().unwrap_or(""),
-                        row["description"].as_str().unwrap_or(""),
-                        row["caller"].as_str().unwrap_or(""),
-                        row["pallet"].as_str().unwrap_or(""),
-                        row["call_name"].as_str().unwrap_or(""),
-                        row["success"].as_bool().unwrap_or(false),
-                        row["chain"].as_str().unwrap_or(""),
-                    ));
-                }
+    let hours = match query.get("hours") {
+        Some(h) => match h.parse::<i32>() {
+            Ok(hours) if (1..=MAX_GRAPH_HOURS).contains(&hours) => hours,
+            Ok(_) => {
+                return bad_request(format!("hours must be between 1 and {}", MAX_GRAPH_HOURS))
+            }
+            Err(_) => return bad_request(format!("Invalid hours: {}", h)),
+        },
+        None => 24,
+    };
 
-                HttpResponse::Ok()
-                    .insert_header((header::CONTENT_TYPE, "text/csv"))
+    with_timeout(QUERY_TIMEOUT, async {
+        if let Some(db) = &data.engine.database {
+            match db.export_graph(hours, MAX_GRAPH_EDGES).await {
+                Ok(graph) => HttpResponse::Ok().json(graph),
+                Err(e) => ApiError::database("Failed to export transaction graph", e).error_response(),
+            }
+        } else {
+            ApiError::database_unavailable().error_response()
+        }
+    })
+    .await
+}
+
+/// GET /api/export/json - Export detection data as JSON (paginated)
+async fn export_json(
+    query: web::Query<HashMap<String, String>>,
+    data: web::Data<ApiState>,
+) -> HttpResponse {
+    let page = match Page::from_query(&query, MAX_PAGE_SIZE) {
+        Ok(page) => page,
+        Err(response) => return response,
+    };
+
+    with_timeout(EXPORT_TIMEOUT, async {
+        if let Some(db) = &data.engine.database {
+            let hours = query
+                .get("hours")
+                .and_then(|h| h.parse::<i32>().ok());
+
+            match db.get_export_data(hours, page.limit as i64, page.offset as i64).await {
+                Ok(export_data) => HttpResponse::Ok()
+                    .insert_header((header::CONTENT_TYPE, "application/json"))
                     .insert_header((
                         header::CONTENT_DISPOSITION,
-                        "attachment; filename=\"detections.csv\"",
+                        "attachment; filename=\"detections.json\"",
                     ))
-                    .body(csv_content)
+                    .json(export_data),
+                Err(e) => ApiError::database("Failed to export data", e).error_response(),
             }
-            Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": format!("Failed to export data: {}", e)
-            })),
+        } else {
+            ApiError::database_unavailable().error_response()
         }
-    } else {
-        HttpResponse::ServiceUnavailable().json(serde_json::json!({
-            "error": "Database not available"
-        }))
-    }
+    })
+    .await
+}
+
+/// GET /api/export/csv - Export detection data as CSV (paginated)
+async fn export_csv(
+    query: web::Query<HashMap<String, String>>,
+    data: web::Data<ApiState>,
+) -> HttpResponse {
+    let page = match Page::from_query(&query, MAX_PAGE_SIZE) {
+        Ok(page) => page,
+        Err(response) => return response,
+    };
+
+    with_timeout(EXPORT_TIMEOUT, async {
+        if let Some(db) = &data.engine.database {
+            let hours = query
+                .get("hours")
+                .and_then(|h| h.parse::<i32>().ok());
+
+            match db.get_export_data(hours, page.limit as i64, page.offset as i64).await {
+                Ok(export_data) => {
+                    // Convert records to CSV
+                    let mut csv_content = String::from("timestamp,detection_id,tx_hash,detector_name,attack_pattern,confidence,severity,description,caller,pallet,call_name,success,chain\n");
+
+                    for row in export_data {
+                        csv_content.push_str(&format!(
+                            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{},{},\"{}\",\"{}\",\"{}\",\"{}\",{},\"{}\"\n",
+                            row.timestamp.to_rfc3339(),
+                            row.detection_id,
+                            row.tx_hash,
+                            row.detector_name,
+                            row.attack_pattern,
+                            row.confidence,
+                            row.severity,
+                            row.description.as_deref().unwrap_or(""),
+                            row.caller.as_deref().unwrap_or(""),
+                            row.pallet.as_deref().unwrap_or(""),
+                            row.call_name.as_deref().unwrap_or(""),
+                            row.success.unwrap_or(false),
+                            row.chain.as_deref().unwrap_or(""),
+                        ));
+                    }
+
+                    HttpResponse::Ok()
+                        .insert_header((header::CONTENT_TYPE, "text/csv"))
+                        .insert_header((
+                            header::CONTENT_DISPOSITION,
+                            "attachment; filename=\"detections.csv\"",
+                        ))
+                        .body(csv_content)
+                }
+                Err(e) => ApiError::database("Failed to export data", e).error_response(),
+            }
+        } else {
+            ApiError::database_unavailable().error_response()
+        }
+    })
+    .await
 }
 
 /// Configure API routes
 fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg
+        .app_data(
+            web::JsonConfig::default()
+                .limit(MAX_JSON_BODY_BYTES)
+                .error_handler(|err, _req| {
+                    let response = bad_request(format!("Invalid request body: {}", err));
+                    actix_web::error::InternalError::from_response(err, response).into()
+                }),
+        )
         .route("/health", web::get().to(health_check))
         .route("/stats", web::get().to(get_stats))
+        .route("/info", web::get().to(get_info))
+        .route("/backfill/status", web::get().to(get_backfill_status))
         .route("/detectors", web::get().to(get_detectors))
+        .route("/detectors/manifest", web::get().to(get_detector_manifests))
+        .route("/detectors/{name}/histogram", web::get().to(get_detector_histogram))
+        .route("/schema/alert", web::get().to(get_alert_schema))
+        .route("/schema/detection", web::get().to(get_detection_schema))
         .route("/alerts", web::get().to(get_alerts))
         .route("/alerts/unacknowledged", web::get().to(get_unacknowledged_alerts))
+        .route("/alerts/export", web::get().to(export_alerts))
         .route("/alerts/{id}/acknowledge", web::post().to(acknowledge_alert))
         .route("/chains", web::get().to(get_available_chains))
         .route("/chains/current", web::get().to(get_current_chain))
         .route("/chains/switch", web::post().to(switch_chain))
         .route("/analytics/ml-features", web::get().to(get_ml_features))
         .route("/analytics/attack-trends", web::get().to(get_attack_trends))
+        .route("/analytics/correlated-trends", web::get().to(get_correlated_trends))
         .route("/analytics/detector-stats", web::get().to(get_detector_stats))
+        .route("/graph", web::get().to(get_transaction_graph))
         .route("/export/json", web::get().to(export_json))
-        .route("/export/csv", web::get().to(export_csv));
+        .route("/export/csv", web::get().to(export_csv))
+        .route("/admin/purge", web::post().to(purge_data));
+}
+
+/// Build the `/api` scope with all routes, limits and shared state
+pub fn api_scope(engine: Arc<MonitoringEngine>, start_time: Instant) -> actix_web::Scope {
+    web::scope("/api")
+        .app_data(web::Data::new(ApiState { engine, start_time }))
+        .configure(configure_routes)
 }
 
 /// Start the API server
@@ -354,10 +911,7 @@ pub async fn start_api_server(
 ) -> Result<()> {
     tracing::info!("Starting API server on {}", bind_address);
 
-    let api_state = web::Data::new(ApiState {
-        engine,
-        start_time: std::time::Instant::now(),
-    });
+    let start_time = Instant::now();
 
     HttpServer::new(move || {
         // SECURITY NOTE: In production, replace allow_any_origin() with specific origins
@@ -406,8 +960,7 @@ pub async fn start_api_server(
         App::new()
             .wrap(cors)
             .wrap(middleware::Logger::default())
-            .app_data(api_state.clone())
-            .service(web::scope("/api").configure(configure_routes))
+            .service(api_scope(engine.clone(), start_time))
     })
     .bind(bind_address)
     .map_err(|e| crate::Error::IoError(e))?
@@ -430,6 +983,8 @@ mod tests {
             blocks_processed: 100,
             transactions_analyzed: 500,
             alerts_triggered: 5,
+            decode_failures: 0,
+            db_writes: Default::default(),
             chain_name: "test".to_string(),
             endpoint: "ws://localhost:9944".to_string(),
             reconnect_attempts: 0,
@@ -451,4 +1006,130 @@ mod tests {
         let json = serde_json::to_string(&health).unwrap();
         assert!(json.contains("\"status\":\"healthy\""));
     }
+
+    #[test]
+    fn test_admin_token_required() {
+        use actix_web::test::TestRequest;
+
+        let with_token = |token: &str| {
+            TestRequest::default()
+                .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+                .to_http_request()
+        };
+
+        // No token configured: admin endpoints are off, whatever is sent
+        let err = authorize_admin(&with_token("secret"), None).unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        let missing = TestRequest::default().to_http_request();
+        assert_eq!(authorize_admin(&missing, Some("secret")).unwrap_err().status, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            authorize_admin(&with_token("secreT"), Some("secret")).unwrap_err().status,
+            StatusCode::UNAUTHORIZED
+        );
+        assert!(authorize_admin(&with_token("secret"), Some("secret")).is_ok());
+    }
+
+    #[actix_web::test]
+    async fn test_false_positive_feedback_requires_admin() {
+        use crate::types::{Alert, AlertSeverity, AttackPattern, SCHEMA_VERSION};
+        use actix_web::test;
+
+        let config = MonitorConfig { admin_token: Some("secret".to_string()), ..MonitorConfig::default() };
+        let engine = Arc::new(MonitoringEngine::new(config));
+        for id in ["fp-1", "fp-2"] {
+            engine
+                .alert_manager
+                .trigger_alert(Alert {
+                    schema_version: SCHEMA_VERSION,
+                    id: id.to_string(),
+                    timestamp: 1234567890,
+                    chain: "test-chain".to_string(),
+                    severity: AlertSeverity::High,
+                    pattern: AttackPattern::FlashLoan,
+                    description: "Test alert".to_string(),
+                    transaction_hash: None,
+                    block_number: Some(100),
+                    metadata: HashMap::new(),
+                    recommended_actions: vec![],
+                    acknowledged: false,
+                })
+                .await;
+        }
+        let app = test::init_service(App::new().service(api_scope(engine.clone(), Instant::now()))).await;
+
+        // A verdict without the admin token changes nothing
+        let req = test::TestRequest::post()
+            .uri("/api/alerts/fp-1/acknowledge?false_positive=true")
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::UNAUTHORIZED);
+        assert!(!engine.alert_manager.get_alert("fp-1").await.unwrap().acknowledged);
+
+        let req = test::TestRequest::post()
+            .uri("/api/alerts/fp-1/acknowledge?false_positive=true")
+            .insert_header((header::AUTHORIZATION, "Bearer secret"))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+        assert!(engine.alert_manager.get_alert("fp-1").await.unwrap().acknowledged);
+
+        // Plain acknowledgments stay open to every client
+        let req = test::TestRequest::post().uri("/api/alerts/fp-2/acknowledge").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_alert_pages_with_extreme_offsets() {
+        use actix_web::test;
+
+        let engine = Arc::new(MonitoringEngine::new(MonitorConfig::default()));
+        let app = test::init_service(App::new().service(api_scope(engine, Instant::now()))).await;
+        let get = |uri: String| test::TestRequest::get().uri(&uri).to_request();
+
+        // The largest accepted offset returns an empty page; larger ones are rejected
+        let resp = test::call_service(&app, get(format!("/api/alerts?offset={}", i64::MAX))).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let alerts: Vec<serde_json::Value> = test::read_body_json(resp).await;
+        assert!(alerts.is_empty());
+
+        let resp = test::call_service(&app, get(format!("/api/alerts?offset={}", usize::MAX))).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = test::call_service(&app, get(format!("/api/alerts?limit={}", MAX_PAGE_SIZE + 1))).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_graph_rejects_invalid_hours() {
+        use actix_web::test;
+
+        let engine = Arc::new(MonitoringEngine::new(MonitorConfig::default()));
+        let app = test::init_service(App::new().service(api_scope(engine, Instant::now()))).await;
+        let status = |uri: String| {
+            let req = test::TestRequest::get().uri(&uri).to_request();
+            let app = &app;
+            async move { test::call_service(app, req).await.status() }
+        };
+
+        for hours in ["abc".to_string(), "0".to_string(), (MAX_GRAPH_HOURS + 1).to_string()] {
+            assert_eq!(status(format!("/api/graph?hours={}", hours)).await, StatusCode::BAD_REQUEST);
+        }
+
+        // Valid windows reach the database check
+        assert_eq!(
+            status(format!("/api/graph?hours={}", MAX_GRAPH_HOURS)).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+
+    #[test]
+    fn test_engine_error_status_codes() {
+        let missing = crate::Error::IoError(std::io::Error::from(std::io::ErrorKind::NotFound));
+        assert_eq!(ApiError::from(missing).status, StatusCode::NOT_FOUND);
+
+        let config = crate::Error::ConfigError("bad endpoint".to_string());
+        assert_eq!(ApiError::from(config).status, StatusCode::BAD_REQUEST);
+
+        let node = crate::Error::ConnectionError("refused".to_string());
+        assert_eq!(ApiError::from(node).status, StatusCode::SERVICE_UNAVAILABLE);
+    }
 }
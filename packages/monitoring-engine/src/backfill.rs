@@ -0,0 +1,192 @@
+//! Resumable historical backfill
+//!
+//! A backfill runs a fixed block range through the detectors. After every
+//! fully processed block the range's checkpoint is saved to the database,
+//! so restarting a backfill over the same range continues at the next
+//! block. A block interrupted midway is processed again from the start;
+//! deterministic detection ids keep that from duplicating detections.
+
+use crate::database::{models::BackfillCheckpoint, DatabaseClient};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tokio::sync::RwLock;
+
+/// Progress of a backfill range
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackfillStatus {
+    pub chain: String,
+    pub from_block: u64,
+    pub to_block: u64,
+    /// Last block whose transactions were all processed
+    pub last_processed_block: Option<u64>,
+    /// Whether a backfill over this range is in progress in this process
+    pub running: bool,
+}
+
+impl BackfillStatus {
+    /// Whether every block of the range has been processed
+    pub fn is_complete(&self) -> bool {
+        self.last_processed_block == Some(self.to_block)
+    }
+}
+
+impl From<BackfillCheckpoint> for BackfillStatus {
+    fn from(checkpoint: BackfillCheckpoint) -> Self {
+        Self {
+            chain: checkpoint.chain,
+            from_block: checkpoint.from_block as u64,
+            to_block: checkpoint.to_block as u64,
+            last_processed_block: Some(checkpoint.last_processed_block as u64),
+            running: false,
+        }
+    }
+}
+
+/// Process `from_block..=to_block` one block at a time
+///
+/// Resumes after the range's stored checkpoint when a database is given.
+/// `status` is kept up to date after every block. Stops at the first block
+/// `process_block` fails on, leaving the checkpoint at the block before it.
+pub async fn run_backfill<F, Fut>(
+    database: Option<&DatabaseClient>,
+    chain: &str,
+    from_block: u64,
+    to_block: u64,
+    status: &RwLock<Option<BackfillStatus>>,
+    mut process_block: F,
+) -> Result<BackfillStatus>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    if from_block > to_block {
+        return Err(Error::ConfigError(format!(
+            "Backfill range start {} is after end {}",
+            from_block, to_block
+        )));
+    }
+
+    let checkpoint = match database {
+        Some(db) => db
+            .load_backfill_checkpoint(chain, from_block as i64, to_block as i64)
+            .await
+            .map_err(|e| Error::DatabaseError(e.to_string()))?,
+        None => None,
+    };
+
+    let mut current = BackfillStatus {
+        chain: chain.to_string(),
+        from_block,
+        to_block,
+        last_processed_block: checkpoint.map(|c| c.last_processed_block as u64),
+        running: true,
+    };
+    let start = current.last_processed_block.map_or(from_block, |last| last + 1);
+
+    if current.last_processed_block.is_some() {
+        tracing::info!(
+            "Resuming backfill of {} blocks #{}..=#{} at #{}",
+            chain,
+            from_block,
+            to_block,
+            start
+        );
+    }
+    *status.write().await = Some(current.clone());
+
+    for number in start..=to_block {
+        let step = match process_block(number).await {
+            Ok(()) => save_checkpoint(database, &current, number).await,
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = step {
+            tracing::warn!("Backfill of {} stopped at block #{}: {}", chain, number, e);
+            current.running = false;
+            *status.write().await = Some(current);
+            return Err(e);
+        }
+
+        current.last_processed_block = Some(number);
+        *status.write().await = Some(current.clone());
+    }
+
+    current.running = false;
+    *status.write().await = Some(current.clone());
+    tracing::info!("Backfill of {} blocks #{}..=#{} complete", chain, from_block, to_block);
+
+    Ok(current)
+}
+
+async fn save_checkpoint(
+    database: Option<&DatabaseClient>,
+    status: &BackfillStatus,
+    block_number: u64,
+) -> Result<()> {
+    let Some(db) = database else {
+        return Ok(());
+    };
+
+    db.save_backfill_checkpoint(&BackfillCheckpoint {
+        chain: status.chain.clone(),
+        from_block: status.from_block as i64,
+        to_block: status.to_block as i64,
+        last_processed_block: block_number as i64,
+        updated_at: chrono::Utc::now(),
+    })
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_backfill_stops_at_failing_block() {
+        let status = RwLock::new(None);
+        let mut seen = Vec::new();
+
+        let result = run_backfill(None, "westend", 10, 14, &status, |number| {
+            seen.push(number);
+            async move {
+                if number == 12 {
+                    Err(Error::ConnectionError("node went away".to_string()))
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(seen, vec![10, 11, 12]);
+
+        let stopped = status.read().await.clone().unwrap();
+        assert_eq!(stopped.last_processed_block, Some(11));
+        assert!(!stopped.running);
+        assert!(!stopped.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_completes_range() {
+        let status = RwLock::new(None);
+
+        let done = run_backfill(None, "westend", 5, 7, &status, |_| async { Ok(()) })
+            .await
+            .unwrap();
+
+        assert!(done.is_complete());
+        assert_eq!(status.read().await.as_ref(), Some(&done));
+    }
+
+    #[tokio::test]
+    async fn test_backfill_rejects_inverted_range() {
+        let status = RwLock::new(None);
+        let result = run_backfill(None, "westend", 9, 3, &status, |_| async { Ok(()) }).await;
+
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+        assert!(status.read().await.is_none());
+    }
+}
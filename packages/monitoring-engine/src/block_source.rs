@@ -0,0 +1,316 @@
+//! Sources of finalized blocks for the block pipeline
+//!
+//! The engine reads blocks through [`BlockSource`] rather than a
+//! `subxt::OnlineClient` directly. [`SubxtBlockSource`] follows a live node,
+//! reading it through a [`BlockReader`] and matching each transaction with
+//! the events it emitted; [`MockBlockSource`] replays canned blocks, so the
+//! whole pipeline (extraction, detectors, statistics, alerts, database
+//! writes) can be exercised without a chain.
+
+use crate::transaction::TransactionExtractor;
+use crate::types::{ChainEvent, ParsedTransaction, TransactionContext};
+use crate::{Error, Result};
+use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use std::sync::Arc;
+use subxt::config::substrate::H256;
+
+/// Where the engine gets finalized blocks and their transactions from
+#[async_trait]
+pub trait BlockSource: Send + Sync {
+    /// Stream of finalized blocks as `(block number, block hash)`, in chain order
+    async fn subscribe_finalized(&self) -> Result<BoxStream<'static, Result<(u64, H256)>>>;
+
+    /// Transactions of a block, with their events and state changes when known
+    async fn extract_transactions(&self, hash: H256, number: u64) -> Result<Vec<TransactionContext>>;
+
+    /// Raw SCALE-encoded extrinsics of a block, captured when decoding fails
+    async fn raw_extrinsics(&self, hash: H256) -> Result<Vec<Vec<u8>>>;
+}
+
+/// Block data as read from a node, before events are matched to transactions
+#[async_trait]
+pub trait BlockReader: Send + Sync {
+    /// Stream of finalized blocks as `(block number, block hash)`, in chain order
+    async fn subscribe_finalized(&self) -> Result<BoxStream<'static, Result<(u64, H256)>>>;
+
+    /// Transactions of a block, without their events
+    async fn transactions(&self, hash: H256, number: u64) -> Result<Vec<ParsedTransaction>>;
+
+    /// All events of a block, in emission order
+    async fn events(&self, hash: H256, number: u64) -> Result<Vec<ChainEvent>>;
+
+    /// Raw SCALE-encoded extrinsics of a block
+    async fn raw_extrinsics(&self, hash: H256) -> Result<Vec<Vec<u8>>>;
+}
+
+#[async_trait]
+impl BlockReader for TransactionExtractor {
+    async fn subscribe_finalized(&self) -> Result<BoxStream<'static, Result<(u64, H256)>>> {
+        let blocks = self
+            .client()
+            .blocks()
+            .subscribe_finalized()
+            .await
+            .map_err(|e| Error::SubscriptionError(format!("Failed to subscribe to blocks: {}", e)))?
+            .map(|block_result| {
+                block_result
+                    .map(|block| (block.number() as u64, block.hash()))
+                    .map_err(|e| Error::SubscriptionError(format!("Block stream error: {}", e)))
+            });
+
+        Ok(blocks.boxed())
+    }
+
+    async fn transactions(&self, hash: H256, number: u64) -> Result<Vec<ParsedTransaction>> {
+        self.extract_from_block(hash, number)
+            .await
+            .map_err(|e| Error::ParseError(format!("{:#}", e)))
+    }
+
+    async fn events(&self, hash: H256, number: u64) -> Result<Vec<ChainEvent>> {
+        self.extract_events(hash, number)
+            .await
+            .map_err(|e| Error::ParseError(format!("{:#}", e)))
+    }
+
+    async fn raw_extrinsics(&self, hash: H256) -> Result<Vec<Vec<u8>>> {
+        TransactionExtractor::raw_extrinsics(self, hash)
+            .await
+            .map_err(|e| Error::ConnectionError(format!("{:#}", e)))
+    }
+}
+
+/// Blocks from a live node
+///
+/// Each transaction is handed to the detectors with the events it emitted,
+/// unless events are disabled (see [`Self::with_events`]).
+pub struct SubxtBlockSource<R: BlockReader = TransactionExtractor> {
+    reader: R,
+    events: bool,
+}
+
+impl SubxtBlockSource {
+    pub fn new(client: subxt::OnlineClient<subxt::PolkadotConfig>) -> Self {
+        Self::with_reader(TransactionExtractor::new(Arc::new(client)))
+    }
+}
+
+impl<R: BlockReader> SubxtBlockSource<R> {
+    /// Read blocks through `reader` instead of a node client
+    pub fn with_reader(reader: R) -> Self {
+        Self { reader, events: true }
+    }
+
+    /// Whether to fetch each block's events and attach them to its
+    /// transactions (on by default)
+    pub fn with_events(mut self, events: bool) -> Self {
+        self.events = events;
+        self
+    }
+}
+
+#[async_trait]
+impl<R: BlockReader> BlockSource for SubxtBlockSource<R> {
+    async fn subscribe_finalized(&self) -> Result<BoxStream<'static, Result<(u64, H256)>>> {
+        self.reader.subscribe_finalized().await
+    }
+
+    async fn extract_transactions(&self, hash: H256, number: u64) -> Result<Vec<TransactionContext>> {
+        let transactions = self.reader.transactions(hash, number).await?;
+        if !self.events {
+            return Ok(transactions.into_iter().map(TransactionContext::from).collect());
+        }
+
+        let events = self.reader.events(hash, number).await?;
+        Ok(transactions
+            .into_iter()
+            .map(|transaction| TransactionExtractor::create_context(transaction, &events))
+            .collect())
+    }
+
+    async fn raw_extrinsics(&self, hash: H256) -> Result<Vec<Vec<u8>>> {
+        self.reader.raw_extrinsics(hash).await
+    }
+}
+
+/// A canned block served by [`MockBlockSource`]
+#[derive(Debug, Clone)]
+pub struct MockBlock {
+    pub number: u64,
+    pub hash: H256,
+    /// Transactions of the block; `None` simulates a block that fails to decode
+    pub transactions: Option<Vec<TransactionContext>>,
+}
+
+/// Replays canned blocks, then ends the stream
+///
+/// For tests and simulations that drive the real engine without a node.
+#[derive(Debug, Clone, Default)]
+pub struct MockBlockSource {
+    blocks: Vec<MockBlock>,
+}
+
+impl MockBlockSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mock block hash of a block number
+    pub fn block_hash(number: u64) -> H256 {
+        H256::from_low_u64_be(number)
+    }
+
+    /// Append a block with these transactions
+    pub fn with_block(mut self, number: u64, transactions: Vec<TransactionContext>) -> Self {
+        self.blocks.push(MockBlock {
+            number,
+            hash: Self::block_hash(number),
+            transactions: Some(transactions),
+        });
+        self
+    }
+
+    /// Append a block whose transactions cannot be decoded
+    pub fn with_undecodable_block(mut self, number: u64) -> Self {
+        self.blocks.push(MockBlock {
+            number,
+            hash: Self::block_hash(number),
+            transactions: None,
+        });
+        self
+    }
+
+    fn block(&self, hash: H256) -> Result<&MockBlock> {
+        self.blocks
+            .iter()
+            .find(|block| block.hash == hash)
+            .ok_or_else(|| Error::ConnectionError(format!("Unknown block 0x{}", hex::encode(hash.0))))
+    }
+}
+
+#[async_trait]
+impl BlockSource for MockBlockSource {
+    async fn subscribe_finalized(&self) -> Result<BoxStream<'static, Result<(u64, H256)>>> {
+        let blocks: Vec<_> = self.blocks.iter().map(|block| Ok((block.number, block.hash))).collect();
+        Ok(futures::stream::iter(blocks).boxed())
+    }
+
+    async fn extract_transactions(&self, hash: H256, number: u64) -> Result<Vec<TransactionContext>> {
+        self.block(hash)?
+            .transactions
+            .clone()
+            .ok_or_else(|| Error::ParseError(format!("Cannot decode extrinsics of block #{}", number)))
+    }
+
+    async fn raw_extrinsics(&self, hash: H256) -> Result<Vec<Vec<u8>>> {
+        // Canned blocks carry their transactions' call arguments as the raw bytes
+        Ok(self
+            .block(hash)?
+            .transactions
+            .iter()
+            .flatten()
+            .map(|ctx| ctx.transaction.args.clone())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reader serving one block's transactions and events
+    struct CannedReader {
+        transactions: Vec<ParsedTransaction>,
+        events: Vec<ChainEvent>,
+    }
+
+    #[async_trait]
+    impl BlockReader for CannedReader {
+        async fn subscribe_finalized(&self) -> Result<BoxStream<'static, Result<(u64, H256)>>> {
+            Ok(futures::stream::empty().boxed())
+        }
+
+        async fn transactions(&self, _hash: H256, _number: u64) -> Result<Vec<ParsedTransaction>> {
+            Ok(self.transactions.clone())
+        }
+
+        async fn events(&self, _hash: H256, _number: u64) -> Result<Vec<ChainEvent>> {
+            Ok(self.events.clone())
+        }
+
+        async fn raw_extrinsics(&self, _hash: H256) -> Result<Vec<Vec<u8>>> {
+            Ok(vec![])
+        }
+    }
+
+    fn context(block_number: u64) -> TransactionContext {
+        TransactionContext::from(ParsedTransaction {
+            hash: format!("0x{:02x}", block_number),
+            block_number,
+            block_hash: format!("0x{}", hex::encode(MockBlockSource::block_hash(block_number).0)),
+            index: 0,
+            caller: "alice".to_string(),
+            pallet: "Balances".to_string(),
+            call: "transfer".to_string(),
+            args: vec![],
+            signature: None,
+            nonce: None,
+            timestamp: 1_700_000_000,
+            success: true,
+            decoded_call: Default::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_transactions_carry_their_events() {
+        let mut second = context(7).transaction;
+        second.index = 1;
+        let reader = CannedReader {
+            transactions: vec![context(7).transaction, second],
+            events: vec![
+                ChainEvent::new("ParachainSystem", "ValidationFunctionStored").at(7, 0),
+                ChainEvent::new("Balances", "Transfer").at(7, 1).with_extrinsic_index(0),
+                ChainEvent::new("Balances", "Withdraw").at(7, 2).with_extrinsic_index(1),
+                ChainEvent::new("System", "ExtrinsicSuccess").at(7, 3).with_extrinsic_index(1),
+            ],
+        };
+        let source = SubxtBlockSource::with_reader(reader);
+
+        let contexts = source.extract_transactions(MockBlockSource::block_hash(7), 7).await.unwrap();
+        let names = |ctx: &TransactionContext| -> Vec<String> {
+            ctx.events.iter().map(|event| event.event_name.clone()).collect()
+        };
+        assert_eq!(names(&contexts[0]), vec!["Transfer"]);
+        assert_eq!(names(&contexts[1]), vec!["Withdraw", "ExtrinsicSuccess"]);
+
+        let source = source.with_events(false);
+        let contexts = source.extract_transactions(MockBlockSource::block_hash(7), 7).await.unwrap();
+        assert!(contexts.iter().all(|ctx| ctx.events.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_mock_replays_blocks_in_order() {
+        let source = MockBlockSource::new()
+            .with_block(1, vec![context(1)])
+            .with_undecodable_block(2)
+            .with_block(3, vec![]);
+
+        let blocks: Vec<_> = source
+            .subscribe_finalized()
+            .await
+            .unwrap()
+            .map(|block| block.unwrap().0)
+            .collect()
+            .await;
+        assert_eq!(blocks, vec![1, 2, 3]);
+
+        let first = source.extract_transactions(MockBlockSource::block_hash(1), 1).await.unwrap();
+        assert_eq!(first[0].transaction.hash, "0x01");
+        assert!(matches!(
+            source.extract_transactions(MockBlockSource::block_hash(2), 2).await,
+            Err(Error::ParseError(_))
+        ));
+        assert!(source.extract_transactions(MockBlockSource::block_hash(9), 9).await.is_err());
+    }
+}
@@ -1,12 +1,32 @@
 //! Configuration persistence module
 //!
-//! Handles saving and loading chain configuration from disk
+//! Handles saving and loading chain configuration from disk, and custom
+//! chain presets that extend the built-in ones.
+//!
+//! Custom presets are read from `chain_presets.json` (or the file named by
+//! `CHAIN_PRESETS_FILE`), keyed by chain name:
+//!
+//! ```json
+//! {
+//!   "my-parachain": {
+//!     "endpoint": "wss://rpc.my-parachain.io",
+//!     "display_name": "My Parachain",
+//!     "description": "Production collators"
+//!   }
+//! }
+//! ```
+//!
+//! A custom preset with the same name as a built-in one replaces it.
 
-use crate::{MonitorConfig, Result, Error};
-use std::path::PathBuf;
+use crate::{ChainInfo, MonitorConfig, Result, Error};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Deserialize, Serialize};
 
+/// Default custom chain presets file
+const DEFAULT_PRESETS_FILE: &str = "chain_presets.json";
+
 /// Saved configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SavedConfig {
@@ -33,6 +53,70 @@ pub fn get_config_path() -> PathBuf {
     PathBuf::from("chain_config.json")
 }
 
+/// A custom chain preset as written in the presets file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainPreset {
+    pub endpoint: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Get the custom chain presets file path
+pub fn get_presets_path() -> PathBuf {
+    std::env::var("CHAIN_PRESETS_FILE")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_PRESETS_FILE))
+}
+
+/// Load custom chain presets from a file
+pub fn load_chain_presets_from(path: &Path) -> Result<Vec<ChainInfo>> {
+    let contents = fs::read_to_string(path)?;
+    let presets: BTreeMap<String, ChainPreset> = serde_json::from_str(&contents)
+        .map_err(|e| Error::ConfigError(format!("Failed to parse chain presets: {}", e)))?;
+
+    presets
+        .into_iter()
+        .map(|(name, preset)| {
+            if !preset.endpoint.starts_with("ws://") && !preset.endpoint.starts_with("wss://") {
+                return Err(Error::ConfigError(format!(
+                    "Chain preset '{}' endpoint must start with ws:// or wss://, got '{}'",
+                    name, preset.endpoint
+                )));
+            }
+            Ok(ChainInfo {
+                display_name: preset.display_name.unwrap_or_else(|| name.clone()),
+                description: preset
+                    .description
+                    .unwrap_or_else(|| format!("Custom preset for {}", name)),
+                endpoint: preset.endpoint,
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Load custom chain presets, if a presets file exists
+///
+/// A malformed file is logged and ignored so built-in presets keep working.
+pub fn load_chain_presets() -> Vec<ChainInfo> {
+    let path = get_presets_path();
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    match load_chain_presets_from(&path) {
+        Ok(presets) => presets,
+        Err(e) => {
+            tracing::error!("Ignoring chain presets in {:?}: {}", path, e);
+            Vec::new()
+        }
+    }
+}
+
 /// Save chain configuration to disk
 pub fn save_chain_config(chain_name: &str) -> Result<()> {
     let config = SavedConfig::new(chain_name.to_string());
@@ -107,6 +191,48 @@ mod tests {
         assert!(config.timestamp > 0);
     }
 
+    #[test]
+    fn test_custom_preset_resolves_by_name() {
+        let path = std::env::temp_dir().join(format!("chain-presets-{}.json", std::process::id()));
+        fs::write(
+            &path,
+            r#"{
+                "my-parachain": { "endpoint": "wss://rpc.my-parachain.io", "display_name": "My Parachain" },
+                "polkadot": { "endpoint": "wss://polkadot.private-rpc.io" }
+            }"#,
+        )
+        .unwrap();
+
+        let presets = load_chain_presets_from(&path).unwrap();
+        fs::remove_file(&path).ok();
+
+        let config = MonitorConfig::from_chain_name_with("my-parachain", &presets).unwrap();
+        assert_eq!(config.ws_endpoint, "wss://rpc.my-parachain.io");
+        assert_eq!(config.chain_name, "my-parachain");
+
+        // Custom presets override built-ins of the same name
+        let polkadot = MonitorConfig::from_chain_name_with("polkadot", &presets).unwrap();
+        assert_eq!(polkadot.ws_endpoint, "wss://polkadot.private-rpc.io");
+
+        let chains = MonitorConfig::available_chains_with(&presets);
+        assert_eq!(chains.len(), 5);
+        let custom = chains.iter().find(|c| c.name == "my-parachain").unwrap();
+        assert_eq!(custom.display_name, "My Parachain");
+
+        assert!(MonitorConfig::from_chain_name_with("unknown-chain", &presets).is_none());
+    }
+
+    #[test]
+    fn test_invalid_preset_endpoint_rejected() {
+        let path = std::env::temp_dir().join(format!("chain-presets-bad-{}.json", std::process::id()));
+        fs::write(&path, r#"{ "bad": { "endpoint": "https://rpc.example.io" } }"#).unwrap();
+
+        let result = load_chain_presets_from(&path);
+        fs::remove_file(&path).ok();
+
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = SavedConfig::new("polkadot".to_string());
@@ -1,17 +1,44 @@
 //! Substrate node connection management
 
-use crate::{Error, Result};
-use subxt::{OnlineClient, PolkadotConfig};
+use crate::{Error, ErrorKind, Result};
+use subxt::{
+    backend::{legacy::LegacyRpcMethods, rpc::{RpcClient, RpcParams}, BlockRef},
+    config::substrate::H256,
+    OnlineClient, PolkadotConfig,
+};
 use tokio::sync::RwLock;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::Instant;
+
+/// Snapshot of the connection for operators
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionStatus {
+    pub connected: bool,
+    pub endpoint: String,
+    /// When the current connection was established
+    pub connected_since: Option<Instant>,
+    /// Successful connections after the first one
+    pub reconnect_count: u64,
+}
+
+/// Connection lifetime tracked for [`ConnectionStatus`]
+#[derive(Debug, Default)]
+struct LinkState {
+    connected_since: Option<Instant>,
+    ever_connected: bool,
+    reconnect_count: u64,
+}
 
 /// Connection manager for Substrate nodes
 pub struct ConnectionManager {
     endpoint: String,
     client: Arc<RwLock<Option<OnlineClient<PolkadotConfig>>>>,
+    /// Raw RPC client behind `client`, for calls subxt does not wrap (block hash by number)
+    rpc: Arc<RwLock<Option<RpcClient>>>,
     reconnect_attempts: Arc<AtomicU32>,
     should_reconnect: Arc<AtomicBool>,
+    link: Arc<RwLock<LinkState>>,
 }
 
 impl ConnectionManager {
@@ -20,8 +47,10 @@ impl ConnectionManager {
         Self {
             endpoint,
             client: Arc::new(RwLock::new(None)),
+            rpc: Arc::new(RwLock::new(None)),
             reconnect_attempts: Arc::new(AtomicU32::new(0)),
             should_reconnect: Arc::new(AtomicBool::new(true)),
+            link: Arc::new(RwLock::new(LinkState::default())),
         }
     }
 
@@ -29,17 +58,28 @@ impl ConnectionManager {
     pub async fn connect(&self) -> Result<()> {
         tracing::info!("Connecting to Substrate node at {}", self.endpoint);
 
+        if !self.endpoint.starts_with("ws://") && !self.endpoint.starts_with("wss://") {
+            return Err(Error::ConfigError(format!(
+                "Endpoint must start with ws:// or wss://, got '{}'",
+                self.endpoint
+            )));
+        }
+
         // Attempt to connect with a timeout
-        let client = tokio::time::timeout(
-            std::time::Duration::from_secs(10),
-            OnlineClient::<PolkadotConfig>::from_url(&self.endpoint)
-        )
+        let (rpc, client) = tokio::time::timeout(std::time::Duration::from_secs(10), async {
+            let rpc = RpcClient::from_url(&self.endpoint).await?;
+            let client = OnlineClient::<PolkadotConfig>::from_rpc_client(rpc.clone()).await?;
+            Ok::<_, subxt::Error>((rpc, client))
+        })
         .await
         .map_err(|_| Error::ConnectionError(format!("Connection timeout to {}", self.endpoint)))?
         .map_err(|e| Error::ConnectionError(format!("Failed to connect: {}", e)))?;
 
         let mut client_lock = self.client.write().await;
         *client_lock = Some(client);
+        drop(client_lock);
+        *self.rpc.write().await = Some(rpc);
+        self.mark_connected().await;
 
         // Reset reconnect attempts on successful connection
         self.reconnect_attempts.store(0, Ordering::SeqCst);
@@ -49,6 +89,9 @@ impl ConnectionManager {
     }
 
     /// Connect with automatic retry using exponential backoff
+    ///
+    /// Only transient failures are retried; e.g. a malformed endpoint fails
+    /// immediately.
     pub async fn connect_with_retry(&self, max_attempts: u32) -> Result<()> {
         let mut attempt = 0;
 
@@ -59,6 +102,11 @@ impl ConnectionManager {
                     attempt += 1;
                     self.reconnect_attempts.store(attempt, Ordering::SeqCst);
 
+                    if e.kind() != ErrorKind::Transient {
+                        tracing::error!("Not retrying connection: {}", e);
+                        return Err(e);
+                    }
+
                     if attempt >= max_attempts {
                         tracing::error!(
                             "Failed to connect after {} attempts: {}",
@@ -100,6 +148,31 @@ impl ConnectionManager {
         self.should_reconnect.store(enabled, Ordering::SeqCst);
     }
 
+    /// Current endpoint, connection age and number of reconnects
+    pub async fn status(&self) -> ConnectionStatus {
+        let link = self.link.read().await;
+        ConnectionStatus {
+            connected: link.connected_since.is_some(),
+            endpoint: self.endpoint.clone(),
+            connected_since: link.connected_since,
+            reconnect_count: link.reconnect_count,
+        }
+    }
+
+    /// Record an established connection, counting it as a reconnect if not the first
+    async fn mark_connected(&self) {
+        let mut link = self.link.write().await;
+        if link.ever_connected {
+            link.reconnect_count += 1;
+        }
+        link.ever_connected = true;
+        link.connected_since = Some(Instant::now());
+    }
+
+    async fn mark_disconnected(&self) {
+        self.link.write().await.connected_since = None;
+    }
+
     /// Check if connected
     pub async fn is_connected(&self) -> bool {
         self.client.read().await.is_some()
@@ -110,6 +183,70 @@ impl ConnectionManager {
         self.client.read().await.clone()
     }
 
+    /// Fetch a raw storage value at a given block
+    ///
+    /// `key` is the fully hashed storage key. Returns `None` if the entry is empty.
+    pub async fn storage_at(&self, block_hash: H256, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let client = self.get_client().await
+            .ok_or_else(|| Error::ConnectionError("Not connected to node".to_string()))?;
+
+        client
+            .storage()
+            .at(BlockRef::from_hash(block_hash))
+            .fetch_raw(key.to_vec())
+            .await
+            .map_err(|e| Error::ConnectionError(format!("Storage query failed: {}", e)))
+    }
+
+    /// Get the parent hash of a block
+    pub async fn parent_hash(&self, block_hash: H256) -> Result<H256> {
+        let client = self.get_client().await
+            .ok_or_else(|| Error::ConnectionError("Not connected to node".to_string()))?;
+
+        let block = client
+            .blocks()
+            .at(BlockRef::from_hash(block_hash))
+            .await
+            .map_err(|e| Error::ConnectionError(format!("Failed to get block: {}", e)))?;
+
+        Ok(block.header().parent_hash)
+    }
+
+    /// Get the hash of the canonical block at `number`
+    ///
+    /// Returns `None` if the node has no block at that height yet.
+    pub async fn block_hash(&self, number: u64) -> Result<Option<H256>> {
+        let rpc = self.rpc.read().await.clone()
+            .ok_or_else(|| Error::ConnectionError("Not connected to node".to_string()))?;
+
+        LegacyRpcMethods::<PolkadotConfig>::new(rpc)
+            .chain_get_block_hash(Some(number.into()))
+            .await
+            .map_err(|e| Error::ConnectionError(format!("Failed to get block hash: {}", e)))
+    }
+
+    /// SCALE-encoded extrinsics currently in the node's transaction pool
+    ///
+    /// Nodes expose no subscription to pool contents, so callers poll this
+    /// (`author_pendingExtrinsics`).
+    pub async fn pending_extrinsics(&self) -> Result<Vec<Vec<u8>>> {
+        let rpc = self.rpc.read().await.clone()
+            .ok_or_else(|| Error::ConnectionError("Not connected to node".to_string()))?;
+
+        let pending: Vec<String> = rpc
+            .request("author_pendingExtrinsics", RpcParams::new())
+            .await
+            .map_err(|e| Error::ConnectionError(format!("Failed to get pending extrinsics: {}", e)))?;
+
+        pending
+            .iter()
+            .map(|extrinsic| {
+                hex::decode(extrinsic.trim_start_matches("0x"))
+                    .map_err(|e| Error::ParseError(format!("Invalid pending extrinsic hex: {}", e)))
+            })
+            .collect()
+    }
+
     /// Disconnect from the node
     pub async fn disconnect(&self) {
         // Disable automatic reconnection when explicitly disconnecting
@@ -117,6 +254,8 @@ impl ConnectionManager {
 
         let mut client_lock = self.client.write().await;
         *client_lock = None;
+        *self.rpc.write().await = None;
+        self.mark_disconnected().await;
         tracing::info!("Disconnected from Substrate node");
     }
 }
@@ -133,6 +272,20 @@ mod tests {
         assert_eq!(manager.get_reconnect_attempts(), 0);
     }
 
+    #[tokio::test]
+    async fn test_storage_at_requires_connection() {
+        let manager = ConnectionManager::new("ws://127.0.0.1:9944".to_string());
+        let result = manager.storage_at(H256::zero(), &[0u8; 32]).await;
+        assert!(matches!(result, Err(Error::ConnectionError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_block_hash_requires_connection() {
+        let manager = ConnectionManager::new("ws://127.0.0.1:9944".to_string());
+        let result = manager.block_hash(1).await;
+        assert!(matches!(result, Err(Error::ConnectionError(_))));
+    }
+
     #[tokio::test]
     async fn test_connection_to_invalid_endpoint() {
         let manager = ConnectionManager::new("ws://127.0.0.1:9999".to_string());
@@ -153,6 +306,42 @@ mod tests {
         assert_eq!(manager.get_reconnect_attempts(), 3);
     }
 
+    #[tokio::test]
+    async fn test_fatal_errors_are_not_retried() {
+        let manager = ConnectionManager::new("http://127.0.0.1:9944".to_string());
+
+        let result = manager.connect_with_retry(3).await;
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+
+        // Gave up after the first attempt instead of backing off
+        assert_eq!(manager.get_reconnect_attempts(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_status_counts_reconnects() {
+        let manager = ConnectionManager::new("ws://127.0.0.1:9944".to_string());
+
+        let status = manager.status().await;
+        assert!(!status.connected);
+        assert_eq!(status.endpoint, "ws://127.0.0.1:9944");
+        assert_eq!(status.connected_since, None);
+
+        manager.mark_connected().await;
+        let first = manager.status().await;
+        assert!(first.connected);
+        assert_eq!(first.reconnect_count, 0);
+
+        // Connection drops and comes back
+        manager.mark_disconnected().await;
+        assert!(!manager.status().await.connected);
+        manager.mark_connected().await;
+
+        let status = manager.status().await;
+        assert!(status.connected);
+        assert_eq!(status.reconnect_count, 1);
+        assert!(status.connected_since >= first.connected_since);
+    }
+
     #[tokio::test]
     async fn test_disable_reconnect_on_disconnect() {
         let manager = ConnectionManager::new("ws://127.0.0.1:9944".to_string());
@@ -1,4 +1,5 @@
 pub mod models;
+pub mod retry;
 
 use anyhow::Result;
 use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
@@ -7,6 +8,20 @@ use tracing::{error, info};
 
 use models::*;
 
+/// Tables the engine reads and writes
+pub const REQUIRED_TABLES: &[&str] = &[
+    "transactions",
+    "detections",
+    "ml_features",
+    "alerts",
+    "detector_state",
+    "backfill_checkpoints",
+    "detection_feedback",
+];
+
+/// Continuous aggregates queried by the stats endpoints
+pub const REQUIRED_AGGREGATES: &[&str] = &["detector_stats_hourly", "tx_volume_hourly"];
+
 /// Database client for TimescaleDB operations
 pub struct DatabaseClient {
     pool: Pool,
@@ -17,6 +32,21 @@ impl DatabaseClient {
     pub async fn new(database_url: &str, max_connections: usize) -> Result<Self> {
         info!("Connecting to TimescaleDB at {}", database_url);
 
+        let db = Self::connect_lazy(database_url, max_connections)?;
+
+        // Test the connection
+        let client = db.pool.get().await?;
+        client.query_one("SELECT 1", &[]).await?;
+        info!("Successfully connected to TimescaleDB");
+
+        Ok(db)
+    }
+
+    /// Create a database client without opening a connection
+    ///
+    /// Connections are established on first use, so this succeeds even when
+    /// the database is not reachable yet.
+    pub fn connect_lazy(database_url: &str, max_connections: usize) -> Result<Self> {
         // Parse the database URL
         let config = database_url.parse::<tokio_postgres::Config>()?;
 
@@ -32,11 +62,6 @@ impl DatabaseClient {
             .build()
             .map_err(|e| anyhow::anyhow!("Failed to create connection pool: {}", e))?;
 
-        // Test the connection
-        let client = pool.get().await?;
-        client.query_one("SELECT 1", &[]).await?;
-        info!("Successfully connected to TimescaleDB");
-
         Ok(Self { pool })
     }
 
@@ -47,8 +72,9 @@ impl DatabaseClient {
         let stmt = client
             .prepare(
                 "INSERT INTO transactions
-                (timestamp, tx_hash, block_number, chain, pallet, call_name, caller, success, args, gas_used, fee_paid)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                (timestamp, tx_hash, block_number, chain, pallet, call_name, caller, success, args, gas_used, fee_paid,
+                 destination, value)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, CAST($13::TEXT AS NUMERIC))
                 ON CONFLICT (tx_hash) DO NOTHING",
             )
             .await?;
@@ -68,6 +94,8 @@ impl DatabaseClient {
                     &tx.args,
                     &tx.gas_used,
                     &tx.fee_paid,
+                    &tx.destination,
+                    &tx.value,
                 ],
             )
             .await?;
@@ -75,20 +103,39 @@ impl DatabaseClient {
         Ok(())
     }
 
+    /// Get a stored transaction by hash
+    pub async fn get_transaction(&self, tx_hash: &str) -> Result<Option<Transaction>> {
+        let client = self.pool.get().await?;
+
+        let row = client
+            .query_opt(
+                "SELECT timestamp, tx_hash, block_number, chain, pallet, call_name, caller, success, args, gas_used, fee_paid,
+                    destination, value::TEXT AS value
+                FROM transactions
+                WHERE tx_hash = $1",
+                &[&tx_hash],
+            )
+            .await?;
+
+        row.as_ref().map(Transaction::from_row).transpose()
+    }
+
     /// Insert a detection into the database
-    pub async fn insert_detection(&self, detection: &Detection) -> Result<()> {
+    ///
+    /// Returns false if a detection with the same id was already stored.
+    pub async fn insert_detection(&self, detection: &Detection) -> Result<bool> {
         let client = self.pool.get().await?;
 
         let stmt = client
             .prepare(
                 "INSERT INTO detections
-                (timestamp, detection_id, tx_hash, detector_name, attack_pattern, confidence, severity, description, evidence, metadata, acknowledged)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                (timestamp, detection_id, tx_hash, detector_name, detector_version, attack_pattern, confidence, severity, description, evidence, metadata, acknowledged, alerted)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
                 ON CONFLICT (detection_id) DO NOTHING",
             )
             .await?;
 
-        client
+        let inserted = client
             .execute(
                 &stmt,
                 &[
@@ -96,6 +143,7 @@ impl DatabaseClient {
                     &detection.detection_id,
                     &detection.tx_hash,
                     &detection.detector_name,
+                    &detection.detector_version,
                     &detection.attack_pattern,
                     &detection.confidence,
                     &detection.severity,
@@ -103,11 +151,12 @@ impl DatabaseClient {
                     &detection.evidence,
                     &detection.metadata,
                     &detection.acknowledged,
+                    &detection.alerted,
                 ],
             )
             .await?;
 
-        Ok(())
+        Ok(inserted > 0)
     }
 
     /// Insert ML features for a transaction
@@ -195,8 +244,8 @@ impl DatabaseClient {
             .prepare(
                 "INSERT INTO hydration_pool_state
                 (timestamp, pool_id, pool_type, total_liquidity, oracle_price,
-                 oracle_deviation, asset_reserves)
-                VALUES ($1, $2, $3, $4, $5, $6, $7)",
+                 oracle_deviation, token_reserves)
+                VALUES ($1, $2, $3, $4::FLOAT8, $5::FLOAT8, $6, $7)",
             )
             .await?;
 
@@ -218,6 +267,32 @@ impl DatabaseClient {
         Ok(())
     }
 
+    /// Snapshots of a pool from the `hours` before `until`, oldest first
+    pub async fn get_pool_state_history(
+        &self,
+        pool_id: &str,
+        until: chrono::DateTime<chrono::Utc>,
+        hours: i32,
+    ) -> Result<Vec<HydrationPoolState>> {
+        let client = self.pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT timestamp, pool_id, pool_type,
+                    total_liquidity::FLOAT8 AS total_liquidity,
+                    oracle_price::FLOAT8 AS oracle_price,
+                    oracle_deviation,
+                    token_reserves AS asset_reserves
+                FROM hydration_pool_state
+                WHERE pool_id = $1 AND timestamp <= $2 AND timestamp >= $2 - INTERVAL '1 hour' * $3
+                ORDER BY timestamp ASC",
+                &[&pool_id, &until, &hours],
+            )
+            .await?;
+
+        rows.iter().map(HydrationPoolState::from_row).collect()
+    }
+
     /// Insert Hydration liquidation event
     pub async fn insert_hydration_liquidation(&self, liq: &HydrationLiquidation) -> Result<()> {
         let client = self.pool.get().await?;
@@ -255,6 +330,240 @@ impl DatabaseClient {
         Ok(())
     }
 
+    /// Insert an alert, updating its metadata if it already exists
+    ///
+    /// A stored acknowledgment is kept: re-persisting an alert (e.g. with an
+    /// updated suppressed count) never reopens it. Use `acknowledge_alert`
+    /// to acknowledge.
+    pub async fn insert_alert(&self, alert: &AlertRecord) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        let stmt = client
+            .prepare(
+                "INSERT INTO alerts
+                (timestamp, alert_id, chain, severity, attack_pattern, description,
+                 tx_hash, block_number, metadata, recommended_actions, acknowledged)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                ON CONFLICT (alert_id) DO UPDATE SET
+                    metadata = EXCLUDED.metadata",
+            )
+            .await?;
+
+        client
+            .execute(
+                &stmt,
+                &[
+                    &alert.timestamp,
+                    &alert.alert_id,
+                    &alert.chain,
+                    &alert.severity,
+                    &alert.attack_pattern,
+                    &alert.description,
+                    &alert.tx_hash,
+                    &alert.block_number,
+                    &alert.metadata,
+                    &alert.recommended_actions,
+                    &alert.acknowledged,
+                ],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the most recent alerts, newest first
+    pub async fn get_alerts(&self, limit: i64) -> Result<Vec<AlertRecord>> {
+        let client = self.pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT * FROM alerts
+                 ORDER BY timestamp DESC
+                 LIMIT $1",
+                &[&limit],
+            )
+            .await?;
+
+        let alerts = rows
+            .iter()
+            .map(AlertRecord::from_row)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(alerts)
+    }
+
+    /// Mark an alert as acknowledged. Returns false if the alert does not exist.
+    pub async fn acknowledge_alert(&self, alert_id: &str) -> Result<bool> {
+        let client = self.pool.get().await?;
+
+        let updated = client
+            .execute(
+                "UPDATE alerts SET acknowledged = TRUE, acknowledged_at = NOW()
+                 WHERE alert_id = $1",
+                &[&alert_id],
+            )
+            .await?;
+
+        Ok(updated > 0)
+    }
+
+    /// Get a stored detection by id
+    pub async fn get_detection(&self, detection_id: &str) -> Result<Option<Detection>> {
+        let client = self.pool.get().await?;
+
+        let row = client
+            .query_opt("SELECT * FROM detections WHERE detection_id = $1 LIMIT 1", &[&detection_id])
+            .await?;
+
+        row.as_ref().map(Detection::from_row).transpose()
+    }
+
+    /// Store an analyst's label for a detection, replacing any previous label
+    pub async fn insert_feedback(&self, feedback: &DetectionFeedback) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "INSERT INTO detection_feedback (detection_id, detector_name, true_positive, labeled_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (detection_id) DO UPDATE SET
+                    detector_name = EXCLUDED.detector_name,
+                    true_positive = EXCLUDED.true_positive,
+                    labeled_at = EXCLUDED.labeled_at",
+                &[
+                    &feedback.detection_id,
+                    &feedback.detector_name,
+                    &feedback.true_positive,
+                    &feedback.labeled_at,
+                ],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Load every detection label, oldest first
+    pub async fn get_feedback(&self) -> Result<Vec<DetectionFeedback>> {
+        let client = self.pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT detection_id, detector_name, true_positive, labeled_at
+                 FROM detection_feedback
+                 ORDER BY labeled_at ASC",
+                &[],
+            )
+            .await?;
+
+        rows.iter().map(DetectionFeedback::from_row).collect()
+    }
+
+    /// Store a detector's state snapshot, replacing any previous one
+    pub async fn save_detector_snapshot(&self, snapshot: &crate::detectors::DetectorSnapshot) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "INSERT INTO detector_state (detector_name, detector_version, state, updated_at)
+                 VALUES ($1, $2, $3, NOW())
+                 ON CONFLICT (detector_name) DO UPDATE SET
+                    detector_version = EXCLUDED.detector_version,
+                    state = EXCLUDED.state,
+                    updated_at = EXCLUDED.updated_at",
+                &[&snapshot.detector, &snapshot.version, &snapshot.state],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Load the latest state snapshot of every detector
+    pub async fn load_detector_snapshots(&self) -> Result<Vec<crate::detectors::DetectorSnapshot>> {
+        let client = self.pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT detector_name, detector_version, state FROM detector_state",
+                &[],
+            )
+            .await?;
+
+        let snapshots = rows
+            .iter()
+            .map(|row| {
+                Ok(crate::detectors::DetectorSnapshot {
+                    detector: row.try_get("detector_name")?,
+                    version: row.try_get("detector_version")?,
+                    state: row.try_get("state")?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(snapshots)
+    }
+
+    /// Record the last fully processed block of a backfill range
+    pub async fn save_backfill_checkpoint(&self, checkpoint: &BackfillCheckpoint) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "INSERT INTO backfill_checkpoints
+                 (chain, from_block, to_block, last_processed_block, updated_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (chain, from_block, to_block) DO UPDATE SET
+                    last_processed_block = EXCLUDED.last_processed_block,
+                    updated_at = EXCLUDED.updated_at",
+                &[
+                    &checkpoint.chain,
+                    &checkpoint.from_block,
+                    &checkpoint.to_block,
+                    &checkpoint.last_processed_block,
+                    &checkpoint.updated_at,
+                ],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Load the checkpoint of a backfill range, if it was started before
+    pub async fn load_backfill_checkpoint(
+        &self,
+        chain: &str,
+        from_block: i64,
+        to_block: i64,
+    ) -> Result<Option<BackfillCheckpoint>> {
+        let client = self.pool.get().await?;
+
+        let row = client
+            .query_opt(
+                "SELECT * FROM backfill_checkpoints
+                 WHERE chain = $1 AND from_block = $2 AND to_block = $3",
+                &[&chain, &from_block, &to_block],
+            )
+            .await?;
+
+        row.as_ref().map(BackfillCheckpoint::from_row).transpose()
+    }
+
+    /// Most recently updated backfill checkpoint of a chain
+    pub async fn latest_backfill_checkpoint(&self, chain: &str) -> Result<Option<BackfillCheckpoint>> {
+        let client = self.pool.get().await?;
+
+        let row = client
+            .query_opt(
+                "SELECT * FROM backfill_checkpoints
+                 WHERE chain = $1
+                 ORDER BY updated_at DESC
+                 LIMIT 1",
+                &[&chain],
+            )
+            .await?;
+
+        row.as_ref().map(BackfillCheckpoint::from_row).transpose()
+    }
+
     /// Get recent detections for a specific detector
     pub async fn get_detections(
         &self,
@@ -291,7 +600,7 @@ impl DatabaseClient {
     }
 
     /// Get detector statistics from the continuous aggregate
-    pub async fn get_detector_stats(&self, hours: i32) -> Result<Vec<DetectorStats>> {
+    pub async fn get_detector_stats(&self, hours: i32) -> Result<Vec<DetectorAggregateStats>> {
         let client = self.pool.get().await?;
 
         let query = "
@@ -312,15 +621,8 @@ impl DatabaseClient {
 
         let stats = rows
             .iter()
-            .map(|row| DetectorStats {
-                detector_name: row.get(0),
-                chain: row.get(1),
-                attack_pattern: row.get(2),
-                total_detections: row.get(3),
-                avg_confidence: row.get(4),
-                critical_count: row.get(5),
-            })
-            .collect();
+            .map(DetectorAggregateStats::from_row)
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(stats)
     }
@@ -335,10 +637,11 @@ impl DatabaseClient {
                 pallet,
                 hour,
                 tx_count,
-                success_rate,
-                avg_gas_used,
-                total_fees
-            FROM transaction_stats_hourly
+                success_count::FLOAT8 / NULLIF(tx_count, 0) AS success_rate,
+                avg_gas_used::FLOAT8 AS avg_gas_used,
+                -- Fees are not aggregated by tx_volume_hourly
+                NULL::FLOAT8 AS total_fees
+            FROM tx_volume_hourly
             WHERE hour >= NOW() - INTERVAL '1 hour' * $1
             ORDER BY hour DESC
         ";
@@ -347,16 +650,8 @@ impl DatabaseClient {
 
         let stats = rows
             .iter()
-            .map(|row| TransactionStats {
-                chain: row.get(0),
-                pallet: row.get(1),
-                hour: row.get(2),
-                tx_count: row.get(3),
-                success_rate: row.get(4),
-                avg_gas_used: row.get(5),
-                total_fees: row.get(6),
-            })
-            .collect();
+            .map(TransactionStats::from_row)
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(stats)
     }
@@ -384,12 +679,12 @@ impl DatabaseClient {
             .iter()
             .map(|row| {
                 serde_json::json!({
-                    "timestamp": row.get::<_, chrono::DateTime<chrono::Utc>>(0),
-                    "tx_hash": row.get::<_, String>(1),
-                    "caller": row.get::<_, String>(2),
-                    "pallet": row.get::<_, String>(3),
-                    "call_name": row.get::<_, String>(4),
-                    "features": row.get::<_, serde_json::Value>(5),
+                    "timestamp": row.get::<_, chrono::DateTime<chrono::Utc>>("timestamp"),
+                    "tx_hash": row.get::<_, String>("tx_hash"),
+                    "caller": row.get::<_, String>("caller"),
+                    "pallet": row.get::<_, String>("pallet"),
+                    "call_name": row.get::<_, String>("call_name"),
+                    "features": row.get::<_, serde_json::Value>("features"),
                 })
             })
             .collect();
@@ -419,10 +714,10 @@ impl DatabaseClient {
             .iter()
             .map(|row| {
                 serde_json::json!({
-                    "hour": row.get::<_, chrono::DateTime<chrono::Utc>>(0),
-                    "attack_pattern": row.get::<_, String>(1),
-                    "count": row.get::<_, i64>(2),
-                    "avg_confidence": row.get::<_, f64>(3),
+                    "hour": row.get::<_, chrono::DateTime<chrono::Utc>>("hour"),
+                    "attack_pattern": row.get::<_, String>("attack_pattern"),
+                    "count": row.get::<_, i64>("count"),
+                    "avg_confidence": row.get::<_, f64>("avg_confidence"),
                 })
             })
             .collect();
@@ -430,35 +725,68 @@ impl DatabaseClient {
         Ok(trends)
     }
 
-    /// Get data for export (all detections with details)
-    pub async fn get_export_data(&self, hours: Option<i32>) -> Result<Vec<serde_json::Value>> {
+    /// Get hourly transaction volume alongside detection counts
+    ///
+    /// Returns one bucket per hour of the window (the current, partial hour
+    /// included), oldest first, so both series share the same time axis.
+    /// Hours without activity have zero counts; the detection ratio is `None`
+    /// when no transactions were stored in that hour.
+    pub async fn get_correlated_trends(&self, hours: i32) -> Result<Vec<CorrelatedTrend>> {
         let client = self.pool.get().await?;
 
-        let query = if let Some(h) = hours {
-            format!(
-                "SELECT
-                    d.timestamp,
-                    d.detection_id,
-                    d.tx_hash,
-                    d.detector_name,
-                    d.attack_pattern,
-                    d.confidence,
-                    d.severity,
-                    d.description,
-                    d.evidence,
-                    t.caller,
-                    t.pallet,
-                    t.call_name,
-                    t.success,
-                    t.chain
-                FROM detections d
-                LEFT JOIN transactions t ON d.tx_hash = t.tx_hash
-                WHERE d.timestamp >= NOW() - INTERVAL '1 hour' * {}
-                ORDER BY d.timestamp DESC",
-                h
+        let query = "
+            WITH buckets AS (
+                SELECT generate_series(
+                    date_trunc('hour', NOW() - INTERVAL '1 hour' * $1),
+                    date_trunc('hour', NOW()),
+                    INTERVAL '1 hour'
+                ) AS hour
+            ),
+            tx AS (
+                SELECT date_trunc('hour', timestamp) AS hour, COUNT(*) AS tx_count
+                FROM transactions
+                WHERE timestamp >= date_trunc('hour', NOW() - INTERVAL '1 hour' * $1)
+                GROUP BY 1
+            ),
+            det AS (
+                SELECT date_trunc('hour', timestamp) AS hour, COUNT(*) AS detection_count
+                FROM detections
+                WHERE timestamp >= date_trunc('hour', NOW() - INTERVAL '1 hour' * $1)
+                GROUP BY 1
             )
-        } else {
-            "SELECT
+            SELECT
+                b.hour,
+                COALESCE(tx.tx_count, 0) AS tx_count,
+                COALESCE(det.detection_count, 0) AS detection_count,
+                COALESCE(det.detection_count, 0)::FLOAT8 / NULLIF(tx.tx_count, 0) AS detection_ratio
+            FROM buckets b
+            LEFT JOIN tx ON tx.hour = b.hour
+            LEFT JOIN det ON det.hour = b.hour
+            ORDER BY b.hour ASC
+        ";
+
+        let rows = client.query(query, &[&hours]).await?;
+
+        let trends = rows
+            .iter()
+            .map(CorrelatedTrend::from_row)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(trends)
+    }
+
+    /// Get data for export (all detections with details)
+    ///
+    /// Results are paginated with `limit`/`offset`, newest first.
+    pub async fn get_export_data(
+        &self,
+        hours: Option<i32>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ExportRecord>> {
+        let client = self.pool.get().await?;
+
+        let select = "SELECT
                 d.timestamp,
                 d.detection_id,
                 d.tx_hash,
@@ -468,44 +796,78 @@ impl DatabaseClient {
                 d.severity,
                 d.description,
                 d.evidence,
+                d.detector_version,
                 t.caller,
                 t.pallet,
                 t.call_name,
                 t.success,
                 t.chain
             FROM detections d
-            LEFT JOIN transactions t ON d.tx_hash = t.tx_hash
-            ORDER BY d.timestamp DESC
-            LIMIT 1000".to_string()
+            LEFT JOIN transactions t ON d.tx_hash = t.tx_hash";
+
+        let rows = if let Some(h) = hours {
+            let query = format!(
+                "{}
+                WHERE d.timestamp >= NOW() - INTERVAL '1 hour' * $1
+                ORDER BY d.timestamp DESC
+                LIMIT $2 OFFSET $3",
+                select
+            );
+            client.query(&query, &[&h, &limit, &offset]).await?
+        } else {
+            let query = format!(
+                "{}
+                ORDER BY d.timestamp DESC
+                LIMIT $1 OFFSET $2",
+                select
+            );
+            client.query(&query, &[&limit, &offset]).await?
         };
 
-        let rows = client.query(&query, &[]).await?;
-
-        let data: Vec<serde_json::Value> = rows
+        let data = rows
             .iter()
-            .map(|row| {
-                serde_json::json!({
-                    "timestamp": row.get::<_, chrono::DateTime<chrono::Utc>>(0),
-                    "detection_id": row.get::<_, String>(1),
-                    "tx_hash": row.get::<_, String>(2),
-                    "detector_name": row.get::<_, String>(3),
-                    "attack_pattern": row.get::<_, String>(4),
-                    "confidence": row.get::<_, f64>(5),
-                    "severity": row.get::<_, String>(6),
-                    "description": row.get::<_, Option<String>>(7),
-                    "evidence": row.get::<_, Option<serde_json::Value>>(8),
-                    "caller": row.get::<_, Option<String>>(9),
-                    "pallet": row.get::<_, Option<String>>(10),
-                    "call_name": row.get::<_, Option<String>>(11),
-                    "success": row.get::<_, Option<bool>>(12),
-                    "chain": row.get::<_, Option<String>>(13),
-                })
-            })
-            .collect();
+            .map(ExportRecord::from_row)
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(data)
     }
 
+    /// Graph of the addresses that transacted in the last `hours`
+    ///
+    /// Transfers become edges from caller to destination; detections count
+    /// toward both ends and their patterns label the edge. Near misses
+    /// (`alerted = false`) are left out. At most `max_edges` edges are
+    /// returned (see [`TransactionGraph::limit_edges`]).
+    pub async fn export_graph(&self, hours: i32, max_edges: usize) -> Result<TransactionGraph> {
+        let client = self.pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT
+                    t.caller,
+                    t.destination,
+                    t.value::TEXT AS value,
+                    COUNT(d.detection_id) AS detections,
+                    COALESCE(
+                        ARRAY_AGG(DISTINCT d.attack_pattern) FILTER (WHERE d.attack_pattern IS NOT NULL),
+                        '{}'
+                    ) AS patterns
+                FROM transactions t
+                LEFT JOIN detections d ON d.tx_hash = t.tx_hash AND d.alerted
+                WHERE t.timestamp >= NOW() - INTERVAL '1 hour' * $1
+                GROUP BY t.tx_hash, t.caller, t.destination, t.value",
+                &[&hours],
+            )
+            .await?;
+
+        let rows = rows
+            .iter()
+            .map(GraphRow::from_row)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(TransactionGraph::from_rows(rows).limit_edges(max_edges))
+    }
+
     /// Health check - verify database connection
     pub async fn health_check(&self) -> Result<bool> {
         match self.pool.get().await {
@@ -522,4 +884,85 @@ impl DatabaseClient {
             }
         }
     }
+
+    /// Check that the required tables and continuous aggregates exist
+    ///
+    /// `health_check` only proves the database answers; a missing aggregate
+    /// otherwise goes unnoticed until a stats query fails. Aggregates count
+    /// only when they are TimescaleDB continuous aggregates in the search
+    /// path, so a plain view of the same name is reported missing, as is
+    /// every aggregate when TimescaleDB is not installed.
+    pub async fn schema_check(&self) -> Result<SchemaReport> {
+        let client = self.pool.get().await?;
+        let mut report = SchemaReport::default();
+
+        let rows = client
+            .query(
+                "SELECT name FROM unnest($1::text[]) AS name
+                WHERE to_regclass(quote_ident(name)) IS NULL",
+                &[&REQUIRED_TABLES],
+            )
+            .await?;
+        for row in rows {
+            report.missing_tables.push(row.try_get("name")?);
+        }
+
+        let timescaledb: bool = client
+            .query_one(
+                "SELECT to_regclass('timescaledb_information.continuous_aggregates') IS NOT NULL",
+                &[],
+            )
+            .await?
+            .try_get(0)?;
+        if !timescaledb {
+            report.missing_aggregates = REQUIRED_AGGREGATES.iter().map(|name| name.to_string()).collect();
+            return Ok(report);
+        }
+
+        let rows = client
+            .query(
+                "SELECT name FROM unnest($1::text[]) AS name
+                WHERE NOT EXISTS (
+                    SELECT 1 FROM timescaledb_information.continuous_aggregates
+                    WHERE view_name = name AND view_schema = ANY(current_schemas(false))
+                )",
+                &[&REQUIRED_AGGREGATES],
+            )
+            .await?;
+        for row in rows {
+            report.missing_aggregates.push(row.try_get("name")?);
+        }
+
+        Ok(report)
+    }
+
+    /// Remove transactions, detections and ML features older than `days`
+    ///
+    /// Rows are deleted rather than dropped chunk by chunk with `drop_chunks`,
+    /// so the cutoff is exact and the purge also works without TimescaleDB.
+    /// Detections and features cascade with their transaction.
+    pub async fn apply_retention(&self, days: i32) -> Result<RetentionReport> {
+        anyhow::ensure!(days > 0, "retention must be at least one day, got {}", days);
+
+        let client = self.pool.get().await?;
+        let mut report = RetentionReport::default();
+        // Children first, so the counts only include rows past the cutoff
+        for (table, deleted) in [
+            ("detections", &mut report.detections),
+            ("ml_features", &mut report.ml_features),
+            ("transactions", &mut report.transactions),
+        ] {
+            let query = format!(
+                "DELETE FROM {} WHERE timestamp < NOW() - make_interval(days => $1)",
+                table
+            );
+            *deleted = client.execute(&query, &[&days]).await?;
+        }
+
+        info!(
+            "Retention of {} day(s) removed {} transaction(s), {} detection(s), {} ML feature row(s)",
+            days, report.transactions, report.detections, report.ml_features
+        );
+        Ok(report)
+    }
 }
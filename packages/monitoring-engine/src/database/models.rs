@@ -18,6 +18,10 @@ pub struct Transaction {
     pub args: Option<JsonValue>,
     pub gas_used: Option<i64>,
     pub fee_paid: Option<f64>,
+    /// Recipient of a Balances transfer (SS58)
+    pub destination: Option<String>,
+    /// Amount of a Balances transfer in planck, as a decimal string
+    pub value: Option<String>,
 }
 
 impl Transaction {
@@ -34,6 +38,8 @@ impl Transaction {
             args: row.try_get("args")?,
             gas_used: row.try_get("gas_used")?,
             fee_paid: row.try_get("fee_paid")?,
+            destination: row.try_get("destination")?,
+            value: row.try_get("value")?,
         })
     }
 }
@@ -45,6 +51,7 @@ pub struct Detection {
     pub detection_id: String,
     pub tx_hash: String,
     pub detector_name: String,
+    pub detector_version: String,
     pub attack_pattern: String,
     pub confidence: f64,
     pub severity: String,
@@ -52,6 +59,8 @@ pub struct Detection {
     pub evidence: Option<JsonValue>,
     pub metadata: Option<JsonValue>,
     pub acknowledged: bool,
+    /// False for near misses stored below the alert threshold
+    pub alerted: bool,
 }
 
 impl Detection {
@@ -61,6 +70,7 @@ impl Detection {
             detection_id: row.try_get("detection_id")?,
             tx_hash: row.try_get("tx_hash")?,
             detector_name: row.try_get("detector_name")?,
+            detector_version: row.try_get("detector_version")?,
             attack_pattern: row.try_get("attack_pattern")?,
             confidence: row.try_get("confidence")?,
             severity: row.try_get("severity")?,
@@ -68,6 +78,7 @@ impl Detection {
             evidence: row.try_get("evidence")?,
             metadata: row.try_get("metadata")?,
             acknowledged: row.try_get("acknowledged")?,
+            alerted: row.try_get("alerted")?,
         })
     }
 }
@@ -106,6 +117,26 @@ impl MlFeatures {
     }
 }
 
+/// An analyst's verdict on a detection, kept for threshold tuning and retraining
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionFeedback {
+    pub detection_id: String,
+    pub detector_name: String,
+    pub true_positive: bool,
+    pub labeled_at: DateTime<Utc>,
+}
+
+impl DetectionFeedback {
+    pub fn from_row(row: &Row) -> Result<Self> {
+        Ok(Self {
+            detection_id: row.try_get("detection_id")?,
+            detector_name: row.try_get("detector_name")?,
+            true_positive: row.try_get("true_positive")?,
+            labeled_at: row.try_get("labeled_at")?,
+        })
+    }
+}
+
 /// Hyperbridge cross-chain message tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HyperbridgeMessage {
@@ -200,9 +231,278 @@ impl HydrationLiquidation {
     }
 }
 
-/// Detector statistics from continuous aggregate
+/// Persisted security alert
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRecord {
+    pub timestamp: DateTime<Utc>,
+    pub alert_id: String,
+    pub chain: String,
+    pub severity: String,
+    pub attack_pattern: String,
+    pub description: String,
+    pub tx_hash: Option<String>,
+    pub block_number: Option<i64>,
+    pub metadata: JsonValue,
+    pub recommended_actions: Vec<String>,
+    pub acknowledged: bool,
+}
+
+impl AlertRecord {
+    pub fn from_row(row: &Row) -> Result<Self> {
+        Ok(Self {
+            timestamp: row.try_get("timestamp")?,
+            alert_id: row.try_get("alert_id")?,
+            chain: row.try_get("chain")?,
+            severity: row.try_get("severity")?,
+            attack_pattern: row.try_get("attack_pattern")?,
+            description: row.try_get("description")?,
+            tx_hash: row.try_get("tx_hash")?,
+            block_number: row.try_get("block_number")?,
+            metadata: row.try_get("metadata")?,
+            recommended_actions: row.try_get("recommended_actions")?,
+            acknowledged: row.try_get("acknowledged")?,
+        })
+    }
+
+    /// Build a record from an in-memory alert
+    ///
+    /// Severity and pattern are stored using their serde names
+    /// (e.g. `high`, `flash_loan`) so they round-trip losslessly.
+    pub fn from_alert(alert: &crate::types::Alert) -> Result<Self> {
+        Ok(Self {
+            timestamp: DateTime::from_timestamp(alert.timestamp as i64, 0)
+                .unwrap_or_else(Utc::now),
+            alert_id: alert.id.clone(),
+            chain: alert.chain.clone(),
+            severity: serde_name(&alert.severity)?,
+            attack_pattern: serde_name(&alert.pattern)?,
+            description: alert.description.clone(),
+            tx_hash: alert.transaction_hash.clone(),
+            block_number: alert.block_number.map(|b| b as i64),
+            metadata: serde_json::to_value(&alert.metadata)?,
+            recommended_actions: alert.recommended_actions.clone(),
+            acknowledged: alert.acknowledged,
+        })
+    }
+
+    /// Convert the record back into an in-memory alert
+    pub fn to_alert(&self) -> Result<crate::types::Alert> {
+        Ok(crate::types::Alert {
+            schema_version: crate::types::SCHEMA_VERSION,
+            id: self.alert_id.clone(),
+            timestamp: self.timestamp.timestamp().max(0) as u64,
+            chain: self.chain.clone(),
+            severity: serde_json::from_value(JsonValue::String(self.severity.clone()))?,
+            pattern: serde_json::from_value(JsonValue::String(self.attack_pattern.clone()))?,
+            description: self.description.clone(),
+            transaction_hash: self.tx_hash.clone(),
+            block_number: self.block_number.map(|b| b as u64),
+            metadata: serde_json::from_value(self.metadata.clone())?,
+            recommended_actions: self.recommended_actions.clone(),
+            acknowledged: self.acknowledged,
+        })
+    }
+}
+
+/// Progress of a historical backfill over `from_block..=to_block`
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DetectorStats {
+pub struct BackfillCheckpoint {
+    pub chain: String,
+    pub from_block: i64,
+    pub to_block: i64,
+    /// Last block whose transactions were all processed
+    pub last_processed_block: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BackfillCheckpoint {
+    pub fn from_row(row: &Row) -> Result<Self> {
+        Ok(Self {
+            chain: row.try_get("chain")?,
+            from_block: row.try_get("from_block")?,
+            to_block: row.try_get("to_block")?,
+            last_processed_block: row.try_get("last_processed_block")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+/// Required tables and continuous aggregates missing from the database
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaReport {
+    pub missing_tables: Vec<String>,
+    pub missing_aggregates: Vec<String>,
+}
+
+impl SchemaReport {
+    /// Whether every required relation exists
+    pub fn is_complete(&self) -> bool {
+        self.missing_tables.is_empty() && self.missing_aggregates.is_empty()
+    }
+}
+
+/// Rows removed by a retention purge, per table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionReport {
+    pub transactions: u64,
+    pub detections: u64,
+    pub ml_features: u64,
+}
+
+/// Graph of the addresses that transacted in a time window
+///
+/// Node-link JSON with `nodes` and `edges` whose `source`/`target` are node
+/// IDs, as read by Cytoscape, Gephi's JSON importer and networkx.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionGraph {
+    pub directed: bool,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// Whether edges were dropped to stay within the export limit
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// An address in the transaction graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// Address (SS58 when known)
+    pub id: String,
+    /// Transactions the address sent or received
+    pub transactions: i64,
+    /// Detections on those transactions
+    pub detections: i64,
+}
+
+/// Transfers from one address to another, aggregated over the window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub transfers: i64,
+    /// Total transferred in planck, as a decimal string
+    pub value: String,
+    /// Attack patterns detected on these transfers, sorted
+    pub patterns: Vec<String>,
+}
+
+/// One transaction as fed into [`TransactionGraph::from_rows`]
+#[derive(Debug, Clone)]
+pub struct GraphRow {
+    pub caller: String,
+    pub destination: Option<String>,
+    /// Transferred planck, as a decimal string
+    pub value: Option<String>,
+    pub detections: i64,
+    pub patterns: Vec<String>,
+}
+
+impl GraphRow {
+    pub fn from_row(row: &Row) -> Result<Self> {
+        Ok(Self {
+            caller: row.try_get("caller")?,
+            destination: row.try_get("destination")?,
+            value: row.try_get("value")?,
+            detections: row.try_get("detections")?,
+            patterns: row.try_get("patterns")?,
+        })
+    }
+}
+
+impl TransactionGraph {
+    /// Aggregate transactions into address nodes and transfer edges
+    ///
+    /// Both ends of a transfer count its detections. Nodes and edges are
+    /// sorted by ID so the same window always exports identically.
+    pub fn from_rows(rows: impl IntoIterator<Item = GraphRow>) -> Self {
+        use std::collections::{BTreeMap, BTreeSet};
+
+        let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
+        let mut edges: BTreeMap<(String, String), (i64, u128, BTreeSet<String>)> = BTreeMap::new();
+
+        for row in rows {
+            let ends = std::iter::once(&row.caller).chain(row.destination.as_ref());
+            for address in ends {
+                let node = nodes.entry(address.clone()).or_insert_with(|| GraphNode {
+                    id: address.clone(),
+                    transactions: 0,
+                    detections: 0,
+                });
+                node.transactions += 1;
+                node.detections += row.detections;
+            }
+
+            let Some(destination) = row.destination else {
+                continue;
+            };
+            let value = row
+                .value
+                .as_deref()
+                .and_then(|v| v.parse::<u128>().ok())
+                .unwrap_or_default();
+            let edge = edges.entry((row.caller, destination)).or_default();
+            edge.0 += 1;
+            edge.1 = edge.1.saturating_add(value);
+            edge.2.extend(row.patterns);
+        }
+
+        Self {
+            directed: true,
+            truncated: false,
+            nodes: nodes.into_values().collect(),
+            edges: edges
+                .into_iter()
+                .map(|((source, target), (transfers, value, patterns))| GraphEdge {
+                    source,
+                    target,
+                    transfers,
+                    value: value.to_string(),
+                    patterns: patterns.into_iter().collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Keep at most `max_edges` edges, those with the most transfers
+    ///
+    /// Kept edges stay sorted by ID and `truncated` is set when any were
+    /// dropped. Nodes are kept, so their counts still cover the whole window.
+    pub fn limit_edges(mut self, max_edges: usize) -> Self {
+        if self.edges.len() <= max_edges {
+            return self;
+        }
+
+        // Stable sort: ties keep their ID order
+        let mut ranked: Vec<(usize, i64)> =
+            self.edges.iter().map(|e| e.transfers).enumerate().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1));
+        let mut keep = vec![false; self.edges.len()];
+        for (index, _) in ranked.into_iter().take(max_edges) {
+            keep[index] = true;
+        }
+
+        self.edges = self
+            .edges
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(edge, keep)| keep.then_some(edge))
+            .collect();
+        self.truncated = true;
+        self
+    }
+}
+
+/// Serialize a unit enum variant to its serde string name
+fn serde_name<T: Serialize>(value: &T) -> Result<String> {
+    match serde_json::to_value(value)? {
+        JsonValue::String(name) => Ok(name),
+        other => Err(anyhow::anyhow!("Expected string variant, got {}", other)),
+    }
+}
+
+/// Detector statistics from the `detector_stats_hourly` continuous aggregate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectorAggregateStats {
     pub detector_name: String,
     pub chain: String,
     pub attack_pattern: String,
@@ -211,6 +511,19 @@ pub struct DetectorStats {
     pub critical_count: i64,
 }
 
+impl DetectorAggregateStats {
+    pub fn from_row(row: &Row) -> Result<Self> {
+        Ok(Self {
+            detector_name: row.try_get("detector_name")?,
+            chain: row.try_get("chain")?,
+            attack_pattern: row.try_get("attack_pattern")?,
+            total_detections: row.try_get("total_detections")?,
+            avg_confidence: row.try_get("avg_confidence")?,
+            critical_count: row.try_get("critical_count")?,
+        })
+    }
+}
+
 /// Transaction statistics from continuous aggregate
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionStats {
@@ -223,6 +536,86 @@ pub struct TransactionStats {
     pub total_fees: Option<f64>,
 }
 
+impl TransactionStats {
+    pub fn from_row(row: &Row) -> Result<Self> {
+        Ok(Self {
+            chain: row.try_get("chain")?,
+            pallet: row.try_get("pallet")?,
+            hour: row.try_get("hour")?,
+            tx_count: row.try_get("tx_count")?,
+            success_rate: row.try_get("success_rate")?,
+            avg_gas_used: row.try_get("avg_gas_used")?,
+            total_fees: row.try_get("total_fees")?,
+        })
+    }
+}
+
+/// Transaction volume and detections in one hour bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelatedTrend {
+    pub hour: DateTime<Utc>,
+    pub tx_count: i64,
+    pub detection_count: i64,
+    /// Detections per stored transaction, `None` for hours without transactions
+    pub detection_ratio: Option<f64>,
+}
+
+impl CorrelatedTrend {
+    pub fn from_row(row: &Row) -> Result<Self> {
+        Ok(Self {
+            hour: row.try_get("hour")?,
+            tx_count: row.try_get("tx_count")?,
+            detection_count: row.try_get("detection_count")?,
+            detection_ratio: row.try_get("detection_ratio")?,
+        })
+    }
+}
+
+/// A detection joined with its transaction, as exported
+///
+/// Transaction columns are optional since the transaction may not have been
+/// stored (LEFT JOIN).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRecord {
+    pub timestamp: DateTime<Utc>,
+    pub detection_id: String,
+    pub tx_hash: String,
+    pub detector_name: String,
+    pub attack_pattern: String,
+    pub confidence: f64,
+    pub severity: String,
+    pub description: Option<String>,
+    pub evidence: Option<JsonValue>,
+    pub detector_version: String,
+    pub caller: Option<String>,
+    pub pallet: Option<String>,
+    pub call_name: Option<String>,
+    pub success: Option<bool>,
+    pub chain: Option<String>,
+}
+
+impl ExportRecord {
+    pub fn from_row(row: &Row) -> Result<Self> {
+        Ok(Self {
+            timestamp: row.try_get("timestamp")?,
+            detection_id: row.try_get("detection_id")?,
+            tx_hash: row.try_get("tx_hash")?,
+            detector_name: row.try_get("detector_name")?,
+            attack_pattern: row.try_get("attack_pattern")?,
+            confidence: row.try_get("confidence")?,
+            severity: row.try_get("severity")?,
+            description: row.try_get("description")?,
+            evidence: row.try_get("evidence")?,
+            detector_version: row.try_get("detector_version")?,
+            caller: row.try_get("caller")?,
+            pallet: row.try_get("pallet")?,
+            call_name: row.try_get("call_name")?,
+            success: row.try_get("success")?,
+            chain: row.try_get("chain")?,
+        })
+    }
+}
+
 /// Attack pattern for ML training
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttackPattern {
@@ -0,0 +1,448 @@
+//! Retried database writes with a dead-letter fallback
+//!
+//! Transaction and detection rows are part of the audit trail, so a failed
+//! insert is retried with backoff instead of being logged and dropped. A
+//! record that still fails is deferred: appended to a JSON Lines dead-letter
+//! file when one is configured, otherwise kept in a bounded in-memory queue.
+//! [`RetryingWriter::replay`] writes deferred records once the database is
+//! back; only records that can be neither written nor deferred are dropped.
+
+use super::models::{Detection, Transaction};
+use super::DatabaseClient;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Default attempts per insert before a record is deferred
+pub const DEFAULT_WRITE_ATTEMPTS: u32 = 3;
+
+/// Records kept in memory while the database is unavailable and no
+/// dead-letter file is configured
+pub const DEFAULT_DEFERRED_CAPACITY: usize = 10_000;
+
+/// Backoff before the first retry; doubled for each further retry
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Destination of the audit trail rows
+#[async_trait::async_trait]
+pub trait RecordStore: Send + Sync {
+    async fn insert_transaction(&self, tx: &Transaction) -> Result<()>;
+
+    /// Insert a detection; `false` if one with the same id is already stored
+    async fn insert_detection(&self, detection: &Detection) -> Result<bool>;
+}
+
+#[async_trait::async_trait]
+impl RecordStore for DatabaseClient {
+    async fn insert_transaction(&self, tx: &Transaction) -> Result<()> {
+        DatabaseClient::insert_transaction(self, tx).await
+    }
+
+    async fn insert_detection(&self, detection: &Detection) -> Result<bool> {
+        DatabaseClient::insert_detection(self, detection).await
+    }
+}
+
+/// A row waiting to be written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "record", rename_all = "snake_case")]
+pub enum PendingRecord {
+    Transaction(Transaction),
+    Detection(Detection),
+}
+
+impl PendingRecord {
+    fn describe(&self) -> String {
+        match self {
+            Self::Transaction(tx) => format!("transaction {}", tx.tx_hash),
+            Self::Detection(detection) => format!("detection {}", detection.detection_id),
+        }
+    }
+
+    /// Write the record once; `Ok(false)` for a detection already stored
+    async fn insert(&self, store: &dyn RecordStore) -> Result<bool> {
+        match self {
+            Self::Transaction(tx) => store.insert_transaction(tx).await.map(|()| true),
+            Self::Detection(detection) => store.insert_detection(detection).await,
+        }
+    }
+}
+
+/// What happened to a record handed to [`RetryingWriter::write`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOutcome {
+    /// Written; `false` when a detection with the same id was already stored
+    Stored(bool),
+    /// Every attempt failed; the record waits for [`RetryingWriter::replay`]
+    Deferred,
+    /// Every attempt failed and the record could not be deferred either
+    Dropped,
+}
+
+/// Write counters, reported in the engine stats
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WriteStats {
+    /// Failed attempts that were retried
+    pub retried: u64,
+    /// Records deferred after exhausting their attempts
+    pub deferred: u64,
+    /// Deferred records written by a later replay
+    pub replayed: u64,
+    /// Records lost: the in-memory queue was full or the dead-letter file
+    /// could not be written
+    pub dropped: u64,
+    /// Deferred records still waiting
+    pub pending: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    retried: AtomicU64,
+    deferred: AtomicU64,
+    replayed: AtomicU64,
+    dropped: AtomicU64,
+    pending: AtomicU64,
+}
+
+/// Writes records to a [`RecordStore`], retrying and deferring failures
+pub struct RetryingWriter {
+    store: Arc<dyn RecordStore>,
+    max_attempts: u32,
+    initial_backoff: Duration,
+    /// Dead-letter file; deferred records stay in `queue` without one
+    dead_letter_file: Option<PathBuf>,
+    /// Also serializes dead-letter file access
+    queue: Mutex<VecDeque<PendingRecord>>,
+    capacity: usize,
+    counters: Counters,
+}
+
+impl RetryingWriter {
+    pub fn new(store: Arc<dyn RecordStore>) -> Self {
+        Self {
+            store,
+            max_attempts: DEFAULT_WRITE_ATTEMPTS,
+            initial_backoff: INITIAL_BACKOFF,
+            dead_letter_file: None,
+            queue: Mutex::new(VecDeque::new()),
+            capacity: DEFAULT_DEFERRED_CAPACITY,
+            counters: Counters::default(),
+        }
+    }
+
+    /// Attempts per insert before the record is deferred (at least one)
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Backoff before the first retry, doubled for each further retry
+    pub fn with_initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Defer failed records to a JSON Lines file instead of memory
+    ///
+    /// Records already in the file, e.g. from a previous run, are written by
+    /// the next [`Self::replay`].
+    pub fn with_dead_letter_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dead_letter_file = Some(path.into());
+        self
+    }
+
+    /// Records kept in memory without a dead-letter file
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn dead_letter_file(&self) -> Option<&Path> {
+        self.dead_letter_file.as_deref()
+    }
+
+    /// Write a record, retrying with backoff, then deferring it
+    pub async fn write(&self, record: PendingRecord) -> WriteOutcome {
+        let mut backoff = self.initial_backoff;
+        for attempt in 1..=self.max_attempts {
+            match record.insert(self.store.as_ref()).await {
+                Ok(inserted) => return WriteOutcome::Stored(inserted),
+                Err(e) => tracing::warn!(
+                    "Failed to store {} in database: {} (attempt {}/{})",
+                    record.describe(),
+                    e,
+                    attempt,
+                    self.max_attempts
+                ),
+            }
+
+            if attempt < self.max_attempts {
+                self.counters.retried.fetch_add(1, Ordering::Relaxed);
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+
+        self.defer(record).await
+    }
+
+    async fn defer(&self, record: PendingRecord) -> WriteOutcome {
+        let mut queue = self.queue.lock().await;
+
+        let deferred = match &self.dead_letter_file {
+            Some(path) => match append(path, &record).await {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to write {} to dead-letter file {}: {}",
+                        record.describe(),
+                        path.display(),
+                        e
+                    );
+                    false
+                }
+            },
+            None if queue.len() < self.capacity => {
+                queue.push_back(record.clone());
+                true
+            }
+            None => false,
+        };
+
+        if deferred {
+            self.counters.deferred.fetch_add(1, Ordering::Relaxed);
+            self.counters.pending.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!("Deferred {} until the database recovers", record.describe());
+            WriteOutcome::Deferred
+        } else {
+            let dropped = self.counters.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            tracing::error!("Dropped {} ({} dropped so far)", record.describe(), dropped);
+            WriteOutcome::Dropped
+        }
+    }
+
+    /// Write deferred records in order, stopping at the first failure
+    ///
+    /// Records not written stay deferred for the next replay. Returns the
+    /// number of records written.
+    pub async fn replay(&self) -> Result<usize> {
+        let mut queue = self.queue.lock().await;
+
+        let mut pending: VecDeque<PendingRecord> = match &self.dead_letter_file {
+            Some(path) => read_all(path).await?.into(),
+            None => std::mem::take(&mut *queue),
+        };
+
+        let mut replayed = 0;
+        while let Some(record) = pending.front() {
+            if let Err(e) = record.insert(self.store.as_ref()).await {
+                tracing::warn!("Database still unavailable, {} record(s) remain deferred: {}", pending.len(), e);
+                break;
+            }
+            pending.pop_front();
+            replayed += 1;
+        }
+
+        self.counters.replayed.fetch_add(replayed as u64, Ordering::Relaxed);
+        self.counters.pending.store(pending.len() as u64, Ordering::Relaxed);
+
+        match &self.dead_letter_file {
+            Some(path) if replayed > 0 => rewrite(path, &pending).await?,
+            Some(_) => {}
+            None => *queue = pending,
+        }
+
+        if replayed > 0 {
+            tracing::info!("Replayed {} deferred database record(s)", replayed);
+        }
+        Ok(replayed)
+    }
+
+    pub fn stats(&self) -> WriteStats {
+        WriteStats {
+            retried: self.counters.retried.load(Ordering::Relaxed),
+            deferred: self.counters.deferred.load(Ordering::Relaxed),
+            replayed: self.counters.replayed.load(Ordering::Relaxed),
+            dropped: self.counters.dropped.load(Ordering::Relaxed),
+            pending: self.counters.pending.load(Ordering::Relaxed),
+        }
+    }
+}
+
+async fn append(path: &Path, record: &PendingRecord) -> Result<()> {
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(&line).await?;
+    file.flush().await?;
+    Ok(())
+}
+
+async fn read_all(path: &Path) -> Result<Vec<PendingRecord>> {
+    let contents = match tokio::fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(Into::into))
+        .collect()
+}
+
+/// Replace the file with the remaining records; write then rename so a
+/// crash never leaves a truncated file
+async fn rewrite(path: &Path, records: &VecDeque<PendingRecord>) -> Result<()> {
+    let mut contents = Vec::new();
+    for record in records {
+        contents.extend(serde_json::to_vec(record)?);
+        contents.push(b'\n');
+    }
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    tokio::fs::write(&tmp, contents).await?;
+    tokio::fs::rename(&tmp, path).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    /// Store failing the next `failures` inserts, recording successful ones
+    struct FlakyStore {
+        failures: AtomicU64,
+        stored: StdMutex<Vec<String>>,
+    }
+
+    impl FlakyStore {
+        fn failing(failures: u64) -> Arc<Self> {
+            Arc::new(Self {
+                failures: AtomicU64::new(failures),
+                stored: StdMutex::default(),
+            })
+        }
+
+        fn fail(&self) -> Result<()> {
+            let remaining = self.failures.load(Ordering::SeqCst);
+            if remaining > 0 {
+                self.failures.store(remaining - 1, Ordering::SeqCst);
+                anyhow::bail!("connection refused");
+            }
+            Ok(())
+        }
+
+        fn stored(&self) -> Vec<String> {
+            self.stored.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl RecordStore for FlakyStore {
+        async fn insert_transaction(&self, tx: &Transaction) -> Result<()> {
+            self.fail()?;
+            self.stored.lock().unwrap().push(tx.tx_hash.clone());
+            Ok(())
+        }
+
+        async fn insert_detection(&self, detection: &Detection) -> Result<bool> {
+            self.fail()?;
+            self.stored.lock().unwrap().push(detection.detection_id.clone());
+            Ok(true)
+        }
+    }
+
+    fn transaction(hash: &str) -> PendingRecord {
+        PendingRecord::Transaction(Transaction {
+            timestamp: chrono::Utc::now(),
+            tx_hash: hash.to_string(),
+            block_number: 42,
+            chain: "westend".to_string(),
+            pallet: "Balances".to_string(),
+            call_name: "transfer_keep_alive".to_string(),
+            caller: "alice".to_string(),
+            success: true,
+            args: None,
+            gas_used: None,
+            fee_paid: None,
+            destination: None,
+            value: None,
+        })
+    }
+
+    fn writer(store: &Arc<FlakyStore>) -> RetryingWriter {
+        RetryingWriter::new(store.clone()).with_initial_backoff(Duration::ZERO)
+    }
+
+    #[tokio::test]
+    async fn test_transient_failure_retried() {
+        let store = FlakyStore::failing(2);
+        let writer = writer(&store);
+
+        assert_eq!(writer.write(transaction("0x01")).await, WriteOutcome::Stored(true));
+        assert_eq!(store.stored(), vec!["0x01"]);
+        assert_eq!(writer.stats(), WriteStats { retried: 2, ..Default::default() });
+    }
+
+    #[tokio::test]
+    async fn test_outage_deferred_then_replayed() {
+        // Down for both attempts of each write and the first replay
+        let store = FlakyStore::failing(5);
+        let writer = writer(&store).with_max_attempts(2);
+
+        assert_eq!(writer.write(transaction("0x01")).await, WriteOutcome::Deferred);
+        assert_eq!(writer.write(transaction("0x02")).await, WriteOutcome::Deferred);
+        assert_eq!(writer.replay().await.unwrap(), 0);
+        assert_eq!(writer.stats().pending, 2);
+
+        assert_eq!(writer.replay().await.unwrap(), 2);
+        assert_eq!(store.stored(), vec!["0x01", "0x02"]);
+        assert_eq!(
+            writer.stats(),
+            WriteStats { retried: 2, deferred: 2, replayed: 2, dropped: 0, pending: 0 }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_drops() {
+        let store = FlakyStore::failing(u64::MAX);
+        let writer = writer(&store).with_max_attempts(1).with_capacity(1);
+
+        assert_eq!(writer.write(transaction("0x01")).await, WriteOutcome::Deferred);
+        assert_eq!(writer.write(transaction("0x02")).await, WriteOutcome::Dropped);
+        assert_eq!(writer.stats().dropped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_file_survives_restart() {
+        let path = std::env::temp_dir().join(format!("deferred-writes-{}.jsonl", std::process::id()));
+        let store = FlakyStore::failing(1);
+
+        let first_run = writer(&store).with_max_attempts(1).with_dead_letter_file(&path);
+        assert_eq!(first_run.write(transaction("0x01")).await, WriteOutcome::Deferred);
+
+        // A new writer over the same file replays what the first one deferred
+        let second_run = writer(&store).with_dead_letter_file(&path);
+        assert_eq!(second_run.replay().await.unwrap(), 1);
+        let remaining = read_all(&path).await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+
+        assert_eq!(store.stored(), vec!["0x01"]);
+        assert!(remaining.is_empty());
+    }
+}
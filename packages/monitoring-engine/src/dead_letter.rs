@@ -0,0 +1,130 @@
+//! Dead-letter store for blocks whose transactions could not be decoded
+//!
+//! When extraction fails (usually after a runtime upgrade the engine's
+//! metadata does not yet understand) the block used to be logged and
+//! dropped. With a dead-letter file configured, each failure is appended as
+//! one JSON line holding the block, the error and the raw extrinsic bytes
+//! when they could be fetched, so the block can be inspected and replayed
+//! once decoding is fixed.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// A block whose transactions could not be extracted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub chain: String,
+    pub block_number: u64,
+    /// Block hash (0x-prefixed hex)
+    pub block_hash: String,
+    pub error: String,
+    /// Raw SCALE-encoded extrinsics of the block (0x-prefixed hex); empty
+    /// when the block body could not be fetched either
+    #[serde(default)]
+    pub extrinsics: Vec<String>,
+    /// When the failure was recorded (Unix seconds)
+    pub recorded_at: u64,
+}
+
+impl DeadLetter {
+    pub fn new(
+        chain: &str,
+        block_number: u64,
+        block_hash: &[u8],
+        error: &impl std::fmt::Display,
+        extrinsics: &[Vec<u8>],
+    ) -> Self {
+        Self {
+            chain: chain.to_string(),
+            block_number,
+            block_hash: format!("0x{}", hex::encode(block_hash)),
+            error: error.to_string(),
+            extrinsics: extrinsics
+                .iter()
+                .map(|bytes| format!("0x{}", hex::encode(bytes)))
+                .collect(),
+            recorded_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Append-only JSON Lines file of [`DeadLetter`]s
+pub struct DeadLetterStore {
+    path: PathBuf,
+    /// Serializes appends so concurrent records never interleave
+    write_lock: Mutex<()>,
+}
+
+impl DeadLetterStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a record to the file, creating it if needed
+    pub async fn record(&self, letter: &DeadLetter) -> anyhow::Result<()> {
+        let mut line = serde_json::to_vec(letter)?;
+        line.push(b'\n');
+
+        let _guard = self.write_lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(&line).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// Read every record in the file; a missing file has none
+    pub async fn read_all(&self) -> anyhow::Result<Vec<DeadLetter>> {
+        let contents = match tokio::fs::read_to_string(&self.path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(Into::into))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_records_append_and_read_back() {
+        let path = std::env::temp_dir().join(format!("dead-letters-{}.jsonl", std::process::id()));
+        let store = DeadLetterStore::new(&path);
+        assert!(store.read_all().await.unwrap().is_empty());
+
+        let first = DeadLetter::new("westend", 7, &[0xab; 32], &"Failed to get extrinsics", &[vec![0x04, 0x01]]);
+        let second = DeadLetter::new("westend", 8, &[0xcd; 32], &"Failed to get block", &[]);
+        store.record(&first).await.unwrap();
+        store.record(&second).await.unwrap();
+
+        let letters = store.read_all().await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+
+        assert_eq!(letters, vec![first, second]);
+        assert_eq!(letters[0].extrinsics, vec!["0x0401"]);
+        assert!(letters[0].block_hash.starts_with("0xabab"));
+    }
+}
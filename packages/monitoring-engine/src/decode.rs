@@ -0,0 +1,563 @@
+//! Metadata-driven decoding of SCALE data into JSON
+//!
+//! Event fields and call arguments arrive as raw SCALE bytes. Using the
+//! runtime's type registry, each field is decoded into a named JSON value so
+//! detectors can read e.g. `event_data.get("amount")`:
+//!
+//! - named structs become objects, tuples and sequences become arrays, and
+//!   single-field wrappers (`AccountId32`, `Compact<T>`) are unwrapped
+//! - byte sequences and arrays (`Vec<u8>`, `[u8; 32]`) become `0x` hex strings
+//! - integers become JSON numbers, or decimal strings when wider than 64 bits
+//! - unit enum variants become their name, others `{"Variant": fields}`
+//!
+//! Bytes that can't be decoded (unknown or opaque types) are kept as hex
+//! under `"raw"`, so nothing is silently dropped.
+
+use crate::types::{DecodedCall, ParsedTransaction};
+use crate::{Error, Result};
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde_json::{Map, Value as JsonValue};
+use subxt::ext::codec::{Compact, Decode};
+use subxt::ext::scale_value::{self, Composite, Primitive, Value, ValueDef};
+use subxt::ext::sp_core::hashing::blake2_256;
+use subxt::Metadata;
+
+/// Key holding the hex of bytes that could not be decoded
+pub const RAW_KEY: &str = "raw";
+
+/// Extrinsic format version this module decodes
+const EXTRINSIC_VERSION: u8 = 4;
+
+/// Version byte bit marking a signed extrinsic
+const SIGNED_BIT: u8 = 0b1000_0000;
+
+/// Caller recorded for unsigned extrinsics
+const UNSIGNED_CALLER: &str = "unsigned";
+
+/// Decode `bytes` holding `fields` (name and type ID, in order) into a JSON object
+///
+/// Unnamed fields are keyed by position. Decoding stops at the first field
+/// whose type can't be decoded; it and the remaining bytes go under `"raw"`.
+pub fn decode_fields(
+    bytes: &[u8],
+    fields: &[(Option<&str>, u32)],
+    types: &PortableRegistry,
+) -> JsonValue {
+    let mut object = Map::new();
+    let mut cursor = bytes;
+
+    for (index, (name, type_id)) in fields.iter().enumerate() {
+        let key = name.map_or_else(|| index.to_string(), str::to_string);
+        let mut attempt = cursor;
+
+        match scale_value::scale::decode_as_type(&mut attempt, type_id, types) {
+            Ok(value) => {
+                object.insert(key, value_to_json(&value, types));
+                cursor = attempt;
+            }
+            Err(e) => {
+                tracing::debug!("Failed to decode field {} (type {}): {}", key, type_id, e);
+                break;
+            }
+        }
+    }
+
+    if !cursor.is_empty() {
+        object.insert(RAW_KEY.to_string(), JsonValue::String(hex_string(cursor)));
+    }
+
+    JsonValue::Object(object)
+}
+
+/// Decode the arguments of `pallet.call` (matched case-insensitively)
+///
+/// Returns `Error::ParseError` when the metadata has no such pallet or call.
+pub fn decode_call(metadata: &Metadata, pallet: &str, call: &str, args: &[u8]) -> Result<DecodedCall> {
+    let pallet_metadata = metadata
+        .pallets()
+        .find(|p| p.name().eq_ignore_ascii_case(pallet))
+        .ok_or_else(|| Error::ParseError(format!("Unknown pallet {}", pallet)))?;
+
+    let variant = pallet_metadata
+        .call_variants()
+        .and_then(|variants| variants.iter().find(|v| v.name.eq_ignore_ascii_case(call)))
+        .ok_or_else(|| Error::ParseError(format!("Unknown call {}.{}", pallet, call)))?;
+
+    let fields: Vec<(Option<&str>, u32)> = variant
+        .fields
+        .iter()
+        .map(|field| (field.name.as_deref(), field.ty.id))
+        .collect();
+
+    Ok(DecodedCall {
+        pallet: pallet_metadata.name().to_string(),
+        call: variant.name.clone(),
+        args: decode_fields(args, &fields, metadata.types()),
+    })
+}
+
+/// Decode a SCALE-encoded extrinsic, e.g. one pending in the transaction pool
+///
+/// Expects the v4 layout: a compact length, the version byte (top bit set
+/// when signed), then for signed extrinsics the address, signature and
+/// signed extension data, and finally the pallet and call indices followed
+/// by the call arguments, which are decoded into `decoded_call`. The hash
+/// is the `blake2_256` of `bytes`, as the node computes it. The extrinsic is
+/// not in a block, so the block fields are left empty.
+pub fn decode_extrinsic(metadata: &Metadata, bytes: &[u8], timestamp: u64) -> Result<ParsedTransaction> {
+    let mut cursor = bytes;
+    Compact::<u32>::decode(&mut cursor)
+        .map_err(|e| Error::ParseError(format!("Invalid extrinsic length: {}", e)))?;
+
+    let [version, rest @ ..] = cursor else {
+        return Err(Error::ParseError("Empty extrinsic".to_string()));
+    };
+    if version & !SIGNED_BIT != EXTRINSIC_VERSION {
+        return Err(Error::ParseError(format!("Unsupported extrinsic version {}", version & !SIGNED_BIT)));
+    }
+    cursor = rest;
+
+    let types = metadata.types();
+    let extrinsic = metadata.extrinsic();
+    let mut caller = UNSIGNED_CALLER.to_string();
+    let mut signature = None;
+    let mut nonce = None;
+
+    if version & SIGNED_BIT != 0 {
+        let address = decode_value(&mut cursor, extrinsic.address_ty(), types, "address")?;
+        caller = account_string(&value_to_json(&address, types));
+
+        let signature_start = cursor;
+        decode_value(&mut cursor, extrinsic.signature_ty(), types, "signature")?;
+        signature = Some(signature_start[..signature_start.len() - cursor.len()].to_vec());
+
+        let extensions = extrinsic.signed_extensions();
+        if extensions.is_empty() {
+            decode_value(&mut cursor, extrinsic.extra_ty(), types, "extra")?;
+        }
+        for extension in extensions {
+            let value = decode_value(&mut cursor, extension.extra_ty(), types, extension.identifier())?;
+            if extension.identifier() == "CheckNonce" {
+                nonce = value_to_json(&value, types).as_u64();
+            }
+        }
+    }
+
+    let [pallet_index, call_index, args @ ..] = cursor else {
+        return Err(Error::ParseError("Extrinsic has no call".to_string()));
+    };
+    let pallet = metadata
+        .pallet_by_index(*pallet_index)
+        .ok_or_else(|| Error::ParseError(format!("Unknown pallet index {}", pallet_index)))?;
+    let call = pallet
+        .call_variant_by_index(*call_index)
+        .ok_or_else(|| Error::ParseError(format!("Unknown call index {} in {}", call_index, pallet.name())))?;
+
+    let tx = ParsedTransaction {
+        hash: hex_string(&blake2_256(bytes)),
+        block_number: 0,
+        block_hash: String::new(),
+        index: 0,
+        caller,
+        pallet: pallet.name().to_string(),
+        call: call.name.clone(),
+        args: args.to_vec(),
+        signature,
+        nonce,
+        timestamp,
+        success: true,
+        decoded_call: Default::default(),
+    };
+    tx.decode_call(metadata)?;
+
+    Ok(tx)
+}
+
+/// Decode the extrinsics of a block, in order
+///
+/// Each transaction is decoded as by [`decode_extrinsic`] and placed at
+/// its index in the block. Fails on the first extrinsic that can't be
+/// decoded, so the block can be captured whole.
+pub fn decode_block(
+    metadata: &Metadata,
+    extrinsics: &[Vec<u8>],
+    block_number: u64,
+    block_hash: &str,
+    timestamp: u64,
+) -> Result<Vec<ParsedTransaction>> {
+    extrinsics
+        .iter()
+        .enumerate()
+        .map(|(index, bytes)| {
+            let mut tx = decode_extrinsic(metadata, bytes, timestamp).map_err(|e| {
+                Error::ParseError(format!("Extrinsic {} of block #{}: {}", index, block_number, e))
+            })?;
+            tx.block_number = block_number;
+            tx.block_hash = block_hash.to_string();
+            tx.index = index as u32;
+            Ok(tx)
+        })
+        .collect()
+}
+
+/// Decode one value of `type_id`, advancing `cursor` past it
+fn decode_value(cursor: &mut &[u8], type_id: u32, types: &PortableRegistry, what: &str) -> Result<Value<u32>> {
+    scale_value::scale::decode_as_type(cursor, &type_id, types)
+        .map_err(|e| Error::ParseError(format!("Failed to decode extrinsic {}: {}", what, e)))
+}
+
+/// Account of a decoded address: the `0x` hex of `MultiAddress::Id` or a
+/// plain account ID, otherwise the address as JSON
+fn account_string(address: &JsonValue) -> String {
+    match address {
+        JsonValue::String(account) => account.clone(),
+        JsonValue::Object(variant) if variant.len() == 1 => match variant.values().next() {
+            Some(JsonValue::String(account)) => account.clone(),
+            _ => address.to_string(),
+        },
+        _ => address.to_string(),
+    }
+}
+
+/// JSON representation of a decoded value
+pub fn value_to_json(value: &Value<u32>, types: &PortableRegistry) -> JsonValue {
+    match &value.value {
+        ValueDef::Composite(composite) => {
+            if is_byte_container(value.context, types) {
+                if let Some(bytes) = composite_bytes(composite) {
+                    return JsonValue::String(hex_string(&bytes));
+                }
+            }
+            composite_to_json(composite, types)
+        }
+        ValueDef::Variant(variant) => match &variant.values {
+            Composite::Named(fields) if fields.is_empty() => JsonValue::String(variant.name.clone()),
+            Composite::Unnamed(values) if values.is_empty() => JsonValue::String(variant.name.clone()),
+            values => {
+                let mut object = Map::new();
+                object.insert(variant.name.clone(), composite_to_json(values, types));
+                JsonValue::Object(object)
+            }
+        },
+        ValueDef::BitSequence(bits) => {
+            JsonValue::String(bits.iter().map(|bit| if bit { '1' } else { '0' }).collect())
+        }
+        ValueDef::Primitive(primitive) => primitive_to_json(primitive),
+    }
+}
+
+fn composite_to_json(composite: &Composite<u32>, types: &PortableRegistry) -> JsonValue {
+    match composite {
+        Composite::Named(fields) => JsonValue::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), value_to_json(value, types)))
+                .collect(),
+        ),
+        // Newtype wrapper: expose the inner value directly
+        Composite::Unnamed(values) if values.len() == 1 => value_to_json(&values[0], types),
+        Composite::Unnamed(values) => {
+            JsonValue::Array(values.iter().map(|value| value_to_json(value, types)).collect())
+        }
+    }
+}
+
+fn primitive_to_json(primitive: &Primitive) -> JsonValue {
+    match primitive {
+        Primitive::Bool(b) => JsonValue::Bool(*b),
+        Primitive::Char(c) => JsonValue::String(c.to_string()),
+        Primitive::String(s) => JsonValue::String(s.clone()),
+        Primitive::U128(n) => match u64::try_from(*n) {
+            Ok(n) => JsonValue::from(n),
+            Err(_) => JsonValue::String(n.to_string()),
+        },
+        Primitive::I128(n) => match i64::try_from(*n) {
+            Ok(n) => JsonValue::from(n),
+            Err(_) => JsonValue::String(n.to_string()),
+        },
+        Primitive::U256(bytes) | Primitive::I256(bytes) => JsonValue::String(hex_string(bytes)),
+    }
+}
+
+/// Whether the type is a sequence or array of `u8`
+fn is_byte_container(type_id: u32, types: &PortableRegistry) -> bool {
+    let element = match types.resolve(type_id).map(|ty| &ty.type_def) {
+        Some(TypeDef::Sequence(sequence)) => sequence.type_param.id,
+        Some(TypeDef::Array(array)) => array.type_param.id,
+        _ => return false,
+    };
+
+    matches!(
+        types.resolve(element).map(|ty| &ty.type_def),
+        Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+    )
+}
+
+/// Bytes of a composite whose values are all `u8`
+fn composite_bytes(composite: &Composite<u32>) -> Option<Vec<u8>> {
+    composite
+        .values()
+        .map(|value| match &value.value {
+            ValueDef::Primitive(Primitive::U128(n)) => u8::try_from(*n).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 32-byte account of a decoded `AccountId32` (`"0x.."`) or 32-byte
+/// `MultiAddress` (`{"Id": "0x.."}`, `{"Address32": "0x.."}`)
+pub(crate) fn json_account(value: &JsonValue) -> Option<[u8; 32]> {
+    match value {
+        JsonValue::String(_) => json_bytes(value)?.try_into().ok(),
+        JsonValue::Object(object) => object
+            .get("Id")
+            .or_else(|| object.get("Address32"))
+            .and_then(json_account),
+        _ => None,
+    }
+}
+
+/// Bytes of a decoded `Vec<u8>` or byte array (`"0x.."`)
+pub(crate) fn json_bytes(value: &JsonValue) -> Option<Vec<u8>> {
+    hex::decode(value.as_str()?.strip_prefix("0x")?).ok()
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ParsedTransaction;
+    use scale_info::build::{Fields, Variants};
+    use scale_info::{meta_type, Path, Registry, Type, TypeInfo};
+    use subxt::ext::codec::{Compact, Decode, Encode};
+    use subxt::ext::frame_metadata::v15::{
+        CustomMetadata, ExtrinsicMetadata, OuterEnums, PalletCallMetadata, PalletMetadata,
+        RuntimeMetadataV15,
+    };
+    use subxt::ext::frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed, META_RESERVED};
+    use subxt::ext::sp_core::crypto::AccountId32;
+    use subxt::ext::sp_runtime::MultiAddress;
+
+    type Address = MultiAddress<AccountId32, ()>;
+
+    /// `pallet_balances::Call`, reduced to `transfer_keep_alive`
+    struct BalancesCall;
+
+    impl TypeInfo for BalancesCall {
+        type Identity = Self;
+
+        fn type_info() -> Type {
+            Type::builder()
+                .path(Path::new("Call", "pallet_balances::pallet"))
+                .variant(Variants::new().variant("transfer_keep_alive", |v| {
+                    v.index(3).fields(
+                        Fields::named()
+                            .field(|f| f.ty::<Address>().name("dest"))
+                            .field(|f| f.compact::<u128>().name("value")),
+                    )
+                }))
+        }
+    }
+
+    /// Runtime metadata with only the `Balances` pallet's calls
+    fn balances_metadata() -> Metadata {
+        let pallets = vec![PalletMetadata {
+            name: "Balances",
+            storage: None,
+            calls: Some(PalletCallMetadata { ty: meta_type::<BalancesCall>() }),
+            event: None,
+            constants: vec![],
+            error: None,
+            index: 10,
+            docs: vec![],
+        }];
+        let extrinsic = ExtrinsicMetadata {
+            version: 4,
+            address_ty: meta_type::<Address>(),
+            call_ty: meta_type::<BalancesCall>(),
+            signature_ty: meta_type::<()>(),
+            extra_ty: meta_type::<()>(),
+            signed_extensions: vec![],
+        };
+        let outer_enums = OuterEnums {
+            call_enum_ty: meta_type::<BalancesCall>(),
+            event_enum_ty: meta_type::<()>(),
+            error_enum_ty: meta_type::<()>(),
+        };
+        let runtime = RuntimeMetadataV15::new(
+            pallets,
+            extrinsic,
+            meta_type::<()>(),
+            vec![],
+            outer_enums,
+            CustomMetadata { map: Default::default() },
+        );
+
+        let bytes = RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V15(runtime)).encode();
+        Metadata::decode(&mut bytes.as_slice()).expect("valid metadata")
+    }
+
+    fn transfer_keep_alive(args: Vec<u8>) -> ParsedTransaction {
+        ParsedTransaction {
+            hash: "0xtransfer".to_string(),
+            block_number: 1,
+            block_hash: "0xblock1".to_string(),
+            index: 1,
+            caller: "alice".to_string(),
+            pallet: "balances".to_string(),
+            call: "transfer_keep_alive".to_string(),
+            args,
+            signature: None,
+            nonce: Some(0),
+            timestamp: 1234567890,
+            success: true,
+            decoded_call: Default::default(),
+        }
+    }
+
+    /// Registry with the types of `Balances::Transfer { from, to, amount }`
+    fn transfer_registry() -> (PortableRegistry, u32, u32) {
+        let mut registry = Registry::new();
+        let account = registry.register_type(&meta_type::<AccountId32>()).id;
+        let balance = registry.register_type(&meta_type::<u128>()).id;
+        (registry.into(), account, balance)
+    }
+
+    #[test]
+    fn test_decode_balances_transfer() {
+        let (types, account, balance) = transfer_registry();
+        let from = AccountId32::new([1; 32]);
+        let to = AccountId32::new([2; 32]);
+        let bytes = (from, to, 1_500_000_000_000u128).encode();
+
+        let fields = [(Some("from"), account), (Some("to"), account), (Some("amount"), balance)];
+        let data = decode_fields(&bytes, &fields, &types);
+
+        assert_eq!(data["from"], JsonValue::String(format!("0x{}", "01".repeat(32))));
+        assert_eq!(data["to"], JsonValue::String(format!("0x{}", "02".repeat(32))));
+        assert_eq!(data.get("amount").and_then(|v| v.as_u64()), Some(1_500_000_000_000));
+        assert!(data.get(RAW_KEY).is_none());
+    }
+
+    #[test]
+    fn test_wide_integers_are_strings() {
+        let (types, _, balance) = transfer_registry();
+        let bytes = u128::MAX.encode();
+
+        let data = decode_fields(&bytes, &[(Some("amount"), balance)], &types);
+        assert_eq!(data["amount"], JsonValue::String(u128::MAX.to_string()));
+    }
+
+    #[test]
+    fn test_undecodable_bytes_are_hex() {
+        let (types, account, balance) = transfer_registry();
+        let mut bytes = AccountId32::new([7; 32]).encode();
+        bytes.extend_from_slice(&[0xde, 0xad]);
+
+        // Unknown type ID for the second field, unnamed first field
+        let data = decode_fields(&bytes, &[(None, account), (Some("amount"), balance + 100)], &types);
+
+        assert_eq!(data["0"], JsonValue::String(format!("0x{}", "07".repeat(32))));
+        assert_eq!(data[RAW_KEY], JsonValue::String("0xdead".to_string()));
+        assert!(data.get("amount").is_none());
+    }
+
+    #[test]
+    fn test_decode_transfer_keep_alive_call() {
+        let metadata = balances_metadata();
+        let dest = Address::Id(AccountId32::new([9; 32]));
+        let tx = transfer_keep_alive((dest, Compact(2_000_000_000_000u128)).encode());
+
+        let decoded = tx.decode_call(&metadata).unwrap();
+        assert_eq!(decoded.pallet, "Balances");
+        assert_eq!(decoded.call, "transfer_keep_alive");
+        assert_eq!(decoded.args["dest"]["Id"], JsonValue::String(format!("0x{}", "09".repeat(32))));
+        assert_eq!(decoded.args.get("value").and_then(|v| v.as_u64()), Some(2_000_000_000_000));
+        assert!(decoded.args.get(RAW_KEY).is_none());
+        assert_eq!(json_account(&decoded.args["dest"]), Some([9; 32]));
+        assert_eq!(json_bytes(&decoded.args["dest"]["Id"]), Some(vec![9; 32]));
+        assert_eq!(json_account(&decoded.args["value"]), None);
+
+        // The decoded form is cached on the transaction
+        assert!(std::ptr::eq(decoded, tx.decode_call(&metadata).unwrap()));
+    }
+
+    #[test]
+    fn test_decode_signed_extrinsic() {
+        let metadata = balances_metadata();
+        let sender = Address::Id(AccountId32::new([4; 32]));
+        let dest = Address::Id(AccountId32::new([9; 32]));
+        let args = (dest, Compact(5_000u128)).encode();
+
+        // Signed v4: address, no signature or extension data in this runtime
+        let mut body = vec![EXTRINSIC_VERSION | SIGNED_BIT];
+        body.extend(sender.encode());
+        body.extend([10, 3]);
+        body.extend(&args);
+        let bytes = body.encode();
+
+        let tx = decode_extrinsic(&metadata, &bytes, 42).unwrap();
+        assert_eq!(tx.hash, hex_string(&blake2_256(&bytes)));
+        assert_eq!(tx.caller, format!("0x{}", "04".repeat(32)));
+        assert_eq!((tx.pallet.as_str(), tx.call.as_str()), ("Balances", "transfer_keep_alive"));
+        assert_eq!(tx.args, args);
+        assert_eq!(tx.signature, Some(vec![]));
+        assert_eq!(tx.timestamp, 42);
+        assert_eq!(tx.transfer_destination(), Some([9; 32]));
+        assert_eq!(tx.transfer_amount(), Some(5_000));
+
+        let decoded = tx.decoded_call.get().expect("call decoded with the extrinsic");
+        assert_eq!(decoded.args.get("value").and_then(|v| v.as_u64()), Some(5_000));
+    }
+
+    #[test]
+    fn test_decode_block_places_extrinsics() {
+        let metadata = balances_metadata();
+        let dest = Address::Id(AccountId32::new([9; 32]));
+        let call = |value: u128| {
+            let mut body = vec![EXTRINSIC_VERSION, 10, 3];
+            body.extend((dest.clone(), Compact(value)).encode());
+            body.encode()
+        };
+
+        let txs = decode_block(&metadata, &[call(1), call(2)], 7, "0xblock7", 42).unwrap();
+        assert_eq!(txs.len(), 2);
+        assert_eq!((txs[1].block_number, txs[1].block_hash.as_str(), txs[1].index), (7, "0xblock7", 1));
+        assert_eq!(txs[1].decoded_call.get().unwrap().args["value"].as_u64(), Some(2));
+
+        let undecodable = vec![EXTRINSIC_VERSION, 10, 7].encode();
+        assert!(matches!(
+            decode_block(&metadata, &[call(1), undecodable], 7, "0xblock7", 42),
+            Err(Error::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_extrinsic_rejects_unknown_call() {
+        let metadata = balances_metadata();
+
+        let unsigned = vec![EXTRINSIC_VERSION, 10, 3].encode();
+        let tx = decode_extrinsic(&metadata, &unsigned, 0).unwrap();
+        assert_eq!(tx.caller, UNSIGNED_CALLER);
+        assert!(tx.signature.is_none());
+
+        let unknown_call = vec![EXTRINSIC_VERSION, 10, 7].encode();
+        assert!(matches!(decode_extrinsic(&metadata, &unknown_call, 0), Err(Error::ParseError(_))));
+
+        let old_version = vec![3, 10, 3].encode();
+        assert!(matches!(decode_extrinsic(&metadata, &old_version, 0), Err(Error::ParseError(_))));
+    }
+
+    #[test]
+    fn test_decode_unknown_call_fails() {
+        let mut tx = transfer_keep_alive(vec![]);
+        tx.call = "transfer_everything".to_string();
+
+        let result = tx.decode_call(&balances_metadata());
+        assert!(matches!(result, Err(Error::ParseError(_))));
+        assert!(tx.decoded_call.get().is_none());
+    }
+}
@@ -0,0 +1,234 @@
+//! Rate-limited logging of detections
+//!
+//! A detector that fires on every block would otherwise flood the logs with
+//! identical warnings. Log lines are keyed on (detector, caller, pattern):
+//! the first `DETECTION_LOG_BURST` detections of a key per window are logged
+//! in full, later ones are counted, and the next logged detection after the
+//! window closes reports how many were suppressed.
+//!
+//! This only affects log volume; alerts are raised for every detection.
+
+use crate::types::{DetectionResult, ParsedTransaction};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Length of a rate-limiting window
+pub const DETECTION_LOG_WINDOW: Duration = Duration::from_secs(60);
+
+/// Detections of the same key logged in full per window
+pub const DETECTION_LOG_BURST: u32 = 3;
+
+/// Keys tracked before expired windows are pruned
+const MAX_TRACKED_KEYS: usize = 10_000;
+
+/// Whether a detection should be logged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogDecision {
+    /// Log the detection, reporting detections suppressed in the previous window
+    Emit { suppressed: u64 },
+    /// Skip the log line
+    Suppress,
+}
+
+/// Log budget of a single key
+#[derive(Debug)]
+struct LogWindow {
+    started: Instant,
+    logged: u32,
+    suppressed: u64,
+}
+
+/// Rate limiter for repeated identical detection log lines
+#[derive(Debug)]
+pub struct DetectionLogLimiter {
+    window: Duration,
+    burst: u32,
+    windows: Mutex<HashMap<(String, String, String), LogWindow>>,
+}
+
+impl DetectionLogLimiter {
+    pub fn new(window: Duration, burst: u32) -> Self {
+        Self {
+            window,
+            burst,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Decide whether to log a detection of `pattern` by `detector` for `caller`
+    pub fn check(&self, detector: &str, caller: &str, pattern: &str) -> LogDecision {
+        self.check_at(detector, caller, pattern, Instant::now())
+    }
+
+    fn check_at(&self, detector: &str, caller: &str, pattern: &str, now: Instant) -> LogDecision {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+
+        if windows.len() >= MAX_TRACKED_KEYS {
+            let window = self.window;
+            windows.retain(|_, w| now.duration_since(w.started) < window);
+        }
+
+        let key = (detector.to_string(), caller.to_string(), pattern.to_string());
+        let entry = windows.entry(key).or_insert(LogWindow {
+            started: now,
+            logged: 0,
+            suppressed: 0,
+        });
+
+        if now.duration_since(entry.started) >= self.window {
+            let suppressed = entry.suppressed;
+            *entry = LogWindow {
+                started: now,
+                logged: 1,
+                suppressed: 0,
+            };
+            return LogDecision::Emit { suppressed };
+        }
+
+        if entry.logged < self.burst {
+            entry.logged += 1;
+            LogDecision::Emit { suppressed: 0 }
+        } else {
+            entry.suppressed += 1;
+            LogDecision::Suppress
+        }
+    }
+}
+
+impl Default for DetectionLogLimiter {
+    fn default() -> Self {
+        Self::new(DETECTION_LOG_WINDOW, DETECTION_LOG_BURST)
+    }
+}
+
+/// Log a detection, subject to rate limiting
+pub fn log_detection(
+    limiter: &DetectionLogLimiter,
+    detector_name: &str,
+    tx: &ParsedTransaction,
+    result: &DetectionResult,
+) {
+    let pattern = result.pattern.to_string();
+    let LogDecision::Emit { suppressed } = limiter.check(detector_name, &tx.caller, &pattern) else {
+        tracing::debug!("{} detection in tx {} (log rate-limited)", detector_name, tx.hash);
+        return;
+    };
+
+    if suppressed > 0 {
+        tracing::warn!(
+            "{} identical {} detections by {} for {} in the last {}s",
+            suppressed,
+            pattern,
+            detector_name,
+            tx.caller,
+            limiter.window.as_secs()
+        );
+    }
+
+    tracing::warn!(
+        "🚨 {} detected suspicious activity in tx {}",
+        detector_name,
+        tx.hash
+    );
+    tracing::warn!("   Confidence: {:.2}%", result.confidence * 100.0);
+    tracing::warn!("   Description: {}", result.description);
+    tracing::warn!("   Evidence: {:?}", result.evidence);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AttackPattern;
+    use std::sync::Arc;
+
+    /// Writer collecting formatted log output
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn transaction(caller: &str) -> ParsedTransaction {
+        ParsedTransaction {
+            hash: "0x01".to_string(),
+            block_number: 1,
+            block_hash: "0xblock1".to_string(),
+            index: 0,
+            caller: caller.to_string(),
+            pallet: "Balances".to_string(),
+            call: "transfer".to_string(),
+            args: vec![],
+            signature: None,
+            nonce: None,
+            timestamp: 1234567890,
+            success: true,
+            decoded_call: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_identical_detections_are_rate_limited() {
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+
+        let limiter = DetectionLogLimiter::default();
+        let tx = transaction("alice");
+        let result = DetectionResult::detected(
+            AttackPattern::VolumeAnomaly,
+            0.7,
+            "Volume spike".to_string(),
+            vec![],
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..100 {
+                log_detection(&limiter, "Volume Anomaly Detector", &tx, &result);
+            }
+        });
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let lines = output.lines().count();
+        assert!(lines > 0);
+        assert!(lines <= 4 * DETECTION_LOG_BURST as usize, "{} log lines", lines);
+    }
+
+    #[test]
+    fn test_suppressed_count_reported_after_window() {
+        let limiter = DetectionLogLimiter::new(Duration::from_secs(60), 2);
+        let start = Instant::now();
+
+        for _ in 0..2 {
+            assert_eq!(
+                limiter.check_at("MEV Detector", "bob", "MEV", start),
+                LogDecision::Emit { suppressed: 0 }
+            );
+        }
+        for _ in 0..5 {
+            assert_eq!(limiter.check_at("MEV Detector", "bob", "MEV", start), LogDecision::Suppress);
+        }
+        // Other callers have their own budget
+        assert_eq!(
+            limiter.check_at("MEV Detector", "carol", "MEV", start),
+            LogDecision::Emit { suppressed: 0 }
+        );
+
+        let later = start + Duration::from_secs(61);
+        assert_eq!(
+            limiter.check_at("MEV Detector", "bob", "MEV", later),
+            LogDecision::Emit { suppressed: 5 }
+        );
+    }
+}
@@ -0,0 +1,408 @@
+//! Allowance abuse detector
+//!
+//! Approval phishing tricks users into granting a large or unlimited asset
+//! approval, which the attacker's delegate account then drains. The detector
+//! tracks `approve_transfer` calls on the `Assets` and `PoolAssets` pallets
+//! and flags:
+//! 1. A `transfer_approved` call whose approval was at least the large
+//!    approval floor, sent by the delegate (not the owner) within
+//!    `CORRELATION_WINDOW_BLOCKS` of the approval
+//!
+//! Confidence rises with the approval's magnitude (an unlimited approval
+//! scores highest) and with the drain's proximity to the approval (a drain
+//! in the same block scores highest).
+//!
+//! Approvals are read from the metadata-decoded call arguments
+//! (`ParsedTransaction::decoded_call`), so transactions whose call wasn't
+//! decoded are never flagged. `cancel_approval` forgets an approval.
+
+use crate::decode::json_account;
+use crate::detectors::{normalize, parse_account, ss58, Detector, DetectorManifest};
+use crate::types::{json_amount, AttackPattern, DetectionResult, ParsedTransaction, TransactionContext};
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Default approval amount (in the asset's smallest unit) considered large
+pub const DEFAULT_LARGE_APPROVAL_FLOOR: u128 = 1_000_000_000_000_000_000;
+
+/// Approvals at or above this amount are treated as unlimited
+const UNLIMITED_APPROVAL: u128 = u128::MAX / 2;
+
+/// Blocks after an approval within which a drain is correlated with it
+const CORRELATION_WINDOW_BLOCKS: u64 = 100;
+
+/// Confidence for a large approval drained at the end of the window
+const BASE_CONFIDENCE: f64 = 0.6;
+
+/// Confidence added for an unlimited approval
+const UNLIMITED_BONUS: f64 = 0.2;
+
+/// Confidence added for a large (but bounded) approval
+const LARGE_BONUS: f64 = 0.1;
+
+/// Confidence added for a drain in the same block, decaying over the window
+const PROXIMITY_BONUS: f64 = 0.15;
+
+/// Asset pallets with `u32` asset IDs
+const ASSET_PALLETS: &[&str] = &["assets", "poolassets"];
+
+/// An outstanding approval
+#[derive(Debug, Clone)]
+struct Approval {
+    amount: u128,
+    block_number: u64,
+    tx_hash: String,
+}
+
+/// Approvals keyed by (asset ID, owner, delegate)
+type ApprovalKey = (u32, [u8; 32], [u8; 32]);
+
+/// Detector for large approvals drained by their delegate
+pub struct AllowanceAbuseDetector {
+    enabled: bool,
+    large_approval_floor: u128,
+    approvals: Arc<RwLock<HashMap<ApprovalKey, Approval>>>,
+}
+
+impl AllowanceAbuseDetector {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            large_approval_floor: DEFAULT_LARGE_APPROVAL_FLOOR,
+            approvals: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Set the approval amount above which a drained approval is flagged
+    pub fn with_large_approval_floor(mut self, floor: u128) -> Self {
+        self.large_approval_floor = floor;
+        self
+    }
+
+    /// Record `approve_transfer(id, delegate, amount)`; approvals accumulate like on-chain
+    async fn record_approval(&self, tx: &ParsedTransaction, owner: [u8; 32]) {
+        let Some((asset, delegate, amount)) = decoded_args(tx).and_then(decode_approval) else {
+            return;
+        };
+
+        let mut approvals = self.approvals.write().await;
+        let approval = approvals.entry((asset, owner, delegate)).or_insert(Approval {
+            amount: 0,
+            block_number: tx.block_number,
+            tx_hash: tx.hash.clone(),
+        });
+        approval.amount = approval.amount.saturating_add(amount);
+        approval.block_number = tx.block_number;
+        approval.tx_hash = tx.hash.clone();
+    }
+
+    /// Forget an approval on `cancel_approval(id, delegate)`
+    async fn cancel_approval(&self, tx: &ParsedTransaction, owner: [u8; 32]) {
+        let Some(args) = decoded_args(tx) else {
+            return;
+        };
+        let (Some(asset), Some(delegate)) = (asset_id(args), args.get("delegate").and_then(json_account)) else {
+            return;
+        };
+        self.approvals.write().await.remove(&(asset, owner, delegate));
+    }
+
+    /// Check `transfer_approved(id, owner, destination, amount)` against recorded approvals
+    async fn check_drain(&self, tx: &ParsedTransaction, delegate: [u8; 32]) -> DetectionResult {
+        let Some(drain) = decoded_args(tx).and_then(decode_drain) else {
+            return DetectionResult::no_detection();
+        };
+        if drain.owner == delegate {
+            return DetectionResult::no_detection();
+        }
+
+        let approvals = self.approvals.read().await;
+        let Some(approval) = approvals.get(&(drain.asset, drain.owner, delegate)) else {
+            return DetectionResult::no_detection();
+        };
+        if approval.amount < self.large_approval_floor {
+            return DetectionResult::no_detection();
+        }
+
+        let blocks_later = tx.block_number.saturating_sub(approval.block_number);
+        let unlimited = approval.amount >= UNLIMITED_APPROVAL;
+        let confidence = confidence(unlimited, blocks_later);
+
+        let approved = if unlimited {
+            "an unlimited amount".to_string()
+        } else {
+            approval.amount.to_string()
+        };
+        let evidence = vec![
+            format!(
+                "{} approved {} of asset {} to {} in block #{} (tx {})",
+                ss58(drain.owner),
+                approved,
+                drain.asset,
+                ss58(delegate),
+                approval.block_number,
+                approval.tx_hash
+            ),
+            format!(
+                "Delegate moved {} to {} via transfer_approved {} block(s) later",
+                drain.amount,
+                ss58(drain.destination),
+                blocks_later
+            ),
+        ];
+
+        DetectionResult::detected(
+            AttackPattern::AllowanceAbuse,
+            confidence,
+            format!(
+                "Approval of {} for asset {} drained by delegate {}",
+                approved,
+                drain.asset,
+                ss58(delegate)
+            ),
+            evidence,
+        )
+    }
+
+    /// Drop approvals too old to correlate with a drain
+    async fn prune(&self, block_number: u64) {
+        self.approvals
+            .write()
+            .await
+            .retain(|_, a| {
+                block_number.saturating_sub(a.block_number) <= CORRELATION_WINDOW_BLOCKS
+            });
+    }
+}
+
+impl Default for AllowanceAbuseDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Confidence for a drain of a large approval `blocks_later` blocks after it was granted
+fn confidence(unlimited: bool, blocks_later: u64) -> f64 {
+    let magnitude = if unlimited { UNLIMITED_BONUS } else { LARGE_BONUS };
+    let elapsed = blocks_later.min(CORRELATION_WINDOW_BLOCKS) as f64;
+    let proximity = 1.0 - elapsed / CORRELATION_WINDOW_BLOCKS as f64;
+    BASE_CONFIDENCE + magnitude + PROXIMITY_BONUS * proximity
+}
+
+/// A decoded `transfer_approved` call
+struct Drain {
+    asset: u32,
+    owner: [u8; 32],
+    destination: [u8; 32],
+    amount: u128,
+}
+
+/// Metadata-decoded arguments of the transaction's call
+fn decoded_args(tx: &ParsedTransaction) -> Option<&JsonValue> {
+    tx.decoded_call.get().map(|decoded| &decoded.args)
+}
+
+/// Asset ID in the `id` argument
+fn asset_id(args: &JsonValue) -> Option<u32> {
+    u32::try_from(json_amount(args.get("id")?)?).ok()
+}
+
+/// Decode `approve_transfer(id, delegate, amount)` into (asset ID, delegate, amount)
+fn decode_approval(args: &JsonValue) -> Option<(u32, [u8; 32], u128)> {
+    Some((
+        asset_id(args)?,
+        json_account(args.get("delegate")?)?,
+        json_amount(args.get("amount")?)?,
+    ))
+}
+
+/// Decode `transfer_approved(id, owner, destination, amount)`
+fn decode_drain(args: &JsonValue) -> Option<Drain> {
+    Some(Drain {
+        asset: asset_id(args)?,
+        owner: json_account(args.get("owner")?)?,
+        destination: json_account(args.get("destination")?)?,
+        amount: json_amount(args.get("amount")?)?,
+    })
+}
+
+#[async_trait]
+impl Detector for AllowanceAbuseDetector {
+    fn name(&self) -> &str {
+        "Allowance Abuse Detector"
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Large asset approvals drained by the delegate shortly after being granted")
+            .with_patterns(&[AttackPattern::AllowanceAbuse])
+            .with_pallets(&["Assets", "PoolAssets"])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        let tx = &ctx.transaction;
+        if !tx.success || !ASSET_PALLETS.contains(&normalize(&tx.pallet).as_str()) {
+            return DetectionResult::no_detection();
+        }
+        let Some(caller) = parse_account(&tx.caller) else {
+            return DetectionResult::no_detection();
+        };
+
+        self.prune(tx.block_number).await;
+
+        match normalize(&tx.call).as_str() {
+            "approvetransfer" => {
+                self.record_approval(tx, caller).await;
+                DetectionResult::no_detection()
+            }
+            "cancelapproval" => {
+                self.cancel_approval(tx, caller).await;
+                DetectionResult::no_detection()
+            }
+            "transferapproved" => self.check_drain(tx, caller).await,
+            _ => DetectionResult::no_detection(),
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DecodedCall;
+
+    const ASSET: u8 = 7;
+
+    fn account(byte: u8) -> [u8; 32] {
+        [byte; 32]
+    }
+
+    /// `MultiAddress::Id` as decoded into JSON
+    fn address(account: [u8; 32]) -> JsonValue {
+        serde_json::json!({ "Id": format!("0x{}", hex::encode(account)) })
+    }
+
+    /// Balance as decoded into JSON: a number, or a decimal string above 64 bits
+    fn balance(value: u128) -> JsonValue {
+        match u64::try_from(value) {
+            Ok(value) => JsonValue::from(value),
+            Err(_) => JsonValue::String(value.to_string()),
+        }
+    }
+
+    fn call(
+        hash: &str,
+        block: u64,
+        caller: [u8; 32],
+        call: &str,
+        args: JsonValue,
+    ) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: hash.to_string(),
+                block_number: block,
+                block_hash: format!("0xblock{}", block),
+                index: 0,
+                caller: ss58(caller),
+                pallet: "Assets".to_string(),
+                call: call.to_string(),
+                args: vec![],
+                signature: None,
+                nonce: Some(1),
+                timestamp: 1234567890,
+                success: true,
+                decoded_call: std::sync::OnceLock::from(DecodedCall {
+                    pallet: "Assets".to_string(),
+                    call: call.to_string(),
+                    args,
+                }),
+            },
+            events: vec![],
+            state_changes: vec![],
+        }
+    }
+
+    type Account = [u8; 32];
+
+    fn approve(block: u64, owner: Account, delegate: Account, amount: u128) -> TransactionContext {
+        let args = serde_json::json!({ "id": ASSET, "delegate": address(delegate), "amount": balance(amount) });
+        call("0xa1", block, owner, "approve_transfer", args)
+    }
+
+    fn drain(block: u64, owner: Account, delegate: Account, amount: u128) -> TransactionContext {
+        let args = serde_json::json!({
+            "id": ASSET,
+            "owner": address(owner),
+            "destination": address(account(9)),
+            "amount": balance(amount),
+        });
+        call("0xd1", block, delegate, "transfer_approved", args)
+    }
+
+    #[tokio::test]
+    async fn test_undecoded_approval_is_ignored() {
+        let (victim, attacker) = (account(1), account(2));
+        let detector = AllowanceAbuseDetector::new();
+
+        let mut undecoded = approve(10, victim, attacker, u128::MAX);
+        undecoded.transaction.decoded_call = Default::default();
+        detector.analyze_transaction(&undecoded).await;
+
+        assert!(!detector.analyze_transaction(&drain(10, victim, attacker, 5_000_000)).await.detected);
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_approval_drained_in_same_block() {
+        let (victim, attacker) = (account(1), account(2));
+        let detector = AllowanceAbuseDetector::new();
+
+        let result = detector.analyze_transaction(&approve(10, victim, attacker, u128::MAX)).await;
+        assert!(!result.detected);
+
+        let result = detector.analyze_transaction(&drain(10, victim, attacker, 5_000_000)).await;
+        assert!(result.detected);
+        assert_eq!(result.pattern, AttackPattern::AllowanceAbuse);
+        assert!((result.confidence - 0.95).abs() < 1e-9);
+        assert!(result.evidence[0].contains("an unlimited amount"));
+    }
+
+    #[tokio::test]
+    async fn test_bounded_approval_not_flagged() {
+        let (owner, spender) = (account(1), account(2));
+        let detector = AllowanceAbuseDetector::new();
+
+        detector.analyze_transaction(&approve(10, owner, spender, 1_000_000_000_000)).await;
+        let result = detector
+            .analyze_transaction(&drain(10, owner, spender, 1_000_000_000_000))
+            .await;
+        assert!(!result.detected);
+    }
+
+    #[tokio::test]
+    async fn test_confidence_decays_with_distance() {
+        let (victim, attacker) = (account(1), account(2));
+        let detector = AllowanceAbuseDetector::new();
+
+        detector
+            .analyze_transaction(&approve(10, victim, attacker, DEFAULT_LARGE_APPROVAL_FLOOR))
+            .await;
+        let late = detector.analyze_transaction(&drain(60, victim, attacker, 1)).await;
+        assert!(late.detected);
+        assert!(late.confidence < confidence(false, 0));
+
+        // Outside the correlation window the approval is forgotten
+        let stale = detector
+            .analyze_transaction(&drain(10 + CORRELATION_WINDOW_BLOCKS + 1, victim, attacker, 1))
+            .await;
+        assert!(!stale.detected);
+    }
+}
@@ -0,0 +1,307 @@
+//! Unsupervised anomaly detector
+//!
+//! Flags transactions whose ML feature vector lies far from the centroid of
+//! recent transactions, without needing a trained model. Every feature is
+//! z-scored against a rolling window of recent vectors, so large-valued
+//! features (block number, timestamp, data size) weigh no more than the
+//! binary flags. The anomaly score is the root-mean-square of those
+//! z-scores: the normalized Euclidean distance to the centroid.
+
+use crate::detectors::{Detector, DetectorManifest};
+use crate::ml::FeatureExtractor;
+use crate::types::{AttackPattern, DetectionResult, TransactionContext};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Default normalized distance from the centroid that counts as an outlier
+pub const DEFAULT_DISTANCE_THRESHOLD: f64 = 3.0;
+
+/// Default number of recent feature vectors the centroid is computed over
+pub const DEFAULT_WINDOW_SIZE: usize = 500;
+
+/// Vectors observed before distances are trusted
+const MIN_HISTORY: usize = 50;
+
+/// Cap on a single feature's z-score, so one never-seen feature value
+/// can't make a transaction an outlier on its own
+const MAX_FEATURE_SCORE: f64 = 10.0;
+
+/// Standard deviation floor for features that have been constant
+const MIN_STD_DEV: f64 = 1e-3;
+
+/// Features reported as evidence for a flagged transaction
+const TOP_FEATURES: usize = 3;
+
+/// Detector for behavioral outliers relative to recent transactions
+pub struct UnsupervisedAnomalyDetector {
+    enabled: bool,
+    distance_threshold: f64,
+    window_size: usize,
+    history: Arc<RwLock<FeatureHistory>>,
+}
+
+/// Rolling window of recent feature vectors
+#[derive(Default, Serialize, Deserialize)]
+struct FeatureHistory {
+    vectors: VecDeque<Vec<f64>>,
+    /// Caller histories are cheap to rebuild, so they are not persisted
+    #[serde(skip)]
+    extractor: FeatureExtractor,
+}
+
+impl FeatureHistory {
+    /// Per-feature z-scores of `vector` against the window
+    fn z_scores(&self, vector: &[f64]) -> Vec<f64> {
+        let count = self.vectors.len() as f64;
+
+        (0..vector.len())
+            .map(|i| {
+                let mean = self.vectors.iter().map(|v| v[i]).sum::<f64>() / count;
+                let variance = self
+                    .vectors
+                    .iter()
+                    .map(|v| (v[i] - mean).powi(2))
+                    .sum::<f64>()
+                    / count;
+                let std_dev = variance.sqrt().max(MIN_STD_DEV);
+
+                ((vector[i] - mean) / std_dev).clamp(-MAX_FEATURE_SCORE, MAX_FEATURE_SCORE)
+            })
+            .collect()
+    }
+
+    fn push(&mut self, vector: Vec<f64>, window_size: usize) {
+        self.vectors.push_back(vector);
+        while self.vectors.len() > window_size {
+            self.vectors.pop_front();
+        }
+    }
+}
+
+impl UnsupervisedAnomalyDetector {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            distance_threshold: DEFAULT_DISTANCE_THRESHOLD,
+            window_size: DEFAULT_WINDOW_SIZE,
+            history: Arc::new(RwLock::new(FeatureHistory::default())),
+        }
+    }
+
+    /// Set the normalized distance from the centroid that counts as an outlier
+    pub fn with_distance_threshold(mut self, threshold: f64) -> Self {
+        self.distance_threshold = threshold;
+        self
+    }
+
+    /// Set how many recent feature vectors the centroid covers
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size.max(MIN_HISTORY);
+        self
+    }
+
+    /// Confidence grows with how far past the threshold the distance is
+    fn confidence(&self, distance: f64) -> f64 {
+        (0.6 + 0.35 * (1.0 - self.distance_threshold / distance)).min(0.95)
+    }
+}
+
+impl Default for UnsupervisedAnomalyDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Detector for UnsupervisedAnomalyDetector {
+    fn name(&self) -> &str {
+        "Unsupervised Anomaly Detector"
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Transactions whose feature vector lies far from the centroid of recent activity")
+            .with_patterns(&[AttackPattern::Anomaly])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        let mut history = self.history.write().await;
+        let features = history.extractor.extract_features(ctx);
+        let vector = FeatureExtractor::to_vector(&features);
+
+        if history.vectors.len() < MIN_HISTORY {
+            history.push(vector, self.window_size);
+            return DetectionResult::no_detection();
+        }
+
+        let z_scores = history.z_scores(&vector);
+        history.push(vector, self.window_size);
+        drop(history);
+
+        let distance =
+            (z_scores.iter().map(|z| z * z).sum::<f64>() / z_scores.len() as f64).sqrt();
+        if distance < self.distance_threshold {
+            return DetectionResult::no_detection();
+        }
+
+        let mut evidence = vec![format!(
+            "Normalized distance from recent centroid: {:.2} (threshold {:.2})",
+            distance, self.distance_threshold
+        )];
+
+        // Features that pulled the transaction furthest from the centroid
+        let names = FeatureExtractor::feature_names();
+        let mut ranked: Vec<(usize, f64)> = z_scores.iter().copied().enumerate().collect();
+        ranked.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+        for (index, z) in ranked.into_iter().take(TOP_FEATURES) {
+            evidence.push(format!("{} deviates by {:+.1} standard deviations", names[index], z));
+        }
+
+        DetectionResult::detected(
+            AttackPattern::Anomaly,
+            self.confidence(distance),
+            format!(
+                "Transaction {}.{} from {} is a behavioral outlier relative to recent activity",
+                ctx.transaction.pallet, ctx.transaction.call, ctx.transaction.caller
+            ),
+            evidence,
+        )
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn snapshot(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(&*self.history.read().await).ok()
+    }
+
+    async fn restore(&self, state: serde_json::Value) -> crate::Result<()> {
+        let restored: FeatureHistory = serde_json::from_value(state)
+            .map_err(|e| crate::Error::ParseError(format!("Invalid anomaly detector state: {}", e)))?;
+
+        let feature_count = FeatureExtractor::feature_names().len();
+        if restored.vectors.iter().any(|v| v.len() != feature_count) {
+            return Err(crate::Error::ParseError(
+                "Anomaly detector state has vectors of the wrong length".to_string(),
+            ));
+        }
+
+        self.history.write().await.vectors = restored.vectors;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainEvent, ParsedTransaction, StateChange};
+
+    fn transfer(block: u64, args_len: usize) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: format!("0x{:x}", block),
+                block_number: block,
+                block_hash: format!("0xblock{}", block),
+                index: 0,
+                caller: "alice".to_string(),
+                pallet: "Balances".to_string(),
+                call: "transfer_keep_alive".to_string(),
+                args: vec![0u8; args_len],
+                signature: None,
+                nonce: None,
+                timestamp: 1_700_000_000 + block * 6,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events: vec![],
+            state_changes: vec![],
+        }
+    }
+
+    fn event(pallet: &str, name: &str) -> ChainEvent {
+        ChainEvent::new(pallet, name)
+    }
+
+    /// A batched borrow/swap/bridge transaction unlike the plain transfers
+    fn outlier(block: u64) -> TransactionContext {
+        let mut ctx = transfer(block, 4096);
+        ctx.transaction.caller = "mallory".to_string();
+        ctx.transaction.pallet = "Utility".to_string();
+        ctx.transaction.call = "batch_all".to_string();
+        ctx.events = vec![
+            event("Loans", "Borrowed"),
+            event("Omnipool", "SellExecuted"),
+            event("Omnipool", "BuyExecuted"),
+            event("Loans", "Repaid"),
+            event("Ismp", "Request"),
+        ];
+        ctx.state_changes = vec![StateChange {
+            key: vec![1; 32],
+            old_value: Some(vec![0; 16]),
+            new_value: Some(vec![0xff; 16]),
+        }];
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_outlier_flagged_after_similar_history() {
+        let detector = UnsupervisedAnomalyDetector::new();
+
+        for block in 1..=200 {
+            let args_len = 32 + (block % 8) as usize;
+            let result = detector.analyze_transaction(&transfer(block, args_len)).await;
+            assert!(!result.detected, "similar transfer at block {} was flagged", block);
+        }
+
+        let result = detector.analyze_transaction(&outlier(201)).await;
+        assert!(result.detected);
+        assert_eq!(result.pattern, AttackPattern::Anomaly);
+        assert!(result.confidence >= 0.6);
+        assert!(result.evidence[0].starts_with("Normalized distance"));
+    }
+
+    #[tokio::test]
+    async fn test_no_detection_during_warmup() {
+        let detector = UnsupervisedAnomalyDetector::new();
+
+        for block in 1..10 {
+            detector.analyze_transaction(&transfer(block, 32)).await;
+        }
+
+        // Too little history to tell what is normal yet
+        assert!(!detector.analyze_transaction(&outlier(10)).await.detected);
+    }
+
+    #[tokio::test]
+    async fn test_threshold_is_configurable() {
+        let detector = UnsupervisedAnomalyDetector::new().with_distance_threshold(100.0);
+
+        for block in 1..=100 {
+            detector.analyze_transaction(&transfer(block, 32)).await;
+        }
+
+        assert!(!detector.analyze_transaction(&outlier(101)).await.detected);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let detector = UnsupervisedAnomalyDetector::new();
+        for block in 1..=60 {
+            detector.analyze_transaction(&transfer(block, 32)).await;
+        }
+
+        let snapshot = detector.snapshot().await.unwrap();
+        let restored = UnsupervisedAnomalyDetector::new();
+        restored.restore(snapshot).await.unwrap();
+
+        // Restored history is past warm-up, so the outlier is caught immediately
+        assert!(restored.analyze_transaction(&outlier(61)).await.detected);
+    }
+}
@@ -0,0 +1,383 @@
+//! Bridge balance detector
+//!
+//! `CrossChainBridgeDetector` judges one transaction at a time, so a drain
+//! split into many modest withdrawals goes unnoticed. This detector keeps a
+//! running ledger per counterparty chain, from the same token flows recorded
+//! in `hyperbridge_messages`:
+//! 1. Outflows: `TokenGateway::AssetTeleported` (amount sent to `dest`)
+//! 2. Inflows: `TokenGateway::AssetReceived` (amount arriving from `source`)
+//!
+//! Flows older than the window (in blocks) are forgotten. When the net
+//! outflow towards one chain exceeds the threshold over at least
+//! `MIN_OUTFLOWS` withdrawals, the bridge is being drained; a single large
+//! withdrawal is left to the per-transaction detectors.
+
+use crate::detectors::{normalize, Detector, DetectorManifest};
+use crate::types::{json_amount, AttackPattern, DetectionResult, TransactionContext};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Default net outflow (in the asset's smallest unit) towards one chain that is flagged
+pub const DEFAULT_OUTFLOW_THRESHOLD: u128 = 10_000_000_000_000_000;
+
+/// Default number of blocks flows are kept for (about an hour of 6s blocks)
+pub const DEFAULT_WINDOW_BLOCKS: u64 = 600;
+
+/// Withdrawals within the window needed for a sustained drain
+const MIN_OUTFLOWS: usize = 3;
+
+/// Normalized pallets emitting bridge token flows
+const BRIDGE_PALLETS: &[&str] = &["tokengateway", "ismp", "hyperbridge"];
+
+/// Detector for sustained net outflows over a bridge
+pub struct BridgeBalanceDetector {
+    enabled: bool,
+    outflow_threshold: u128,
+    window_blocks: u64,
+    ledgers: Arc<RwLock<HashMap<String, BridgeLedger>>>,
+}
+
+/// Token flows between this chain and one counterparty within the window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BridgeLedger {
+    flows: VecDeque<Flow>,
+    /// Set once a drain is reported, until the net outflow drops below the threshold
+    reported: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Flow {
+    block: u64,
+    amount: u128,
+    outbound: bool,
+}
+
+/// Net position of a ledger over its window
+#[derive(Debug, Clone, Copy)]
+struct NetFlow {
+    outflow: u128,
+    inflow: u128,
+    outflows: usize,
+}
+
+impl NetFlow {
+    fn net_outflow(&self) -> u128 {
+        self.outflow.saturating_sub(self.inflow)
+    }
+}
+
+impl BridgeLedger {
+    fn record(&mut self, flow: Flow, window_blocks: u64) {
+        let oldest = flow.block.saturating_sub(window_blocks);
+        self.flows.push_back(flow);
+        while self.flows.front().is_some_and(|f| f.block < oldest) {
+            self.flows.pop_front();
+        }
+    }
+
+    fn net(&self) -> NetFlow {
+        let mut net = NetFlow { outflow: 0, inflow: 0, outflows: 0 };
+        for flow in &self.flows {
+            if flow.outbound {
+                net.outflow = net.outflow.saturating_add(flow.amount);
+                net.outflows += 1;
+            } else {
+                net.inflow = net.inflow.saturating_add(flow.amount);
+            }
+        }
+        net
+    }
+}
+
+impl BridgeBalanceDetector {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            outflow_threshold: DEFAULT_OUTFLOW_THRESHOLD,
+            window_blocks: DEFAULT_WINDOW_BLOCKS,
+            ledgers: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Set the net outflow towards one chain above which the bridge is flagged
+    pub fn with_outflow_threshold(mut self, threshold: u128) -> Self {
+        self.outflow_threshold = threshold;
+        self
+    }
+
+    /// Set how many blocks flows are kept for
+    pub fn with_window_blocks(mut self, blocks: u64) -> Self {
+        self.window_blocks = blocks;
+        self
+    }
+
+    /// Bridge token flows in the transaction, as (counterparty chain, flow)
+    ///
+    /// An event without a decoded amount is valued at the transaction's
+    /// [`TransactionContext::total_value_moved`].
+    fn extract_flows(ctx: &TransactionContext) -> Vec<(String, Flow)> {
+        ctx.events
+            .iter()
+            .filter(|e| {
+                let pallet = normalize(&e.pallet);
+                BRIDGE_PALLETS.contains(&pallet.as_str())
+            })
+            .filter_map(|e| {
+                let (outbound, chain_field) = match e.event_name.to_lowercase().as_str() {
+                    "assetteleported" => (true, "dest"),
+                    "assetreceived" => (false, "source"),
+                    _ => return None,
+                };
+                let data = e.event_data.as_ref()?;
+                let amount = data
+                    .get("amount")
+                    .and_then(json_amount)
+                    .or_else(|| ctx.total_value_moved())?;
+                let chain = match data.get(chain_field)? {
+                    serde_json::Value::String(chain) => chain.clone(),
+                    other => other.to_string(),
+                };
+
+                Some((
+                    chain,
+                    Flow {
+                        block: ctx.transaction.block_number,
+                        amount,
+                        outbound,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Record flows, returning the chains newly found to be drained
+    async fn record_flows(&self, flows: Vec<(String, Flow)>) -> Vec<(String, NetFlow)> {
+        let mut ledgers = self.ledgers.write().await;
+        let mut drained = Vec::new();
+
+        for (chain, flow) in flows {
+            let ledger = ledgers.entry(chain.clone()).or_default();
+            ledger.record(flow, self.window_blocks);
+
+            let net = ledger.net();
+            if net.net_outflow() <= self.outflow_threshold {
+                ledger.reported = false;
+            } else if net.outflows >= MIN_OUTFLOWS && !ledger.reported {
+                ledger.reported = true;
+                drained.push((chain, net));
+            }
+        }
+
+        drained
+    }
+
+    /// Confidence grows with how far past the threshold the drain goes
+    fn confidence(&self, net: &NetFlow) -> f64 {
+        let ratio = net.net_outflow() as f64 / self.outflow_threshold.max(1) as f64;
+        (0.8 + 0.05 * ratio.log2()).clamp(0.8, 0.95)
+    }
+}
+
+impl Default for BridgeBalanceDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Detector for BridgeBalanceDetector {
+    fn name(&self) -> &str {
+        "Bridge Balance Detector"
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Sustained net token outflow to one chain over the bridge, a slow drain split across transactions")
+            .with_patterns(&[AttackPattern::CrossChainBridge])
+            .with_pallets(&["TokenGateway"])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        if !self.enabled {
+            return DetectionResult::no_detection();
+        }
+
+        let flows = Self::extract_flows(ctx);
+        if flows.is_empty() {
+            return DetectionResult::no_detection();
+        }
+
+        let drained = self.record_flows(flows).await;
+        let Some((chain, net)) = drained.into_iter().max_by_key(|(_, net)| net.net_outflow()) else {
+            return DetectionResult::no_detection();
+        };
+
+        let evidence = vec![
+            format!(
+                "Net outflow of {} to {} within {} blocks (threshold {})",
+                net.net_outflow(),
+                chain,
+                self.window_blocks,
+                self.outflow_threshold
+            ),
+            format!(
+                "{} withdrawals totalling {} against {} received",
+                net.outflows, net.outflow, net.inflow
+            ),
+        ];
+
+        let triggers = ctx
+            .events
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.event_name.eq_ignore_ascii_case("assetteleported"))
+            .map(|(index, _)| index)
+            .collect();
+
+        DetectionResult::detected(
+            AttackPattern::CrossChainBridge,
+            self.confidence(&net),
+            format!("Bridge drain: sustained net outflow to {}", chain),
+            evidence,
+        )
+        .with_triggers(triggers, vec![])
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn snapshot(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(&*self.ledgers.read().await).ok()
+    }
+
+    async fn restore(&self, state: serde_json::Value) -> crate::Result<()> {
+        let ledgers: HashMap<String, BridgeLedger> = serde_json::from_value(state)
+            .map_err(|e| crate::Error::ParseError(format!("Invalid bridge balance state: {}", e)))?;
+        *self.ledgers.write().await = ledgers;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainEvent, ParsedTransaction};
+
+    const THRESHOLD: u128 = 10_000;
+
+    fn flow(block: u64, event: &str, chain_field: &str, chain: &str, amount: u128) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: format!("0x{:04x}", block),
+                block_number: block,
+                block_hash: format!("0xblock{}", block),
+                index: 0,
+                caller: "alice".to_string(),
+                pallet: "TokenGateway".to_string(),
+                call: "teleport".to_string(),
+                args: vec![],
+                signature: Some(vec![1]),
+                nonce: Some(block),
+                timestamp: 1234567890 + block,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events: vec![ChainEvent::new("TokenGateway", event).with_event_data(serde_json::json!({
+                "amount": amount.to_string(),
+                chain_field: chain,
+            }))],
+            state_changes: vec![],
+        }
+    }
+
+    fn outflow(block: u64, amount: u128) -> TransactionContext {
+        flow(block, "AssetTeleported", "dest", "EVM-1", amount)
+    }
+
+    fn inflow(block: u64, amount: u128) -> TransactionContext {
+        flow(block, "AssetReceived", "source", "EVM-1", amount)
+    }
+
+    #[tokio::test]
+    async fn test_sustained_outflow_is_flagged() {
+        let detector = BridgeBalanceDetector::new().with_outflow_threshold(THRESHOLD);
+
+        // No single withdrawal is large; together they pass the threshold
+        for block in 1..=3 {
+            let result = detector.analyze_transaction(&outflow(block, 3_000)).await;
+            assert!(!result.detected);
+        }
+        let result = detector.analyze_transaction(&outflow(4, 3_000)).await;
+        assert!(result.detected);
+        assert_eq!(result.pattern, AttackPattern::CrossChainBridge);
+        assert!(result.confidence >= 0.8);
+        assert!(result.evidence[0].contains("EVM-1"));
+
+        // Reported once, not on every following withdrawal
+        assert!(!detector.analyze_transaction(&outflow(5, 3_000)).await.detected);
+    }
+
+    #[tokio::test]
+    async fn test_balanced_flows_are_not_flagged() {
+        let detector = BridgeBalanceDetector::new().with_outflow_threshold(THRESHOLD);
+
+        for block in 1..=10 {
+            assert!(!detector.analyze_transaction(&outflow(block * 2, 5_000)).await.detected);
+            assert!(!detector.analyze_transaction(&inflow(block * 2 + 1, 5_000)).await.detected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flow_without_amount_uses_value_moved() {
+        let detector = BridgeBalanceDetector::new().with_outflow_threshold(THRESHOLD);
+
+        // Teleports without an amount field, each withdrawing 4,000
+        let teleport = |block| {
+            let mut ctx = outflow(block, 0);
+            ctx.events = vec![
+                ChainEvent::new("TokenGateway", "AssetTeleported")
+                    .with_event_data(serde_json::json!({ "dest": "EVM-1" })),
+                ChainEvent::new("Balances", "Withdraw")
+                    .with_event_data(serde_json::json!({ "amount": 4_000 })),
+            ];
+            ctx
+        };
+
+        for block in 1..=2 {
+            assert!(!detector.analyze_transaction(&teleport(block)).await.detected);
+        }
+        let result = detector.analyze_transaction(&teleport(3)).await;
+        assert!(result.detected);
+        assert!(result.evidence[0].contains("EVM-1"));
+    }
+
+    #[tokio::test]
+    async fn test_flows_outside_window_are_forgotten() {
+        let detector = BridgeBalanceDetector::new()
+            .with_outflow_threshold(THRESHOLD)
+            .with_window_blocks(10);
+
+        for block in [1, 20, 40, 60, 80] {
+            assert!(!detector.analyze_transaction(&outflow(block, 6_000)).await.detected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_restore_keeps_ledger() {
+        let detector = BridgeBalanceDetector::new().with_outflow_threshold(THRESHOLD);
+        for block in 1..=3 {
+            detector.analyze_transaction(&outflow(block, 3_000)).await;
+        }
+
+        let restored = BridgeBalanceDetector::new().with_outflow_threshold(THRESHOLD);
+        restored.restore(detector.snapshot().await.unwrap()).await.unwrap();
+        assert!(restored.analyze_transaction(&outflow(4, 3_000)).await.detected);
+    }
+}
@@ -0,0 +1,276 @@
+//! Fee anomaly detector
+//!
+//! MEV bots compete for ordering by outbidding each other with tips, so a
+//! transaction paying far more than usual, tip included, is a priority-fee
+//! bidding signal. Fees are read from the `TransactionPayment::TransactionFeePaid`
+//! event and compared against a rolling average of recent fees:
+//! 1. Only tipped transactions are flagged; a high fee without a tip is a
+//!    heavy call, not a bid for priority
+//! 2. Confidence grows with the fee's multiple of the baseline and is raised
+//!    on DEX pallets, where ordering is worth paying for
+
+use crate::detectors::{normalize, Detector, DetectorManifest};
+use crate::types::{AttackPattern, DetectionResult, FeePaid, TransactionContext};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Fees observed before the baseline is trusted
+const MIN_BASELINE_TXS: u64 = 20;
+
+/// Default multiple of the baseline fee above which a tipped transaction is flagged
+pub const DEFAULT_FEE_MULTIPLIER: f64 = 10.0;
+
+/// Weight of the newest fee in the rolling average
+const BASELINE_WEIGHT: f64 = 0.05;
+
+/// Lowercased pallets of DEXes and aggregators
+const DEX_PALLETS: &[&str] = &[
+    "omnipool",
+    "router",
+    "xyk",
+    "stableswap",
+    "assetconversion",
+    "dex",
+    "dexprotocol",
+];
+
+/// Detector for transactions outbidding the network on fees
+pub struct FeeAnomalyDetector {
+    enabled: bool,
+    multiplier: f64,
+    baseline: Arc<RwLock<FeeBaseline>>,
+}
+
+/// Rolling average of fees paid across transactions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct FeeBaseline {
+    avg_fee: f64,
+    observed: u64,
+}
+
+impl FeeAnomalyDetector {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            multiplier: DEFAULT_FEE_MULTIPLIER,
+            baseline: Arc::new(RwLock::new(FeeBaseline::default())),
+        }
+    }
+
+    /// Set the multiple of the baseline fee above which tipped transactions are flagged
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Compare a fee against the baseline, returning the baseline if it is anomalous
+    ///
+    /// Anomalous fees are kept out of the average so a bidding war does not
+    /// raise the bar for detecting the next one.
+    async fn observe_fee(&self, fee: &FeePaid) -> Option<f64> {
+        let mut baseline = self.baseline.write().await;
+        let actual_fee = fee.actual_fee as f64;
+
+        let is_anomalous = baseline.observed >= MIN_BASELINE_TXS
+            && fee.tip > 0
+            && actual_fee > baseline.avg_fee.max(1.0) * self.multiplier;
+        if is_anomalous {
+            return Some(baseline.avg_fee);
+        }
+
+        baseline.avg_fee = if baseline.observed == 0 {
+            actual_fee
+        } else {
+            baseline.avg_fee * (1.0 - BASELINE_WEIGHT) + actual_fee * BASELINE_WEIGHT
+        };
+        baseline.observed += 1;
+        None
+    }
+
+    fn is_dex(pallet: &str) -> bool {
+        let pallet = normalize(pallet);
+        DEX_PALLETS.contains(&pallet.as_str())
+    }
+
+    /// Confidence grows with how far past the threshold the fee goes
+    fn confidence(&self, ratio: f64, is_dex: bool) -> f64 {
+        let excess = (1.0 - self.multiplier / ratio).max(0.0);
+        let dex_bonus = if is_dex { 0.15 } else { 0.0 };
+        (0.55 + 0.25 * excess + dex_bonus).min(0.95)
+    }
+}
+
+impl Default for FeeAnomalyDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Detector for FeeAnomalyDetector {
+    fn name(&self) -> &str {
+        "Fee Anomaly Detector"
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Tipped transactions paying far above the rolling fee baseline, a priority-fee MEV signal")
+            .with_patterns(&[AttackPattern::Mev])
+            .with_pallets(&["TransactionPayment"])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        if !self.enabled {
+            return DetectionResult::no_detection();
+        }
+
+        let Some(fee) = ctx.fee_paid() else {
+            return DetectionResult::no_detection();
+        };
+        let Some(avg_fee) = self.observe_fee(&fee).await else {
+            return DetectionResult::no_detection();
+        };
+
+        let tx = &ctx.transaction;
+        let ratio = fee.actual_fee as f64 / avg_fee.max(1.0);
+        let is_dex = Self::is_dex(&tx.pallet);
+
+        let mut evidence = vec![
+            format!(
+                "Fee of {} is {:.1}x the baseline of {:.0}",
+                fee.actual_fee, ratio, avg_fee
+            ),
+            format!(
+                "Tip of {} ({:.0}% of the fee)",
+                fee.tip,
+                fee.tip as f64 / fee.actual_fee.max(1) as f64 * 100.0
+            ),
+        ];
+        if is_dex {
+            evidence.push(format!("DEX call {}::{} where ordering is valuable", tx.pallet, tx.call));
+        }
+
+        let triggers = ctx
+            .events
+            .iter()
+            .position(|e| e.event_name.eq_ignore_ascii_case("transactionfeepaid"))
+            .into_iter()
+            .collect();
+
+        DetectionResult::detected(
+            AttackPattern::Mev,
+            self.confidence(ratio, is_dex),
+            format!(
+                "Priority-fee bidding: {}::{} paid {:.1}x the usual fee",
+                tx.pallet, tx.call, ratio
+            ),
+            evidence,
+        )
+        .with_triggers(triggers, vec![])
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn snapshot(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(&*self.baseline.read().await).ok()
+    }
+
+    async fn restore(&self, state: serde_json::Value) -> crate::Result<()> {
+        let baseline: FeeBaseline = serde_json::from_value(state)
+            .map_err(|e| crate::Error::ParseError(format!("Invalid fee detector state: {}", e)))?;
+        *self.baseline.write().await = baseline;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainEvent, ParsedTransaction};
+
+    fn context(index: u32, pallet: &str, actual_fee: u128, tip: u128) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: format!("0x{:04x}", index),
+                block_number: 1,
+                block_hash: "0xblock1".to_string(),
+                index,
+                caller: "alice".to_string(),
+                pallet: pallet.to_string(),
+                call: "sell".to_string(),
+                args: vec![],
+                signature: Some(vec![1]),
+                nonce: Some(index as u64),
+                timestamp: 1234567890,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events: vec![ChainEvent::new("TransactionPayment", "TransactionFeePaid").with_event_data(
+                serde_json::json!({
+                    "who": "alice",
+                    "actual_fee": actual_fee.to_string(),
+                    "tip": tip.to_string(),
+                }),
+            )],
+            state_changes: vec![],
+        }
+    }
+
+    /// Detector with a baseline of 1_000_000 per transaction
+    async fn warmed_up() -> FeeAnomalyDetector {
+        let detector = FeeAnomalyDetector::new();
+        for index in 0..MIN_BASELINE_TXS as u32 {
+            let result = detector.analyze_transaction(&context(index, "Balances", 1_000_000, 0)).await;
+            assert!(!result.detected);
+        }
+        detector
+    }
+
+    #[tokio::test]
+    async fn test_flags_only_extreme_tip() {
+        let detector = warmed_up().await;
+
+        let normal = detector.analyze_transaction(&context(100, "Omnipool", 1_200_000, 100_000)).await;
+        assert!(!normal.detected);
+
+        let extreme = detector.analyze_transaction(&context(101, "Omnipool", 50_000_000, 49_000_000)).await;
+        assert!(extreme.detected);
+        assert_eq!(extreme.pattern, AttackPattern::Mev);
+        assert_eq!(extreme.triggering_events, vec![0]);
+        assert!(extreme.evidence.iter().any(|e| e.contains("DEX call")));
+    }
+
+    #[tokio::test]
+    async fn test_heavy_call_without_tip_is_not_flagged() {
+        let detector = warmed_up().await;
+
+        let heavy = detector.analyze_transaction(&context(100, "Utility", 50_000_000, 0)).await;
+        assert!(!heavy.detected);
+    }
+
+    #[tokio::test]
+    async fn test_dex_raises_confidence() {
+        let detector = warmed_up().await;
+
+        let transfer = detector.analyze_transaction(&context(100, "Balances", 50_000_000, 49_000_000)).await;
+        let swap = detector.analyze_transaction(&context(101, "Omnipool", 50_000_000, 49_000_000)).await;
+        assert!(transfer.detected && swap.detected);
+        assert!(swap.confidence > transfer.confidence);
+    }
+
+    #[tokio::test]
+    async fn test_no_fee_event_is_ignored() {
+        let detector = warmed_up().await;
+        let mut ctx = context(100, "Omnipool", 50_000_000, 49_000_000);
+        ctx.events.clear();
+
+        assert!(!detector.analyze_transaction(&ctx).await.detected);
+    }
+}
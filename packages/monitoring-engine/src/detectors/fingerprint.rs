@@ -0,0 +1,317 @@
+//! Transaction fingerprint detector
+//!
+//! Attack bots and spammers tend to submit the same call with identical or
+//! templated arguments at a high rate. Each transaction is fingerprinted by
+//! hashing its pallet, call and arguments (the decoded arguments when
+//! available, the raw SCALE bytes otherwise), and a caller repeating one
+//! fingerprint more than a configurable number of times within a rolling
+//! window is flagged as bot activity.
+
+use crate::detectors::{normalize, Detector, DetectorManifest};
+use crate::types::{AttackPattern, DetectionResult, ParsedTransaction, TransactionContext};
+use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Default length of the rolling window, in seconds
+pub const DEFAULT_WINDOW_SECS: u64 = 60;
+
+/// Default number of identical transactions a caller may submit within the window
+pub const DEFAULT_MAX_REPEATS: usize = 10;
+
+/// Maximum number of callers kept in the window
+const MAX_TRACKED_CALLERS: usize = 10_000;
+
+/// Detector for callers repeating the same transaction at a high rate
+pub struct TransactionFingerprintDetector {
+    enabled: bool,
+    window_secs: u64,
+    max_repeats: usize,
+    /// Recent submissions per caller, oldest first
+    recent: Arc<RwLock<HashMap<String, VecDeque<Submission>>>>,
+}
+
+/// A transaction seen within the window
+#[derive(Debug, Clone)]
+struct Submission {
+    hash: String,
+    fingerprint: blake3::Hash,
+    timestamp: u64,
+}
+
+impl TransactionFingerprintDetector {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            window_secs: DEFAULT_WINDOW_SECS,
+            max_repeats: DEFAULT_MAX_REPEATS,
+            recent: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Set the length of the rolling window, in seconds
+    pub fn with_window_secs(mut self, window_secs: u64) -> Self {
+        self.window_secs = window_secs;
+        self
+    }
+
+    /// Set the number of identical transactions within the window above which a caller is flagged
+    pub fn with_max_repeats(mut self, max_repeats: usize) -> Self {
+        self.max_repeats = max_repeats;
+        self
+    }
+
+    /// Hash of the call and its normalized arguments
+    ///
+    /// Pallet and call names are compared case-insensitively and without
+    /// underscores. Decoded arguments are hashed with object keys sorted, so
+    /// the same arguments always give the same fingerprint.
+    pub fn fingerprint(tx: &ParsedTransaction) -> blake3::Hash {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(normalize(&tx.pallet).as_bytes());
+        hasher.update(b".");
+        hasher.update(normalize(&tx.call).as_bytes());
+        hasher.update(b"(");
+        match tx.decoded_call.get() {
+            Some(decoded) => hash_json(&mut hasher, &decoded.args),
+            None => {
+                hasher.update(&tx.args);
+            }
+        }
+        hasher.update(b")");
+        hasher.finalize()
+    }
+
+    /// Record the submission and count the caller's repeats of its fingerprint
+    ///
+    /// A transaction seen again (e.g. in the mempool and then in a block) is
+    /// only counted once.
+    async fn observe(&self, tx: &ParsedTransaction, fingerprint: blake3::Hash) -> usize {
+        let mut recent = self.recent.write().await;
+        let cutoff = tx.timestamp.saturating_sub(self.window_secs);
+
+        if !recent.contains_key(&tx.caller) && recent.len() >= MAX_TRACKED_CALLERS {
+            Self::evict(&mut recent, cutoff);
+        }
+
+        let submissions = recent.entry(tx.caller.clone()).or_default();
+        while submissions.front().is_some_and(|s| s.timestamp < cutoff) {
+            submissions.pop_front();
+        }
+
+        if !submissions.iter().any(|s| s.hash == tx.hash) {
+            submissions.push_back(Submission {
+                hash: tx.hash.clone(),
+                fingerprint,
+                timestamp: tx.timestamp,
+            });
+        }
+
+        submissions.iter().filter(|s| s.fingerprint == fingerprint).count()
+    }
+
+    /// Drop callers with nothing left in the window, or else the least recently active one
+    fn evict(recent: &mut HashMap<String, VecDeque<Submission>>, cutoff: u64) {
+        recent.retain(|_, submissions| submissions.back().is_some_and(|s| s.timestamp >= cutoff));
+        if recent.len() < MAX_TRACKED_CALLERS {
+            return;
+        }
+
+        if let Some(oldest) = recent
+            .iter()
+            .min_by_key(|(_, submissions)| submissions.back().map_or(0, |s| s.timestamp))
+            .map(|(caller, _)| caller.clone())
+        {
+            recent.remove(&oldest);
+        }
+    }
+
+    /// Confidence grows with how far past the limit the caller goes
+    fn confidence(&self, repeats: usize) -> f64 {
+        let ratio = repeats as f64 / self.max_repeats.max(1) as f64;
+        (0.55 + 0.4 * (1.0 - 1.0 / ratio)).clamp(0.55, 0.95)
+    }
+}
+
+impl Default for TransactionFingerprintDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Feed a JSON value to the hasher with object keys in sorted order
+fn hash_json(hasher: &mut blake3::Hasher, value: &serde_json::Value) {
+    match value {
+        serde_json::Value::Object(object) => {
+            let mut keys: Vec<_> = object.keys().collect();
+            keys.sort();
+            hasher.update(b"{");
+            for key in keys {
+                hasher.update(serde_json::to_string(key).unwrap_or_default().as_bytes());
+                hasher.update(b":");
+                hash_json(hasher, &object[key]);
+                hasher.update(b",");
+            }
+            hasher.update(b"}");
+        }
+        serde_json::Value::Array(items) => {
+            hasher.update(b"[");
+            for item in items {
+                hash_json(hasher, item);
+                hasher.update(b",");
+            }
+            hasher.update(b"]");
+        }
+        scalar => {
+            hasher.update(scalar.to_string().as_bytes());
+        }
+    }
+}
+
+#[async_trait]
+impl Detector for TransactionFingerprintDetector {
+    fn name(&self) -> &str {
+        "Transaction Fingerprint Detector"
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Callers submitting the same call with identical arguments at a high rate")
+            .with_patterns(&[AttackPattern::BotActivity])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        if !self.enabled {
+            return DetectionResult::no_detection();
+        }
+
+        let tx = &ctx.transaction;
+        let fingerprint = Self::fingerprint(tx);
+        let repeats = self.observe(tx, fingerprint).await;
+
+        if repeats <= self.max_repeats {
+            return DetectionResult::no_detection();
+        }
+
+        DetectionResult::detected(
+            AttackPattern::BotActivity,
+            self.confidence(repeats),
+            format!(
+                "{} submitted {}.{} with identical arguments {} times within {}s - likely automated",
+                tx.caller, tx.pallet, tx.call, repeats, self.window_secs
+            ),
+            vec![
+                format!(
+                    "{} identical transactions within {}s (limit {})",
+                    repeats, self.window_secs, self.max_repeats
+                ),
+                format!("Fingerprint {}", &fingerprint.to_hex()[..16]),
+            ],
+        )
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_context(hash: &str, call: &str, args: Vec<u8>, timestamp: u64) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: hash.to_string(),
+                block_number: 1,
+                block_hash: "0xblock1".to_string(),
+                index: 0,
+                caller: "bot".to_string(),
+                pallet: "Balances".to_string(),
+                call: call.to_string(),
+                args,
+                signature: None,
+                nonce: None,
+                timestamp,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events: vec![],
+            state_changes: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_identical_transactions_flagged() {
+        let detector = TransactionFingerprintDetector::new();
+
+        let mut results = Vec::new();
+        for i in 0..20 {
+            let ctx = create_context(&format!("0x{:02x}", i), "transfer_keep_alive", vec![1, 2, 3], 1_700_000_000 + i);
+            results.push(detector.analyze_transaction(&ctx).await);
+        }
+
+        assert!(results[..DEFAULT_MAX_REPEATS].iter().all(|r| !r.detected));
+        let last = results.last().unwrap();
+        assert!(last.detected);
+        assert_eq!(last.pattern, AttackPattern::BotActivity);
+        assert!(last.evidence[0].starts_with("20 identical transactions"));
+        assert!(last.confidence >= 0.55);
+    }
+
+    #[tokio::test]
+    async fn test_varied_transactions_not_flagged() {
+        let detector = TransactionFingerprintDetector::new();
+
+        for i in 0..20u8 {
+            let ctx = create_context(&format!("0x{:02x}", i), "transfer_keep_alive", vec![i], 1_700_000_000);
+            assert!(!detector.analyze_transaction(&ctx).await.detected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeats_outside_window_not_counted() {
+        let detector = TransactionFingerprintDetector::new().with_max_repeats(2);
+
+        for i in 0..5u64 {
+            let ctx = create_context(&format!("0x{:02x}", i), "remark", vec![], 1_700_000_000 + i * DEFAULT_WINDOW_SECS * 2);
+            assert!(!detector.analyze_transaction(&ctx).await.detected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resubmitted_transaction_counted_once() {
+        let detector = TransactionFingerprintDetector::new().with_max_repeats(2);
+
+        // Mempool sighting then inclusion of the same transaction
+        for _ in 0..5 {
+            let ctx = create_context("0xsame", "remark", vec![], 1_700_000_000);
+            assert!(!detector.analyze_transaction(&ctx).await.detected);
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_name_style_and_key_order() {
+        let a = create_context("0x01", "transfer_keep_alive", vec![], 0).transaction;
+        let b = create_context("0x02", "TransferKeepAlive", vec![], 0).transaction;
+        a.decoded_call.get_or_init(|| crate::types::DecodedCall {
+            pallet: "Balances".to_string(),
+            call: "transfer_keep_alive".to_string(),
+            args: serde_json::json!({ "dest": "alice", "value": 10 }),
+        });
+        b.decoded_call.get_or_init(|| crate::types::DecodedCall {
+            pallet: "Balances".to_string(),
+            call: "transfer_keep_alive".to_string(),
+            args: serde_json::json!({ "value": 10, "dest": "alice" }),
+        });
+
+        assert_eq!(
+            TransactionFingerprintDetector::fingerprint(&a),
+            TransactionFingerprintDetector::fingerprint(&b)
+        );
+    }
+}
@@ -3,16 +3,22 @@
 //! Detects flash loan attack patterns by analyzing:
 //! 1. Borrow and repayment events in the same transaction
 //! 2. Multiple DeFi protocol interactions (swaps, liquidations)
-//! 3. Large balance changes (>50% threshold)
+//! 3. Large value moved (see [`TransactionContext::total_value_moved`])
 //! 4. Transaction complexity and manipulation indicators
 
-use crate::detectors::Detector;
+use crate::detectors::{Detector, DetectorManifest};
 use crate::types::{AttackPattern, DetectionResult, TransactionContext};
 use async_trait::async_trait;
 
+/// Default value (smallest units) moved by a transaction that counts as
+/// large: 1,000,000 tokens on a 12-decimal chain
+pub const DEFAULT_LARGE_VALUE_FLOOR: u128 = 1_000_000 * 10u128.pow(12);
+
 /// Detector for flash loan attacks
 pub struct FlashLoanDetector {
     enabled: bool,
+    /// Value moved at or above which a transaction counts as large
+    large_value_floor: u128,
 }
 
 /// Flash loan pattern indicators
@@ -21,35 +27,53 @@ struct FlashLoanIndicators {
     has_repay: bool,
     dex_interaction_count: usize,
     lending_protocol_interactions: usize,
-    large_balance_changes: usize,
+    /// Value moved by the transaction, when at or above the large value floor
+    large_value_moved: Option<u128>,
+    /// Indices of the borrow, repay, swap and lending events
+    triggering_events: Vec<usize>,
+    /// Indices of the account balance changes at or above the floor
+    triggering_state_changes: Vec<usize>,
 }
 
 impl FlashLoanDetector {
     pub fn new() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            large_value_floor: DEFAULT_LARGE_VALUE_FLOOR,
+        }
+    }
+
+    /// Set the value moved (smallest units) at or above which a transaction counts as large
+    pub fn with_large_value_floor(mut self, floor: u128) -> Self {
+        self.large_value_floor = floor;
+        self
     }
 
     /// Analyze events to extract flash loan indicators
-    fn analyze_events(ctx: &TransactionContext) -> FlashLoanIndicators {
+    fn analyze_events(&self, ctx: &TransactionContext) -> FlashLoanIndicators {
         let mut has_borrow = false;
         let mut has_repay = false;
         let mut dex_interaction_count = 0;
         let mut lending_protocol_interactions = 0;
+        let mut triggering_events = Vec::new();
 
-        for event in &ctx.events {
+        for (index, event) in ctx.events.iter().enumerate() {
             let event_name_lower = event.event_name.to_lowercase();
             let pallet_lower = event.pallet.to_lowercase();
+            let mut triggered = false;
 
             // Detect borrow events
             if event_name_lower.contains("borrow") {
                 has_borrow = true;
                 lending_protocol_interactions += 1;
+                triggered = true;
             }
 
             // Detect repayment events
             if event_name_lower.contains("repay") || event_name_lower.contains("repaid") {
                 has_repay = true;
                 lending_protocol_interactions += 1;
+                triggered = true;
             }
 
             // Detect DEX interactions (swaps, trades)
@@ -58,73 +82,43 @@ impl FlashLoanDetector {
                 || event_name_lower.contains("trade")
             {
                 dex_interaction_count += 1;
+                triggered = true;
             }
 
             // Other lending protocol events
             if pallet_lower.contains("lending") || pallet_lower.contains("loan") {
                 lending_protocol_interactions += 1;
+                triggered = true;
+            }
+
+            if triggered {
+                triggering_events.push(index);
             }
         }
 
+        let large_value_moved = ctx
+            .total_value_moved()
+            .filter(|value| *value >= self.large_value_floor);
+        let triggering_state_changes = ctx
+            .state_changes
+            .iter()
+            .enumerate()
+            .filter(|(_, change)| {
+                change
+                    .balance_delta()
+                    .is_some_and(|delta| delta >= self.large_value_floor)
+            })
+            .map(|(index, _)| index)
+            .collect();
+
         FlashLoanIndicators {
             has_borrow,
             has_repay,
             dex_interaction_count,
             lending_protocol_interactions,
-            large_balance_changes: Self::count_large_balance_changes(&ctx.state_changes),
-        }
-    }
-
-    /// Count balance changes larger than 50%
-    fn count_large_balance_changes(state_changes: &[crate::types::StateChange]) -> usize {
-        let mut count = 0;
-
-        for change in state_changes {
-            if let (Some(old_val), Some(new_val)) = (&change.old_value, &change.new_value) {
-                // Simple heuristic: if values are different lengths or differ significantly
-                if old_val.len() == new_val.len() && !old_val.is_empty() {
-                    // Convert to u64 for comparison (simplified)
-                    let old_num = Self::bytes_to_u64(old_val);
-                    let new_num = Self::bytes_to_u64(new_val);
-
-                    if old_num > 0 {
-                        let change_ratio = if new_num > old_num {
-                            (new_num as f64) / (old_num as f64)
-                        } else {
-                            (old_num as f64) / (new_num as f64)
-                        };
-
-                        // >50% change threshold
-                        if change_ratio > 1.5 {
-                            count += 1;
-                        }
-                    }
-                }
-            }
-        }
-
-        count
-    }
-
-    /// Convert byte array to u64 (simplified, takes last 8 bytes)
-    fn bytes_to_u64(bytes: &[u8]) -> u64 {
-        let len = bytes.len();
-        if len >= 8 {
-            u64::from_be_bytes([
-                bytes[len - 8],
-                bytes[len - 7],
-                bytes[len - 6],
-                bytes[len - 5],
-                bytes[len - 4],
-                bytes[len - 3],
-                bytes[len - 2],
-                bytes[len - 1],
-            ])
-        } else {
-            // For smaller arrays, pad with zeros
-            let mut padded = [0u8; 8];
-            padded[8 - len..].copy_from_slice(bytes);
-            u64::from_be_bytes(padded)
+            large_value_moved,
+            triggering_events,
+            triggering_state_changes,
         }
     }
 
@@ -141,8 +135,8 @@ impl FlashLoanDetector {
                 confidence += 0.15 * (indicators.dex_interaction_count as f64).min(4.0) / 2.0;
             }
 
-            // Large balance changes indicate manipulation (+20%)
-            if indicators.large_balance_changes > 0 {
+            // Large value moved indicates manipulation (+20%)
+            if indicators.large_value_moved.is_some() {
                 confidence += 0.2;
             }
 
@@ -177,11 +171,8 @@ impl FlashLoanDetector {
             ));
         }
 
-        if indicators.large_balance_changes > 0 {
-            evidence.push(format!(
-                "Large balance changes detected: {} changes >50%",
-                indicators.large_balance_changes
-            ));
+        if let Some(value) = indicators.large_value_moved {
+            evidence.push(format!("Large value moved: {}", value));
         }
 
         if indicators.lending_protocol_interactions > 2 {
@@ -213,13 +204,18 @@ impl Detector for FlashLoanDetector {
         "Flash Loan Detector"
     }
 
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Borrow and repayment in one transaction combined with swaps, liquidations or large value moved")
+            .with_patterns(&[AttackPattern::FlashLoan])
+    }
+
     async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
         let tx = &ctx.transaction;
         let mut suspicion_score: f64 = 0.0;
         let mut evidence = Vec::new();
 
         // Extract traditional indicators from events
-        let indicators = Self::analyze_events(ctx);
+        let indicators = self.analyze_events(ctx);
 
         // Check transaction pallet and call for lending patterns
         let pallet_lower = tx.pallet.to_lowercase();
@@ -271,10 +267,10 @@ impl Detector for FlashLoanDetector {
             evidence.push(format!("{} DEX/swap interactions detected", indicators.dex_interaction_count));
         }
 
-        // Large balance changes
-        if indicators.large_balance_changes > 0 {
+        // Large value moved
+        if let Some(value) = indicators.large_value_moved {
             suspicion_score += 0.2;
-            evidence.push(format!("{} large balance changes (>50%)", indicators.large_balance_changes));
+            evidence.push(format!("Large value moved: {} (smallest units)", value));
         }
 
         // Complex lending protocol usage
@@ -308,6 +304,7 @@ impl Detector for FlashLoanDetector {
                 description,
                 evidence
             )
+            .with_triggers(indicators.triggering_events, indicators.triggering_state_changes)
         } else {
             DetectionResult::no_detection()
         }
@@ -321,7 +318,8 @@ impl Detector for FlashLoanDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{ChainEvent, ParsedTransaction, StateChange};
+    use crate::storage::storage_prefix;
+    use crate::types::{account_info, ChainEvent, ParsedTransaction, StateChange};
 
     fn create_test_transaction(hash: &str, pallet: &str, call: &str) -> ParsedTransaction {
         ParsedTransaction {
@@ -337,19 +335,12 @@ mod tests {
             nonce: Some(1),
             timestamp: 1234567890,
             success: true,
+            decoded_call: Default::default(),
         }
     }
 
     fn create_event(pallet: &str, event_name: &str, data: &str) -> ChainEvent {
-        ChainEvent {
-            block_number: 1000,
-            event_index: 0,
-            extrinsic_index: Some(0),
-            pallet: pallet.to_string(),
-            event_name: event_name.to_string(),
-            data: data.as_bytes().to_vec(),
-            topics: vec![],
-        }
+        ChainEvent::new(pallet, event_name).with_event_data(serde_json::Value::String(data.to_string()))
     }
 
     fn create_state_change(key: &str, old_value: Vec<u8>, new_value: Vec<u8>) -> StateChange {
@@ -360,6 +351,17 @@ mod tests {
         }
     }
 
+    /// `System::Account` change between two free balances
+    fn account_change(old_free: u128, new_free: u128) -> StateChange {
+        let mut key = storage_prefix("System", "Account").to_vec();
+        key.extend_from_slice(&[7u8; 48]);
+        StateChange {
+            key,
+            old_value: Some(account_info(old_free)),
+            new_value: Some(account_info(new_free)),
+        }
+    }
+
     #[tokio::test]
     async fn test_flash_loan_detector_basic() {
         let detector = FlashLoanDetector::new();
@@ -637,33 +639,69 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_balance_change_calculation() {
-        let detector = FlashLoanDetector::new();
+    async fn test_large_value_moved_from_account_balances() {
+        let detector = FlashLoanDetector::new().with_large_value_floor(1_000_000);
 
-        // Test with extreme balance changes
-        let tx = create_test_transaction("0xtest1", "Test", "test");
+        let ctx = |state_changes| TransactionContext {
+            transaction: create_test_transaction("0xtest1", "Test", "test"),
+            events: vec![],
+            state_changes,
+        };
 
+        // Opaque storage carries no value, however much it changes
+        let opaque = ctx(vec![create_state_change(
+            "balance:1",
+            100u64.to_be_bytes().to_vec(),
+            500_000_000u64.to_be_bytes().to_vec(),
+        )]);
+        assert_eq!(detector.analyze_events(&opaque).large_value_moved, None);
+
+        let accounts = ctx(vec![
+            account_change(1_000, 1_100),
+            account_change(5_000_000, 2_000_000),
+        ]);
+        let indicators = detector.analyze_events(&accounts);
+        assert_eq!(indicators.large_value_moved, Some(3_000_000));
+        assert_eq!(indicators.triggering_state_changes, vec![1]);
+
+        // Multi-asset balances (`Tokens::Accounts`) count as well
+        let mut key = storage_prefix("Tokens", "Accounts").to_vec();
+        key.extend_from_slice(&[7u8; 56]);
+        let tokens = ctx(vec![StateChange {
+            key,
+            old_value: Some([4_000_000u128.to_le_bytes(), [0u8; 16], [0u8; 16]].concat()),
+            new_value: None,
+        }]);
+        let indicators = detector.analyze_events(&tokens);
+        assert_eq!(indicators.large_value_moved, Some(4_000_000));
+        assert_eq!(indicators.triggering_state_changes, vec![0]);
+    }
+
+    #[tokio::test]
+    async fn test_triggering_indices_point_at_borrow_and_repay() {
+        let detector = FlashLoanDetector::new().with_large_value_floor(1_000_000);
+
+        let events = vec![
+            create_event("System", "ExtrinsicSuccess", ""),
+            create_event("Lending", "Borrowed", "amount: 1000000 USDT"),
+            create_event("Balances", "Transfer", "fee"),
+            create_event("Lending", "Repaid", "amount: 1000000 USDT"),
+        ];
         let state_changes = vec![
-            create_state_change(
-                "balance:1",
-                100u64.to_be_bytes().to_vec(),
-                500u64.to_be_bytes().to_vec(), // 5x increase (>50%)
-            ),
-            create_state_change(
-                "balance:2",
-                1000u64.to_be_bytes().to_vec(),
-                1100u64.to_be_bytes().to_vec(), // 10% increase (<50%)
-            ),
-            create_state_change(
-                "balance:3",
-                10000u64.to_be_bytes().to_vec(),
-                2000u64.to_be_bytes().to_vec(), // 5x decrease (>50%)
-            ),
+            account_change(1_000, 1_100),
+            account_change(1_000, 2_000_000),
         ];
 
-        let count = FlashLoanDetector::count_large_balance_changes(&state_changes);
+        let ctx = TransactionContext {
+            transaction: create_test_transaction("0xflashloan2", "Lending", "borrow_flash"),
+            events,
+            state_changes,
+        };
 
-        // Should detect 2 large changes (5x increase and 5x decrease)
-        assert_eq!(count, 2, "Should count 2 large balance changes (>50%)");
+        let result = detector.analyze_transaction(&ctx).await;
+        assert!(result.detected);
+        assert_eq!(result.triggering_events, vec![1, 3]);
+        assert_eq!(result.triggering_state_changes, vec![1]);
+        assert!(result.evidence.iter().any(|e| e.contains("Large value moved: 1999000")));
     }
 }
@@ -6,7 +6,7 @@
 //! 3. Sandwich patterns (front-run + victim + back-run)
 //! 4. High gas prices indicating priority transactions
 
-use crate::detectors::Detector;
+use crate::detectors::{Detector, DetectorManifest};
 use crate::types::{AttackPattern, DetectionResult, ParsedTransaction, TransactionContext};
 use async_trait::async_trait;
 
@@ -240,6 +240,16 @@ impl Detector for FrontRunningDetector {
         "FrontRunning Detector"
     }
 
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Duplicate or similar transactions racing a victim's transaction in the mempool")
+            .with_patterns(&[AttackPattern::FrontRunning, AttackPattern::Sandwich])
+            .with_pallets(&["Balances", "Assets", "Staking", "Democracy", "Utility"])
+    }
+
     async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
         // Add transaction to history for future pattern detection
         self.add_to_history(&ctx.transaction).await;
@@ -300,7 +310,6 @@ impl Detector for FrontRunningDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::ChainEvent;
 
     fn create_test_transaction(hash: &str, caller: &str, pallet: &str, call: &str, block: u64, index: u32) -> ParsedTransaction {
         ParsedTransaction {
@@ -316,6 +325,7 @@ mod tests {
             nonce: Some(0),
             timestamp: 1234567890,
             success: true,
+            decoded_call: Default::default(),
         }
     }
 
@@ -5,14 +5,44 @@
 //! 2. Liquidity drain attacks (massive withdrawals, rug pulls)
 //! 3. Collateral manipulation (liquidation cascades, ratio manipulation)
 
-use crate::detectors::Detector;
-use crate::types::{AttackPattern, DetectionResult, TransactionContext};
+use crate::database::models::HydrationPoolState;
+use crate::detectors::omnipool_state::{largest_shift, OmnipoolStateProvider, PoolHistoryProvider, PoolReserves};
+use crate::detectors::{Detector, DetectorManifest};
+use crate::types::{json_amount, AttackPattern, DetectionResult, TransactionContext, SCHEMA_VERSION};
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Price impact above which a swap counts as large
+const LARGE_PRICE_IMPACT: f64 = 0.05;
+
+/// Hours back the Omnipool detector looks for a stored snapshot to compare against
+pub const DEFAULT_POOL_HISTORY_HOURS: i32 = 1;
+
+/// Move between consecutive pool snapshots above which the pool counts as shifted
+const POOL_SHIFT_THRESHOLD: f64 = 0.2;
+
+/// Latest snapshot of one Omnipool asset, cached between transactions
+#[derive(Default)]
+struct PoolSnapshots {
+    /// Block the latest snapshot was taken after
+    block: Option<u64>,
+    latest: Option<HydrationPoolState>,
+    /// Move from the previous snapshot into the latest one
+    shift: Option<f64>,
+}
 
 /// Detector for Omnipool manipulation attacks
 pub struct OmnipoolManipulationDetector {
     enabled: bool,
+    /// Chain state used to measure price impact; event fields are used when absent
+    state_provider: Option<Arc<dyn OmnipoolStateProvider>>,
+    /// Stored snapshots compared for sudden shifts; skipped when absent
+    pool_history: Option<Arc<dyn PoolHistoryProvider>>,
+    history_hours: i32,
+    /// Latest snapshot per asset, so history is read once per asset
+    snapshots: Arc<RwLock<HashMap<u32, PoolSnapshots>>>,
 }
 
 /// Omnipool attack indicators
@@ -25,11 +55,119 @@ struct OmnipoolIndicators {
     flash_loan_pattern: bool,
     rapid_swaps: bool,
     oracle_deviation: bool,
+    /// Asset ids traded in swap events
+    swapped_assets: Vec<u32>,
+    /// Price impact measured from reserves, when chain state was available
+    measured_price_impact: Option<f64>,
+    /// Largest move between recent pool snapshots, when above the threshold
+    pool_shift: Option<f64>,
+    /// Indices of the events behind these indicators
+    triggering_events: Vec<usize>,
 }
 
 impl OmnipoolManipulationDetector {
     pub fn new() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            state_provider: None,
+            pool_history: None,
+            history_hours: DEFAULT_POOL_HISTORY_HOURS,
+            snapshots: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Measure price impact from chain state instead of event fields
+    pub fn with_state_provider(mut self, provider: Arc<dyn OmnipoolStateProvider>) -> Self {
+        self.state_provider = Some(provider);
+        self
+    }
+
+    /// Snapshot swapped assets after each block and compare with the
+    /// previous snapshot, looking back at most `hours` for stored ones
+    ///
+    /// Snapshots are taken from the reserves read through the state
+    /// provider, so both providers are needed to measure shifts.
+    pub fn with_pool_history(mut self, provider: Arc<dyn PoolHistoryProvider>, hours: i32) -> Self {
+        self.pool_history = Some(provider);
+        self.history_hours = hours;
+        self
+    }
+
+    /// Largest move into this block's snapshot of the swapped assets
+    ///
+    /// The first transaction of a block to touch an asset stores its
+    /// snapshot; later ones reuse the shift measured for that block. Snapshots
+    /// are stamped with the transaction's timestamp and the history window
+    /// ends there, so replayed blocks compare against their own past. Returns
+    /// `None` without a history provider or a previous snapshot to compare.
+    async fn measure_pool_shift(&self, ctx: &TransactionContext, reserves: &[(u32, PoolReserves)]) -> Option<f64> {
+        let provider = self.pool_history.as_ref()?;
+        let block = ctx.transaction.block_number;
+        let at = chrono::DateTime::from_timestamp(ctx.transaction.timestamp as i64, 0)?;
+        let cutoff = at - chrono::Duration::hours(self.history_hours.into());
+        let mut largest: Option<f64> = None;
+
+        for (asset, after) in reserves {
+            let pool_id = asset.to_string();
+            // Stored history is only read the first time an asset is seen
+            let cached = self.snapshots.read().await.contains_key(asset);
+            let stored = match cached {
+                true => None,
+                false => provider.pool_history(&pool_id, at, self.history_hours).await,
+            };
+
+            let mut snapshots = self.snapshots.write().await;
+            let cached = snapshots.entry(*asset).or_insert_with(|| PoolSnapshots {
+                latest: stored.and_then(|history| history.into_iter().last()),
+                ..Default::default()
+            });
+
+            let snapshot = if cached.block == Some(block) {
+                None
+            } else {
+                let snapshot = pool_snapshot(&pool_id, after, at);
+                cached.shift = cached
+                    .latest
+                    .take()
+                    .filter(|previous| previous.timestamp >= cutoff)
+                    .and_then(|previous| largest_shift(&[previous, snapshot.clone()]));
+                cached.latest = Some(snapshot.clone());
+                cached.block = Some(block);
+                Some(snapshot)
+            };
+            let shift = cached.shift;
+            drop(snapshots);
+
+            if let Some(snapshot) = snapshot {
+                provider.record_snapshot(&snapshot).await;
+            }
+            if let Some(shift) = shift {
+                largest = Some(largest.map_or(shift, |l| l.max(shift)));
+            }
+        }
+
+        largest
+    }
+
+    /// Reserves of the swapped assets before and after the transaction's block
+    ///
+    /// Empty without a provider or when no reserves could be read.
+    async fn read_reserves(&self, ctx: &TransactionContext, assets: &[u32]) -> Vec<(u32, PoolReserves, PoolReserves)> {
+        let Some(provider) = self.state_provider.as_ref() else {
+            return Vec::new();
+        };
+        let mut reserves = Vec::new();
+
+        for asset in assets {
+            if let Some((before, after)) = provider
+                .reserves_around(*asset, &ctx.transaction.block_hash)
+                .await
+            {
+                reserves.push((*asset, before, after));
+            }
+        }
+
+        reserves
     }
 
     /// Analyze events for Omnipool indicators
@@ -41,8 +179,10 @@ impl OmnipoolManipulationDetector {
         let mut large_price_impact = false;
         let mut flash_loan_pattern = false;
         let mut oracle_deviation = false;
+        let mut swapped_assets = Vec::new();
+        let mut triggering_events = Vec::new();
 
-        for event in &ctx.events {
+        for (index, event) in ctx.events.iter().enumerate() {
             let event_name_lower = event.event_name.to_lowercase();
             let pallet_lower = event.pallet.to_lowercase();
 
@@ -52,15 +192,26 @@ impl OmnipoolManipulationDetector {
                 if event_name_lower.contains("swap") || event_name_lower.contains("trade") {
                     has_swap = true;
                     swap_count += 1;
+                    triggering_events.push(index);
 
                     // Check for large price impact in event data
                     if let Some(data) = &event.event_data {
                         if let Some(impact) = data.get("price_impact").and_then(|v| v.as_f64()) {
-                            if impact > 0.05 { // More than 5% price impact
+                            if impact > LARGE_PRICE_IMPACT {
                                 large_price_impact = true;
                             }
                         }
 
+                        // Remember traded assets for reserve lookups
+                        for field in ["asset_in", "asset_out"] {
+                            if let Some(asset) = data.get(field).and_then(|v| v.as_u64()) {
+                                let asset = asset as u32;
+                                if !swapped_assets.contains(&asset) {
+                                    swapped_assets.push(asset);
+                                }
+                            }
+                        }
+
                         // Check for oracle deviation
                         if let Some(deviation) = data.get("oracle_deviation").and_then(|v| v.as_f64()) {
                             if deviation > 0.03 { // More than 3% deviation
@@ -73,26 +224,32 @@ impl OmnipoolManipulationDetector {
                 // Detect liquidity operations
                 if event_name_lower.contains("addliquidity") || event_name_lower.contains("add_liquidity") {
                     has_add_liquidity = true;
+                    triggering_events.push(index);
                 }
 
                 if event_name_lower.contains("removeliquidity") || event_name_lower.contains("remove_liquidity") {
                     has_remove_liquidity = true;
+                    triggering_events.push(index);
                 }
             }
 
             // Detect flash loan pattern (borrow + repay in same tx)
             if event_name_lower.contains("borrow") || event_name_lower.contains("loan") {
                 // Check if there's also a repay in the same transaction
-                let has_repay = ctx.events.iter().any(|e| {
+                let repay = ctx.events.iter().position(|e| {
                     e.event_name.to_lowercase().contains("repay") ||
                     e.event_name.to_lowercase().contains("repaid")
                 });
-                if has_repay {
+                if let Some(repay) = repay {
                     flash_loan_pattern = true;
+                    triggering_events.extend([index, repay]);
                 }
             }
         }
 
+        triggering_events.sort_unstable();
+        triggering_events.dedup();
+
         OmnipoolIndicators {
             has_swap,
             has_add_liquidity,
@@ -102,6 +259,10 @@ impl OmnipoolManipulationDetector {
             flash_loan_pattern,
             rapid_swaps: swap_count > 2, // More than 2 swaps in one tx
             oracle_deviation,
+            swapped_assets,
+            measured_price_impact: None,
+            pool_shift: None,
+            triggering_events,
         }
     }
 
@@ -129,6 +290,11 @@ impl OmnipoolManipulationDetector {
             confidence += 0.2;
         }
 
+        // Pool moved suddenly across recent snapshots
+        if indicators.pool_shift.is_some() {
+            confidence += 0.3;
+        }
+
         // Add + Remove liquidity in same tx (potential wash trading)
         if indicators.has_add_liquidity && indicators.has_remove_liquidity {
             confidence += 0.15;
@@ -146,7 +312,13 @@ impl OmnipoolManipulationDetector {
         }
 
         if indicators.large_price_impact {
-            evidence.push("Large price impact detected (>5%)".to_string());
+            match indicators.measured_price_impact {
+                Some(impact) => evidence.push(format!(
+                    "Large price impact of {:.1}% measured from Omnipool reserves",
+                    impact * 100.0
+                )),
+                None => evidence.push("Large price impact detected (>5%)".to_string()),
+            }
         }
 
         if indicators.rapid_swaps {
@@ -157,6 +329,13 @@ impl OmnipoolManipulationDetector {
             evidence.push("Oracle price deviation detected (>3%)".to_string());
         }
 
+        if let Some(shift) = indicators.pool_shift {
+            evidence.push(format!(
+                "Pool price or liquidity moved {:.1}% since the previous snapshot",
+                shift * 100.0
+            ));
+        }
+
         if indicators.has_add_liquidity && indicators.has_remove_liquidity {
             evidence.push("Liquidity added and removed in same transaction".to_string());
         }
@@ -165,29 +344,85 @@ impl OmnipoolManipulationDetector {
     }
 }
 
+/// Snapshot of an Omnipool asset from its reserves at `at`
+fn pool_snapshot(pool_id: &str, reserves: &PoolReserves, at: chrono::DateTime<chrono::Utc>) -> HydrationPoolState {
+    HydrationPoolState {
+        timestamp: at,
+        pool_id: pool_id.to_string(),
+        pool_type: "omnipool".to_string(),
+        total_liquidity: Some(reserves.asset_reserve as f64),
+        oracle_price: reserves.spot_price(),
+        oracle_deviation: None,
+        asset_reserves: Some(serde_json::json!({
+            "asset_reserve": reserves.asset_reserve.to_string(),
+            "hub_reserve": reserves.hub_reserve.to_string(),
+        })),
+    }
+}
+
 #[async_trait]
 impl Detector for OmnipoolManipulationDetector {
     fn name(&self) -> &str {
         "Omnipool Manipulation Detector"
     }
 
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Price impact, oracle deviation and flash-loan-backed trades against the Hydration Omnipool")
+            .with_patterns(&[AttackPattern::OmnipoolManipulation])
+            .with_pallets(&["Omnipool"])
+    }
+
+    fn version(&self) -> &str {
+        // 2: price impact measured from chain state when available
+        // 3: sudden shifts across stored pool snapshots
+        // 4: snapshots taken after each block, shift measured into the current one
+        "4"
+    }
+
     async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
         if !self.enabled {
             return DetectionResult::safe();
         }
 
-        let indicators = Self::analyze_events(ctx);
+        let mut indicators = Self::analyze_events(ctx);
 
         // Only analyze if there's Omnipool activity
         if !indicators.has_swap && !indicators.has_add_liquidity && !indicators.has_remove_liquidity {
             return DetectionResult::safe();
         }
 
+        // Reserves measured on chain take precedence over event-reported impact
+        if indicators.has_swap {
+            let reserves = self.read_reserves(ctx, &indicators.swapped_assets).await;
+            let impact = reserves
+                .iter()
+                .filter_map(|(_, before, after)| before.price_impact(after))
+                .reduce(f64::max);
+            if let Some(impact) = impact {
+                indicators.measured_price_impact = Some(impact);
+                indicators.large_price_impact = impact > LARGE_PRICE_IMPACT;
+            }
+
+            let after: Vec<(u32, PoolReserves)> = reserves
+                .iter()
+                .map(|(asset, _, after)| (*asset, *after))
+                .collect();
+            indicators.pool_shift = self
+                .measure_pool_shift(ctx, &after)
+                .await
+                .filter(|shift| *shift > POOL_SHIFT_THRESHOLD);
+        }
+
         let confidence = Self::calculate_confidence(&indicators);
         let evidence = Self::build_evidence(&indicators);
         let detected = confidence > 0.5;
 
         DetectionResult {
+            schema_version: SCHEMA_VERSION,
             detected,
             confidence,
             pattern: AttackPattern::OmnipoolManipulation,
@@ -198,6 +433,8 @@ impl Detector for OmnipoolManipulationDetector {
             },
             evidence,
             metadata: HashMap::new(),
+            triggering_events: indicators.triggering_events,
+            triggering_state_changes: Vec::new(),
         }
     }
 
@@ -206,9 +443,14 @@ impl Detector for OmnipoolManipulationDetector {
     }
 }
 
+/// Default withdrawal amount considered large by the liquidity drain detector
+pub const DEFAULT_LARGE_WITHDRAWAL_FLOOR: f64 = 1_000_000.0;
+
 /// Detector for liquidity drain attacks
 pub struct LiquidityDrainDetector {
     enabled: bool,
+    /// Withdrawal amount above which a withdrawal counts as large
+    large_withdrawal_floor: f64,
 }
 
 /// Liquidity drain indicators
@@ -218,21 +460,33 @@ struct LiquidityDrainIndicators {
     rapid_succession: bool,
     pool_depletion_risk: bool,
     suspicious_timing: bool,
+    /// Indices of the withdrawal events
+    triggering_events: Vec<usize>,
 }
 
 impl LiquidityDrainDetector {
     pub fn new() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            large_withdrawal_floor: DEFAULT_LARGE_WITHDRAWAL_FLOOR,
+        }
+    }
+
+    /// Set the withdrawal amount above which a withdrawal counts as large
+    pub fn with_large_withdrawal_floor(mut self, floor: f64) -> Self {
+        self.large_withdrawal_floor = floor;
+        self
     }
 
     /// Analyze events for liquidity drain indicators
-    fn analyze_events(ctx: &TransactionContext) -> LiquidityDrainIndicators {
+    fn analyze_events(&self, ctx: &TransactionContext) -> LiquidityDrainIndicators {
         let mut withdrawal_count = 0;
         let mut large_withdrawal = false;
         let mut pool_depletion_risk = false;
         let mut suspicious_timing = false;
+        let mut triggering_events = Vec::new();
 
-        for event in &ctx.events {
+        for (index, event) in ctx.events.iter().enumerate() {
             let event_name_lower = event.event_name.to_lowercase();
             let pallet_lower = event.pallet.to_lowercase();
 
@@ -242,15 +496,21 @@ impl LiquidityDrainDetector {
                    event_name_lower.contains("remove_liquidity") ||
                    event_name_lower.contains("withdraw") {
                     withdrawal_count += 1;
+                    triggering_events.push(index);
+
+                    // Check withdrawal size, falling back to the value the
+                    // whole transaction moved when the event has no amount
+                    let amount = event
+                        .event_data
+                        .as_ref()
+                        .and_then(|data| data.get("amount"))
+                        .and_then(json_amount)
+                        .or_else(|| ctx.total_value_moved());
+                    if amount.is_some_and(|amount| amount as f64 > self.large_withdrawal_floor) {
+                        large_withdrawal = true;
+                    }
 
-                    // Check withdrawal size
                     if let Some(data) = &event.event_data {
-                        if let Some(amount) = data.get("amount").and_then(|v| v.as_f64()) {
-                            // Check if withdrawal is more than 10% of pool (simplified)
-                            if amount > 1_000_000.0 { // Large amount threshold
-                                large_withdrawal = true;
-                            }
-                        }
 
                         // Check pool state
                         if let Some(remaining) = data.get("remaining_liquidity").and_then(|v| v.as_f64()) {
@@ -274,6 +534,7 @@ impl LiquidityDrainDetector {
             rapid_succession: withdrawal_count > 1,
             pool_depletion_risk,
             suspicious_timing,
+            triggering_events,
         }
     }
 
@@ -334,12 +595,18 @@ impl Detector for LiquidityDrainDetector {
         "Liquidity Drain Detector"
     }
 
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Large or sudden liquidity withdrawals from Hydration pools")
+            .with_patterns(&[AttackPattern::LiquidityDrain])
+            .with_pallets(&["Omnipool"])
+    }
+
     async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
         if !self.enabled {
             return DetectionResult::safe();
         }
 
-        let indicators = Self::analyze_events(ctx);
+        let indicators = self.analyze_events(ctx);
 
         // Only analyze if there are withdrawals
         if indicators.multiple_withdrawals == 0 {
@@ -351,6 +618,7 @@ impl Detector for LiquidityDrainDetector {
         let detected = confidence > 0.5;
 
         DetectionResult {
+            schema_version: SCHEMA_VERSION,
             detected,
             confidence,
             pattern: AttackPattern::LiquidityDrain,
@@ -361,6 +629,8 @@ impl Detector for LiquidityDrainDetector {
             },
             evidence,
             metadata: HashMap::new(),
+            triggering_events: indicators.triggering_events,
+            triggering_state_changes: Vec::new(),
         }
     }
 
@@ -382,6 +652,8 @@ struct CollateralIndicators {
     health_factor_drop: bool,
     flash_loan_liquidation: bool,
     cascade_risk: bool,
+    /// Indices of the liquidation and collateral events
+    triggering_events: Vec<usize>,
 }
 
 impl CollateralManipulationDetector {
@@ -397,6 +669,7 @@ impl CollateralManipulationDetector {
         let mut health_factor_drop = false;
         let mut flash_loan_liquidation = false;
         let mut cascade_risk = false;
+        let mut triggering_events = Vec::new();
 
         // Check for flash loans
         let has_flash_loan = ctx.events.iter().any(|e| {
@@ -405,7 +678,7 @@ impl CollateralManipulationDetector {
             ctx.events.iter().any(|e2| e2.event_name.to_lowercase().contains("repay"))
         });
 
-        for event in &ctx.events {
+        for (index, event) in ctx.events.iter().enumerate() {
             let event_name_lower = event.event_name.to_lowercase();
             let pallet_lower = event.pallet.to_lowercase();
 
@@ -415,6 +688,7 @@ impl CollateralManipulationDetector {
                 if event_name_lower.contains("liquidat") {
                     has_liquidation = true;
                     liquidation_count += 1;
+                    triggering_events.push(index);
 
                     if has_flash_loan {
                         flash_loan_liquidation = true;
@@ -431,6 +705,9 @@ impl CollateralManipulationDetector {
                 // Detect collateral changes
                 if event_name_lower.contains("collateral") {
                     has_collateral_change = true;
+                    if triggering_events.last() != Some(&index) {
+                        triggering_events.push(index);
+                    }
 
                     // Check health factor
                     if let Some(data) = &event.event_data {
@@ -454,6 +731,7 @@ impl CollateralManipulationDetector {
             health_factor_drop,
             flash_loan_liquidation,
             cascade_risk,
+            triggering_events,
         }
     }
 
@@ -514,6 +792,11 @@ impl Detector for CollateralManipulationDetector {
         "Collateral Manipulation Detector"
     }
 
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Borrowing around collateral changes and liquidation cascades in lending markets")
+            .with_patterns(&[AttackPattern::CollateralManipulation])
+    }
+
     async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
         if !self.enabled {
             return DetectionResult::safe();
@@ -531,6 +814,7 @@ impl Detector for CollateralManipulationDetector {
         let detected = confidence > 0.5;
 
         DetectionResult {
+            schema_version: SCHEMA_VERSION,
             detected,
             confidence,
             pattern: AttackPattern::CollateralManipulation,
@@ -541,6 +825,8 @@ impl Detector for CollateralManipulationDetector {
             },
             evidence,
             metadata: HashMap::new(),
+            triggering_events: indicators.triggering_events,
+            triggering_state_changes: Vec::new(),
         }
     }
 
@@ -552,7 +838,7 @@ impl Detector for CollateralManipulationDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{ChainEvent, ParsedTransaction, StateChange};
+    use crate::types::{ChainEvent, ParsedTransaction};
 
     #[tokio::test]
     async fn test_omnipool_detector_safe() {
@@ -571,6 +857,7 @@ mod tests {
                 timestamp: 0,
                 signature: None,
                 nonce: None,
+                decoded_call: Default::default(),
             },
             events: vec![],
             state_changes: vec![],
@@ -580,6 +867,200 @@ mod tests {
         assert!(!result.detected);
     }
 
+    use crate::database::models::HydrationPoolState;
+    use crate::detectors::omnipool_state::PoolReserves;
+
+    /// State provider returning fixed reserves, or nothing (RPC unavailable)
+    struct MockOmnipoolState {
+        reserves: Option<(PoolReserves, PoolReserves)>,
+    }
+
+    #[async_trait]
+    impl OmnipoolStateProvider for MockOmnipoolState {
+        async fn reserves_around(&self, _asset_id: u32, _block_hash: &str) -> Option<(PoolReserves, PoolReserves)> {
+            self.reserves
+        }
+    }
+
+    /// Three swaps of asset 5, with or without a pallet-reported price impact
+    fn swap_context(price_impact: Option<f64>) -> TransactionContext {
+        let mut data = serde_json::json!({ "asset_in": 5, "asset_out": 0 });
+        if let Some(impact) = price_impact {
+            data["price_impact"] = serde_json::json!(impact);
+        }
+
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: "0x789".to_string(),
+                block_number: 102,
+                block_hash: format!("0x{}", "ab".repeat(32)),
+                index: 0,
+                caller: "Trader".to_string(),
+                pallet: "Omnipool".to_string(),
+                call: "sell".to_string(),
+                args: vec![],
+                success: true,
+                timestamp: 0,
+                signature: None,
+                nonce: None,
+                decoded_call: Default::default(),
+            },
+            events: (0..3)
+                .map(|_| ChainEvent::new("Omnipool", "SwapExecuted").with_event_data(data.clone()))
+                .collect(),
+            state_changes: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_omnipool_price_impact_from_state() {
+        // Asset reserve drops 20% against a fixed hub reserve: 25% price move
+        let provider = MockOmnipoolState {
+            reserves: Some((
+                PoolReserves { asset_reserve: 1_000_000, hub_reserve: 1_000_000 },
+                PoolReserves { asset_reserve: 800_000, hub_reserve: 1_000_000 },
+            )),
+        };
+        let detector = OmnipoolManipulationDetector::new().with_state_provider(Arc::new(provider));
+
+        // No price_impact field in the events
+        let result = detector.analyze_transaction(&swap_context(None)).await;
+
+        assert!(result.detected);
+        assert!(result.evidence.iter().any(|e| e.contains("measured from Omnipool reserves")));
+
+        // Without chain state the same transaction stays below the threshold
+        let result = OmnipoolManipulationDetector::new()
+            .analyze_transaction(&swap_context(None))
+            .await;
+        assert!(!result.detected);
+    }
+
+    /// History provider returning fixed snapshots for every pool and
+    /// recording the snapshots it is asked to store
+    ///
+    /// Snapshots are a minute apart and end at `end`, or at the requested
+    /// time when unset.
+    struct MockPoolHistory {
+        prices: Vec<f64>,
+        end: Option<chrono::DateTime<chrono::Utc>>,
+        loads: std::sync::atomic::AtomicUsize,
+        recorded: std::sync::Mutex<Vec<HydrationPoolState>>,
+    }
+
+    impl MockPoolHistory {
+        fn new(prices: Vec<f64>) -> Self {
+            Self { prices, end: None, loads: Default::default(), recorded: Default::default() }
+        }
+    }
+
+    #[async_trait]
+    impl PoolHistoryProvider for MockPoolHistory {
+        async fn pool_history(
+            &self,
+            pool_id: &str,
+            until: chrono::DateTime<chrono::Utc>,
+            _hours: i32,
+        ) -> Option<Vec<HydrationPoolState>> {
+            self.loads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let start = self.end.unwrap_or(until) - chrono::Duration::minutes(self.prices.len() as i64);
+            Some(
+                self.prices
+                    .iter()
+                    .enumerate()
+                    .map(|(i, price)| HydrationPoolState {
+                        timestamp: start + chrono::Duration::minutes(i as i64),
+                        pool_id: pool_id.to_string(),
+                        pool_type: "omnipool".to_string(),
+                        total_liquidity: Some(1_000_000.0),
+                        oracle_price: Some(*price),
+                        oracle_deviation: None,
+                        asset_reserves: None,
+                    })
+                    .collect(),
+            )
+        }
+
+        async fn record_snapshot(&self, state: &HydrationPoolState) {
+            self.recorded.lock().unwrap().push(state.clone());
+        }
+    }
+
+    /// Reserves at a spot price of 1.5 that the block leaves unchanged
+    fn steady_reserves() -> MockOmnipoolState {
+        let reserves = PoolReserves { asset_reserve: 1_000_000, hub_reserve: 1_500_000 };
+        MockOmnipoolState { reserves: Some((reserves, reserves)) }
+    }
+
+    #[tokio::test]
+    async fn test_omnipool_pool_shift_from_history() {
+        // The last stored snapshot priced the asset at 1.0
+        let history = Arc::new(MockPoolHistory::new(vec![1.0, 1.02, 1.0]));
+        let detector = OmnipoolManipulationDetector::new()
+            .with_state_provider(Arc::new(steady_reserves()))
+            .with_pool_history(history.clone(), DEFAULT_POOL_HISTORY_HOURS);
+
+        let result = detector.analyze_transaction(&swap_context(None)).await;
+        assert!(result.detected);
+        assert!(result.evidence.iter().any(|e| e.contains("since the previous snapshot")));
+
+        // Later transactions of the same block share its snapshot and shift
+        assert!(detector.analyze_transaction(&swap_context(None)).await.detected);
+
+        // The move is not held against the next block, where the pool is steady
+        let mut next = swap_context(None);
+        next.transaction.block_number += 1;
+        assert!(!detector.analyze_transaction(&next).await.detected);
+
+        // Stored history is read once per swapped asset; each asset gets one
+        // snapshot per block
+        assert_eq!(history.loads.load(std::sync::atomic::Ordering::SeqCst), 2);
+        let recorded = history.recorded.lock().unwrap();
+        assert_eq!(recorded.len(), 4);
+        assert_eq!(recorded[0].pool_id, "5");
+        assert_eq!(recorded[0].oracle_price, Some(1.5));
+        assert_eq!(recorded[0].timestamp, chrono::DateTime::UNIX_EPOCH);
+        assert_eq!(recorded[1].pool_id, "0");
+
+        // Gradual drift stays below the threshold
+        let drift = Arc::new(MockPoolHistory::new(vec![1.3, 1.35, 1.4, 1.45]));
+        let detector = OmnipoolManipulationDetector::new()
+            .with_state_provider(Arc::new(steady_reserves()))
+            .with_pool_history(drift, DEFAULT_POOL_HISTORY_HOURS);
+        assert!(!detector.analyze_transaction(&swap_context(None)).await.detected);
+    }
+
+    #[tokio::test]
+    async fn test_omnipool_history_window_ends_at_transaction_time() {
+        // History from the transaction's own past, hours before wall-clock now
+        let detector = |end| {
+            let history = MockPoolHistory { end: Some(end), ..MockPoolHistory::new(vec![1.0]) };
+            OmnipoolManipulationDetector::new()
+                .with_state_provider(Arc::new(steady_reserves()))
+                .with_pool_history(Arc::new(history), DEFAULT_POOL_HISTORY_HOURS)
+        };
+        let mut ctx = swap_context(None);
+        ctx.transaction.timestamp = 600;
+
+        let end = chrono::DateTime::UNIX_EPOCH;
+        assert!(detector(end).analyze_transaction(&ctx).await.detected);
+
+        // The same snapshot is stale for a transaction a window later
+        ctx.transaction.timestamp += DEFAULT_POOL_HISTORY_HOURS as u64 * 3600;
+        assert!(!detector(end).analyze_transaction(&ctx).await.detected);
+    }
+
+    #[tokio::test]
+    async fn test_omnipool_falls_back_to_event_field() {
+        let provider = MockOmnipoolState { reserves: None };
+        let detector = OmnipoolManipulationDetector::new().with_state_provider(Arc::new(provider));
+
+        let result = detector.analyze_transaction(&swap_context(Some(0.1))).await;
+
+        assert!(result.detected);
+        assert!(result.evidence.iter().any(|e| e.contains("Large price impact detected")));
+    }
+
     #[tokio::test]
     async fn test_liquidity_drain_detector() {
         let detector = LiquidityDrainDetector::new();
@@ -597,16 +1078,13 @@ mod tests {
                 timestamp: 0,
                 signature: None,
                 nonce: None,
+                decoded_call: Default::default(),
             },
             events: vec![
-                ChainEvent {
-                    pallet: "Omnipool".to_string(),
-                    event_name: "LiquidityRemoved".to_string(),
-                    event_data: Some(serde_json::json!({
-                        "amount": 2000000.0,
-                        "remaining_liquidity": 50000.0
-                    })),
-                },
+                ChainEvent::new("Omnipool", "LiquidityRemoved").with_event_data(serde_json::json!({
+                    "amount": 2000000.0,
+                    "remaining_liquidity": 50000.0
+                })),
             ],
             state_changes: vec![],
         };
@@ -615,4 +1093,33 @@ mod tests {
         assert!(result.detected);
         assert!(result.confidence >= 0.5);
     }
+
+    #[tokio::test]
+    async fn test_liquidity_drain_withdrawal_without_amount_uses_value_moved() {
+        let detector = LiquidityDrainDetector::new();
+        let ctx = TransactionContext {
+            transaction: ParsedTransaction {
+                hash: "0x457".to_string(),
+                block_number: 101,
+                block_hash: "0xdef".to_string(),
+                index: 0,
+                caller: "Attacker".to_string(),
+                pallet: "Omnipool".to_string(),
+                call: "remove_liquidity".to_string(),
+                args: vec![],
+                success: true,
+                timestamp: 0,
+                signature: None,
+                nonce: None,
+                decoded_call: Default::default(),
+            },
+            events: vec![
+                ChainEvent::new("Omnipool", "LiquidityWithdrawn").with_event_data(serde_json::json!({})),
+                ChainEvent::new("Tokens", "Transfer").with_event_data(serde_json::json!({ "amount": "2000000" })),
+            ],
+            state_changes: vec![],
+        };
+
+        assert!(detector.analyze_events(&ctx).large_withdrawal);
+    }
 }
@@ -0,0 +1,388 @@
+//! Layering detector
+//!
+//! Money-laundering-style layering moves funds through a chain of accounts
+//! controlled by one entity to obscure their origin. Within a single block,
+//! decoded transfers form a graph (signer -> destination). Flags:
+//! 1. Cycles of `MIN_CYCLE_HOPS` or more transfers that return funds to the
+//!    account that started them (A -> B -> C -> A)
+//! 2. Chains of `LONG_CHAIN_HOPS` or more consecutive hops, where each hop is
+//!    sent by the previous hop's recipient after it received the funds
+//!
+//! The transfer that closes the cycle (or extends the chain past the limit)
+//! is flagged, with every hop in the evidence. Chains follow transfers in
+//! block order, so the longest chain into each account is kept as transfers
+//! arrive instead of searched for.
+//!
+//! `analyze_batch` builds the graph from a block's contexts. When fed one
+//! transaction at a time, the detector keeps the current block's transfers
+//! and resets them when the block number changes. Signers must be SS58 or
+//! 0x-prefixed hex account IDs. Destinations come from
+//! `ParsedTransaction::transfer_destination`, which reads the decoded `dest`
+//! argument or falls back to the raw `Balances` transfer arguments, so only
+//! `Balances` transfers are followed.
+
+use crate::detectors::{parse_account, ss58, Detector, DetectorManifest};
+use crate::types::{AttackPattern, DetectionResult, ParsedTransaction, TransactionContext};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Minimum transfers in a cycle to flag it (A -> B -> A is often a refund)
+const MIN_CYCLE_HOPS: usize = 3;
+
+/// Minimum consecutive hops in a chain to flag it
+const LONG_CHAIN_HOPS: usize = 5;
+
+/// Maximum hops followed when searching the transfer graph, and listed as
+/// evidence for a chain
+const MAX_SEARCH_HOPS: usize = 10;
+
+/// Confidence for a transfer cycle (maps to High severity)
+const CYCLE_CONFIDENCE: f64 = 0.8;
+
+/// Confidence for a long transfer chain
+const CHAIN_CONFIDENCE: f64 = 0.65;
+
+/// A decoded transfer within a block
+#[derive(Debug, Clone)]
+struct Transfer {
+    from: [u8; 32],
+    to: [u8; 32],
+    tx_hash: String,
+    /// Previous hop of the longest chain ending with this transfer
+    prev: Option<usize>,
+}
+
+/// Transfers seen so far in one block
+#[derive(Debug, Default)]
+struct BlockTransfers {
+    block_number: u64,
+    transfers: Vec<Transfer>,
+    /// Longest chain ending at each account, as (hops, last transfer)
+    chain_ends: HashMap<[u8; 32], (usize, usize)>,
+}
+
+impl BlockTransfers {
+    fn new(block_number: u64) -> Self {
+        Self {
+            block_number,
+            ..Self::default()
+        }
+    }
+
+    /// Hops from `start` to `target`, as transfer indices, if such a path exists
+    fn path(&self, start: [u8; 32], target: [u8; 32]) -> Option<Vec<usize>> {
+        let mut path = Vec::new();
+        let mut visited = Vec::new();
+        self.search(start, target, &mut path, &mut visited).then_some(path)
+    }
+
+    fn search(
+        &self,
+        current: [u8; 32],
+        target: [u8; 32],
+        path: &mut Vec<usize>,
+        visited: &mut Vec<[u8; 32]>,
+    ) -> bool {
+        if current == target && !path.is_empty() {
+            return true;
+        }
+        if path.len() >= MAX_SEARCH_HOPS || visited.contains(&current) {
+            return false;
+        }
+        visited.push(current);
+
+        for (index, transfer) in self.transfers.iter().enumerate() {
+            if transfer.from != current {
+                continue;
+            }
+            path.push(index);
+            if self.search(transfer.to, target, path, visited) {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    /// Last `MAX_SEARCH_HOPS` hops of the longest chain ending with `last`,
+    /// as transfer indices
+    fn chain_ending_at(&self, last: usize) -> Vec<usize> {
+        let mut hops = vec![last];
+        while let Some(prev) = self.transfers[hops[hops.len() - 1]].prev {
+            if hops.len() >= MAX_SEARCH_HOPS {
+                break;
+            }
+            hops.push(prev);
+        }
+        hops.reverse();
+        hops
+    }
+
+    /// Record a transfer and check whether it completes a layering pattern
+    fn add(&mut self, mut transfer: Transfer) -> Option<DetectionResult> {
+        // A path back from the destination to the sender means this transfer closes a cycle
+        let cycle = self.path(transfer.to, transfer.from);
+
+        // Extend the longest chain that delivered funds to the sender
+        let closing = self.transfers.len();
+        let (chain_hops, prev) = match self.chain_ends.get(&transfer.from) {
+            Some(&(hops, last)) => (hops + 1, Some(last)),
+            None => (1, None),
+        };
+        transfer.prev = prev;
+        if self.chain_ends.get(&transfer.to).map_or(true, |&(hops, _)| chain_hops > hops) {
+            self.chain_ends.insert(transfer.to, (chain_hops, closing));
+        }
+        self.transfers.push(transfer);
+
+        if let Some(mut hops) = cycle {
+            hops.push(closing);
+            if hops.len() >= MIN_CYCLE_HOPS {
+                return Some(self.detection(&hops, true));
+            }
+        }
+
+        (chain_hops >= LONG_CHAIN_HOPS).then(|| self.detection(&self.chain_ending_at(closing), false))
+    }
+
+    fn detection(&self, hops: &[usize], is_cycle: bool) -> DetectionResult {
+        let origin = ss58(self.transfers[hops[0]].from);
+        let mut evidence: Vec<String> = hops
+            .iter()
+            .map(|&i| {
+                let t = &self.transfers[i];
+                format!("{} -> {} (tx {})", ss58(t.from), ss58(t.to), t.tx_hash)
+            })
+            .collect();
+
+        let (confidence, description) = if is_cycle {
+            evidence.insert(0, format!("Funds returned to {} after {} hops", origin, hops.len()));
+            (
+                CYCLE_CONFIDENCE,
+                format!(
+                    "Transfer cycle of {} hops starting and ending at {} in block #{}",
+                    hops.len(),
+                    origin,
+                    self.block_number
+                ),
+            )
+        } else {
+            evidence.insert(0, format!("{} consecutive hops within one block", hops.len()));
+            (
+                CHAIN_CONFIDENCE,
+                format!(
+                    "Transfer chain of {} hops from {} in block #{}",
+                    hops.len(),
+                    origin,
+                    self.block_number
+                ),
+            )
+        };
+
+        DetectionResult::detected(AttackPattern::Layering, confidence, description, evidence)
+    }
+}
+
+/// Detector for same-block transfer cycles and chains
+pub struct LayeringDetector {
+    enabled: bool,
+    /// Transfers of the block currently being processed
+    current_block: Arc<RwLock<BlockTransfers>>,
+}
+
+impl LayeringDetector {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            current_block: Arc::new(RwLock::new(BlockTransfers::default())),
+        }
+    }
+
+    /// Decode a transaction into a transfer edge
+    fn transfer(tx: &ParsedTransaction) -> Option<Transfer> {
+        Some(Transfer {
+            from: parse_account(&tx.caller)?,
+            to: tx.transfer_destination()?,
+            tx_hash: tx.hash.clone(),
+            prev: None,
+        })
+    }
+}
+
+impl Default for LayeringDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Detector for LayeringDetector {
+    fn name(&self) -> &str {
+        "Layering Detector"
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Transfer cycles and long hop chains within a block that obscure the origin of funds")
+            .with_patterns(&[AttackPattern::Layering])
+            .with_pallets(&["Balances"])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        let tx = &ctx.transaction;
+        let Some(transfer) = Self::transfer(tx) else {
+            return DetectionResult::no_detection();
+        };
+
+        let mut block = self.current_block.write().await;
+        if block.block_number != tx.block_number {
+            *block = BlockTransfers::new(tx.block_number);
+        }
+
+        block.add(transfer).unwrap_or_else(DetectionResult::no_detection)
+    }
+
+    async fn analyze_batch(&self, contexts: &[TransactionContext]) -> Vec<DetectionResult> {
+        let mut block = BlockTransfers::default();
+
+        contexts
+            .iter()
+            .map(|ctx| {
+                let tx = &ctx.transaction;
+                let Some(transfer) = Self::transfer(tx) else {
+                    return DetectionResult::no_detection();
+                };
+                if block.block_number != tx.block_number {
+                    block = BlockTransfers::new(tx.block_number);
+                }
+                block.add(transfer).unwrap_or_else(DetectionResult::no_detection)
+            })
+            .collect()
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(byte: u8) -> [u8; 32] {
+        [byte; 32]
+    }
+
+    fn transfer(hash: &str, from: [u8; 32], to: [u8; 32]) -> TransactionContext {
+        let mut args = vec![0u8];
+        args.extend_from_slice(&to);
+        args.extend_from_slice(&[0x13, 0x00, 0x00, 0xe8, 0x89, 0x04, 0x23, 0xc7, 0x8a]); // compact-encoded amount
+
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: hash.to_string(),
+                block_number: 7,
+                block_hash: "0xblock7".to_string(),
+                index: 0,
+                caller: ss58(from),
+                pallet: "Balances".to_string(),
+                call: "transfer_keep_alive".to_string(),
+                args,
+                signature: None,
+                nonce: Some(1),
+                timestamp: 1234567890,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events: vec![],
+            state_changes: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_three_hop_cycle_flagged() {
+        let (a, b, c) = (account(1), account(2), account(3));
+        let contexts = vec![
+            transfer("0x01", a, b),
+            transfer("0x02", b, c),
+            transfer("0x03", c, a),
+        ];
+
+        let results = LayeringDetector::new().analyze_batch(&contexts).await;
+
+        assert!(!results[0].detected);
+        assert!(!results[1].detected);
+        assert!(results[2].detected);
+        assert_eq!(results[2].pattern, AttackPattern::Layering);
+        assert_eq!(results[2].confidence, CYCLE_CONFIDENCE);
+        // Summary line plus one line per hop
+        assert_eq!(results[2].evidence.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_independent_transfers_not_flagged() {
+        let contexts = vec![
+            transfer("0x01", account(1), account(2)),
+            transfer("0x02", account(3), account(4)),
+            transfer("0x03", account(5), account(1)),
+            // A direct refund is not layering
+            transfer("0x04", account(2), account(1)),
+        ];
+
+        let results = LayeringDetector::new().analyze_batch(&contexts).await;
+        assert!(results.iter().all(|r| !r.detected));
+    }
+
+    #[tokio::test]
+    async fn test_cycle_detected_one_transaction_at_a_time() {
+        let (a, b, c) = (account(1), account(2), account(3));
+        let detector = LayeringDetector::new();
+
+        assert!(!detector.analyze_transaction(&transfer("0x01", a, b)).await.detected);
+        assert!(!detector.analyze_transaction(&transfer("0x02", b, c)).await.detected);
+
+        // The closing hop lands in the next block: no cycle within a block
+        let mut next_block = transfer("0x03", c, a);
+        next_block.transaction.block_number = 8;
+        assert!(!detector.analyze_transaction(&next_block).await.detected);
+    }
+
+    #[tokio::test]
+    async fn test_long_chain_flagged() {
+        let contexts: Vec<_> = (1..=LONG_CHAIN_HOPS as u8)
+            .map(|i| transfer(&format!("0x{:02x}", i), account(i), account(i + 1)))
+            .collect();
+
+        let results = LayeringDetector::new().analyze_batch(&contexts).await;
+
+        assert!(results[..LONG_CHAIN_HOPS - 1].iter().all(|r| !r.detected));
+        let last = results.last().unwrap();
+        assert!(last.detected);
+        assert_eq!(last.confidence, CHAIN_CONFIDENCE);
+    }
+
+    #[tokio::test]
+    async fn test_dense_transfer_graph_is_fast() {
+        // Every account pays every other: exponentially many paths to search
+        let accounts: Vec<_> = (1..=12u8).map(account).collect();
+        let contexts: Vec<_> = accounts
+            .iter()
+            .flat_map(|&from| accounts.iter().filter(move |&&to| to != from).map(move |&to| (from, to)))
+            .enumerate()
+            .map(|(i, (from, to))| transfer(&format!("0x{:04x}", i), from, to))
+            .collect();
+
+        let started = std::time::Instant::now();
+        let results = LayeringDetector::new().analyze_batch(&contexts).await;
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert!(results.iter().any(|r| r.detected));
+        // Summary line, at most MAX_SEARCH_HOPS hops and the closing transfer
+        assert!(results.iter().all(|r| r.evidence.len() <= MAX_SEARCH_HOPS + 2));
+    }
+}
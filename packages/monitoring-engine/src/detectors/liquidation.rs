@@ -0,0 +1,429 @@
+//! Liquidation front-running detector
+//!
+//! Liquidations on lending protocols pay a bonus to whoever lands first, so
+//! searchers race for them around price changes. Within a single block,
+//! liquidations (same fields as `hydration_liquidations`: account,
+//! liquidator, collateral and debt assets) and oracle price updates are
+//! tracked by extrinsic index. Flags:
+//! 1. A liquidation landing right after a price update on one of its assets
+//!    (back-running the oracle)
+//! 2. A price update landing right after a liquidation on one of its assets
+//!    (the liquidation front-ran the price it depended on)
+//! 3. A liquidation of an account another liquidator already went after in
+//!    the same block, including failed attempts (a liquidation race)
+//!
+//! "Right after" means at most `MAX_GAP` extrinsics apart. `analyze_batch`
+//! looks at a block's contexts; when fed one transaction at a time, the
+//! detector keeps the current block's activity and resets it when the block
+//! number changes.
+
+use crate::detectors::{normalize, Detector, DetectorManifest};
+use crate::types::{AttackPattern, ChainEvent, DetectionResult, TransactionContext};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Maximum extrinsic index distance for two transactions to be adjacent
+const MAX_GAP: u32 = 2;
+
+/// Confidence for a liquidation right after a price update on its assets
+const BACKRUN_CONFIDENCE: f64 = 0.75;
+
+/// Confidence for a price update right after a liquidation on its assets
+const FRONTRUN_CONFIDENCE: f64 = 0.7;
+
+/// Confidence for a liquidation competing with another on the same account
+const RACE_CONFIDENCE: f64 = 0.7;
+
+/// Normalized pallets emitting liquidations
+const LENDING_PALLETS: &[&str] = &["lending", "loan", "collateral", "liquidation", "moneymarket"];
+
+/// Detector for MEV around liquidations
+pub struct LiquidationFrontRunningDetector {
+    enabled: bool,
+    current_block: Arc<RwLock<BlockActivity>>,
+}
+
+/// An oracle price update
+#[derive(Debug, Clone)]
+struct PriceUpdate {
+    index: u32,
+    caller: String,
+    assets: Vec<String>,
+}
+
+/// A liquidation, or a failed attempt at one
+#[derive(Debug, Clone)]
+struct Liquidation {
+    index: u32,
+    hash: String,
+    account: String,
+    liquidator: String,
+    assets: Vec<String>,
+    succeeded: bool,
+}
+
+/// Price updates and liquidations seen so far in one block
+#[derive(Debug, Default)]
+struct BlockActivity {
+    block_number: u64,
+    price_updates: Vec<PriceUpdate>,
+    liquidations: Vec<Liquidation>,
+}
+
+/// Whether two asset lists overlap; unknown assets (empty lists) match anything
+fn shares_asset(a: &[String], b: &[String]) -> bool {
+    a.is_empty() || b.is_empty() || a.iter().any(|asset| b.contains(asset))
+}
+
+fn adjacent(earlier: u32, later: u32) -> bool {
+    later > earlier && later - earlier <= MAX_GAP
+}
+
+/// First of `keys` present in `data`, as a string
+fn field(data: &serde_json::Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| match data.get(key)? {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    })
+}
+
+/// Assets referenced by an event or call
+fn assets(data: &serde_json::Value) -> Vec<String> {
+    ["asset", "asset_id", "asset_in", "asset_out", "collateral_asset", "debt_asset"]
+        .iter()
+        .filter_map(|key| field(data, &[*key]))
+        .collect()
+}
+
+fn is_price_update(event: &ChainEvent) -> bool {
+    let name = event.event_name.to_lowercase();
+    event.pallet.to_lowercase().contains("oracle") && (name.contains("price") || name.contains("update"))
+}
+
+fn is_liquidation(event: &ChainEvent) -> bool {
+    let pallet = normalize(&event.pallet);
+    LENDING_PALLETS.iter().any(|p| pallet.contains(p)) && event.event_name.to_lowercase().contains("liquidat")
+}
+
+impl BlockActivity {
+    fn reset_if_new(&mut self, block_number: u64) {
+        if self.block_number != block_number {
+            *self = BlockActivity {
+                block_number,
+                ..Default::default()
+            };
+        }
+    }
+
+    /// Record the transaction's activity, returning a detection if it raced
+    /// a price update or another liquidation
+    fn add(&mut self, ctx: &TransactionContext) -> Option<DetectionResult> {
+        let (price_updates, liquidations) = LiquidationFrontRunningDetector::extract(ctx);
+        if price_updates.is_empty() && liquidations.is_empty() {
+            return None;
+        }
+
+        let mut evidence = Vec::new();
+        let mut confidences = Vec::new();
+
+        for liquidation in liquidations.iter().filter(|l| l.succeeded) {
+            if let Some(update) = self
+                .price_updates
+                .iter()
+                .find(|u| adjacent(u.index, liquidation.index) && shares_asset(&u.assets, &liquidation.assets))
+            {
+                evidence.push(format!(
+                    "Liquidation of {} by {} at extrinsic {} follows a price update by {} at extrinsic {}",
+                    liquidation.account, liquidation.liquidator, liquidation.index, update.caller, update.index
+                ));
+                confidences.push(BACKRUN_CONFIDENCE);
+            }
+
+            let rivals: Vec<&Liquidation> = self
+                .liquidations
+                .iter()
+                .filter(|l| l.account == liquidation.account && l.liquidator != liquidation.liquidator)
+                .collect();
+            if !rivals.is_empty() {
+                evidence.push(format!(
+                    "Account {} targeted by {} other liquidator(s) in block {}: {}",
+                    liquidation.account,
+                    rivals.len(),
+                    self.block_number,
+                    rivals.iter().map(|l| l.hash.as_str()).collect::<Vec<_>>().join(", ")
+                ));
+                confidences.push(RACE_CONFIDENCE);
+            }
+        }
+
+        for update in &price_updates {
+            if let Some(liquidation) = self
+                .liquidations
+                .iter()
+                .find(|l| l.succeeded && adjacent(l.index, update.index) && shares_asset(&l.assets, &update.assets))
+            {
+                evidence.push(format!(
+                    "Price update by {} at extrinsic {} follows liquidation of {} by {} at extrinsic {}",
+                    update.caller, update.index, liquidation.account, liquidation.liquidator, liquidation.index
+                ));
+                confidences.push(FRONTRUN_CONFIDENCE);
+            }
+        }
+
+        self.price_updates.extend(price_updates);
+        self.liquidations.extend(liquidations);
+
+        if evidence.is_empty() {
+            return None;
+        }
+        let strongest = confidences.iter().copied().fold(0.0, f64::max);
+
+        // Each additional signal makes a coincidence less likely
+        let confidence = (strongest + 0.1 * (confidences.len() - 1) as f64).min(0.95);
+
+        let triggers = ctx
+            .events
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| is_liquidation(e) || is_price_update(e))
+            .map(|(index, _)| index)
+            .collect();
+
+        Some(
+            DetectionResult::detected(
+                AttackPattern::Mev,
+                confidence,
+                format!(
+                    "Liquidation MEV in block {}: liquidation raced a price update or another liquidator",
+                    self.block_number
+                ),
+                evidence,
+            )
+            .with_triggers(triggers, vec![]),
+        )
+    }
+}
+
+impl LiquidationFrontRunningDetector {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            current_block: Arc::new(RwLock::new(BlockActivity::default())),
+        }
+    }
+
+    /// Price updates and liquidations in the transaction
+    ///
+    /// A failed liquidation call has no events; its target account comes from
+    /// the decoded call arguments, when available.
+    fn extract(ctx: &TransactionContext) -> (Vec<PriceUpdate>, Vec<Liquidation>) {
+        let tx = &ctx.transaction;
+        let mut price_updates = Vec::new();
+        let mut liquidations = Vec::new();
+
+        for event in &ctx.events {
+            let data = event.event_data.clone().unwrap_or_default();
+
+            if is_price_update(event) {
+                price_updates.push(PriceUpdate {
+                    index: tx.index,
+                    caller: tx.caller.clone(),
+                    assets: assets(&data),
+                });
+            } else if is_liquidation(event) {
+                let Some(account) = field(&data, &["account", "user", "who", "borrower"]) else {
+                    continue;
+                };
+                liquidations.push(Liquidation {
+                    index: tx.index,
+                    hash: tx.hash.clone(),
+                    account,
+                    liquidator: field(&data, &["liquidator"]).unwrap_or_else(|| tx.caller.clone()),
+                    assets: assets(&data),
+                    succeeded: true,
+                });
+            }
+        }
+
+        if !tx.success && liquidations.is_empty() && tx.call.to_lowercase().contains("liquidat") {
+            if let Some(decoded) = tx.decoded_call.get() {
+                if let Some(account) = field(&decoded.args, &["account", "user", "who", "borrower"]) {
+                    liquidations.push(Liquidation {
+                        index: tx.index,
+                        hash: tx.hash.clone(),
+                        account,
+                        liquidator: tx.caller.clone(),
+                        assets: assets(&decoded.args),
+                        succeeded: false,
+                    });
+                }
+            }
+        }
+
+        (price_updates, liquidations)
+    }
+}
+
+impl Default for LiquidationFrontRunningDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Detector for LiquidationFrontRunningDetector {
+    fn name(&self) -> &str {
+        "Liquidation Front-Running Detector"
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Liquidations racing an oracle price update or another liquidator in the same block")
+            .with_patterns(&[AttackPattern::Mev])
+            .with_pallets(&["Liquidation", "EmaOracle"])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        if !self.enabled {
+            return DetectionResult::no_detection();
+        }
+
+        let mut block = self.current_block.write().await;
+        block.reset_if_new(ctx.transaction.block_number);
+        block.add(ctx).unwrap_or_else(DetectionResult::no_detection)
+    }
+
+    async fn analyze_batch(&self, contexts: &[TransactionContext]) -> Vec<DetectionResult> {
+        let mut block = BlockActivity::default();
+
+        contexts
+            .iter()
+            .map(|ctx| {
+                if !self.enabled {
+                    return DetectionResult::no_detection();
+                }
+                block.reset_if_new(ctx.transaction.block_number);
+                block.add(ctx).unwrap_or_else(DetectionResult::no_detection)
+            })
+            .collect()
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ParsedTransaction;
+
+    fn context(index: u32, caller: &str, pallet: &str, events: Vec<ChainEvent>) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: format!("0xtx{}", index),
+                block_number: 100,
+                block_hash: "0xblock100".to_string(),
+                index,
+                caller: caller.to_string(),
+                pallet: pallet.to_string(),
+                call: "liquidate".to_string(),
+                args: vec![],
+                signature: None,
+                nonce: Some(1),
+                timestamp: 1234567890,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events,
+            state_changes: vec![],
+        }
+    }
+
+    fn price_update(index: u32, asset: u32) -> TransactionContext {
+        context(
+            index,
+            "oracle_feeder",
+            "EmaOracle",
+            vec![ChainEvent::new("EmaOracle", "PriceUpdated").with_event_data(serde_json::json!({ "asset_id": asset, "price": "2500000000" }))],
+        )
+    }
+
+    fn liquidation(index: u32, liquidator: &str, account: &str) -> TransactionContext {
+        context(
+            index,
+            liquidator,
+            "Liquidation",
+            vec![ChainEvent::new("Liquidation", "Liquidated").with_event_data(serde_json::json!({
+                "user": account,
+                "liquidator": liquidator,
+                "collateral_asset": 5,
+                "debt_asset": 10,
+                "debt_to_cover": "1000000000000",
+            }))],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_price_update_then_competing_liquidation_is_flagged() {
+        let contexts = vec![
+            price_update(1, 5),
+            liquidation(2, "searcher_a", "victim"),
+            liquidation(3, "searcher_b", "victim"),
+        ];
+
+        let results = LiquidationFrontRunningDetector::new().analyze_batch(&contexts).await;
+
+        assert!(!results[0].detected);
+
+        // Back-ran the oracle update
+        assert!(results[1].detected);
+        assert_eq!(results[1].pattern, AttackPattern::Mev);
+        assert!(results[1].evidence[0].contains("follows a price update"));
+
+        // Back-ran the update and raced the first liquidator
+        assert!(results[2].detected);
+        assert_eq!(results[2].pattern, AttackPattern::Mev);
+        assert!(results[2].confidence > results[1].confidence);
+        assert!(results[2].evidence.iter().any(|e| e.contains("other liquidator")));
+        assert_eq!(results[2].triggering_events, vec![0]);
+    }
+
+    #[tokio::test]
+    async fn test_unrelated_activity_is_not_flagged() {
+        let detector = LiquidationFrontRunningDetector::new();
+
+        // Price update on another asset, distant liquidations of different accounts
+        let contexts = vec![
+            price_update(1, 42),
+            liquidation(2, "searcher_a", "alice"),
+            liquidation(9, "searcher_a", "bob"),
+        ];
+
+        let results = detector.analyze_batch(&contexts).await;
+        assert!(results.iter().all(|r| !r.detected));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_resets_per_block() {
+        let detector = LiquidationFrontRunningDetector::new();
+
+        assert!(!detector.analyze_transaction(&price_update(1, 5)).await.detected);
+
+        let mut next_block = liquidation(2, "searcher_a", "victim");
+        next_block.transaction.block_number = 101;
+        assert!(!detector.analyze_transaction(&next_block).await.detected);
+
+        // Same block as the liquidation: the price update right after it is flagged
+        let mut update = price_update(3, 10);
+        update.transaction.block_number = 101;
+        let result = detector.analyze_transaction(&update).await;
+        assert!(result.detected);
+        assert!(result.evidence[0].contains("follows liquidation"));
+    }
+}
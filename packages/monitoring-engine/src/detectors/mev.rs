@@ -6,8 +6,8 @@
 //! 3. Back-running: Transaction immediately after large trade
 //! 4. Position-based exploitation
 
-use crate::detectors::Detector;
-use crate::types::{AttackPattern, DetectionResult, TransactionContext};
+use crate::detectors::{Detector, DetectorManifest};
+use crate::types::{AttackPattern, DetectionResult, TransactionContext, SCHEMA_VERSION};
 use async_trait::async_trait;
 use std::collections::HashMap;
 
@@ -291,6 +291,11 @@ impl Detector for MevDetector {
         "MEV Detector"
     }
 
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Sandwich, front-running and back-running sequences around DEX trades within a block")
+            .with_patterns(&[AttackPattern::Mev, AttackPattern::Sandwich, AttackPattern::FrontRunning])
+    }
+
     async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
         let tx = &ctx.transaction;
         let mut suspicion_score: f64 = 0.0;
@@ -328,6 +333,7 @@ impl Detector for MevDetector {
         // If we have suspicion, report it
         if suspicion_score > 0.55 {
             DetectionResult {
+                schema_version: SCHEMA_VERSION,
                 detected: true,
                 confidence: suspicion_score.min(0.90),
                 pattern: AttackPattern::Mev,
@@ -337,6 +343,8 @@ impl Detector for MevDetector {
                 ),
                 evidence,
                 metadata: std::collections::HashMap::new(),
+                triggering_events: Vec::new(),
+                triggering_state_changes: Vec::new(),
             }
         } else {
             DetectionResult::no_detection()
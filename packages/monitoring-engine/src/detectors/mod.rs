@@ -1,21 +1,56 @@
 //! Attack pattern detectors
 
+pub mod allowance;
+pub mod anomaly;
+pub mod bridge_balance;
+pub mod fee;
+pub mod fingerprint;
 pub mod flash_loan;
 pub mod mev;
 pub mod volume;
 pub mod frontrunning;
 pub mod hyperbridge;
 pub mod hydration;
+pub mod layering;
+pub mod liquidation;
+pub mod nonce;
+pub mod omnipool_state;
+pub mod proxy;
+pub mod runtime_upgrade;
+pub mod watchlist;
+pub mod xcm;
 
+pub use allowance::AllowanceAbuseDetector;
+pub use anomaly::UnsupervisedAnomalyDetector;
+pub use bridge_balance::BridgeBalanceDetector;
+pub use fee::FeeAnomalyDetector;
+pub use fingerprint::TransactionFingerprintDetector;
 pub use flash_loan::FlashLoanDetector;
 pub use mev::MevDetector;
 pub use volume::VolumeAnomalyDetector;
 pub use frontrunning::FrontRunningDetector;
 pub use hyperbridge::{CrossChainBridgeDetector, StateProofVerificationDetector};
 pub use hydration::{OmnipoolManipulationDetector, LiquidityDrainDetector, CollateralManipulationDetector};
+pub use layering::LayeringDetector;
+pub use liquidation::LiquidationFrontRunningDetector;
+pub use nonce::NonceAnomalyDetector;
+pub use omnipool_state::{OmnipoolStateProvider, PoolHistoryProvider, PoolReserves, RpcOmnipoolState};
+pub use proxy::ProxyAbuseDetector;
+pub use runtime_upgrade::RuntimeUpgradeDetector;
+pub use watchlist::WatchlistDetector;
+pub use xcm::XcmFanoutDetector;
 
-use crate::types::{DetectionResult, TransactionContext};
+use crate::types::{AttackPattern, DetectionResult, TransactionContext};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use subxt::ext::sp_core::crypto::{AccountId32, Ss58Codec};
+
+/// Priority of detectors that do not set one
+pub const DEFAULT_PRIORITY: i32 = 0;
+
+/// Priority of detectors for privileged operations, which run first
+pub const PRIVILEGED_PRIORITY: i32 = 100;
 
 /// Trait for attack pattern detectors
 #[async_trait]
@@ -23,6 +58,21 @@ pub trait Detector: Send + Sync {
     /// Get the name of this detector
     fn name(&self) -> &str;
 
+    /// Version of the detection logic
+    ///
+    /// Bump this when a detector's heuristics change so stored detections
+    /// can be grouped by the logic that produced them.
+    fn version(&self) -> &str {
+        "1"
+    }
+
+    /// Self-description: emitted patterns, inspected pallets and purpose
+    ///
+    /// The default only carries the name and version.
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "")
+    }
+
     /// Analyze a transaction context for suspicious patterns
     ///
     /// The context includes the transaction itself, associated events,
@@ -42,4 +92,189 @@ pub trait Detector: Send + Sync {
     fn is_enabled(&self) -> bool {
         true
     }
+
+    /// Execution priority; detectors run in descending priority
+    ///
+    /// Detectors flagging privileged operations use [`PRIVILEGED_PRIORITY`]
+    /// so they run before the DeFi detectors and can short-circuit them.
+    fn priority(&self) -> i32 {
+        DEFAULT_PRIORITY
+    }
+
+    /// Whether detections always raise an alert, bypassing confidence thresholds
+    ///
+    /// For informational detectors whose events must never be filtered out
+    /// by a sensitivity profile (e.g. runtime upgrades).
+    fn always_alert(&self) -> bool {
+        false
+    }
+
+    /// Whether results depend on transactions analyzed before
+    ///
+    /// Stateful detectors (baselines, per-block accumulators, histories)
+    /// must see each transaction once, in block order, so they are not run
+    /// on pending extrinsics.
+    fn is_stateful(&self) -> bool {
+        false
+    }
+
+    /// How far each analyst label moves this detector's alert threshold
+    ///
+    /// Net false positives raise the effective threshold by this much each
+    /// (see [`crate::feedback`]). Detectors whose hits are facts rather than
+    /// scores return `0.0` to ignore feedback.
+    fn feedback_step(&self) -> f64 {
+        crate::feedback::DEFAULT_FEEDBACK_STEP
+    }
+
+    /// Serialize state learned across blocks (baselines, histories)
+    ///
+    /// Stateless detectors return `None`. The engine persists snapshots
+    /// periodically and restores them on startup to avoid a cold start.
+    async fn snapshot(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Replace the detector's state with a previously taken snapshot
+    async fn restore(&self, _state: serde_json::Value) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runtime self-description of a detector, for integrators and generated UIs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DetectorManifest {
+    pub name: String,
+    pub version: String,
+    /// Attack patterns the detector can emit
+    pub patterns: Vec<AttackPattern>,
+    /// Pallets whose calls or events are inspected; empty when any pallet may match
+    pub relevant_pallets: Vec<String>,
+    pub description: String,
+    /// Confidence above which detections alert, filled in by the engine;
+    /// `None` for detectors that always alert
+    #[serde(default)]
+    pub alert_threshold: Option<f64>,
+}
+
+impl DetectorManifest {
+    /// Manifest carrying the detector's name and version
+    pub fn new<D: Detector + ?Sized>(detector: &D, description: &str) -> Self {
+        Self {
+            name: detector.name().to_string(),
+            version: detector.version().to_string(),
+            patterns: Vec::new(),
+            relevant_pallets: Vec::new(),
+            description: description.to_string(),
+            alert_threshold: None,
+        }
+    }
+
+    /// Set the attack patterns the detector can emit
+    pub fn with_patterns(mut self, patterns: &[AttackPattern]) -> Self {
+        self.patterns = patterns.to_vec();
+        self
+    }
+
+    /// Set the pallets the detector inspects
+    pub fn with_pallets(mut self, pallets: &[&str]) -> Self {
+        self.relevant_pallets = pallets.iter().map(|p| p.to_string()).collect();
+        self
+    }
+}
+
+/// Persisted state of a single detector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectorSnapshot {
+    /// Detector name
+    pub detector: String,
+    /// Detector version that produced the state
+    pub version: String,
+    pub state: serde_json::Value,
+}
+
+/// Take snapshots of every detector that keeps state
+pub(crate) async fn snapshot_detectors(
+    detectors: &[Box<dyn Detector + Send + Sync>],
+) -> Vec<DetectorSnapshot> {
+    let mut snapshots = Vec::new();
+    for detector in detectors {
+        if let Some(state) = detector.snapshot().await {
+            snapshots.push(DetectorSnapshot {
+                detector: detector.name().to_string(),
+                version: detector.version().to_string(),
+                state,
+            });
+        }
+    }
+    snapshots
+}
+
+/// Restore detectors from snapshots, returning how many were restored
+///
+/// Snapshots from a different detector version are skipped, since their
+/// state may no longer match the detection logic.
+pub(crate) async fn restore_detectors(
+    detectors: &[Box<dyn Detector + Send + Sync>],
+    snapshots: &[DetectorSnapshot],
+) -> usize {
+    let mut restored = 0;
+    for detector in detectors {
+        let Some(snapshot) = snapshots.iter().find(|s| s.detector == detector.name()) else {
+            continue;
+        };
+
+        if snapshot.version != detector.version() {
+            tracing::info!(
+                "Skipping {} state from version {} (running {})",
+                snapshot.detector,
+                snapshot.version,
+                detector.version()
+            );
+            continue;
+        }
+
+        match detector.restore(snapshot.state.clone()).await {
+            Ok(()) => restored += 1,
+            Err(e) => tracing::warn!("Failed to restore {} state: {}", snapshot.detector, e),
+        }
+    }
+    restored
+}
+
+/// Order detectors by descending priority, keeping registration order for ties
+///
+/// `overrides` maps detector names to priorities that replace their own.
+pub(crate) fn sort_by_priority(
+    detectors: &mut [Box<dyn Detector + Send + Sync>],
+    overrides: &HashMap<String, i32>,
+) {
+    detectors.sort_by_key(|detector| {
+        let priority = overrides
+            .get(detector.name())
+            .copied()
+            .unwrap_or_else(|| detector.priority());
+        std::cmp::Reverse(priority)
+    });
+}
+
+/// Parse an SS58 or 0x-prefixed hex account ID
+pub(crate) fn parse_account(account: &str) -> Option<[u8; 32]> {
+    if let Some(hex_id) = account.strip_prefix("0x") {
+        return hex::decode(hex_id).ok()?.try_into().ok();
+    }
+    AccountId32::from_ss58check(account).ok().map(Into::into)
+}
+
+/// SS58 form of an account ID, for evidence and descriptions
+pub(crate) fn ss58(account: [u8; 32]) -> String {
+    AccountId32::from(account).to_ss58check()
+}
+
+/// Lowercase a pallet, call or event name and drop underscores (`as_multi` == `asMulti`)
+pub(crate) fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
 }
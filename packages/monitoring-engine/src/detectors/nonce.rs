@@ -0,0 +1,347 @@
+//! Nonce anomaly detector
+//!
+//! Detects possible account takeover or key compromise by analyzing:
+//! 1. Nonce jumps far beyond the caller's established cadence
+//! 2. Unexpected nonce resets (nonce lower than previously observed)
+//! 3. How well established the caller's history is
+
+use crate::detectors::{Detector, DetectorManifest};
+use crate::types::{AttackPattern, DetectionResult, TransactionContext};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Minimum nonce observations before a caller's cadence is considered established
+const MIN_OBSERVATIONS: u64 = 3;
+
+/// A step must exceed the average step by this factor to count as a jump
+const JUMP_MULTIPLIER: f64 = 10.0;
+
+/// Absolute minimum nonce step considered a jump
+const MIN_JUMP: u64 = 10;
+
+/// Maximum number of callers kept in history
+const MAX_TRACKED_CALLERS: usize = 10_000;
+
+/// Detector for abnormal nonce gaps and resets
+pub struct NonceAnomalyDetector {
+    enabled: bool,
+    /// Per-caller nonce history
+    history: Arc<RwLock<HashMap<String, NonceHistory>>>,
+}
+
+/// Nonce cadence observed for a single caller
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct NonceHistory {
+    last_nonce: u64,
+    last_seen_block: u64,
+    avg_step: f64,
+    observations: u64,
+}
+
+/// Nonce anomaly indicators
+struct NonceIndicators {
+    nonce_jump: Option<u64>,
+    jump_ratio: f64,
+    nonce_reset: Option<u64>,
+    observations: u64,
+}
+
+impl NonceAnomalyDetector {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            history: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Compare the nonce against the caller's history and record it
+    async fn observe_nonce(&self, caller: &str, nonce: u64, block: u64) -> Option<NonceIndicators> {
+        let mut history = self.history.write().await;
+
+        let Some(entry) = history.get_mut(caller) else {
+            if history.len() >= MAX_TRACKED_CALLERS {
+                Self::evict_oldest(&mut history);
+            }
+            history.insert(
+                caller.to_string(),
+                NonceHistory {
+                    last_nonce: nonce,
+                    last_seen_block: block,
+                    avg_step: 0.0,
+                    observations: 1,
+                },
+            );
+            return None;
+        };
+
+        // Same nonce seen again (e.g. mempool then block) carries no new information
+        if nonce == entry.last_nonce {
+            return None;
+        }
+
+        let established = entry.observations >= MIN_OBSERVATIONS;
+        let mut indicators = NonceIndicators {
+            nonce_jump: None,
+            jump_ratio: 0.0,
+            nonce_reset: None,
+            observations: entry.observations,
+        };
+
+        if nonce < entry.last_nonce {
+            if established {
+                indicators.nonce_reset = Some(entry.last_nonce);
+            }
+        } else {
+            let step = nonce - entry.last_nonce;
+            let expected = entry.avg_step.max(1.0);
+            let ratio = step as f64 / expected;
+
+            if established && step >= MIN_JUMP && ratio > JUMP_MULTIPLIER {
+                indicators.nonce_jump = Some(step);
+                indicators.jump_ratio = ratio;
+            } else {
+                // Only fold normal steps into the cadence so an outlier
+                // does not mask the next one
+                entry.avg_step = if entry.observations == 1 {
+                    step as f64
+                } else {
+                    (entry.avg_step * 0.8) + (step as f64 * 0.2)
+                };
+            }
+        }
+
+        entry.last_nonce = nonce;
+        entry.last_seen_block = block;
+        entry.observations += 1;
+
+        Some(indicators)
+    }
+
+    /// Drop the caller that has been inactive the longest
+    fn evict_oldest(history: &mut HashMap<String, NonceHistory>) {
+        if let Some(oldest) = history
+            .iter()
+            .min_by_key(|(_, h)| h.last_seen_block)
+            .map(|(caller, _)| caller.clone())
+        {
+            history.remove(&oldest);
+        }
+    }
+
+    /// Calculate confidence score based on indicators
+    fn calculate_confidence(indicators: &NonceIndicators) -> f64 {
+        let mut confidence: f64 = 0.0;
+
+        if indicators.nonce_jump.is_some() {
+            confidence += 0.6;
+
+            // Jumps an order of magnitude beyond the threshold are more telling
+            if indicators.jump_ratio > JUMP_MULTIPLIER * 10.0 {
+                confidence += 0.15;
+            }
+        }
+
+        if indicators.nonce_reset.is_some() {
+            confidence += 0.7;
+        }
+
+        // A long history makes a deviation more meaningful
+        if confidence > 0.0 && indicators.observations >= 10 {
+            confidence += 0.1;
+        }
+
+        confidence.min(0.95)
+    }
+
+    /// Build evidence list from indicators
+    fn build_evidence(indicators: &NonceIndicators, nonce: u64) -> Vec<String> {
+        let mut evidence = Vec::new();
+
+        if let Some(step) = indicators.nonce_jump {
+            evidence.push(format!(
+                "Nonce jumped by {} to {} ({:.1}x the established cadence)",
+                step, nonce, indicators.jump_ratio
+            ));
+        }
+
+        if let Some(previous) = indicators.nonce_reset {
+            evidence.push(format!(
+                "Nonce reset from {} to {}",
+                previous, nonce
+            ));
+        }
+
+        evidence.push(format!(
+            "Caller history: {} previous transactions",
+            indicators.observations
+        ));
+
+        evidence
+    }
+}
+
+impl Default for NonceAnomalyDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Detector for NonceAnomalyDetector {
+    fn name(&self) -> &str {
+        "Nonce Anomaly Detector"
+    }
+
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Nonce jumps and resets that break a caller's established cadence")
+            .with_patterns(&[AttackPattern::AccountAnomaly])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        let tx = &ctx.transaction;
+
+        let Some(nonce) = tx.nonce else {
+            return DetectionResult::no_detection();
+        };
+
+        let Some(indicators) = self.observe_nonce(&tx.caller, nonce, tx.block_number).await else {
+            return DetectionResult::no_detection();
+        };
+
+        let confidence = Self::calculate_confidence(&indicators);
+        if confidence <= 0.0 {
+            return DetectionResult::no_detection();
+        }
+
+        DetectionResult::detected(
+            AttackPattern::AccountAnomaly,
+            confidence,
+            format!(
+                "Abnormal nonce behaviour for {} - possible account takeover or key compromise",
+                tx.caller
+            ),
+            Self::build_evidence(&indicators, nonce),
+        )
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn snapshot(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(&*self.history.read().await).ok()
+    }
+
+    async fn restore(&self, state: serde_json::Value) -> crate::Result<()> {
+        let history: HashMap<String, NonceHistory> = serde_json::from_value(state)
+            .map_err(|e| crate::Error::ParseError(format!("Invalid nonce detector state: {}", e)))?;
+        *self.history.write().await = history;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ParsedTransaction;
+
+    fn create_context(caller: &str, nonce: u64, block: u64) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: format!("0x{}{}", caller, nonce),
+                block_number: block,
+                block_hash: format!("0xblock{}", block),
+                index: 0,
+                caller: caller.to_string(),
+                pallet: "Balances".to_string(),
+                call: "transfer".to_string(),
+                args: vec![],
+                signature: None,
+                nonce: Some(nonce),
+                timestamp: 1234567890,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events: vec![],
+            state_changes: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_nonce_detector_basic() {
+        let detector = NonceAnomalyDetector::new();
+        assert_eq!(detector.name(), "Nonce Anomaly Detector");
+        assert!(detector.is_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_steady_cadence_then_jump() {
+        let detector = NonceAnomalyDetector::new();
+
+        for nonce in 1..=6 {
+            let result = detector
+                .analyze_transaction(&create_context("alice", nonce, 100 + nonce))
+                .await;
+            assert!(!result.detected, "steady nonce {} should not be flagged", nonce);
+        }
+
+        let result = detector
+            .analyze_transaction(&create_context("alice", 500, 110))
+            .await;
+
+        assert!(result.detected);
+        assert_eq!(result.pattern, AttackPattern::AccountAnomaly);
+        assert!(result.confidence > 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_nonce_reset_detected() {
+        let detector = NonceAnomalyDetector::new();
+
+        for nonce in 40..45 {
+            detector
+                .analyze_transaction(&create_context("bob", nonce, nonce))
+                .await;
+        }
+
+        let result = detector
+            .analyze_transaction(&create_context("bob", 0, 50))
+            .await;
+
+        assert!(result.detected);
+        assert_eq!(result.pattern, AttackPattern::AccountAnomaly);
+    }
+
+    #[tokio::test]
+    async fn test_restored_history_flags_jump() {
+        let detector = NonceAnomalyDetector::new();
+        for nonce in 1..=6 {
+            detector.analyze_transaction(&create_context("dave", nonce, nonce)).await;
+        }
+
+        let restored = NonceAnomalyDetector::new();
+        restored.restore(detector.snapshot().await.unwrap()).await.unwrap();
+
+        let result = restored.analyze_transaction(&create_context("dave", 500, 10)).await;
+        assert!(result.detected);
+    }
+
+    #[tokio::test]
+    async fn test_new_caller_not_flagged() {
+        let detector = NonceAnomalyDetector::new();
+
+        // Without an established cadence, a large nonce is not suspicious
+        let first = detector.analyze_transaction(&create_context("carol", 1000, 1)).await;
+        let second = detector.analyze_transaction(&create_context("carol", 5000, 2)).await;
+
+        assert!(!first.detected);
+        assert!(!second.detected);
+    }
+}
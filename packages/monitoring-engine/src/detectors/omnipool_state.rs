@@ -0,0 +1,273 @@
+//! Omnipool reserve lookups from chain state
+//!
+//! Lets the Omnipool detector measure price impact from the reserves before
+//! and after a block instead of trusting a `price_impact` event field.
+//! Measurements are block-level: other trades in the same block are included.
+//!
+//! Snapshots stored in `hydration_pool_state` add a longer view: the
+//! reserves after each block with a swap are stored, and a sudden move since
+//! the previous snapshot is flagged even when the block itself looks normal.
+
+use crate::connection::ConnectionManager;
+use crate::database::{models::HydrationPoolState, DatabaseClient};
+use crate::storage::storage_prefix;
+use async_trait::async_trait;
+use std::sync::Arc;
+use subxt::config::substrate::H256;
+use subxt::ext::sp_core::hashing::{blake2_128, twox_64};
+
+/// Native asset id, whose balance lives in `System::Account`
+const NATIVE_ASSET_ID: u32 = 0;
+
+/// Omnipool pallet account: `PalletId(*b"omnipool")` into an account id
+const OMNIPOOL_ACCOUNT: [u8; 32] = *b"modlomnipool\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
+
+/// Reserves of a single Omnipool asset
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolReserves {
+    /// Amount of the asset held by the pool
+    pub asset_reserve: u128,
+    /// Hub asset (LRNA) matched against the asset
+    pub hub_reserve: u128,
+}
+
+impl PoolReserves {
+    /// Spot price of the asset denominated in the hub asset
+    pub fn spot_price(&self) -> Option<f64> {
+        if self.asset_reserve == 0 {
+            return None;
+        }
+        Some(self.hub_reserve as f64 / self.asset_reserve as f64)
+    }
+
+    /// Relative price change from `self` to `after`
+    pub fn price_impact(&self, after: &PoolReserves) -> Option<f64> {
+        let before = self.spot_price()?;
+        let after = after.spot_price()?;
+        if before == 0.0 {
+            return None;
+        }
+        Some(((after - before) / before).abs())
+    }
+}
+
+/// Source of Omnipool reserves around a block
+#[async_trait]
+pub trait OmnipoolStateProvider: Send + Sync {
+    /// Reserves of `asset_id` before (parent block) and after the given block
+    ///
+    /// Returns `None` when state is unavailable, e.g. the node is unreachable.
+    async fn reserves_around(&self, asset_id: u32, block_hash: &str) -> Option<(PoolReserves, PoolReserves)>;
+}
+
+/// Source of stored pool snapshots
+///
+/// Omnipool snapshots are keyed by the asset id, as a string.
+#[async_trait]
+pub trait PoolHistoryProvider: Send + Sync {
+    /// Snapshots of `pool_id` from the `hours` before `until`, oldest first
+    ///
+    /// Returns `None` when history is unavailable, e.g. the database is unreachable.
+    async fn pool_history(
+        &self,
+        pool_id: &str,
+        until: chrono::DateTime<chrono::Utc>,
+        hours: i32,
+    ) -> Option<Vec<HydrationPoolState>>;
+
+    /// Store a new snapshot; failures are logged, not returned
+    async fn record_snapshot(&self, state: &HydrationPoolState);
+}
+
+#[async_trait]
+impl PoolHistoryProvider for DatabaseClient {
+    async fn pool_history(
+        &self,
+        pool_id: &str,
+        until: chrono::DateTime<chrono::Utc>,
+        hours: i32,
+    ) -> Option<Vec<HydrationPoolState>> {
+        match self.get_pool_state_history(pool_id, until, hours).await {
+            Ok(history) => Some(history),
+            Err(e) => {
+                tracing::warn!("Failed to load pool state history for {}: {}", pool_id, e);
+                None
+            }
+        }
+    }
+
+    async fn record_snapshot(&self, state: &HydrationPoolState) {
+        if let Err(e) = self.insert_hydration_pool_state(state).await {
+            tracing::warn!("Failed to store pool state for {}: {}", state.pool_id, e);
+        }
+    }
+}
+
+/// Largest relative change in oracle price or total liquidity between
+/// consecutive snapshots (oldest first)
+pub fn largest_shift(history: &[HydrationPoolState]) -> Option<f64> {
+    fn change(before: Option<f64>, after: Option<f64>) -> Option<f64> {
+        let (before, after) = (before?, after?);
+        (before > 0.0).then(|| ((after - before) / before).abs())
+    }
+
+    history
+        .windows(2)
+        .flat_map(|pair| {
+            [
+                change(pair[0].oracle_price, pair[1].oracle_price),
+                change(pair[0].total_liquidity, pair[1].total_liquidity),
+            ]
+        })
+        .flatten()
+        .reduce(f64::max)
+}
+
+/// Reads Omnipool reserves over RPC through the connection manager
+pub struct RpcOmnipoolState {
+    connection: Arc<ConnectionManager>,
+}
+
+impl RpcOmnipoolState {
+    pub fn new(connection: Arc<ConnectionManager>) -> Self {
+        Self { connection }
+    }
+
+    /// Reserves of an asset at a block
+    async fn reserves_at(&self, asset_id: u32, block_hash: H256) -> Option<PoolReserves> {
+        let asset_state = self
+            .connection
+            .storage_at(block_hash, &omnipool_asset_key(asset_id))
+            .await
+            .ok()??;
+        // AssetState { hub_reserve: u128, shares, protocol_shares, cap, tradable }
+        let hub_reserve = decode_u128(&asset_state, 0)?;
+
+        let asset_reserve = if asset_id == NATIVE_ASSET_ID {
+            let account = self
+                .connection
+                .storage_at(block_hash, &system_account_key(&OMNIPOOL_ACCOUNT))
+                .await
+                .ok()??;
+            // AccountInfo { nonce, consumers, providers, sufficients: u32, data: { free: u128, .. } }
+            decode_u128(&account, 16)?
+        } else {
+            let account = self
+                .connection
+                .storage_at(block_hash, &tokens_account_key(&OMNIPOOL_ACCOUNT, asset_id))
+                .await
+                .ok()??;
+            // AccountData { free: u128, reserved, frozen }
+            decode_u128(&account, 0)?
+        };
+
+        Some(PoolReserves { asset_reserve, hub_reserve })
+    }
+}
+
+#[async_trait]
+impl OmnipoolStateProvider for RpcOmnipoolState {
+    async fn reserves_around(&self, asset_id: u32, block_hash: &str) -> Option<(PoolReserves, PoolReserves)> {
+        let block_hash = parse_block_hash(block_hash)?;
+        let parent_hash = self.connection.parent_hash(block_hash).await.ok()?;
+
+        let before = self.reserves_at(asset_id, parent_hash).await?;
+        let after = self.reserves_at(asset_id, block_hash).await?;
+        Some((before, after))
+    }
+}
+
+/// Parse a `0x`-prefixed block hash
+fn parse_block_hash(hash: &str) -> Option<H256> {
+    let bytes = hex::decode(hash.trim_start_matches("0x")).ok()?;
+    (bytes.len() == 32).then(|| H256::from_slice(&bytes))
+}
+
+/// Little-endian u128 at `offset`
+fn decode_u128(bytes: &[u8], offset: usize) -> Option<u128> {
+    let raw: [u8; 16] = bytes.get(offset..offset + 16)?.try_into().ok()?;
+    Some(u128::from_le_bytes(raw))
+}
+
+fn blake2_128_concat(data: &[u8]) -> Vec<u8> {
+    let mut out = blake2_128(data).to_vec();
+    out.extend_from_slice(data);
+    out
+}
+
+fn twox_64_concat(data: &[u8]) -> Vec<u8> {
+    let mut out = twox_64(data).to_vec();
+    out.extend_from_slice(data);
+    out
+}
+
+/// `Omnipool::Assets(asset_id)`
+fn omnipool_asset_key(asset_id: u32) -> Vec<u8> {
+    let mut key = storage_prefix("Omnipool", "Assets").to_vec();
+    key.extend(blake2_128_concat(&asset_id.to_le_bytes()));
+    key
+}
+
+/// `System::Account(account)`
+fn system_account_key(account: &[u8; 32]) -> Vec<u8> {
+    let mut key = storage_prefix("System", "Account").to_vec();
+    key.extend(blake2_128_concat(account));
+    key
+}
+
+/// `Tokens::Accounts(account, asset_id)`
+fn tokens_account_key(account: &[u8; 32], asset_id: u32) -> Vec<u8> {
+    let mut key = storage_prefix("Tokens", "Accounts").to_vec();
+    key.extend(blake2_128_concat(account));
+    key.extend(twox_64_concat(&asset_id.to_le_bytes()));
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_impact_from_reserves() {
+        let before = PoolReserves { asset_reserve: 1_000_000, hub_reserve: 2_000_000 };
+        let after = PoolReserves { asset_reserve: 800_000, hub_reserve: 2_000_000 };
+
+        assert_eq!(before.spot_price(), Some(2.0));
+        let impact = before.price_impact(&after).unwrap();
+        assert!((impact - 0.25).abs() < 1e-9);
+
+        let empty = PoolReserves { asset_reserve: 0, hub_reserve: 1 };
+        assert!(empty.price_impact(&after).is_none());
+    }
+
+    fn snapshot(minutes_ago: i64, price: f64, liquidity: f64) -> HydrationPoolState {
+        HydrationPoolState {
+            timestamp: chrono::Utc::now() - chrono::Duration::minutes(minutes_ago),
+            pool_id: "5".to_string(),
+            pool_type: "omnipool".to_string(),
+            total_liquidity: Some(liquidity),
+            oracle_price: Some(price),
+            oracle_deviation: None,
+            asset_reserves: None,
+        }
+    }
+
+    #[test]
+    fn test_largest_shift_between_snapshots() {
+        let history = [snapshot(30, 2.0, 1_000.0), snapshot(20, 2.1, 1_000.0), snapshot(10, 3.0, 700.0)];
+        let shift = largest_shift(&history).unwrap();
+        assert!((shift - 0.9 / 2.1).abs() < 1e-9);
+
+        assert!(largest_shift(&history[..1]).is_none());
+    }
+
+    #[test]
+    fn test_storage_keys() {
+        // 16-byte pallet prefix + 16-byte entry prefix + blake2_128 + 4-byte key
+        assert_eq!(omnipool_asset_key(5).len(), 32 + 16 + 4);
+        assert_eq!(tokens_account_key(&OMNIPOOL_ACCOUNT, 5).len(), 32 + 16 + 32 + 8 + 4);
+        assert!(OMNIPOOL_ACCOUNT.starts_with(b"modlomnipool"));
+        assert!(parse_block_hash("0x1234").is_none());
+        assert!(parse_block_hash(&format!("0x{}", "ab".repeat(32))).is_some());
+    }
+}
@@ -0,0 +1,381 @@
+//! Proxy and multisig abuse detector
+//!
+//! `Proxy` and `Multisig` calls dispatch an inner call on behalf of another
+//! account, so the extrinsic only shows the wrapper and its signer. This
+//! detector unwraps them (nested wrappers included) and re-runs the privileged
+//! and watchlist checks on the inner call, as if submitted by the real origin:
+//! - `Proxy::proxy` / `proxy_announced`: the proxied `real` account
+//! - `Multisig::as_multi` / `as_multi_threshold_1`: the multisig account
+//!   derived from the signatories and threshold
+//!
+//! Inner calls and origins come from the metadata-decoded arguments
+//! (`ParsedTransaction::decoded_call`), so undecoded wrappers are never
+//! flagged. The inner call is checked through its decoded arguments only;
+//! its raw SCALE arguments are left empty.
+
+use crate::decode::json_account;
+use crate::detectors::{
+    normalize, parse_account, ss58, Detector, DetectorManifest, RuntimeUpgradeDetector, WatchlistDetector,
+    PRIVILEGED_PRIORITY,
+};
+use crate::types::{json_amount, AttackPattern, DecodedCall, DetectionResult, ParsedTransaction, TransactionContext};
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use subxt::ext::codec::Encode;
+use subxt::ext::sp_core::hashing::blake2_256;
+
+/// Maximum wrappers unwrapped around one call
+const MAX_DEPTH: usize = 4;
+
+/// Seed of multisig account derivation in `pallet_multisig`
+const MULTISIG_ENTROPY_PREFIX: &[u8; 16] = b"modlpy/utilisuba";
+
+/// Detector for sensitive calls hidden behind proxy and multisig wrappers
+pub struct ProxyAbuseDetector {
+    enabled: bool,
+    /// Detectors re-run on the unwrapped call
+    inner_detectors: Vec<Box<dyn Detector + Send + Sync>>,
+}
+
+/// The innermost call of a chain of wrappers
+struct WrappedCall {
+    pallet: String,
+    call: String,
+    /// Named arguments, from the decoded wrapper
+    args: JsonValue,
+    /// Account the call is dispatched as, when known
+    origin: Option<[u8; 32]>,
+    /// Wrappers from outermost to innermost, as `Pallet::call`
+    wrappers: Vec<String>,
+}
+
+/// Pallet, call and arguments of a decoded `RuntimeCall` (`{"Pallet": {"call": {..}}}`)
+fn call_parts(call: &JsonValue) -> Option<(String, String, JsonValue)> {
+    let (pallet, inner) = call.as_object()?.iter().next()?;
+    match inner {
+        // Calls without arguments decode to their name
+        JsonValue::String(name) => Some((pallet.clone(), name.clone(), JsonValue::Object(Default::default()))),
+        JsonValue::Object(fields) if fields.len() == 1 => {
+            let (name, args) = fields.iter().next()?;
+            Some((pallet.clone(), name.clone(), args.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Account of a multisig, as derived by `pallet_multisig::multi_account_id`
+pub fn multisig_account(signatories: &[[u8; 32]], threshold: u16) -> [u8; 32] {
+    let mut sorted = signatories.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut entropy = MULTISIG_ENTROPY_PREFIX.to_vec();
+    sorted.encode_to(&mut entropy);
+    threshold.encode_to(&mut entropy);
+    blake2_256(&entropy)
+}
+
+/// Whether `pallet::call` dispatches the call in its `call` argument
+fn is_wrapper(pallet: &str, call: &str) -> bool {
+    matches!(
+        (normalize(pallet).as_str(), normalize(call).as_str()),
+        ("proxy", "proxy" | "proxyannounced") | ("multisig", "asmulti" | "asmultithreshold1")
+    )
+}
+
+/// Account a wrapper dispatches its inner call as, from its decoded arguments
+///
+/// A multisig's account needs the signer, the last of its signatories.
+/// Returns `None` when the arguments don't have the expected layout.
+fn wrapper_origin(pallet: &str, call: &str, args: &JsonValue, signer: Option<[u8; 32]>) -> Option<[u8; 32]> {
+    let signatories = || {
+        let mut signatories = args
+            .get("other_signatories")?
+            .as_array()?
+            .iter()
+            .map(json_account)
+            .collect::<Option<Vec<_>>>()?;
+        signatories.push(signer?);
+        Some(signatories)
+    };
+
+    match (normalize(pallet).as_str(), normalize(call).as_str()) {
+        ("proxy", _) => json_account(args.get("real")?),
+        ("multisig", "asmultithreshold1") => Some(multisig_account(&signatories()?, 1)),
+        ("multisig", _) => {
+            let threshold = u16::try_from(json_amount(args.get("threshold")?)?).ok()?;
+            Some(multisig_account(&signatories()?, threshold))
+        }
+        _ => None,
+    }
+}
+
+impl ProxyAbuseDetector {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            inner_detectors: vec![Box::new(RuntimeUpgradeDetector::new())],
+        }
+    }
+
+    /// Also check wrapped transfers against a watchlist
+    pub fn with_watchlist(mut self, watchlist: WatchlistDetector) -> Self {
+        self.inner_detectors.push(Box::new(watchlist));
+        self
+    }
+
+    /// Unwrap the proxy and multisig calls around the transaction's call
+    ///
+    /// Returns `None` when the call is not a wrapper or wasn't decoded.
+    fn unwrap(tx: &ParsedTransaction) -> Option<WrappedCall> {
+        let decoded = tx.decoded_call.get()?;
+        let mut current = WrappedCall {
+            pallet: decoded.pallet.clone(),
+            call: decoded.call.clone(),
+            args: decoded.args.clone(),
+            origin: parse_account(&tx.caller),
+            wrappers: Vec::new(),
+        };
+
+        for _ in 0..MAX_DEPTH {
+            if !is_wrapper(&current.pallet, &current.call) {
+                break;
+            }
+            let Some((pallet, call, args)) = current.args.get("call").and_then(call_parts) else {
+                break;
+            };
+            let origin = wrapper_origin(&current.pallet, &current.call, &current.args, current.origin);
+
+            current.wrappers.push(format!("{}::{}", current.pallet, current.call));
+            current = WrappedCall {
+                pallet,
+                call,
+                args,
+                origin,
+                wrappers: current.wrappers,
+            };
+        }
+
+        (!current.wrappers.is_empty()).then_some(current)
+    }
+}
+
+impl Default for ProxyAbuseDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Detector for ProxyAbuseDetector {
+    fn name(&self) -> &str {
+        "Proxy Abuse Detector"
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Privileged or watchlisted calls dispatched through proxy and multisig wrappers")
+            .with_patterns(&[AttackPattern::RuntimeUpgrade, AttackPattern::WatchlistHit])
+            .with_pallets(&["Proxy", "Multisig"])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        if !self.enabled {
+            return DetectionResult::no_detection();
+        }
+        let tx = &ctx.transaction;
+        let Some(inner) = Self::unwrap(tx) else {
+            return DetectionResult::no_detection();
+        };
+
+        let origin = inner.origin.map(ss58).unwrap_or_else(|| "unknown".to_string());
+        let inner_ctx = TransactionContext {
+            transaction: ParsedTransaction {
+                caller: origin.clone(),
+                pallet: inner.pallet.clone(),
+                call: inner.call.clone(),
+                args: Vec::new(),
+                decoded_call: std::sync::OnceLock::from(DecodedCall {
+                    pallet: inner.pallet.clone(),
+                    call: inner.call.clone(),
+                    args: inner.args.clone(),
+                }),
+                ..tx.clone()
+            },
+            events: ctx.events.clone(),
+            state_changes: vec![],
+        };
+
+        let mut hits = Vec::new();
+        for detector in &self.inner_detectors {
+            let result = detector.analyze_transaction(&inner_ctx).await;
+            if result.detected {
+                hits.push(result);
+            }
+        }
+        let Some(found) = hits.into_iter().max_by(|a, b| a.confidence.total_cmp(&b.confidence)) else {
+            return DetectionResult::no_detection();
+        };
+
+        let mut evidence = vec![
+            format!(
+                "{}::{} wrapped in {} by {}",
+                inner.pallet,
+                inner.call,
+                inner.wrappers.join(" > "),
+                tx.caller
+            ),
+            format!("Real origin: {}", origin),
+        ];
+        evidence.extend(found.evidence);
+
+        DetectionResult::detected(
+            found.pattern,
+            found.confidence,
+            format!("{} (hidden behind {})", found.description, inner.wrappers.join(" > ")),
+            evidence,
+        )
+        .with_triggers(found.triggering_events, found.triggering_state_changes)
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn priority(&self) -> i32 {
+        PRIVILEGED_PRIORITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNER: [u8; 32] = [1; 32];
+    const REAL: [u8; 32] = [2; 32];
+    const COSIGNER: [u8; 32] = [3; 32];
+
+    fn wrapper_context(pallet: &str, call: &str, decoded: JsonValue) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: "0xwrapped".to_string(),
+                block_number: 42,
+                block_hash: "0xblock42".to_string(),
+                index: 1,
+                caller: ss58(SIGNER),
+                pallet: pallet.to_string(),
+                call: call.to_string(),
+                args: vec![],
+                signature: Some(vec![1]),
+                nonce: Some(7),
+                timestamp: 1234567890,
+                success: true,
+                decoded_call: std::sync::OnceLock::from(DecodedCall {
+                    pallet: pallet.to_string(),
+                    call: call.to_string(),
+                    args: decoded,
+                }),
+            },
+            events: vec![],
+            state_changes: vec![],
+        }
+    }
+
+    /// `Proxy::proxy(real, None, System::set_code(code))`
+    fn proxied_set_code(code: &[u8]) -> TransactionContext {
+        wrapper_context(
+            "Proxy",
+            "proxy",
+            serde_json::json!({
+                "real": { "Id": format!("0x{}", hex::encode(REAL)) },
+                "force_proxy_type": "None",
+                "call": { "System": { "set_code": { "code": format!("0x{}", hex::encode(code)) } } },
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_proxied_runtime_upgrade_is_flagged() {
+        let code = b"new runtime blob";
+        let result = ProxyAbuseDetector::new().analyze_transaction(&proxied_set_code(code)).await;
+
+        assert!(result.detected);
+        assert_eq!(result.pattern, AttackPattern::RuntimeUpgrade);
+        assert_eq!(result.confidence, 1.0);
+        assert!(result.evidence[0].contains("set_code wrapped in Proxy::proxy"));
+        assert_eq!(result.evidence[1], format!("Real origin: {}", ss58(REAL)));
+        let code_hash = format!("New code hash: 0x{}", hex::encode(blake2_256(code)));
+        assert!(result.evidence.contains(&code_hash));
+    }
+
+    #[tokio::test]
+    async fn test_multisig_transfer_to_watchlisted_address() {
+        let blocked = [9u8; 32];
+        let detector = ProxyAbuseDetector::new()
+            .with_watchlist(WatchlistDetector::with_addresses([format!("0x{}", hex::encode(blocked))]).unwrap());
+
+        // Multisig::as_multi_threshold_1([cosigner], Balances::transfer_keep_alive(blocked, 100))
+        let ctx = wrapper_context(
+            "Multisig",
+            "as_multi_threshold_1",
+            serde_json::json!({
+                "other_signatories": [format!("0x{}", hex::encode(COSIGNER))],
+                "call": { "Balances": { "transfer_keep_alive": { "dest": { "Id": format!("0x{}", hex::encode(blocked)) }, "value": 100 } } },
+            }),
+        );
+
+        let result = detector.analyze_transaction(&ctx).await;
+        assert!(result.detected);
+        assert_eq!(result.pattern, AttackPattern::WatchlistHit);
+        let multisig = ss58(multisig_account(&[SIGNER, COSIGNER], 1));
+        assert_eq!(result.evidence[1], format!("Real origin: {}", multisig));
+    }
+
+    #[tokio::test]
+    async fn test_multisig_origin_uses_threshold() {
+        let ctx = wrapper_context(
+            "Multisig",
+            "as_multi",
+            serde_json::json!({
+                "threshold": 2,
+                "other_signatories": [format!("0x{}", hex::encode(COSIGNER))],
+                "maybe_timepoint": "None",
+                "call": { "System": { "set_code": { "code": "0x00" } } },
+                "max_weight": { "ref_time": 0, "proof_size": 0 },
+            }),
+        );
+
+        let result = ProxyAbuseDetector::new().analyze_transaction(&ctx).await;
+        assert!(result.detected);
+        let multisig = ss58(multisig_account(&[SIGNER, COSIGNER], 2));
+        assert_eq!(result.evidence[1], format!("Real origin: {}", multisig));
+    }
+
+    #[tokio::test]
+    async fn test_harmless_or_undecoded_wrappers_are_ignored() {
+        let remark = wrapper_context(
+            "Proxy",
+            "proxy",
+            serde_json::json!({
+                "real": { "Id": format!("0x{}", hex::encode(REAL)) },
+                "call": { "System": { "remark": { "remark": "0x" } } },
+            }),
+        );
+        assert!(!ProxyAbuseDetector::new().analyze_transaction(&remark).await.detected);
+
+        let mut undecoded = proxied_set_code(b"code");
+        undecoded.transaction.decoded_call = Default::default();
+        assert!(!ProxyAbuseDetector::new().analyze_transaction(&undecoded).await.detected);
+    }
+
+    #[test]
+    fn test_multisig_account_ignores_signatory_order() {
+        assert_eq!(
+            multisig_account(&[SIGNER, COSIGNER], 2),
+            multisig_account(&[COSIGNER, SIGNER], 2)
+        );
+        assert_ne!(
+            multisig_account(&[SIGNER, COSIGNER], 2),
+            multisig_account(&[SIGNER, COSIGNER], 1)
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Runtime upgrade detector
+//!
+//! A runtime upgrade replaces the chain's logic wholesale and must always be
+//! surfaced. Flags:
+//! 1. `System::set_code`, `set_code_without_checks` and `apply_authorized_upgrade` calls
+//! 2. `System::CodeUpdated` events
+//!
+//! Detections carry full confidence and bypass alert thresholds, so every
+//! upgrade produces a Critical alert. The new code hash (blake2-256 of the
+//! runtime blob) is included in the evidence when the call was decoded.
+
+use crate::decode::json_bytes;
+use crate::detectors::{normalize, Detector, DetectorManifest, PRIVILEGED_PRIORITY};
+use crate::types::{AttackPattern, DetectionResult, ParsedTransaction, TransactionContext};
+use async_trait::async_trait;
+use subxt::ext::sp_core::hashing::blake2_256;
+
+/// `System` calls that replace the runtime code
+const SET_CODE_CALLS: &[&str] = &["setcode", "setcodewithoutchecks", "applyauthorizedupgrade"];
+
+/// Detector for runtime code upgrades
+pub struct RuntimeUpgradeDetector {
+    enabled: bool,
+}
+
+impl RuntimeUpgradeDetector {
+    pub fn new() -> Self {
+        Self { enabled: true }
+    }
+
+    /// Hash of the runtime blob in the decoded `code` argument
+    fn code_hash(tx: &ParsedTransaction) -> Option<String> {
+        let code = json_bytes(tx.decoded_call.get()?.args.get("code")?)?;
+        Some(format!("0x{}", hex::encode(blake2_256(&code))))
+    }
+}
+
+impl Default for RuntimeUpgradeDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Detector for RuntimeUpgradeDetector {
+    fn name(&self) -> &str {
+        "Runtime Upgrade Detector"
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Runtime code upgrades, always alerted")
+            .with_patterns(&[AttackPattern::RuntimeUpgrade])
+            .with_pallets(&["System"])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        let tx = &ctx.transaction;
+        let mut evidence = Vec::new();
+
+        let is_set_code = tx.pallet.eq_ignore_ascii_case("system")
+            && SET_CODE_CALLS.contains(&normalize(&tx.call).as_str());
+        if is_set_code {
+            evidence.push(format!("Runtime code replaced via {}::{}", tx.pallet, tx.call));
+            if let Some(hash) = Self::code_hash(tx) {
+                evidence.push(format!("New code hash: {}", hash));
+            }
+        }
+
+        for event in &ctx.events {
+            if event.pallet.eq_ignore_ascii_case("system")
+                && normalize(&event.event_name) == "codeupdated"
+            {
+                evidence.push("System::CodeUpdated event emitted".to_string());
+                if let Some(hash) = event
+                    .event_data
+                    .as_ref()
+                    .and_then(|data| data.get("code_hash"))
+                    .and_then(|v| v.as_str())
+                {
+                    evidence.push(format!("New code hash: {}", hash));
+                }
+            }
+        }
+
+        if evidence.is_empty() {
+            return DetectionResult::no_detection();
+        }
+
+        DetectionResult::detected(
+            AttackPattern::RuntimeUpgrade,
+            1.0,
+            format!(
+                "Runtime upgrade in block #{} - verify it was authorized",
+                tx.block_number
+            ),
+            evidence,
+        )
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn always_alert(&self) -> bool {
+        true
+    }
+
+    fn priority(&self) -> i32 {
+        PRIVILEGED_PRIORITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainEvent, DecodedCall};
+
+    fn create_context(pallet: &str, call: &str, args: Vec<u8>) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: "0xupgrade".to_string(),
+                block_number: 42,
+                block_hash: "0xblock42".to_string(),
+                index: 1,
+                caller: "root".to_string(),
+                pallet: pallet.to_string(),
+                call: call.to_string(),
+                args,
+                signature: None,
+                nonce: None,
+                timestamp: 1234567890,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events: vec![],
+            state_changes: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_code_detected_with_hash() {
+        let code = b"\0asm runtime blob".to_vec();
+        let mut args = vec![(code.len() as u8) << 2];
+        args.extend_from_slice(&code);
+        let ctx = create_context("System", "set_code", args);
+        ctx.transaction.decoded_call.get_or_init(|| DecodedCall {
+            pallet: "System".to_string(),
+            call: "set_code".to_string(),
+            args: serde_json::json!({ "code": format!("0x{}", hex::encode(&code)) }),
+        });
+
+        let detector = RuntimeUpgradeDetector::new();
+        let result = detector.analyze_transaction(&ctx).await;
+
+        assert!(result.detected);
+        assert_eq!(result.pattern, AttackPattern::RuntimeUpgrade);
+        assert_eq!(result.confidence, 1.0);
+        let expected = format!("New code hash: 0x{}", hex::encode(blake2_256(&code)));
+        assert!(result.evidence.contains(&expected));
+        assert!(detector.always_alert());
+    }
+
+    #[tokio::test]
+    async fn test_code_updated_event_detected() {
+        let mut ctx = create_context("ParachainSystem", "enact_authorized_upgrade", vec![]);
+        ctx.events.push(ChainEvent::new("System", "CodeUpdated"));
+
+        let result = RuntimeUpgradeDetector::new().analyze_transaction(&ctx).await;
+        assert!(result.detected);
+    }
+
+    #[tokio::test]
+    async fn test_regular_call_not_detected() {
+        let result = RuntimeUpgradeDetector::new()
+            .analyze_transaction(&create_context("System", "remark", vec![0]))
+            .await;
+        assert!(!result.detected);
+    }
+}
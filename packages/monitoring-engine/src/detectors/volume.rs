@@ -1,17 +1,84 @@
 //! Volume anomaly detector
+//!
+//! Detects unusual volume activity by analyzing:
+//! 1. High-volume call types (transfers, asset ops, batches, XCM)
+//! 2. Complex calls with many arguments
+//! 3. Per-block transaction counts far above the learned baseline
 
-use crate::detectors::Detector;
-use crate::types::{AttackPattern, DetectionResult, TransactionContext};
+use crate::detectors::{Detector, DetectorManifest};
+use crate::types::{AttackPattern, DetectionResult, TransactionContext, SCHEMA_VERSION};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Blocks observed before the per-block baseline is trusted
+const MIN_BASELINE_BLOCKS: u64 = 10;
+
+/// A block must exceed the baseline by this factor to count as a spike
+const SPIKE_MULTIPLIER: f64 = 3.0;
+
+/// Absolute minimum transactions in a block considered a spike
+const MIN_SPIKE_TXS: u64 = 10;
 
 /// Detector for unusual volume spikes
 pub struct VolumeAnomalyDetector {
     enabled: bool,
+    /// Per-block transaction count baseline
+    baseline: Arc<RwLock<VolumeBaseline>>,
+}
+
+/// Transactions-per-block baseline learned across blocks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct VolumeBaseline {
+    current_block: u64,
+    current_count: u64,
+    avg_per_block: f64,
+    blocks_observed: u64,
+    spike_reported: bool,
 }
 
 impl VolumeAnomalyDetector {
     pub fn new() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            baseline: Arc::new(RwLock::new(VolumeBaseline::default())),
+        }
+    }
+
+    /// Count the transaction towards its block, returning the block's count
+    /// and baseline if it is a newly observed spike
+    async fn observe_block_volume(&self, block: u64) -> Option<(u64, f64)> {
+        let mut baseline = self.baseline.write().await;
+
+        if block != baseline.current_block {
+            if baseline.current_count > 0 {
+                let count = baseline.current_count as f64;
+                baseline.avg_per_block = if baseline.blocks_observed == 0 {
+                    count
+                } else {
+                    (baseline.avg_per_block * 0.9) + (count * 0.1)
+                };
+                baseline.blocks_observed += 1;
+            }
+            baseline.current_block = block;
+            baseline.current_count = 0;
+            baseline.spike_reported = false;
+        }
+
+        baseline.current_count += 1;
+
+        let is_spike = baseline.blocks_observed >= MIN_BASELINE_BLOCKS
+            && baseline.current_count >= MIN_SPIKE_TXS
+            && baseline.current_count as f64 > baseline.avg_per_block.max(1.0) * SPIKE_MULTIPLIER;
+
+        // Report each spike once rather than on every following transaction
+        if is_spike && !baseline.spike_reported {
+            baseline.spike_reported = true;
+            Some((baseline.current_count, baseline.avg_per_block))
+        } else {
+            None
+        }
     }
 }
 
@@ -27,6 +94,20 @@ impl Detector for VolumeAnomalyDetector {
         "Volume Anomaly Detector"
     }
 
+    fn is_stateful(&self) -> bool {
+        true
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "High-volume call types and per-block transaction counts far above the learned baseline")
+            .with_patterns(&[AttackPattern::VolumeAnomaly])
+            .with_pallets(&["Balances", "Assets", "Staking", "Utility", "XTokens", "PolkadotXcm"])
+    }
+
+    fn version(&self) -> &str {
+        "2"
+    }
+
     async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
         let tx = &ctx.transaction;
         let mut suspicion_score: f64 = 0.0;
@@ -74,9 +155,22 @@ impl Detector for VolumeAnomalyDetector {
             evidence.push(format!("Complex transaction with {} arguments", tx.args.len()));
         }
 
+        // Compare the block's transaction count against the learned baseline
+        if let Some((count, avg)) = self.observe_block_volume(tx.block_number).await {
+            suspicion_score += 0.6;
+            evidence.push(format!(
+                "Block {} has {} transactions ({:.1}x the baseline of {:.1})",
+                tx.block_number,
+                count,
+                count as f64 / avg.max(1.0),
+                avg
+            ));
+        }
+
         // If we have any suspicion, report it
         if suspicion_score > 0.5 {
             DetectionResult {
+                schema_version: SCHEMA_VERSION,
                 detected: true,
                 confidence: suspicion_score.min(0.95), // Cap at 0.95
                 pattern: AttackPattern::VolumeAnomaly,
@@ -86,6 +180,8 @@ impl Detector for VolumeAnomalyDetector {
                 ),
                 evidence,
                 metadata: std::collections::HashMap::new(),
+                triggering_events: Vec::new(),
+                triggering_state_changes: Vec::new(),
             }
         } else {
             DetectionResult::no_detection()
@@ -95,11 +191,57 @@ impl Detector for VolumeAnomalyDetector {
     fn is_enabled(&self) -> bool {
         self.enabled
     }
+
+    async fn snapshot(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(&*self.baseline.read().await).ok()
+    }
+
+    async fn restore(&self, state: serde_json::Value) -> crate::Result<()> {
+        let baseline: VolumeBaseline = serde_json::from_value(state)
+            .map_err(|e| crate::Error::ParseError(format!("Invalid volume detector state: {}", e)))?;
+        *self.baseline.write().await = baseline;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::ParsedTransaction;
+
+    fn create_context(block: u64, index: u32) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: format!("0x{}{}", block, index),
+                block_number: block,
+                block_hash: format!("0xblock{}", block),
+                index,
+                caller: "alice".to_string(),
+                pallet: "System".to_string(),
+                call: "remark".to_string(),
+                args: vec![],
+                signature: None,
+                nonce: None,
+                timestamp: 1234567890,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events: vec![],
+            state_changes: vec![],
+        }
+    }
+
+    /// Feed a block of `count` transactions, returning whether any was flagged
+    async fn feed_block(detector: &VolumeAnomalyDetector, block: u64, count: u32) -> bool {
+        let mut flagged = false;
+        for index in 0..count {
+            flagged |= detector
+                .analyze_transaction(&create_context(block, index))
+                .await
+                .detected;
+        }
+        flagged
+    }
 
     #[tokio::test]
     async fn test_volume_detector() {
@@ -107,4 +249,23 @@ mod tests {
         assert_eq!(detector.name(), "Volume Anomaly Detector");
         assert!(detector.is_enabled());
     }
+
+    #[tokio::test]
+    async fn test_restored_baseline_flags_spike() {
+        let detector = VolumeAnomalyDetector::new();
+        for block in 1..=12 {
+            assert!(!feed_block(&detector, block, 2).await);
+        }
+
+        let state = detector.snapshot().await.expect("volume detector keeps state");
+        let restored = VolumeAnomalyDetector::new();
+        restored.restore(state).await.unwrap();
+
+        // The spike is flagged without re-warming the baseline
+        assert!(feed_block(&restored, 13, 12).await);
+
+        // A cold detector has no baseline to compare against
+        let cold = VolumeAnomalyDetector::new();
+        assert!(!feed_block(&cold, 13, 12).await);
+    }
 }
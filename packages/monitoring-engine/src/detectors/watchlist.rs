@@ -0,0 +1,321 @@
+//! Watchlist detector
+//!
+//! Flags transfers whose decoded destination is on a watchlist of
+//! sanctioned or known scam addresses.
+//!
+//! The watchlist file holds one address per line (SS58 or 0x-prefixed hex
+//! account ID); blank lines and `#` comments are ignored. The file is
+//! re-read when its modification time changes, checked at most every
+//! `RELOAD_CHECK_INTERVAL`.
+//!
+//! Destinations come from `ParsedTransaction::transfer_destination`: the
+//! decoded `dest` argument when the call was decoded, otherwise the raw
+//! SCALE arguments of a `Balances` transfer. Other calls are never flagged.
+
+use crate::detectors::{Detector, DetectorManifest};
+use crate::types::{AttackPattern, DetectionResult, TransactionContext};
+use crate::{Error, Result};
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use subxt::ext::sp_core::crypto::{AccountId32, Ss58Codec};
+use tokio::sync::RwLock;
+
+/// Minimum time between watchlist file modification checks
+const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Confidence for a watchlist hit (maps to High severity)
+const WATCHLIST_HIT_CONFIDENCE: f64 = 0.85;
+
+/// Detector for transfers to watchlisted addresses
+///
+/// Clones share the address set and reload state, so a file loaded once
+/// can back several detectors and is re-read once for all of them.
+#[derive(Clone)]
+pub struct WatchlistDetector {
+    enabled: bool,
+    /// Flagged account IDs
+    addresses: Arc<RwLock<HashSet<[u8; 32]>>>,
+    /// File the watchlist was loaded from, if any
+    source: Option<PathBuf>,
+    reload_state: Arc<RwLock<ReloadState>>,
+}
+
+/// Tracks the watchlist file for hot reloading
+#[derive(Debug, Default)]
+struct ReloadState {
+    modified: Option<SystemTime>,
+    last_check: Option<Instant>,
+}
+
+impl WatchlistDetector {
+    /// Create a detector with an empty watchlist
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            addresses: Arc::new(RwLock::new(HashSet::new())),
+            source: None,
+            reload_state: Arc::new(RwLock::new(ReloadState::default())),
+        }
+    }
+
+    /// Create a detector from a fixed list of addresses
+    pub fn with_addresses<I, S>(addresses: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let parsed = addresses
+            .into_iter()
+            .map(|a| parse_address(a.as_ref()))
+            .collect::<Result<HashSet<_>>>()?;
+
+        Ok(Self {
+            addresses: Arc::new(RwLock::new(parsed)),
+            ..Self::new()
+        })
+    }
+
+    /// Load the watchlist from a file, reloading it when the file changes
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let addresses = read_watchlist(&path)?;
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+
+        Ok(Self {
+            enabled: true,
+            addresses: Arc::new(RwLock::new(addresses)),
+            source: Some(path),
+            reload_state: Arc::new(RwLock::new(ReloadState {
+                modified,
+                last_check: Some(Instant::now()),
+            })),
+        })
+    }
+
+    /// Number of addresses currently on the watchlist
+    pub async fn len(&self) -> usize {
+        self.addresses.read().await.len()
+    }
+
+    /// Whether the watchlist is empty
+    pub async fn is_empty(&self) -> bool {
+        self.addresses.read().await.is_empty()
+    }
+
+    /// Re-read the watchlist file, returning the new number of addresses
+    ///
+    /// On a read or parse error the current list is kept.
+    pub async fn reload(&self) -> Result<usize> {
+        let Some(path) = &self.source else {
+            return Ok(self.len().await);
+        };
+
+        let addresses = read_watchlist(path)?;
+        let count = addresses.len();
+        *self.addresses.write().await = addresses;
+
+        let mut state = self.reload_state.write().await;
+        state.modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        state.last_check = Some(Instant::now());
+
+        tracing::info!("Reloaded watchlist from {} ({} addresses)", path.display(), count);
+        Ok(count)
+    }
+
+    /// Reload the watchlist if the file changed since it was last read
+    async fn reload_if_changed(&self) {
+        let Some(path) = &self.source else {
+            return;
+        };
+
+        let mut state = self.reload_state.write().await;
+        if state
+            .last_check
+            .is_some_and(|checked| checked.elapsed() < RELOAD_CHECK_INTERVAL)
+        {
+            return;
+        }
+        state.last_check = Some(Instant::now());
+
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        if modified == state.modified {
+            return;
+        }
+        drop(state);
+
+        if let Err(e) = self.reload().await {
+            tracing::warn!("Keeping previous watchlist, reload failed: {}", e);
+        }
+    }
+}
+
+impl Default for WatchlistDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read and parse a watchlist file
+fn read_watchlist(path: &Path) -> Result<HashSet<[u8; 32]>> {
+    let content = std::fs::read_to_string(path)?;
+
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(parse_address)
+        .collect()
+}
+
+/// Parse an SS58 or 0x-prefixed hex account ID
+fn parse_address(address: &str) -> Result<[u8; 32]> {
+    let address = address.trim();
+
+    if let Some(hex_id) = address.strip_prefix("0x") {
+        return hex::decode(hex_id)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| Error::ConfigError(format!("Invalid watchlist account ID: {}", address)));
+    }
+
+    AccountId32::from_ss58check(address)
+        .map(Into::into)
+        .map_err(|e| Error::ConfigError(format!("Invalid watchlist address {}: {:?}", address, e)))
+}
+
+#[async_trait]
+impl Detector for WatchlistDetector {
+    fn name(&self) -> &str {
+        "Watchlist Detector"
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Transfers to sanctioned or known scam addresses on the watchlist")
+            .with_patterns(&[AttackPattern::WatchlistHit])
+            .with_pallets(&["Balances"])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        self.reload_if_changed().await;
+
+        let tx = &ctx.transaction;
+        let Some(dest) = tx.transfer_destination() else {
+            return DetectionResult::no_detection();
+        };
+
+        if !self.addresses.read().await.contains(&dest) {
+            return DetectionResult::no_detection();
+        }
+
+        let ss58 = AccountId32::from(dest).to_ss58check();
+        DetectionResult::detected(
+            AttackPattern::WatchlistHit,
+            WATCHLIST_HIT_CONFIDENCE,
+            format!("{} sent funds to watchlisted address {}", tx.caller, ss58),
+            vec![
+                format!("{}::{} destination is on the watchlist", tx.pallet, tx.call),
+                format!("Destination account: {} (0x{})", ss58, hex::encode(dest)),
+            ],
+        )
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// A listed destination is a fact; false positives are fixed in the list
+    fn feedback_step(&self) -> f64 {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ParsedTransaction;
+
+    fn transfer_to(dest: [u8; 32]) -> TransactionContext {
+        let mut args = vec![0u8];
+        args.extend_from_slice(&dest);
+        args.extend_from_slice(&[0x13, 0x00, 0x00, 0xe8, 0x89, 0x04, 0x23, 0xc7, 0x8a]); // compact-encoded amount
+
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: "0x01".to_string(),
+                block_number: 1,
+                block_hash: "0xblock1".to_string(),
+                index: 0,
+                caller: "alice".to_string(),
+                pallet: "Balances".to_string(),
+                call: "transfer_keep_alive".to_string(),
+                args,
+                signature: None,
+                nonce: Some(1),
+                timestamp: 1234567890,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events: vec![],
+            state_changes: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_to_watchlisted_address() {
+        let flagged = [0xabu8; 32];
+        let detector = WatchlistDetector::with_addresses([format!("0x{}", hex::encode(flagged))]).unwrap();
+
+        let result = detector.analyze_transaction(&transfer_to(flagged)).await;
+        assert!(result.detected);
+        assert_eq!(result.pattern, AttackPattern::WatchlistHit);
+        assert!(result.confidence >= 0.75 && result.confidence < 0.9);
+
+        let result = detector.analyze_transaction(&transfer_to([0x01u8; 32])).await;
+        assert!(!result.detected);
+    }
+
+    #[tokio::test]
+    async fn test_ss58_and_hex_entries_match() {
+        let account = [0x42u8; 32];
+        let ss58 = AccountId32::from(account).to_ss58check();
+        let detector = WatchlistDetector::with_addresses([ss58]).unwrap();
+
+        assert!(detector.analyze_transaction(&transfer_to(account)).await.detected);
+        assert!(WatchlistDetector::with_addresses(["not-an-address"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reload_picks_up_new_entries() {
+        let path = std::env::temp_dir().join(format!("watchlist-{}.txt", std::process::id()));
+        std::fs::write(&path, "# scam list\n\n").unwrap();
+
+        let detector = WatchlistDetector::from_file(&path).unwrap();
+        let target = [0x77u8; 32];
+        assert!(!detector.analyze_transaction(&transfer_to(target)).await.detected);
+
+        std::fs::write(&path, format!("0x{}  # drainer\n", hex::encode(target))).unwrap();
+        assert_eq!(detector.reload().await.unwrap(), 1);
+        std::fs::remove_file(&path).ok();
+
+        assert!(detector.analyze_transaction(&transfer_to(target)).await.detected);
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_reloads() {
+        let path = std::env::temp_dir().join(format!("watchlist-shared-{}.txt", std::process::id()));
+        std::fs::write(&path, "").unwrap();
+
+        let detector = WatchlistDetector::from_file(&path).unwrap();
+        let shared = detector.clone();
+        let target = [0x78u8; 32];
+
+        std::fs::write(&path, format!("0x{}\n", hex::encode(target))).unwrap();
+        detector.reload().await.unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert!(shared.analyze_transaction(&transfer_to(target)).await.detected);
+    }
+}
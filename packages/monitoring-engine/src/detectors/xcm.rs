@@ -0,0 +1,248 @@
+//! XCM fan-out detector
+//!
+//! A single transaction dispatching many outbound XCM messages, or messages
+//! to many different chains, floods the destinations' inbound queues and can
+//! be used as a cross-chain spam or DoS vector. Outbound messages are counted
+//! from the Sent events of:
+//! 1. `XcmpQueue` (`XcmpMessageSent`, one per message queued to a parachain)
+//! 2. `PolkadotXcm` / `XcmPallet` (`Sent`, carrying the destination)
+//!
+//! A message sent through `pallet-xcm` to a sibling parachain is reported by
+//! both pallets, so the larger of the two counts is used instead of the sum.
+
+use crate::detectors::{normalize, Detector, DetectorManifest};
+use crate::types::{AttackPattern, DetectionResult, TransactionContext};
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+/// Default number of outbound XCM messages a single transaction may send
+pub const DEFAULT_MAX_MESSAGES: usize = 5;
+
+/// Default number of distinct destinations a single transaction may reach
+pub const DEFAULT_MAX_DESTINATIONS: usize = 3;
+
+/// Normalized pallet names whose Sent events carry a destination
+const XCM_PALLETS: &[&str] = &["polkadotxcm", "xcmpallet"];
+
+/// Normalized name of the XCMP queue pallet
+const XCMP_QUEUE_PALLET: &str = "xcmpqueue";
+
+/// Detector for transactions sending excessive outbound XCM messages
+pub struct XcmFanoutDetector {
+    enabled: bool,
+    max_messages: usize,
+    max_destinations: usize,
+}
+
+/// Outbound XCM activity of one transaction
+#[derive(Debug, Default)]
+struct XcmFanout {
+    /// `PolkadotXcm::Sent` / `XcmPallet::Sent` events
+    pallet_messages: usize,
+    /// `XcmpQueue::XcmpMessageSent` events
+    queued_messages: usize,
+    /// Destinations of the pallet Sent events, as JSON
+    destinations: HashSet<String>,
+}
+
+impl XcmFanout {
+    fn messages(&self) -> usize {
+        self.pallet_messages.max(self.queued_messages)
+    }
+}
+
+impl XcmFanoutDetector {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            max_messages: DEFAULT_MAX_MESSAGES,
+            max_destinations: DEFAULT_MAX_DESTINATIONS,
+        }
+    }
+
+    /// Set the number of outbound messages per transaction above which it is flagged
+    pub fn with_max_messages(mut self, max_messages: usize) -> Self {
+        self.max_messages = max_messages;
+        self
+    }
+
+    /// Set the number of distinct destinations per transaction above which it is flagged
+    pub fn with_max_destinations(mut self, max_destinations: usize) -> Self {
+        self.max_destinations = max_destinations;
+        self
+    }
+
+    fn fanout(ctx: &TransactionContext) -> XcmFanout {
+        let mut fanout = XcmFanout::default();
+
+        for event in &ctx.events {
+            let pallet = normalize(&event.pallet);
+            let name = normalize(&event.event_name);
+
+            if pallet == XCMP_QUEUE_PALLET && name == "xcmpmessagesent" {
+                fanout.queued_messages += 1;
+            } else if XCM_PALLETS.contains(&pallet.as_str()) && name == "sent" {
+                fanout.pallet_messages += 1;
+                if let Some(destination) = event
+                    .event_data
+                    .as_ref()
+                    .and_then(|data| data.get("destination").or_else(|| data.get("dest")))
+                {
+                    fanout.destinations.insert(destination.to_string());
+                }
+            }
+        }
+
+        fanout
+    }
+
+    /// Confidence grows with how far past the limits the transaction goes
+    fn confidence(&self, fanout: &XcmFanout) -> f64 {
+        let excess = |count: usize, limit: usize| count as f64 / limit.max(1) as f64;
+        let ratio = excess(fanout.messages(), self.max_messages)
+            .max(excess(fanout.destinations.len(), self.max_destinations));
+
+        (0.6 + 0.35 * (1.0 - 1.0 / ratio)).clamp(0.6, 0.95)
+    }
+}
+
+impl Default for XcmFanoutDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Detector for XcmFanoutDetector {
+    fn name(&self) -> &str {
+        "XCM Fan-out Detector"
+    }
+
+    fn manifest(&self) -> DetectorManifest {
+        DetectorManifest::new(self, "Transactions sending many outbound XCM messages or reaching many destinations")
+            .with_patterns(&[AttackPattern::XcmSpam])
+            .with_pallets(&["XcmpQueue", "PolkadotXcm", "XcmPallet"])
+    }
+
+    async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+        if !self.enabled {
+            return DetectionResult::no_detection();
+        }
+
+        let fanout = Self::fanout(ctx);
+        let messages = fanout.messages();
+        let destinations = fanout.destinations.len();
+
+        let mut evidence = Vec::new();
+        if messages > self.max_messages {
+            evidence.push(format!(
+                "{} outbound XCM messages in one transaction (limit {})",
+                messages, self.max_messages
+            ));
+        }
+        if destinations > self.max_destinations {
+            evidence.push(format!(
+                "Messages sent to {} distinct destinations (limit {})",
+                destinations, self.max_destinations
+            ));
+        }
+
+        if evidence.is_empty() {
+            return DetectionResult::no_detection();
+        }
+
+        DetectionResult::detected(
+            AttackPattern::XcmSpam,
+            self.confidence(&fanout),
+            format!(
+                "Transaction {}.{} from {} fans out {} XCM messages to {} destinations",
+                ctx.transaction.pallet, ctx.transaction.call, ctx.transaction.caller, messages, destinations
+            ),
+            evidence,
+        )
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainEvent, ParsedTransaction};
+
+    fn create_context(events: Vec<ChainEvent>) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: "0xfanout".to_string(),
+                block_number: 7,
+                block_hash: "0xblock7".to_string(),
+                index: 2,
+                caller: "spammer".to_string(),
+                pallet: "Utility".to_string(),
+                call: "batch".to_string(),
+                args: vec![],
+                signature: None,
+                nonce: None,
+                timestamp: 1234567890,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events,
+            state_changes: vec![],
+        }
+    }
+
+    /// `PolkadotXcm::Sent` and the matching `XcmpQueue::XcmpMessageSent` to a sibling
+    fn sent_to(para_id: u32) -> Vec<ChainEvent> {
+        vec![
+            ChainEvent::new("XcmpQueue", "XcmpMessageSent"),
+            ChainEvent::new("PolkadotXcm", "Sent").with_event_data(serde_json::json!({
+                "destination": { "parents": 1, "interior": { "X1": { "Parachain": para_id } } }
+            })),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_many_messages_flagged() {
+        let events = (0..12).flat_map(|i| sent_to(2000 + i)).collect();
+
+        let result = XcmFanoutDetector::new()
+            .analyze_transaction(&create_context(events))
+            .await;
+
+        assert!(result.detected);
+        assert_eq!(result.pattern, AttackPattern::XcmSpam);
+        assert!(result.confidence >= 0.6);
+        // Each message is reported by both pallets but counted once
+        assert!(result.evidence[0].starts_with("12 outbound XCM messages"));
+        assert!(result.evidence[1].starts_with("Messages sent to 12 distinct destinations"));
+    }
+
+    #[tokio::test]
+    async fn test_single_message_not_flagged() {
+        let result = XcmFanoutDetector::new()
+            .analyze_transaction(&create_context(sent_to(2000)))
+            .await;
+
+        assert!(!result.detected);
+    }
+
+    #[tokio::test]
+    async fn test_destination_limit_is_configurable() {
+        let events: Vec<_> = (0..3).flat_map(|i| sent_to(2000 + i)).collect();
+
+        let default = XcmFanoutDetector::new()
+            .analyze_transaction(&create_context(events.clone()))
+            .await;
+        assert!(!default.detected);
+
+        let strict = XcmFanoutDetector::new()
+            .with_max_destinations(2)
+            .analyze_transaction(&create_context(events))
+            .await;
+        assert!(strict.detected);
+        assert_eq!(strict.evidence.len(), 1);
+    }
+}
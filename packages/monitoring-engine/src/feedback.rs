@@ -0,0 +1,148 @@
+//! Analyst feedback on detections
+//!
+//! Analysts label alerts as true or false positives. Each label is kept per
+//! detector and shifts that detector's effective alert threshold: every false
+//! positive beyond the confirmed true positives raises it by the detector's
+//! [`feedback_step`](crate::detectors::Detector::feedback_step).
+//!
+//! The shift is never negative, so feedback can quiet a noisy detector but
+//! never make one more eager than configured. It is also capped at
+//! [`MAX_THRESHOLD_SHIFT`], so a burst of labels cannot silence a detector.
+//! Relabeling a detection replaces its previous label.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Threshold shift per net false positive, unless the detector sets its own
+pub const DEFAULT_FEEDBACK_STEP: f64 = 0.02;
+
+/// Largest threshold increase feedback can cause
+pub const MAX_THRESHOLD_SHIFT: f64 = 0.2;
+
+/// Labels recorded for one detector
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedbackCounts {
+    pub true_positives: u64,
+    pub false_positives: u64,
+}
+
+#[derive(Debug, Default)]
+struct Labels {
+    counts: HashMap<String, FeedbackCounts>,
+    steps: HashMap<String, f64>,
+    /// Detector and label of each labeled detection, for relabeling
+    detections: HashMap<String, (String, bool)>,
+}
+
+/// Per-detector feedback, shared with the alert thresholds
+#[derive(Debug, Default)]
+pub struct FeedbackTracker {
+    labels: RwLock<Labels>,
+}
+
+impl FeedbackTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how far each label moves a detector's threshold
+    pub fn set_step(&self, detector_name: &str, step: f64) {
+        let mut labels = self.labels.write().expect("feedback lock poisoned");
+        labels.steps.insert(detector_name.to_string(), step.max(0.0));
+    }
+
+    /// Record a label, replacing any previous label of the same detection
+    pub fn record(&self, detection_id: &str, detector_name: &str, true_positive: bool) {
+        let mut labels = self.labels.write().expect("feedback lock poisoned");
+
+        let previous = labels
+            .detections
+            .insert(detection_id.to_string(), (detector_name.to_string(), true_positive));
+        if let Some((detector, was_true_positive)) = previous {
+            let counts = labels.counts.entry(detector).or_default();
+            if was_true_positive {
+                counts.true_positives = counts.true_positives.saturating_sub(1);
+            } else {
+                counts.false_positives = counts.false_positives.saturating_sub(1);
+            }
+        }
+
+        let counts = labels.counts.entry(detector_name.to_string()).or_default();
+        if true_positive {
+            counts.true_positives += 1;
+        } else {
+            counts.false_positives += 1;
+        }
+    }
+
+    /// Labels recorded for a detector
+    pub fn counts(&self, detector_name: &str) -> FeedbackCounts {
+        let labels = self.labels.read().expect("feedback lock poisoned");
+        labels.counts.get(detector_name).copied().unwrap_or_default()
+    }
+
+    /// Amount added to a detector's configured alert threshold
+    pub fn threshold_shift(&self, detector_name: &str) -> f64 {
+        let labels = self.labels.read().expect("feedback lock poisoned");
+        let Some(counts) = labels.counts.get(detector_name) else {
+            return 0.0;
+        };
+        let step = labels
+            .steps
+            .get(detector_name)
+            .copied()
+            .unwrap_or(DEFAULT_FEEDBACK_STEP);
+
+        let net_false_positives = counts.false_positives.saturating_sub(counts.true_positives);
+        (net_false_positives as f64 * step).min(MAX_THRESHOLD_SHIFT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DETECTOR: &str = "MEV Detector";
+
+    #[test]
+    fn test_false_positives_raise_threshold_up_to_cap() {
+        let tracker = FeedbackTracker::new();
+        assert_eq!(tracker.threshold_shift(DETECTOR), 0.0);
+
+        for i in 0..3 {
+            tracker.record(&format!("fp-{}", i), DETECTOR, false);
+        }
+        assert!((tracker.threshold_shift(DETECTOR) - 3.0 * DEFAULT_FEEDBACK_STEP).abs() < 1e-9);
+
+        // True positives offset false positives, never going below zero
+        for i in 0..5 {
+            tracker.record(&format!("tp-{}", i), DETECTOR, true);
+        }
+        assert_eq!(tracker.threshold_shift(DETECTOR), 0.0);
+
+        for i in 3..100 {
+            tracker.record(&format!("fp-{}", i), DETECTOR, false);
+        }
+        assert_eq!(tracker.threshold_shift(DETECTOR), MAX_THRESHOLD_SHIFT);
+    }
+
+    #[test]
+    fn test_relabel_replaces_label() {
+        let tracker = FeedbackTracker::new();
+        tracker.record("d1", DETECTOR, false);
+        tracker.record("d1", DETECTOR, false);
+        assert_eq!(tracker.counts(DETECTOR), FeedbackCounts { true_positives: 0, false_positives: 1 });
+
+        tracker.record("d1", DETECTOR, true);
+        assert_eq!(tracker.counts(DETECTOR), FeedbackCounts { true_positives: 1, false_positives: 0 });
+    }
+
+    #[test]
+    fn test_zero_step_opts_out() {
+        let tracker = FeedbackTracker::new();
+        tracker.set_step(DETECTOR, 0.0);
+        tracker.record("d1", DETECTOR, false);
+        assert_eq!(tracker.threshold_shift(DETECTOR), 0.0);
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Real-time security monitoring for Polkadot parachains.
 //! Detects suspicious patterns, potential attacks, and anomalies in blockchain activity.
+//!
+//! Embedders should import from [`prelude`], the stable public API.
 
 pub mod detectors;
 pub mod mempool;
@@ -10,17 +12,33 @@ pub mod types;
 pub mod connection;
 pub mod api;
 pub mod transaction;
+pub mod decode;
 pub mod config;
 pub mod database;
 pub mod ml;
+pub mod sensitivity;
+pub mod backfill;
+pub mod sampling;
+pub mod dead_letter;
+pub mod feedback;
+pub mod block_source;
+pub mod schema;
+pub mod severity;
+pub mod storage;
+pub mod prelude;
+mod detection_log;
 
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use subxt::config::substrate::H256;
 use thiserror::Error;
 use tokio::sync::RwLock;
 
-pub use types::{Alert, AlertSeverity, AttackPattern, ChainEvent, DetectionResult, Transaction, ParsedTransaction, TransactionContext};
+pub use sensitivity::{
+    AlertThresholds, ConfidenceHistogram, HistogramBucket, SensitivityProfile, SeverityCutoffs,
+};
+pub use types::{Alert, AlertSeverity, AttackPattern, ChainEvent, DecodedCall, DetectionResult, Transaction, ParsedTransaction, TransactionContext, SCHEMA_VERSION};
 
 /// Main error type for the monitoring engine
 #[derive(Error, Debug)]
@@ -43,10 +61,50 @@ pub enum Error {
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
+    #[error("Not found: {0}")]
+    NotFound(String),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
 
+/// Broad classification of an [`Error`], for deciding whether to retry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// May succeed if retried (node or database unreachable, timeouts)
+    Transient,
+    /// Will fail again until something is fixed (bad config, malformed data)
+    Fatal,
+    /// The requested resource does not exist
+    NotFound,
+}
+
+impl Error {
+    /// Classify the error so callers can branch without matching on messages
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::ConnectionError(_)
+            | Error::SubscriptionError(_)
+            | Error::DatabaseError(_)
+            | Error::AlertError(_) => ErrorKind::Transient,
+            Error::ParseError(_) | Error::ConfigError(_) => ErrorKind::Fatal,
+            Error::NotFound(_) => ErrorKind::NotFound,
+            Error::IoError(e) => match e.kind() {
+                std::io::ErrorKind::NotFound => ErrorKind::NotFound,
+                std::io::ErrorKind::Interrupted
+                | std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::WouldBlock
+                | std::io::ErrorKind::ConnectionRefused
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe => ErrorKind::Transient,
+                _ => ErrorKind::Fatal,
+            },
+        }
+    }
+}
+
 /// Result type alias
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -57,27 +115,187 @@ pub struct MonitorConfig {
     pub ws_endpoint: String,
     /// Chain name identifier
     pub chain_name: String,
-    /// Enable mempool monitoring
+    /// Analyze extrinsics pending in the node's transaction pool
     pub enable_mempool: bool,
     /// Enable block monitoring
     pub enable_blocks: bool,
-    /// Enable event monitoring
+    /// Hand each transaction to the detectors with the events it emitted
     pub enable_events: bool,
     /// Alert webhook URL (optional)
     pub alert_webhook: Option<String>,
     /// Minimum alert severity to trigger notifications
     pub min_alert_severity: AlertSeverity,
-    /// Buffer size for event processing
+    /// Capacity of the queue between block extraction and detector
+    /// processing, in blocks; extraction pauses while it is full. Also bounds
+    /// the pending mempool transactions kept for correlation.
     pub buffer_size: usize,
+    /// Extract and store ML features for every transaction (requires a database)
+    #[serde(default)]
+    pub enable_ml_features: bool,
     /// Maximum reconnection attempts (0 = no retry, use connect_with_retry)
     #[serde(default = "default_max_reconnect_attempts")]
     pub max_reconnect_attempts: u32,
+    /// TimescaleDB connection URL (optional)
+    #[serde(default, skip_serializing)]
+    pub database_url: Option<String>,
+    /// Maximum database connections in the pool
+    #[serde(default = "default_database_max_connections")]
+    pub database_max_connections: usize,
+    /// Address the REST API binds to
+    #[serde(default = "default_api_bind_address")]
+    pub api_bind_address: String,
+    /// Detector sensitivity profile (strict/balanced/lenient)
+    #[serde(default)]
+    pub sensitivity: SensitivityProfile,
+    /// Explicit per-detector confidence thresholds, keyed by detector name.
+    /// These take precedence over the sensitivity profile.
+    #[serde(default)]
+    pub detector_thresholds: std::collections::HashMap<String, f64>,
+    /// Per-detector execution priority overrides (detector name -> priority);
+    /// detectors run in descending priority
+    #[serde(default)]
+    pub detector_priorities: std::collections::HashMap<String, i32>,
+    /// Skip the remaining detectors on a transaction once a privileged
+    /// pattern (e.g. a runtime upgrade) is detected. The skipped detectors
+    /// neither alert nor update their state for that transaction
+    #[serde(default)]
+    pub short_circuit_privileged: bool,
+    /// Minimum confidence for Critical/High/Medium alerts
+    #[serde(default)]
+    pub severity_cutoffs: SeverityCutoffs,
+    /// Blocks whose transactions are extracted concurrently. Detectors still
+    /// analyze one block at a time, in block order.
+    #[serde(default = "default_block_parallelism")]
+    pub block_parallelism: usize,
+    /// Maximum evidence entries kept per detection; the rest are summarized
+    #[serde(default = "default_max_evidence_entries")]
+    pub max_evidence_entries: usize,
+    /// Lowest confidence at which results that do not alert are still stored
+    /// as near misses (`alerted = false`); `None` discards them
+    #[serde(default)]
+    pub record_floor: Option<f64>,
+    /// Normalized distance from the recent feature centroid that the
+    /// unsupervised anomaly detector treats as an outlier
+    #[serde(default = "default_anomaly_distance_threshold")]
+    pub anomaly_distance_threshold: f64,
+    /// File for detector state snapshots when no database is configured
+    #[serde(default)]
+    pub detector_state_file: Option<String>,
+    /// Send one alert digest per interval (seconds) instead of a webhook per
+    /// alert. Critical alerts are always sent immediately.
+    #[serde(default)]
+    pub alert_digest_interval_secs: Option<u64>,
+    /// After alerting on a caller for a pattern, suppress further alerts for
+    /// that caller and pattern for this many seconds (suppressed hits are
+    /// counted on the original alert)
+    #[serde(default)]
+    pub alert_cooldown_secs: Option<u64>,
+    /// File of sanctioned/scam addresses for the watchlist detector
+    #[serde(default)]
+    pub watchlist_file: Option<String>,
+    /// Fraction (0.0–1.0) of transactions run through the detectors; `None`
+    /// analyzes all of them. Sampling reduces detection completeness, though
+    /// sensitive pallets and large transfers are always analyzed (see
+    /// [`sampling`])
+    #[serde(default)]
+    pub sample_rate: Option<f64>,
+    /// Transfer amount (planck) at or above which sampling never skips a transaction
+    #[serde(default = "default_sample_value_threshold")]
+    pub sample_value_threshold: u128,
+    /// Pallets whose transactions are run through the detectors; others are
+    /// stored and counted but never analyzed. The sensitive pallets (see
+    /// `sampling::SENSITIVE_PALLETS`) are always analyzed. `None` analyzes
+    /// every pallet
+    #[serde(default)]
+    pub monitored_pallets: Option<Vec<String>>,
+    /// Days of transactions, detections and ML features kept in the
+    /// database; older rows are purged periodically. `None` keeps everything
+    #[serde(default)]
+    pub retention_days: Option<u32>,
+    /// Kafka bootstrap brokers (`host:port`) alerts are published to; empty disables Kafka
+    #[serde(default)]
+    pub kafka_brokers: Vec<String>,
+    /// Kafka topic for alerts
+    #[serde(default = "default_alert_topic")]
+    pub kafka_topic: String,
+    /// NATS server URL alerts are published to
+    #[serde(default)]
+    pub nats_url: Option<String>,
+    /// NATS subject for alerts
+    #[serde(default = "default_alert_topic")]
+    pub nats_subject: String,
+    /// Alerts buffered per message bus sink while its broker is unavailable;
+    /// further alerts are dropped and counted
+    #[serde(default = "default_sink_queue_capacity")]
+    pub sink_queue_capacity: usize,
+    /// Drop pending mempool transactions older than this many seconds, even
+    /// below `buffer_size`; `None` evicts by capacity only
+    #[serde(default)]
+    pub mempool_max_age_secs: Option<u64>,
+    /// Webhook requests allowed in flight at once; further deliveries queue
+    #[serde(default = "default_webhook_concurrency")]
+    pub webhook_concurrency: usize,
+    /// JSON Lines file recording blocks whose transactions could not be
+    /// decoded (see [`dead_letter`]); `None` only logs and counts them
+    #[serde(default)]
+    pub dead_letter_file: Option<String>,
+    /// Attempts per transaction or detection insert before the record is
+    /// deferred for replay (see [`database::retry`])
+    #[serde(default = "default_db_write_attempts")]
+    pub db_write_attempts: u32,
+    /// JSON Lines file deferred database records are written to; `None`
+    /// keeps up to [`database::retry::DEFAULT_DEFERRED_CAPACITY`] in memory
+    #[serde(default)]
+    pub db_dead_letter_file: Option<String>,
+    /// Bearer token required by the admin API endpoints; `None` disables them
+    #[serde(default, skip_serializing)]
+    pub admin_token: Option<String>,
 }
 
 fn default_max_reconnect_attempts() -> u32 {
     5
 }
 
+fn default_database_max_connections() -> usize {
+    10
+}
+
+fn default_api_bind_address() -> String {
+    "0.0.0.0:8080".to_string()
+}
+
+fn default_block_parallelism() -> usize {
+    1
+}
+
+fn default_max_evidence_entries() -> usize {
+    sensitivity::DEFAULT_MAX_EVIDENCE_ENTRIES
+}
+
+fn default_anomaly_distance_threshold() -> f64 {
+    detectors::anomaly::DEFAULT_DISTANCE_THRESHOLD
+}
+
+fn default_sample_value_threshold() -> u128 {
+    sampling::DEFAULT_VALUE_THRESHOLD
+}
+
+fn default_alert_topic() -> String {
+    "security-nexus.alerts".to_string()
+}
+
+fn default_sink_queue_capacity() -> usize {
+    alerts::sink::DEFAULT_QUEUE_CAPACITY
+}
+
+fn default_webhook_concurrency() -> usize {
+    alerts::DEFAULT_WEBHOOK_CONCURRENCY
+}
+
+fn default_db_write_attempts() -> u32 {
+    database::retry::DEFAULT_WRITE_ATTEMPTS
+}
+
 impl Default for MonitorConfig {
     fn default() -> Self {
         Self::westend()
@@ -86,68 +304,115 @@ impl Default for MonitorConfig {
 
 /// Chain configuration presets
 impl MonitorConfig {
-    /// Westend testnet configuration
-    pub fn westend() -> Self {
+    /// Base configuration shared by all presets
+    fn preset(chain_name: &str, ws_endpoint: &str) -> Self {
         Self {
-            ws_endpoint: "wss://westend-rpc.polkadot.io".to_string(),
-            chain_name: "westend".to_string(),
+            ws_endpoint: ws_endpoint.to_string(),
+            chain_name: chain_name.to_string(),
             enable_mempool: true,
             enable_blocks: true,
             enable_events: true,
             alert_webhook: None,
             min_alert_severity: AlertSeverity::Medium,
             buffer_size: 1000,
-            max_reconnect_attempts: 5,
+            enable_ml_features: false,
+            max_reconnect_attempts: default_max_reconnect_attempts(),
+            database_url: None,
+            database_max_connections: default_database_max_connections(),
+            api_bind_address: default_api_bind_address(),
+            sensitivity: SensitivityProfile::default(),
+            detector_thresholds: std::collections::HashMap::new(),
+            detector_priorities: std::collections::HashMap::new(),
+            short_circuit_privileged: false,
+            severity_cutoffs: SeverityCutoffs::default(),
+            block_parallelism: default_block_parallelism(),
+            max_evidence_entries: default_max_evidence_entries(),
+            record_floor: None,
+            anomaly_distance_threshold: default_anomaly_distance_threshold(),
+            detector_state_file: None,
+            alert_digest_interval_secs: None,
+            alert_cooldown_secs: None,
+            watchlist_file: None,
+            sample_rate: None,
+            sample_value_threshold: default_sample_value_threshold(),
+            monitored_pallets: None,
+            retention_days: None,
+            kafka_brokers: Vec::new(),
+            kafka_topic: default_alert_topic(),
+            nats_url: None,
+            nats_subject: default_alert_topic(),
+            sink_queue_capacity: default_sink_queue_capacity(),
+            mempool_max_age_secs: None,
+            webhook_concurrency: default_webhook_concurrency(),
+            dead_letter_file: None,
+            db_write_attempts: default_db_write_attempts(),
+            db_dead_letter_file: None,
+            admin_token: None,
+        }
+    }
+
+    /// Resolve the alert thresholds from the profile, explicit overrides and severity cutoffs
+    pub fn alert_thresholds(&self) -> AlertThresholds {
+        AlertThresholds::new(self.sensitivity, self.detector_thresholds.clone())
+            .with_severity_cutoffs(self.severity_cutoffs)
+            .with_max_evidence_entries(self.max_evidence_entries)
+            .with_record_floor(self.record_floor)
+            .with_short_circuit_privileged(self.short_circuit_privileged)
+    }
+
+    /// Message bus sinks alerts are published to, besides the webhook
+    pub fn alert_sinks(&self) -> Vec<Arc<dyn alerts::Sink>> {
+        let mut sinks: Vec<Arc<dyn alerts::Sink>> = Vec::new();
+        if !self.kafka_brokers.is_empty() {
+            sinks.push(Arc::new(alerts::KafkaSink::new(
+                self.kafka_brokers.clone(),
+                self.kafka_topic.clone(),
+            )));
         }
+        if let Some(url) = &self.nats_url {
+            sinks.push(Arc::new(alerts::NatsSink::new(url.clone(), self.nats_subject.clone())));
+        }
+        sinks
+    }
+
+    /// Sampler deciding which transactions the detectors run on
+    pub fn sampler(&self) -> sampling::TransactionSampler {
+        sampling::TransactionSampler::new(self.sample_rate)
+            .with_value_threshold(self.sample_value_threshold)
+            .with_monitored_pallets(self.monitored_pallets.clone())
+    }
+
+    /// Westend testnet configuration
+    pub fn westend() -> Self {
+        Self::preset("westend", "wss://westend-rpc.polkadot.io")
     }
 
     /// Asset Hub (Westend) configuration
     pub fn asset_hub() -> Self {
-        Self {
-            ws_endpoint: "wss://westend-asset-hub-rpc.polkadot.io".to_string(),
-            chain_name: "asset-hub".to_string(),
-            enable_mempool: true,
-            enable_blocks: true,
-            enable_events: true,
-            alert_webhook: None,
-            min_alert_severity: AlertSeverity::Medium,
-            buffer_size: 1000,
-            max_reconnect_attempts: 5,
-        }
+        Self::preset("asset-hub", "wss://westend-asset-hub-rpc.polkadot.io")
     }
 
     /// Polkadot mainnet configuration
     pub fn polkadot() -> Self {
-        Self {
-            ws_endpoint: "wss://rpc.polkadot.io".to_string(),
-            chain_name: "polkadot".to_string(),
-            enable_mempool: true,
-            enable_blocks: true,
-            enable_events: true,
-            alert_webhook: None,
-            min_alert_severity: AlertSeverity::Medium,
-            buffer_size: 1000,
-            max_reconnect_attempts: 5,
-        }
+        Self::preset("polkadot", "wss://rpc.polkadot.io")
     }
 
     /// Kusama configuration
     pub fn kusama() -> Self {
-        Self {
-            ws_endpoint: "wss://kusama-rpc.polkadot.io".to_string(),
-            chain_name: "kusama".to_string(),
-            enable_mempool: true,
-            enable_blocks: true,
-            enable_events: true,
-            alert_webhook: None,
-            min_alert_severity: AlertSeverity::Medium,
-            buffer_size: 1000,
-            max_reconnect_attempts: 5,
-        }
+        Self::preset("kusama", "wss://kusama-rpc.polkadot.io")
     }
 
-    /// Get chain config by name
+    /// Get chain config by name, including custom presets from the presets file
     pub fn from_chain_name(name: &str) -> Option<Self> {
+        Self::from_chain_name_with(name, &config::load_chain_presets())
+    }
+
+    /// Get chain config by name, checking `custom` presets before the built-ins
+    pub fn from_chain_name_with(name: &str, custom: &[ChainInfo]) -> Option<Self> {
+        if let Some(preset) = custom.iter().find(|c| c.name.eq_ignore_ascii_case(name)) {
+            return Some(Self::preset(&preset.name, &preset.endpoint));
+        }
+
         match name.to_lowercase().as_str() {
             "westend" => Some(Self::westend()),
             "asset-hub" | "asset_hub" | "assethub" => Some(Self::asset_hub()),
@@ -157,8 +422,25 @@ impl MonitorConfig {
         }
     }
 
-    /// Get list of available chain presets
+    /// Get list of available chain presets, including custom presets
     pub fn available_chains() -> Vec<ChainInfo> {
+        Self::available_chains_with(&config::load_chain_presets())
+    }
+
+    /// Built-in chain presets merged with `custom` ones (custom wins on name clashes)
+    pub fn available_chains_with(custom: &[ChainInfo]) -> Vec<ChainInfo> {
+        let mut chains = Self::builtin_chains();
+        for preset in custom {
+            match chains.iter_mut().find(|c| c.name.eq_ignore_ascii_case(&preset.name)) {
+                Some(existing) => *existing = preset.clone(),
+                None => chains.push(preset.clone()),
+            }
+        }
+        chains
+    }
+
+    /// Built-in chain presets
+    fn builtin_chains() -> Vec<ChainInfo> {
         vec![
             ChainInfo {
                 name: "westend".to_string(),
@@ -188,6 +470,334 @@ impl MonitorConfig {
     }
 }
 
+/// Environment variable overrides
+///
+/// | Variable                     | Field                        |
+/// |------------------------------|------------------------------|
+/// | `WS_ENDPOINT`                | `ws_endpoint`                |
+/// | `CHAIN_NAME`                 | `chain_name`                 |
+/// | `ALERT_WEBHOOK`              | `alert_webhook`              |
+/// | `MIN_ALERT_SEVERITY`         | `min_alert_severity`         |
+/// | `ENABLE_MEMPOOL`             | `enable_mempool`             |
+/// | `ENABLE_BLOCKS`              | `enable_blocks`              |
+/// | `ENABLE_EVENTS`              | `enable_events`              |
+/// | `ENABLE_ML_FEATURES`         | `enable_ml_features`         |
+/// | `BUFFER_SIZE`                | `buffer_size`                |
+/// | `MAX_RECONNECT_ATTEMPTS`     | `max_reconnect_attempts`     |
+/// | `DATABASE_URL`               | `database_url`               |
+/// | `DATABASE_MAX_CONNECTIONS`   | `database_max_connections`   |
+/// | `API_BIND_ADDRESS`           | `api_bind_address`           |
+/// | `SENSITIVITY_PROFILE`        | `sensitivity`                |
+/// | `SEVERITY_CUTOFFS`           | `severity_cutoffs`           |
+/// | `BLOCK_PARALLELISM`          | `block_parallelism`          |
+/// | `MAX_EVIDENCE_ENTRIES`       | `max_evidence_entries`       |
+/// | `DETECTION_RECORD_FLOOR`     | `record_floor`               |
+/// | `ANOMALY_DISTANCE_THRESHOLD` | `anomaly_distance_threshold` |
+/// | `DETECTOR_STATE_FILE`        | `detector_state_file`        |
+/// | `ALERT_DIGEST_INTERVAL`      | `alert_digest_interval_secs` |
+/// | `ALERT_COOLDOWN`             | `alert_cooldown_secs`        |
+/// | `WATCHLIST_FILE`             | `watchlist_file`             |
+/// | `SAMPLE_RATE`                | `sample_rate`                |
+/// | `SAMPLE_VALUE_THRESHOLD`     | `sample_value_threshold`     |
+/// | `MONITORED_PALLETS`          | `monitored_pallets`          |
+/// | `RETENTION_DAYS`             | `retention_days`             |
+/// | `KAFKA_BROKERS`              | `kafka_brokers`              |
+/// | `KAFKA_TOPIC`                | `kafka_topic`                |
+/// | `NATS_URL`                   | `nats_url`                   |
+/// | `NATS_SUBJECT`               | `nats_subject`               |
+/// | `SINK_QUEUE_CAPACITY`        | `sink_queue_capacity`        |
+/// | `MEMPOOL_MAX_AGE`            | `mempool_max_age_secs`       |
+/// | `WEBHOOK_CONCURRENCY`        | `webhook_concurrency`        |
+/// | `DEAD_LETTER_FILE`           | `dead_letter_file`           |
+/// | `DB_WRITE_ATTEMPTS`          | `db_write_attempts`          |
+/// | `DB_DEAD_LETTER_FILE`        | `db_dead_letter_file`        |
+/// | `SHORT_CIRCUIT_PRIVILEGED`   | `short_circuit_privileged`   |
+/// | `ADMIN_TOKEN`                | `admin_token`                |
+impl MonitorConfig {
+    /// Build a configuration from environment variables over the default preset
+    pub fn from_env() -> Result<Self> {
+        Self::from_env_with_base(Self::default())
+    }
+
+    /// Layer environment variables over an existing configuration
+    ///
+    /// Unset or empty variables leave the base value untouched. Malformed
+    /// values return `Error::ConfigError` instead of being silently ignored.
+    pub fn from_env_with_base(base: Self) -> Result<Self> {
+        Self::from_lookup(base, |name| std::env::var(name).ok())
+    }
+
+    /// Apply overrides from an arbitrary variable source
+    fn from_lookup<F>(base: Self, lookup: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut config = base;
+        let var = |name: &str| lookup(name).filter(|v| !v.trim().is_empty());
+
+        if let Some(ws_endpoint) = var("WS_ENDPOINT") {
+            if !ws_endpoint.starts_with("ws://") && !ws_endpoint.starts_with("wss://") {
+                return Err(Error::ConfigError(format!(
+                    "WS_ENDPOINT must start with ws:// or wss://, got '{}'",
+                    ws_endpoint
+                )));
+            }
+            config.ws_endpoint = ws_endpoint;
+        }
+        if let Some(chain_name) = var("CHAIN_NAME") {
+            config.chain_name = chain_name;
+        }
+        if let Some(webhook) = var("ALERT_WEBHOOK") {
+            config.alert_webhook = Some(webhook);
+        }
+        if let Some(severity) = var("MIN_ALERT_SEVERITY") {
+            config.min_alert_severity = parse_env_value("MIN_ALERT_SEVERITY", &severity)?;
+        }
+        if let Some(value) = var("ENABLE_MEMPOOL") {
+            config.enable_mempool = parse_env_bool("ENABLE_MEMPOOL", &value)?;
+        }
+        if let Some(value) = var("ENABLE_BLOCKS") {
+            config.enable_blocks = parse_env_bool("ENABLE_BLOCKS", &value)?;
+        }
+        if let Some(value) = var("ENABLE_EVENTS") {
+            config.enable_events = parse_env_bool("ENABLE_EVENTS", &value)?;
+        }
+        if let Some(value) = var("ENABLE_ML_FEATURES") {
+            config.enable_ml_features = parse_env_bool("ENABLE_ML_FEATURES", &value)?;
+        }
+        if let Some(value) = var("BUFFER_SIZE") {
+            config.buffer_size = parse_env_value("BUFFER_SIZE", &value)?;
+        }
+        if let Some(value) = var("MAX_RECONNECT_ATTEMPTS") {
+            config.max_reconnect_attempts = parse_env_value("MAX_RECONNECT_ATTEMPTS", &value)?;
+        }
+        if let Some(database_url) = var("DATABASE_URL") {
+            config.database_url = Some(database_url);
+        }
+        if let Some(value) = var("DATABASE_MAX_CONNECTIONS") {
+            let max_connections: usize = parse_env_value("DATABASE_MAX_CONNECTIONS", &value)?;
+            if max_connections == 0 {
+                return Err(Error::ConfigError(
+                    "DATABASE_MAX_CONNECTIONS must be greater than 0".to_string(),
+                ));
+            }
+            config.database_max_connections = max_connections;
+        }
+        if let Some(bind_address) = var("API_BIND_ADDRESS") {
+            config.api_bind_address = bind_address;
+        }
+        if let Some(profile) = var("SENSITIVITY_PROFILE") {
+            config.sensitivity = parse_env_value("SENSITIVITY_PROFILE", &profile)?;
+        }
+        if let Some(cutoffs) = var("SEVERITY_CUTOFFS") {
+            config.severity_cutoffs = parse_env_value("SEVERITY_CUTOFFS", &cutoffs)?;
+        }
+        if let Some(value) = var("BLOCK_PARALLELISM") {
+            let parallelism: usize = parse_env_value("BLOCK_PARALLELISM", &value)?;
+            if parallelism == 0 {
+                return Err(Error::ConfigError(
+                    "BLOCK_PARALLELISM must be greater than 0".to_string(),
+                ));
+            }
+            config.block_parallelism = parallelism;
+        }
+        if let Some(value) = var("MAX_EVIDENCE_ENTRIES") {
+            let max_entries: usize = parse_env_value("MAX_EVIDENCE_ENTRIES", &value)?;
+            if max_entries == 0 {
+                return Err(Error::ConfigError(
+                    "MAX_EVIDENCE_ENTRIES must be greater than 0".to_string(),
+                ));
+            }
+            config.max_evidence_entries = max_entries;
+        }
+        if let Some(value) = var("DETECTION_RECORD_FLOOR") {
+            let floor: f64 = parse_env_value("DETECTION_RECORD_FLOOR", &value)?;
+            if !(0.0..=1.0).contains(&floor) {
+                return Err(Error::ConfigError(
+                    "DETECTION_RECORD_FLOOR must be between 0 and 1".to_string(),
+                ));
+            }
+            config.record_floor = Some(floor);
+        }
+        if let Some(value) = var("ANOMALY_DISTANCE_THRESHOLD") {
+            let threshold: f64 = parse_env_value("ANOMALY_DISTANCE_THRESHOLD", &value)?;
+            if !(threshold > 0.0 && threshold.is_finite()) {
+                return Err(Error::ConfigError(
+                    "ANOMALY_DISTANCE_THRESHOLD must be a positive number".to_string(),
+                ));
+            }
+            config.anomaly_distance_threshold = threshold;
+        }
+        if let Some(path) = var("DETECTOR_STATE_FILE") {
+            config.detector_state_file = Some(path);
+        }
+        if let Some(value) = var("ALERT_DIGEST_INTERVAL") {
+            let interval: u64 = parse_env_value("ALERT_DIGEST_INTERVAL", &value)?;
+            if interval == 0 {
+                return Err(Error::ConfigError(
+                    "ALERT_DIGEST_INTERVAL must be greater than 0".to_string(),
+                ));
+            }
+            config.alert_digest_interval_secs = Some(interval);
+        }
+        if let Some(value) = var("ALERT_COOLDOWN") {
+            let cooldown: u64 = parse_env_value("ALERT_COOLDOWN", &value)?;
+            if cooldown == 0 {
+                return Err(Error::ConfigError(
+                    "ALERT_COOLDOWN must be greater than 0".to_string(),
+                ));
+            }
+            config.alert_cooldown_secs = Some(cooldown);
+        }
+        if let Some(path) = var("WATCHLIST_FILE") {
+            config.watchlist_file = Some(path);
+        }
+        if let Some(value) = var("SAMPLE_RATE") {
+            let rate: f64 = parse_env_value("SAMPLE_RATE", &value)?;
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(Error::ConfigError(
+                    "SAMPLE_RATE must be between 0 and 1".to_string(),
+                ));
+            }
+            config.sample_rate = Some(rate);
+        }
+        if let Some(value) = var("SAMPLE_VALUE_THRESHOLD") {
+            config.sample_value_threshold = parse_env_value("SAMPLE_VALUE_THRESHOLD", &value)?;
+        }
+        if let Some(value) = var("MONITORED_PALLETS") {
+            config.monitored_pallets = Some(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|pallet| !pallet.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            );
+        }
+        if let Some(value) = var("RETENTION_DAYS") {
+            let days: u32 = parse_env_value("RETENTION_DAYS", &value)?;
+            if days == 0 || days > i32::MAX as u32 {
+                return Err(Error::ConfigError(
+                    "RETENTION_DAYS must be a positive number of days".to_string(),
+                ));
+            }
+            config.retention_days = Some(days);
+        }
+        if let Some(value) = var("KAFKA_BROKERS") {
+            config.kafka_brokers = value
+                .split(',')
+                .map(str::trim)
+                .filter(|broker| !broker.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        if let Some(topic) = var("KAFKA_TOPIC") {
+            config.kafka_topic = topic;
+        }
+        if let Some(url) = var("NATS_URL") {
+            config.nats_url = Some(url);
+        }
+        if let Some(subject) = var("NATS_SUBJECT") {
+            config.nats_subject = subject;
+        }
+        if let Some(value) = var("SINK_QUEUE_CAPACITY") {
+            let capacity: usize = parse_env_value("SINK_QUEUE_CAPACITY", &value)?;
+            if capacity == 0 {
+                return Err(Error::ConfigError(
+                    "SINK_QUEUE_CAPACITY must be greater than 0".to_string(),
+                ));
+            }
+            config.sink_queue_capacity = capacity;
+        }
+        if let Some(value) = var("MEMPOOL_MAX_AGE") {
+            let max_age: u64 = parse_env_value("MEMPOOL_MAX_AGE", &value)?;
+            if max_age == 0 {
+                return Err(Error::ConfigError(
+                    "MEMPOOL_MAX_AGE must be greater than 0".to_string(),
+                ));
+            }
+            config.mempool_max_age_secs = Some(max_age);
+        }
+        if let Some(value) = var("WEBHOOK_CONCURRENCY") {
+            let limit: usize = parse_env_value("WEBHOOK_CONCURRENCY", &value)?;
+            if limit == 0 {
+                return Err(Error::ConfigError(
+                    "WEBHOOK_CONCURRENCY must be greater than 0".to_string(),
+                ));
+            }
+            config.webhook_concurrency = limit;
+        }
+        if let Some(path) = var("DEAD_LETTER_FILE") {
+            config.dead_letter_file = Some(path);
+        }
+        if let Some(value) = var("DB_WRITE_ATTEMPTS") {
+            let attempts: u32 = parse_env_value("DB_WRITE_ATTEMPTS", &value)?;
+            if attempts == 0 {
+                return Err(Error::ConfigError(
+                    "DB_WRITE_ATTEMPTS must be greater than 0".to_string(),
+                ));
+            }
+            config.db_write_attempts = attempts;
+        }
+        if let Some(path) = var("DB_DEAD_LETTER_FILE") {
+            config.db_dead_letter_file = Some(path);
+        }
+        if let Some(value) = var("SHORT_CIRCUIT_PRIVILEGED") {
+            config.short_circuit_privileged = parse_env_bool("SHORT_CIRCUIT_PRIVILEGED", &value)?;
+        }
+        if let Some(token) = var("ADMIN_TOKEN") {
+            config.admin_token = Some(token);
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check settings that environment parsing alone cannot, e.g. values
+    /// coming from a saved or hand-built configuration
+    pub fn validate(&self) -> Result<()> {
+        if self.buffer_size == 0 {
+            return Err(Error::ConfigError(
+                "buffer_size must be greater than 0".to_string(),
+            ));
+        }
+        if self.admin_token.as_ref().is_some_and(|token| token.trim().is_empty()) {
+            return Err(Error::ConfigError(
+                "admin_token must not be blank".to_string(),
+            ));
+        }
+        if self.alert_digest_interval_secs == Some(0) {
+            return Err(Error::ConfigError(
+                "alert_digest_interval_secs must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Parse an environment variable value, mapping failures to `ConfigError`
+fn parse_env_value<T>(name: &str, value: &str) -> Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.trim().parse::<T>().map_err(|e| {
+        Error::ConfigError(format!("Invalid value '{}' for {}: {}", value, name, e))
+    })
+}
+
+/// Parse a boolean environment variable (true/false, 1/0, yes/no, on/off)
+fn parse_env_bool(name: &str, value: &str) -> Result<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Ok(true),
+        "false" | "0" | "no" | "off" => Ok(false),
+        _ => Err(Error::ConfigError(format!(
+            "Invalid value '{}' for {}: expected a boolean",
+            value, name
+        ))),
+    }
+}
+
 /// Chain information for API responses
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainInfo {
@@ -197,13 +807,118 @@ pub struct ChainInfo {
     pub description: String,
 }
 
+/// Number of persisted alerts restored into memory on startup
+const ALERT_HISTORY_LOAD_LIMIT: usize = 1000;
+
+/// How often detector state is persisted while the engine runs
+const DETECTOR_SNAPSHOT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How often the configured data retention is enforced
+const RETENTION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// How often deferred database records are replayed
+const DB_REPLAY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How often the node's transaction pool is polled for pending extrinsics
+const MEMPOOL_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Format event or state change indices as a comma-separated list
+fn join_indices(indices: &[usize]) -> String {
+    indices.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(",")
+}
+
+/// Deterministic id of a detection
+///
+/// Derived from the chain, extrinsic hash and detector so that
+/// re-processing a block (e.g. a resumed backfill) yields the same ids
+/// and the database drops the duplicates. The block is left out so an
+/// extrinsic alerted while pending keeps its id once included.
+fn detection_id(chain_name: &str, tx: &ParsedTransaction, detector_name: &str) -> String {
+    let key = format!("{}:{}:{}", chain_name, tx.hash, detector_name);
+    let digest = blake3::hash(key.as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest.as_bytes()[..16]);
+    uuid::Uuid::from_bytes(bytes).to_string()
+}
+
+/// Everything the block pipeline needs to analyze and commit blocks
+struct BlockPipeline {
+    state: Arc<RwLock<EngineState>>,
+    chain_name: String,
+    detectors: Arc<Vec<Box<dyn detectors::Detector + Send + Sync>>>,
+    alert_manager: Arc<alerts::AlertManager>,
+    database: Option<Arc<database::DatabaseClient>>,
+    /// Retries transaction and detection inserts, deferring failures
+    db_writer: Option<Arc<database::retry::RetryingWriter>>,
+    thresholds: Arc<AlertThresholds>,
+    enable_ml_features: bool,
+    mempool: Arc<mempool::MempoolMonitor>,
+    /// Blocks extracted concurrently ahead of in-order analysis
+    parallelism: usize,
+    /// Extracted blocks queued for analysis before extraction waits
+    buffer_size: usize,
+    sampler: sampling::TransactionSampler,
+    /// Where blocks that fail to decode are recorded
+    dead_letters: Option<Arc<dead_letter::DeadLetterStore>>,
+    /// Fetches the raw extrinsics of a failed block for its dead letter
+    raw_blocks: Option<Arc<dyn block_source::BlockSource>>,
+}
+
+/// Everything the mempool poller needs to analyze pending extrinsics
+struct MempoolPipeline {
+    state: Arc<RwLock<EngineState>>,
+    chain_name: String,
+    detectors: Arc<Vec<Box<dyn detectors::Detector + Send + Sync>>>,
+    alert_manager: Arc<alerts::AlertManager>,
+    thresholds: Arc<AlertThresholds>,
+    mempool: Arc<mempool::MempoolMonitor>,
+    sampler: sampling::TransactionSampler,
+}
+
+/// A transaction with the result of every detector, not yet recorded
+struct AnalyzedTransaction {
+    ctx: TransactionContext,
+    /// One result per detector, in detector order; empty when sampled out and
+    /// cut short when a privileged detection short-circuits the rest
+    results: Vec<DetectionResult>,
+}
+
+/// What happens to a detector result when it is committed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DetectionOutcome {
+    /// Stored and alerted
+    Alert,
+    /// Stored with `alerted = false` as a near miss
+    Record,
+    /// Discarded
+    Drop,
+}
+
+/// A block whose transactions were extracted and analyzed
+struct AnalyzedBlock {
+    number: u64,
+    hash: H256,
+    transactions: Result<Vec<AnalyzedTransaction>>,
+}
+
 /// Main monitoring engine
 pub struct MonitoringEngine {
     pub config: MonitorConfig,
     state: Arc<RwLock<EngineState>>,
     pub alert_manager: Arc<alerts::AlertManager>,
-    pub connection: Arc<connection::ConnectionManager>,
+    pub(crate) connection: Arc<connection::ConnectionManager>,
     pub database: Option<Arc<database::DatabaseClient>>,
+    /// Writes transactions and detections to `database`, retrying and
+    /// deferring failed inserts
+    db_writer: Option<Arc<database::retry::RetryingWriter>>,
+    /// Pending transactions, shared with the block pipeline for front-run correlation
+    pub(crate) mempool: Arc<mempool::MempoolMonitor>,
+    /// Progress of the backfill started in this process, if any
+    backfill_status: Arc<RwLock<Option<backfill::BackfillStatus>>>,
+    /// Replaces the node as the source of finalized blocks
+    block_source: Option<Arc<dyn block_source::BlockSource>>,
+    /// Analyst labels, shifting detector thresholds
+    feedback: Arc<feedback::FeedbackTracker>,
 }
 
 /// Internal engine state
@@ -212,8 +927,18 @@ struct EngineState {
     blocks_processed: u64,
     transactions_analyzed: u64,
     alerts_triggered: u64,
+    /// Blocks whose transactions could not be extracted
+    decode_failures: u64,
     detector_stats: std::collections::HashMap<String, DetectorStatsInternal>,
+    /// Confidence distribution per detector, including sub-threshold results
+    confidence_histograms: std::collections::HashMap<String, ConfidenceHistogram>,
     feature_extractor: ml::FeatureExtractor,
+    /// Rate limiter for repeated detection log lines
+    log_limiter: Arc<detection_log::DetectionLogLimiter>,
+    /// When the engine was created, for uptime
+    started_at: std::time::Instant,
+    /// Creation time as a Unix timestamp (seconds)
+    started_at_unix: u64,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -227,21 +952,38 @@ impl Default for EngineState {
         let mut detector_stats = std::collections::HashMap::new();
         detector_stats.insert("Flash Loan Detector".to_string(), DetectorStatsInternal::default());
         detector_stats.insert("MEV Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("Fee Anomaly Detector".to_string(), DetectorStatsInternal::default());
         detector_stats.insert("Volume Anomaly Detector".to_string(), DetectorStatsInternal::default());
         detector_stats.insert("FrontRunning Detector".to_string(), DetectorStatsInternal::default());
         detector_stats.insert("Cross-Chain Bridge Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("Bridge Balance Detector".to_string(), DetectorStatsInternal::default());
         detector_stats.insert("State Proof Verification Detector".to_string(), DetectorStatsInternal::default());
         detector_stats.insert("Omnipool Manipulation Detector".to_string(), DetectorStatsInternal::default());
         detector_stats.insert("Liquidity Drain Detector".to_string(), DetectorStatsInternal::default());
         detector_stats.insert("Collateral Manipulation Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("Liquidation Front-Running Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("Nonce Anomaly Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("Watchlist Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("Runtime Upgrade Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("Layering Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("Allowance Abuse Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("Unsupervised Anomaly Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("XCM Fan-out Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("Transaction Fingerprint Detector".to_string(), DetectorStatsInternal::default());
+        detector_stats.insert("Proxy Abuse Detector".to_string(), DetectorStatsInternal::default());
 
         Self {
             is_running: false,
             blocks_processed: 0,
             transactions_analyzed: 0,
             alerts_triggered: 0,
+            decode_failures: 0,
             detector_stats,
+            confidence_histograms: std::collections::HashMap::new(),
             feature_extractor: ml::FeatureExtractor::new(),
+            log_limiter: Arc::new(detection_log::DetectionLogLimiter::default()),
+            started_at: std::time::Instant::now(),
+            started_at_unix: chrono::Utc::now().timestamp() as u64,
         }
     }
 }
@@ -249,47 +991,119 @@ impl Default for EngineState {
 impl MonitoringEngine {
     /// Create a new monitoring engine with the given configuration
     pub fn new(config: MonitorConfig) -> Self {
-        let alert_manager = Arc::new(alerts::AlertManager::new(
-            config.min_alert_severity,
-            config.alert_webhook.clone(),
+        let alert_manager = Arc::new(Self::with_alert_config(
+            &config,
+            alerts::AlertManager::new(config.min_alert_severity, config.alert_webhook.clone()),
         ));
 
         let connection = Arc::new(connection::ConnectionManager::new(
             config.ws_endpoint.clone(),
         ));
 
+        let mempool = Self::mempool_monitor(&config, &alert_manager);
+
         Self {
             config,
             state: Arc::new(RwLock::new(EngineState::default())),
             alert_manager,
             connection,
             database: None,
+            db_writer: None,
+            mempool,
+            backfill_status: Arc::new(RwLock::new(None)),
+            block_source: None,
+            feedback: Arc::new(feedback::FeedbackTracker::new()),
         }
     }
 
     /// Create a new monitoring engine with database support
     pub fn with_database(config: MonitorConfig, database: Arc<database::DatabaseClient>) -> Self {
-        let alert_manager = Arc::new(alerts::AlertManager::new(
-            config.min_alert_severity,
-            config.alert_webhook.clone(),
+        let alert_manager = Arc::new(Self::with_alert_config(
+            &config,
+            alerts::AlertManager::new(config.min_alert_severity, config.alert_webhook.clone())
+                .with_database(database.clone()),
         ));
 
         let connection = Arc::new(connection::ConnectionManager::new(
             config.ws_endpoint.clone(),
         ));
 
+        let mempool = Self::mempool_monitor(&config, &alert_manager);
+        let db_writer = Arc::new(Self::db_writer(&config, database.clone()));
+
         Self {
             config,
             state: Arc::new(RwLock::new(EngineState::default())),
             alert_manager,
             connection,
             database: Some(database),
+            db_writer: Some(db_writer),
+            mempool,
+            backfill_status: Arc::new(RwLock::new(None)),
+            block_source: None,
+            feedback: Arc::new(feedback::FeedbackTracker::new()),
+        }
+    }
+
+    /// Read finalized blocks from `source` instead of the node
+    ///
+    /// The engine then never connects to `ws_endpoint`; event monitoring,
+    /// which needs a node, is skipped.
+    pub fn with_block_source(mut self, source: Arc<dyn block_source::BlockSource>) -> Self {
+        self.block_source = Some(source);
+        self
+    }
+
+    /// Apply the webhook concurrency limit, and enable digest mode, the
+    /// per-caller cooldown and message bus sinks when configured
+    fn with_alert_config(config: &MonitorConfig, manager: alerts::AlertManager) -> alerts::AlertManager {
+        let manager = manager.with_webhook_concurrency(config.webhook_concurrency);
+        let manager = config
+            .alert_sinks()
+            .into_iter()
+            .fold(manager, |manager, sink| manager.with_sink(sink, config.sink_queue_capacity));
+        let manager = match config.alert_digest_interval_secs {
+            Some(secs) => manager.with_digest(std::time::Duration::from_secs(secs)),
+            None => manager,
+        };
+        match config.alert_cooldown_secs {
+            Some(secs) => manager.with_cooldown(std::time::Duration::from_secs(secs)),
+            None => manager,
+        }
+    }
+
+    /// Writer retrying inserts into `database`, deferring records per the config
+    fn db_writer(config: &MonitorConfig, database: Arc<database::DatabaseClient>) -> database::retry::RetryingWriter {
+        let writer = database::retry::RetryingWriter::new(database).with_max_attempts(config.db_write_attempts);
+        match &config.db_dead_letter_file {
+            Some(path) => writer.with_dead_letter_file(path),
+            None => writer,
         }
     }
 
+    /// Mempool monitor that raises correlation alerts through the engine's alert manager
+    fn mempool_monitor(
+        config: &MonitorConfig,
+        alert_manager: &Arc<alerts::AlertManager>,
+    ) -> Arc<mempool::MempoolMonitor> {
+        let mempool = mempool::MempoolMonitor::with_detectors(
+            config.buffer_size,
+            Arc::new(Vec::new()),
+            Some(alert_manager.clone()),
+        )
+        .with_severity_cutoffs(config.severity_cutoffs)
+        .with_max_evidence_entries(config.max_evidence_entries);
+
+        Arc::new(match config.mempool_max_age_secs {
+            Some(secs) => mempool.with_max_age(std::time::Duration::from_secs(secs)),
+            None => mempool,
+        })
+    }
+
     /// Start monitoring the configured chain
     pub async fn start(&self) -> Result<()> {
         tracing::info!("Starting monitoring engine for {}", self.config.chain_name);
+        self.config.validate()?;
 
         let mut state = self.state.write().await;
         if state.is_running {
@@ -298,34 +1112,53 @@ impl MonitoringEngine {
         state.is_running = true;
         drop(state);
 
-        // Connect to the Substrate node with automatic retry
-        let connect_result = if self.config.max_reconnect_attempts > 0 {
-            self.connection.connect_with_retry(self.config.max_reconnect_attempts).await
-        } else {
-            self.connection.connect().await
-        };
+        // Connect to the Substrate node with automatic retry, unless blocks
+        // come from another source
+        if self.block_source.is_none() {
+            let connect_result = if self.config.max_reconnect_attempts > 0 {
+                self.connection.connect_with_retry(self.config.max_reconnect_attempts).await
+            } else {
+                self.connection.connect().await
+            };
+
+            if let Err(e) = connect_result {
+                // Reset is_running flag on connection failure
+                let mut state = self.state.write().await;
+                state.is_running = false;
+                return Err(e);
+            }
+        }
 
-        if let Err(e) = connect_result {
-            // Reset is_running flag on connection failure
-            let mut state = self.state.write().await;
-            state.is_running = false;
-            return Err(e);
+        // Restore persisted alert history
+        if self.database.is_some() {
+            self.alert_manager.load_from_database(ALERT_HISTORY_LOAD_LIMIT).await;
+        }
+
+        if self.alert_manager.start_digest().is_some() {
+            tracing::info!("Alert digest mode enabled");
+        }
+        let sinks = self.alert_manager.start_sinks();
+        if sinks > 0 {
+            tracing::info!("Publishing alerts to {} message bus sink(s)", sinks);
         }
 
         // Initialize detectors
         let detectors = self.initialize_detectors();
 
+        // Resume cross-block detector state from the previous run
+        self.restore_detector_state(&detectors).await;
+        self.restore_feedback().await;
+        self.start_detector_persistence(detectors.clone());
+        self.start_retention();
+        self.start_db_replay();
+
         // Start monitoring tasks
         if self.config.enable_mempool {
-            self.start_mempool_monitoring(detectors.clone()).await?;
+            self.start_mempool_monitoring().await?;
         }
 
         if self.config.enable_blocks {
-            self.start_block_monitoring(detectors.clone()).await?;
-        }
-
-        if self.config.enable_events {
-            self.start_event_monitoring(detectors).await?;
+            self.start_block_monitoring(detectors).await?;
         }
 
         tracing::info!("Monitoring engine started successfully");
@@ -355,6 +1188,126 @@ impl MonitoringEngine {
             blocks_processed: state.blocks_processed,
             transactions_analyzed: state.transactions_analyzed,
             alerts_triggered: state.alerts_triggered,
+            decode_failures: state.decode_failures,
+            db_writes: self
+                .db_writer
+                .as_ref()
+                .map(|writer| writer.stats())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Get build and uptime information for fleet monitoring
+    pub async fn get_info(&self) -> EngineInfo {
+        let state = self.state.read().await;
+        let connection = self.connection.status().await;
+        EngineInfo {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_hash: env!("GIT_HASH").to_string(),
+            started_at: state.started_at_unix,
+            uptime_seconds: state.started_at.elapsed().as_secs(),
+            chains: vec![ConnectedChain {
+                name: self.config.chain_name.clone(),
+                endpoint: connection.endpoint,
+                connected: connection.connected,
+                connected_for_seconds: connection.connected_since.map(|since| since.elapsed().as_secs()),
+                reconnect_count: connection.reconnect_count,
+            }],
+            database_attached: self.database.is_some(),
+        }
+    }
+
+    /// Run a historical block range through the detectors
+    ///
+    /// Progress is checkpointed in the database after every block, so calling
+    /// this again with the same range after an interruption resumes at the
+    /// block after the checkpoint. Mempool correlation is skipped.
+    pub async fn backfill(&self, from_block: u64, to_block: u64) -> Result<backfill::BackfillStatus> {
+        use block_source::BlockSource;
+
+        let client = self.connection.get_client().await
+            .ok_or_else(|| Error::ConnectionError("Not connected to node".to_string()))?;
+
+        let source = &block_source::SubxtBlockSource::new(client).with_events(self.config.enable_events);
+        let detectors = &self.initialize_detectors();
+        let thresholds = &self.alert_thresholds();
+        let sampler = &self.config.sampler();
+        let chain_name = self.config.chain_name.as_str();
+
+        backfill::run_backfill(
+            self.database.as_deref(),
+            chain_name,
+            from_block,
+            to_block,
+            &self.backfill_status,
+            |block_number| async move {
+                let block_hash = self.connection.block_hash(block_number).await?
+                    .ok_or_else(|| Error::ConnectionError(format!("Block #{} not found", block_number)))?;
+                let transactions = source.extract_transactions(block_hash, block_number).await?;
+
+                let mut state_lock = self.state.write().await;
+                state_lock.blocks_processed += 1;
+                state_lock.transactions_analyzed += transactions.len() as u64;
+                drop(state_lock);
+
+                for ctx in transactions {
+                    Self::process_transaction(
+                        ctx,
+                        detectors,
+                        &self.state,
+                        &self.alert_manager,
+                        chain_name,
+                        &self.database,
+                        self.db_writer.as_deref(),
+                        thresholds,
+                        sampler,
+                        self.config.enable_ml_features,
+                    ).await;
+                }
+                Ok(())
+            },
+        )
+        .await
+    }
+
+    /// Get the progress of the latest backfill
+    ///
+    /// Reports the backfill started in this process, falling back to the most
+    /// recent checkpoint stored for the chain.
+    pub async fn backfill_status(&self) -> Option<backfill::BackfillStatus> {
+        if let Some(status) = self.backfill_status.read().await.clone() {
+            return Some(status);
+        }
+
+        let db = self.database.as_ref()?;
+        match db.latest_backfill_checkpoint(&self.config.chain_name).await {
+            Ok(checkpoint) => checkpoint.map(backfill::BackfillStatus::from),
+            Err(e) => {
+                tracing::warn!("Failed to load backfill checkpoint: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Receive every alert raised by the engine from now on
+    ///
+    /// For embedders reacting to alerts in-process instead of polling
+    /// `AlertManager::get_recent_alerts`; see `AlertManager::subscribe` for
+    /// what happens when a receiver falls behind.
+    pub fn subscribe_alerts(&self) -> tokio::sync::broadcast::Receiver<Alert> {
+        self.alert_manager.subscribe()
+    }
+
+    /// Get the confidence histogram of a detector
+    ///
+    /// Returns `None` for unknown detectors, and an empty histogram for known
+    /// detectors that have not scored anything yet.
+    pub async fn get_confidence_histogram(&self, detector_name: &str) -> Option<ConfidenceHistogram> {
+        let state = self.state.read().await;
+        match state.confidence_histograms.get(detector_name) {
+            Some(histogram) => Some(histogram.clone()),
+            None if state.detector_stats.contains_key(detector_name) => Some(ConfidenceHistogram::default()),
+            None => None,
         }
     }
 
@@ -362,7 +1315,7 @@ impl MonitoringEngine {
     pub async fn get_detector_stats(&self) -> AllDetectorStats {
         let state = self.state.read().await;
         let detectors = vec![
-            DetectorStats {
+            DetectorStatus {
                 name: "Flash Loan Detector".to_string(),
                 enabled: true,
                 detections: state.detector_stats.get("Flash Loan Detector")
@@ -371,7 +1324,7 @@ impl MonitoringEngine {
                 last_detection: state.detector_stats.get("Flash Loan Detector")
                     .and_then(|s| s.last_detection),
             },
-            DetectorStats {
+            DetectorStatus {
                 name: "MEV Detector".to_string(),
                 enabled: true,
                 detections: state.detector_stats.get("MEV Detector")
@@ -380,7 +1333,7 @@ impl MonitoringEngine {
                 last_detection: state.detector_stats.get("MEV Detector")
                     .and_then(|s| s.last_detection),
             },
-            DetectorStats {
+            DetectorStatus {
                 name: "Volume Anomaly Detector".to_string(),
                 enabled: true,
                 detections: state.detector_stats.get("Volume Anomaly Detector")
@@ -389,7 +1342,7 @@ impl MonitoringEngine {
                 last_detection: state.detector_stats.get("Volume Anomaly Detector")
                     .and_then(|s| s.last_detection),
             },
-            DetectorStats {
+            DetectorStatus {
                 name: "FrontRunning Detector".to_string(),
                 enabled: true,
                 detections: state.detector_stats.get("FrontRunning Detector")
@@ -398,7 +1351,7 @@ impl MonitoringEngine {
                 last_detection: state.detector_stats.get("FrontRunning Detector")
                     .and_then(|s| s.last_detection),
             },
-            DetectorStats {
+            DetectorStatus {
                 name: "Cross-Chain Bridge Detector".to_string(),
                 enabled: true,
                 detections: state.detector_stats.get("Cross-Chain Bridge Detector")
@@ -407,7 +1360,7 @@ impl MonitoringEngine {
                 last_detection: state.detector_stats.get("Cross-Chain Bridge Detector")
                     .and_then(|s| s.last_detection),
             },
-            DetectorStats {
+            DetectorStatus {
                 name: "State Proof Verification Detector".to_string(),
                 enabled: true,
                 detections: state.detector_stats.get("State Proof Verification Detector")
@@ -421,51 +1374,514 @@ impl MonitoringEngine {
         AllDetectorStats { detectors }
     }
 
+    /// Alert thresholds from the config, raised by analyst feedback
+    pub fn alert_thresholds(&self) -> AlertThresholds {
+        self.config.alert_thresholds().with_feedback(self.feedback.clone())
+    }
+
+    /// Record an analyst's verdict on a detection (its alert ID)
+    ///
+    /// The label shifts the detector's effective alert threshold (see
+    /// [`feedback`]) and is stored in the database, when configured, for
+    /// offline retraining. Returns the detector's new threshold.
+    pub async fn record_feedback(&self, detection_id: &str, was_true_positive: bool) -> Result<f64> {
+        let detector_name = match self
+            .alert_manager
+            .get_alert(detection_id)
+            .await
+            .and_then(|alert| alert.metadata.get("detector").cloned())
+        {
+            Some(name) => name,
+            None => match &self.database {
+                Some(db) => db
+                    .get_detection(detection_id)
+                    .await
+                    .map_err(|e| Error::DatabaseError(e.to_string()))?
+                    .map(|detection| detection.detector_name)
+                    .ok_or_else(|| Error::NotFound(format!("detection {}", detection_id)))?,
+                None => return Err(Error::NotFound(format!("detection {}", detection_id))),
+            },
+        };
+
+        if let Some(db) = &self.database {
+            let label = database::models::DetectionFeedback {
+                detection_id: detection_id.to_string(),
+                detector_name: detector_name.clone(),
+                true_positive: was_true_positive,
+                labeled_at: chrono::Utc::now(),
+            };
+            db.insert_feedback(&label)
+                .await
+                .map_err(|e| Error::DatabaseError(e.to_string()))?;
+        }
+
+        self.feedback.record(detection_id, &detector_name, was_true_positive);
+        let threshold = self.alert_thresholds().threshold_for(&detector_name);
+        tracing::info!(
+            "Detection {} labeled {} positive; {} threshold now {:.2}",
+            detection_id,
+            if was_true_positive { "true" } else { "false" },
+            detector_name,
+            threshold
+        );
+
+        Ok(threshold)
+    }
+
+    /// Manifests of the registered detectors, with their alert thresholds
+    pub fn detector_manifests(&self) -> Vec<detectors::DetectorManifest> {
+        let thresholds = self.alert_thresholds();
+
+        self.initialize_detectors()
+            .iter()
+            .map(|detector| {
+                let mut manifest = detector.manifest();
+                manifest.alert_threshold = (!detector.always_alert())
+                    .then(|| thresholds.threshold_for(detector.name()));
+                manifest
+            })
+            .collect()
+    }
+
+    /// Omnipool detector reading reserves over RPC, and stored pool
+    /// snapshots when a database is configured
+    fn omnipool_detector(&self) -> detectors::OmnipoolManipulationDetector {
+        let detector = detectors::OmnipoolManipulationDetector::new().with_state_provider(
+            Arc::new(detectors::RpcOmnipoolState::new(self.connection.clone())),
+        );
+        match &self.database {
+            Some(db) => detector.with_pool_history(db.clone(), detectors::hydration::DEFAULT_POOL_HISTORY_HOURS),
+            None => detector,
+        }
+    }
+
     /// Initialize attack pattern detectors
     fn initialize_detectors(&self) -> Arc<Vec<Box<dyn detectors::Detector + Send + Sync>>> {
-        let detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> = vec![
-            Box::new(detectors::FlashLoanDetector::new()),
+        let mut detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> = vec![
+            Box::new(detectors::FlashLoanDetector::new().with_large_value_floor(
+                (detectors::flash_loan::DEFAULT_LARGE_VALUE_FLOOR as f64
+                    * self.config.sensitivity.value_floor_scale()) as u128,
+            )),
             Box::new(detectors::MevDetector::new()),
+            Box::new(detectors::FeeAnomalyDetector::new()),
             Box::new(detectors::VolumeAnomalyDetector::new()),
             Box::new(detectors::FrontRunningDetector::new()),
             Box::new(detectors::CrossChainBridgeDetector::new()),
+            Box::new(detectors::BridgeBalanceDetector::new().with_outflow_threshold(
+                (detectors::bridge_balance::DEFAULT_OUTFLOW_THRESHOLD as f64
+                    * self.config.sensitivity.value_floor_scale()) as u128,
+            )),
             Box::new(detectors::StateProofVerificationDetector::new()),
-            Box::new(detectors::OmnipoolManipulationDetector::new()),
-            Box::new(detectors::LiquidityDrainDetector::new()),
+            Box::new(self.omnipool_detector()),
+            Box::new(detectors::LiquidityDrainDetector::new().with_large_withdrawal_floor(
+                detectors::hydration::DEFAULT_LARGE_WITHDRAWAL_FLOOR
+                    * self.config.sensitivity.value_floor_scale(),
+            )),
             Box::new(detectors::CollateralManipulationDetector::new()),
+            Box::new(detectors::LiquidationFrontRunningDetector::new()),
+            Box::new(detectors::NonceAnomalyDetector::new()),
+            Box::new(detectors::RuntimeUpgradeDetector::new()),
+            Box::new(detectors::LayeringDetector::new()),
+            Box::new(detectors::AllowanceAbuseDetector::new().with_large_approval_floor(
+                (detectors::allowance::DEFAULT_LARGE_APPROVAL_FLOOR as f64
+                    * self.config.sensitivity.value_floor_scale()) as u128,
+            )),
+            Box::new(detectors::UnsupervisedAnomalyDetector::new()
+                .with_distance_threshold(self.config.anomaly_distance_threshold)),
+            Box::new(detectors::XcmFanoutDetector::new()),
+            Box::new(detectors::TransactionFingerprintDetector::new()),
         ];
 
+        let mut proxy_detector = detectors::ProxyAbuseDetector::new();
+        if let Some(path) = &self.config.watchlist_file {
+            match detectors::WatchlistDetector::from_file(path) {
+                // The wrapped-call check shares the loaded list and its reloads
+                Ok(watchlist) => {
+                    proxy_detector = proxy_detector.with_watchlist(watchlist.clone());
+                    detectors.push(Box::new(watchlist));
+                }
+                Err(e) => tracing::error!("Watchlist detectors disabled, failed to load {}: {}", path, e),
+            }
+        }
+        detectors.push(Box::new(proxy_detector));
+
+        detectors::sort_by_priority(&mut detectors, &self.config.detector_priorities);
+        for detector in &detectors {
+            self.feedback.set_step(detector.name(), detector.feedback_step());
+        }
         Arc::new(detectors)
     }
 
-    /// Start mempool monitoring
-    async fn start_mempool_monitoring(
-        &self,
-        _detectors: Arc<Vec<Box<dyn detectors::Detector + Send + Sync>>>,
-    ) -> Result<()> {
-        tracing::info!("Starting mempool monitoring");
-        // TODO: Implement mempool monitoring using subxt
-        Ok(())
+    /// Detectors run on pending extrinsics
+    ///
+    /// A separate set from the block pipeline's, without the stateful
+    /// detectors: those must see each transaction once, in block order, and
+    /// pending extrinsics are analyzed again when they are included.
+    fn mempool_detectors(&self) -> Arc<Vec<Box<dyn detectors::Detector + Send + Sync>>> {
+        let mut detectors = Arc::try_unwrap(self.initialize_detectors())
+            .unwrap_or_else(|_| unreachable!("freshly built detector set is not shared"));
+        detectors.retain(|detector| !detector.is_stateful());
+        Arc::new(detectors)
     }
 
-    /// Start block monitoring
-    async fn start_block_monitoring(
-        &self,
-        detectors: Arc<Vec<Box<dyn detectors::Detector + Send + Sync>>>,
-    ) -> Result<()> {
-        tracing::info!("Starting block monitoring");
+    /// Restore detector snapshots from the database or state file
+    async fn restore_detector_state(&self, detectors: &[Box<dyn detectors::Detector + Send + Sync>]) {
+        let snapshots = match Self::load_detector_snapshots(
+            self.database.as_deref(),
+            self.config.detector_state_file.as_deref(),
+        )
+        .await
+        {
+            Ok(snapshots) => snapshots,
+            Err(e) => {
+                tracing::warn!("Failed to load detector state: {}", e);
+                return;
+            }
+        };
 
-        let client = self.connection.get_client().await
-            .ok_or_else(|| Error::ConnectionError("Not connected to node".to_string()))?;
+        if !snapshots.is_empty() {
+            let restored = detectors::restore_detectors(detectors, &snapshots).await;
+            tracing::info!("Restored state for {} detector(s)", restored);
+        }
+    }
+
+    /// Replay stored analyst labels so threshold shifts survive restarts
+    async fn restore_feedback(&self) {
+        let Some(db) = &self.database else {
+            return;
+        };
+
+        match db.get_feedback().await {
+            Ok(labels) => {
+                for label in &labels {
+                    self.feedback.record(&label.detection_id, &label.detector_name, label.true_positive);
+                }
+                if !labels.is_empty() {
+                    tracing::info!("Restored {} detection label(s)", labels.len());
+                }
+            }
+            Err(e) => tracing::warn!("Failed to load detection feedback: {}", e),
+        }
+    }
+
+    /// Periodically persist detector snapshots while the engine is running
+    fn start_detector_persistence(&self, detectors: Arc<Vec<Box<dyn detectors::Detector + Send + Sync>>>) {
+        if self.database.is_none() && self.config.detector_state_file.is_none() {
+            return;
+        }
 
         let state = self.state.clone();
-        let chain_name = self.config.chain_name.clone();
-        let alert_manager = self.alert_manager.clone();
         let database = self.database.clone();
+        let state_file = self.config.detector_state_file.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(DETECTOR_SNAPSHOT_INTERVAL);
+            // The first tick completes immediately; nothing has been learned yet
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                if !state.read().await.is_running {
+                    break;
+                }
+
+                if let Err(e) =
+                    Self::persist_detector_state(&detectors, database.as_deref(), state_file.as_deref()).await
+                {
+                    tracing::warn!("Failed to persist detector state: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Periodically purge rows older than the configured retention
+    fn start_retention(&self) {
+        let (Some(database), Some(days)) = (self.database.clone(), self.config.retention_days) else {
+            return;
+        };
+
+        let state = self.state.clone();
+        tracing::info!("Keeping {} day(s) of data", days);
+
+        tokio::spawn(async move {
+            // The first tick completes immediately, purging on startup
+            let mut interval = tokio::time::interval(RETENTION_INTERVAL);
+
+            loop {
+                interval.tick().await;
+                if !state.read().await.is_running {
+                    break;
+                }
+
+                if let Err(e) = database.apply_retention(days as i32).await {
+                    tracing::warn!("Failed to apply data retention: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Periodically replay database records deferred while inserts failed
+    fn start_db_replay(&self) {
+        let Some(writer) = self.db_writer.clone() else {
+            return;
+        };
+
+        let state = self.state.clone();
+
+        tokio::spawn(async move {
+            // The first tick completes immediately, replaying records
+            // deferred to the dead-letter file by a previous run
+            let mut interval = tokio::time::interval(DB_REPLAY_INTERVAL);
+
+            loop {
+                interval.tick().await;
+                if !state.read().await.is_running {
+                    break;
+                }
+
+                if let Err(e) = writer.replay().await {
+                    tracing::warn!("Failed to replay deferred database records: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Load snapshots, preferring the database over the state file
+    async fn load_detector_snapshots(
+        database: Option<&database::DatabaseClient>,
+        state_file: Option<&str>,
+    ) -> anyhow::Result<Vec<detectors::DetectorSnapshot>> {
+        if let Some(db) = database {
+            return db.load_detector_snapshots().await;
+        }
+
+        let Some(path) = state_file else {
+            return Ok(Vec::new());
+        };
+
+        match tokio::fs::read(path).await {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Save snapshots to the database, or to the state file without one
+    async fn persist_detector_state(
+        detectors: &[Box<dyn detectors::Detector + Send + Sync>],
+        database: Option<&database::DatabaseClient>,
+        state_file: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let snapshots = detectors::snapshot_detectors(detectors).await;
+        if snapshots.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(db) = database {
+            for snapshot in &snapshots {
+                db.save_detector_snapshot(snapshot).await?;
+            }
+        } else if let Some(path) = state_file {
+            // Write then rename so a crash never leaves a truncated file
+            let tmp = format!("{}.tmp", path);
+            tokio::fs::write(&tmp, serde_json::to_vec(&snapshots)?).await?;
+            tokio::fs::rename(&tmp, path).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Start mempool monitoring
+    ///
+    /// Polls the node's transaction pool every `MEMPOOL_POLL_INTERVAL` until
+    /// the engine stops, analyzing pending extrinsics with
+    /// [`Self::mempool_detectors`]. Other block sources have no pool, so
+    /// nothing is started for them.
+    async fn start_mempool_monitoring(&self) -> Result<()> {
+        if self.block_source.is_some() {
+            tracing::info!("Mempool monitoring needs a node connection, skipping");
+            return Ok(());
+        }
+
+        tracing::info!("Starting mempool monitoring");
+
+        let client = self.connection.get_client().await
+            .ok_or_else(|| Error::ConnectionError("Not connected to node".to_string()))?;
+        let extractor = transaction::TransactionExtractor::new(Arc::new(client));
+        let connection = self.connection.clone();
+
+        let pipeline = MempoolPipeline {
+            state: self.state.clone(),
+            chain_name: self.config.chain_name.clone(),
+            detectors: self.mempool_detectors(),
+            alert_manager: self.alert_manager.clone(),
+            thresholds: Arc::new(self.alert_thresholds()),
+            mempool: self.mempool.clone(),
+            sampler: self.config.sampler(),
+        };
+
+        tokio::spawn(async move {
+            let extractor = &extractor;
+            let connection = &connection;
+            Self::poll_mempool(
+                || async move {
+                    let pending = connection.pending_extrinsics().await?;
+                    Ok(pending
+                        .iter()
+                        .filter_map(|bytes| match extractor.parse_pending(bytes) {
+                            Ok(tx) => Some(tx),
+                            Err(e) => {
+                                tracing::debug!("Skipping undecodable pending extrinsic: {}", e);
+                                None
+                            }
+                        })
+                        .collect())
+                },
+                &pipeline,
+                MEMPOOL_POLL_INTERVAL,
+            )
+            .await;
+        });
+
+        Ok(())
+    }
+
+    /// Run the transactions returned by `poll` through the mempool pipeline
+    /// every `period`, until the engine stops
+    async fn poll_mempool<F, Fut>(poll: F, pipeline: &MempoolPipeline, period: std::time::Duration)
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<ParsedTransaction>>>,
+    {
+        let mut interval = tokio::time::interval(period);
+
+        loop {
+            interval.tick().await;
+            if !pipeline.state.read().await.is_running {
+                break;
+            }
+
+            match poll().await {
+                Ok(pending) => {
+                    for tx in pending {
+                        Self::process_pending(tx, pipeline).await;
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to poll the transaction pool: {}", e),
+            }
+        }
+    }
+
+    /// Analyze an extrinsic seen in the transaction pool
+    ///
+    /// Each extrinsic is analyzed once, when first seen. Detections alert
+    /// as they do for block transactions, but nothing is written to the
+    /// database since the extrinsic may never be included. Pending alerts
+    /// have no block number and are marked `pending` in their metadata;
+    /// they share the detection id of the included extrinsic, so its block
+    /// does not alert again.
+    async fn process_pending(tx: ParsedTransaction, pipeline: &MempoolPipeline) {
+        if !pipeline.mempool.observe_pending(&tx).await {
+            return;
+        }
+
+        pipeline
+            .mempool
+            .add_transaction(Transaction {
+                hash: tx.hash.clone(),
+                block_number: tx.block_number,
+                from: tx.caller.clone(),
+                to: tx.transfer_destination().map(|dest| format!("0x{}", hex::encode(dest))),
+                value: tx.transfer_amount().unwrap_or(0).to_string(),
+                data: tx.args.clone(),
+                gas_used: None,
+                success: tx.success,
+                timestamp: tx.timestamp,
+            })
+            .await;
+
+        let ctx = TransactionContext {
+            transaction: tx,
+            events: vec![],
+            state_changes: vec![],
+        };
+
+        let thresholds = &pipeline.thresholds;
+        let AnalyzedTransaction { ctx, results } = Self::analyze_with_detectors(
+            ctx,
+            &pipeline.detectors,
+            thresholds.max_evidence_entries(),
+            thresholds.short_circuit_privileged(),
+            &pipeline.sampler,
+        )
+        .await;
+        let tx = &ctx.transaction;
+
+        let log_limiter = pipeline.state.read().await.log_limiter.clone();
+        for (detector, result) in pipeline.detectors.iter().zip(results) {
+            if Self::detection_outcome(detector.as_ref(), &result, thresholds) != DetectionOutcome::Alert {
+                continue;
+            }
+
+            let detector_name = detector.name();
+            detection_log::log_detection(&log_limiter, detector_name, tx, &result);
+            Self::count_detection(&pipeline.state, detector_name).await;
+
+            let mut alert = Self::detection_alert(
+                detection_id(&pipeline.chain_name, tx, detector_name),
+                tx,
+                detector.as_ref(),
+                &result,
+                thresholds.severity_for(result.confidence),
+                &pipeline.chain_name,
+            );
+            alert.block_number = None;
+            alert.metadata.insert("pending".to_string(), "true".to_string());
+
+            pipeline.alert_manager.trigger_alert(alert).await;
+        }
+    }
+
+    /// Start block monitoring
+    async fn start_block_monitoring(
+        &self,
+        detectors: Arc<Vec<Box<dyn detectors::Detector + Send + Sync>>>,
+    ) -> Result<()> {
+        tracing::info!("Starting block monitoring");
+
+        let source: Arc<dyn block_source::BlockSource> = match &self.block_source {
+            Some(source) => source.clone(),
+            None => {
+                let client = self.connection.get_client().await
+                    .ok_or_else(|| Error::ConnectionError("Not connected to node".to_string()))?;
+                Arc::new(block_source::SubxtBlockSource::new(client).with_events(self.config.enable_events))
+            }
+        };
+
+        let pipeline = BlockPipeline {
+            state: self.state.clone(),
+            chain_name: self.config.chain_name.clone(),
+            detectors,
+            alert_manager: self.alert_manager.clone(),
+            database: self.database.clone(),
+            db_writer: self.db_writer.clone(),
+            thresholds: Arc::new(self.alert_thresholds()),
+            enable_ml_features: self.config.enable_ml_features,
+            mempool: self.mempool.clone(),
+            parallelism: self.config.block_parallelism,
+            buffer_size: self.config.buffer_size,
+            sampler: self.config.sampler(),
+            dead_letters: self
+                .config
+                .dead_letter_file
+                .as_ref()
+                .map(|path| Arc::new(dead_letter::DeadLetterStore::new(path))),
+            raw_blocks: Some(source.clone()),
+        };
 
         // Spawn background task for block subscription
         tokio::spawn(async move {
-            match Self::subscribe_to_blocks(client, state, chain_name, detectors, alert_manager, database).await {
+            match Self::subscribe_to_blocks(source, pipeline).await {
                 Ok(_) => tracing::info!("Block subscription ended"),
                 Err(e) => tracing::error!("Block subscription error: {}", e),
             }
@@ -476,100 +1892,280 @@ impl MonitoringEngine {
 
     /// Subscribe to finalized blocks
     async fn subscribe_to_blocks(
-        client: subxt::OnlineClient<subxt::PolkadotConfig>,
-        state: Arc<RwLock<EngineState>>,
-        chain_name: String,
-        detectors: Arc<Vec<Box<dyn detectors::Detector + Send + Sync>>>,
-        alert_manager: Arc<alerts::AlertManager>,
-        database: Option<Arc<database::DatabaseClient>>,
+        source: Arc<dyn block_source::BlockSource>,
+        pipeline: BlockPipeline,
     ) -> Result<()> {
-        tracing::info!("Subscribing to finalized blocks on {}", chain_name);
+        tracing::info!("Subscribing to finalized blocks on {}", pipeline.chain_name);
 
-        // Create transaction extractor
-        let extractor = Arc::new(transaction::TransactionExtractor::new(Arc::new(client.clone())));
+        let source = source.as_ref();
+        let blocks = source.subscribe_finalized().await?;
 
-        let mut blocks_sub = client
-            .blocks()
-            .subscribe_finalized()
-            .await
-            .map_err(|e| Error::SubscriptionError(format!("Failed to subscribe to blocks: {}", e)))?;
-
-        while let Some(block_result) = blocks_sub.next().await {
-            match block_result {
-                Ok(block) => {
-                    let block_number = block.number();
-                    let block_hash = block.hash();
-
-                    tracing::info!(
-                        "Processing block #{} (hash: 0x{}) on {}",
-                        block_number,
-                        hex::encode(&block_hash.0[..8]),
-                        chain_name
-                    );
+        Self::process_block_stream(
+            blocks,
+            move |block_hash, block_number| source.extract_transactions(block_hash, block_number),
+            &pipeline,
+        )
+        .await
+    }
 
-                    // Update block statistics
-                    let mut state_lock = state.write().await;
-                    state_lock.blocks_processed += 1;
-                    drop(state_lock);
-
-                    // Extract transactions from block
-                    match extractor.extract_from_block(block_hash, block.number() as u64).await {
-                        Ok(transactions) => {
-                            if !transactions.is_empty() {
-                                tracing::info!(
-                                    "Extracted {} transactions from block #{}",
-                                    transactions.len(),
-                                    block_number
-                                );
-
-                                // Update transaction statistics
-                                let mut state_lock = state.write().await;
-                                state_lock.transactions_analyzed += transactions.len() as u64;
-                                drop(state_lock);
-
-                                // Process each transaction through detectors
-                                for tx in transactions {
-                                    Self::process_transaction(
-                                        tx,
-                                        &detectors,
-                                        &state,
-                                        &alert_manager,
-                                        &chain_name,
-                                        &database
-                                    ).await;
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            tracing::warn!(
-                                "Failed to extract transactions from block #{}: {}",
-                                block_number,
-                                e
+    /// Run a stream of `(block number, block hash)` through the detectors
+    ///
+    /// Extraction runs for up to `pipeline.parallelism` blocks at once.
+    /// Detectors keep state across blocks (baselines, per-block counts), so
+    /// analysis and commit (statistics, database rows, alerts) handle one
+    /// block at a time, strictly in stream order. Extracted blocks wait in a
+    /// queue of `pipeline.buffer_size` blocks; when analysis falls behind,
+    /// extraction pauses instead of buffering without bound.
+    async fn process_block_stream<S, F, Fut>(
+        blocks: S,
+        extract: F,
+        pipeline: &BlockPipeline,
+    ) -> Result<()>
+    where
+        S: futures::Stream<Item = Result<(u64, H256)>>,
+        F: Fn(H256, u64) -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<TransactionContext>>>,
+    {
+        let extract = &extract;
+        let parallelism = pipeline.parallelism.max(1);
+        let max_evidence_entries = pipeline.thresholds.max_evidence_entries();
+        let (sender, receiver) = tokio::sync::mpsc::channel(pipeline.buffer_size.max(1));
+
+        let extraction = async move {
+            let extracted = blocks
+                .map(|block| async move {
+                    let (number, hash) = block?;
+                    Ok::<_, Error>((number, hash, extract(hash, number).await))
+                })
+                .buffered(parallelism);
+            futures::pin_mut!(extracted);
+
+            while let Some(block) = extracted.next().await {
+                let failed = block.is_err();
+                // A closed queue means analysis stopped on an error
+                if sender.send(block).await.is_err() || failed {
+                    break;
+                }
+            }
+        };
+
+        let analysis = async move {
+            let mut receiver = receiver;
+
+            while let Some(block) = receiver.recv().await {
+                let (number, hash, transactions) = match block {
+                    Ok(block) => block,
+                    Err(e) => {
+                        tracing::error!("Error receiving block: {}", e);
+                        return Err(e);
+                    }
+                };
+
+                let transactions = match transactions {
+                    Ok(transactions) => {
+                        let mut analyzed = Vec::with_capacity(transactions.len());
+                        for ctx in transactions {
+                            let result = Self::analyze_with_detectors(
+                                ctx,
+                                &pipeline.detectors,
+                                max_evidence_entries,
+                                pipeline.thresholds.short_circuit_privileged(),
+                                &pipeline.sampler,
                             );
+                            analyzed.push(result.await);
                         }
+                        Ok(analyzed)
                     }
-                }
-                Err(e) => {
-                    tracing::error!("Error receiving block: {}", e);
-                    return Err(Error::SubscriptionError(format!("Block stream error: {}", e)));
-                }
+                    Err(e) => Err(e),
+                };
+
+                Self::commit_block(AnalyzedBlock { number, hash, transactions }, pipeline).await;
             }
+
+            Ok(())
+        };
+
+        let ((), result) = futures::join!(extraction, analysis);
+        result
+    }
+
+    /// Count a block that failed extraction and write its dead letter
+    async fn record_decode_failure(number: u64, hash: H256, error: &Error, pipeline: &BlockPipeline) {
+        pipeline.state.write().await.decode_failures += 1;
+
+        let Some(store) = &pipeline.dead_letters else {
+            return;
+        };
+
+        let extrinsics = match &pipeline.raw_blocks {
+            Some(raw_blocks) => raw_blocks.raw_extrinsics(hash).await.unwrap_or_else(|e| {
+                tracing::warn!("Failed to fetch raw extrinsics of block #{}: {}", number, e);
+                Vec::new()
+            }),
+            None => Vec::new(),
+        };
+
+        let letter = dead_letter::DeadLetter::new(&pipeline.chain_name, number, &hash.0, error, &extrinsics);
+        if let Err(e) = store.record(&letter).await {
+            tracing::error!(
+                "Failed to write dead letter for block #{} to {}: {}",
+                number,
+                store.path().display(),
+                e
+            );
         }
+    }
 
-        Ok(())
+    /// Record an analyzed block and its transactions
+    async fn commit_block(block: AnalyzedBlock, pipeline: &BlockPipeline) {
+        tracing::info!(
+            "Processing block #{} (hash: 0x{}) on {}",
+            block.number,
+            hex::encode(&block.hash.0[..8]),
+            pipeline.chain_name
+        );
+
+        // Update block statistics
+        pipeline.state.write().await.blocks_processed += 1;
+
+        let transactions = match block.transactions {
+            Ok(transactions) => transactions,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to extract transactions from block #{}: {}",
+                    block.number,
+                    e
+                );
+                Self::record_decode_failure(block.number, block.hash, &e, pipeline).await;
+                return;
+            }
+        };
+        if transactions.is_empty() {
+            return;
+        }
+
+        tracing::info!(
+            "Extracted {} transactions from block #{}",
+            transactions.len(),
+            block.number
+        );
+
+        // Update transaction statistics
+        pipeline.state.write().await.transactions_analyzed += transactions.len() as u64;
+
+        // Match against transactions seen pending in the mempool
+        let parsed: Vec<ParsedTransaction> =
+            transactions.iter().map(|analyzed| analyzed.ctx.transaction.clone()).collect();
+        let front_runs = pipeline.mempool.correlate_block(&parsed).await;
+        if !front_runs.is_empty() {
+            tracing::warn!(
+                "Correlated {} front-running transaction(s) in block #{}",
+                front_runs.len(),
+                block.number
+            );
+        }
+
+        for analyzed in transactions {
+            Self::commit_transaction(
+                analyzed,
+                &pipeline.detectors,
+                &pipeline.state,
+                &pipeline.alert_manager,
+                &pipeline.chain_name,
+                &pipeline.database,
+                pipeline.db_writer.as_deref(),
+                &pipeline.thresholds,
+                pipeline.enable_ml_features,
+            )
+            .await;
+        }
     }
 
     /// Process a transaction through all detectors
     async fn process_transaction(
-        tx: ParsedTransaction,
+        ctx: TransactionContext,
+        detectors: &[Box<dyn detectors::Detector + Send + Sync>],
+        state: &Arc<RwLock<EngineState>>,
+        alert_manager: &Arc<alerts::AlertManager>,
+        chain_name: &str,
+        database: &Option<Arc<database::DatabaseClient>>,
+        db_writer: Option<&database::retry::RetryingWriter>,
+        thresholds: &AlertThresholds,
+        sampler: &sampling::TransactionSampler,
+        enable_ml_features: bool,
+    ) {
+        let analyzed = Self::analyze_with_detectors(
+            ctx,
+            detectors,
+            thresholds.max_evidence_entries(),
+            thresholds.short_circuit_privileged(),
+            sampler,
+        )
+        .await;
+        Self::commit_transaction(
+            analyzed,
+            detectors,
+            state,
+            alert_manager,
+            chain_name,
+            database,
+            db_writer,
+            thresholds,
+            enable_ml_features,
+        )
+        .await;
+    }
+
+    /// Run every detector over a transaction without recording anything
+    ///
+    /// Transactions the sampler skips get no detector results. With
+    /// `short_circuit` set, detectors after the first privileged detection
+    /// are not run.
+    async fn analyze_with_detectors(
+        ctx: TransactionContext,
+        detectors: &[Box<dyn detectors::Detector + Send + Sync>],
+        max_evidence_entries: usize,
+        short_circuit: bool,
+        sampler: &sampling::TransactionSampler,
+    ) -> AnalyzedTransaction {
+        if !sampler.should_analyze(&ctx.transaction) {
+            return AnalyzedTransaction { ctx, results: Vec::new() };
+        }
+
+        let mut results = Vec::with_capacity(detectors.len());
+        for detector in detectors {
+            let mut result = detector.analyze_transaction(&ctx).await;
+            result.truncate_evidence(max_evidence_entries);
+            let privileged = result.detected && result.pattern.is_privileged();
+            results.push(result);
+
+            if short_circuit && privileged {
+                break;
+            }
+        }
+
+        AnalyzedTransaction { ctx, results }
+    }
+
+    /// Record an analyzed transaction: database rows, statistics and alerts
+    ///
+    /// `detectors` must be the list the transaction was analyzed with.
+    async fn commit_transaction(
+        analyzed: AnalyzedTransaction,
         detectors: &[Box<dyn detectors::Detector + Send + Sync>],
         state: &Arc<RwLock<EngineState>>,
         alert_manager: &Arc<alerts::AlertManager>,
         chain_name: &str,
         database: &Option<Arc<database::DatabaseClient>>,
+        db_writer: Option<&database::retry::RetryingWriter>,
+        thresholds: &AlertThresholds,
+        enable_ml_features: bool,
     ) {
+        let AnalyzedTransaction { ctx, results } = analyzed;
+        let tx = &ctx.transaction;
+
         // Store transaction in database if available
-        if let Some(db) = database {
+        if let Some(writer) = db_writer {
             // Convert args bytes to JSON Value
             let args_json = if !tx.args.is_empty() {
                 match serde_json::from_slice::<serde_json::Value>(&tx.args) {
@@ -597,214 +2193,223 @@ impl MonitoringEngine {
                 args: args_json,
                 gas_used: None,  // TODO: Extract from transaction
                 fee_paid: None,  // TODO: Extract from transaction
+                destination: tx.transfer_destination().map(|dest| {
+                    use subxt::ext::sp_core::crypto::{AccountId32, Ss58Codec};
+                    AccountId32::from(dest).to_ss58check()
+                }),
+                value: tx.transfer_amount().map(|amount| amount.to_string()),
             };
 
-            if let Err(e) = db.insert_transaction(&db_tx).await {
-                tracing::warn!("Failed to store transaction in database: {}", e);
-            }
+            // Failures are retried, then deferred for replay
+            writer.write(database::retry::PendingRecord::Transaction(db_tx)).await;
         }
 
-        // Create transaction context (simplified - no events/state changes for now)
-        let ctx = TransactionContext {
-            transaction: tx.clone(),
-            events: vec![],
-            state_changes: vec![],
-        };
-
-        // Extract ML features and store in database
-        if database.is_some() {
-            let mut state_lock = state.write().await;
-            let features = state_lock.feature_extractor.extract_features(&ctx);
-            drop(state_lock);
-
+        // Extract ML features and store in database (opt-in)
+        if enable_ml_features {
             if let Some(db) = database {
+                let mut state_lock = state.write().await;
+                let features = state_lock.feature_extractor.extract_features(&ctx);
+                drop(state_lock);
+
                 if let Err(e) = db.insert_ml_features(&features).await {
                     tracing::warn!("Failed to store ML features in database: {}", e);
                 }
             }
         }
 
-        // Run all detectors
-        for detector in detectors {
-            let result = detector.analyze_transaction(&ctx).await;
-
-            if result.detected && result.confidence > 0.5 {
-                let detector_name = detector.name();
-                tracing::warn!(
-                    "🚨 {} detected suspicious activity in tx {}",
-                    detector_name,
-                    tx.hash
-                );
-                tracing::warn!("   Confidence: {:.2}%", result.confidence * 100.0);
-                tracing::warn!("   Description: {}", result.description);
-                tracing::warn!("   Evidence: {:?}", result.evidence);
-
-                // Determine severity based on confidence
-                let severity = if result.confidence >= 0.9 {
-                    AlertSeverity::Critical
-                } else if result.confidence >= 0.75 {
-                    AlertSeverity::High
-                } else if result.confidence >= 0.6 {
-                    AlertSeverity::Medium
-                } else {
-                    AlertSeverity::Low
-                };
+        // Record detector results
+        let log_limiter = state.read().await.log_limiter.clone();
+        for (detector, result) in detectors.iter().zip(results) {
+            // Record every scored result, alerting or not, for threshold tuning
+            if result.detected || result.confidence > 0.0 {
+                state
+                    .write()
+                    .await
+                    .confidence_histograms
+                    .entry(detector.name().to_string())
+                    .or_default()
+                    .record(result.confidence);
+            }
 
-                // Update detector statistics
-                let mut state_lock = state.write().await;
-                if let Some(detector_stat) = state_lock.detector_stats.get_mut(detector_name) {
-                    detector_stat.detections += 1;
-                    detector_stat.last_detection = Some(
-                        std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap()
-                            .as_secs()
+            let outcome = Self::detection_outcome(detector.as_ref(), &result, thresholds);
+            if outcome == DetectionOutcome::Record {
+                // Near miss: kept for threshold tuning and ML labels, never alerted
+                if let Some(writer) = db_writer {
+                    let detection = Self::detection_record(
+                        &detection_id(chain_name, tx, detector.name()),
+                        tx,
+                        detector.as_ref(),
+                        &result,
+                        thresholds.severity_for(result.confidence),
+                        false,
                     );
+                    writer.write(database::retry::PendingRecord::Detection(detection)).await;
                 }
-                state_lock.alerts_triggered += 1;
-                drop(state_lock);
-
-                // Create recommended actions from evidence
-                let recommended_actions = if !result.evidence.is_empty() {
-                    vec![
-                        "Review transaction details and evidence".to_string(),
-                        format!("Investigate pattern: {}", result.pattern),
-                        "Monitor related transactions from same sender".to_string(),
-                    ]
-                } else {
-                    vec!["Review transaction for suspicious activity".to_string()]
-                };
-
-                // Create and trigger alert
-                let alert_id = uuid::Uuid::new_v4().to_string();
-                let alert = Alert {
-                    id: alert_id.clone(),
-                    timestamp: std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs(),
-                    severity: severity.clone(),
-                    pattern: result.pattern.clone(),
-                    description: result.description.clone(),
-                    transaction_hash: Some(tx.hash.clone()),
-                    block_number: Some(tx.block_number),
-                    chain: chain_name.to_string(),
-                    metadata: std::collections::HashMap::new(),
-                    recommended_actions,
-                    acknowledged: false,
-                };
+            } else if outcome == DetectionOutcome::Alert {
+                let detector_name = detector.name();
+                detection_log::log_detection(&log_limiter, detector_name, tx, &result);
+
+                let severity = thresholds.severity_for(result.confidence);
+                let alert_id = detection_id(chain_name, tx, detector_name);
+
+                // Store detection in database if available; an id that is
+                // already stored means this block was processed before. A
+                // deferred detection is still alerted
+                if let Some(writer) = db_writer {
+                    let detection = Self::detection_record(
+                        &alert_id,
+                        tx,
+                        detector.as_ref(),
+                        &result,
+                        severity,
+                        true,
+                    );
 
-                // Store detection in database if available
-                if let Some(db) = database {
-                    let detection = database::models::Detection {
-                        timestamp: chrono::Utc::now(),
-                        detection_id: alert_id.clone(),
-                        tx_hash: tx.hash.clone(),
-                        detector_name: detector_name.to_string(),
-                        attack_pattern: result.pattern.clone().to_string(),
-                        confidence: result.confidence,
-                        severity: match severity {
-                            AlertSeverity::Critical => "critical",
-                            AlertSeverity::High => "high",
-                            AlertSeverity::Medium => "medium",
-                            AlertSeverity::Low => "low",
-                        }.to_string(),
-                        description: Some(result.description.clone()),
-                        evidence: Some(serde_json::json!(result.evidence)),
-                        metadata: None,
-                        acknowledged: false,
-                    };
-
-                    if let Err(e) = db.insert_detection(&detection).await {
-                        tracing::warn!("Failed to store detection in database: {}", e);
+                    let outcome = writer.write(database::retry::PendingRecord::Detection(detection)).await;
+                    if outcome == database::retry::WriteOutcome::Stored(false) {
+                        tracing::debug!("Detection {} already stored, skipping alert", alert_id);
+                        continue;
                     }
                 }
 
+                // Already alerted while the extrinsic was pending
+                if alert_manager.get_alert(&alert_id).await.is_some() {
+                    tracing::debug!("Detection {} already alerted from the mempool", alert_id);
+                    continue;
+                }
+
+                Self::count_detection(state, detector_name).await;
+
+                let alert = Self::detection_alert(alert_id, tx, detector.as_ref(), &result, severity, chain_name);
                 alert_manager.trigger_alert(alert).await;
             }
         }
     }
 
-    /// Start event monitoring
-    async fn start_event_monitoring(
-        &self,
-        _detectors: Arc<Vec<Box<dyn detectors::Detector + Send + Sync>>>,
-    ) -> Result<()> {
-        tracing::info!("Starting event monitoring");
-
-        let client = self.connection.get_client().await
-            .ok_or_else(|| Error::ConnectionError("Not connected to node".to_string()))?;
+    /// Count an alerted detection in the detector and engine statistics
+    async fn count_detection(state: &RwLock<EngineState>, detector_name: &str) {
+        let mut state_lock = state.write().await;
+        if let Some(detector_stat) = state_lock.detector_stats.get_mut(detector_name) {
+            detector_stat.detections += 1;
+            detector_stat.last_detection = Some(
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs()
+            );
+        }
+        state_lock.alerts_triggered += 1;
+    }
 
-        let chain_name = self.config.chain_name.clone();
+    /// Build the alert for a detection
+    fn detection_alert(
+        alert_id: String,
+        tx: &ParsedTransaction,
+        detector: &dyn detectors::Detector,
+        result: &DetectionResult,
+        severity: AlertSeverity,
+        chain_name: &str,
+    ) -> Alert {
+        // Create recommended actions from evidence
+        let recommended_actions = if !result.evidence.is_empty() {
+            vec![
+                "Review transaction details and evidence".to_string(),
+                format!("Investigate pattern: {}", result.pattern),
+                "Monitor related transactions from same sender".to_string(),
+            ]
+        } else {
+            vec!["Review transaction for suspicious activity".to_string()]
+        };
 
-        // Spawn background task for event subscription
-        tokio::spawn(async move {
-            match Self::subscribe_to_events(client, chain_name).await {
-                Ok(_) => tracing::info!("Event subscription ended"),
-                Err(e) => tracing::error!("Event subscription error: {}", e),
-            }
-        });
+        let mut metadata = std::collections::HashMap::from([
+            ("detector".to_string(), detector.name().to_string()),
+            ("detector_version".to_string(), detector.version().to_string()),
+            ("caller".to_string(), tx.caller.clone()),
+            ("cwe_ids".to_string(), result.pattern.cwe_labels().join(",")),
+            ("references".to_string(), result.pattern.references().join(",")),
+        ]);
+        // Indices into the transaction's events/state changes, e.g. "1,3"
+        if !result.triggering_events.is_empty() {
+            metadata.insert("triggering_events".to_string(), join_indices(&result.triggering_events));
+        }
+        if !result.triggering_state_changes.is_empty() {
+            metadata.insert(
+                "triggering_state_changes".to_string(),
+                join_indices(&result.triggering_state_changes),
+            );
+        }
 
-        Ok(())
+        Alert {
+            schema_version: SCHEMA_VERSION,
+            id: alert_id,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            severity,
+            pattern: result.pattern.clone(),
+            description: result.description.clone(),
+            transaction_hash: Some(tx.hash.clone()),
+            block_number: Some(tx.block_number),
+            chain: chain_name.to_string(),
+            metadata,
+            recommended_actions,
+            acknowledged: false,
+        }
     }
 
-    /// Subscribe to runtime events
-    async fn subscribe_to_events(
-        client: subxt::OnlineClient<subxt::PolkadotConfig>,
-        chain_name: String,
-    ) -> Result<()> {
-        tracing::info!("Subscribing to runtime events on {}", chain_name);
-
-        let mut blocks_sub = client
-            .blocks()
-            .subscribe_finalized()
-            .await
-            .map_err(|e| Error::SubscriptionError(format!("Failed to subscribe to blocks for events: {}", e)))?;
-
-        while let Some(block_result) = blocks_sub.next().await {
-            match block_result {
-                Ok(block) => {
-                    let block_number = block.number();
-
-                    // Get events from this block
-                    match block.events().await {
-                        Ok(events) => {
-                            let event_count = events.iter().count();
-
-                            if event_count > 0 {
-                                tracing::debug!(
-                                    "Block #{} on {} contains {} events",
-                                    block_number,
-                                    chain_name,
-                                    event_count
-                                );
-                            }
-
-                            // TODO: Process events and pass to detectors
-                            // for event in events.iter() {
-                            //     match event {
-                            //         Ok(event_details) => {
-                            //             // Process event
-                            //         }
-                            //         Err(e) => {
-                            //             tracing::warn!("Error decoding event: {}", e);
-                            //         }
-                            //     }
-                            // }
-                        }
-                        Err(e) => {
-                            tracing::error!("Error fetching events for block #{}: {}", block_number, e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("Error receiving block for events: {}", e);
-                    return Err(Error::SubscriptionError(format!("Event stream error: {}", e)));
-                }
-            }
+    /// Decide whether a detector result alerts, is stored as a near miss, or is dropped
+    fn detection_outcome(
+        detector: &dyn detectors::Detector,
+        result: &DetectionResult,
+        thresholds: &AlertThresholds,
+    ) -> DetectionOutcome {
+        let should_alert = detector.always_alert()
+            || thresholds.should_alert(detector.name(), result.confidence);
+
+        if result.detected && should_alert {
+            DetectionOutcome::Alert
+        } else if thresholds.should_record(result.confidence) {
+            DetectionOutcome::Record
+        } else {
+            DetectionOutcome::Drop
         }
+    }
 
-        Ok(())
+    /// Build the database record for a detection
+    fn detection_record(
+        alert_id: &str,
+        tx: &ParsedTransaction,
+        detector: &dyn detectors::Detector,
+        result: &DetectionResult,
+        severity: AlertSeverity,
+        alerted: bool,
+    ) -> database::models::Detection {
+        database::models::Detection {
+            timestamp: chrono::Utc::now(),
+            detection_id: alert_id.to_string(),
+            tx_hash: tx.hash.clone(),
+            detector_name: detector.name().to_string(),
+            detector_version: detector.version().to_string(),
+            attack_pattern: result.pattern.to_string(),
+            confidence: result.confidence,
+            severity: match severity {
+                AlertSeverity::Critical => "critical",
+                AlertSeverity::High => "high",
+                AlertSeverity::Medium => "medium",
+                AlertSeverity::Low => "low",
+            }.to_string(),
+            description: Some(result.description.clone()),
+            evidence: Some(serde_json::json!(result.evidence)),
+            metadata: Some(serde_json::json!({
+                "cwe_ids": result.pattern.cwe_labels(),
+                "references": result.pattern.references(),
+                "triggering_events": result.triggering_events,
+                "triggering_state_changes": result.triggering_state_changes,
+            })),
+            acknowledged: false,
+            alerted,
+        }
     }
 }
 
@@ -815,11 +2420,48 @@ pub struct EngineStats {
     pub blocks_processed: u64,
     pub transactions_analyzed: u64,
     pub alerts_triggered: u64,
+    /// Blocks whose transactions could not be extracted
+    pub decode_failures: u64,
+    /// Retried, deferred and dropped database inserts
+    #[serde(default)]
+    pub db_writes: database::retry::WriteStats,
+}
+
+/// Engine build and uptime information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineInfo {
+    /// Crate version
+    pub version: String,
+    /// Short git commit hash the engine was built from ("unknown" outside a checkout)
+    pub git_hash: String,
+    /// Engine start time (Unix seconds)
+    pub started_at: u64,
+    pub uptime_seconds: u64,
+    /// Chains the engine monitors
+    pub chains: Vec<ConnectedChain>,
+    /// Whether a database is attached for persistence and analytics
+    pub database_attached: bool,
+}
+
+/// A chain monitored by the engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectedChain {
+    pub name: String,
+    pub endpoint: String,
+    /// Whether the WebSocket connection is currently up
+    pub connected: bool,
+    /// Age of the current connection
+    pub connected_for_seconds: Option<u64>,
+    /// Times the connection was re-established since startup
+    pub reconnect_count: u64,
 }
 
-/// Statistics for a specific detector
+/// Live status of a specific detector in this engine
+///
+/// Historical per-detector aggregates from the database are
+/// [`database::models::DetectorAggregateStats`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DetectorStats {
+pub struct DetectorStatus {
     pub name: String,
     pub enabled: bool,
     pub detections: u64,
@@ -829,7 +2471,7 @@ pub struct DetectorStats {
 /// Collection of all detector statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AllDetectorStats {
-    pub detectors: Vec<DetectorStats>,
+    pub detectors: Vec<DetectorStatus>,
 }
 
 #[cfg(test)]
@@ -846,6 +2488,71 @@ mod tests {
         assert_eq!(stats.blocks_processed, 0);
     }
 
+    #[tokio::test]
+    async fn test_detector_state_file_round_trip() {
+        let path = std::env::temp_dir().join(format!("detector-state-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        let detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> =
+            vec![Box::new(detectors::NonceAnomalyDetector::new())];
+        let mut tx = ParsedTransaction {
+            hash: "0x01".to_string(),
+            block_number: 1,
+            block_hash: "0xblock1".to_string(),
+            index: 0,
+            caller: "alice".to_string(),
+            pallet: "Balances".to_string(),
+            call: "transfer".to_string(),
+            args: vec![],
+            signature: None,
+            nonce: Some(1),
+            timestamp: 1234567890,
+            success: true,
+            decoded_call: Default::default(),
+        };
+        for nonce in 1..=6 {
+            tx.nonce = Some(nonce);
+            let ctx = TransactionContext { transaction: tx.clone(), events: vec![], state_changes: vec![] };
+            detectors[0].analyze_transaction(&ctx).await;
+        }
+
+        MonitoringEngine::persist_detector_state(&detectors, None, Some(path)).await.unwrap();
+        let snapshots = MonitoringEngine::load_detector_snapshots(None, Some(path)).await.unwrap();
+        std::fs::remove_file(path).ok();
+
+        let fresh: Vec<Box<dyn detectors::Detector + Send + Sync>> =
+            vec![Box::new(detectors::NonceAnomalyDetector::new())];
+        assert_eq!(detectors::restore_detectors(&fresh, &snapshots).await, 1);
+
+        tx.nonce = Some(500);
+        let ctx = TransactionContext { transaction: tx, events: vec![], state_changes: vec![] };
+        assert!(fresh[0].analyze_transaction(&ctx).await.detected);
+    }
+
+    #[tokio::test]
+    async fn test_missing_state_file_is_empty() {
+        let snapshots = MonitoringEngine::load_detector_snapshots(None, Some("/nonexistent/detector-state.json"))
+            .await
+            .unwrap();
+        assert!(snapshots.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_every_detector_has_a_manifest() {
+        let engine = MonitoringEngine::new(MonitorConfig::default());
+        let detectors = engine.initialize_detectors();
+        let manifests = engine.detector_manifests();
+        assert_eq!(manifests.len(), detectors.len());
+
+        for (detector, manifest) in detectors.iter().zip(&manifests) {
+            assert_eq!(manifest.name, detector.name());
+            assert_eq!(manifest.version, detector.version());
+            assert!(!manifest.patterns.is_empty(), "{} lists no patterns", manifest.name);
+            assert!(!manifest.description.is_empty(), "{} has no description", manifest.name);
+            assert_eq!(manifest.alert_threshold.is_none(), detector.always_alert());
+        }
+    }
+
     #[tokio::test]
     async fn test_engine_start_stop() {
         let config = MonitorConfig::default();
@@ -862,4 +2569,1156 @@ mod tests {
         assert_eq!(config.chain_name, "westend");
         assert!(config.enable_mempool);
     }
+
+    fn ml_test_transaction(caller: &str) -> ParsedTransaction {
+        ParsedTransaction {
+            hash: format!("0x{}", caller),
+            block_number: 1,
+            block_hash: "0xblock".to_string(),
+            index: 0,
+            caller: caller.to_string(),
+            pallet: "Balances".to_string(),
+            call: "transfer".to_string(),
+            args: vec![],
+            signature: None,
+            nonce: Some(1),
+            timestamp: 1234567890,
+            success: true,
+            decoded_call: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ml_features_skipped_when_disabled() {
+        // Lazily connected pool pointing nowhere: inserts fail fast and are only logged
+        let database = Some(Arc::new(
+            database::DatabaseClient::connect_lazy("postgres://nexus@127.0.0.1:1/nexus", 1).unwrap(),
+        ));
+        let state = Arc::new(RwLock::new(EngineState::default()));
+        let alert_manager = Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None));
+        let thresholds = AlertThresholds::default();
+
+        MonitoringEngine::process_transaction(
+            ml_test_transaction("alice").into(),
+            &[],
+            &state,
+            &alert_manager,
+            "test",
+            &database,
+            None,
+            &thresholds,
+            &sampling::TransactionSampler::default(),
+            false,
+        )
+        .await;
+
+        // Extraction (and therefore the ML feature insert) never ran
+        assert!(!state.read().await.feature_extractor.has_caller_history("alice"));
+
+        MonitoringEngine::process_transaction(
+            ml_test_transaction("bob").into(),
+            &[],
+            &state,
+            &alert_manager,
+            "test",
+            &database,
+            None,
+            &thresholds,
+            &sampling::TransactionSampler::default(),
+            true,
+        )
+        .await;
+
+        assert!(state.read().await.feature_extractor.has_caller_history("bob"));
+    }
+
+    #[tokio::test]
+    async fn test_runtime_upgrade_alerts_critical_regardless_of_threshold() {
+        let state = Arc::new(RwLock::new(EngineState::default()));
+        let alert_manager = Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None));
+        // An override no detection can exceed
+        let thresholds = AlertThresholds::new(
+            SensitivityProfile::Lenient,
+            std::collections::HashMap::from([("Runtime Upgrade Detector".to_string(), 1.0)]),
+        );
+        let detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> =
+            vec![Box::new(detectors::RuntimeUpgradeDetector::new())];
+
+        let mut tx = ml_test_transaction("root");
+        tx.pallet = "System".to_string();
+        tx.call = "set_code".to_string();
+
+        MonitoringEngine::process_transaction(
+            tx.into(),
+            &detectors,
+            &state,
+            &alert_manager,
+            "test",
+            &None,
+            None,
+            &thresholds,
+            &sampling::TransactionSampler::default(),
+            false,
+        )
+        .await;
+
+        let alerts = alert_manager.get_recent_alerts(10).await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].severity, AlertSeverity::Critical);
+        assert_eq!(alerts[0].pattern, AttackPattern::RuntimeUpgrade);
+    }
+
+    /// Scores each transaction with the confidence encoded in its nonce (percent)
+    struct NonceConfidenceDetector;
+
+    #[async_trait::async_trait]
+    impl detectors::Detector for NonceConfidenceDetector {
+        fn name(&self) -> &str {
+            "MEV Detector"
+        }
+
+        async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+            let confidence = ctx.transaction.nonce.unwrap_or(0) as f64 / 100.0;
+            DetectionResult::detected(AttackPattern::Mev, confidence, "test".to_string(), vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confidence_histogram_includes_sub_threshold_results() {
+        let engine = MonitoringEngine::new(MonitorConfig::default());
+        let alert_manager = Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None));
+        let thresholds = AlertThresholds::default();
+        let detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> =
+            vec![Box::new(NonceConfidenceDetector)];
+
+        for nonce in [12, 15, 35, 55, 58, 95] {
+            let mut tx = ml_test_transaction("alice");
+            tx.nonce = Some(nonce);
+            MonitoringEngine::process_transaction(
+                tx.into(),
+                &detectors,
+                &engine.state,
+                &alert_manager,
+                "test",
+                &None,
+                None,
+                &thresholds,
+                &sampling::TransactionSampler::default(),
+                false,
+            )
+            .await;
+        }
+
+        let histogram = engine.get_confidence_histogram("MEV Detector").await.unwrap();
+        assert_eq!(histogram.total, 6);
+        assert_eq!(histogram.counts[1], 2);
+        assert_eq!(histogram.counts[3], 1);
+        assert_eq!(histogram.counts[5], 2);
+        assert_eq!(histogram.counts[9], 1);
+
+        // Only the three results above the balanced 0.5 threshold alerted
+        assert_eq!(alert_manager.get_recent_alerts(10).await.len(), 3);
+
+        let empty = engine.get_confidence_histogram("Flash Loan Detector").await.unwrap();
+        assert_eq!(empty.total, 0);
+        assert!(engine.get_confidence_histogram("No Such Detector").await.is_none());
+    }
+
+    /// Lists one evidence line per duplicate, like the bridge detector on a flood of commitments
+    struct EvidenceFloodDetector;
+
+    #[async_trait::async_trait]
+    impl detectors::Detector for EvidenceFloodDetector {
+        fn name(&self) -> &str {
+            "Cross-Chain Bridge Detector"
+        }
+
+        async fn analyze_transaction(&self, _ctx: &TransactionContext) -> DetectionResult {
+            let evidence = (0..100).map(|i| format!("Duplicate commitment #{}", i)).collect();
+            DetectionResult::detected(
+                AttackPattern::CrossChainBridge,
+                0.8,
+                "flood".to_string(),
+                evidence,
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_evidence_capped_before_storing() {
+        let state = Arc::new(RwLock::new(EngineState::default()));
+        let alert_manager = Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None));
+        let config = MonitorConfig {
+            max_evidence_entries: 20,
+            ..MonitorConfig::default()
+        };
+        let thresholds = config.alert_thresholds();
+        let store = Arc::new(RecordingStore::default());
+        let writer = database::retry::RetryingWriter::new(store.clone());
+        let detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> =
+            vec![Box::new(EvidenceFloodDetector)];
+
+        MonitoringEngine::process_transaction(
+            ml_test_transaction("alice").into(),
+            &detectors,
+            &state,
+            &alert_manager,
+            "test",
+            &None,
+            Some(&writer),
+            &thresholds,
+            &sampling::TransactionSampler::default(),
+            false,
+        )
+        .await;
+        assert_eq!(alert_manager.get_recent_alerts(10).await.len(), 1);
+
+        // The record persisted for the alert holds the capped evidence
+        let records = store.detections.lock().unwrap().clone();
+        assert_eq!(records.len(), 1);
+        let evidence = records[0].evidence.clone().unwrap();
+        let evidence = evidence.as_array().unwrap();
+        assert_eq!(evidence.len(), 21);
+        assert_eq!(evidence[19], "Duplicate commitment #19");
+        assert_eq!(evidence[20], "... 80 more");
+    }
+
+    #[tokio::test]
+    async fn test_front_run_evidence_capped() {
+        let config = MonitorConfig {
+            max_evidence_entries: 2,
+            ..MonitorConfig::default()
+        };
+        let alert_manager = Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None));
+        let mempool = MonitoringEngine::mempool_monitor(&config, &alert_manager);
+
+        let extrinsic = |hash: &str, caller: &str, index: u32| ParsedTransaction {
+            hash: hash.to_string(),
+            index,
+            pallet: "Omnipool".to_string(),
+            call: "sell".to_string(),
+            ..ml_test_transaction(caller)
+        };
+        let victim = extrinsic("0xvictim", "alice", 1);
+        mempool.observe_pending(&victim).await;
+
+        let correlations = mempool
+            .correlate_block(&[extrinsic("0xattacker", "mallory", 0), victim])
+            .await;
+        assert_eq!(correlations.len(), 1);
+        assert_eq!(correlations[0].result.evidence.len(), 3);
+        assert_eq!(correlations[0].result.evidence[2], "... 1 more");
+        assert_eq!(alert_manager.get_recent_alerts(10).await.len(), 1);
+    }
+
+    struct VersionedDetector;
+
+    #[async_trait::async_trait]
+    impl detectors::Detector for VersionedDetector {
+        fn name(&self) -> &str {
+            "Versioned Detector"
+        }
+
+        fn version(&self) -> &str {
+            "2.1"
+        }
+
+        async fn analyze_transaction(&self, _ctx: &TransactionContext) -> DetectionResult {
+            DetectionResult::no_detection()
+        }
+    }
+
+    #[test]
+    fn test_detection_record_carries_detector_version() {
+        let tx = ParsedTransaction {
+            hash: "0xabc".to_string(),
+            block_number: 42,
+            block_hash: "0xblock".to_string(),
+            index: 0,
+            caller: "alice".to_string(),
+            pallet: "Balances".to_string(),
+            call: "transfer".to_string(),
+            args: vec![],
+            signature: None,
+            nonce: Some(1),
+            timestamp: 1234567890,
+            success: true,
+            decoded_call: Default::default(),
+        };
+        let result = DetectionResult::detected(
+            AttackPattern::Mev,
+            0.8,
+            "test".to_string(),
+            vec![],
+        );
+
+        let detection = MonitoringEngine::detection_record(
+            "alert-1",
+            &tx,
+            &VersionedDetector,
+            &result,
+            AlertSeverity::High,
+            true,
+        );
+
+        assert_eq!(detection.detector_name, "Versioned Detector");
+        assert_eq!(detection.detector_version, "2.1");
+        assert_eq!(detection.severity, "high");
+        assert!(detection.alerted);
+        assert_eq!(
+            detection.metadata.as_ref().unwrap()["cwe_ids"],
+            serde_json::json!(["CWE-362"])
+        );
+
+        // Detectors that don't override the version report "1"
+        let flash_loan = detectors::FlashLoanDetector::new();
+        let detection = MonitoringEngine::detection_record(
+            "alert-2",
+            &tx,
+            &flash_loan,
+            &result,
+            AlertSeverity::High,
+            false,
+        );
+        assert_eq!(detection.detector_version, "1");
+        assert!(!detection.alerted);
+    }
+
+    /// Flags every transaction, so each committed transaction raises one alert
+    struct FlagEverythingDetector;
+
+    #[async_trait::async_trait]
+    impl detectors::Detector for FlagEverythingDetector {
+        fn name(&self) -> &str {
+            "Flag Everything Detector"
+        }
+
+        async fn analyze_transaction(&self, _ctx: &TransactionContext) -> DetectionResult {
+            DetectionResult::detected(AttackPattern::Unknown, 0.95, "test".to_string(), vec![])
+        }
+    }
+
+    struct FixedConfidenceDetector(f64);
+
+    #[async_trait::async_trait]
+    impl detectors::Detector for FixedConfidenceDetector {
+        fn name(&self) -> &str {
+            "Fixed Confidence Detector"
+        }
+
+        async fn analyze_transaction(&self, _ctx: &TransactionContext) -> DetectionResult {
+            DetectionResult::detected(AttackPattern::Unknown, self.0, "test".to_string(), vec![])
+        }
+    }
+
+    /// Record store keeping the detections the pipeline writes
+    #[derive(Default)]
+    struct RecordingStore {
+        detections: std::sync::Mutex<Vec<database::models::Detection>>,
+    }
+
+    #[async_trait::async_trait]
+    impl database::retry::RecordStore for RecordingStore {
+        async fn insert_transaction(&self, _tx: &database::models::Transaction) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn insert_detection(&self, detection: &database::models::Detection) -> anyhow::Result<bool> {
+            self.detections.lock().unwrap().push(detection.clone());
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sampling_skips_all_but_forced_transactions() {
+        let detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> =
+            vec![Box::new(FlagEverythingDetector)];
+        let sudo = ParsedTransaction {
+            pallet: "Sudo".to_string(),
+            call: "sudo".to_string(),
+            ..ml_test_transaction("root")
+        };
+        let transactions = [ml_test_transaction("alice"), ml_test_transaction("bob"), sudo];
+
+        for (rate, expected_alerts) in [(0.0, 1), (1.0, 3)] {
+            let state = Arc::new(RwLock::new(EngineState::default()));
+            let alert_manager = Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None));
+            let sampler = sampling::TransactionSampler::new(Some(rate));
+
+            for tx in transactions.iter().cloned() {
+                MonitoringEngine::process_transaction(
+                    tx.into(),
+                    &detectors,
+                    &state,
+                    &alert_manager,
+                    "test",
+                    &None,
+                    None,
+                    &AlertThresholds::default(),
+                    &sampler,
+                    false,
+                )
+                .await;
+            }
+
+            // Only the sensitive Sudo call is analyzed at a rate of 0
+            assert_eq!(state.read().await.alerts_triggered, expected_alerts, "rate {}", rate);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unmonitored_pallets_are_not_analyzed() {
+        let detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> =
+            vec![Box::new(FlagEverythingDetector)];
+        let timestamp_set = ParsedTransaction {
+            pallet: "Timestamp".to_string(),
+            call: "set".to_string(),
+            ..ml_test_transaction("block-author")
+        };
+        let transfer = ml_test_transaction("alice");
+
+        let state = Arc::new(RwLock::new(EngineState::default()));
+        let alert_manager = Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None));
+        let config = MonitorConfig {
+            monitored_pallets: Some(vec!["balances".to_string()]),
+            ..MonitorConfig::default()
+        };
+        let sampler = config.sampler();
+        assert!(!sampler.should_analyze(&timestamp_set));
+
+        for tx in [timestamp_set, transfer] {
+            MonitoringEngine::process_transaction(
+                tx.into(),
+                &detectors,
+                &state,
+                &alert_manager,
+                "test",
+                &None,
+                None,
+                &AlertThresholds::default(),
+                &sampler,
+                false,
+            )
+            .await;
+        }
+
+        // Only the transfer reached the detectors
+        assert_eq!(state.read().await.alerts_triggered, 1);
+        let alerts = alert_manager.get_recent_alerts(10).await;
+        assert_eq!(alerts[0].metadata.get("caller").map(String::as_str), Some("alice"));
+    }
+
+    #[tokio::test]
+    async fn test_false_positive_feedback_raises_threshold() {
+        let engine = MonitoringEngine::new(MonitorConfig {
+            min_alert_severity: AlertSeverity::Low,
+            ..MonitorConfig::default()
+        });
+        let detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> =
+            vec![Box::new(FixedConfidenceDetector(0.55))];
+
+        // Analyzed with the thresholds in effect at the time
+        async fn process(engine: &MonitoringEngine, detectors: &[Box<dyn detectors::Detector + Send + Sync>], hash: &str) {
+            let tx = ParsedTransaction { hash: hash.to_string(), ..ml_test_transaction("alice") };
+            MonitoringEngine::process_transaction(
+                tx.into(),
+                detectors,
+                &engine.state,
+                &engine.alert_manager,
+                "test",
+                &None,
+                None,
+                &engine.alert_thresholds(),
+                &engine.config.sampler(),
+                false,
+            )
+            .await;
+        }
+
+        for hash in ["0x01", "0x02", "0x03"] {
+            process(&engine, &detectors, hash).await;
+        }
+        let alerts = engine.alert_manager.get_recent_alerts(10).await;
+        assert_eq!(alerts.len(), 3);
+        assert_eq!(engine.alert_thresholds().threshold_for("Fixed Confidence Detector"), 0.5);
+
+        let mut threshold = 0.0;
+        for alert in &alerts {
+            threshold = engine.record_feedback(&alert.id, false).await.unwrap();
+        }
+        assert!((threshold - (0.5 + 3.0 * feedback::DEFAULT_FEEDBACK_STEP)).abs() < 1e-9);
+        assert_eq!(engine.alert_thresholds().threshold_for("Fixed Confidence Detector"), threshold);
+
+        // The same 0.55 detection no longer clears the raised threshold
+        process(&engine, &detectors, "0x04").await;
+        assert_eq!(engine.alert_manager.get_recent_alerts(10).await.len(), 3);
+
+        let missing = engine.record_feedback("unknown", false).await;
+        assert!(matches!(missing, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_short_circuit_runs_only_runtime_upgrade_detector() {
+        let mut detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> = vec![
+            Box::new(FlagEverythingDetector),
+            Box::new(detectors::RuntimeUpgradeDetector::new()),
+        ];
+        detectors::sort_by_priority(&mut detectors, &std::collections::HashMap::new());
+        assert_eq!(detectors[0].name(), "Runtime Upgrade Detector");
+
+        let set_code = ParsedTransaction {
+            pallet: "System".to_string(),
+            call: "set_code".to_string(),
+            ..ml_test_transaction("root")
+        };
+
+        for (short_circuit, expected_alerts) in [(true, 1), (false, 2)] {
+            let state = Arc::new(RwLock::new(EngineState::default()));
+            let alert_manager = Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None));
+
+            MonitoringEngine::process_transaction(
+                set_code.clone().into(),
+                &detectors,
+                &state,
+                &alert_manager,
+                "test",
+                &None,
+                None,
+                &AlertThresholds::default().with_short_circuit_privileged(short_circuit),
+                &sampling::TransactionSampler::default(),
+                false,
+            )
+            .await;
+
+            let alerts = alert_manager.get_recent_alerts(10).await;
+            assert_eq!(alerts.len(), expected_alerts, "short circuit {}", short_circuit);
+            assert!(alerts.iter().any(|alert| alert.pattern == AttackPattern::RuntimeUpgrade));
+        }
+    }
+
+    #[test]
+    fn test_priority_overrides_reorder_detectors() {
+        let mut detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> = vec![
+            Box::new(detectors::RuntimeUpgradeDetector::new()),
+            Box::new(FlagEverythingDetector),
+            Box::new(FixedConfidenceDetector(0.5)),
+        ];
+        let overrides = std::collections::HashMap::from([("Fixed Confidence Detector".to_string(), 200)]);
+        detectors::sort_by_priority(&mut detectors, &overrides);
+
+        let names: Vec<_> = detectors.iter().map(|d| d.name()).collect();
+        assert_eq!(names, ["Fixed Confidence Detector", "Runtime Upgrade Detector", "Flag Everything Detector"]);
+    }
+
+    #[tokio::test]
+    async fn test_near_miss_recorded_but_not_alerted() {
+        let thresholds = AlertThresholds::default().with_record_floor(Some(0.2));
+
+        let near_miss = FixedConfidenceDetector(0.4);
+        let result = DetectionResult::detected(AttackPattern::Unknown, 0.4, "test".to_string(), vec![]);
+        assert_eq!(
+            MonitoringEngine::detection_outcome(&near_miss, &result, &thresholds),
+            DetectionOutcome::Record
+        );
+
+        let noise = FixedConfidenceDetector(0.1);
+        let result = DetectionResult::detected(AttackPattern::Unknown, 0.1, "test".to_string(), vec![]);
+        assert_eq!(
+            MonitoringEngine::detection_outcome(&noise, &result, &thresholds),
+            DetectionOutcome::Drop
+        );
+
+        // Without a floor, sub-threshold results are dropped as before
+        let result = DetectionResult::detected(AttackPattern::Unknown, 0.4, "test".to_string(), vec![]);
+        assert_eq!(
+            MonitoringEngine::detection_outcome(&near_miss, &result, &AlertThresholds::default()),
+            DetectionOutcome::Drop
+        );
+
+        // Committing the near miss stores it unalerted and raises no alert;
+        // the noise is not stored
+        let state = Arc::new(RwLock::new(EngineState::default()));
+        let alert_manager = Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None));
+        let store = Arc::new(RecordingStore::default());
+        let writer = database::retry::RetryingWriter::new(store.clone());
+        let detectors: Vec<Box<dyn detectors::Detector + Send + Sync>> =
+            vec![Box::new(near_miss), Box::new(noise)];
+        MonitoringEngine::process_transaction(
+            ml_test_transaction("alice").into(),
+            &detectors,
+            &state,
+            &alert_manager,
+            "test",
+            &None,
+            Some(&writer),
+            &thresholds,
+            &sampling::TransactionSampler::default(),
+            false,
+        )
+        .await;
+
+        let records = store.detections.lock().unwrap().clone();
+        assert_eq!(records.len(), 1);
+        assert!(!records[0].alerted);
+        assert_eq!(records[0].severity, "low");
+        assert_eq!(records[0].confidence, 0.4);
+        assert_eq!(records[0].detector_name, "Fixed Confidence Detector");
+        assert_eq!(state.read().await.alerts_triggered, 0);
+        assert!(alert_manager.get_recent_alerts(10).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pending_extrinsics_alert_once() {
+        let pipeline = MempoolPipeline {
+            state: Arc::new(RwLock::new(EngineState::default())),
+            chain_name: "test".to_string(),
+            detectors: Arc::new(vec![Box::new(FlagEverythingDetector)]),
+            alert_manager: Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None)),
+            thresholds: Arc::new(AlertThresholds::default()),
+            mempool: Arc::new(mempool::MempoolMonitor::new(100)),
+            sampler: sampling::TransactionSampler::default(),
+        };
+
+        // The pool is polled repeatedly, so the same extrinsic shows up again
+        for caller in ["alice", "bob", "alice"] {
+            let tx = ParsedTransaction {
+                block_number: 0,
+                ..ml_test_transaction(caller)
+            };
+            MonitoringEngine::process_pending(tx, &pipeline).await;
+        }
+
+        let alerts = pipeline.alert_manager.get_recent_alerts(10).await;
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts.iter().all(|alert| alert.block_number.is_none()));
+        assert!(alerts.iter().all(|alert| alert.metadata["pending"] == "true"));
+        assert_eq!(pipeline.state.read().await.alerts_triggered, 2);
+        assert_eq!(pipeline.mempool.pending_count().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_pending_extrinsic_not_realerted_when_included() {
+        let pipeline = MempoolPipeline {
+            state: Arc::new(RwLock::new(EngineState::default())),
+            chain_name: "test".to_string(),
+            detectors: Arc::new(vec![Box::new(FlagEverythingDetector)]),
+            alert_manager: Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None)),
+            thresholds: Arc::new(AlertThresholds::default()),
+            mempool: Arc::new(mempool::MempoolMonitor::new(100)),
+            sampler: sampling::TransactionSampler::default(),
+        };
+
+        let included = ml_test_transaction("alice");
+        let pending = ParsedTransaction { block_number: 0, ..included.clone() };
+        MonitoringEngine::process_pending(pending, &pipeline).await;
+
+        MonitoringEngine::process_transaction(
+            included.into(),
+            &pipeline.detectors,
+            &pipeline.state,
+            &pipeline.alert_manager,
+            &pipeline.chain_name,
+            &None,
+            None,
+            &pipeline.thresholds,
+            &pipeline.sampler,
+            false,
+        )
+        .await;
+
+        let alerts = pipeline.alert_manager.get_recent_alerts(10).await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].metadata["pending"], "true");
+        assert_eq!(pipeline.state.read().await.alerts_triggered, 1);
+    }
+
+    #[tokio::test]
+    async fn test_mempool_poller_stops_with_engine() {
+        let engine = MonitoringEngine::new(MonitorConfig::default());
+        engine.state.write().await.is_running = true;
+        let pipeline = Arc::new(MempoolPipeline {
+            state: engine.state.clone(),
+            chain_name: "test".to_string(),
+            detectors: Arc::new(vec![Box::new(FlagEverythingDetector)]),
+            alert_manager: Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None)),
+            thresholds: Arc::new(AlertThresholds::default()),
+            mempool: Arc::new(mempool::MempoolMonitor::new(100)),
+            sampler: sampling::TransactionSampler::default(),
+        });
+
+        let polls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let poller = tokio::spawn({
+            let pipeline = pipeline.clone();
+            let polls = polls.clone();
+            async move {
+                MonitoringEngine::poll_mempool(
+                    || {
+                        polls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                        async { Ok(vec![ParsedTransaction { block_number: 0, ..ml_test_transaction("alice") }]) }
+                    },
+                    &pipeline,
+                    std::time::Duration::from_millis(1),
+                )
+                .await
+            }
+        });
+
+        while polls.load(std::sync::atomic::Ordering::SeqCst) < 3 {
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+        engine.stop().await.unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), poller)
+            .await
+            .expect("poller stops once the engine is stopped")
+            .unwrap();
+        // The same pending extrinsic on every poll alerts once
+        assert_eq!(pipeline.alert_manager.get_recent_alerts(10).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mempool_skips_stateful_detectors() {
+        let engine = MonitoringEngine::new(MonitorConfig::default());
+        let all = engine.initialize_detectors();
+        let pending = engine.mempool_detectors();
+
+        assert!(pending.iter().all(|detector| !detector.is_stateful()));
+        assert!(pending.iter().any(|detector| detector.name() == "Flash Loan Detector"));
+        assert!(!pending.iter().any(|detector| detector.name() == "Volume Anomaly Detector"));
+        assert_eq!(
+            pending.len(),
+            all.iter().filter(|detector| !detector.is_stateful()).count()
+        );
+    }
+
+    const BURST_BLOCKS: u64 = 12;
+
+    /// Records the block of every transaction it analyzes, in analysis order
+    struct BlockOrderDetector(Arc<std::sync::Mutex<Vec<u64>>>);
+
+    #[async_trait::async_trait]
+    impl detectors::Detector for BlockOrderDetector {
+        fn name(&self) -> &str {
+            "Block Order Detector"
+        }
+
+        async fn analyze_transaction(&self, ctx: &TransactionContext) -> DetectionResult {
+            self.0.lock().unwrap().push(ctx.transaction.block_number);
+            DetectionResult::no_detection()
+        }
+    }
+
+    /// Feed a burst of blocks through the pipeline, returning the final
+    /// (blocks, transactions, alerts) counters, the block of each alert in
+    /// commit order and the block of each transaction in analysis order
+    async fn run_block_burst(parallelism: usize) -> ((u64, u64, u64), Vec<u64>, Vec<u64>) {
+        let analyzed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let pipeline = BlockPipeline {
+            state: Arc::new(RwLock::new(EngineState::default())),
+            chain_name: "test".to_string(),
+            detectors: Arc::new(vec![
+                Box::new(FlagEverythingDetector),
+                Box::new(BlockOrderDetector(analyzed.clone())),
+            ]),
+            alert_manager: Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None)),
+            database: None,
+            db_writer: None,
+            thresholds: Arc::new(AlertThresholds::default()),
+            enable_ml_features: false,
+            mempool: Arc::new(mempool::MempoolMonitor::new(100)),
+            parallelism,
+            buffer_size: 100,
+            sampler: sampling::TransactionSampler::default(),
+            dead_letters: None,
+            raw_blocks: None,
+        };
+
+        let blocks = futures::stream::iter(
+            (1..=BURST_BLOCKS).map(|number| Ok::<_, Error>((number, H256::from_low_u64_be(number)))),
+        );
+        let extract = |_hash: H256, number: u64| async move {
+            // Later blocks extract faster, so analysis finishes out of order
+            let delay = (BURST_BLOCKS - number) * 5;
+            tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+
+            Ok::<_, Error>((0..number % 3 + 1)
+                .map(|index| TransactionContext {
+                    transaction: ParsedTransaction {
+                        hash: format!("0x{}-{}", number, index),
+                        block_number: number,
+                        index: index as u32,
+                        ..ml_test_transaction("alice")
+                    },
+                    events: vec![],
+                    state_changes: vec![],
+                })
+                .collect::<Vec<_>>())
+        };
+
+        MonitoringEngine::process_block_stream(blocks, extract, &pipeline)
+            .await
+            .unwrap();
+
+        let state = pipeline.state.read().await;
+        let counters = (state.blocks_processed, state.transactions_analyzed, state.alerts_triggered);
+
+        let mut alerts = pipeline.alert_manager.get_recent_alerts(usize::MAX).await;
+        alerts.reverse();
+        let alert_blocks = alerts.iter().map(|alert| alert.block_number.unwrap()).collect();
+        let analyzed_blocks = analyzed.lock().unwrap().clone();
+
+        (counters, alert_blocks, analyzed_blocks)
+    }
+
+    /// Takes a few milliseconds per transaction, so analysis lags extraction
+    struct SlowDetector;
+
+    #[async_trait::async_trait]
+    impl detectors::Detector for SlowDetector {
+        fn name(&self) -> &str {
+            "Slow Detector"
+        }
+
+        async fn analyze_transaction(&self, _ctx: &TransactionContext) -> DetectionResult {
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+            DetectionResult::no_detection()
+        }
+    }
+
+    const FLOOD_BLOCKS: u64 = 50;
+
+    /// Flood the pipeline with instantly extracted blocks, returning the
+    /// furthest extraction got ahead of committed blocks
+    async fn run_block_flood(buffer_size: usize) -> u64 {
+        let pipeline = BlockPipeline {
+            state: Arc::new(RwLock::new(EngineState::default())),
+            chain_name: "test".to_string(),
+            detectors: Arc::new(vec![Box::new(SlowDetector)]),
+            alert_manager: Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None)),
+            database: None,
+            db_writer: None,
+            thresholds: Arc::new(AlertThresholds::default()),
+            enable_ml_features: false,
+            mempool: Arc::new(mempool::MempoolMonitor::new(100)),
+            parallelism: 1,
+            buffer_size,
+            sampler: sampling::TransactionSampler::default(),
+            dead_letters: None,
+            raw_blocks: None,
+        };
+
+        let extracted = std::sync::atomic::AtomicU64::new(0);
+        let max_lead = std::sync::atomic::AtomicU64::new(0);
+        let blocks = futures::stream::iter(
+            (1..=FLOOD_BLOCKS).map(|number| Ok::<_, Error>((number, H256::from_low_u64_be(number)))),
+        );
+        let extract = |_hash: H256, number: u64| {
+            let (state, extracted, max_lead) = (pipeline.state.clone(), &extracted, &max_lead);
+            async move {
+                let extracted = extracted.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                let processed = state.read().await.blocks_processed;
+                max_lead.fetch_max(extracted - processed, std::sync::atomic::Ordering::SeqCst);
+
+                Ok::<_, Error>(vec![TransactionContext {
+                    transaction: ParsedTransaction { block_number: number, ..ml_test_transaction("alice") },
+                    events: vec![],
+                    state_changes: vec![],
+                }])
+            }
+        };
+
+        MonitoringEngine::process_block_stream(blocks, extract, &pipeline)
+            .await
+            .unwrap();
+
+        assert_eq!(pipeline.state.read().await.blocks_processed, FLOOD_BLOCKS);
+        max_lead.into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_buffer_size_applies_backpressure() {
+        // Queued blocks, plus one in analysis, one being queued and one extracting
+        let bounded = run_block_flood(1).await;
+        assert!(bounded <= 4, "extraction ran {} blocks ahead", bounded);
+
+        let unbounded = run_block_flood(100).await;
+        assert!(unbounded > 20, "extraction only ran {} blocks ahead", unbounded);
+    }
+
+    #[tokio::test]
+    async fn test_decode_failure_counted_and_dead_lettered() {
+        let path = std::env::temp_dir().join(format!("decode-failures-{}.jsonl", std::process::id()));
+        let store = Arc::new(dead_letter::DeadLetterStore::new(&path));
+        let pipeline = BlockPipeline {
+            state: Arc::new(RwLock::new(EngineState::default())),
+            chain_name: "test".to_string(),
+            detectors: Arc::new(vec![Box::new(FlagEverythingDetector)]),
+            alert_manager: Arc::new(alerts::AlertManager::new(AlertSeverity::Low, None)),
+            database: None,
+            db_writer: None,
+            thresholds: Arc::new(AlertThresholds::default()),
+            enable_ml_features: false,
+            mempool: Arc::new(mempool::MempoolMonitor::new(100)),
+            parallelism: 1,
+            buffer_size: 100,
+            sampler: sampling::TransactionSampler::default(),
+            dead_letters: Some(store.clone()),
+            raw_blocks: None,
+        };
+
+        let blocks = futures::stream::iter(
+            (1..=3u64).map(|number| Ok::<_, Error>((number, H256::from_low_u64_be(number)))),
+        );
+        let extract = |_hash: H256, number: u64| async move {
+            if number == 2 {
+                return Err(Error::ParseError("Cannot decode extrinsic 0".to_string()));
+            }
+            Ok(vec![TransactionContext {
+                transaction: ParsedTransaction { block_number: number, ..ml_test_transaction("alice") },
+                events: vec![],
+                state_changes: vec![],
+            }])
+        };
+
+        MonitoringEngine::process_block_stream(blocks, extract, &pipeline)
+            .await
+            .unwrap();
+
+        let letters = store.read_all().await.unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let state = pipeline.state.read().await;
+        assert_eq!(state.blocks_processed, 3);
+        assert_eq!(state.transactions_analyzed, 2);
+        assert_eq!(state.decode_failures, 1);
+
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].chain, "test");
+        assert_eq!(letters[0].block_number, 2);
+        assert_eq!(letters[0].block_hash, format!("0x{}", hex::encode(H256::from_low_u64_be(2).0)));
+        assert!(letters[0].error.contains("Cannot decode extrinsic 0"));
+    }
+
+    #[tokio::test]
+    async fn test_parallel_blocks_commit_in_order() {
+        let serial = run_block_burst(1).await;
+        let parallel = run_block_burst(4).await;
+
+        assert_eq!(serial.0 .0, BURST_BLOCKS);
+        assert_eq!(parallel, serial);
+        assert!(parallel.1.windows(2).all(|pair| pair[0] <= pair[1]));
+        // Detectors never see a later block before an earlier one finishes
+        assert!(parallel.2.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_detection_id_is_deterministic() {
+        let tx = ParsedTransaction {
+            hash: "0xabc".to_string(),
+            block_number: 42,
+            block_hash: "0xblock".to_string(),
+            index: 0,
+            caller: "alice".to_string(),
+            pallet: "Balances".to_string(),
+            call: "transfer".to_string(),
+            args: vec![],
+            signature: None,
+            nonce: Some(1),
+            timestamp: 1234567890,
+            success: true,
+            decoded_call: Default::default(),
+        };
+
+        let id = detection_id("westend", &tx, "MEV Detector");
+        assert_eq!(id, detection_id("westend", &tx, "MEV Detector"));
+        assert!(uuid::Uuid::parse_str(&id).is_ok());
+
+        assert_ne!(id, detection_id("westend", &tx, "Flash Loan Detector"));
+        assert_ne!(id, detection_id("rococo", &tx, "MEV Detector"));
+        // The same extrinsic keeps its id whether pending or included
+        let pending = ParsedTransaction { block_number: 0, ..tx.clone() };
+        assert_eq!(id, detection_id("westend", &pending, "MEV Detector"));
+        let other = ParsedTransaction { hash: "0xdef".to_string(), ..tx.clone() };
+        assert_ne!(id, detection_id("westend", &other, "MEV Detector"));
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        let cases = [
+            (Error::ConnectionError("refused".to_string()), ErrorKind::Transient),
+            (Error::SubscriptionError("stream closed".to_string()), ErrorKind::Transient),
+            (Error::DatabaseError("pool timeout".to_string()), ErrorKind::Transient),
+            (Error::AlertError("webhook 503".to_string()), ErrorKind::Transient),
+            (Error::ParseError("bad extrinsic".to_string()), ErrorKind::Fatal),
+            (Error::ConfigError("bad endpoint".to_string()), ErrorKind::Fatal),
+            (Error::NotFound("detection 42".to_string()), ErrorKind::NotFound),
+            (
+                Error::IoError(std::io::Error::from(std::io::ErrorKind::NotFound)),
+                ErrorKind::NotFound,
+            ),
+            (
+                Error::IoError(std::io::Error::from(std::io::ErrorKind::TimedOut)),
+                ErrorKind::Transient,
+            ),
+            (
+                Error::IoError(std::io::Error::from(std::io::ErrorKind::PermissionDenied)),
+                ErrorKind::Fatal,
+            ),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(error.kind(), expected, "{}", error);
+        }
+    }
+
+    fn lookup_from(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: std::collections::HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_env_overrides_base_config() {
+        let config = MonitorConfig::from_lookup(
+            MonitorConfig::kusama(),
+            lookup_from(&[
+                ("WS_ENDPOINT", "ws://127.0.0.1:9944"),
+                ("ALERT_WEBHOOK", "https://hooks.example.com/alerts"),
+                ("MIN_ALERT_SEVERITY", "high"),
+                ("ENABLE_MEMPOOL", "false"),
+                ("DATABASE_URL", "postgres://localhost/nexus"),
+                ("DATABASE_MAX_CONNECTIONS", "25"),
+                ("SENSITIVITY_PROFILE", "lenient"),
+                ("SEVERITY_CUTOFFS", "0.85,0.7,0.5"),
+                ("BLOCK_PARALLELISM", "4"),
+                ("MAX_EVIDENCE_ENTRIES", "50"),
+                ("DETECTION_RECORD_FLOOR", "0.2"),
+                ("ANOMALY_DISTANCE_THRESHOLD", "4.5"),
+                ("ALERT_COOLDOWN", "300"),
+                ("SAMPLE_RATE", "0.25"),
+                ("MONITORED_PALLETS", "Omnipool, TokenGateway"),
+                ("RETENTION_DAYS", "30"),
+                ("KAFKA_BROKERS", "kafka-1:9092, kafka-2:9092"),
+                ("NATS_URL", "nats://127.0.0.1:4222"),
+                ("MEMPOOL_MAX_AGE", "600"),
+                ("WEBHOOK_CONCURRENCY", "2"),
+                ("DEAD_LETTER_FILE", "/var/lib/nexus/dead-letters.jsonl"),
+                ("DB_WRITE_ATTEMPTS", "5"),
+                ("DB_DEAD_LETTER_FILE", "/var/lib/nexus/deferred-writes.jsonl"),
+                ("SHORT_CIRCUIT_PRIVILEGED", "yes"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(config.ws_endpoint, "ws://127.0.0.1:9944");
+        // Unset variables keep the base value
+        assert_eq!(config.chain_name, "kusama");
+        assert_eq!(config.alert_webhook.as_deref(), Some("https://hooks.example.com/alerts"));
+        assert_eq!(config.min_alert_severity, AlertSeverity::High);
+        assert!(!config.enable_mempool);
+        assert_eq!(config.database_url.as_deref(), Some("postgres://localhost/nexus"));
+        assert_eq!(config.database_max_connections, 25);
+        assert_eq!(config.sensitivity, SensitivityProfile::Lenient);
+        assert_eq!(config.severity_cutoffs, SeverityCutoffs::new(0.85, 0.7, 0.5).unwrap());
+        assert_eq!(config.alert_thresholds().severity_for(0.72), AlertSeverity::High);
+        assert_eq!(config.block_parallelism, 4);
+        assert_eq!(config.max_evidence_entries, 50);
+        assert_eq!(config.record_floor, Some(0.2));
+        assert_eq!(config.anomaly_distance_threshold, 4.5);
+        assert_eq!(config.alert_cooldown_secs, Some(300));
+        assert_eq!(config.sample_rate, Some(0.25));
+        assert_eq!(config.sample_value_threshold, sampling::DEFAULT_VALUE_THRESHOLD);
+        assert_eq!(
+            config.monitored_pallets,
+            Some(vec!["Omnipool".to_string(), "TokenGateway".to_string()])
+        );
+        assert_eq!(config.retention_days, Some(30));
+        assert_eq!(config.kafka_brokers, vec!["kafka-1:9092", "kafka-2:9092"]);
+        assert_eq!(config.kafka_topic, "security-nexus.alerts");
+        assert_eq!(config.nats_url.as_deref(), Some("nats://127.0.0.1:4222"));
+        assert_eq!(config.alert_sinks().len(), 2);
+        assert_eq!(config.mempool_max_age_secs, Some(600));
+        assert_eq!(config.webhook_concurrency, 2);
+        assert_eq!(config.dead_letter_file.as_deref(), Some("/var/lib/nexus/dead-letters.jsonl"));
+        assert_eq!(config.db_write_attempts, 5);
+        assert_eq!(config.db_dead_letter_file.as_deref(), Some("/var/lib/nexus/deferred-writes.jsonl"));
+        assert!(config.short_circuit_privileged);
+        assert!(config.alert_thresholds().short_circuit_privileged());
+    }
+
+    #[test]
+    fn test_env_rejects_malformed_values() {
+        let result = MonitorConfig::from_lookup(
+            MonitorConfig::default(),
+            lookup_from(&[("DATABASE_MAX_CONNECTIONS", "many")]),
+        );
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+
+        let result = MonitorConfig::from_lookup(
+            MonitorConfig::default(),
+            lookup_from(&[("ENABLE_EVENTS", "sometimes")]),
+        );
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+
+        let result = MonitorConfig::from_lookup(
+            MonitorConfig::default(),
+            lookup_from(&[("WS_ENDPOINT", "http://localhost:9944")]),
+        );
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+
+        // Cutoffs must decrease from critical to medium
+        let result = MonitorConfig::from_lookup(
+            MonitorConfig::default(),
+            lookup_from(&[("SEVERITY_CUTOFFS", "0.6,0.75,0.9")]),
+        );
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+
+        let result = MonitorConfig::from_lookup(
+            MonitorConfig::default(),
+            lookup_from(&[("SAMPLE_RATE", "1.5")]),
+        );
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+
+        let result = MonitorConfig::from_lookup(
+            MonitorConfig::default(),
+            lookup_from(&[("BUFFER_SIZE", "0")]),
+        );
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+
+        let result = MonitorConfig::from_lookup(
+            MonitorConfig::default(),
+            lookup_from(&[("RETENTION_DAYS", "0")]),
+        );
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+
+        let result = MonitorConfig::from_lookup(
+            MonitorConfig::default(),
+            lookup_from(&[("DB_WRITE_ATTEMPTS", "0")]),
+        );
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_digest_interval() {
+        let config = MonitorConfig { alert_digest_interval_secs: Some(0), ..MonitorConfig::default() };
+        assert!(matches!(config.validate(), Err(Error::ConfigError(_))));
+
+        let config = MonitorConfig { alert_digest_interval_secs: Some(60), ..MonitorConfig::default() };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_env_leaves_unset_values_at_defaults() {
+        let config = MonitorConfig::from_lookup(
+            MonitorConfig::default(),
+            lookup_from(&[("CHAIN_NAME", "env-test-chain"), ("BUFFER_SIZE", "42")]),
+        )
+        .unwrap();
+
+        assert_eq!(config.chain_name, "env-test-chain");
+        assert_eq!(config.buffer_size, 42);
+        assert_eq!(config.ws_endpoint, "wss://westend-rpc.polkadot.io");
+    }
 }
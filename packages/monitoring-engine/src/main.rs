@@ -1,6 +1,6 @@
 //! Monitoring Engine Binary
 
-use monitoring_engine::{MonitoringEngine, api::start_api_server, config, database::DatabaseClient};
+use monitoring_engine::{MonitorConfig, MonitoringEngine, api::start_api_server, config, database::DatabaseClient};
 use tracing::Level;
 use tracing_subscriber::FmtSubscriber;
 use std::sync::Arc;
@@ -17,39 +17,40 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     tracing::info!("Starting Polkadot Security Nexus - Monitoring Engine");
 
-    // Load configuration from saved file, or use default
-    let mut config = config::load_monitor_config();
-
-    // Allow environment variables to override saved configuration
-    if let Ok(ws_endpoint) = std::env::var("WS_ENDPOINT") {
-        tracing::info!("Overriding WebSocket endpoint from environment variable");
-        config.ws_endpoint = ws_endpoint;
-    }
-    if let Ok(chain_name) = std::env::var("CHAIN_NAME") {
-        tracing::info!("Overriding chain name from environment variable");
-        config.chain_name = chain_name;
-    }
-    if let Ok(webhook) = std::env::var("ALERT_WEBHOOK") {
-        config.alert_webhook = Some(webhook);
-    }
+    // Load configuration from saved file, then let environment variables override it
+    let config = match MonitorConfig::from_env_with_base(config::load_monitor_config()) {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("Invalid configuration: {}", e);
+            return Err(e.into());
+        }
+    };
 
     tracing::info!("Configuration:");
     tracing::info!("  WebSocket: {}", config.ws_endpoint);
     tracing::info!("  Chain: {}", config.chain_name);
     tracing::info!("  Mempool monitoring: {}", config.enable_mempool);
     tracing::info!("  Block monitoring: {}", config.enable_blocks);
-    tracing::info!("  Event monitoring: {}", config.enable_events);
+    tracing::info!("  Transaction events: {}", config.enable_events);
+    tracing::info!("  Sensitivity profile: {}", config.sensitivity);
 
     // Initialize database client if DATABASE_URL is provided
-    let database = if let Ok(database_url) = std::env::var("DATABASE_URL") {
-        let max_connections = std::env::var("DATABASE_MAX_CONNECTIONS")
-            .unwrap_or_else(|_| "10".to_string())
-            .parse::<usize>()
-            .unwrap_or(10);
+    let database = if let Some(database_url) = config.database_url.as_deref() {
+        let max_connections = config.database_max_connections;
 
-        match DatabaseClient::new(&database_url, max_connections).await {
+        match DatabaseClient::new(database_url, max_connections).await {
             Ok(client) => {
                 tracing::info!("Successfully connected to TimescaleDB");
+                match client.schema_check().await {
+                    Ok(report) if !report.is_complete() => tracing::warn!(
+                        "Database schema is incomplete (missing tables: {:?}, missing continuous aggregates: {:?}); \
+                        apply schema.sql before relying on the stats endpoints",
+                        report.missing_tables,
+                        report.missing_aggregates
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Failed to check database schema: {}", e),
+                }
                 Some(Arc::new(client))
             }
             Err(e) => {
@@ -62,6 +63,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         None
     };
 
+    let api_bind = config.api_bind_address.clone();
+
     // Create and start monitoring engine
     let engine = Arc::new(if let Some(db) = database {
         MonitoringEngine::with_database(config, db)
@@ -73,10 +76,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Ok(_) => {
             tracing::info!("Monitoring engine started.");
 
-            // Start API server
-            let api_bind = std::env::var("API_BIND_ADDRESS")
-                .unwrap_or_else(|_| "0.0.0.0:8080".to_string());
-
             tracing::info!("Press Ctrl+C to stop.");
 
             // Run API server (blocks until shutdown)
@@ -1,19 +1,97 @@
 //! Mempool monitoring and analysis
 
 use crate::detectors::Detector;
-use crate::types::{Alert, AlertSeverity, DetectionResult, Transaction, TransactionContext};
-use std::collections::VecDeque;
+use crate::sensitivity::{SeverityCutoffs, DEFAULT_MAX_EVIDENCE_ENTRIES};
+use crate::types::{
+    Alert, AlertSeverity, AttackPattern, DetectionResult, ParsedTransaction, Transaction,
+    TransactionContext, SCHEMA_VERSION,
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+/// Confidence when the attacker's copy was seen pending after the victim
+const COPIED_AFTER_VICTIM_CONFIDENCE: f64 = 0.9;
+
+/// Confidence when the attacker's copy never appeared in the mempool
+/// (e.g. submitted through a private channel)
+const UNSEEN_ATTACKER_CONFIDENCE: f64 = 0.8;
+
+/// Decoded call arguments naming what a transaction acts on (the asset,
+/// pool, route or recipient); a copy must match the victim on all of them
+const TARGET_ARGS: &[&str] = &[
+    "asset",
+    "asset_id",
+    "asset_in",
+    "asset_out",
+    "currency_id",
+    "currency_in",
+    "currency_out",
+    "pool",
+    "pool_id",
+    "route",
+    "path",
+    "dest",
+    "target",
+];
+
+/// Whether `attacker` acts on the same target as `victim`
+///
+/// Compares the [`TARGET_ARGS`] of both decoded calls. When either call was
+/// not decoded only the pallet and call names can be compared, so they match.
+fn same_target(attacker: &ParsedTransaction, victim: &ParsedTransaction) -> bool {
+    let (Some(attacker), Some(victim)) = (attacker.decoded_call.get(), victim.decoded_call.get()) else {
+        return true;
+    };
+
+    TARGET_ARGS
+        .iter()
+        .filter_map(|name| Some((*name, victim.args.get(name)?)))
+        .all(|(name, value)| attacker.args.get(name) == Some(value))
+}
+
 /// Mempool monitor tracks pending transactions and analyzes for attacks
 pub struct MempoolMonitor {
     pending_txs: RwLock<VecDeque<Transaction>>,
     max_size: usize,
+    /// Pending extrinsics by hash, kept for correlation with finalized blocks
+    sightings: RwLock<HashMap<String, PendingSighting>>,
+    /// Order in which the next pending extrinsic is seen
+    next_sighting: AtomicU64,
     /// Detectors for analyzing mempool transactions
     detectors: Arc<Vec<Box<dyn Detector + Send + Sync>>>,
     /// Alert manager for triggering security alerts
     alert_manager: Option<Arc<crate::alerts::AlertManager>>,
+    /// Confidence cutoffs for alert severities
+    severity_cutoffs: SeverityCutoffs,
+    /// Pending transactions older than this are dropped regardless of capacity
+    max_age: Option<Duration>,
+    /// Evidence entries kept per result before alerting
+    max_evidence_entries: usize,
+}
+
+/// A pending extrinsic observed in the mempool
+#[derive(Debug, Clone)]
+struct PendingSighting {
+    pallet: String,
+    call: String,
+    caller: String,
+    /// Position in the order extrinsics were seen
+    order: u64,
+    /// Unix timestamp (seconds) of the extrinsic
+    timestamp: u64,
+}
+
+/// A block transaction that front-ran a victim seen earlier in the mempool
+#[derive(Debug, Clone)]
+pub struct FrontRunCorrelation {
+    /// Hash of the transaction that landed first
+    pub attacker_hash: String,
+    /// Hash of the victim transaction seen pending earlier
+    pub victim_hash: String,
+    pub result: DetectionResult,
 }
 
 impl MempoolMonitor {
@@ -22,8 +100,13 @@ impl MempoolMonitor {
         Self {
             pending_txs: RwLock::new(VecDeque::with_capacity(max_size)),
             max_size,
+            sightings: RwLock::new(HashMap::new()),
+            next_sighting: AtomicU64::new(0),
             detectors: Arc::new(Vec::new()),
             alert_manager: None,
+            severity_cutoffs: SeverityCutoffs::default(),
+            max_age: None,
+            max_evidence_entries: DEFAULT_MAX_EVIDENCE_ENTRIES,
         }
     }
 
@@ -36,9 +119,63 @@ impl MempoolMonitor {
         Self {
             pending_txs: RwLock::new(VecDeque::with_capacity(max_size)),
             max_size,
+            sightings: RwLock::new(HashMap::new()),
+            next_sighting: AtomicU64::new(0),
             detectors,
             alert_manager,
+            severity_cutoffs: SeverityCutoffs::default(),
+            max_age: None,
+            max_evidence_entries: DEFAULT_MAX_EVIDENCE_ENTRIES,
+        }
+    }
+
+    /// Use custom confidence cutoffs for alert severities
+    pub fn with_severity_cutoffs(mut self, severity_cutoffs: SeverityCutoffs) -> Self {
+        self.severity_cutoffs = severity_cutoffs;
+        self
+    }
+
+    /// Keep at most `max` evidence entries per detection or correlation
+    /// (see [`DetectionResult::truncate_evidence`])
+    pub fn with_max_evidence_entries(mut self, max: usize) -> Self {
+        self.max_evidence_entries = max;
+        self
+    }
+
+    /// Drop pending transactions older than `max_age` in `prune_stale`
+    ///
+    /// Stale transactions that never confirm otherwise linger until pushed
+    /// out by volume and skew front-run correlation.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Drop pending transactions and sightings older than the max age
+    ///
+    /// `now` is a Unix timestamp in seconds, compared against the
+    /// transactions' `timestamp`. Returns the number of pending transactions
+    /// removed; without a max age nothing is pruned.
+    pub async fn prune_stale(&self, now: u64) -> usize {
+        let Some(max_age) = self.max_age else {
+            return 0;
+        };
+        let cutoff = now.saturating_sub(max_age.as_secs());
+
+        self.sightings
+            .write()
+            .await
+            .retain(|_, sighting| sighting.timestamp >= cutoff);
+
+        let mut pending = self.pending_txs.write().await;
+        let before = pending.len();
+        pending.retain(|tx| tx.timestamp >= cutoff);
+        let pruned = before - pending.len();
+
+        if pruned > 0 {
+            tracing::debug!("Pruned {} stale pending transactions", pruned);
         }
+        pruned
     }
 
     /// Add a transaction to the mempool
@@ -77,13 +214,166 @@ impl MempoolMonitor {
         pending.clear();
     }
 
+    /// Record a pending extrinsic for later correlation with finalized blocks
+    ///
+    /// Returns `false` if the extrinsic was already seen.
+    pub async fn observe_pending(&self, tx: &ParsedTransaction) -> bool {
+        let mut sightings = self.sightings.write().await;
+
+        if sightings.contains_key(&tx.hash) {
+            return false;
+        }
+
+        // Drop the earliest sighting when at capacity
+        if sightings.len() >= self.max_size {
+            if let Some(oldest) = sightings
+                .iter()
+                .min_by_key(|(_, s)| s.order)
+                .map(|(hash, _)| hash.clone())
+            {
+                sightings.remove(&oldest);
+            }
+        }
+
+        sightings.insert(
+            tx.hash.clone(),
+            PendingSighting {
+                pallet: tx.pallet.clone(),
+                call: tx.call.clone(),
+                caller: tx.caller.clone(),
+                order: self.next_sighting.fetch_add(1, Ordering::Relaxed),
+                timestamp: tx.timestamp,
+            },
+        );
+        true
+    }
+
+    /// Correlate a finalized block with the pending extrinsics seen earlier
+    ///
+    /// A transaction is flagged as front-running when a victim was seen pending
+    /// first, yet a transaction from a different caller making the same call on
+    /// the same target (asset, pool, route or recipient) precedes it in the
+    /// block. Correlated transactions are cleared from the pending set,
+    /// and stale ones are pruned first when a max age is set.
+    pub async fn correlate_block(&self, block_txs: &[ParsedTransaction]) -> Vec<FrontRunCorrelation> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        self.prune_stale(now).await;
+
+        let mut ordered: Vec<&ParsedTransaction> = block_txs.iter().collect();
+        ordered.sort_by_key(|tx| tx.index);
+
+        let mut correlations = Vec::new();
+        let mut sightings = self.sightings.write().await;
+
+        for (position, victim) in ordered.iter().enumerate() {
+            let Some(victim_seen) = sightings.get(&victim.hash) else {
+                continue;
+            };
+
+            for attacker in &ordered[..position] {
+                if attacker.caller == victim_seen.caller
+                    || attacker.pallet != victim_seen.pallet
+                    || attacker.call != victim_seen.call
+                    || !same_target(attacker, victim)
+                {
+                    continue;
+                }
+
+                let attacker_seen = sightings.get(&attacker.hash).map(|s| s.order);
+
+                // Submitted before the victim: ordinary ordering, not a copy
+                if matches!(attacker_seen, Some(order) if order < victim_seen.order) {
+                    continue;
+                }
+
+                let confidence = if attacker_seen.is_some() {
+                    COPIED_AFTER_VICTIM_CONFIDENCE
+                } else {
+                    UNSEEN_ATTACKER_CONFIDENCE
+                };
+
+                let mut evidence = vec![
+                    format!(
+                        "Victim {} ({}) was seen pending before the attacker's copy",
+                        victim.hash, victim.caller
+                    ),
+                    format!(
+                        "Attacker {} ({}) landed at index {} ahead of the victim at index {}",
+                        attacker.hash, attacker.caller, attacker.index, victim.index
+                    ),
+                    match attacker_seen {
+                        Some(_) => "Attacker's transaction appeared in the mempool after the victim's".to_string(),
+                        None => "Attacker's transaction never appeared in the mempool".to_string(),
+                    },
+                ];
+                if let Some(decoded) = victim.decoded_call.get() {
+                    let targets: Vec<String> = TARGET_ARGS
+                        .iter()
+                        .filter_map(|name| Some(format!("{}={}", name, decoded.args.get(name)?)))
+                        .collect();
+                    if !targets.is_empty() {
+                        evidence.push(format!("Both calls target {}", targets.join(", ")));
+                    }
+                }
+
+                let mut result = DetectionResult::detected(
+                    AttackPattern::FrontRunning,
+                    confidence,
+                    format!(
+                        "Front-running: {} copied pending {}.{} from {} and landed first",
+                        attacker.caller, victim.pallet, victim.call, victim.caller
+                    ),
+                    evidence,
+                );
+                result.truncate_evidence(self.max_evidence_entries);
+
+                correlations.push(FrontRunCorrelation {
+                    attacker_hash: attacker.hash.clone(),
+                    victim_hash: victim.hash.clone(),
+                    result,
+                });
+            }
+        }
+
+        // Finalized transactions are no longer pending
+        for tx in block_txs {
+            sightings.remove(&tx.hash);
+        }
+        drop(sightings);
+
+        let confirmed: Vec<String> = block_txs.iter().map(|tx| tx.hash.clone()).collect();
+        self.clear_confirmed(&confirmed).await;
+
+        if let Some(alert_manager) = &self.alert_manager {
+            for correlation in &correlations {
+                let Some(attacker) = block_txs.iter().find(|tx| tx.hash == correlation.attacker_hash) else {
+                    continue;
+                };
+                let ctx = TransactionContext {
+                    transaction: attacker.clone(),
+                    events: vec![],
+                    state_changes: vec![],
+                };
+                let severity = self.severity_cutoffs.severity_for(correlation.result.confidence);
+                let alert = Self::detection_to_alert(&correlation.result, &ctx, severity);
+                alert_manager.trigger_alert(alert).await;
+            }
+        }
+
+        correlations
+    }
+
     /// Analyze a transaction context with all enabled detectors
     pub async fn analyze_transaction(&self, ctx: &TransactionContext) -> Vec<DetectionResult> {
         let mut results = Vec::new();
 
         for detector in self.detectors.iter() {
             if detector.is_enabled() {
-                let result = detector.analyze_transaction(ctx).await;
+                let mut result = detector.analyze_transaction(ctx).await;
+                result.truncate_evidence(self.max_evidence_entries);
 
                 if result.detected {
                     tracing::info!(
@@ -95,7 +385,7 @@ impl MempoolMonitor {
 
                     // Trigger alert if alert manager is configured
                     if let Some(alert_manager) = &self.alert_manager {
-                        let severity = Self::confidence_to_severity(result.confidence);
+                        let severity = self.severity_cutoffs.severity_for(result.confidence);
                         let alert = Self::detection_to_alert(&result, ctx, severity);
                         alert_manager.trigger_alert(alert).await;
                     }
@@ -120,19 +410,6 @@ impl MempoolMonitor {
         all_results
     }
 
-    /// Convert confidence score to alert severity
-    fn confidence_to_severity(confidence: f64) -> AlertSeverity {
-        if confidence >= 0.9 {
-            AlertSeverity::Critical
-        } else if confidence >= 0.7 {
-            AlertSeverity::High
-        } else if confidence >= 0.5 {
-            AlertSeverity::Medium
-        } else {
-            AlertSeverity::Low
-        }
-    }
-
     /// Convert detection result to alert
     fn detection_to_alert(
         result: &DetectionResult,
@@ -147,8 +424,11 @@ impl MempoolMonitor {
         metadata.insert("call".to_string(), ctx.transaction.call.clone());
         metadata.insert("caller".to_string(), ctx.transaction.caller.clone());
         metadata.insert("confidence".to_string(), format!("{:.2}", result.confidence));
+        metadata.insert("cwe_ids".to_string(), result.pattern.cwe_labels().join(","));
+        metadata.insert("references".to_string(), result.pattern.references().join(","));
 
         Alert {
+            schema_version: SCHEMA_VERSION,
             id: format!("mempool-{}-{}", ctx.transaction.block_number, ctx.transaction.index),
             timestamp: ctx.transaction.timestamp,
             chain: "polkadot".to_string(), // TODO: Make configurable
@@ -164,9 +444,7 @@ impl MempoolMonitor {
     }
 
     /// Generate recommended actions based on attack pattern
-    fn generate_recommended_actions(pattern: &crate::types::AttackPattern) -> Vec<String> {
-        use crate::types::AttackPattern;
-
+    fn generate_recommended_actions(pattern: &AttackPattern) -> Vec<String> {
         match pattern {
             AttackPattern::FrontRunning => vec![
                 "Monitor subsequent transactions for profit extraction".to_string(),
@@ -188,6 +466,46 @@ impl MempoolMonitor {
                 "Monitor for repeated MEV extraction patterns".to_string(),
                 "Consider MEV-resistant transaction ordering".to_string(),
             ],
+            AttackPattern::AccountAnomaly => vec![
+                "Contact the account owner to confirm the activity".to_string(),
+                "Check for recent key exposure or compromised signers".to_string(),
+                "Monitor funds moved out of the account".to_string(),
+            ],
+            AttackPattern::WatchlistHit => vec![
+                "Escalate to compliance for review".to_string(),
+                "Trace further movements of the transferred funds".to_string(),
+                "Consider restricting the sending account".to_string(),
+            ],
+            AttackPattern::RuntimeUpgrade => vec![
+                "Confirm the upgrade was approved by governance".to_string(),
+                "Verify the code hash against the published release".to_string(),
+                "Re-run integration checks against the new runtime".to_string(),
+            ],
+            AttackPattern::Layering => vec![
+                "Trace the funds through every hop of the cycle".to_string(),
+                "Check the involved accounts for a common controller".to_string(),
+                "Escalate to compliance for review".to_string(),
+            ],
+            AttackPattern::AllowanceAbuse => vec![
+                "Warn the approving account and advise revoking remaining approvals".to_string(),
+                "Trace the drained assets from the destination account".to_string(),
+                "Check how the approval was solicited (phishing dApp or link)".to_string(),
+            ],
+            AttackPattern::Anomaly => vec![
+                "Compare the transaction with the caller's usual activity".to_string(),
+                "Review the deviating features listed in the evidence".to_string(),
+                "Monitor the caller for follow-up transactions".to_string(),
+            ],
+            AttackPattern::XcmSpam => vec![
+                "Check the destination chains' inbound XCM queues for congestion".to_string(),
+                "Review the sender's recent XCM activity for repeated fan-out".to_string(),
+                "Consider filtering the sender at the XCM barrier".to_string(),
+            ],
+            AttackPattern::BotActivity => vec![
+                "Review the caller's other recent transactions for the same template".to_string(),
+                "Check whether the repeated calls target a time-sensitive opportunity".to_string(),
+                "Consider rate limiting the caller".to_string(),
+            ],
             _ => vec![
                 "Investigate transaction for malicious activity".to_string(),
                 "Monitor related addresses".to_string(),
@@ -199,6 +517,7 @@ impl MempoolMonitor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::DecodedCall;
 
     fn create_test_transaction(hash: &str) -> Transaction {
         Transaction {
@@ -239,6 +558,30 @@ mod tests {
         assert_eq!(pending[1].hash, "0x3");
     }
 
+    #[tokio::test]
+    async fn test_prune_stale_removes_only_old_transactions() {
+        let monitor = MempoolMonitor::new(100).with_max_age(Duration::from_secs(300));
+        let now = 1_700_000_000;
+
+        let mut old = create_test_transaction("0xold");
+        old.timestamp = now - 301;
+        let mut fresh = create_test_transaction("0xfresh");
+        fresh.timestamp = now - 10;
+        monitor.add_transaction(old).await;
+        monitor.add_transaction(fresh).await;
+
+        assert_eq!(monitor.prune_stale(now).await, 1);
+        let pending = monitor.get_pending_transactions().await;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].hash, "0xfresh");
+
+        // Without a max age nothing is pruned
+        let unbounded = MempoolMonitor::new(100);
+        unbounded.add_transaction(create_test_transaction("0x1")).await;
+        assert_eq!(unbounded.prune_stale(now).await, 0);
+        assert_eq!(unbounded.pending_count().await, 1);
+    }
+
     #[tokio::test]
     async fn test_clear_confirmed() {
         let monitor = MempoolMonitor::new(100);
@@ -256,4 +599,93 @@ mod tests {
         let pending = monitor.get_pending_transactions().await;
         assert_eq!(pending[0].hash, "0x2");
     }
+
+    fn create_extrinsic(hash: &str, caller: &str, index: u32) -> ParsedTransaction {
+        ParsedTransaction {
+            hash: hash.to_string(),
+            block_number: 100,
+            block_hash: "0xblock".to_string(),
+            index,
+            caller: caller.to_string(),
+            pallet: "Omnipool".to_string(),
+            call: "sell".to_string(),
+            args: vec![],
+            signature: None,
+            nonce: None,
+            timestamp: 1234567890,
+            success: true,
+            decoded_call: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_front_run_correlated_with_pending_victim() {
+        let monitor = MempoolMonitor::new(100);
+
+        let victim = create_extrinsic("0xvictim", "alice", 1);
+        assert!(monitor.observe_pending(&victim).await);
+        assert!(!monitor.observe_pending(&victim).await);
+
+        // The attacker's copy was never seen pending but lands first
+        let attacker = create_extrinsic("0xattacker", "mallory", 0);
+        let correlations = monitor.correlate_block(&[attacker, victim]).await;
+
+        assert_eq!(correlations.len(), 1);
+        assert_eq!(correlations[0].attacker_hash, "0xattacker");
+        assert_eq!(correlations[0].victim_hash, "0xvictim");
+        assert_eq!(correlations[0].result.pattern, AttackPattern::FrontRunning);
+        assert!(correlations[0].result.confidence >= UNSEEN_ATTACKER_CONFIDENCE);
+
+        // Both transactions are finalized, so nothing is left to correlate
+        assert!(monitor.sightings.read().await.is_empty());
+    }
+
+    fn with_args(tx: ParsedTransaction, args: serde_json::Value) -> ParsedTransaction {
+        tx.decoded_call.set(DecodedCall {
+            pallet: tx.pallet.clone(),
+            call: tx.call.clone(),
+            args,
+        })
+        .unwrap();
+        tx
+    }
+
+    #[tokio::test]
+    async fn test_same_call_on_other_asset_not_flagged() {
+        let monitor = MempoolMonitor::new(100);
+
+        let sell = |asset_in: u32, amount: u64| {
+            serde_json::json!({ "asset_in": asset_in, "asset_out": 0, "amount": amount })
+        };
+        let victim = with_args(create_extrinsic("0xvictim", "alice", 2), sell(5, 1_000));
+        monitor.observe_pending(&victim).await;
+
+        // Same pallet and call, but a different pool asset
+        let unrelated = with_args(create_extrinsic("0xunrelated", "bob", 0), sell(7, 1_000));
+        // Same asset pair with a different amount is still a copy
+        let attacker = with_args(create_extrinsic("0xattacker", "mallory", 1), sell(5, 9_000));
+
+        let correlations = monitor.correlate_block(&[unrelated, attacker, victim]).await;
+        assert_eq!(correlations.len(), 1);
+        assert_eq!(correlations[0].attacker_hash, "0xattacker");
+        assert!(correlations[0]
+            .result
+            .evidence
+            .iter()
+            .any(|e| e == "Both calls target asset_in=5, asset_out=0"));
+    }
+
+    #[tokio::test]
+    async fn test_earlier_submission_not_flagged() {
+        let monitor = MempoolMonitor::new(100);
+
+        // The first caller was genuinely seen first and landed first
+        let first = create_extrinsic("0xfirst", "bob", 0);
+        let second = create_extrinsic("0xsecond", "alice", 1);
+        monitor.observe_pending(&first).await;
+        monitor.observe_pending(&second).await;
+
+        let correlations = monitor.correlate_block(&[first, second]).await;
+        assert!(correlations.is_empty());
+    }
 }
@@ -464,6 +464,11 @@ impl FeatureExtractor {
     pub fn get_caller_history(&self, caller: &str) -> Option<&CallerHistory> {
         self.caller_history.get(caller)
     }
+
+    /// Whether a transaction from `caller` has been through extraction
+    pub fn has_caller_history(&self, caller: &str) -> bool {
+        self.caller_history.contains_key(caller)
+    }
 }
 
 impl Default for FeatureExtractor {
@@ -491,17 +496,14 @@ mod tests {
                 pallet: "Balances".to_string(),
                 call: "transfer".to_string(),
                 args: vec![1, 2, 3, 4],
-                signature: Some("sig".to_string()),
+                signature: Some(b"sig".to_vec()),
                 nonce: Some(10),
                 timestamp: 1700000000,
                 success: true,
+                decoded_call: Default::default(),
             },
             events: vec![
-                ChainEvent {
-                    pallet: "Balances".to_string(),
-                    event_name: "Transfer".to_string(),
-                    event_data: None,
-                },
+                ChainEvent::new("Balances", "Transfer"),
             ],
             state_changes: vec![],
         };
@@ -519,16 +521,8 @@ mod tests {
     #[test]
     fn test_flash_loan_detection() {
         let events = vec![
-            ChainEvent {
-                pallet: "Lending".to_string(),
-                event_name: "Borrow".to_string(),
-                event_data: None,
-            },
-            ChainEvent {
-                pallet: "Lending".to_string(),
-                event_name: "Repay".to_string(),
-                event_data: None,
-            },
+            ChainEvent::new("Lending", "Borrow"),
+            ChainEvent::new("Lending", "Repay"),
         ];
 
         assert!(FeatureExtractor::detect_flash_loan_pattern(&events));
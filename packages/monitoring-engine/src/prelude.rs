@@ -0,0 +1,55 @@
+//! Stable public API of the monitoring engine
+//!
+//! Embedders should import from here rather than from module paths, which
+//! may move between releases:
+//!
+//! ```
+//! use monitoring_engine::prelude::*;
+//!
+//! fn custom_detectors() -> Vec<Box<dyn Detector + Send + Sync>> {
+//!     vec![Box::new(FlashLoanDetector::new()), Box::new(OmnipoolManipulationDetector::new())]
+//! }
+//!
+//! fn persisted(_: &Detection, _: &AlertRecord, _: Option<&DatabaseClient>) {}
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<()> {
+//! let config = MonitorConfig::westend();
+//! config.validate()?;
+//!
+//! let engine = MonitoringEngine::new(config);
+//! let stats: EngineStats = engine.get_stats().await;
+//! assert!(!stats.is_running);
+//!
+//! let detectors: AllDetectorStats = engine.get_detector_stats().await;
+//! assert!(detectors.detectors.iter().all(|d: &DetectorStatus| d.detections == 0));
+//! assert!(!custom_detectors().is_empty());
+//! # Ok(())
+//! # }
+//! ```
+
+pub use crate::alerts::{AlertExportFormat, AlertManager, Sink, SinkStats};
+pub use crate::backfill::BackfillStatus;
+pub use crate::block_source::BlockSource;
+pub use crate::database::models::{AlertRecord, Detection, DetectorAggregateStats};
+pub use crate::database::retry::WriteStats;
+pub use crate::database::DatabaseClient;
+pub use crate::detectors::{
+    AllowanceAbuseDetector, BridgeBalanceDetector, CollateralManipulationDetector,
+    CrossChainBridgeDetector, Detector, DetectorManifest, FeeAnomalyDetector,
+    FlashLoanDetector, FrontRunningDetector, LayeringDetector, LiquidationFrontRunningDetector,
+    LiquidityDrainDetector, MevDetector, NonceAnomalyDetector, OmnipoolManipulationDetector,
+    ProxyAbuseDetector, RuntimeUpgradeDetector, StateProofVerificationDetector,
+    TransactionFingerprintDetector, UnsupervisedAnomalyDetector, VolumeAnomalyDetector,
+    WatchlistDetector, XcmFanoutDetector,
+};
+pub use crate::feedback::FeedbackCounts;
+pub use crate::sensitivity::{AlertThresholds, ConfidenceHistogram, SensitivityProfile, SeverityCutoffs};
+pub use crate::types::{
+    Alert, AlertSeverity, AttackPattern, ChainEvent, DecodedCall, DetectionResult,
+    ParsedTransaction, StateChange, Transaction, TransactionContext, SCHEMA_VERSION,
+};
+pub use crate::{
+    AllDetectorStats, ChainInfo, ConnectedChain, DetectorStatus, EngineInfo, EngineStats, Error,
+    ErrorKind, MonitorConfig, MonitoringEngine, Result,
+};
@@ -0,0 +1,188 @@
+//! Transaction sampling under load
+//!
+//! On very busy chains analyzing a fraction of transactions can be enough for
+//! statistical coverage. Sampling reduces detection completeness: an attack
+//! in a skipped transaction is never seen by the detectors, and detectors that
+//! learn across transactions (volume, layering, anomaly) see a thinned stream.
+//! Skipped transactions are still stored and counted.
+//!
+//! Transactions on sensitive pallets and transfers at or above the value
+//! threshold are always analyzed. The decision is derived from the
+//! transaction hash, so re-processing a block (e.g. a backfill) samples the
+//! same transactions.
+//!
+//! Independently of sampling, the analysis can be scoped to a list of
+//! monitored pallets (e.g. only DeFi and bridge pallets on a general-purpose
+//! chain). Transactions on other pallets are never analyzed, except on the
+//! sensitive pallets, which stay in scope whatever the list.
+
+use crate::types::ParsedTransaction;
+
+/// Pallets whose calls are always analyzed: runtime upgrades, dispatch on
+/// behalf of other accounts, batches that can hide any call, and XCM
+pub const SENSITIVE_PALLETS: &[&str] = &[
+    "Sudo",
+    "System",
+    "ParachainSystem",
+    "Proxy",
+    "Multisig",
+    "Utility",
+    "PolkadotXcm",
+    "XcmPallet",
+];
+
+/// Default transfer amount (planck) at or above which a transaction is always
+/// analyzed: 1,000 tokens on a 12-decimal chain
+pub const DEFAULT_VALUE_THRESHOLD: u128 = 1_000 * 10u128.pow(12);
+
+/// Decides which transactions are analyzed when sampling is enabled
+#[derive(Debug, Clone)]
+pub struct TransactionSampler {
+    /// Fraction of transactions analyzed; `None` analyzes all of them
+    rate: Option<f64>,
+    value_threshold: u128,
+    /// Pallets analyzed at all; `None` analyzes every pallet
+    monitored_pallets: Option<Vec<String>>,
+}
+
+impl TransactionSampler {
+    /// Analyze roughly `rate` (0.0–1.0) of transactions, or all of them when `None`
+    pub fn new(rate: Option<f64>) -> Self {
+        Self {
+            rate,
+            value_threshold: DEFAULT_VALUE_THRESHOLD,
+            monitored_pallets: None,
+        }
+    }
+
+    /// Set the transfer amount at or above which transactions are always analyzed
+    pub fn with_value_threshold(mut self, value_threshold: u128) -> Self {
+        self.value_threshold = value_threshold;
+        self
+    }
+
+    /// Only analyze transactions on these pallets (matched case-insensitively)
+    /// and on [`SENSITIVE_PALLETS`]; `None` analyzes every pallet
+    pub fn with_monitored_pallets(mut self, pallets: Option<Vec<String>>) -> Self {
+        self.monitored_pallets = pallets;
+        self
+    }
+
+    /// Whether the transaction's pallet is in scope
+    pub fn is_monitored(&self, tx: &ParsedTransaction) -> bool {
+        is_sensitive(tx)
+            || self.monitored_pallets.as_ref().map_or(true, |pallets| {
+                pallets.iter().any(|pallet| pallet.eq_ignore_ascii_case(&tx.pallet))
+            })
+    }
+
+    /// Whether the transaction is analyzed regardless of the sample rate
+    pub fn is_forced(&self, tx: &ParsedTransaction) -> bool {
+        is_sensitive(tx)
+            || tx
+                .transfer_amount()
+                .is_some_and(|amount| amount >= self.value_threshold)
+    }
+
+    /// Whether the detectors should run on the transaction
+    pub fn should_analyze(&self, tx: &ParsedTransaction) -> bool {
+        if !self.is_monitored(tx) {
+            return false;
+        }
+        let Some(rate) = self.rate else {
+            return true;
+        };
+
+        rate >= 1.0 || self.is_forced(tx) || sample_point(&tx.hash) < rate
+    }
+}
+
+impl Default for TransactionSampler {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+/// Whether the transaction is on one of the [`SENSITIVE_PALLETS`]
+fn is_sensitive(tx: &ParsedTransaction) -> bool {
+    SENSITIVE_PALLETS
+        .iter()
+        .any(|pallet| pallet.eq_ignore_ascii_case(&tx.pallet))
+}
+
+/// Position of a transaction in [0, 1), uniformly distributed over hashes
+fn sample_point(tx_hash: &str) -> f64 {
+    let digest = blake3::hash(tx_hash.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest.as_bytes()[..8]);
+
+    // Top 53 bits, so the division is exact in an f64
+    (u64::from_le_bytes(bytes) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(hash: &str, pallet: &str, call: &str, args: Vec<u8>) -> ParsedTransaction {
+        ParsedTransaction {
+            hash: hash.to_string(),
+            block_number: 1,
+            block_hash: "0xblock1".to_string(),
+            index: 0,
+            caller: "alice".to_string(),
+            pallet: pallet.to_string(),
+            call: call.to_string(),
+            args,
+            signature: None,
+            nonce: None,
+            timestamp: 1234567890,
+            success: true,
+            decoded_call: Default::default(),
+        }
+    }
+
+    /// `Balances::transfer_keep_alive` to a 32-byte account
+    fn transfer_args(amount: u128) -> Vec<u8> {
+        use subxt::ext::codec::{Compact, Encode};
+
+        let mut args = vec![0u8];
+        args.extend_from_slice(&[9; 32]);
+        Compact(amount).encode_to(&mut args);
+        args
+    }
+
+    #[test]
+    fn test_forced_transactions() {
+        let sampler = TransactionSampler::new(Some(0.0)).with_value_threshold(1_000);
+
+        assert!(sampler.is_forced(&transaction("0x1", "sudo", "sudo", vec![])));
+        assert!(sampler.is_forced(&transaction("0x2", "Balances", "transfer_keep_alive", transfer_args(1_000))));
+        assert!(!sampler.is_forced(&transaction("0x3", "Balances", "transfer_keep_alive", transfer_args(999))));
+        assert!(!sampler.is_forced(&transaction("0x4", "Assets", "transfer", vec![])));
+    }
+
+    #[test]
+    fn test_monitored_pallets_scope_analysis() {
+        let sampler = TransactionSampler::default()
+            .with_monitored_pallets(Some(vec!["Omnipool".to_string(), "TokenGateway".to_string()]));
+
+        assert!(sampler.should_analyze(&transaction("0x1", "omnipool", "sell", vec![])));
+        assert!(!sampler.should_analyze(&transaction("0x2", "Timestamp", "set", vec![])));
+        // Sensitive pallets are analyzed even when left out of the list
+        assert!(sampler.should_analyze(&transaction("0x3", "Sudo", "sudo", vec![])));
+        assert!(sampler.should_analyze(&transaction("0x5", "System", "set_code", vec![])));
+
+        assert!(TransactionSampler::default().should_analyze(&transaction("0x4", "Timestamp", "set", vec![])));
+    }
+
+    #[test]
+    fn test_sample_rate_is_roughly_respected() {
+        let sampler = TransactionSampler::new(Some(0.25));
+        let analyzed = (0..4000)
+            .filter(|i| sampler.should_analyze(&transaction(&format!("0x{:x}", i), "Assets", "transfer", vec![])))
+            .count();
+
+        assert!((800..1200).contains(&analyzed), "analyzed {} of 4000", analyzed);
+    }
+}
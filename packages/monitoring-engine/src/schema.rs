@@ -0,0 +1,113 @@
+//! JSON Schema of the serialized alert contract
+//!
+//! The schemas are generated from [`Alert`] and [`DetectionResult`] with
+//! `schemars`, so they always match what the engine emits. Each carries the
+//! [`SCHEMA_VERSION`] it describes under `x-schema-version`; consumers can
+//! pin that version and validate payloads against the schema served at
+//! `/api/schema/alert` and `/api/schema/detection`.
+
+use crate::types::{Alert, DetectionResult, SCHEMA_VERSION};
+use schemars::schema::RootSchema;
+
+/// Extension key holding the described [`SCHEMA_VERSION`]
+pub const VERSION_KEY: &str = "x-schema-version";
+
+/// JSON Schema of a serialized [`Alert`]
+pub fn alert_schema() -> RootSchema {
+    versioned(schemars::schema_for!(Alert))
+}
+
+/// JSON Schema of a serialized [`DetectionResult`]
+pub fn detection_result_schema() -> RootSchema {
+    versioned(schemars::schema_for!(DetectionResult))
+}
+
+fn versioned(mut schema: RootSchema) -> RootSchema {
+    schema
+        .schema
+        .extensions
+        .insert(VERSION_KEY.to_string(), SCHEMA_VERSION.into());
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AlertSeverity, AttackPattern};
+    use jsonschema::JSONSchema;
+    use std::collections::HashMap;
+
+    fn compile(schema: RootSchema) -> JSONSchema {
+        JSONSchema::compile(&serde_json::to_value(schema).unwrap()).unwrap()
+    }
+
+    fn sample_alert() -> Alert {
+        Alert {
+            schema_version: SCHEMA_VERSION,
+            id: "alert-1".to_string(),
+            timestamp: 1_700_000_000,
+            chain: "westend".to_string(),
+            severity: AlertSeverity::Critical,
+            pattern: AttackPattern::FlashLoan,
+            description: "Borrow, swap, repay in one transaction".to_string(),
+            transaction_hash: Some("0xabc".to_string()),
+            block_number: None,
+            metadata: HashMap::from([("detector".to_string(), "Flash Loan Detector".to_string())]),
+            recommended_actions: vec!["Review transaction".to_string()],
+            acknowledged: false,
+        }
+    }
+
+    #[test]
+    fn test_sample_alert_validates() {
+        let alert = serde_json::to_value(sample_alert()).unwrap();
+
+        assert_eq!(alert["schema_version"], SCHEMA_VERSION);
+        assert!(compile(alert_schema()).is_valid(&alert));
+    }
+
+    #[test]
+    fn test_detection_result_validates() {
+        let result = DetectionResult::detected(
+            AttackPattern::Sandwich,
+            0.8,
+            "Victim swap wrapped by attacker swaps".to_string(),
+            vec!["Same pool, same block".to_string()],
+        )
+        .with_triggers(vec![0, 2], vec![]);
+        let result = serde_json::to_value(result).unwrap();
+
+        assert_eq!(result["schema_version"], SCHEMA_VERSION);
+        assert!(compile(detection_result_schema()).is_valid(&result));
+    }
+
+    #[test]
+    fn test_schema_rejects_contract_violations() {
+        let schema = compile(alert_schema());
+
+        let mut unknown_severity = serde_json::to_value(sample_alert()).unwrap();
+        unknown_severity["severity"] = "catastrophic".into();
+        assert!(!schema.is_valid(&unknown_severity));
+
+        let mut missing_field = serde_json::to_value(sample_alert()).unwrap();
+        missing_field.as_object_mut().unwrap().remove("chain");
+        assert!(!schema.is_valid(&missing_field));
+    }
+
+    #[test]
+    fn test_schemas_carry_version() {
+        for schema in [alert_schema(), detection_result_schema()] {
+            let schema = serde_json::to_value(schema).unwrap();
+            assert_eq!(schema[VERSION_KEY], SCHEMA_VERSION);
+        }
+    }
+
+    #[test]
+    fn test_alert_without_version_reads_as_current() {
+        let mut legacy = serde_json::to_value(sample_alert()).unwrap();
+        legacy.as_object_mut().unwrap().remove("schema_version");
+
+        let alert: Alert = serde_json::from_value(legacy).unwrap();
+        assert_eq!(alert.schema_version, SCHEMA_VERSION);
+    }
+}
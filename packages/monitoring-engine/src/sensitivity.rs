@@ -0,0 +1,442 @@
+//! Detector sensitivity profiles
+//!
+//! A single switch that tunes how eagerly detections become alerts.
+//! Each profile sets a per-detector confidence threshold (a detection alerts
+//! when its confidence is strictly greater than the threshold) and a scale
+//! applied to the detectors' value floors (e.g. the large withdrawal amount
+//! used by the liquidity drain detector).
+//!
+//! | Detector                  | Strict | Balanced | Lenient |
+//! |---------------------------|--------|----------|---------|
+//! | Volume Anomaly Detector   | 0.45   | 0.50     | 0.80    |
+//! | All other detectors       | 0.35   | 0.50     | 0.70    |
+//! | Value floor scale         | 0.5x   | 1.0x     | 2.0x    |
+//!
+//! `Balanced` matches the engine's historical behaviour. The volume detector
+//! scores routine transfers highly, so its Strict/Lenient thresholds sit
+//! higher than the rest. Explicit per-detector thresholds configured via
+//! `MonitorConfig::detector_thresholds` always take precedence over the profile.
+//! Analyst feedback (see [`crate::feedback`]) raises a detector's threshold on
+//! top of either.
+//!
+//! Once a detection alerts, `SeverityCutoffs` maps its confidence to a
+//! severity. The same cutoffs apply to block and mempool alerts. Evidence
+//! is capped at `max_evidence_entries` before it is persisted or alerted.
+
+use crate::feedback::FeedbackTracker;
+use crate::types::AlertSeverity;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Name of the detector with profile-specific thresholds
+const VOLUME_DETECTOR: &str = "Volume Anomaly Detector";
+
+/// Preset sensitivity for all detectors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SensitivityProfile {
+    /// Lower thresholds: more alerts, more false positives
+    Strict,
+    /// Default thresholds
+    #[default]
+    Balanced,
+    /// Higher thresholds: fewer alerts, may miss borderline attacks
+    Lenient,
+}
+
+impl SensitivityProfile {
+    /// Confidence threshold this profile sets for a detector
+    pub fn confidence_threshold(&self, detector_name: &str) -> f64 {
+        let is_volume = detector_name == VOLUME_DETECTOR;
+        match self {
+            SensitivityProfile::Strict if is_volume => 0.45,
+            SensitivityProfile::Strict => 0.35,
+            SensitivityProfile::Balanced => 0.5,
+            SensitivityProfile::Lenient if is_volume => 0.8,
+            SensitivityProfile::Lenient => 0.7,
+        }
+    }
+
+    /// Multiplier applied to detector value floors
+    pub fn value_floor_scale(&self) -> f64 {
+        match self {
+            SensitivityProfile::Strict => 0.5,
+            SensitivityProfile::Balanced => 1.0,
+            SensitivityProfile::Lenient => 2.0,
+        }
+    }
+}
+
+impl std::fmt::Display for SensitivityProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SensitivityProfile::Strict => write!(f, "strict"),
+            SensitivityProfile::Balanced => write!(f, "balanced"),
+            SensitivityProfile::Lenient => write!(f, "lenient"),
+        }
+    }
+}
+
+impl std::str::FromStr for SensitivityProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "strict" => Ok(SensitivityProfile::Strict),
+            "balanced" => Ok(SensitivityProfile::Balanced),
+            "lenient" => Ok(SensitivityProfile::Lenient),
+            other => Err(format!("unknown sensitivity profile '{}'", other)),
+        }
+    }
+}
+
+/// Minimum confidence for each alert severity
+///
+/// Confidences below `medium` map to Low. Cutoffs must lie in `[0, 1]` and
+/// strictly decrease from `critical` to `medium`; deserialization and
+/// parsing reject anything else.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawSeverityCutoffs")]
+pub struct SeverityCutoffs {
+    pub critical: f64,
+    pub high: f64,
+    pub medium: f64,
+}
+
+/// Unvalidated cutoffs as written in a config file
+#[derive(Deserialize)]
+struct RawSeverityCutoffs {
+    critical: f64,
+    high: f64,
+    medium: f64,
+}
+
+impl Default for SeverityCutoffs {
+    fn default() -> Self {
+        Self {
+            critical: 0.9,
+            high: 0.75,
+            medium: 0.6,
+        }
+    }
+}
+
+impl SeverityCutoffs {
+    /// Create validated cutoffs
+    pub fn new(critical: f64, high: f64, medium: f64) -> Result<Self, String> {
+        let cutoffs = Self { critical, high, medium };
+        cutoffs.validate()?;
+        Ok(cutoffs)
+    }
+
+    /// Check the cutoffs lie in `[0, 1]` and strictly decrease
+    pub fn validate(&self) -> Result<(), String> {
+        let cutoffs = [("critical", self.critical), ("high", self.high), ("medium", self.medium)];
+        for (name, value) in cutoffs {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("{} cutoff {} is outside [0, 1]", name, value));
+            }
+        }
+        if !(self.critical > self.high && self.high > self.medium) {
+            return Err(format!(
+                "severity cutoffs must decrease from critical to medium, got {}/{}/{}",
+                self.critical, self.high, self.medium
+            ));
+        }
+        Ok(())
+    }
+
+    /// Severity of an alert with this confidence
+    pub fn severity_for(&self, confidence: f64) -> AlertSeverity {
+        if confidence >= self.critical {
+            AlertSeverity::Critical
+        } else if confidence >= self.high {
+            AlertSeverity::High
+        } else if confidence >= self.medium {
+            AlertSeverity::Medium
+        } else {
+            AlertSeverity::Low
+        }
+    }
+}
+
+impl TryFrom<RawSeverityCutoffs> for SeverityCutoffs {
+    type Error = String;
+
+    fn try_from(raw: RawSeverityCutoffs) -> Result<Self, Self::Error> {
+        Self::new(raw.critical, raw.high, raw.medium)
+    }
+}
+
+/// Parses `critical,high,medium`, e.g. `0.9,0.75,0.6`
+impl std::str::FromStr for SeverityCutoffs {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split(',')
+            .map(|v| v.trim().parse::<f64>().map_err(|e| format!("'{}': {}", v.trim(), e)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match values[..] {
+            [critical, high, medium] => Self::new(critical, high, medium),
+            _ => Err(format!("expected 3 cutoffs (critical,high,medium), got {}", values.len())),
+        }
+    }
+}
+
+/// Default maximum evidence entries kept per detection
+pub const DEFAULT_MAX_EVIDENCE_ENTRIES: usize = 20;
+
+/// Resolved alert thresholds: a profile plus explicit per-detector overrides
+#[derive(Debug, Clone)]
+pub struct AlertThresholds {
+    profile: SensitivityProfile,
+    overrides: HashMap<String, f64>,
+    severity_cutoffs: SeverityCutoffs,
+    max_evidence_entries: usize,
+    record_floor: Option<f64>,
+    short_circuit_privileged: bool,
+    feedback: Option<Arc<FeedbackTracker>>,
+}
+
+impl Default for AlertThresholds {
+    fn default() -> Self {
+        Self::new(SensitivityProfile::default(), HashMap::new())
+    }
+}
+
+impl AlertThresholds {
+    pub fn new(profile: SensitivityProfile, overrides: HashMap<String, f64>) -> Self {
+        Self {
+            profile,
+            overrides,
+            severity_cutoffs: SeverityCutoffs::default(),
+            max_evidence_entries: DEFAULT_MAX_EVIDENCE_ENTRIES,
+            record_floor: None,
+            short_circuit_privileged: false,
+            feedback: None,
+        }
+    }
+
+    /// Use custom confidence cutoffs for alert severities
+    pub fn with_severity_cutoffs(mut self, severity_cutoffs: SeverityCutoffs) -> Self {
+        self.severity_cutoffs = severity_cutoffs;
+        self
+    }
+
+    /// Active sensitivity profile
+    pub fn profile(&self) -> SensitivityProfile {
+        self.profile
+    }
+
+    /// Raise thresholds of detectors whose alerts analysts marked as false positives
+    pub fn with_feedback(mut self, feedback: Arc<FeedbackTracker>) -> Self {
+        self.feedback = Some(feedback);
+        self
+    }
+
+    /// Confidence threshold for a detector (explicit override wins), plus any feedback shift
+    pub fn threshold_for(&self, detector_name: &str) -> f64 {
+        let configured = self
+            .overrides
+            .get(detector_name)
+            .copied()
+            .unwrap_or_else(|| self.profile.confidence_threshold(detector_name));
+        let shift = self
+            .feedback
+            .as_ref()
+            .map_or(0.0, |feedback| feedback.threshold_shift(detector_name));
+        configured + shift
+    }
+
+    /// Whether a detection with this confidence should raise an alert
+    pub fn should_alert(&self, detector_name: &str, confidence: f64) -> bool {
+        confidence > self.threshold_for(detector_name)
+    }
+
+    /// Severity of an alert raised with this confidence
+    pub fn severity_for(&self, confidence: f64) -> AlertSeverity {
+        self.severity_cutoffs.severity_for(confidence)
+    }
+
+    /// Cap the evidence entries kept per detection
+    pub fn with_max_evidence_entries(mut self, max_evidence_entries: usize) -> Self {
+        self.max_evidence_entries = max_evidence_entries;
+        self
+    }
+
+    /// Maximum evidence entries kept per detection
+    pub fn max_evidence_entries(&self) -> usize {
+        self.max_evidence_entries
+    }
+
+    /// Store results that do not alert down to this confidence
+    pub fn with_record_floor(mut self, record_floor: Option<f64>) -> Self {
+        self.record_floor = record_floor;
+        self
+    }
+
+    /// Whether a result that does not alert should still be stored as a near miss
+    pub fn should_record(&self, confidence: f64) -> bool {
+        self.record_floor
+            .is_some_and(|floor| confidence > 0.0 && confidence >= floor)
+    }
+
+    /// Skip the remaining detectors once a privileged pattern is detected
+    pub fn with_short_circuit_privileged(mut self, short_circuit: bool) -> Self {
+        self.short_circuit_privileged = short_circuit;
+        self
+    }
+
+    /// Whether detectors stop after a privileged pattern is detected
+    pub fn short_circuit_privileged(&self) -> bool {
+        self.short_circuit_privileged
+    }
+}
+
+/// Number of equal-width buckets in a confidence histogram
+pub const HISTOGRAM_BUCKETS: usize = 10;
+
+/// Distribution of the confidence scores a detector produced
+///
+/// Records every scored result, including those below the alert threshold,
+/// so operators can see where a threshold would cut the distribution.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceHistogram {
+    /// Bucket `i` counts confidences in `[i / 10, (i + 1) / 10)`; the last bucket includes 1.0
+    pub counts: [u64; HISTOGRAM_BUCKETS],
+    pub total: u64,
+}
+
+/// A single histogram bucket for API responses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistogramBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: u64,
+}
+
+impl ConfidenceHistogram {
+    /// Record a confidence score (clamped to `[0, 1]`)
+    pub fn record(&mut self, confidence: f64) {
+        let confidence = if confidence.is_nan() { 0.0 } else { confidence.clamp(0.0, 1.0) };
+        let index = ((confidence * HISTOGRAM_BUCKETS as f64) as usize).min(HISTOGRAM_BUCKETS - 1);
+        self.counts[index] += 1;
+        self.total += 1;
+    }
+
+    /// Buckets with their confidence bounds
+    pub fn buckets(&self) -> Vec<HistogramBucket> {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| HistogramBucket {
+                lower: i as f64 / HISTOGRAM_BUCKETS as f64,
+                upper: (i + 1) as f64 / HISTOGRAM_BUCKETS as f64,
+                count,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_bucketing() {
+        let mut histogram = ConfidenceHistogram::default();
+        for confidence in [0.0, 0.05, 0.55, 0.59, 0.9, 1.0, 1.5] {
+            histogram.record(confidence);
+        }
+
+        assert_eq!(histogram.total, 7);
+        assert_eq!(histogram.counts[0], 2);
+        assert_eq!(histogram.counts[5], 2);
+        assert_eq!(histogram.counts[9], 3);
+
+        let buckets = histogram.buckets();
+        assert_eq!(buckets.len(), HISTOGRAM_BUCKETS);
+        assert_eq!(buckets[5].lower, 0.5);
+        assert_eq!(buckets[5].count, 2);
+    }
+
+    #[test]
+    fn test_borderline_detection_by_profile() {
+        let strict = AlertThresholds::new(SensitivityProfile::Strict, HashMap::new());
+        let balanced = AlertThresholds::new(SensitivityProfile::Balanced, HashMap::new());
+        let lenient = AlertThresholds::new(SensitivityProfile::Lenient, HashMap::new());
+
+        assert!(strict.should_alert("MEV Detector", 0.55));
+        assert!(balanced.should_alert("MEV Detector", 0.55));
+        assert!(!lenient.should_alert("MEV Detector", 0.55));
+    }
+
+    #[test]
+    fn test_explicit_threshold_overrides_profile() {
+        let mut overrides = HashMap::new();
+        overrides.insert("MEV Detector".to_string(), 0.9);
+        let thresholds = AlertThresholds::new(SensitivityProfile::Strict, overrides);
+
+        assert_eq!(thresholds.threshold_for("MEV Detector"), 0.9);
+        assert!(!thresholds.should_alert("MEV Detector", 0.55));
+        // Detectors without an override still follow the profile
+        assert!(thresholds.should_alert("Flash Loan Detector", 0.55));
+    }
+
+    #[test]
+    fn test_feedback_raises_threshold_over_override() {
+        let mut overrides = HashMap::new();
+        overrides.insert("MEV Detector".to_string(), 0.6);
+        let feedback = Arc::new(FeedbackTracker::new());
+        let thresholds = AlertThresholds::new(SensitivityProfile::Balanced, overrides).with_feedback(feedback.clone());
+
+        feedback.record("d1", "MEV Detector", false);
+        feedback.record("d2", "MEV Detector", false);
+
+        let expected = 0.6 + 2.0 * crate::feedback::DEFAULT_FEEDBACK_STEP;
+        assert!((thresholds.threshold_for("MEV Detector") - expected).abs() < 1e-9);
+        assert_eq!(thresholds.threshold_for("Flash Loan Detector"), 0.5);
+    }
+
+    #[test]
+    fn test_custom_severity_cutoffs() {
+        let cautious = SeverityCutoffs::new(0.9, 0.8, 0.6).unwrap();
+        let aggressive = SeverityCutoffs::new(0.85, 0.7, 0.5).unwrap();
+
+        assert_eq!(cautious.severity_for(0.72), AlertSeverity::Medium);
+        assert_eq!(aggressive.severity_for(0.72), AlertSeverity::High);
+
+        let thresholds = AlertThresholds::default().with_severity_cutoffs(aggressive);
+        assert_eq!(thresholds.severity_for(0.72), AlertSeverity::High);
+        assert_eq!(AlertThresholds::default().severity_for(0.72), AlertSeverity::Medium);
+    }
+
+    #[test]
+    fn test_severity_cutoffs_validation() {
+        assert!(SeverityCutoffs::default().validate().is_ok());
+        assert!(SeverityCutoffs::new(0.9, 0.9, 0.6).is_err());
+        assert!(SeverityCutoffs::new(0.6, 0.75, 0.9).is_err());
+        assert!(SeverityCutoffs::new(1.2, 0.75, 0.6).is_err());
+        assert!(SeverityCutoffs::new(0.9, 0.75, -0.1).is_err());
+
+        assert_eq!(
+            "0.95, 0.8, 0.5".parse::<SeverityCutoffs>(),
+            SeverityCutoffs::new(0.95, 0.8, 0.5)
+        );
+        assert!("0.9,0.75".parse::<SeverityCutoffs>().is_err());
+
+        let parsed: Result<SeverityCutoffs, _> =
+            serde_json::from_str(r#"{"critical": 0.5, "high": 0.75, "medium": 0.6}"#);
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn test_profile_parsing() {
+        assert_eq!("STRICT".parse::<SensitivityProfile>().unwrap(), SensitivityProfile::Strict);
+        assert_eq!(SensitivityProfile::default(), SensitivityProfile::Balanced);
+        assert!("paranoid".parse::<SensitivityProfile>().is_err());
+    }
+}
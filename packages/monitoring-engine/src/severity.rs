@@ -0,0 +1,82 @@
+//! Conversions between the severity scales of the workspace
+//!
+//! Three crates grade findings: runtime alerts ([`AlertSeverity`]), SAFT
+//! static findings ([`saft_enhanced::Severity`]) and private vulnerability
+//! reports (`privacy_layer::types::Severity`). They share the
+//! `low`/`medium`/`high`/`critical` levels, which convert one-to-one.
+//!
+//! SAFT also has `Info`, which no other scale has. Converting it into an
+//! [`AlertSeverity`] fails rather than silently promoting an informational
+//! finding to an alert; callers that still want to raise it should map the
+//! error to [`AlertSeverity::Low`] explicitly. Report severities convert in
+//! the privacy layer behind its `monitoring` feature, so the engine doesn't
+//! depend on the proof system; SAFT and the privacy layer are converted
+//! through [`AlertSeverity`], since neither crate depends on the other.
+
+use crate::types::AlertSeverity;
+use crate::Error;
+use saft_enhanced::Severity as SaftSeverity;
+
+impl From<AlertSeverity> for SaftSeverity {
+    fn from(severity: AlertSeverity) -> Self {
+        match severity {
+            AlertSeverity::Low => SaftSeverity::Low,
+            AlertSeverity::Medium => SaftSeverity::Medium,
+            AlertSeverity::High => SaftSeverity::High,
+            AlertSeverity::Critical => SaftSeverity::Critical,
+        }
+    }
+}
+
+impl TryFrom<SaftSeverity> for AlertSeverity {
+    type Error = Error;
+
+    /// Fails for `Info`, which has no alert severity
+    fn try_from(severity: SaftSeverity) -> Result<Self, Self::Error> {
+        match severity {
+            SaftSeverity::Info => Err(Error::ParseError(
+                "SAFT severity 'info' has no alert severity".to_string(),
+            )),
+            SaftSeverity::Low => Ok(AlertSeverity::Low),
+            SaftSeverity::Medium => Ok(AlertSeverity::Medium),
+            SaftSeverity::High => Ok(AlertSeverity::High),
+            SaftSeverity::Critical => Ok(AlertSeverity::Critical),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEVELS: [AlertSeverity; 4] = [
+        AlertSeverity::Low,
+        AlertSeverity::Medium,
+        AlertSeverity::High,
+        AlertSeverity::Critical,
+    ];
+
+    #[test]
+    fn test_shared_levels_round_trip_through_saft() {
+        for level in LEVELS {
+            let saft = SaftSeverity::from(level);
+            assert_eq!(AlertSeverity::try_from(saft).unwrap(), level);
+        }
+    }
+
+    #[test]
+    fn test_saft_info_has_no_alert_severity() {
+        assert!(matches!(
+            AlertSeverity::try_from(SaftSeverity::Info),
+            Err(Error::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_conversions_keep_serialized_names() {
+        for level in LEVELS {
+            let name = serde_json::to_value(level).unwrap();
+            assert_eq!(serde_json::to_value(SaftSeverity::from(level)).unwrap(), name);
+        }
+    }
+}
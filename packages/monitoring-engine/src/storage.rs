@@ -0,0 +1,344 @@
+//! Typed storage keys for state change analysis
+//!
+//! A FRAME storage key starts with `twox_128(pallet prefix) ++
+//! twox_128(item name)`, followed by the hashed map keys. Those 32 bytes are
+//! not readable, so rather than sniffing key bytes for names, state changes
+//! are resolved against a table of known prefixes:
+//!
+//! - [`StorageKeyDecoder::default`] knows the account balance items detectors
+//!   care about (`System::Account`, `Balances::Account`, `Tokens::Accounts`,
+//!   `Assets::Account`) and works without a connection
+//! - [`StorageKeyDecoder::from_metadata`] adds every storage item of a runtime
+//!
+//! For balance items the free balance is decoded from the old and new
+//! values, so detectors can tell by how much an account changed.
+
+use crate::types::{StateChange, TransactionContext};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fmt;
+use subxt::ext::sp_core::hashing::twox_128;
+use subxt::Metadata;
+
+/// Length of the `twox_128(pallet) ++ twox_128(item)` prefix
+pub const STORAGE_PREFIX_LEN: usize = 32;
+
+/// Balance-holding items and the offset of the free balance (u128) in their value
+///
+/// `System::Account` holds an `AccountInfo` (four u32 counters, then
+/// `AccountData`); the others store the balance first.
+const BALANCE_ITEMS: &[(&str, &str, usize)] = &[
+    ("System", "Account", 16),
+    ("Balances", "Account", 0),
+    ("Tokens", "Accounts", 0),
+    ("Assets", "Account", 0),
+];
+
+/// Decoder over the well-known balance items, for callers without metadata
+static WELL_KNOWN: Lazy<StorageKeyDecoder> = Lazy::new(StorageKeyDecoder::default);
+
+/// A storage item, e.g. `System::Account`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StorageItem {
+    pub pallet: String,
+    pub item: String,
+    /// Offset of the free balance in the item's value, for balance items
+    balance_offset: Option<usize>,
+}
+
+impl StorageItem {
+    pub fn new(pallet: &str, item: &str) -> Self {
+        let balance_offset = BALANCE_ITEMS
+            .iter()
+            .find(|(p, i, _)| p.eq_ignore_ascii_case(pallet) && i.eq_ignore_ascii_case(item))
+            .map(|(_, _, offset)| *offset);
+
+        Self {
+            pallet: pallet.to_string(),
+            item: item.to_string(),
+            balance_offset,
+        }
+    }
+
+    /// Whether the item stores an account balance
+    pub fn is_balance(&self) -> bool {
+        self.balance_offset.is_some()
+    }
+
+    /// `twox_128(pallet) ++ twox_128(item)`
+    pub fn prefix(&self) -> [u8; STORAGE_PREFIX_LEN] {
+        storage_prefix(&self.pallet, &self.item)
+    }
+
+    /// Free balance in an encoded value of this item
+    fn balance(&self, value: &[u8]) -> Option<u128> {
+        let offset = self.balance_offset?;
+        let bytes = value.get(offset..offset + 16)?;
+        Some(u128::from_le_bytes(bytes.try_into().ok()?))
+    }
+}
+
+impl fmt::Display for StorageItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}", self.pallet, self.item)
+    }
+}
+
+/// A state change resolved to the storage item it touched
+#[derive(Debug, Clone)]
+pub struct TypedStateChange<'a> {
+    pub item: &'a StorageItem,
+    /// Hashed map keys after the prefix (empty for plain values)
+    pub map_key: &'a [u8],
+    pub change: &'a StateChange,
+}
+
+impl TypedStateChange<'_> {
+    /// Free balance before and after, for balance items
+    ///
+    /// A missing old value (a new account) counts as a zero balance; a
+    /// missing new value (a reaped account) too.
+    pub fn balance_change(&self) -> Option<BalanceChange> {
+        if !self.item.is_balance() {
+            return None;
+        }
+
+        let balance = |value: &Option<Vec<u8>>| match value {
+            Some(value) => self.item.balance(value),
+            None => Some(0),
+        };
+
+        Some(BalanceChange {
+            old: balance(&self.change.old_value)?,
+            new: balance(&self.change.new_value)?,
+        })
+    }
+}
+
+/// Free balance of an account before and after a state change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub old: u128,
+    pub new: u128,
+}
+
+impl BalanceChange {
+    /// Absolute amount the balance moved by
+    pub fn delta(&self) -> u128 {
+        self.old.abs_diff(self.new)
+    }
+
+    /// Whether the balance went down
+    pub fn is_decrease(&self) -> bool {
+        self.new < self.old
+    }
+}
+
+/// Resolves storage keys to the pallet and item they belong to
+#[derive(Debug, Clone)]
+pub struct StorageKeyDecoder {
+    items: HashMap<[u8; STORAGE_PREFIX_LEN], StorageItem>,
+}
+
+impl StorageKeyDecoder {
+    /// Decoder that knows no items
+    pub fn empty() -> Self {
+        Self { items: HashMap::new() }
+    }
+
+    /// Well-known balance items plus every storage item in the runtime metadata
+    pub fn from_metadata(metadata: &Metadata) -> Self {
+        let mut decoder = Self::default();
+        for pallet in metadata.pallets() {
+            let Some(storage) = pallet.storage() else {
+                continue;
+            };
+            for entry in storage.entries() {
+                decoder.register(StorageItem::new(storage.prefix(), entry.name()));
+            }
+        }
+        decoder
+    }
+
+    /// Shared decoder over the well-known balance items
+    pub fn well_known() -> &'static Self {
+        &WELL_KNOWN
+    }
+
+    /// Add an item, replacing any item with the same prefix
+    pub fn register(&mut self, item: StorageItem) {
+        self.items.insert(item.prefix(), item);
+    }
+
+    /// Known items
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Storage item a key belongs to, `None` for unknown prefixes
+    pub fn item(&self, key: &[u8]) -> Option<&StorageItem> {
+        let prefix: [u8; STORAGE_PREFIX_LEN] = key.get(..STORAGE_PREFIX_LEN)?.try_into().ok()?;
+        self.items.get(&prefix)
+    }
+
+    /// Resolve a state change, `None` when its key has an unknown prefix
+    pub fn decode<'a>(&'a self, change: &'a StateChange) -> Option<TypedStateChange<'a>> {
+        Some(TypedStateChange {
+            item: self.item(&change.key)?,
+            map_key: &change.key[STORAGE_PREFIX_LEN..],
+            change,
+        })
+    }
+}
+
+impl Default for StorageKeyDecoder {
+    /// Decoder over the well-known balance items
+    fn default() -> Self {
+        let mut decoder = Self::empty();
+        for (pallet, item, _) in BALANCE_ITEMS {
+            decoder.register(StorageItem::new(pallet, item));
+        }
+        decoder
+    }
+}
+
+impl TransactionContext {
+    /// State changes to items the decoder knows, paired with their index
+    pub fn typed_state_changes<'a>(
+        &'a self,
+        decoder: &'a StorageKeyDecoder,
+    ) -> impl Iterator<Item = (usize, TypedStateChange<'a>)> + 'a {
+        self.state_changes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, change)| Some((index, decoder.decode(change)?)))
+    }
+}
+
+/// `twox_128(pallet) ++ twox_128(item)`
+pub fn storage_prefix(pallet: &str, item: &str) -> [u8; STORAGE_PREFIX_LEN] {
+    let mut prefix = [0u8; STORAGE_PREFIX_LEN];
+    prefix[..16].copy_from_slice(&twox_128(pallet.as_bytes()));
+    prefix[16..].copy_from_slice(&twox_128(item.as_bytes()));
+    prefix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::account_info;
+    use scale_info::meta_type;
+    use subxt::ext::codec::{Decode, Encode};
+    use subxt::ext::frame_metadata::v15::{
+        CustomMetadata, ExtrinsicMetadata, OuterEnums, PalletMetadata, PalletStorageMetadata,
+        RuntimeMetadataV15, StorageEntryMetadata, StorageEntryModifier, StorageEntryType,
+    };
+    use subxt::ext::frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed, META_RESERVED};
+
+    /// `System::Account` key of Alice, as seen in block storage
+    const ALICE_SYSTEM_ACCOUNT_KEY: &str = "26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9\
+        de1e86a9a8c739864cf3cc5ec2bea59fd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+    #[test]
+    fn test_decodes_system_account_key() {
+        let key = hex::decode(ALICE_SYSTEM_ACCOUNT_KEY).unwrap();
+        let change = StateChange {
+            key,
+            old_value: Some(account_info(10_000)),
+            new_value: Some(account_info(2_500)),
+        };
+
+        let typed = StorageKeyDecoder::well_known().decode(&change).unwrap();
+        assert_eq!(typed.item.pallet, "System");
+        assert_eq!(typed.item.item, "Account");
+        assert_eq!(typed.item.to_string(), "System::Account");
+        // blake2_128_concat(AccountId32)
+        assert_eq!(typed.map_key.len(), 16 + 32);
+
+        let balance = typed.balance_change().unwrap();
+        assert_eq!(balance.delta(), 7_500);
+        assert!(balance.is_decrease());
+    }
+
+    #[test]
+    fn test_unknown_prefix_is_not_decoded() {
+        let change = StateChange {
+            key: b":code".to_vec(),
+            old_value: None,
+            new_value: Some(vec![0u8; 64]),
+        };
+        assert!(StorageKeyDecoder::well_known().decode(&change).is_none());
+
+        let mut key = storage_prefix("Treasury", "Proposals").to_vec();
+        key.extend_from_slice(&[1u8; 8]);
+        let change = StateChange { key, old_value: None, new_value: Some(vec![1]) };
+        assert!(StorageKeyDecoder::well_known().decode(&change).is_none());
+    }
+
+    /// Runtime metadata with a single `Treasury::ProposalCount` storage item
+    fn treasury_metadata() -> Metadata {
+        let pallets = vec![PalletMetadata {
+            name: "Treasury",
+            storage: Some(PalletStorageMetadata {
+                prefix: "Treasury",
+                entries: vec![StorageEntryMetadata {
+                    name: "ProposalCount",
+                    modifier: StorageEntryModifier::Default,
+                    ty: StorageEntryType::Plain(meta_type::<u32>()),
+                    default: vec![0, 0, 0, 0],
+                    docs: vec![],
+                }],
+            }),
+            calls: None,
+            event: None,
+            constants: vec![],
+            error: None,
+            index: 18,
+            docs: vec![],
+        }];
+        let extrinsic = ExtrinsicMetadata {
+            version: 4,
+            address_ty: meta_type::<()>(),
+            call_ty: meta_type::<()>(),
+            signature_ty: meta_type::<()>(),
+            extra_ty: meta_type::<()>(),
+            signed_extensions: vec![],
+        };
+        let outer_enums = OuterEnums {
+            call_enum_ty: meta_type::<()>(),
+            event_enum_ty: meta_type::<()>(),
+            error_enum_ty: meta_type::<()>(),
+        };
+        let runtime = RuntimeMetadataV15::new(
+            pallets,
+            extrinsic,
+            meta_type::<()>(),
+            vec![],
+            outer_enums,
+            CustomMetadata { map: Default::default() },
+        );
+
+        let bytes = RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V15(runtime)).encode();
+        Metadata::decode(&mut bytes.as_slice()).expect("valid metadata")
+    }
+
+    #[test]
+    fn test_decoder_from_metadata() {
+        let decoder = StorageKeyDecoder::from_metadata(&treasury_metadata());
+        assert_eq!(decoder.len(), BALANCE_ITEMS.len() + 1);
+
+        let change = StateChange {
+            key: storage_prefix("Treasury", "ProposalCount").to_vec(),
+            old_value: Some(3u32.encode()),
+            new_value: Some(4u32.encode()),
+        };
+        let typed = decoder.decode(&change).unwrap();
+        assert_eq!(typed.item.to_string(), "Treasury::ProposalCount");
+        assert!(typed.map_key.is_empty());
+        assert!(typed.balance_change().is_none());
+    }
+}
@@ -1,8 +1,10 @@
 //! Transaction extraction and parsing module
 //!
 //! This module is responsible for extracting transactions (extrinsics) from
-//! Substrate blocks and parsing their metadata.
+//! Substrate blocks and parsing their metadata. Event fields are decoded
+//! into named JSON with the runtime's type registry (see [`crate::decode`]).
 
+use crate::decode;
 use crate::types::{ChainEvent, ParsedTransaction, TransactionContext};
 use anyhow::{Context, Result};
 use std::sync::Arc;
@@ -10,6 +12,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use subxt::{
     backend::BlockRef,
     config::substrate::H256,
+    events::Phase,
     OnlineClient, PolkadotConfig,
 };
 use tracing::debug;
@@ -25,13 +28,16 @@ impl TransactionExtractor {
         Self { client }
     }
 
+    /// Node client the extractor reads from
+    pub fn client(&self) -> &OnlineClient<PolkadotConfig> {
+        &self.client
+    }
+
     /// Extract all transactions from a block
     ///
-    /// Returns a vector of ParsedTransaction objects, one for each extrinsic
-    /// in the block (including inherents like timestamp)
-    ///
-    /// MVP: For now, we just count extrinsics and create placeholder records.
-    /// Full parsing will be added in Phase 2 with proper metadata support.
+    /// Returns one ParsedTransaction per extrinsic in the block (including
+    /// inherents like timestamp), with its call arguments decoded using the
+    /// runtime metadata (see [`decode::decode_block`]).
     pub async fn extract_from_block(
         &self,
         block_hash: H256,
@@ -43,55 +49,22 @@ impl TransactionExtractor {
             hex::encode(block_hash.0)
         );
 
-        // Get the block
-        let block = self
-            .client
-            .blocks()
-            .at(BlockRef::from_hash(block_hash))
-            .await
-            .context("Failed to get block")?;
-
-        // Get extrinsics count
-        let extrinsics = block.extrinsics().await.context("Failed to get extrinsics")?;
-        let extrinsic_count = extrinsics.iter().count();
-
-        debug!(
-            "Block #{} contains {} extrinsics",
-            block_number, extrinsic_count
-        );
-
-        // For MVP: Create placeholder transaction records
-        // We'll add full parsing in Phase 2
-        let mut transactions = Vec::new();
+        let extrinsics = self.raw_extrinsics(block_hash).await?;
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
 
-        for index in 0..extrinsic_count {
-            let hash = format!(
-                "0x{}",
-                hex::encode(blake3::hash(format!("{}-{}", block_number, index).as_bytes()).as_bytes())
-            );
-
-            transactions.push(ParsedTransaction {
-                hash,
-                block_number,
-                block_hash: format!("0x{}", hex::encode(block_hash.0)),
-                index: index as u32,
-                caller: format!("extrinsic_{}", index),
-                pallet: "system".to_string(), // Placeholder
-                call: "extrinsic".to_string(), // Placeholder
-                args: Vec::new(),
-                signature: None,
-                nonce: None,
-                timestamp,
-                success: true,
-            });
-        }
+        let transactions = decode::decode_block(
+            &self.client.metadata(),
+            &extrinsics,
+            block_number,
+            &format!("0x{}", hex::encode(block_hash.0)),
+            timestamp,
+        )?;
 
         debug!(
-            "Created {} transaction records from block #{}",
+            "Decoded {} transactions from block #{}",
             transactions.len(),
             block_number
         );
@@ -99,14 +72,42 @@ impl TransactionExtractor {
         Ok(transactions)
     }
 
+    /// Raw SCALE-encoded extrinsics of a block, without decoding them
+    ///
+    /// Used to capture blocks that [`Self::extract_from_block`] could not
+    /// decode, so they can be replayed later.
+    pub async fn raw_extrinsics(&self, block_hash: H256) -> Result<Vec<Vec<u8>>> {
+        self.client
+            .backend()
+            .block_body(block_hash)
+            .await
+            .context("Failed to get block body")?
+            .context("Block body not found")
+    }
+
+    /// Parse an extrinsic pending in the transaction pool
+    ///
+    /// See [`decode::decode_extrinsic`]; the transaction is stamped with the
+    /// current time.
+    pub fn parse_pending(&self, bytes: &[u8]) -> Result<ParsedTransaction> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        Ok(decode::decode_extrinsic(&self.client.metadata(), bytes, timestamp)?)
+    }
+
     /// Create full context for a transaction including associated events
     pub fn create_context(
         transaction: ParsedTransaction,
         all_events: &[ChainEvent],
     ) -> TransactionContext {
-        // For now, include all events since we simplified ChainEvent structure
-        // TODO: Re-add extrinsic_index filtering when event extraction is improved
-        let tx_events: Vec<ChainEvent> = all_events.to_vec();
+        let tx_events: Vec<ChainEvent> = all_events
+            .iter()
+            .filter(|event| event.extrinsic_index == Some(transaction.index))
+            .cloned()
+            .collect();
 
         debug!(
             "Transaction {} context created with {} events",
@@ -135,9 +136,9 @@ impl TransactionExtractor {
             .context("Failed to get block for events")?;
 
         let events = block.events().await.context("Failed to get events")?;
+        let metadata = self.client.metadata();
 
         let mut chain_events = Vec::new();
-        let mut event_index = 0u32;
 
         for event_details in events.iter() {
             let event = event_details?;
@@ -145,18 +146,25 @@ impl TransactionExtractor {
             let pallet_name = event.pallet_name().to_string();
             let event_name = event.variant_name().to_string();
 
-            // Get the extrinsic index if this event is associated with one
-            // Note: field_bytes() returns the raw event data, we'll parse extrinsic_index later if needed
-            // Try to parse event data as JSON (simplified for now)
-            let event_data_json = None; // TODO: Parse event fields into JSON
-
-            chain_events.push(ChainEvent {
-                pallet: pallet_name,
-                event_name,
-                event_data: event_data_json,
-            });
-
-            event_index += 1;
+            // Decode the event's fields into named JSON using the runtime types
+            let fields: Vec<(Option<&str>, u32)> = event
+                .event_metadata()
+                .variant
+                .fields
+                .iter()
+                .map(|field| (field.name.as_deref(), field.ty.id))
+                .collect();
+            let event_data = decode::decode_fields(event.field_bytes(), &fields, metadata.types());
+
+            let mut chain_event = ChainEvent::new(pallet_name, event_name)
+                .at(block_number, event.index())
+                .with_data(event.field_bytes().to_vec())
+                .with_topics(event.topics().iter().map(|topic| format!("{:?}", topic)).collect())
+                .with_event_data(event_data);
+            if let Phase::ApplyExtrinsic(extrinsic_index) = event.phase() {
+                chain_event = chain_event.with_extrinsic_index(extrinsic_index);
+            }
+            chain_events.push(chain_event);
         }
 
         debug!(
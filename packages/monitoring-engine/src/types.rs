@@ -1,10 +1,26 @@
 //! Core types for the monitoring engine
 
+use crate::decode;
+use crate::storage::StorageKeyDecoder;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::OnceLock;
+use subxt::ext::codec::{Compact, Decode};
+use subxt::Metadata;
+
+/// Version of the serialized [`Alert`] and [`DetectionResult`]
+///
+/// Bumped whenever a field is removed, renamed or changes meaning, so
+/// consumers can pin the contract (see [`crate::schema`]).
+pub const SCHEMA_VERSION: u32 = 1;
+
+fn current_schema_version() -> u32 {
+    SCHEMA_VERSION
+}
 
 /// Severity level for alerts
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum AlertSeverity {
     Low,
@@ -24,8 +40,22 @@ impl std::fmt::Display for AlertSeverity {
     }
 }
 
+impl std::str::FromStr for AlertSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(AlertSeverity::Low),
+            "medium" => Ok(AlertSeverity::Medium),
+            "high" => Ok(AlertSeverity::High),
+            "critical" => Ok(AlertSeverity::Critical),
+            other => Err(format!("unknown severity '{}'", other)),
+        }
+    }
+}
+
 /// Type of attack pattern detected
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AttackPattern {
     /// Flash loan attack
@@ -56,6 +86,22 @@ pub enum AttackPattern {
     LiquidityDrain,
     /// Collateral manipulation (Hydration)
     CollateralManipulation,
+    /// Abnormal account behaviour (e.g. nonce jumps after key compromise)
+    AccountAnomaly,
+    /// Funds sent to a watchlisted (sanctioned or known scam) address
+    WatchlistHit,
+    /// Runtime code upgrade (`set_code`, `CodeUpdated`)
+    RuntimeUpgrade,
+    /// Funds cycled through related addresses to obscure their origin
+    Layering,
+    /// Large or unlimited asset approval drained by the delegate
+    AllowanceAbuse,
+    /// Behavior far from recent activity, without a known attack signature
+    Anomaly,
+    /// One transaction flooding other chains with outbound XCM messages
+    XcmSpam,
+    /// Automated submission of the same transaction at a high rate
+    BotActivity,
     /// Unknown pattern
     Unknown,
 }
@@ -77,14 +123,86 @@ impl std::fmt::Display for AttackPattern {
             AttackPattern::OmnipoolManipulation => write!(f, "Omnipool Manipulation"),
             AttackPattern::LiquidityDrain => write!(f, "Liquidity Drain"),
             AttackPattern::CollateralManipulation => write!(f, "Collateral Manipulation"),
+            AttackPattern::AccountAnomaly => write!(f, "Account Anomaly"),
+            AttackPattern::WatchlistHit => write!(f, "Watchlist Hit"),
+            AttackPattern::RuntimeUpgrade => write!(f, "Runtime Upgrade"),
+            AttackPattern::Layering => write!(f, "Layering"),
+            AttackPattern::AllowanceAbuse => write!(f, "Allowance Abuse"),
+            AttackPattern::Anomaly => write!(f, "Anomaly"),
+            AttackPattern::XcmSpam => write!(f, "XCM Spam"),
+            AttackPattern::BotActivity => write!(f, "Bot Activity"),
             AttackPattern::Unknown => write!(f, "Unknown"),
         }
     }
 }
 
+/// Taxonomy mapping for SOC correlation
+impl AttackPattern {
+    /// CWE identifiers (MITRE Common Weakness Enumeration) related to this pattern
+    pub fn cwe_ids(&self) -> &'static [u32] {
+        match self {
+            // Business logic abuse within a single transaction
+            AttackPattern::FlashLoan => &[841, 682],
+            // Transaction ordering dependence
+            AttackPattern::Mev => &[362],
+            AttackPattern::FrontRunning => &[362],
+            AttackPattern::Sandwich => &[362],
+            // Trusting manipulable price data
+            AttackPattern::OracleManipulation => &[345, 20],
+            AttackPattern::GovernanceAttack => &[284, 841],
+            AttackPattern::Reentrancy => &[1265, 841],
+            AttackPattern::VolumeAnomaly => &[400],
+            AttackPattern::SuspiciousApproval => &[285],
+            AttackPattern::CrossChainBridge => &[345, 347],
+            AttackPattern::StateProofManipulation => &[347, 345],
+            AttackPattern::OmnipoolManipulation => &[682, 345],
+            AttackPattern::LiquidityDrain => &[841, 682],
+            AttackPattern::CollateralManipulation => &[682, 1284],
+            AttackPattern::AccountAnomaly => &[287],
+            // Interaction with an untrusted party
+            AttackPattern::WatchlistHit => &[346],
+            // Replacement of the executing code
+            AttackPattern::RuntimeUpgrade => &[494],
+            // Obfuscated value flow
+            AttackPattern::Layering => &[656],
+            // Users misled into granting over-broad authorization
+            AttackPattern::AllowanceAbuse => &[451, 285],
+            // No known weakness, only a deviation from normal behavior
+            AttackPattern::Anomaly => &[693],
+            // Uncontrolled consumption of other chains' message queues
+            AttackPattern::XcmSpam => &[400, 770],
+            // Missing rate limiting on repeated automated requests
+            AttackPattern::BotActivity => &[799, 770],
+            AttackPattern::Unknown => &[693],
+        }
+    }
+
+    /// Reference URLs for the related CWE entries
+    pub fn references(&self) -> Vec<String> {
+        self.cwe_ids()
+            .iter()
+            .map(|id| format!("https://cwe.mitre.org/data/definitions/{}.html", id))
+            .collect()
+    }
+
+    /// CWE identifiers formatted as `CWE-<id>`
+    pub fn cwe_labels(&self) -> Vec<String> {
+        self.cwe_ids().iter().map(|id| format!("CWE-{}", id)).collect()
+    }
+
+    /// Whether the pattern is a privileged chain operation that is noteworthy
+    /// on its own, after which the remaining detectors may be skipped
+    pub fn is_privileged(&self) -> bool {
+        matches!(self, AttackPattern::RuntimeUpgrade)
+    }
+}
+
 /// Security alert
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Alert {
+    /// Version of this layout ([`SCHEMA_VERSION`]); absent in alerts stored before it was added
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
     /// Unique alert ID
     pub id: String,
     /// Timestamp when alert was triggered
@@ -134,17 +252,84 @@ pub struct Transaction {
 }
 
 /// A blockchain event
+///
+/// Carries both the raw SCALE-encoded fields and, when the runtime types
+/// could decode them, the fields as named JSON. Build one with
+/// [`ChainEvent::new`] and the `with_*` methods.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainEvent {
+    /// Block the event was emitted in
+    #[serde(default)]
+    pub block_number: u64,
+    /// Position of the event in the block's event list
+    #[serde(default)]
+    pub event_index: u32,
+    /// Extrinsic that emitted the event, `None` for block initialization
+    /// and finalization events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extrinsic_index: Option<u32>,
     /// Pallet name
     pub pallet: String,
     /// Event variant name
     pub event_name: String,
+    /// Raw SCALE-encoded event fields
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data: Vec<u8>,
+    /// Event topics, hex-encoded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
     /// Event data (parsed as JSON if possible)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub event_data: Option<serde_json::Value>,
 }
 
+impl ChainEvent {
+    /// Create an event with no position, raw data or decoded data
+    pub fn new(pallet: impl Into<String>, event_name: impl Into<String>) -> Self {
+        Self {
+            block_number: 0,
+            event_index: 0,
+            extrinsic_index: None,
+            pallet: pallet.into(),
+            event_name: event_name.into(),
+            data: Vec::new(),
+            topics: Vec::new(),
+            event_data: None,
+        }
+    }
+
+    /// Set the block and the position of the event within it
+    pub fn at(mut self, block_number: u64, event_index: u32) -> Self {
+        self.block_number = block_number;
+        self.event_index = event_index;
+        self
+    }
+
+    /// Set the extrinsic that emitted the event
+    pub fn with_extrinsic_index(mut self, extrinsic_index: u32) -> Self {
+        self.extrinsic_index = Some(extrinsic_index);
+        self
+    }
+
+    /// Set the raw SCALE-encoded fields
+    pub fn with_data(mut self, data: Vec<u8>) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Set the topics
+    pub fn with_topics(mut self, topics: Vec<String>) -> Self {
+        self.topics = topics;
+        self
+    }
+
+    /// Set the decoded fields
+    pub fn with_event_data(mut self, event_data: serde_json::Value) -> Self {
+        self.event_data = Some(event_data);
+        self
+    }
+}
+
 /// A parsed transaction extracted from a block
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedTransaction {
@@ -172,6 +357,114 @@ pub struct ParsedTransaction {
     pub timestamp: u64,
     /// Whether the transaction succeeded
     pub success: bool,
+    /// Decoded call, filled in on the first `decode_call`
+    #[serde(skip)]
+    pub decoded_call: OnceLock<DecodedCall>,
+}
+
+/// A call decoded with the runtime metadata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecodedCall {
+    /// Pallet name as declared in the metadata
+    pub pallet: String,
+    /// Call name as declared in the metadata
+    pub call: String,
+    /// Named call arguments (see [`crate::decode`] for the JSON layout)
+    pub args: serde_json::Value,
+}
+
+/// `Balances` calls whose arguments start with `dest: MultiAddress`
+const DEST_FIRST_TRANSFERS: &[&str] = &[
+    "transfer",
+    "transfer_keep_alive",
+    "transfer_allow_death",
+    "transfer_all",
+];
+
+impl ParsedTransaction {
+    /// Decode the call name and named arguments using the runtime metadata
+    ///
+    /// The pallet and call are matched case-insensitively. The result is
+    /// cached, so later calls don't decode the arguments again; failures are
+    /// not cached.
+    pub fn decode_call(&self, metadata: &Metadata) -> crate::Result<&DecodedCall> {
+        if let Some(decoded) = self.decoded_call.get() {
+            return Ok(decoded);
+        }
+
+        let decoded = decode::decode_call(metadata, &self.pallet, &self.call, &self.args)?;
+        Ok(self.decoded_call.get_or_init(|| decoded))
+    }
+
+    /// Destination account of a `Balances` transfer, decoded from the call arguments
+    ///
+    /// Reads the `dest` argument of the decoded call when available, and
+    /// otherwise the SCALE layout of `transfer*` (`dest` first) and
+    /// `force_transfer` (`source`, then `dest`). Only 32-byte `MultiAddress`
+    /// variants (`Id`, `Address32`) are returned; other pallets and
+    /// address kinds return `None`.
+    pub fn transfer_destination(&self) -> Option<[u8; 32]> {
+        let force = self.balances_transfer()?;
+        if let Some(decoded) = self.decoded_call.get() {
+            return decode::json_account(decoded.args.get("dest")?);
+        }
+
+        let args = if force {
+            self.args.get(multi_address_len(&self.args)?..)?
+        } else {
+            self.args.as_slice()
+        };
+
+        match args.first()? {
+            // MultiAddress::Id / MultiAddress::Address32
+            0 | 3 => args.get(1..33)?.try_into().ok(),
+            _ => None,
+        }
+    }
+
+    /// Amount of a `Balances` transfer, decoded from the call arguments
+    ///
+    /// Uses the same sources as [`Self::transfer_destination`]: the decoded
+    /// `value` argument, or the compact `value` following the destination.
+    /// `transfer_all` has no amount and returns `None`.
+    pub fn transfer_amount(&self) -> Option<u128> {
+        let force = self.balances_transfer()?;
+        if self.call.eq_ignore_ascii_case("transfer_all") {
+            return None;
+        }
+        if let Some(decoded) = self.decoded_call.get() {
+            return json_amount(decoded.args.get("value")?);
+        }
+
+        let dest_offset = if force { multi_address_len(&self.args)? } else { 0 };
+        let dest = self.args.get(dest_offset..)?;
+        let mut value = dest.get(multi_address_len(dest)?..)?;
+        Compact::<u128>::decode(&mut value).ok().map(|amount| amount.0)
+    }
+
+    /// Whether the call is a `Balances` transfer with a `source` before `dest`
+    /// (`force_transfer`); `None` for other calls
+    fn balances_transfer(&self) -> Option<bool> {
+        if !self.pallet.eq_ignore_ascii_case("balances") {
+            return None;
+        }
+
+        let call = self.call.to_lowercase();
+        if call == "force_transfer" {
+            Some(true)
+        } else {
+            DEST_FIRST_TRANSFERS.contains(&call.as_str()).then_some(false)
+        }
+    }
+}
+
+/// Encoded length of a `MultiAddress` with a fixed-size payload
+fn multi_address_len(bytes: &[u8]) -> Option<usize> {
+    match bytes.first()? {
+        0 | 3 => Some(33),
+        4 => Some(21),
+        _ => None,
+    }
 }
 
 /// Full context for a transaction including associated events
@@ -185,6 +478,122 @@ pub struct TransactionContext {
     pub state_changes: Vec<StateChange>,
 }
 
+impl From<ParsedTransaction> for TransactionContext {
+    /// Context for a transaction whose events are not known
+    fn from(transaction: ParsedTransaction) -> Self {
+        Self {
+            transaction,
+            events: vec![],
+            state_changes: vec![],
+        }
+    }
+}
+
+/// Event names that move an amount between accounts
+const TRANSFER_EVENTS: &[&str] = &["transfer", "transferred"];
+
+/// Event names that change a single account's balance
+const BALANCE_CHANGE_EVENTS: &[&str] = &[
+    "deposit", "withdraw", "minted", "burned", "reserved", "unreserved", "slashed", "issued",
+];
+
+impl TransactionContext {
+    /// Best-effort estimate of the value moved by this transaction
+    ///
+    /// Sources, in order of preference:
+    /// 1. The sum of `amount` fields on transfer events (`Transfer`, `Transferred`)
+    /// 2. The largest `amount` on other balance-change events (`Deposit`,
+    ///    `Withdraw`, ...). The maximum rather than the sum is used since a
+    ///    single transfer often emits a matching withdraw/deposit pair.
+    /// 3. The largest free balance delta across account balance state changes
+    ///    (`System::Account`, `Balances::Account`, `Tokens::Accounts`,
+    ///    `Assets::Account`)
+    ///
+    /// Limitations:
+    /// - Amounts are in each asset's smallest unit and are summed without
+    ///   price conversion, so mixed-asset transactions are only indicative.
+    /// - Events carry amounts only when the block pipeline decoded their fields.
+    /// - Other storage (e.g. pool reserves) is opaque and ignored; returns
+    ///   `None` when nothing can be decoded.
+    pub fn total_value_moved(&self) -> Option<u128> {
+        let event_amounts = |names: &[&str]| {
+            self.events
+                .iter()
+                .filter(|e| {
+                    let name = e.event_name.to_lowercase();
+                    names.iter().any(|n| name == *n)
+                })
+                .filter_map(|e| e.event_data.as_ref()?.get("amount").and_then(json_amount))
+                .collect::<Vec<u128>>()
+        };
+
+        let transfers = event_amounts(TRANSFER_EVENTS);
+        if !transfers.is_empty() {
+            return Some(transfers.iter().fold(0u128, |acc, a| acc.saturating_add(*a)));
+        }
+
+        if let Some(max) = event_amounts(BALANCE_CHANGE_EVENTS).into_iter().max() {
+            return Some(max);
+        }
+
+        self.state_changes.iter().filter_map(StateChange::balance_delta).max()
+    }
+}
+
+/// Fee charged for a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeePaid {
+    /// Total fee charged, tip included
+    pub actual_fee: u128,
+    /// Tip the sender offered for priority
+    pub tip: u128,
+}
+
+impl TransactionContext {
+    /// Fee and tip from the `TransactionPayment::TransactionFeePaid` event
+    ///
+    /// `None` when the event is missing or its fields were not decoded
+    /// (unsigned transactions pay no fee).
+    pub fn fee_paid(&self) -> Option<FeePaid> {
+        let data = self
+            .events
+            .iter()
+            .find(|e| {
+                e.pallet.eq_ignore_ascii_case("transactionpayment")
+                    && e.event_name.eq_ignore_ascii_case("transactionfeepaid")
+            })?
+            .event_data
+            .as_ref()?;
+
+        Some(FeePaid {
+            actual_fee: data.get("actual_fee").and_then(json_amount)?,
+            tip: data.get("tip").and_then(json_amount).unwrap_or(0),
+        })
+    }
+}
+
+/// Decode an event amount encoded as a number or a decimal string (large u128 values)
+pub(crate) fn json_amount(value: &serde_json::Value) -> Option<u128> {
+    match value {
+        serde_json::Value::Number(n) => n
+            .as_u64()
+            .map(u128::from)
+            .or_else(|| n.as_f64().filter(|f| *f >= 0.0).map(|f| f as u128)),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// SCALE-encoded `AccountInfo` holding `free`, every other field zeroed
+#[cfg(test)]
+pub(crate) fn account_info(free: u128) -> Vec<u8> {
+    // Four u32 counters precede `data.free`
+    let mut value = vec![0u8; 16];
+    value.extend_from_slice(&free.to_le_bytes());
+    value.extend_from_slice(&[0u8; 48]);
+    value
+}
+
 /// A state change in the blockchain
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateChange {
@@ -196,9 +605,23 @@ pub struct StateChange {
     pub new_value: Option<Vec<u8>>,
 }
 
+impl StateChange {
+    /// Free balance delta of a change to a well-known account balance item
+    ///
+    /// Resolved through [`StorageKeyDecoder::well_known`]; `None` for any
+    /// other storage, which is opaque without metadata.
+    pub fn balance_delta(&self) -> Option<u128> {
+        let change = StorageKeyDecoder::well_known().decode(self)?;
+        Some(change.balance_change()?.delta())
+    }
+}
+
 /// Pattern matching result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DetectionResult {
+    /// Version of this layout ([`SCHEMA_VERSION`])
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
     /// Whether a pattern was detected
     pub detected: bool,
     /// Confidence level (0.0 to 1.0)
@@ -211,18 +634,25 @@ pub struct DetectionResult {
     pub evidence: Vec<String>,
     /// Additional metadata
     pub metadata: HashMap<String, String>,
+    /// Indices into `TransactionContext::events` that contributed to the detection
+    pub triggering_events: Vec<usize>,
+    /// Indices into `TransactionContext::state_changes` that contributed to the detection
+    pub triggering_state_changes: Vec<usize>,
 }
 
 impl DetectionResult {
     /// Create a new detection result indicating no pattern was found
     pub fn no_detection() -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             detected: false,
             confidence: 0.0,
             pattern: AttackPattern::Unknown,
             description: "No suspicious pattern detected".to_string(),
             evidence: Vec::new(),
             metadata: HashMap::new(),
+            triggering_events: Vec::new(),
+            triggering_state_changes: Vec::new(),
         }
     }
 
@@ -239,12 +669,31 @@ impl DetectionResult {
         evidence: Vec<String>,
     ) -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             detected: true,
             confidence,
             pattern,
             description,
             evidence,
             metadata: HashMap::new(),
+            triggering_events: Vec::new(),
+            triggering_state_changes: Vec::new(),
+        }
+    }
+
+    /// Record the events and state changes that triggered the detection
+    pub fn with_triggers(mut self, events: Vec<usize>, state_changes: Vec<usize>) -> Self {
+        self.triggering_events = events;
+        self.triggering_state_changes = state_changes;
+        self
+    }
+
+    /// Keep at most `max` evidence entries, replacing the rest with a "... N more" marker
+    pub fn truncate_evidence(&mut self, max: usize) {
+        if self.evidence.len() > max {
+            let overflow = self.evidence.len() - max;
+            self.evidence.truncate(max);
+            self.evidence.push(format!("... {} more", overflow));
         }
     }
 }
@@ -252,6 +701,22 @@ impl DetectionResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::storage::storage_prefix;
+
+    #[test]
+    fn test_truncate_evidence() {
+        let evidence = (0..25).map(|i| format!("entry {}", i)).collect();
+        let mut result =
+            DetectionResult::detected(AttackPattern::Mev, 0.8, "test".to_string(), evidence);
+
+        result.truncate_evidence(30);
+        assert_eq!(result.evidence.len(), 25);
+
+        result.truncate_evidence(10);
+        assert_eq!(result.evidence.len(), 11);
+        assert_eq!(result.evidence[9], "entry 9");
+        assert_eq!(result.evidence[10], "... 15 more");
+    }
 
     #[test]
     fn test_severity_ordering() {
@@ -275,4 +740,262 @@ mod tests {
         assert!(detected.detected);
         assert_eq!(detected.confidence, 0.95);
     }
+
+    #[test]
+    fn test_flash_loan_taxonomy() {
+        assert_eq!(AttackPattern::FlashLoan.cwe_ids(), &[841, 682]);
+        assert_eq!(
+            AttackPattern::FlashLoan.references(),
+            vec![
+                "https://cwe.mitre.org/data/definitions/841.html".to_string(),
+                "https://cwe.mitre.org/data/definitions/682.html".to_string(),
+            ]
+        );
+        assert_eq!(AttackPattern::FlashLoan.cwe_labels(), vec!["CWE-841", "CWE-682"]);
+    }
+
+    #[test]
+    fn test_every_pattern_has_taxonomy_mapping() {
+        // Exhaustive match: adding a variant fails to compile until it is listed here
+        fn listed(pattern: &AttackPattern) -> bool {
+            match pattern {
+                AttackPattern::FlashLoan
+                | AttackPattern::Mev
+                | AttackPattern::FrontRunning
+                | AttackPattern::Sandwich
+                | AttackPattern::OracleManipulation
+                | AttackPattern::GovernanceAttack
+                | AttackPattern::Reentrancy
+                | AttackPattern::VolumeAnomaly
+                | AttackPattern::SuspiciousApproval
+                | AttackPattern::CrossChainBridge
+                | AttackPattern::StateProofManipulation
+                | AttackPattern::OmnipoolManipulation
+                | AttackPattern::LiquidityDrain
+                | AttackPattern::CollateralManipulation
+                | AttackPattern::AccountAnomaly
+                | AttackPattern::WatchlistHit
+                | AttackPattern::RuntimeUpgrade
+                | AttackPattern::Layering
+                | AttackPattern::AllowanceAbuse
+                | AttackPattern::Anomaly
+                | AttackPattern::XcmSpam
+                | AttackPattern::BotActivity
+                | AttackPattern::Unknown => true,
+            }
+        }
+
+        let all = [
+            AttackPattern::FlashLoan,
+            AttackPattern::Mev,
+            AttackPattern::FrontRunning,
+            AttackPattern::Sandwich,
+            AttackPattern::OracleManipulation,
+            AttackPattern::GovernanceAttack,
+            AttackPattern::Reentrancy,
+            AttackPattern::VolumeAnomaly,
+            AttackPattern::SuspiciousApproval,
+            AttackPattern::CrossChainBridge,
+            AttackPattern::StateProofManipulation,
+            AttackPattern::OmnipoolManipulation,
+            AttackPattern::LiquidityDrain,
+            AttackPattern::CollateralManipulation,
+            AttackPattern::AccountAnomaly,
+            AttackPattern::WatchlistHit,
+            AttackPattern::RuntimeUpgrade,
+            AttackPattern::Layering,
+            AttackPattern::AllowanceAbuse,
+            AttackPattern::Anomaly,
+            AttackPattern::XcmSpam,
+            AttackPattern::BotActivity,
+            AttackPattern::Unknown,
+        ];
+
+        for pattern in all.iter() {
+            assert!(listed(pattern));
+            assert!(!pattern.cwe_ids().is_empty(), "{} has no CWE mapping", pattern);
+            assert_eq!(pattern.references().len(), pattern.cwe_ids().len());
+        }
+    }
+
+    fn context_with(events: Vec<ChainEvent>, state_changes: Vec<StateChange>) -> TransactionContext {
+        TransactionContext {
+            transaction: ParsedTransaction {
+                hash: "0x01".to_string(),
+                block_number: 1,
+                block_hash: "0xblock1".to_string(),
+                index: 0,
+                caller: "alice".to_string(),
+                pallet: "Balances".to_string(),
+                call: "transfer_keep_alive".to_string(),
+                args: vec![],
+                signature: None,
+                nonce: Some(1),
+                timestamp: 1234567890,
+                success: true,
+                decoded_call: Default::default(),
+            },
+            events,
+            state_changes,
+        }
+    }
+
+    fn event(pallet: &str, name: &str, data: serde_json::Value) -> ChainEvent {
+        ChainEvent::new(pallet, name).with_event_data(data)
+    }
+
+    #[test]
+    fn test_event_builder_keeps_raw_and_decoded_data() {
+        let event = ChainEvent::new("Balances", "Transfer")
+            .at(42, 3)
+            .with_extrinsic_index(1)
+            .with_data(vec![0xaa; 8])
+            .with_topics(vec!["0x01".to_string()])
+            .with_event_data(serde_json::json!({ "amount": "1000" }));
+
+        assert_eq!(event.block_number, 42);
+        assert_eq!(event.event_index, 3);
+        assert_eq!(event.extrinsic_index, Some(1));
+        assert_eq!(event.data, vec![0xaa; 8]);
+        assert_eq!(event.topics, vec!["0x01".to_string()]);
+        assert_eq!(event.event_data.as_ref().unwrap()["amount"], "1000");
+
+        // Events stored before the raw fields existed still deserialize
+        let stored: ChainEvent = serde_json::from_value(serde_json::json!({
+            "pallet": "System",
+            "event_name": "ExtrinsicSuccess",
+        }))
+        .unwrap();
+        assert!(stored.data.is_empty());
+        assert_eq!(stored.extrinsic_index, None);
+        assert!(stored.event_data.is_none());
+    }
+
+    #[test]
+    fn test_value_moved_from_transfer_event() {
+        let ctx = context_with(
+            vec![
+                event("Balances", "Withdraw", serde_json::json!({ "amount": 1_000 })),
+                event("Balances", "Transfer", serde_json::json!({ "amount": "25000000000000000000" })),
+                event("Balances", "Deposit", serde_json::json!({ "amount": 1_000 })),
+            ],
+            vec![],
+        );
+
+        // Fee withdraw/deposit events do not inflate the transfer amount
+        assert_eq!(ctx.total_value_moved(), Some(25_000_000_000_000_000_000));
+    }
+
+    #[test]
+    fn test_value_moved_from_account_state_change() {
+        let mut key = storage_prefix("System", "Account").to_vec();
+        key.extend_from_slice(&[7u8; 48]);
+        let ctx = context_with(
+            vec![],
+            vec![StateChange {
+                key,
+                old_value: Some(account_info(10_000)),
+                new_value: Some(account_info(2_500)),
+            }],
+        );
+
+        assert_eq!(ctx.total_value_moved(), Some(7_500));
+    }
+
+    #[test]
+    fn test_value_moved_from_tokens_state_change() {
+        // `Tokens::Accounts` stores the free balance first
+        let balance = |free: u128| {
+            let mut value = free.to_le_bytes().to_vec();
+            value.extend_from_slice(&[0u8; 32]);
+            value
+        };
+        let mut key = storage_prefix("Tokens", "Accounts").to_vec();
+        key.extend_from_slice(&[7u8; 56]);
+        let change = StateChange {
+            key,
+            old_value: Some(balance(1_000)),
+            new_value: Some(balance(51_000)),
+        };
+
+        assert_eq!(change.balance_delta(), Some(50_000));
+        assert_eq!(context_with(vec![], vec![change]).total_value_moved(), Some(50_000));
+    }
+
+    #[test]
+    fn test_opaque_state_changes_have_no_value() {
+        let ctx = context_with(
+            vec![event("System", "ExtrinsicSuccess", serde_json::json!({}))],
+            vec![StateChange {
+                key: vec![0xde, 0xad, 0xbe, 0xef],
+                old_value: Some(vec![1, 2, 3, 4]),
+                new_value: Some(vec![9, 9, 9, 9, 9, 9, 9, 9]),
+            }],
+        );
+
+        assert_eq!(ctx.total_value_moved(), None);
+    }
+
+    #[test]
+    fn test_fee_paid_from_event() {
+        let ctx = context_with(
+            vec![
+                event("Balances", "Withdraw", serde_json::json!({ "amount": 1_000 })),
+                event(
+                    "TransactionPayment",
+                    "TransactionFeePaid",
+                    serde_json::json!({ "who": "alice", "actual_fee": "150000000", "tip": 50_000_000 }),
+                ),
+            ],
+            vec![],
+        );
+
+        assert_eq!(
+            ctx.fee_paid(),
+            Some(FeePaid {
+                actual_fee: 150_000_000,
+                tip: 50_000_000,
+            })
+        );
+        assert_eq!(context_with(vec![], vec![]).fee_paid(), None);
+    }
+
+    #[test]
+    fn test_force_transfer_destination() {
+        let mut args = vec![0u8];
+        args.extend_from_slice(&[1u8; 32]);
+        args.push(0);
+        args.extend_from_slice(&[2u8; 32]);
+        args.push(0x04); // Compact(1)
+
+        let mut ctx = context_with(vec![], vec![]);
+        ctx.transaction.call = "force_transfer".to_string();
+        ctx.transaction.args = args;
+
+        assert_eq!(ctx.transaction.transfer_destination(), Some([2u8; 32]));
+        assert_eq!(ctx.transaction.transfer_amount(), Some(1));
+
+        ctx.transaction.pallet = "Assets".to_string();
+        assert_eq!(ctx.transaction.transfer_destination(), None);
+        assert_eq!(ctx.transaction.transfer_amount(), None);
+    }
+
+    #[test]
+    fn test_transfer_from_decoded_call() {
+        let mut ctx = context_with(vec![], vec![]);
+        ctx.transaction.call = "force_transfer".to_string();
+        ctx.transaction.args = vec![];
+        ctx.transaction.decoded_call = OnceLock::from(DecodedCall {
+            pallet: "Balances".to_string(),
+            call: "force_transfer".to_string(),
+            args: serde_json::json!({
+                "source": { "Id": format!("0x{}", "01".repeat(32)) },
+                "dest": { "Id": format!("0x{}", "02".repeat(32)) },
+                "value": "340282366920938463463374607431768211455",
+            }),
+        });
+
+        assert_eq!(ctx.transaction.transfer_destination(), Some([2u8; 32]));
+        assert_eq!(ctx.transaction.transfer_amount(), Some(u128::MAX));
+    }
 }
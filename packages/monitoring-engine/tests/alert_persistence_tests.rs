@@ -0,0 +1,91 @@
+// Integration tests for persistent alert history
+
+use monitoring_engine::alerts::{AlertManager, SUPPRESSED_COUNT_KEY};
+use monitoring_engine::database::{models::AlertRecord, DatabaseClient};
+use monitoring_engine::*;
+use std::sync::Arc;
+
+#[path = "common/mod.rs"]
+#[allow(dead_code)]
+mod common;
+use common::*;
+
+#[test]
+fn test_alert_record_round_trip() {
+    let mut alert = create_test_alert(AlertSeverity::High, AttackPattern::FlashLoan);
+    alert.metadata.insert("detector".to_string(), "Flash Loan Detector".to_string());
+
+    let record = AlertRecord::from_alert(&alert).unwrap();
+    assert_eq!(record.severity, "high");
+    assert_eq!(record.attack_pattern, "flash_loan");
+
+    let restored = record.to_alert().unwrap();
+    assert_eq!(restored.id, alert.id);
+    assert_eq!(restored.timestamp, alert.timestamp);
+    assert_eq!(restored.severity, alert.severity);
+    assert_eq!(restored.pattern, alert.pattern);
+    assert_eq!(restored.metadata, alert.metadata);
+    assert_eq!(restored.recommended_actions, alert.recommended_actions);
+}
+
+#[tokio::test]
+#[ignore] // Run with: TEST_DATABASE_URL=... cargo test -- --ignored
+async fn test_alert_persistence_round_trip() {
+    let database_url = test_database_or_skip!();
+    let db = Arc::new(
+        DatabaseClient::new(&database_url, 2)
+            .await
+            .expect("Failed to connect to test database"),
+    );
+
+    let alert = create_test_alert(AlertSeverity::Critical, AttackPattern::Sandwich);
+    let alert_id = alert.id.clone();
+
+    // Trigger through one manager
+    let manager = AlertManager::new(AlertSeverity::Low, None).with_database(db.clone());
+    manager.trigger_alert(alert).await;
+    assert!(manager.acknowledge_alert(&alert_id).await);
+
+    // A fresh manager (simulating a restart) restores it with the acknowledgment
+    let restarted = AlertManager::new(AlertSeverity::Low, None).with_database(db);
+    assert!(restarted.load_from_database(1000).await >= 1);
+
+    let restored = restarted
+        .get_recent_alerts(1000)
+        .await
+        .into_iter()
+        .find(|a| a.id == alert_id)
+        .expect("Alert should be restored from the database");
+
+    assert_eq!(restored.severity, AlertSeverity::Critical);
+    assert_eq!(restored.pattern, AttackPattern::Sandwich);
+    assert!(restored.acknowledged);
+    assert_eq!(restored.recommended_actions, vec!["Review transaction".to_string()]);
+}
+
+#[tokio::test]
+#[ignore] // Run with: TEST_DATABASE_URL=... cargo test -- --ignored
+async fn test_reinserting_alert_keeps_acknowledgment() {
+    let database_url = test_database_or_skip!();
+    let db = DatabaseClient::new(&database_url, 2)
+        .await
+        .expect("Failed to connect to test database");
+
+    let mut alert = create_test_alert(AlertSeverity::High, AttackPattern::FlashLoan);
+    db.insert_alert(&AlertRecord::from_alert(&alert).unwrap()).await.unwrap();
+    assert!(db.acknowledge_alert(&alert.id).await.unwrap());
+
+    // Persisted again, unacknowledged in memory, with updated metadata
+    alert.metadata.insert(SUPPRESSED_COUNT_KEY.to_string(), "3".to_string());
+    db.insert_alert(&AlertRecord::from_alert(&alert).unwrap()).await.unwrap();
+
+    let stored = db
+        .get_alerts(1000)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|a| a.alert_id == alert.id)
+        .expect("Alert should be stored");
+    assert!(stored.acknowledged);
+    assert_eq!(stored.metadata[SUPPRESSED_COUNT_KEY], "3");
+}
@@ -0,0 +1,229 @@
+// Integration tests for REST API limits (timeouts, pagination caps, body size)
+// and structured error responses
+
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App, HttpResponse};
+use monitoring_engine::api::{
+    api_scope, with_timeout, ApiError, ErrorBody, MAX_JSON_BODY_BYTES, MAX_PAGE_SIZE,
+};
+use monitoring_engine::*;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[path = "common/mod.rs"]
+#[allow(dead_code)]
+mod common;
+use common::*;
+
+async fn slow_handler() -> HttpResponse {
+    with_timeout(Duration::from_millis(50), async {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        HttpResponse::Ok().finish()
+    })
+    .await
+}
+
+fn test_engine() -> Arc<MonitoringEngine> {
+    Arc::new(MonitoringEngine::new(test_config()))
+}
+
+#[actix_web::test]
+async fn test_slow_handler_returns_503_after_timeout() {
+    let app = test::init_service(App::new().route("/slow", web::get().to(slow_handler))).await;
+
+    let started = Instant::now();
+    let req = test::TestRequest::get().uri("/slow").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(started.elapsed() < Duration::from_secs(2), "Handler should be cut off at the timeout");
+}
+
+#[actix_web::test]
+async fn test_export_row_cap_enforced() {
+    let app = test::init_service(App::new().service(api_scope(test_engine(), Instant::now()))).await;
+
+    let over_cap = format!("/api/export/json?limit={}", MAX_PAGE_SIZE + 1);
+    let req = test::TestRequest::get().uri(&over_cap).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let csv_over_cap = format!("/api/export/csv?limit={}", MAX_PAGE_SIZE * 10);
+    let req = test::TestRequest::get().uri(&csv_over_cap).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Within the cap the request passes validation (no database in this test)
+    let at_cap = format!("/api/export/json?limit={}", MAX_PAGE_SIZE);
+    let req = test::TestRequest::get().uri(&at_cap).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[actix_web::test]
+async fn test_alerts_pagination() {
+    let engine = test_engine();
+    for _ in 0..3 {
+        engine
+            .alert_manager
+            .trigger_alert(create_test_alert(AlertSeverity::High, AttackPattern::Mev))
+            .await;
+    }
+
+    let app = test::init_service(App::new().service(api_scope(engine, Instant::now()))).await;
+
+    let req = test::TestRequest::get().uri("/api/alerts?limit=2").to_request();
+    let first_page: Vec<Alert> = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(first_page.len(), 2);
+
+    let req = test::TestRequest::get().uri("/api/alerts?limit=2&offset=2").to_request();
+    let second_page: Vec<Alert> = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(second_page.len(), 1);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/alerts?limit={}", MAX_PAGE_SIZE + 1))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_oversized_body_rejected() {
+    let app = test::init_service(App::new().service(api_scope(test_engine(), Instant::now()))).await;
+
+    let body = serde_json::json!({
+        "chain_name": "x".repeat(MAX_JSON_BODY_BYTES + 1)
+    });
+    let req = test::TestRequest::post()
+        .uri("/api/chains/switch")
+        .set_json(&body)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+async fn failing_db_handler() -> std::result::Result<HttpResponse, ApiError> {
+    Err(Error::DatabaseError(
+        "connection to postgresql://nexus:hunter2@db:5432/nexus refused".to_string(),
+    )
+    .into())
+}
+
+#[actix_web::test]
+async fn test_database_error_returns_structured_503() {
+    let app = test::init_service(App::new().route("/fail", web::get().to(failing_db_handler))).await;
+
+    let req = test::TestRequest::get().uri("/fail").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let body: ErrorBody = test::read_body_json(resp).await;
+    assert_eq!(body.error.code, "database_unavailable");
+    // Connection details stay in the server logs
+    assert!(!body.error.message.contains("hunter2"));
+    assert!(!body.error.message.contains("postgresql://"));
+}
+
+#[actix_web::test]
+async fn test_validation_errors_use_structured_body() {
+    let app = test::init_service(App::new().service(api_scope(test_engine(), Instant::now()))).await;
+
+    let req = test::TestRequest::get().uri("/api/alerts?limit=abc").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let body: ErrorBody = test::read_body_json(resp).await;
+    assert_eq!(body.error.code, "bad_request");
+    assert!(body.error.message.contains("limit"));
+}
+
+#[actix_web::test]
+async fn test_detector_histogram_endpoint() {
+    let app = test::init_service(App::new().service(api_scope(test_engine(), Instant::now()))).await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/detectors/MEV%20Detector/histogram")
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["detector"], "MEV Detector");
+    assert_eq!(body["total"], 0);
+    assert_eq!(body["buckets"].as_array().unwrap().len(), 10);
+
+    let req = test::TestRequest::get()
+        .uri("/api/detectors/Unknown/histogram")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_alert_history_export() {
+    let engine = test_engine();
+    engine
+        .alert_manager
+        .trigger_alert(create_test_alert(AlertSeverity::High, AttackPattern::Mev))
+        .await;
+
+    let app = test::init_service(App::new().service(api_scope(engine, Instant::now()))).await;
+
+    let req = test::TestRequest::get().uri("/api/alerts/export?format=csv").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "text/csv");
+    let body = test::read_body(resp).await;
+    assert_eq!(String::from_utf8_lossy(&body).lines().count(), 2);
+
+    let req = test::TestRequest::get().uri("/api/alerts/export").to_request();
+    let alerts: Vec<serde_json::Value> = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(alerts.len(), 1);
+
+    let req = test::TestRequest::get().uri("/api/alerts/export?format=xml").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_info_reports_version_and_uptime() {
+    let app = test::init_service(App::new().service(api_scope(test_engine(), Instant::now()))).await;
+
+    let req = test::TestRequest::get().uri("/api/info").to_request();
+    let info: EngineInfo = test::call_and_read_body_json(&app, req).await;
+
+    assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+    assert!(!info.git_hash.is_empty());
+    assert!(info.started_at > 0);
+    assert!(info.uptime_seconds < 60);
+    assert_eq!(info.chains.len(), 1);
+    assert_eq!(info.chains[0].name, "development");
+    assert!(!info.chains[0].connected);
+    assert!(!info.database_attached);
+}
+
+#[actix_web::test]
+async fn test_detector_manifests() {
+    let app = test::init_service(App::new().service(api_scope(test_engine(), Instant::now()))).await;
+
+    let req = test::TestRequest::get().uri("/api/detectors/manifest").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let manifests: Vec<serde_json::Value> = test::read_body_json(resp).await;
+    let runtime_upgrade = manifests
+        .iter()
+        .find(|m| m["name"] == "Runtime Upgrade Detector")
+        .expect("Runtime upgrade detector should be registered");
+    assert_eq!(runtime_upgrade["patterns"], serde_json::json!(["runtime_upgrade"]));
+    assert_eq!(runtime_upgrade["relevant_pallets"], serde_json::json!(["System"]));
+    // Always alerts, so no threshold applies
+    assert!(runtime_upgrade["alert_threshold"].is_null());
+}
+
+#[actix_web::test]
+async fn test_backfill_status_not_found_before_any_backfill() {
+    let app = test::init_service(App::new().service(api_scope(test_engine(), Instant::now()))).await;
+
+    let req = test::TestRequest::get().uri("/api/backfill/status").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
@@ -16,6 +16,7 @@ pub fn test_config() -> MonitorConfig {
         min_alert_severity: AlertSeverity::Low,
         buffer_size: 100,
         max_reconnect_attempts: 3,
+        ..MonitorConfig::default()
     }
 }
 
@@ -32,6 +33,7 @@ pub fn create_test_alert(severity: AlertSeverity, pattern: AttackPattern) -> Ale
     let description = format!("Test alert for {:?}", pattern);
 
     Alert {
+        schema_version: SCHEMA_VERSION,
         id: uuid::Uuid::new_v4().to_string(),
         timestamp: chrono::Utc::now().timestamp() as u64,
         chain: "test-chain".to_string(),
@@ -68,3 +70,18 @@ macro_rules! skip_if_no_chain {
         }
     };
 }
+
+/// Skip test if no test database is configured
+#[macro_export]
+macro_rules! test_database_or_skip {
+    () => {
+        match std::env::var("TEST_DATABASE_URL") {
+            Ok(url) => url,
+            Err(_) => {
+                eprintln!("⚠️  Skipping test: TEST_DATABASE_URL not set");
+                eprintln!("   Point it at a TimescaleDB instance with schema.sql applied");
+                return;
+            }
+        }
+    };
+}
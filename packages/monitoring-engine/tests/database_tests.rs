@@ -0,0 +1,512 @@
+// Integration tests for typed database row mapping, backfill checkpoints, schema checks, retention, graph export,
+// pool state history, correlated trends and detection feedback
+
+use monitoring_engine::backfill::run_backfill;
+use monitoring_engine::database::{
+    models::{Detection, DetectionFeedback, GraphRow, HydrationPoolState, Transaction, TransactionGraph},
+    DatabaseClient, REQUIRED_AGGREGATES, REQUIRED_TABLES,
+};
+use monitoring_engine::Error;
+use tokio::sync::RwLock;
+
+#[path = "common/mod.rs"]
+#[allow(dead_code)]
+mod common;
+
+#[tokio::test]
+#[ignore] // Run with: TEST_DATABASE_URL=... cargo test -- --ignored
+async fn test_transaction_and_export_rows_map_by_column_name() {
+    let database_url = test_database_or_skip!();
+    let db = DatabaseClient::new(&database_url, 2)
+        .await
+        .expect("Failed to connect to test database");
+
+    let tx_hash = format!("0x{}", uuid::Uuid::new_v4().simple());
+    let tx = Transaction {
+        timestamp: chrono::Utc::now(),
+        tx_hash: tx_hash.clone(),
+        block_number: 4242,
+        chain: "test-chain".to_string(),
+        pallet: "Balances".to_string(),
+        call_name: "transfer_keep_alive".to_string(),
+        caller: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+        success: false,
+        args: Some(serde_json::json!({ "raw": "00ff" })),
+        gas_used: Some(1_000),
+        fee_paid: Some(0.25),
+        destination: None,
+        value: None,
+    };
+    db.insert_transaction(&tx).await.unwrap();
+
+    let stored = db.get_transaction(&tx_hash).await.unwrap().expect("Transaction should be stored");
+    assert_eq!(stored.block_number, 4242);
+    assert_eq!(stored.chain, "test-chain");
+    assert_eq!(stored.pallet, "Balances");
+    assert_eq!(stored.call_name, "transfer_keep_alive");
+    assert_eq!(stored.caller, tx.caller);
+    assert!(!stored.success);
+    assert_eq!(stored.args, tx.args);
+    assert_eq!(stored.gas_used, Some(1_000));
+    assert_eq!(stored.fee_paid, Some(0.25));
+
+    let detection_id = uuid::Uuid::new_v4().to_string();
+    db.insert_detection(&Detection {
+        timestamp: chrono::Utc::now(),
+        detection_id: detection_id.clone(),
+        tx_hash: tx_hash.clone(),
+        detector_name: "MEV Detector".to_string(),
+        detector_version: "3".to_string(),
+        attack_pattern: "Mev".to_string(),
+        confidence: 0.8,
+        severity: "High".to_string(),
+        description: Some("Sandwich attack".to_string()),
+        evidence: None,
+        metadata: None,
+        acknowledged: false,
+        alerted: true,
+    })
+    .await
+    .unwrap();
+
+    // Near miss stored below the alert threshold
+    let near_miss_detector = format!("Near Miss {}", uuid::Uuid::new_v4().simple());
+    db.insert_detection(&Detection {
+        timestamp: chrono::Utc::now(),
+        detection_id: uuid::Uuid::new_v4().to_string(),
+        tx_hash: tx_hash.clone(),
+        detector_name: near_miss_detector.clone(),
+        detector_version: "1".to_string(),
+        attack_pattern: "Mev".to_string(),
+        confidence: 0.4,
+        severity: "low".to_string(),
+        description: None,
+        evidence: None,
+        metadata: None,
+        acknowledged: false,
+        alerted: false,
+    })
+    .await
+    .unwrap();
+
+    let near_misses = db.get_detections(Some(near_miss_detector), 10).await.unwrap();
+    assert_eq!(near_misses.len(), 1);
+    assert!(!near_misses[0].alerted);
+    assert_eq!(near_misses[0].confidence, 0.4);
+
+    let export = db.get_export_data(Some(1), 1000, 0).await.unwrap();
+    let record = export
+        .into_iter()
+        .find(|r| r.detection_id == detection_id)
+        .expect("Detection should be exported");
+    assert_eq!(record.tx_hash, tx_hash);
+    assert_eq!(record.detector_version, "3");
+    assert_eq!(record.caller.as_deref(), Some(tx.caller.as_str()));
+    assert_eq!(record.call_name.as_deref(), Some("transfer_keep_alive"));
+    assert_eq!(record.success, Some(false));
+    assert_eq!(record.chain.as_deref(), Some("test-chain"));
+}
+
+#[tokio::test]
+#[ignore] // Run with: TEST_DATABASE_URL=... cargo test -- --ignored
+async fn test_interrupted_backfill_resumes_after_checkpoint() {
+    let database_url = test_database_or_skip!();
+    let db = DatabaseClient::new(&database_url, 2)
+        .await
+        .expect("Failed to connect to test database");
+
+    // Unique chain so reruns don't pick up an old checkpoint
+    let chain = format!("backfill-{}", uuid::Uuid::new_v4().simple());
+    let (from, to) = (100, 110);
+    let interrupted_at = 104;
+
+    // First run fails on the block after 104, leaving the checkpoint at 104
+    let status = RwLock::new(None);
+    let first = run_backfill(Some(&db), &chain, from, to, &status, |number| async move {
+        if number > interrupted_at {
+            Err(Error::ConnectionError("interrupted".to_string()))
+        } else {
+            Ok(())
+        }
+    })
+    .await;
+    assert!(first.is_err());
+
+    let checkpoint = db
+        .load_backfill_checkpoint(&chain, from as i64, to as i64)
+        .await
+        .unwrap()
+        .expect("Checkpoint should be stored");
+    assert_eq!(checkpoint.last_processed_block, interrupted_at as i64);
+
+    // A fresh run over the same range starts right after the checkpoint
+    let status = RwLock::new(None);
+    let mut processed = Vec::new();
+    let resumed = run_backfill(Some(&db), &chain, from, to, &status, |number| {
+        processed.push(number);
+        async { Ok(()) }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(processed.first(), Some(&(interrupted_at + 1)));
+    assert_eq!(processed.len() as u64, to - interrupted_at);
+    assert!(resumed.is_complete());
+
+    let latest = db.latest_backfill_checkpoint(&chain).await.unwrap().unwrap();
+    assert_eq!(latest.last_processed_block, to as i64);
+}
+
+#[tokio::test]
+#[ignore] // Run with: TEST_DATABASE_URL=... cargo test -- --ignored
+async fn test_schema_check_reports_missing_aggregate() {
+    let database_url = test_database_or_skip!();
+
+    // Empty schema holding only the tables and a plain view named like an aggregate
+    let schema = format!("schema_check_{}", uuid::Uuid::new_v4().simple());
+    let (admin, connection) = tokio_postgres::connect(&database_url, tokio_postgres::NoTls)
+        .await
+        .expect("Failed to connect to test database");
+    tokio::spawn(connection);
+
+    let mut ddl = format!("CREATE SCHEMA {schema};");
+    for table in REQUIRED_TABLES {
+        ddl.push_str(&format!("CREATE TABLE {schema}.{table} (id INT);"));
+    }
+    ddl.push_str(&format!(
+        "CREATE VIEW {schema}.detector_stats_hourly AS SELECT 1 AS hour;"
+    ));
+    admin.batch_execute(&ddl).await.unwrap();
+
+    let separator = if database_url.contains('?') { '&' } else { '?' };
+    let scoped_url = format!("{database_url}{separator}options=-c%20search_path%3D{schema}");
+    let db = DatabaseClient::new(&scoped_url, 1)
+        .await
+        .expect("Failed to connect to test database");
+
+    let report = db.schema_check().await;
+    admin
+        .batch_execute(&format!("DROP SCHEMA {schema} CASCADE"))
+        .await
+        .unwrap();
+
+    let report = report.unwrap();
+    assert!(report.missing_tables.is_empty());
+    // A plain view is not a continuous aggregate
+    assert_eq!(
+        report.missing_aggregates,
+        vec!["detector_stats_hourly".to_string(), "tx_volume_hourly".to_string()]
+    );
+    assert!(!report.is_complete());
+}
+
+#[test]
+fn test_required_aggregates_are_continuous_in_schema() {
+    let schema = include_str!("../schema.sql");
+
+    for name in REQUIRED_AGGREGATES {
+        let definition = format!("CREATE MATERIALIZED VIEW IF NOT EXISTS {}\nWITH (timescaledb.continuous)", name);
+        assert!(schema.contains(&definition), "{} is not a continuous aggregate in schema.sql", name);
+    }
+}
+
+#[tokio::test]
+#[ignore] // Run with: TEST_DATABASE_URL=... cargo test -- --ignored
+async fn test_apply_retention_purges_only_old_rows() {
+    let database_url = test_database_or_skip!();
+    let db = DatabaseClient::new(&database_url, 2)
+        .await
+        .expect("Failed to connect to test database");
+
+    let transaction = |tx_hash: &str, age_days: i64| Transaction {
+        timestamp: chrono::Utc::now() - chrono::Duration::days(age_days),
+        tx_hash: tx_hash.to_string(),
+        block_number: 7,
+        chain: "test-chain".to_string(),
+        pallet: "Balances".to_string(),
+        call_name: "transfer_keep_alive".to_string(),
+        caller: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+        success: true,
+        args: None,
+        gas_used: None,
+        fee_paid: None,
+        destination: None,
+        value: None,
+    };
+
+    let old_hash = format!("0x{}", uuid::Uuid::new_v4().simple());
+    let new_hash = format!("0x{}", uuid::Uuid::new_v4().simple());
+    let old_tx = transaction(&old_hash, 90);
+    db.insert_transaction(&old_tx).await.unwrap();
+    db.insert_transaction(&transaction(&new_hash, 1)).await.unwrap();
+
+    db.insert_detection(&Detection {
+        timestamp: old_tx.timestamp,
+        detection_id: uuid::Uuid::new_v4().to_string(),
+        tx_hash: old_hash.clone(),
+        detector_name: "Retention Detector".to_string(),
+        detector_version: "1".to_string(),
+        attack_pattern: "Mev".to_string(),
+        confidence: 0.9,
+        severity: "High".to_string(),
+        description: None,
+        evidence: None,
+        metadata: None,
+        acknowledged: false,
+        alerted: true,
+    })
+    .await
+    .unwrap();
+
+    let report = db.apply_retention(30).await.unwrap();
+    assert!(report.transactions >= 1);
+    assert!(report.detections >= 1);
+
+    assert!(db.get_transaction(&old_hash).await.unwrap().is_none());
+    assert!(db.get_transaction(&new_hash).await.unwrap().is_some());
+
+    assert!(db.apply_retention(0).await.is_err());
+}
+
+#[tokio::test]
+#[ignore] // Run with: TEST_DATABASE_URL=... cargo test -- --ignored
+async fn test_export_graph_links_caller_to_destination() {
+    let database_url = test_database_or_skip!();
+    let db = DatabaseClient::new(&database_url, 2)
+        .await
+        .expect("Failed to connect to test database");
+
+    // Fresh addresses so rows from other tests don't merge into these nodes
+    let caller = format!("caller-{}", uuid::Uuid::new_v4().simple());
+    let destination = format!("destination-{}", uuid::Uuid::new_v4().simple());
+    let tx_hash = format!("0x{}", uuid::Uuid::new_v4().simple());
+
+    db.insert_transaction(&Transaction {
+        timestamp: chrono::Utc::now(),
+        tx_hash: tx_hash.clone(),
+        block_number: 9,
+        chain: "test-chain".to_string(),
+        pallet: "Balances".to_string(),
+        call_name: "transfer_keep_alive".to_string(),
+        caller: caller.clone(),
+        success: true,
+        args: None,
+        gas_used: None,
+        fee_paid: None,
+        destination: Some(destination.clone()),
+        value: Some("340282366920938463463374607431768211455".to_string()),
+    })
+    .await
+    .unwrap();
+
+    db.insert_detection(&Detection {
+        timestamp: chrono::Utc::now(),
+        detection_id: uuid::Uuid::new_v4().to_string(),
+        tx_hash,
+        detector_name: "Layering Detector".to_string(),
+        detector_version: "1".to_string(),
+        attack_pattern: "Layering".to_string(),
+        confidence: 0.9,
+        severity: "High".to_string(),
+        description: None,
+        evidence: None,
+        metadata: None,
+        acknowledged: false,
+        alerted: true,
+    })
+    .await
+    .unwrap();
+
+    let graph = db.export_graph(1, usize::MAX).await.unwrap();
+    assert!(graph.directed);
+
+    let edge = graph
+        .edges
+        .iter()
+        .find(|e| e.source == caller && e.target == destination)
+        .expect("Transfer should be an edge");
+    assert_eq!(edge.transfers, 1);
+    // u128::MAX survives the NUMERIC round trip
+    assert_eq!(edge.value, u128::MAX.to_string());
+    assert_eq!(edge.patterns, vec!["Layering".to_string()]);
+
+    for address in [&caller, &destination] {
+        let node = graph.nodes.iter().find(|n| &n.id == address).expect("Both ends should be nodes");
+        assert_eq!(node.detections, 1);
+    }
+}
+
+#[test]
+fn test_graph_edge_limit_keeps_busiest_edges() {
+    let transfer = |caller: &str, destination: &str| GraphRow {
+        caller: caller.to_string(),
+        destination: Some(destination.to_string()),
+        value: Some("1".to_string()),
+        detections: 0,
+        patterns: vec![],
+    };
+    let rows = vec![
+        transfer("a", "b"),
+        transfer("c", "d"),
+        transfer("c", "d"),
+        transfer("e", "f"),
+        transfer("e", "f"),
+        transfer("e", "f"),
+    ];
+
+    let graph = TransactionGraph::from_rows(rows.clone()).limit_edges(2);
+    assert!(graph.truncated);
+    let edges: Vec<_> = graph.edges.iter().map(|e| (e.source.as_str(), e.transfers)).collect();
+    assert_eq!(edges, vec![("c", 2), ("e", 3)]);
+    assert_eq!(graph.nodes.len(), 6);
+
+    let graph = TransactionGraph::from_rows(rows).limit_edges(3);
+    assert!(!graph.truncated);
+    assert_eq!(graph.edges.len(), 3);
+}
+
+#[tokio::test]
+#[ignore] // Run with: TEST_DATABASE_URL=... cargo test -- --ignored
+async fn test_pool_state_history_is_ordered() {
+    let database_url = test_database_or_skip!();
+    let db = DatabaseClient::new(&database_url, 2)
+        .await
+        .expect("Failed to connect to test database");
+
+    let pool_id = format!("test-{}", uuid::Uuid::new_v4().simple());
+    let snapshot = |minutes_ago: i64, price: f64| HydrationPoolState {
+        timestamp: chrono::Utc::now() - chrono::Duration::minutes(minutes_ago),
+        pool_id: pool_id.clone(),
+        pool_type: "omnipool".to_string(),
+        total_liquidity: Some(1_000_000.0),
+        oracle_price: Some(price),
+        oracle_deviation: Some(0.01),
+        asset_reserves: Some(serde_json::json!({ "5": "1000000" })),
+    };
+
+    // Newest inserted first, with a 50% price swing
+    db.insert_hydration_pool_state(&snapshot(5, 1.5)).await.unwrap();
+    db.insert_hydration_pool_state(&snapshot(20, 1.0)).await.unwrap();
+    // Outside the window, before and after it
+    db.insert_hydration_pool_state(&snapshot(180, 0.5)).await.unwrap();
+    db.insert_hydration_pool_state(&snapshot(-10, 2.0)).await.unwrap();
+
+    let history = db.get_pool_state_history(&pool_id, chrono::Utc::now(), 1).await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert!(history[0].timestamp < history[1].timestamp);
+    assert_eq!(history[0].oracle_price, Some(1.0));
+    assert_eq!(history[1].oracle_price, Some(1.5));
+    assert_eq!(history[1].asset_reserves, Some(serde_json::json!({ "5": "1000000" })));
+
+    let shift = monitoring_engine::detectors::omnipool_state::largest_shift(&history).unwrap();
+    assert!((shift - 0.5).abs() < 1e-9);
+}
+
+#[tokio::test]
+#[ignore] // Run with: TEST_DATABASE_URL=... cargo test -- --ignored
+async fn test_correlated_trends_align_volume_and_detections() {
+    let database_url = test_database_or_skip!();
+    let db = DatabaseClient::new(&database_url, 2)
+        .await
+        .expect("Failed to connect to test database");
+
+    let now = chrono::Utc::now();
+    let tx_hash = format!("0x{}", uuid::Uuid::new_v4().simple());
+    db.insert_transaction(&Transaction {
+        timestamp: now,
+        tx_hash: tx_hash.clone(),
+        block_number: 77,
+        chain: "test-chain".to_string(),
+        pallet: "Balances".to_string(),
+        call_name: "transfer_keep_alive".to_string(),
+        caller: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+        success: true,
+        args: None,
+        gas_used: None,
+        fee_paid: None,
+        destination: None,
+        value: None,
+    })
+    .await
+    .unwrap();
+
+    for _ in 0..2 {
+        db.insert_detection(&Detection {
+            timestamp: now,
+            detection_id: uuid::Uuid::new_v4().to_string(),
+            tx_hash: tx_hash.clone(),
+            detector_name: "MEV Detector".to_string(),
+            detector_version: "3".to_string(),
+            attack_pattern: "Mev".to_string(),
+            confidence: 0.8,
+            severity: "High".to_string(),
+            description: None,
+            evidence: None,
+            metadata: None,
+            acknowledged: false,
+            alerted: true,
+        })
+        .await
+        .unwrap();
+    }
+
+    let trends = db.get_correlated_trends(3).await.unwrap();
+
+    // One bucket per hour, partial current hour included, oldest first
+    assert_eq!(trends.len(), 4);
+    for pair in trends.windows(2) {
+        assert_eq!(pair[1].hour - pair[0].hour, chrono::Duration::hours(1));
+    }
+
+    for bucket in &trends {
+        match bucket.detection_ratio {
+            Some(ratio) => {
+                let expected = bucket.detection_count as f64 / bucket.tx_count as f64;
+                assert!((ratio - expected).abs() < 1e-9);
+            }
+            None => assert_eq!(bucket.tx_count, 0),
+        }
+    }
+
+    let bucket = trends
+        .iter()
+        .find(|b| b.hour <= now && now < b.hour + chrono::Duration::hours(1))
+        .expect("Insert hour should have a bucket");
+    assert!(bucket.tx_count >= 1);
+    assert!(bucket.detection_count >= 2);
+    assert!(bucket.detection_ratio.is_some());
+}
+
+#[tokio::test]
+#[ignore] // Run with: TEST_DATABASE_URL=... cargo test -- --ignored
+async fn test_feedback_relabel_replaces_previous_label() {
+    let database_url = test_database_or_skip!();
+    let db = DatabaseClient::new(&database_url, 2)
+        .await
+        .expect("Failed to connect to test database");
+
+    let detection_id = uuid::Uuid::new_v4().to_string();
+    let detector_name = format!("Feedback {}", uuid::Uuid::new_v4().simple());
+    let label = |true_positive| DetectionFeedback {
+        detection_id: detection_id.clone(),
+        detector_name: detector_name.clone(),
+        true_positive,
+        labeled_at: chrono::Utc::now(),
+    };
+
+    db.insert_feedback(&label(false)).await.unwrap();
+    db.insert_feedback(&label(true)).await.unwrap();
+
+    let stored: Vec<_> = db
+        .get_feedback()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|f| f.detection_id == detection_id)
+        .collect();
+    assert_eq!(stored.len(), 1);
+    assert!(stored[0].true_positive);
+    assert_eq!(stored[0].detector_name, detector_name);
+
+    assert!(db.get_detection(&detection_id).await.unwrap().is_none());
+}
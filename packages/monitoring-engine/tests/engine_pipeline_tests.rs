@@ -0,0 +1,173 @@
+// End-to-end tests of the block pipeline against a mock block source
+
+use monitoring_engine::block_source::MockBlockSource;
+use monitoring_engine::database::DatabaseClient;
+use monitoring_engine::types::StateChange;
+use monitoring_engine::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[path = "common/mod.rs"]
+#[allow(dead_code)]
+mod common;
+use common::*;
+
+/// Block with the flash loan transaction
+const FLASH_LOAN_BLOCK: u64 = 1000;
+
+fn transaction(hash: &str, block_number: u64, pallet: &str, call: &str) -> ParsedTransaction {
+    ParsedTransaction {
+        hash: hash.to_string(),
+        block_number,
+        block_hash: format!("0x{}", hex::encode(MockBlockSource::block_hash(block_number).0)),
+        index: 1,
+        caller: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+        pallet: pallet.to_string(),
+        call: call.to_string(),
+        args: vec![],
+        signature: Some(vec![1, 2, 3]),
+        nonce: Some(1),
+        timestamp: chrono::Utc::now().timestamp() as u64,
+        success: true,
+        decoded_call: Default::default(),
+    }
+}
+
+fn event(pallet: &str, event_name: &str) -> ChainEvent {
+    ChainEvent::new(pallet, event_name)
+}
+
+/// Borrow, two swaps and a repayment in one transaction
+fn flash_loan(hash: &str) -> TransactionContext {
+    TransactionContext {
+        transaction: transaction(hash, FLASH_LOAN_BLOCK, "Contracts", "call"),
+        events: vec![
+            event("LendingProtocol", "Borrowed"),
+            event("DexProtocol", "Swapped"),
+            event("DexProtocol", "Swapped"),
+            event("LendingProtocol", "Repaid"),
+        ],
+        state_changes: vec![StateChange {
+            key: b"balance:token_a".to_vec(),
+            old_value: Some(vec![0, 0, 0, 100]),
+            new_value: Some(vec![0, 0, 0, 250]),
+        }],
+    }
+}
+
+fn transfer(hash: &str, block_number: u64) -> TransactionContext {
+    TransactionContext {
+        transaction: transaction(hash, block_number, "Balances", "transfer_keep_alive"),
+        events: vec![],
+        state_changes: vec![],
+    }
+}
+
+/// Flash loan block, an ordinary block, then a block that fails to decode
+fn mock_chain(flash_loan_hash: &str) -> MockBlockSource {
+    MockBlockSource::new()
+        .with_block(FLASH_LOAN_BLOCK, vec![flash_loan(flash_loan_hash)])
+        .with_block(FLASH_LOAN_BLOCK + 1, vec![transfer("0xtransfer", FLASH_LOAN_BLOCK + 1)])
+        .with_undecodable_block(FLASH_LOAN_BLOCK + 2)
+}
+
+fn mock_config() -> MonitorConfig {
+    MonitorConfig {
+        enable_mempool: false,
+        max_reconnect_attempts: 0,
+        ..test_config()
+    }
+}
+
+/// Wait until the last (undecodable) mock block has been committed
+async fn wait_for_mock_chain(engine: &MonitoringEngine) -> EngineStats {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let stats = engine.get_stats().await;
+            if stats.decode_failures > 0 {
+                return stats;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("Mock blocks were not processed in time")
+}
+
+#[tokio::test]
+async fn test_flash_loan_block_alerts_through_engine() {
+    let engine = MonitoringEngine::new(mock_config())
+        .with_block_source(Arc::new(mock_chain("0xflashloan")));
+
+    // No node is running: the engine must not try to connect
+    engine.start().await.expect("Engine should start on a mock block source");
+    let stats = wait_for_mock_chain(&engine).await;
+    engine.stop().await.unwrap();
+
+    assert_eq!(stats.blocks_processed, 3);
+    assert_eq!(stats.transactions_analyzed, 2);
+    assert_eq!(stats.decode_failures, 1);
+    assert!(stats.alerts_triggered >= 1);
+
+    let alerts = engine.alert_manager.get_recent_alerts(100).await;
+    let alert = alerts
+        .iter()
+        .find(|alert| alert.pattern == AttackPattern::FlashLoan)
+        .expect("Flash loan should raise an alert");
+    assert_eq!(alert.transaction_hash.as_deref(), Some("0xflashloan"));
+    assert_eq!(alert.block_number, Some(FLASH_LOAN_BLOCK));
+    assert_eq!(alert.chain, "development");
+    assert!(alert.severity >= AlertSeverity::High);
+
+    assert!(
+        alerts.iter().all(|alert| alert.transaction_hash.as_deref() != Some("0xtransfer")),
+        "A plain transfer should not alert"
+    );
+
+    let detector = engine
+        .get_detector_stats()
+        .await
+        .detectors
+        .into_iter()
+        .find(|stats| stats.name == "Flash Loan Detector")
+        .unwrap();
+    assert_eq!(detector.detections, 1);
+}
+
+#[tokio::test]
+#[ignore] // Run with: TEST_DATABASE_URL=... cargo test -- --ignored
+async fn test_flash_loan_block_persisted() {
+    let database_url = test_database_or_skip!();
+    let db = Arc::new(
+        DatabaseClient::new(&database_url, 2)
+            .await
+            .expect("Failed to connect to test database"),
+    );
+
+    let hash = format!("0x{}", uuid::Uuid::new_v4().simple());
+    let engine = MonitoringEngine::with_database(mock_config(), db.clone())
+        .with_block_source(Arc::new(mock_chain(&hash)));
+
+    engine.start().await.unwrap();
+    wait_for_mock_chain(&engine).await;
+    engine.stop().await.unwrap();
+
+    let detection = db
+        .get_detections(Some("Flash Loan Detector".to_string()), 100)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|detection| detection.tx_hash == hash)
+        .expect("Flash loan detection should be stored");
+    assert_eq!(detection.attack_pattern, AttackPattern::FlashLoan.to_string());
+    assert!(detection.alerted);
+
+    let alert = db
+        .get_alerts(100)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|alert| alert.tx_hash.as_deref() == Some(hash.as_str()))
+        .expect("Flash loan alert should be stored");
+    assert_eq!(alert.block_number, Some(FLASH_LOAN_BLOCK as i64));
+}
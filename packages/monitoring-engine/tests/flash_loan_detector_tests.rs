@@ -29,50 +29,35 @@ fn create_flash_loan_context() -> TransactionContext {
         nonce: Some(42),
         timestamp: 1234567890,
         success: true,
+        decoded_call: Default::default(),
     };
 
     // Flash loan pattern: borrow → swap → swap → repay
     let events = vec![
         // Event 1: Large borrow from lending protocol
-        ChainEvent {
-            block_number: 1000,
-            event_index: 0,
-            extrinsic_index: Some(5),
-            pallet: "lending_protocol".to_string(),
-            event_name: "Borrowed".to_string(),
-            data: vec![0; 32], // Placeholder for large amount
-            topics: vec!["0xborrow_topic".to_string()],
-        },
+        ChainEvent::new("lending_protocol", "Borrowed")
+            .at(1000, 0)
+            .with_extrinsic_index(5)
+            .with_data(vec![0; 32]) // Placeholder for large amount
+            .with_topics(vec!["0xborrow_topic".to_string()]),
         // Event 2: Swap on DEX 1
-        ChainEvent {
-            block_number: 1000,
-            event_index: 1,
-            extrinsic_index: Some(5),
-            pallet: "dex_protocol".to_string(),
-            event_name: "Swapped".to_string(),
-            data: vec![0; 32],
-            topics: vec!["0xswap_topic".to_string()],
-        },
+        ChainEvent::new("dex_protocol", "Swapped")
+            .at(1000, 1)
+            .with_extrinsic_index(5)
+            .with_data(vec![0; 32])
+            .with_topics(vec!["0xswap_topic".to_string()]),
         // Event 3: Swap on DEX 2 (price manipulation)
-        ChainEvent {
-            block_number: 1000,
-            event_index: 2,
-            extrinsic_index: Some(5),
-            pallet: "dex_protocol".to_string(),
-            event_name: "Swapped".to_string(),
-            data: vec![0; 32],
-            topics: vec!["0xswap_topic".to_string()],
-        },
+        ChainEvent::new("dex_protocol", "Swapped")
+            .at(1000, 2)
+            .with_extrinsic_index(5)
+            .with_data(vec![0; 32])
+            .with_topics(vec!["0xswap_topic".to_string()]),
         // Event 4: Repayment to lending protocol
-        ChainEvent {
-            block_number: 1000,
-            event_index: 3,
-            extrinsic_index: Some(5),
-            pallet: "lending_protocol".to_string(),
-            event_name: "Repaid".to_string(),
-            data: vec![0; 32],
-            topics: vec!["0xrepay_topic".to_string()],
-        },
+        ChainEvent::new("lending_protocol", "Repaid")
+            .at(1000, 3)
+            .with_extrinsic_index(5)
+            .with_data(vec![0; 32])
+            .with_topics(vec!["0xrepay_topic".to_string()]),
     ];
 
     // Large balance changes indicating manipulation
@@ -111,18 +96,15 @@ fn create_normal_defi_context() -> TransactionContext {
         nonce: Some(10),
         timestamp: 1234567900,
         success: true,
+        decoded_call: Default::default(),
     };
 
     // Single swap - normal DeFi activity
-    let events = vec![ChainEvent {
-        block_number: 1001,
-        event_index: 0,
-        extrinsic_index: Some(3),
-        pallet: "dex_protocol".to_string(),
-        event_name: "Swapped".to_string(),
-        data: vec![0; 16], // Small amount
-        topics: vec![],
-    }];
+    let events = vec![ChainEvent::new("dex_protocol", "Swapped")
+        .at(1001, 0)
+        .with_extrinsic_index(3)
+        .with_data(vec![0; 16]) // Small amount
+    ];
 
     // Small balance change (normal trading)
     let state_changes = vec![StateChange {
@@ -153,18 +135,14 @@ fn create_incomplete_flash_loan_context() -> TransactionContext {
         nonce: Some(20),
         timestamp: 1234567910,
         success: false, // Failed transaction
+        decoded_call: Default::default(),
     };
 
     // Borrow without repayment (should have lower confidence)
-    let events = vec![ChainEvent {
-        block_number: 1002,
-        event_index: 0,
-        extrinsic_index: Some(7),
-        pallet: "lending_protocol".to_string(),
-        event_name: "Borrowed".to_string(),
-        data: vec![0; 32],
-        topics: vec![],
-    }];
+    let events = vec![ChainEvent::new("lending_protocol", "Borrowed")
+        .at(1002, 0)
+        .with_extrinsic_index(7)
+        .with_data(vec![0; 32])];
 
     TransactionContext {
         transaction,
@@ -318,15 +296,10 @@ async fn test_multiple_dex_interactions_increase_confidence() {
     let mut context = create_flash_loan_context();
 
     // Add more DEX interactions
-    context.events.push(ChainEvent {
-        block_number: 1000,
-        event_index: 4,
-        extrinsic_index: Some(5),
-        pallet: "another_dex".to_string(),
-        event_name: "Swapped".to_string(),
-        data: vec![0; 32],
-        topics: vec![],
-    });
+    context.events.push(ChainEvent::new("another_dex", "Swapped")
+        .at(1000, 4)
+        .with_extrinsic_index(5)
+        .with_data(vec![0; 32]));
 
     // When: We analyze the transaction
     let result = detector.analyze_transaction(&context).await;
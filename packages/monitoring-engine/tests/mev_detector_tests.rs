@@ -34,16 +34,13 @@ fn create_sandwich_attack_contexts() -> Vec<TransactionContext> {
             nonce: Some(100),
             timestamp: 1234567920,
             success: true,
+            decoded_call: Default::default(),
         },
-        events: vec![ChainEvent {
-            block_number,
-            event_index: 0,
-            extrinsic_index: Some(10),
-            pallet: "dex_protocol".to_string(),
-            event_name: "Swapped".to_string(),
-            data: vec![0; 32],
-            topics: vec!["0xbuy_token_a".to_string()],
-        }],
+        events: vec![ChainEvent::new("dex_protocol", "Swapped")
+            .at(block_number, 0)
+            .with_extrinsic_index(10)
+            .with_data(vec![0; 32])
+            .with_topics(vec!["0xbuy_token_a".to_string()])],
         state_changes: vec![StateChange {
             key: b"price:token_a".to_vec(),
             old_value: Some(vec![0, 0, 0, 100]),
@@ -66,16 +63,13 @@ fn create_sandwich_attack_contexts() -> Vec<TransactionContext> {
             nonce: Some(50),
             timestamp: 1234567921,
             success: true,
+            decoded_call: Default::default(),
         },
-        events: vec![ChainEvent {
-            block_number,
-            event_index: 1,
-            extrinsic_index: Some(11),
-            pallet: "dex_protocol".to_string(),
-            event_name: "Swapped".to_string(),
-            data: vec![0; 32],
-            topics: vec!["0xvictim_swap".to_string()],
-        }],
+        events: vec![ChainEvent::new("dex_protocol", "Swapped")
+            .at(block_number, 1)
+            .with_extrinsic_index(11)
+            .with_data(vec![0; 32])
+            .with_topics(vec!["0xvictim_swap".to_string()])],
         state_changes: vec![StateChange {
             key: b"price:token_a".to_vec(),
             old_value: Some(vec![0, 0, 0, 110]),
@@ -98,16 +92,13 @@ fn create_sandwich_attack_contexts() -> Vec<TransactionContext> {
             nonce: Some(101), // Sequential nonce
             timestamp: 1234567922,
             success: true,
+            decoded_call: Default::default(),
         },
-        events: vec![ChainEvent {
-            block_number,
-            event_index: 2,
-            extrinsic_index: Some(12),
-            pallet: "dex_protocol".to_string(),
-            event_name: "Swapped".to_string(),
-            data: vec![0; 32],
-            topics: vec!["0xsell_token_a".to_string()],
-        }],
+        events: vec![ChainEvent::new("dex_protocol", "Swapped")
+            .at(block_number, 2)
+            .with_extrinsic_index(12)
+            .with_data(vec![0; 32])
+            .with_topics(vec!["0xsell_token_a".to_string()])],
         state_changes: vec![StateChange {
             key: b"price:token_a".to_vec(),
             old_value: Some(vec![0, 0, 0, 120]),
@@ -138,16 +129,12 @@ fn create_frontrunning_contexts() -> Vec<TransactionContext> {
             nonce: Some(200),
             timestamp: 1234567930,
             success: true,
+            decoded_call: Default::default(),
         },
-        events: vec![ChainEvent {
-            block_number,
-            event_index: 0,
-            extrinsic_index: Some(5),
-            pallet: "contracts".to_string(),
-            event_name: "Minted".to_string(),
-            data: vec![0; 16],
-            topics: vec![],
-        }],
+        events: vec![ChainEvent::new("contracts", "Minted")
+            .at(block_number, 0)
+            .with_extrinsic_index(5)
+            .with_data(vec![0; 16])],
         state_changes: vec![],
     };
 
@@ -166,6 +153,7 @@ fn create_frontrunning_contexts() -> Vec<TransactionContext> {
             nonce: Some(25),
             timestamp: 1234567931,
             success: false, // Failed because frontrun
+            decoded_call: Default::default(),
         },
         events: vec![],
         state_changes: vec![],
@@ -193,16 +181,12 @@ fn create_normal_trading_contexts() -> Vec<TransactionContext> {
                 nonce: Some(10),
                 timestamp: 1234567940,
                 success: true,
+                decoded_call: Default::default(),
             },
-            events: vec![ChainEvent {
-                block_number,
-                event_index: 0,
-                extrinsic_index: Some(1),
-                pallet: "dex_protocol".to_string(),
-                event_name: "Swapped".to_string(),
-                data: vec![0; 16],
-                topics: vec![],
-            }],
+            events: vec![ChainEvent::new("dex_protocol", "Swapped")
+                .at(block_number, 0)
+                .with_extrinsic_index(1)
+                .with_data(vec![0; 16])],
             state_changes: vec![],
         },
         TransactionContext {
@@ -219,16 +203,12 @@ fn create_normal_trading_contexts() -> Vec<TransactionContext> {
                 nonce: Some(15),
                 timestamp: 1234567945,
                 success: true,
+                decoded_call: Default::default(),
             },
-            events: vec![ChainEvent {
-                block_number,
-                event_index: 1,
-                extrinsic_index: Some(8),
-                pallet: "dex_protocol".to_string(),
-                event_name: "LiquidityAdded".to_string(),
-                data: vec![0; 16],
-                topics: vec![],
-            }],
+            events: vec![ChainEvent::new("dex_protocol", "LiquidityAdded")
+                .at(block_number, 1)
+                .with_extrinsic_index(8)
+                .with_data(vec![0; 16])],
             state_changes: vec![],
         },
     ]
@@ -254,16 +234,12 @@ fn create_backrunning_contexts() -> Vec<TransactionContext> {
             nonce: Some(1),
             timestamp: 1234567950,
             success: true,
+            decoded_call: Default::default(),
         },
-        events: vec![ChainEvent {
-            block_number,
-            event_index: 0,
-            extrinsic_index: Some(15),
-            pallet: "dex_protocol".to_string(),
-            event_name: "Swapped".to_string(),
-            data: vec![0; 64],
-            topics: vec![],
-        }],
+        events: vec![ChainEvent::new("dex_protocol", "Swapped")
+            .at(block_number, 0)
+            .with_extrinsic_index(15)
+            .with_data(vec![0; 64])],
         state_changes: vec![StateChange {
             key: b"price:token_b".to_vec(),
             old_value: Some(vec![0, 0, 1, 0]),
@@ -286,16 +262,12 @@ fn create_backrunning_contexts() -> Vec<TransactionContext> {
             nonce: Some(300),
             timestamp: 1234567951,
             success: true,
+            decoded_call: Default::default(),
         },
-        events: vec![ChainEvent {
-            block_number,
-            event_index: 1,
-            extrinsic_index: Some(16),
-            pallet: "dex_protocol".to_string(),
-            event_name: "Swapped".to_string(),
-            data: vec![0; 32],
-            topics: vec![],
-        }],
+        events: vec![ChainEvent::new("dex_protocol", "Swapped")
+            .at(block_number, 1)
+            .with_extrinsic_index(16)
+            .with_data(vec![0; 32])],
         state_changes: vec![],
     };
 
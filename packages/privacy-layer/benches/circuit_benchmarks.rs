@@ -0,0 +1,67 @@
+// Proving system benchmarks for the privacy layer
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use privacy_layer::types::Severity;
+use privacy_layer::{PrivacyLayer, VulnerabilityReport};
+
+fn sample_report() -> VulnerabilityReport {
+    VulnerabilityReport {
+        severity: Severity::High,
+        category: "reentrancy".to_string(),
+        description: "Re-entrancy in withdraw allows draining funds".to_string(),
+        affected_code: "fn withdraw(amount: u128) { ... }".to_string(),
+        remediation: Some("Add a non-reentrant guard".to_string()),
+        reporter_id: None,
+    }
+}
+
+fn bench_circuit_size(_c: &mut Criterion) {
+    let stats = PrivacyLayer::circuit_stats().expect("circuit synthesis failed");
+    println!(
+        "VulnerabilityCircuit: {} constraints, {} witness variables, {} public inputs",
+        stats.constraints, stats.witness_variables, stats.public_inputs
+    );
+}
+
+fn bench_setup(c: &mut Criterion) {
+    c.bench_function("groth16_setup", |b| {
+        b.iter(|| {
+            let mut layer = PrivacyLayer::new();
+            layer.setup().unwrap();
+            black_box(layer);
+        });
+    });
+}
+
+fn bench_generate_proof(c: &mut Criterion) {
+    let mut layer = PrivacyLayer::new();
+    layer.setup().unwrap();
+    let report = sample_report();
+
+    c.bench_function("groth16_generate_proof", |b| {
+        b.iter(|| {
+            black_box(layer.generate_proof(black_box(&report)).unwrap());
+        });
+    });
+}
+
+fn bench_verify_proof(c: &mut Criterion) {
+    let mut layer = PrivacyLayer::new();
+    layer.setup().unwrap();
+    let proof = layer.generate_proof(&sample_report()).unwrap();
+
+    c.bench_function("groth16_verify_proof", |b| {
+        b.iter(|| {
+            black_box(layer.verify_proof(black_box(&proof)).unwrap());
+        });
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_circuit_size,
+    bench_setup,
+    bench_generate_proof,
+    bench_verify_proof
+);
+criterion_main!(benches);
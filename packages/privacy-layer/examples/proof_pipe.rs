@@ -0,0 +1,86 @@
+//! Example: Generate and verify proofs over stdin/stdout
+//!
+//! Lets a proof travel through a pipe (e.g. over SSH) without writing files.
+//!
+//! Usage:
+//!   cargo run --example proof_pipe -- prove  < report.json > proof.b64
+//!   cargo run --example proof_pipe -- verify < proof.b64
+//!
+//!   # Prove locally, verify on a remote host
+//!   cargo run --example proof_pipe -- prove < report.json \
+//!     | ssh verifier 'proof_pipe verify'
+//!
+//! `prove` reads a JSON `VulnerabilityReport` and prints the proof as one
+//! line of base64. `verify` reads that line and exits non-zero if the proof
+//! is invalid.
+//!
+//! Environment Variables:
+//!   PROVING_KEY   - Proving key file (default: keys from `setup()`)
+//!   VERIFYING_KEY - Verifying key file (default: keys from `setup()`)
+
+use privacy_layer::{PrivacyLayer, VulnerabilityProof, VulnerabilityReport};
+use std::io::Read;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let mode = std::env::args().nth(1).unwrap_or_default();
+
+    let result = match mode.as_str() {
+        "prove" => prove(),
+        "verify" => verify(),
+        _ => {
+            eprintln!("Usage: proof_pipe <prove|verify>  (input on stdin)");
+            return ExitCode::from(2);
+        }
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Set up keys, loading them from files when configured
+fn privacy_layer() -> Result<PrivacyLayer, Box<dyn std::error::Error>> {
+    let mut layer = PrivacyLayer::new();
+    let proving_key = std::env::var("PROVING_KEY").ok();
+    let verifying_key = std::env::var("VERIFYING_KEY").ok();
+
+    if proving_key.is_none() || verifying_key.is_none() {
+        layer.setup()?;
+    }
+    if let Some(path) = proving_key {
+        layer.load_proving_key(&path)?;
+    }
+    if let Some(path) = verifying_key {
+        layer.load_verifying_key(&path)?;
+    }
+
+    Ok(layer)
+}
+
+fn read_stdin() -> Result<String, Box<dyn std::error::Error>> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+fn prove() -> Result<bool, Box<dyn std::error::Error>> {
+    let report: VulnerabilityReport = serde_json::from_str(&read_stdin()?)?;
+    let proof = privacy_layer()?.generate_proof(&report)?;
+
+    println!("{}", proof.to_base64()?);
+    Ok(true)
+}
+
+fn verify() -> Result<bool, Box<dyn std::error::Error>> {
+    let proof = VulnerabilityProof::from_base64(&read_stdin()?)?;
+    let valid = privacy_layer()?.verify_proof(&proof)?;
+
+    println!("{}", if valid { "valid" } else { "invalid" });
+    Ok(valid)
+}
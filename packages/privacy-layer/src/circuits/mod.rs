@@ -1,25 +1,87 @@
 //! ZK circuits for vulnerability proofs
 
+use ark_crypto_primitives::sponge::constraints::CryptographicSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::{find_poseidon_ark_and_mds, PoseidonConfig, PoseidonSponge};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
 use ark_ff::PrimeField;
 use ark_r1cs_std::prelude::AllocVar;
 use ark_r1cs_std::eq::EqGadget;
 use ark_r1cs_std::fields::{fp::FpVar, FieldVar};
 use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
 
+/// Poseidon rate: field elements absorbed per permutation (width 3 with one capacity element)
+const POSEIDON_RATE: usize = 2;
+
+/// Poseidon full rounds for a width-3, x^5 instance over a ~254-bit field
+const POSEIDON_FULL_ROUNDS: usize = 8;
+
+/// Poseidon partial rounds for a width-3, x^5 instance over a ~254-bit field
+const POSEIDON_PARTIAL_ROUNDS: usize = 57;
+
+/// Poseidon S-box exponent
+const POSEIDON_ALPHA: u64 = 5;
+
+/// Poseidon parameters the report commitment is hashed with
+///
+/// Round constants and MDS matrix come from the reference Grain LFSR, so the
+/// same parameters are derived on every platform.
+pub fn poseidon_config<F: PrimeField>() -> PoseidonConfig<F> {
+    let (ark, mds) = find_poseidon_ark_and_mds::<F>(
+        F::MODULUS_BIT_SIZE as u64,
+        POSEIDON_RATE,
+        POSEIDON_FULL_ROUNDS as u64,
+        POSEIDON_PARTIAL_ROUNDS as u64,
+        0,
+    );
+    PoseidonConfig::new(
+        POSEIDON_FULL_ROUNDS,
+        POSEIDON_PARTIAL_ROUNDS,
+        POSEIDON_ALPHA,
+        mds,
+        ark,
+        POSEIDON_RATE,
+        1,
+    )
+}
+
+/// Commitment to a report, as constrained by [`VulnerabilityCircuit`]
+///
+/// `Poseidon(severity, category_hash, description_hash, affected_code_hash, blinding_factor)`
+/// with [`poseidon_config`]. Finding another report (or blinding factor)
+/// with the same commitment means finding a Poseidon collision.
+pub fn compute_commitment<F: PrimeField + Absorb>(
+    severity: F,
+    category_hash: F,
+    description_hash: F,
+    affected_code_hash: F,
+    blinding_factor: F,
+) -> F {
+    let mut sponge = PoseidonSponge::<F>::new(&poseidon_config::<F>());
+    sponge.absorb(&vec![severity, category_hash, description_hash, affected_code_hash, blinding_factor]);
+    sponge.squeeze_field_elements::<F>(1)[0]
+}
+
 /// Circuit for proving knowledge of a vulnerability without revealing its details
 ///
 /// This circuit proves:
-/// 1. Knowledge of vulnerability severity and description hash
+/// 1. Knowledge of the vulnerability severity and the hashes of its category,
+///    description and affected code
 /// 2. That severity is within valid range (0-3: Low, Medium, High, Critical)
-/// 3. That the commitment is correctly formed as: Hash(severity || description_hash || blinding)
+/// 3. That the commitment is the Poseidon hash of all of them and the
+///    blinding factor (see [`compute_commitment`])
 pub struct VulnerabilityCircuit<F: PrimeField> {
     /// Private: The vulnerability severity (0=Low, 1=Medium, 2=High, 3=Critical)
     pub severity: Option<F>,
+    /// Private: Hash of vulnerability category
+    pub category_hash: Option<F>,
     /// Private: Hash of vulnerability description
     pub description_hash: Option<F>,
+    /// Private: Hash of the affected code
+    pub affected_code_hash: Option<F>,
     /// Private: Blinding factor for commitment
     pub blinding_factor: Option<F>,
-    /// Public: Commitment to the vulnerability (Hash of all private inputs)
+    /// Public: Commitment to the vulnerability (Poseidon hash of all private inputs)
     pub commitment: Option<F>,
 }
 
@@ -27,13 +89,17 @@ impl<F: PrimeField> VulnerabilityCircuit<F> {
     /// Create a new vulnerability circuit with witness values
     pub fn new(
         severity: F,
+        category_hash: F,
         description_hash: F,
+        affected_code_hash: F,
         blinding_factor: F,
         commitment: F,
     ) -> Self {
         Self {
             severity: Some(severity),
+            category_hash: Some(category_hash),
             description_hash: Some(description_hash),
+            affected_code_hash: Some(affected_code_hash),
             blinding_factor: Some(blinding_factor),
             commitment: Some(commitment),
         }
@@ -43,7 +109,9 @@ impl<F: PrimeField> VulnerabilityCircuit<F> {
     pub fn empty() -> Self {
         Self {
             severity: None,
+            category_hash: None,
             description_hash: None,
+            affected_code_hash: None,
             blinding_factor: None,
             commitment: None,
         }
@@ -57,11 +125,21 @@ impl<F: PrimeField> ConstraintSynthesizer<F> for VulnerabilityCircuit<F> {
             self.severity.ok_or(SynthesisError::AssignmentMissing)
         })?;
 
+        let category_hash = FpVar::new_witness(cs.clone(), || {
+            self.category_hash
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
         let description_hash = FpVar::new_witness(cs.clone(), || {
             self.description_hash
                 .ok_or(SynthesisError::AssignmentMissing)
         })?;
 
+        let affected_code_hash = FpVar::new_witness(cs.clone(), || {
+            self.affected_code_hash
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
         let blinding_factor = FpVar::new_witness(cs.clone(), || {
             self.blinding_factor
                 .ok_or(SynthesisError::AssignmentMissing)
@@ -73,31 +151,119 @@ impl<F: PrimeField> ConstraintSynthesizer<F> for VulnerabilityCircuit<F> {
         })?;
 
         // Constraint 1: Severity must be in range [0, 3]
-        // We'll enforce this by checking: severity * (severity - 1) * (severity - 2) * (severity - 3) == 0
-        let zero = FpVar::zero();
-        let one = FpVar::one();
-        let two = &one + &one;
-        let three = &two + &one;
-
-        let diff_0 = &severity - &zero;
-        let diff_1 = &severity - &one;
-        let diff_2 = &severity - &two;
-        let diff_3 = &severity - &three;
-
-        let product = &diff_0 * &diff_1;
-        let product = &product * &diff_2;
-        let product = &product * &diff_3;
-
-        product.enforce_equal(&zero)?;
-
-        // Constraint 2: Compute commitment as simple hash-like function
-        // commitment = severity + description_hash * 2 + blinding_factor * 3
-        // (This is a simplified commitment scheme for demonstration)
+        enforce_severity_in_range(&severity)?;
+
+        // Constraint 2: Hash the report and blinding factor, as in compute_commitment
+        let mut sponge = PoseidonSpongeVar::new(cs.clone(), &poseidon_config());
+        sponge.absorb(&vec![severity, category_hash, description_hash, affected_code_hash, blinding_factor])?;
+        let commitment_computed = sponge.squeeze_field_elements(1)?.remove(0);
+
+        // Constraint 3: Computed commitment must equal public commitment
+        commitment_computed.enforce_equal(&commitment_public)?;
+
+        Ok(())
+    }
+}
+
+/// Constrain `severity` to [0, 3]
+///
+/// Enforced as `severity * (severity - 1) * (severity - 2) * (severity - 3) == 0`.
+fn enforce_severity_in_range<F: PrimeField>(severity: &FpVar<F>) -> Result<(), SynthesisError> {
+    let zero = FpVar::zero();
+    let one = FpVar::one();
+    let two = &one + &one;
+    let three = &two + &one;
+
+    let diff_0 = severity - &zero;
+    let diff_1 = severity - &one;
+    let diff_2 = severity - &two;
+    let diff_3 = severity - &three;
+
+    let product = &diff_0 * &diff_1;
+    let product = &product * &diff_2;
+    let product = &product * &diff_3;
+
+    product.enforce_equal(&zero)
+}
+
+/// Commitment constrained by [`VulnerabilityCircuitV1`]
+///
+/// `severity + description_hash * 2 + blinding_factor * 3`. Linear, so not
+/// binding: only kept to verify proofs generated before the v2 upgrade.
+pub fn compute_commitment_v1<F: PrimeField>(severity: F, description_hash: F, blinding_factor: F) -> F {
+    severity + description_hash * F::from(2u64) + blinding_factor * F::from(3u64)
+}
+
+/// The v1 circuit, superseded by [`VulnerabilityCircuit`]
+///
+/// Its verifying key stays registered so v1 proofs remain verifiable; new
+/// proofs are never generated with it.
+pub struct VulnerabilityCircuitV1<F: PrimeField> {
+    /// Private: The vulnerability severity (0=Low, 1=Medium, 2=High, 3=Critical)
+    pub severity: Option<F>,
+    /// Private: Hash of vulnerability description
+    pub description_hash: Option<F>,
+    /// Private: Blinding factor for commitment
+    pub blinding_factor: Option<F>,
+    /// Public: Commitment to the vulnerability (see [`compute_commitment_v1`])
+    pub commitment: Option<F>,
+}
+
+impl<F: PrimeField> VulnerabilityCircuitV1<F> {
+    /// Create a new v1 circuit with witness values
+    pub fn new(
+        severity: F,
+        description_hash: F,
+        blinding_factor: F,
+        commitment: F,
+    ) -> Self {
+        Self {
+            severity: Some(severity),
+            description_hash: Some(description_hash),
+            blinding_factor: Some(blinding_factor),
+            commitment: Some(commitment),
+        }
+    }
+
+    /// Create an empty circuit (for setup phase)
+    pub fn empty() -> Self {
+        Self {
+            severity: None,
+            description_hash: None,
+            blinding_factor: None,
+            commitment: None,
+        }
+    }
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for VulnerabilityCircuitV1<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let severity = FpVar::new_witness(cs.clone(), || {
+            self.severity.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let description_hash = FpVar::new_witness(cs.clone(), || {
+            self.description_hash
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let blinding_factor = FpVar::new_witness(cs.clone(), || {
+            self.blinding_factor
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let commitment_public = FpVar::new_input(cs.clone(), || {
+            self.commitment.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        enforce_severity_in_range(&severity)?;
+
+        let two = FpVar::Constant(F::from(2u64));
+        let three = FpVar::Constant(F::from(3u64));
         let commitment_computed = &severity
             + &(&description_hash * &two)
             + &(&blinding_factor * &three);
 
-        // Constraint 3: Computed commitment must equal public commitment
         commitment_computed.enforce_equal(&commitment_public)?;
 
         Ok(())
@@ -118,82 +284,96 @@ mod tests {
         assert!(circuit.description_hash.is_none());
     }
 
-    #[test]
-    fn test_circuit_with_valid_witness() {
+    /// Circuit for random report hashes, committed with `commitment` or correctly when `None`
+    fn circuit_with(severity: Fr, commitment: Option<Fr>) -> VulnerabilityCircuit<Fr> {
         let mut rng = ark_std::test_rng();
-
-        // Severity = 2 (High)
-        let severity = Fr::from(2u64);
+        let category_hash = Fr::rand(&mut rng);
         let description_hash = Fr::rand(&mut rng);
+        let affected_code_hash = Fr::rand(&mut rng);
         let blinding_factor = Fr::rand(&mut rng);
 
-        // Compute commitment: severity + description_hash * 2 + blinding_factor * 3
-        let commitment = severity
-            + description_hash * Fr::from(2u64)
-            + blinding_factor * Fr::from(3u64);
+        let commitment = commitment.unwrap_or_else(|| {
+            compute_commitment(severity, category_hash, description_hash, affected_code_hash, blinding_factor)
+        });
 
-        let circuit = VulnerabilityCircuit::new(
+        VulnerabilityCircuit::new(
             severity,
+            category_hash,
             description_hash,
+            affected_code_hash,
             blinding_factor,
             commitment,
-        );
+        )
+    }
 
+    fn is_satisfied(circuit: VulnerabilityCircuit<Fr>) -> bool {
         let cs = ConstraintSystem::<Fr>::new_ref();
         circuit.generate_constraints(cs.clone()).unwrap();
+        cs.is_satisfied().unwrap()
+    }
 
-        assert!(cs.is_satisfied().unwrap());
+    #[test]
+    fn test_circuit_with_valid_witness() {
+        // Severity = 2 (High)
+        assert!(is_satisfied(circuit_with(Fr::from(2u64), None)));
     }
 
     #[test]
     fn test_circuit_with_invalid_severity() {
-        let mut rng = ark_std::test_rng();
-
         // Severity = 5 (invalid, must be 0-3)
-        let severity = Fr::from(5u64);
-        let description_hash = Fr::rand(&mut rng);
-        let blinding_factor = Fr::rand(&mut rng);
+        // Should not be satisfied because severity is out of range
+        assert!(!is_satisfied(circuit_with(Fr::from(5u64), None)));
+    }
 
-        let commitment = severity
-            + description_hash * Fr::from(2u64)
-            + blinding_factor * Fr::from(3u64);
+    #[test]
+    fn test_circuit_with_wrong_commitment() {
+        // Wrong commitment (random value instead of correct formula)
+        let wrong_commitment = Fr::rand(&mut ark_std::test_rng());
 
-        let circuit = VulnerabilityCircuit::new(
+        // Should not be satisfied because commitment is wrong
+        assert!(!is_satisfied(circuit_with(Fr::from(1u64), Some(wrong_commitment))));
+    }
+
+    #[test]
+    fn test_commitment_is_not_linear() {
+        let mut rng = ark_std::test_rng();
+        let [severity, category_hash, description_hash, affected_code_hash, blinding_factor] =
+            [(); 5].map(|_| Fr::rand(&mut rng));
+
+        // Under a linear commitment, shifting the description by 3 and the
+        // blinding factor by -2 would give the same commitment
+        let commitment =
+            compute_commitment(severity, category_hash, description_hash, affected_code_hash, blinding_factor);
+        let forged = compute_commitment(
             severity,
-            description_hash,
-            blinding_factor,
-            commitment,
+            category_hash,
+            description_hash + Fr::from(3u64),
+            affected_code_hash,
+            blinding_factor - Fr::from(2u64),
         );
+        assert_ne!(commitment, forged);
+    }
 
-        let cs = ConstraintSystem::<Fr>::new_ref();
-        circuit.generate_constraints(cs.clone()).unwrap();
+    #[test]
+    fn test_commitment_binds_affected_code() {
+        let mut circuit = circuit_with(Fr::from(3u64), None);
+        circuit.affected_code_hash = Some(circuit.affected_code_hash.unwrap() + Fr::from(1u64));
 
-        // Should not be satisfied because severity is out of range
-        assert!(!cs.is_satisfied().unwrap());
+        // Same commitment, different affected code
+        assert!(!is_satisfied(circuit));
     }
 
     #[test]
-    fn test_circuit_with_wrong_commitment() {
+    fn test_v1_circuit_with_valid_witness() {
         let mut rng = ark_std::test_rng();
-
-        let severity = Fr::from(1u64);
+        let severity = Fr::from(3u64);
         let description_hash = Fr::rand(&mut rng);
         let blinding_factor = Fr::rand(&mut rng);
+        let commitment = compute_commitment_v1(severity, description_hash, blinding_factor);
 
-        // Wrong commitment (random value instead of correct formula)
-        let wrong_commitment = Fr::rand(&mut rng);
-
-        let circuit = VulnerabilityCircuit::new(
-            severity,
-            description_hash,
-            blinding_factor,
-            wrong_commitment,
-        );
-
+        let circuit = VulnerabilityCircuitV1::new(severity, description_hash, blinding_factor, commitment);
         let cs = ConstraintSystem::<Fr>::new_ref();
         circuit.generate_constraints(cs.clone()).unwrap();
-
-        // Should not be satisfied because commitment is wrong
-        assert!(!cs.is_satisfied().unwrap());
+        assert!(cs.is_satisfied().unwrap());
     }
 }
@@ -7,6 +7,8 @@
 pub mod circuits;
 pub mod credentials;
 pub mod proofs;
+#[cfg(feature = "monitoring")]
+pub mod severity;
 pub mod types;
 
 use ark_bn254::Bn254;
@@ -14,6 +16,7 @@ use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_snark::SNARK;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 pub use types::{VulnerabilityProof, VulnerabilityReport, ReportCommitment};
@@ -21,6 +24,19 @@ pub use types::{VulnerabilityProof, VulnerabilityReport, ReportCommitment};
 /// Type alias for the pairing-friendly elliptic curve
 pub type PairingCurve = Bn254;
 
+/// Circuit version produced by `generate_proof` and registered by `setup`
+pub const CURRENT_CIRCUIT_VERSION: &str = "v2";
+
+/// Version of the superseded linear-commitment circuit, verified only once
+/// its verifying key is loaded with `load_v1_verifying_key`
+pub const V1_CIRCUIT_VERSION: &str = "v1";
+
+/// Size of a compressed Groth16 proof over BN254 (two G1 points and a G2 point)
+pub const PROOF_SIZE: usize = 128;
+
+/// Largest `proof_data` accepted by `verify_proof`, checked before deserialization
+pub const MAX_PROOF_SIZE: usize = 1024;
+
 /// Main error type for the privacy layer
 #[derive(Error, Debug)]
 pub enum Error {
@@ -49,10 +65,25 @@ pub enum Error {
 /// Result type alias
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Size of the R1CS instance produced by `VulnerabilityCircuit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitStats {
+    /// Number of R1CS constraints
+    pub constraints: usize,
+    /// Number of private witness variables (including intermediates)
+    pub witness_variables: usize,
+    /// Number of public inputs (excluding the constant one)
+    pub public_inputs: usize,
+}
+
 /// Zero-knowledge proof system for vulnerability reporting
 pub struct PrivacyLayer {
     proving_key: Option<ProvingKey<PairingCurve>>,
-    verifying_key: Option<VerifyingKey<PairingCurve>>,
+    /// Verifying keys indexed by circuit version, so proofs generated before
+    /// a circuit upgrade remain verifiable
+    verifying_keys: BTreeMap<String, VerifyingKey<PairingCurve>>,
+    /// Fixed seed replacing secure entropy, for reproducible tests
+    rng_seed: Option<u64>,
 }
 
 impl PrivacyLayer {
@@ -60,13 +91,78 @@ impl PrivacyLayer {
     pub fn new() -> Self {
         Self {
             proving_key: None,
-            verifying_key: None,
+            verifying_keys: BTreeMap::new(),
+            rng_seed: None,
+        }
+    }
+
+    /// Derive all randomness from `seed`, making setup, commitments and
+    /// proofs reproducible
+    ///
+    /// Blinding factors become predictable, which breaks the privacy of every
+    /// commitment, so this is only available to tests and the `test-utils`
+    /// feature.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        self.rng_seed = Some(seed);
+        self
+    }
+
+    /// RNG for one commitment or proof: the fixed seed if set, secure entropy otherwise
+    fn rng(&self) -> Result<ark_std::rand::rngs::StdRng> {
+        use ark_std::rand::SeedableRng;
+
+        match self.rng_seed {
+            Some(seed) => Ok(ark_std::rand::rngs::StdRng::seed_from_u64(seed)),
+            None => blinding_rng(),
         }
     }
 
+    /// Register a verifying key for a specific circuit version
+    ///
+    /// Replaces any key previously registered for the same version.
+    pub fn register_verifying_key(
+        &mut self,
+        version: impl Into<String>,
+        verifying_key: VerifyingKey<PairingCurve>,
+    ) {
+        self.verifying_keys.insert(version.into(), verifying_key);
+    }
+
+    /// Get the circuit versions this layer can verify
+    pub fn supported_versions(&self) -> Vec<&str> {
+        self.verifying_keys.keys().map(|v| v.as_str()).collect()
+    }
+
+    /// Synthesize the current circuit and report its size
+    ///
+    /// Runs in setup mode, so no witness values are needed and no keys are
+    /// generated.
+    pub fn circuit_stats() -> Result<CircuitStats> {
+        use crate::circuits::VulnerabilityCircuit;
+
+        let cs = synthesize_for_setup(VulnerabilityCircuit::empty())?;
+
+        Ok(CircuitStats {
+            constraints: cs.num_constraints(),
+            witness_variables: cs.num_witness_variables(),
+            // Instance variables include the constant one
+            public_inputs: cs.num_instance_variables().saturating_sub(1),
+        })
+    }
+
+    /// Identifier of the current circuit's constraint structure
+    ///
+    /// Stored alongside saved keys so keys generated for a different circuit
+    /// are rejected on load.
+    pub fn circuit_id() -> Result<CircuitId> {
+        use crate::circuits::VulnerabilityCircuit;
+
+        constraint_structure_hash(VulnerabilityCircuit::empty())
+    }
+
     /// Setup the proving and verifying keys using Groth16
     pub fn setup(&mut self) -> Result<()> {
-        use ark_std::rand::SeedableRng;
         use crate::circuits::VulnerabilityCircuit;
 
         tracing::info!("Setting up privacy layer with Groth16...");
@@ -75,14 +171,14 @@ impl PrivacyLayer {
         let circuit = VulnerabilityCircuit::empty();
 
         // Generate random parameters (in production, use MPC ceremony)
-        let mut rng = ark_std::rand::rngs::StdRng::seed_from_u64(0u64);
+        let mut rng = self.rng()?;
 
         // Generate proving and verifying keys
         let (pk, vk) = Groth16::<PairingCurve>::circuit_specific_setup(circuit, &mut rng)
             .map_err(|e| Error::CircuitError(format!("Setup failed: {}", e)))?;
 
         self.proving_key = Some(pk);
-        self.verifying_key = Some(vk);
+        self.register_verifying_key(CURRENT_CIRCUIT_VERSION, vk);
 
         tracing::info!("Privacy layer setup complete");
         Ok(())
@@ -93,11 +189,41 @@ impl PrivacyLayer {
         &self,
         report: &VulnerabilityReport,
     ) -> Result<VulnerabilityProof> {
-        use ark_bn254::Fr;
-        use ark_ff::PrimeField;
-        use ark_std::rand::SeedableRng;
         use ark_std::UniformRand;
-        use blake2::{Blake2b512, Digest};
+
+        let mut rng = self.rng()?;
+        let blinding_factor = ark_bn254::Fr::rand(&mut rng);
+        self.prove(report, blinding_factor, &mut rng)
+    }
+
+    /// Generate a proof for a report committed to earlier with [`commit`](Self::commit)
+    ///
+    /// The proof reuses the commitment's blinding factor, so its public
+    /// commitment equals the published one. Fails if the report doesn't open
+    /// the commitment.
+    pub fn generate_proof_for_commitment(
+        &self,
+        report: &VulnerabilityReport,
+        commitment: &ReportCommitment,
+    ) -> Result<VulnerabilityProof> {
+        if !self.reveal(report, commitment) {
+            return Err(Error::InvalidWitness(
+                "Report does not match the commitment".to_string(),
+            ));
+        }
+
+        let blinding_factor = ark_bn254::Fr::deserialize_compressed(&commitment.blinding_factor[..])
+            .map_err(|e| Error::SerializationError(format!("Blinding factor deserialization failed: {}", e)))?;
+        self.prove(report, blinding_factor, &mut self.rng()?)
+    }
+
+    /// Prove knowledge of a report opening the commitment under `blinding_factor_fr`
+    fn prove(
+        &self,
+        report: &VulnerabilityReport,
+        blinding_factor_fr: ark_bn254::Fr,
+        rng: &mut ark_std::rand::rngs::StdRng,
+    ) -> Result<VulnerabilityProof> {
         use crate::circuits::VulnerabilityCircuit;
 
         tracing::debug!("Generating ZK proof for vulnerability report");
@@ -108,40 +234,22 @@ impl PrivacyLayer {
             )
         })?;
 
-        // Convert severity to field element (0=Low, 1=Medium, 2=High, 3=Critical)
-        let severity_value = match report.severity {
-            types::Severity::Low => 0u64,
-            types::Severity::Medium => 1u64,
-            types::Severity::High => 2u64,
-            types::Severity::Critical => 3u64,
-        };
-        let severity_fr = Fr::from(severity_value);
-
-        // Hash the description to get description_hash
-        let mut hasher = Blake2b512::new();
-        hasher.update(report.description.as_bytes());
-        let desc_hash_bytes = hasher.finalize();
-        let description_hash_fr = Fr::from_le_bytes_mod_order(&desc_hash_bytes[..32]);
-
-        // Generate random blinding factor
-        let mut rng = ark_std::rand::rngs::StdRng::seed_from_u64(chrono::Utc::now().timestamp() as u64);
-        let blinding_factor_fr = Fr::rand(&mut rng);
-
-        // Compute commitment: severity + description_hash * 2 + blinding_factor * 3
-        let commitment_fr = severity_fr
-            + description_hash_fr * Fr::from(2u64)
-            + blinding_factor_fr * Fr::from(3u64);
+        let witness = ReportWitness::new(report);
+        let commitment = report_commitment(&witness, blinding_factor_fr)?;
+        let commitment_fr = witness.commitment(blinding_factor_fr);
 
         // Create circuit with witness
         let circuit = VulnerabilityCircuit::new(
-            severity_fr,
-            description_hash_fr,
+            witness.severity,
+            witness.category_hash,
+            witness.description_hash,
+            witness.affected_code_hash,
             blinding_factor_fr,
             commitment_fr,
         );
 
         // Generate proof
-        let proof = Groth16::<PairingCurve>::prove(proving_key, circuit, &mut rng)
+        let proof = Groth16::<PairingCurve>::prove(proving_key, circuit, rng)
             .map_err(|e| Error::ProofGenerationError(format!("Proof generation failed: {}", e)))?;
 
         // Serialize proof
@@ -149,53 +257,55 @@ impl PrivacyLayer {
         proof.serialize_compressed(&mut proof_bytes)
             .map_err(|e| Error::SerializationError(format!("Proof serialization failed: {}", e)))?;
 
-        // Serialize public inputs (just the commitment)
-        let mut commitment_bytes = Vec::new();
-        commitment_fr.serialize_compressed(&mut commitment_bytes)
-            .map_err(|e| Error::SerializationError(format!("Commitment serialization failed: {}", e)))?;
+        // Public inputs are just the commitment
+        let public_inputs = vec![commitment.hash.clone()];
 
         Ok(VulnerabilityProof {
-            commitment: ReportCommitment {
-                hash: hex::encode(&commitment_bytes),
-                blinding_factor: {
-                    let mut bf_bytes = Vec::new();
-                    blinding_factor_fr.serialize_compressed(&mut bf_bytes).ok();
-                    bf_bytes
-                },
-            },
+            commitment,
             proof_data: proof_bytes,
-            public_inputs: vec![hex::encode(&commitment_bytes)],
+            public_inputs,
             metadata: ProofMetadata {
                 created_at: chrono::Utc::now().timestamp() as u64,
-                circuit_version: "v1".to_string(),
+                circuit_version: CURRENT_CIRCUIT_VERSION.to_string(),
                 curve: "BN254".to_string(),
             },
         })
     }
 
     /// Verify a zero-knowledge proof
+    ///
+    /// The verifying key is selected by `proof.metadata.circuit_version`.
     pub fn verify_proof(&self, proof: &VulnerabilityProof) -> Result<bool> {
         use ark_bn254::Fr;
 
-        tracing::debug!("Verifying ZK proof");
+        tracing::debug!(
+            "Verifying ZK proof (circuit {})",
+            proof.metadata.circuit_version
+        );
+
+        validate_proof_shape(proof)?;
 
-        let verifying_key = self.verifying_key.as_ref().ok_or_else(|| {
-            Error::ProofVerificationError(
+        if self.verifying_keys.is_empty() {
+            return Err(Error::ProofVerificationError(
                 "Verifying key not initialized. Call setup() first.".to_string(),
-            )
-        })?;
+            ));
+        }
+
+        let verifying_key = self
+            .verifying_keys
+            .get(&proof.metadata.circuit_version)
+            .ok_or_else(|| {
+                Error::ProofVerificationError(format!(
+                    "unsupported circuit version: {}",
+                    proof.metadata.circuit_version
+                ))
+            })?;
 
         // Deserialize the proof
         let groth_proof = Proof::<PairingCurve>::deserialize_compressed(&proof.proof_data[..])
             .map_err(|e| Error::ProofVerificationError(format!("Proof deserialization failed: {}", e)))?;
 
         // Deserialize public inputs (commitment)
-        if proof.public_inputs.is_empty() {
-            return Err(Error::ProofVerificationError(
-                "No public inputs provided".to_string(),
-            ));
-        }
-
         let commitment_bytes = hex::decode(&proof.public_inputs[0])
             .map_err(|e| Error::ProofVerificationError(format!("Public input decode failed: {}", e)))?;
 
@@ -213,50 +323,207 @@ impl PrivacyLayer {
         Ok(is_valid)
     }
 
-    /// Create a commitment to vulnerability details
-    fn create_commitment(&self, report: &VulnerabilityReport) -> Result<ReportCommitment> {
-        use blake2::{Blake2b512, Digest};
+    /// Commit to a report without proving anything yet
+    ///
+    /// The commitment uses the circuit's scheme under a fresh random blinding
+    /// factor. Its `hash` can be published to timestamp the discovery; the
+    /// blinding factor must be kept private, as together with a guessable
+    /// report it opens the commitment. Open it later with
+    /// [`reveal`](Self::reveal) or prove it with
+    /// [`generate_proof_for_commitment`](Self::generate_proof_for_commitment).
+    pub fn commit(&self, report: &VulnerabilityReport) -> Result<ReportCommitment> {
+        use ark_std::UniformRand;
 
-        let mut hasher = Blake2b512::new();
-        hasher.update(report.severity.as_bytes());
-        hasher.update(report.category.as_bytes());
-        hasher.update(report.description.as_bytes());
+        let blinding_factor = ark_bn254::Fr::rand(&mut self.rng()?);
+        report_commitment(&ReportWitness::new(report), blinding_factor)
+    }
 
-        let hash = hasher.finalize();
+    /// Whether `report` is the report committed to
+    ///
+    /// False for a different report or a malformed blinding factor.
+    pub fn reveal(&self, report: &VulnerabilityReport, commitment: &ReportCommitment) -> bool {
+        let Ok(blinding_factor) = ark_bn254::Fr::deserialize_compressed(&commitment.blinding_factor[..]) else {
+            return false;
+        };
 
-        Ok(ReportCommitment {
-            hash: hex::encode(hash),
-            blinding_factor: vec![], // Placeholder for actual blinding factor
-        })
+        report_commitment(&ReportWitness::new(report), blinding_factor)
+            .is_ok_and(|opened| opened.hash == commitment.hash)
     }
 
     /// Load proving key from file
+    ///
+    /// Fails with `key/circuit mismatch` if the key was saved for a different circuit.
     pub fn load_proving_key(&mut self, path: &str) -> Result<()> {
-        tracing::info!("Loading proving key from {}", path);
-        // TODO: Implement key loading
-        Ok(())
+        self.load_proving_key_for(path, &Self::circuit_id()?)
     }
 
-    /// Load verifying key from file
+    /// Load verifying key from file and register it for the current circuit version
+    ///
+    /// Fails with `key/circuit mismatch` if the key was saved for a different circuit.
     pub fn load_verifying_key(&mut self, path: &str) -> Result<()> {
-        tracing::info!("Loading verifying key from {}", path);
-        // TODO: Implement key loading
+        self.load_verifying_key_for(path, &Self::circuit_id()?)
+    }
+
+    /// Load the v1 circuit's verifying key from file, opting in to v1 proofs
+    ///
+    /// Fails with `key/circuit mismatch` if the key wasn't saved for the v1 circuit.
+    pub fn load_v1_verifying_key(&mut self, path: &str) -> Result<()> {
+        use crate::circuits::VulnerabilityCircuitV1;
+
+        let v1_id = constraint_structure_hash(VulnerabilityCircuitV1::empty())?;
+        tracing::info!("Loading v1 verifying key from {}", path);
+        let verifying_key = read_key_file(path, &v1_id)?;
+        self.register_verifying_key(V1_CIRCUIT_VERSION, verifying_key);
         Ok(())
     }
 
     /// Save proving key to file
     pub fn save_proving_key(&self, path: &str) -> Result<()> {
         tracing::info!("Saving proving key to {}", path);
-        // TODO: Implement key saving
-        Ok(())
+
+        let proving_key = self.proving_key.as_ref().ok_or_else(|| {
+            Error::SerializationError("Proving key not initialized. Call setup() first.".to_string())
+        })?;
+
+        write_key_file(path, &Self::circuit_id()?, proving_key)
     }
 
-    /// Save verifying key to file
+    /// Save the current circuit version's verifying key to file
     pub fn save_verifying_key(&self, path: &str) -> Result<()> {
         tracing::info!("Saving verifying key to {}", path);
-        // TODO: Implement key saving
+
+        let verifying_key = self.verifying_keys.get(CURRENT_CIRCUIT_VERSION).ok_or_else(|| {
+            Error::SerializationError("Verifying key not initialized. Call setup() first.".to_string())
+        })?;
+
+        write_key_file(path, &Self::circuit_id()?, verifying_key)
+    }
+
+    fn load_proving_key_for(&mut self, path: &str, expected: &CircuitId) -> Result<()> {
+        tracing::info!("Loading proving key from {}", path);
+        self.proving_key = Some(read_key_file(path, expected)?);
         Ok(())
     }
+
+    fn load_verifying_key_for(&mut self, path: &str, expected: &CircuitId) -> Result<()> {
+        tracing::info!("Loading verifying key from {}", path);
+        let verifying_key = read_key_file(path, expected)?;
+        self.register_verifying_key(CURRENT_CIRCUIT_VERSION, verifying_key);
+        Ok(())
+    }
+}
+
+/// Hash of a circuit's constraint structure
+pub type CircuitId = [u8; 32];
+
+/// Reject proofs whose size or public inputs can't be a proof of this circuit
+///
+/// `proof_data` comes from untrusted clients, so it is bounded before any
+/// curve point is decoded.
+fn validate_proof_shape(proof: &VulnerabilityProof) -> Result<()> {
+    let size = proof.proof_data.len();
+    if size == 0 {
+        return Err(Error::ProofVerificationError("Empty proof data".to_string()));
+    }
+    if size > MAX_PROOF_SIZE {
+        return Err(Error::ProofVerificationError(format!(
+            "Proof data too large: {} bytes (maximum {})",
+            size, MAX_PROOF_SIZE
+        )));
+    }
+    if size != PROOF_SIZE {
+        return Err(Error::ProofVerificationError(format!(
+            "Unexpected proof size: {} bytes (expected {})",
+            size, PROOF_SIZE
+        )));
+    }
+
+    match proof.public_inputs.len() {
+        0 => Err(Error::ProofVerificationError(
+            "No public inputs provided".to_string(),
+        )),
+        1 => Ok(()),
+        n => Err(Error::ProofVerificationError(format!(
+            "Expected 1 public input (the commitment), got {}",
+            n
+        ))),
+    }
+}
+
+/// Synthesize a circuit in setup mode (no witness values required)
+fn synthesize_for_setup<C>(circuit: C) -> Result<ark_relations::r1cs::ConstraintSystemRef<ark_bn254::Fr>>
+where
+    C: ark_relations::r1cs::ConstraintSynthesizer<ark_bn254::Fr>,
+{
+    use ark_relations::r1cs::{ConstraintSystem, SynthesisMode};
+
+    let cs = ConstraintSystem::new_ref();
+    cs.set_mode(SynthesisMode::Setup);
+    circuit
+        .generate_constraints(cs.clone())
+        .map_err(|e| Error::CircuitError(format!("Synthesis failed: {}", e)))?;
+    Ok(cs)
+}
+
+/// SHA-256 over the R1CS matrices of a circuit
+fn constraint_structure_hash<C>(circuit: C) -> Result<CircuitId>
+where
+    C: ark_relations::r1cs::ConstraintSynthesizer<ark_bn254::Fr>,
+{
+    use sha2::{Digest, Sha256};
+
+    let cs = synthesize_for_setup(circuit)?;
+    cs.finalize();
+    let matrices = cs
+        .to_matrices()
+        .ok_or_else(|| Error::CircuitError("Constraint matrices unavailable".to_string()))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update((matrices.num_instance_variables as u64).to_le_bytes());
+    hasher.update((matrices.num_witness_variables as u64).to_le_bytes());
+    hasher.update((matrices.num_constraints as u64).to_le_bytes());
+
+    let mut coeff_bytes = Vec::new();
+    for matrix in [&matrices.a, &matrices.b, &matrices.c] {
+        for row in matrix {
+            hasher.update((row.len() as u64).to_le_bytes());
+            for (coeff, index) in row {
+                coeff_bytes.clear();
+                coeff
+                    .serialize_compressed(&mut coeff_bytes)
+                    .map_err(|e| Error::SerializationError(format!("Coefficient serialization failed: {}", e)))?;
+                hasher.update(&coeff_bytes);
+                hasher.update((*index as u64).to_le_bytes());
+            }
+        }
+    }
+
+    Ok(hasher.finalize().into())
+}
+
+/// Write a key prefixed with the identifier of the circuit it was generated for
+fn write_key_file<K: CanonicalSerialize>(path: &str, circuit_id: &CircuitId, key: &K) -> Result<()> {
+    let mut bytes = circuit_id.to_vec();
+    key.serialize_compressed(&mut bytes)
+        .map_err(|e| Error::SerializationError(format!("Key serialization failed: {}", e)))?;
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Read a key file, rejecting keys saved for a different circuit
+fn read_key_file<K: CanonicalDeserialize>(path: &str, expected: &CircuitId) -> Result<K> {
+    let bytes = std::fs::read(path)?;
+    if bytes.len() < expected.len() {
+        return Err(Error::SerializationError(format!("Key file {} is truncated", path)));
+    }
+
+    let (circuit_id, key_bytes) = bytes.split_at(expected.len());
+    if circuit_id != expected.as_slice() {
+        return Err(Error::CircuitError("key/circuit mismatch".to_string()));
+    }
+
+    K::deserialize_compressed(key_bytes)
+        .map_err(|e| Error::SerializationError(format!("Key deserialization failed: {}", e)))
 }
 
 impl Default for PrivacyLayer {
@@ -265,6 +532,84 @@ impl Default for PrivacyLayer {
     }
 }
 
+/// Private circuit inputs derived from a report
+struct ReportWitness {
+    severity: ark_bn254::Fr,
+    category_hash: ark_bn254::Fr,
+    description_hash: ark_bn254::Fr,
+    affected_code_hash: ark_bn254::Fr,
+}
+
+impl ReportWitness {
+    fn new(report: &VulnerabilityReport) -> Self {
+        // Convert severity to field element (0=Low, 1=Medium, 2=High, 3=Critical)
+        let severity = match report.severity {
+            types::Severity::Low => 0u64,
+            types::Severity::Medium => 1u64,
+            types::Severity::High => 2u64,
+            types::Severity::Critical => 3u64,
+        };
+
+        Self {
+            severity: ark_bn254::Fr::from(severity),
+            category_hash: hash_to_field(&report.category),
+            description_hash: hash_to_field(&report.description),
+            affected_code_hash: hash_to_field(&report.affected_code),
+        }
+    }
+
+    /// Commitment to the report under a blinding factor, as proven by the circuit
+    fn commitment(&self, blinding_factor: ark_bn254::Fr) -> ark_bn254::Fr {
+        circuits::compute_commitment(
+            self.severity,
+            self.category_hash,
+            self.description_hash,
+            self.affected_code_hash,
+            blinding_factor,
+        )
+    }
+}
+
+/// Commitment to a witness with its hex-encoded hash and serialized blinding factor
+fn report_commitment(witness: &ReportWitness, blinding_factor: ark_bn254::Fr) -> Result<ReportCommitment> {
+    let mut commitment_bytes = Vec::new();
+    witness
+        .commitment(blinding_factor)
+        .serialize_compressed(&mut commitment_bytes)
+        .map_err(|e| Error::SerializationError(format!("Commitment serialization failed: {}", e)))?;
+
+    let mut blinding_bytes = Vec::new();
+    blinding_factor
+        .serialize_compressed(&mut blinding_bytes)
+        .map_err(|e| Error::SerializationError(format!("Blinding factor serialization failed: {}", e)))?;
+
+    Ok(ReportCommitment {
+        hash: hex::encode(&commitment_bytes),
+        blinding_factor: blinding_bytes,
+    })
+}
+
+/// RNG for blinding factors and proving randomness
+///
+/// Seeded from the operating system's CSPRNG.
+fn blinding_rng() -> Result<ark_std::rand::rngs::StdRng> {
+    use ark_std::rand::SeedableRng;
+
+    let mut seed = [0u8; 32];
+    getrandom::getrandom(&mut seed)
+        .map_err(|e| Error::CryptoError(format!("OS randomness unavailable: {}", e)))?;
+    Ok(ark_std::rand::rngs::StdRng::from_seed(seed))
+}
+
+/// Blake2b-512 of a report field, truncated to 32 bytes and reduced into the field
+fn hash_to_field(text: &str) -> ark_bn254::Fr {
+    use ark_ff::PrimeField;
+    use blake2::{Blake2b512, Digest};
+
+    let hash = Blake2b512::digest(text.as_bytes());
+    ark_bn254::Fr::from_le_bytes_mod_order(&hash[..32])
+}
+
 /// Metadata about a generated proof
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProofMetadata {
@@ -282,24 +627,178 @@ mod tests {
     fn test_privacy_layer_creation() {
         let layer = PrivacyLayer::new();
         assert!(layer.proving_key.is_none());
-        assert!(layer.verifying_key.is_none());
+        assert!(layer.verifying_keys.is_empty());
     }
 
     #[test]
-    fn test_commitment_creation() {
-        let layer = PrivacyLayer::new();
+    fn test_circuit_stats() {
+        let stats = PrivacyLayer::circuit_stats().expect("Synthesis should succeed");
+
+        // Range check: 3 multiplications + 1 equality; commitment: 1 equality
+        // plus Poseidon over 5 inputs: 3 permutations of 8 full and 57 partial
+        // rounds at 3 constraints per x^5 S-box, less the first round's S-box
+        // on the still-constant capacity element (726). A change here means
+        // the circuit grew - update deliberately.
+        assert_eq!(stats.constraints, 731);
+        // 5 private inputs + 3 range check intermediates + 1 per Poseidon constraint
+        assert_eq!(stats.witness_variables, 734);
+        assert_eq!(stats.public_inputs, 1);
+    }
+
+    /// The current circuit plus one extra constraint, standing in for a circuit upgrade
+    struct ChangedCircuit;
+
+    impl ark_relations::r1cs::ConstraintSynthesizer<ark_bn254::Fr> for ChangedCircuit {
+        fn generate_constraints(
+            self,
+            cs: ark_relations::r1cs::ConstraintSystemRef<ark_bn254::Fr>,
+        ) -> std::result::Result<(), ark_relations::r1cs::SynthesisError> {
+            use ark_r1cs_std::prelude::*;
+            use ark_r1cs_std::fields::fp::FpVar;
+
+            crate::circuits::VulnerabilityCircuit::empty().generate_constraints(cs.clone())?;
+            let extra = FpVar::new_witness(cs, || Ok(ark_bn254::Fr::from(0u64)))?;
+            extra.enforce_equal(&FpVar::zero())
+        }
+    }
+
+    fn temp_key_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("privacy-layer-{}-{}.key", name, std::process::id()))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_saved_keys_round_trip() {
+        let mut layer = PrivacyLayer::new();
+        layer.setup().expect("Setup should succeed");
+
+        let pk_path = temp_key_path("pk");
+        let vk_path = temp_key_path("vk");
+        layer.save_proving_key(&pk_path).expect("Saving proving key should succeed");
+        layer.save_verifying_key(&vk_path).expect("Saving verifying key should succeed");
+
+        let mut loaded = PrivacyLayer::new();
+        loaded.load_proving_key(&pk_path).expect("Proving key should load");
+        loaded.load_verifying_key(&vk_path).expect("Verifying key should load");
+        std::fs::remove_file(&pk_path).unwrap();
+        std::fs::remove_file(&vk_path).unwrap();
 
         let report = VulnerabilityReport {
+            severity: Severity::Medium,
+            category: "test".to_string(),
+            description: "Proof from loaded keys".to_string(),
+            affected_code: "code".to_string(),
+            remediation: None,
+            reporter_id: None,
+        };
+        let proof = loaded.generate_proof(&report).expect("Proof generation should succeed");
+        assert!(loaded.verify_proof(&proof).expect("Verification should succeed"));
+    }
+
+    #[test]
+    fn test_keys_rejected_after_circuit_change() {
+        let mut layer = PrivacyLayer::new();
+        layer.setup().expect("Setup should succeed");
+
+        let pk_path = temp_key_path("stale-pk");
+        let vk_path = temp_key_path("stale-vk");
+        layer.save_proving_key(&pk_path).unwrap();
+        layer.save_verifying_key(&vk_path).unwrap();
+
+        let changed_id = constraint_structure_hash(ChangedCircuit).unwrap();
+        assert_ne!(changed_id, PrivacyLayer::circuit_id().unwrap());
+
+        let mut loaded = PrivacyLayer::new();
+        let pk_result = loaded.load_proving_key_for(&pk_path, &changed_id);
+        let vk_result = loaded.load_verifying_key_for(&vk_path, &changed_id);
+        std::fs::remove_file(&pk_path).unwrap();
+        std::fs::remove_file(&vk_path).unwrap();
+
+        for result in [pk_result, vk_result] {
+            match result {
+                Err(Error::CircuitError(msg)) => assert_eq!(msg, "key/circuit mismatch"),
+                other => panic!("Expected key/circuit mismatch, got {:?}", other),
+            }
+        }
+        assert!(loaded.proving_key.is_none());
+        assert!(loaded.verifying_keys.is_empty());
+    }
+
+    fn sample_report(description: &str) -> VulnerabilityReport {
+        VulnerabilityReport {
             severity: Severity::High,
             category: "integer_overflow".to_string(),
-            description: "Test vulnerability".to_string(),
+            description: description.to_string(),
             affected_code: "fn example() {}".to_string(),
             remediation: Some("Use checked arithmetic".to_string()),
             reporter_id: None,
+        }
+    }
+
+    #[test]
+    fn test_commit_then_reveal() {
+        let layer = PrivacyLayer::new();
+        let report = sample_report("Test vulnerability");
+
+        let commitment = layer.commit(&report).unwrap();
+        assert!(!commitment.blinding_factor.is_empty());
+        assert!(layer.reveal(&report, &commitment));
+
+        // Fresh blinding: committing twice doesn't produce a linkable hash
+        assert_ne!(layer.commit(&report).unwrap().hash, commitment.hash);
+    }
+
+    #[test]
+    fn test_reveal_rejects_different_report() {
+        let layer = PrivacyLayer::new();
+        let commitment = layer.commit(&sample_report("Test vulnerability")).unwrap();
+
+        assert!(!layer.reveal(&sample_report("Another vulnerability"), &commitment));
+
+        let tampered = ReportCommitment {
+            blinding_factor: vec![0xff; 3],
+            ..commitment
+        };
+        assert!(!layer.reveal(&sample_report("Test vulnerability"), &tampered));
+    }
+
+    #[test]
+    fn test_proof_references_earlier_commitment() {
+        let mut layer = PrivacyLayer::new();
+        layer.setup().unwrap();
+        let report = sample_report("Test vulnerability");
+
+        let commitment = layer.commit(&report).unwrap();
+        let proof = layer.generate_proof_for_commitment(&report, &commitment).unwrap();
+
+        assert_eq!(proof.public_inputs, vec![commitment.hash.clone()]);
+        assert!(layer.verify_proof(&proof).unwrap());
+        assert!(layer
+            .generate_proof_for_commitment(&sample_report("Another vulnerability"), &commitment)
+            .is_err());
+    }
+
+    #[test]
+    fn test_seeded_proofs_are_reproducible() {
+        let proof = |seed| {
+            let mut layer = PrivacyLayer::new().with_rng_seed(seed);
+            layer.setup().unwrap();
+            let commitment = layer.commit(&sample_report("Test vulnerability")).unwrap();
+            (commitment, layer.generate_proof(&sample_report("Test vulnerability")).unwrap())
         };
 
-        let commitment = layer.create_commitment(&report);
-        assert!(commitment.is_ok());
+        let (first_commitment, first) = proof(7);
+        let (second_commitment, second) = proof(7);
+        assert_eq!(first_commitment.hash, second_commitment.hash);
+        assert_eq!(first_commitment.blinding_factor, second_commitment.blinding_factor);
+        assert_eq!(first.proof_data, second.proof_data);
+        assert_eq!(first.public_inputs, second.public_inputs);
+
+        let (other_commitment, other) = proof(8);
+        assert_ne!(first_commitment.hash, other_commitment.hash);
+        assert_ne!(first.proof_data, other.proof_data);
     }
 
     #[test]
@@ -346,7 +845,7 @@ mod tests {
         assert!(!proof.proof_data.is_empty(), "Proof data should not be empty");
         assert!(!proof.public_inputs.is_empty(), "Public inputs should not be empty");
         assert_eq!(proof.metadata.curve, "BN254");
-        assert_eq!(proof.metadata.circuit_version, "v1");
+        assert_eq!(proof.metadata.circuit_version, "v2");
 
         // Verify the proof
         let is_valid = layer
@@ -356,6 +855,27 @@ mod tests {
         assert!(is_valid, "Proof should be valid");
     }
 
+    #[test]
+    fn test_proof_verifies_after_base64_round_trip() {
+        let mut layer = PrivacyLayer::new();
+        layer.setup().expect("Setup should succeed");
+
+        let report = VulnerabilityReport {
+            severity: Severity::High,
+            category: "access_control".to_string(),
+            description: "Missing origin check on admin call".to_string(),
+            affected_code: "code".to_string(),
+            remediation: None,
+            reporter_id: None,
+        };
+
+        let proof = layer.generate_proof(&report).expect("Proof generation should succeed");
+        let encoded = proof.to_base64().expect("Encoding should succeed");
+        let decoded = VulnerabilityProof::from_base64(&encoded).expect("Decoding should succeed");
+
+        assert!(layer.verify_proof(&decoded).expect("Verification should succeed"));
+    }
+
     #[test]
     fn test_different_severity_levels() {
         let mut layer = PrivacyLayer::new();
@@ -423,4 +943,146 @@ mod tests {
             assert!(is_valid, "Proof should be valid");
         }
     }
+
+    #[test]
+    fn test_commitment_binds_affected_code() {
+        let mut layer = PrivacyLayer::new();
+        layer.setup().expect("Setup should succeed");
+
+        let report = |affected_code: &str| VulnerabilityReport {
+            severity: Severity::High,
+            category: "reentrancy".to_string(),
+            description: "Withdraw sends funds before updating the balance".to_string(),
+            affected_code: affected_code.to_string(),
+            remediation: None,
+            reporter_id: None,
+        };
+        let first = report("fn withdraw() { transfer(); Balances::remove(); }");
+        let second = report("fn claim() { transfer(); Rewards::remove(); }");
+
+        let blinding_factor = ark_bn254::Fr::from(42u64);
+        assert_ne!(
+            ReportWitness::new(&first).commitment(blinding_factor),
+            ReportWitness::new(&second).commitment(blinding_factor)
+        );
+
+        for report in [first, second] {
+            let proof = layer.generate_proof(&report).expect("Proof generation should succeed");
+            assert!(layer.verify_proof(&proof).expect("Verification should succeed"));
+        }
+    }
+
+    #[test]
+    fn test_old_version_proof_verifies_after_upgrade() {
+        use ark_bn254::Fr;
+        use ark_std::rand::{rngs::StdRng, SeedableRng};
+        use ark_std::UniformRand;
+        use crate::circuits::{compute_commitment_v1, VulnerabilityCircuitV1};
+
+        let mut layer = PrivacyLayer::new();
+        layer.setup().expect("Setup should succeed");
+        assert_eq!(layer.supported_versions(), vec!["v2"]);
+
+        // Keys as the v1 release generated them; only the verifying key is shipped
+        let mut rng = StdRng::seed_from_u64(7);
+        let (v1_pk, v1_vk) = Groth16::<PairingCurve>::circuit_specific_setup(VulnerabilityCircuitV1::empty(), &mut rng)
+            .expect("v1 setup should succeed");
+        let v1_id = constraint_structure_hash(VulnerabilityCircuitV1::empty()).unwrap();
+        let vk_path = temp_key_path("v1-vk");
+        write_key_file(&vk_path, &v1_id, &v1_vk).unwrap();
+
+        // The current circuit's loader refuses the v1 key
+        assert!(layer.load_verifying_key(&vk_path).is_err());
+        let loaded = layer.load_v1_verifying_key(&vk_path);
+        std::fs::remove_file(&vk_path).unwrap();
+        loaded.expect("v1 verifying key should load");
+        assert_eq!(layer.supported_versions(), vec!["v1", "v2"]);
+
+        // A proof as the v1 release generated it, with the v1 proving key
+        let severity = Fr::from(2u64);
+        let description_hash = Fr::rand(&mut rng);
+        let blinding_factor = Fr::rand(&mut rng);
+        let commitment = compute_commitment_v1(severity, description_hash, blinding_factor);
+        let circuit = VulnerabilityCircuitV1::new(severity, description_hash, blinding_factor, commitment);
+
+        let groth_proof = Groth16::<PairingCurve>::prove(&v1_pk, circuit, &mut rng)
+            .expect("v1 proof generation should succeed");
+
+        let mut proof = layer
+            .generate_proof(&sample_report("Proof generated before circuit upgrade"))
+            .expect("Proof generation should succeed");
+        proof.proof_data.clear();
+        groth_proof.serialize_compressed(&mut proof.proof_data).unwrap();
+        let mut commitment_bytes = Vec::new();
+        commitment.serialize_compressed(&mut commitment_bytes).unwrap();
+        proof.public_inputs = vec![hex::encode(commitment_bytes)];
+        proof.metadata.circuit_version = "v1".to_string();
+
+        assert!(layer.verify_proof(&proof).expect("v1 proof should verify"));
+
+        // The v2 key does not accept it
+        proof.metadata.circuit_version = "v2".to_string();
+        assert!(!layer.verify_proof(&proof).expect("verification should run"));
+    }
+
+    fn verification_error(layer: &PrivacyLayer, proof: &VulnerabilityProof) -> String {
+        match layer.verify_proof(proof) {
+            Err(Error::ProofVerificationError(msg)) => msg,
+            other => panic!("Expected verification error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_malformed_proofs_are_rejected() {
+        let mut layer = PrivacyLayer::new();
+        layer.setup().expect("Setup should succeed");
+        let mut proof = layer
+            .generate_proof(&sample_report("Test vulnerability"))
+            .expect("Proof generation should succeed");
+        assert_eq!(proof.proof_data.len(), PROOF_SIZE);
+
+        proof.proof_data = vec![];
+        let empty = verification_error(&layer, &proof);
+        assert!(empty.contains("Empty proof data"));
+
+        proof.proof_data = vec![0; MAX_PROOF_SIZE + 1];
+        let oversized = verification_error(&layer, &proof);
+        assert!(oversized.contains("too large"));
+
+        // Right size, but not valid curve points
+        proof.proof_data = vec![0xff; PROOF_SIZE];
+        let invalid = verification_error(&layer, &proof);
+        assert!(invalid.contains("Proof deserialization failed"));
+
+        assert_ne!(empty, oversized);
+        assert_ne!(oversized, invalid);
+        assert_ne!(empty, invalid);
+    }
+
+    #[test]
+    fn test_unsupported_circuit_version() {
+        let mut layer = PrivacyLayer::new();
+        layer.setup().expect("Setup should succeed");
+
+        let report = VulnerabilityReport {
+            severity: Severity::Low,
+            category: "test".to_string(),
+            description: "Test vulnerability".to_string(),
+            affected_code: "code".to_string(),
+            remediation: None,
+            reporter_id: None,
+        };
+
+        let mut proof = layer
+            .generate_proof(&report)
+            .expect("Proof generation should succeed");
+        proof.metadata.circuit_version = "v99".to_string();
+
+        match layer.verify_proof(&proof) {
+            Err(Error::ProofVerificationError(msg)) => {
+                assert!(msg.contains("unsupported circuit version"));
+            }
+            other => panic!("Expected unsupported version error, got {:?}", other),
+        }
+    }
 }
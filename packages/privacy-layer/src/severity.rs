@@ -0,0 +1,60 @@
+//! Conversions to and from the monitoring engine's alert severity
+//!
+//! Report severities and runtime alerts ([`AlertSeverity`]) share the
+//! `low`/`medium`/`high`/`critical` levels, which convert one-to-one. Only
+//! built with the `monitoring` feature, so the engine never has to pull in
+//! the proof system.
+
+use crate::types::Severity;
+use monitoring_engine::AlertSeverity;
+
+impl From<AlertSeverity> for Severity {
+    fn from(severity: AlertSeverity) -> Self {
+        match severity {
+            AlertSeverity::Low => Severity::Low,
+            AlertSeverity::Medium => Severity::Medium,
+            AlertSeverity::High => Severity::High,
+            AlertSeverity::Critical => Severity::Critical,
+        }
+    }
+}
+
+impl From<Severity> for AlertSeverity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Low => AlertSeverity::Low,
+            Severity::Medium => AlertSeverity::Medium,
+            Severity::High => AlertSeverity::High,
+            Severity::Critical => AlertSeverity::Critical,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEVELS: [AlertSeverity; 4] = [
+        AlertSeverity::Low,
+        AlertSeverity::Medium,
+        AlertSeverity::High,
+        AlertSeverity::Critical,
+    ];
+
+    #[test]
+    fn test_shared_levels_round_trip_through_alerts() {
+        for level in LEVELS {
+            assert_eq!(AlertSeverity::from(Severity::from(level)), level);
+        }
+    }
+
+    #[test]
+    fn test_conversions_keep_serialized_names() {
+        for level in LEVELS {
+            assert_eq!(
+                serde_json::to_value(Severity::from(level)).unwrap(),
+                serde_json::to_value(level).unwrap()
+            );
+        }
+    }
+}
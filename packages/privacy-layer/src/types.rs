@@ -1,6 +1,14 @@
 //! Core types for the privacy layer
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// zstd level used for compressed proofs
+const PROOF_COMPRESSION_LEVEL: i32 = 3;
+
+/// Largest decompressed proof accepted, so a small upload cannot expand without bound
+pub const MAX_DECOMPRESSED_PROOF_SIZE: u64 = 16 * 1024 * 1024;
 
 /// Severity level of a vulnerability
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -53,6 +61,78 @@ pub struct VulnerabilityProof {
     pub metadata: crate::ProofMetadata,
 }
 
+impl VulnerabilityProof {
+    /// Serialize the proof (JSON encoding)
+    pub fn to_bytes(&self) -> crate::Result<Vec<u8>> {
+        serde_json::to_vec(self)
+            .map_err(|e| crate::Error::SerializationError(format!("Proof encoding failed: {}", e)))
+    }
+
+    /// Deserialize a proof produced by `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|e| crate::Error::SerializationError(format!("Proof decoding failed: {}", e)))
+    }
+
+    /// Encode the proof as a single line of base64, for piping between tools
+    pub fn to_base64(&self) -> crate::Result<String> {
+        Ok(STANDARD.encode(self.to_bytes()?))
+    }
+
+    /// Decode a proof produced by `to_base64` (surrounding whitespace is ignored)
+    pub fn from_base64(encoded: &str) -> crate::Result<Self> {
+        let bytes = STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| crate::Error::SerializationError(format!("Invalid base64 proof: {}", e)))?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Serialize the proof and compress it with zstd, for bandwidth-constrained uploads
+    ///
+    /// The zstd frame carries a content checksum, so corruption in transit is
+    /// detected by `from_bytes_compressed`.
+    pub fn to_bytes_compressed(&self) -> crate::Result<Vec<u8>> {
+        let bytes = self.to_bytes()?;
+        compress(&bytes)
+            .map_err(|e| crate::Error::SerializationError(format!("Proof compression failed: {}", e)))
+    }
+
+    /// Decompress and deserialize a proof produced by `to_bytes_compressed`
+    ///
+    /// Fails on corrupt or truncated input and on proofs that decompress to
+    /// more than [`MAX_DECOMPRESSED_PROOF_SIZE`] bytes.
+    pub fn from_bytes_compressed(bytes: &[u8]) -> crate::Result<Self> {
+        let bytes = decompress(bytes, MAX_DECOMPRESSED_PROOF_SIZE).map_err(|e| {
+            crate::Error::SerializationError(format!("Proof decompression failed: {}", e))
+        })?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Compress into a single checksummed zstd frame
+fn compress(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = zstd::stream::write::Encoder::new(Vec::new(), PROOF_COMPRESSION_LEVEL)?;
+    encoder.include_checksum(true)?;
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+/// Decompress zstd data, refusing output larger than `limit` bytes
+fn decompress(bytes: &[u8], limit: u64) -> std::io::Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    zstd::stream::read::Decoder::new(bytes)?
+        .take(limit + 1)
+        .read_to_end(&mut decompressed)?;
+
+    if decompressed.len() as u64 > limit {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("decompressed size exceeds {} bytes", limit),
+        ));
+    }
+    Ok(decompressed)
+}
+
 /// Commitment to a vulnerability report
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportCommitment {
@@ -102,6 +182,79 @@ mod tests {
         assert_eq!(Severity::Critical.as_bytes(), b"critical");
     }
 
+    fn sample_proof() -> VulnerabilityProof {
+        VulnerabilityProof {
+            commitment: ReportCommitment {
+                hash: "ab".repeat(32),
+                blinding_factor: vec![1, 2, 3],
+            },
+            proof_data: vec![0, 255, 17, 42],
+            public_inputs: vec!["ab".repeat(32)],
+            metadata: crate::ProofMetadata {
+                created_at: 1_700_000_000,
+                circuit_version: "v1".to_string(),
+                curve: "BN254".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_proof_base64_round_trip() {
+        let proof = sample_proof();
+
+        let encoded = proof.to_base64().unwrap();
+        assert!(!encoded.contains('\n'));
+
+        let decoded = VulnerabilityProof::from_base64(&format!("{}\n", encoded)).unwrap();
+        assert_eq!(decoded.proof_data, proof.proof_data);
+        assert_eq!(decoded.public_inputs, proof.public_inputs);
+        assert_eq!(decoded.commitment.blinding_factor, proof.commitment.blinding_factor);
+        assert_eq!(decoded.metadata.circuit_version, "v1");
+
+        assert!(matches!(
+            VulnerabilityProof::from_base64("not base64!"),
+            Err(crate::Error::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_proof_compressed_round_trip() {
+        let mut proof = sample_proof();
+        proof.proof_data = vec![7; 4096];
+
+        let compressed = proof.to_bytes_compressed().unwrap();
+        assert!(compressed.len() < proof.to_bytes().unwrap().len());
+
+        let decoded = VulnerabilityProof::from_bytes_compressed(&compressed).unwrap();
+        assert_eq!(decoded.proof_data, proof.proof_data);
+        assert_eq!(decoded.public_inputs, proof.public_inputs);
+        assert_eq!(decoded.commitment.hash, proof.commitment.hash);
+    }
+
+    #[test]
+    fn test_corrupt_compressed_proof_rejected() {
+        let compressed = sample_proof().to_bytes_compressed().unwrap();
+
+        let mut flipped = compressed.clone();
+        let middle = flipped.len() / 2;
+        flipped[middle] ^= 0xff;
+        let truncated = &compressed[..compressed.len() - 4];
+
+        for corrupt in [&flipped[..], truncated, b"not zstd at all"] {
+            assert!(matches!(
+                VulnerabilityProof::from_bytes_compressed(corrupt),
+                Err(crate::Error::SerializationError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_decompression_size_limit() {
+        let bomb = compress(&vec![0; 1024]).unwrap();
+        assert!(decompress(&bomb, 1024).is_ok());
+        assert!(decompress(&bomb, 1023).is_err());
+    }
+
     #[test]
     fn test_vulnerability_report_creation() {
         let report = VulnerabilityReport {
@@ -5,7 +5,7 @@ pub mod access_control;
 pub mod reentrancy;
 pub mod xcm_precision;
 
-use crate::{Result, Vulnerability};
+use crate::{Result, Vulnerability, VulnerabilityCategory};
 use std::path::Path;
 use syn::File;
 
@@ -17,3 +17,59 @@ pub trait VulnerabilityAnalyzer {
     /// Get the name of this analyzer
     fn name(&self) -> &str;
 }
+
+/// A built-in analyzer and the rule its findings are reported under
+#[derive(Debug)]
+pub struct BuiltinAnalyzer {
+    pub name: &'static str,
+    /// ID of the [`SecurityRule`](crate::rules::SecurityRule) set as the `id` of every finding
+    pub rule_id: &'static str,
+    /// Category that enables the analyzer in [`AnalyzerConfig`](crate::AnalyzerConfig)
+    pub category: VulnerabilityCategory,
+    pub analyze: fn(&File, &Path) -> Result<Vec<Vulnerability>>,
+}
+
+/// Built-in analyzers, in the order they run
+pub const BUILTIN_ANALYZERS: &[BuiltinAnalyzer] = &[
+    BuiltinAnalyzer {
+        name: "overflow",
+        rule_id: overflow::RULE_ID,
+        category: VulnerabilityCategory::IntegerOverflow,
+        analyze: overflow::analyze,
+    },
+    BuiltinAnalyzer {
+        name: "xcm_precision",
+        rule_id: xcm_precision::RULE_ID,
+        category: VulnerabilityCategory::XcmDecimalPrecision,
+        analyze: xcm_precision::analyze,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_source;
+    use crate::rules::rule_docs;
+
+    /// Parse a documented example, wrapping bare statements in a function
+    fn parse_example(example: &str) -> File {
+        parse_source(example)
+            .or_else(|_| parse_source(&format!("fn example() {{\n{}\n}}", example)))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_analyzers_flag_their_rule_examples() {
+        for analyzer in BUILTIN_ANALYZERS {
+            let docs = rule_docs(analyzer.rule_id).expect("analyzer rule without docs");
+            let path = Path::new("example.rs");
+
+            let vulnerable = (analyzer.analyze)(&parse_example(docs.vulnerable_example), path).unwrap();
+            assert!(!vulnerable.is_empty(), "{} misses the {} example", analyzer.name, analyzer.rule_id);
+            assert!(vulnerable.iter().all(|v| v.id == analyzer.rule_id && v.category == analyzer.category));
+
+            let fixed = (analyzer.analyze)(&parse_example(docs.fixed_example), path).unwrap();
+            assert!(fixed.is_empty(), "{} flags the fixed {} example", analyzer.name, analyzer.rule_id);
+        }
+    }
+}
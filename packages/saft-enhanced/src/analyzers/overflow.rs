@@ -7,6 +7,9 @@ use crate::{
 use std::path::Path;
 use syn::{visit::Visit, File};
 
+/// Rule reported by this analyzer
+pub const RULE_ID: &str = "SAFT-001";
+
 /// Analyze for integer overflow/underflow vulnerabilities
 pub fn analyze(ast: &File, file_path: &Path) -> Result<Vec<Vulnerability>> {
     let mut visitor = ArithmeticVisitor::default();
@@ -65,7 +68,7 @@ pub fn analyze(ast: &File, file_path: &Path) -> Result<Vec<Vulnerability>> {
 
         if is_unchecked {
             vulnerabilities.push(Vulnerability {
-                id: "SAFT-001".to_string(),
+                id: RULE_ID.to_string(),
                 severity: Severity::High,
                 category: VulnerabilityCategory::IntegerOverflow,
                 message: format!(
@@ -10,6 +10,9 @@ use crate::{
 use std::path::Path;
 use syn::{visit::Visit, Expr, ExprCall, ExprMethodCall, File, Lit};
 
+/// Rule reported by this analyzer
+pub const RULE_ID: &str = "SAFT-005";
+
 /// Patterns that indicate XCM-related operations
 const XCM_PATTERNS: &[&str] = &[
     "transfer_multiasset",
@@ -152,7 +155,7 @@ pub fn analyze(ast: &File, file_path: &Path) -> Result<Vec<Vulnerability>> {
         // Flag if XCM call has hardcoded amounts without decimal conversion
         if call.has_hardcoded_amount && !call.has_decimal_conversion {
             vulnerabilities.push(Vulnerability {
-                id: "SAFT-004".to_string(),
+                id: RULE_ID.to_string(),
                 severity: Severity::Critical,
                 category: VulnerabilityCategory::XcmDecimalPrecision,
                 message: format!(
@@ -184,7 +187,7 @@ pub fn analyze(ast: &File, file_path: &Path) -> Result<Vec<Vulnerability>> {
         // This is lower severity as it might be intentional
         if !call.has_decimal_conversion && !call.has_hardcoded_amount {
             vulnerabilities.push(Vulnerability {
-                id: "SAFT-004".to_string(),
+                id: RULE_ID.to_string(),
                 severity: Severity::Medium,
                 category: VulnerabilityCategory::XcmDecimalPrecision,
                 message: format!(
@@ -0,0 +1,322 @@
+//! Baselines of accepted findings
+//!
+//! A baseline is a JSON file listing findings a team has reviewed and
+//! accepted, so CI only fails on new ones. Each finding is identified by a
+//! [`fingerprint`] of its rule, file, message and source snippet. Line
+//! numbers are left out, so it survives unrelated edits that move code
+//! around, but editing the flagged code itself reports the finding again.
+//!
+//! Entries record when the finding was first baselined. A baseline is kept
+//! current by:
+//! - [`Baseline::record`], which writes the current findings, keeping the
+//!   first-seen time of known ones and dropping those no longer reported
+//! - [`Baseline::prune`], which only drops entries whose finding was fixed
+//! - [`Baseline::stale`], which lists entries accepted long ago, so they can
+//!   be revisited instead of lingering forever
+
+use crate::{AnalysisResult, Error, Result, Vulnerability};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Stable identifier of a finding: FNV-1a of its rule, file, message and
+/// snippet (empty when the finding has none)
+pub fn fingerprint(vuln: &Vulnerability) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let file = vuln.location.file.to_string_lossy();
+    let snippet = vuln.location.snippet.as_deref().unwrap_or_default();
+
+    for part in [vuln.id.as_str(), &*file, vuln.message.as_str(), snippet] {
+        // Separator so ("ab", "c") and ("a", "bc") differ
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    format!("{:016x}", hash)
+}
+
+/// Seconds since the Unix epoch
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// An accepted finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub rule_id: String,
+    pub file: PathBuf,
+    pub message: String,
+    /// When the finding was first baselined, in seconds since the Unix epoch
+    pub first_seen: u64,
+}
+
+/// Findings accepted by the team, keyed by [`fingerprint`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    #[serde(default)]
+    pub entries: BTreeMap<String, BaselineEntry>,
+}
+
+/// Entries added and removed by [`Baseline::record`]
+#[derive(Debug, Clone, Default)]
+pub struct BaselineChanges {
+    pub added: usize,
+    pub removed: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// Load a baseline file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(|e| {
+            Error::ConfigError(format!("Invalid baseline {}: {}", path.display(), e))
+        })
+    }
+
+    /// Load a baseline file, or start an empty baseline if it doesn't exist yet
+    pub fn from_file_or_default<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if path.exists() {
+            Self::from_file(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Write the baseline as pretty-printed JSON
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut contents = serde_json::to_string_pretty(self)?;
+        contents.push('\n');
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether a finding has been accepted
+    pub fn contains(&self, vuln: &Vulnerability) -> bool {
+        self.entries.contains_key(&fingerprint(vuln))
+    }
+
+    /// Remove accepted findings from the results; returns how many were suppressed
+    pub fn filter(&self, results: &mut [AnalysisResult]) -> usize {
+        results
+            .iter_mut()
+            .map(|result| {
+                let before = result.vulnerabilities.len();
+                result.retain_vulnerabilities(|vuln| !self.contains(vuln));
+                before - result.vulnerabilities.len()
+            })
+            .sum()
+    }
+
+    /// Accept the current findings, dropping entries that are no longer reported
+    ///
+    /// Findings already in the baseline keep their first-seen time; new ones
+    /// get `now`.
+    pub fn record(&mut self, results: &[AnalysisResult], now: u64) -> BaselineChanges {
+        let removed = self.prune(results);
+        let mut added = 0;
+
+        for vuln in results.iter().flat_map(|r| &r.vulnerabilities) {
+            self.entries.entry(fingerprint(vuln)).or_insert_with(|| {
+                added += 1;
+                BaselineEntry {
+                    rule_id: vuln.id.clone(),
+                    file: vuln.location.file.clone(),
+                    message: vuln.message.clone(),
+                    first_seen: now,
+                }
+            });
+        }
+
+        BaselineChanges { added, removed }
+    }
+
+    /// Drop entries whose finding is gone, returning them
+    ///
+    /// Only files fully analyzed in this run are judged, so analyzing a
+    /// subdirectory (or a file timing out) doesn't wipe the rest of the
+    /// baseline. Entries for deleted files are dropped as well.
+    pub fn prune(&mut self, results: &[AnalysisResult]) -> Vec<BaselineEntry> {
+        let analyzed: HashSet<&Path> = results
+            .iter()
+            .filter(|r| r.metadata.incomplete.is_none())
+            .map(|r| r.file.as_path())
+            .collect();
+        let current: HashSet<String> = results
+            .iter()
+            .flat_map(|r| &r.vulnerabilities)
+            .map(fingerprint)
+            .collect();
+
+        let fixed: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(key, entry)| {
+                let judged = analyzed.contains(entry.file.as_path()) || !entry.file.exists();
+                judged && !current.contains(*key)
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        fixed
+            .iter()
+            .filter_map(|key| self.entries.remove(key))
+            .collect()
+    }
+
+    /// Entries first baselined more than `max_age` before `now`, oldest first
+    pub fn stale(&self, now: u64, max_age: Duration) -> Vec<&BaselineEntry> {
+        let cutoff = now.saturating_sub(max_age.as_secs());
+        let mut stale: Vec<_> = self
+            .entries
+            .values()
+            .filter(|entry| entry.first_seen < cutoff)
+            .collect();
+        stale.sort_by_key(|entry| entry.first_seen);
+        stale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnalysisMetadata, Location, Severity, SeverityCounts, VulnerabilityCategory};
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn finding(file: &Path, id: &str, message: &str) -> Vulnerability {
+        Vulnerability {
+            id: id.to_string(),
+            severity: Severity::High,
+            category: VulnerabilityCategory::IntegerOverflow,
+            message: message.to_string(),
+            description: String::new(),
+            location: Location {
+                file: file.to_path_buf(),
+                line: 0,
+                column: 0,
+                snippet: None,
+            },
+            remediation: None,
+            references: vec![],
+        }
+    }
+
+    fn result(file: &Path, vulnerabilities: Vec<Vulnerability>) -> AnalysisResult {
+        let mut result = AnalysisResult {
+            file: file.to_path_buf(),
+            vulnerabilities,
+            metadata: AnalysisMetadata {
+                total_vulnerabilities: 0,
+                severity_counts: SeverityCounts::default(),
+                risk_score: 0,
+                duration_ms: 0,
+                analyzer_version: env!("CARGO_PKG_VERSION").to_string(),
+                incomplete: None,
+            },
+        };
+        result.retain_vulnerabilities(|_| true);
+        result
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_line_numbers() {
+        let file = Path::new("pallet.rs");
+        let mut moved = finding(file, "SAFT-001", "Unchecked addition");
+        moved.location.line = 42;
+
+        assert_eq!(fingerprint(&finding(file, "SAFT-001", "Unchecked addition")), fingerprint(&moved));
+        assert_ne!(
+            fingerprint(&finding(file, "SAFT-001", "Unchecked addition")),
+            fingerprint(&finding(file, "SAFT-004", "Unchecked addition"))
+        );
+    }
+
+    #[test]
+    fn test_fixed_finding_is_pruned() {
+        // Entries of files that no longer exist are pruned, so the file must be real
+        let vault = std::env::temp_dir().join(format!("saft-baseline-vault-{}.rs", std::process::id()));
+        std::fs::write(&vault, "fn deposit() {}").unwrap();
+        let file = vault.as_path();
+        let overflow = finding(file, "SAFT-001", "Unchecked addition in 'deposit'");
+        let unwrap = finding(file, "SAFT-004", "Call to unwrap() in 'owner' can panic");
+
+        let mut baseline = Baseline::default();
+        let changes = baseline.record(&[result(file, vec![overflow.clone(), unwrap.clone()])], 0);
+        assert_eq!(changes.added, 2);
+
+        // The overflow was fixed; the unwrap remains
+        let run = [result(file, vec![unwrap.clone()])];
+        let removed = baseline.prune(&run);
+
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].rule_id, "SAFT-001");
+        assert!(!baseline.contains(&overflow));
+        assert!(baseline.contains(&unwrap));
+
+        // Existing files not analyzed in this run keep their entries
+        let other = std::env::temp_dir().join(format!("saft-baseline-other-{}.rs", std::process::id()));
+        std::fs::write(&other, "fn other() {}").unwrap();
+        baseline.record(&[result(&other, vec![finding(&other, "SAFT-002", "Unchecked origin")])], 0);
+        let kept = baseline.prune(&run).is_empty();
+        std::fs::remove_file(&other).unwrap();
+        std::fs::remove_file(&vault).unwrap();
+
+        assert!(kept);
+        assert_eq!(baseline.len(), 2);
+    }
+
+    #[test]
+    fn test_record_keeps_first_seen_and_filters() {
+        let file = Path::new("pallet.rs");
+        let old = finding(file, "SAFT-001", "Unchecked addition");
+        let new = finding(file, "SAFT-003", "Storage written after transfer");
+
+        let mut baseline = Baseline::default();
+        baseline.record(&[result(file, vec![old.clone()])], 0);
+        let changes = baseline.record(&[result(file, vec![old.clone(), new.clone()])], 100 * DAY);
+        assert_eq!(changes.added, 1);
+        assert!(changes.removed.is_empty());
+
+        let stale = baseline.stale(100 * DAY, Duration::from_secs(90 * DAY));
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].rule_id, "SAFT-001");
+
+        let mut results = vec![result(file, vec![old, new])];
+        baseline.entries.retain(|_, entry| entry.rule_id == "SAFT-001");
+        assert_eq!(baseline.filter(&mut results), 1);
+        assert_eq!(results[0].vulnerabilities.len(), 1);
+        assert_eq!(results[0].metadata.severity_counts.high, 1);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let path = std::env::temp_dir().join(format!("saft-baseline-{}.json", std::process::id()));
+        let file = Path::new("pallet.rs");
+
+        let mut baseline = Baseline::from_file_or_default(&path).unwrap();
+        assert!(baseline.is_empty());
+        baseline.record(&[result(file, vec![finding(file, "SAFT-001", "Unchecked addition")])], 7);
+        baseline.save(&path).unwrap();
+
+        let loaded = Baseline::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.entries, baseline.entries);
+    }
+}
@@ -0,0 +1,127 @@
+//! Rust files changed since a git ref
+//!
+//! Lets CI analyze only the files a pull request touches. Changed files are
+//! listed by `git diff --name-only <base-ref>` (committed, staged and
+//! unstaged changes; untracked files are not included), deleted files are
+//! left out, and only `.rs` files under the analysis target are kept.
+//!
+//! Returned paths are spelled from the target path the way a directory walk
+//! would spell them, so baseline fingerprints match those of a full run.
+
+use crate::{Error, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// `.rs` files under `target` changed since `base_ref`
+///
+/// `target` is a file or directory inside a git work tree.
+pub fn changed_files(target: &Path, base_ref: &str) -> Result<Vec<PathBuf>> {
+    let dir = if target.is_file() {
+        target.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."))
+    } else {
+        target
+    };
+
+    let root = git(dir, &["rev-parse", "--show-toplevel"])?;
+    let names = git(dir, &["diff", "--name-only", "--diff-filter=d", base_ref, "--"])?;
+
+    select_changed(Path::new(root.trim()), &names, target)
+}
+
+/// Keep the `.rs` files of a `git diff --name-only` listing under `target`
+///
+/// `names` are relative to the work tree `root`.
+pub fn select_changed(root: &Path, names: &str, target: &Path) -> Result<Vec<PathBuf>> {
+    let root = root.canonicalize()?;
+    let canonical_target = target.canonicalize()?;
+
+    let files = names
+        .lines()
+        .map(str::trim)
+        .filter(|name| name.ends_with(".rs"))
+        .filter_map(|name| {
+            let relative = root.join(name).strip_prefix(&canonical_target).ok()?.to_path_buf();
+            Some(if relative.as_os_str().is_empty() { target.to_path_buf() } else { target.join(relative) })
+        })
+        .collect();
+
+    Ok(files)
+}
+
+/// Run git in `dir`, returning its stdout
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                Error::GitError("git was not found on PATH; it is required to list changed files".to_string())
+            }
+            _ => Error::GitError(format!("Failed to run git: {}", e)),
+        })?;
+
+    if !output.status.success() {
+        return Err(Error::GitError(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git_in(dir: &Path, args: &[&str]) {
+        git(dir, args).unwrap();
+    }
+
+    #[test]
+    fn test_select_changed_keeps_rust_files_under_target() {
+        let root = std::env::temp_dir().join(format!("saft-select-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("pallets/vault/src")).unwrap();
+        std::fs::create_dir_all(root.join("node")).unwrap();
+
+        let names = "pallets/vault/src/lib.rs\npallets/vault/Cargo.toml\nnode/main.rs\n";
+        let target = root.join("pallets");
+        let files = select_changed(&root, names, &target).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(files, vec![target.join("vault/src/lib.rs")]);
+    }
+
+    #[test]
+    fn test_changed_files_from_git_repo() {
+        if Command::new("git").arg("--version").output().is_err() {
+            eprintln!("git not available, skipping");
+            return;
+        }
+
+        let repo = std::env::temp_dir().join(format!("saft-changed-{}", std::process::id()));
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        std::fs::write(repo.join("src/a.rs"), "fn a() {}").unwrap();
+        std::fs::write(repo.join("src/b.rs"), "fn b() {}").unwrap();
+        std::fs::write(repo.join("src/c.rs"), "fn c() {}").unwrap();
+
+        git_in(&repo, &["init", "-q"]);
+        git_in(&repo, &["add", "."]);
+        git_in(&repo, &["-c", "user.name=saft", "-c", "user.email=saft@example.com", "commit", "-qm", "base"]);
+
+        std::fs::write(repo.join("src/b.rs"), "fn b() { let x = 1 + 2; }").unwrap();
+        std::fs::remove_file(repo.join("src/c.rs")).unwrap();
+        std::fs::write(repo.join("notes.md"), "changed").unwrap();
+
+        let target = repo.join("src");
+        let files = changed_files(&target, "HEAD");
+        let bad_ref = changed_files(&target, "no-such-ref");
+        std::fs::remove_dir_all(&repo).unwrap();
+
+        assert_eq!(files.unwrap(), vec![target.join("b.rs")]);
+        assert!(matches!(bad_ref, Err(Error::GitError(_))));
+    }
+}
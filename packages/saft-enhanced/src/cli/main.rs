@@ -1,17 +1,44 @@
 //! SAFT Enhanced CLI
 //!
 //! Command-line interface for static analysis of FRAME pallets
+//!
+//! Exit codes:
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0    | No finding at or above `--fail-on` (default: high) |
+//! | 1    | Findings at or above `--fail-on`; `rules --coverage` found a rule without an analyzer |
+//! | 2    | The tool failed: unreadable or unparseable input, invalid config, report or baseline not written |
 
 use clap::{Parser, Subcommand, ValueEnum};
 use colored::Colorize;
+use serde::Serialize;
 use saft_enhanced::{
-    reporter::{ReportFormat, Reporter},
-    Analyzer, AnalyzerConfig, Severity,
+    baseline::{unix_now, Baseline},
+    changed,
+    config::{ProjectConfig, DEFAULT_CONFIG_FILE},
+    reporter::{
+        split::{SplitBy, INDEX_FILE},
+        Compression, GroupBy, ReportFormat, Reporter,
+    },
+    rules::{self, RuleSet},
+    AnalysisResult, Analyzer, AnalyzerConfig, Severity,
 };
-use std::path::PathBuf;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tracing::Level;
 use tracing_subscriber::FmtSubscriber;
 
+/// Exit code when no finding reaches the fail-on severity
+const EXIT_CLEAN: i32 = 0;
+
+/// Exit code when findings reach the fail-on severity
+const EXIT_FINDINGS: i32 = 1;
+
+/// Exit code when the tool itself failed, so CI can tell it from findings
+const EXIT_ERROR: i32 = 2;
+
 #[derive(Parser)]
 #[command(name = "saft")]
 #[command(author = "Juan Ignacio Raggio & Victoria Park")]
@@ -25,9 +52,17 @@ struct Cli {
     #[arg(short, long, global = true)]
     verbose: bool,
 
-    /// Minimum severity level to report
-    #[arg(short = 's', long, global = true, value_enum, default_value = "info")]
-    min_severity: SeverityArg,
+    /// Suppress progress output
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Minimum severity level to report (overrides the config file; default: info)
+    #[arg(short = 's', long, global = true, value_enum)]
+    min_severity: Option<SeverityArg>,
+
+    /// Project config file (TOML or JSON); defaults to `.saft.toml` in the analyzed directory
+    #[arg(short = 'c', long, global = true)]
+    config: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -41,20 +76,85 @@ enum Commands {
         #[arg(short = 'f', long, value_enum, default_value = "text")]
         format: FormatArg,
 
-        /// Output file (defaults to stdout)
+        /// Output file (defaults to stdout); a `.zst` extension (e.g. `report.json.zst`) compresses it with zstd
         #[arg(short = 'o', long)]
         output: Option<PathBuf>,
 
-        /// Maximum file size to analyze in bytes
-        #[arg(long, default_value = "10485760")]
-        max_file_size: usize,
+        /// Write one report per analyzed file into DIR, named after the source path, plus an index.json summary
+        #[arg(long, value_name = "DIR", conflicts_with = "output")]
+        output_dir: Option<PathBuf>,
+
+        /// Split --output-dir reports per analyzed file or per top-level subdirectory (default: file)
+        #[arg(long, value_enum, requires = "output_dir")]
+        split_by: Option<SplitByArg>,
+
+        /// Maximum file size to analyze in bytes (overrides the config file; default: 10485760)
+        #[arg(long)]
+        max_file_size: Option<usize>,
+
+        /// Seconds after which analysis of a single file is abandoned (overrides the config file; default: no limit)
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+
+        /// Group findings in the text report by file, severity or rule
+        #[arg(long, value_enum, default_value = "file")]
+        group_by: GroupByArg,
+
+        /// Baseline of accepted findings (JSON); findings listed in it are not reported
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<PathBuf>,
+
+        /// Remove baseline entries whose finding is no longer reported and save the baseline
+        #[arg(long, requires = "baseline", conflicts_with = "changed")]
+        prune_baseline: bool,
+
+        /// If no finding at or above --fail-on remains, write the current findings to the baseline, dropping fixed ones
+        #[arg(long, requires = "baseline", conflicts_with = "changed")]
+        baseline_update_on_pass: bool,
+
+        /// Warn about baseline entries accepted more than DAYS ago
+        #[arg(long, value_name = "DAYS", requires = "baseline")]
+        stale_after: Option<u64>,
+
+        /// Exit with code 1 when a reported finding has this severity or higher
+        #[arg(long, value_enum, default_value = "high")]
+        fail_on: SeverityArg,
+
+        /// Only analyze .rs files under PATH changed since BASE_REF (per `git diff --name-only`)
+        #[arg(long, value_name = "BASE_REF")]
+        changed: Option<String>,
     },
 
     /// Show version information
-    Version,
+    Version {
+        /// Output format
+        #[arg(short = 'f', long, value_enum, default_value = "text")]
+        format: OutputFormatArg,
+    },
 
     /// List available security rules
-    Rules,
+    Rules {
+        /// Show which analyzer reports each rule; exits with an error if any rule has none
+        #[arg(long, conflicts_with = "format")]
+        coverage: bool,
+
+        /// Output format
+        #[arg(short = 'f', long, value_enum, default_value = "text")]
+        format: OutputFormatArg,
+    },
+
+    /// Explain a rule with vulnerable and fixed code examples
+    Explain {
+        /// Rule ID, e.g. SAFT-003
+        rule_id: String,
+    },
+
+    /// Write a commented .saft.toml with the default settings to the current directory
+    Init {
+        /// Overwrite an existing .saft.toml
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[derive(Clone, ValueEnum)]
@@ -63,6 +163,49 @@ enum FormatArg {
     Json,
     Html,
     Sarif,
+    Csv,
+}
+
+/// Output format of the informational commands
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+enum OutputFormatArg {
+    Text,
+    Json,
+}
+
+/// Version information, as printed by `saft version --format json`
+#[derive(Serialize)]
+struct VersionInfo {
+    name: &'static str,
+    version: &'static str,
+    authors: &'static str,
+    repository: &'static str,
+}
+
+impl VersionInfo {
+    fn current() -> Self {
+        Self {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+            authors: env!("CARGO_PKG_AUTHORS"),
+            repository: env!("CARGO_PKG_REPOSITORY"),
+        }
+    }
+}
+
+/// What the analyze command analyzes
+struct AnalyzeTarget {
+    path: PathBuf,
+    /// Only files changed since this git ref
+    changed_since: Option<String>,
+}
+
+/// Baseline flags of the analyze command
+struct BaselineOptions {
+    path: PathBuf,
+    prune: bool,
+    update_on_pass: bool,
+    stale_after: Option<u64>,
 }
 
 impl From<FormatArg> for ReportFormat {
@@ -72,10 +215,51 @@ impl From<FormatArg> for ReportFormat {
             FormatArg::Json => ReportFormat::Json,
             FormatArg::Html => ReportFormat::Html,
             FormatArg::Sarif => ReportFormat::Sarif,
+            FormatArg::Csv => ReportFormat::Csv,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum GroupByArg {
+    File,
+    Severity,
+    Rule,
+}
+
+impl From<GroupByArg> for GroupBy {
+    fn from(arg: GroupByArg) -> Self {
+        match arg {
+            GroupByArg::File => GroupBy::File,
+            GroupByArg::Severity => GroupBy::Severity,
+            GroupByArg::Rule => GroupBy::Rule,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum SplitByArg {
+    File,
+    Directory,
+}
+
+impl From<SplitByArg> for SplitBy {
+    fn from(arg: SplitByArg) -> Self {
+        match arg {
+            SplitByArg::File => SplitBy::File,
+            SplitByArg::Directory => SplitBy::Directory,
         }
     }
 }
 
+/// Where the analyze command writes its report
+enum ReportOutput {
+    Stdout,
+    File(PathBuf),
+    /// One report per group of files, plus an index
+    Directory { dir: PathBuf, split_by: SplitBy },
+}
+
 #[derive(Clone, ValueEnum)]
 enum SeverityArg {
     Info,
@@ -118,115 +302,414 @@ fn main() {
             path,
             format,
             output,
+            output_dir,
+            split_by,
             max_file_size,
+            timeout,
+            group_by,
+            baseline,
+            prune_baseline,
+            baseline_update_on_pass,
+            stale_after,
+            fail_on,
+            changed,
         } => {
-            let config = AnalyzerConfig {
-                min_severity: cli.min_severity.into(),
+            let config = match build_config(
+                cli.config.as_deref(),
+                &path,
+                cli.min_severity,
                 max_file_size,
-                verbose: cli.verbose,
-                ..Default::default()
+                timeout,
+                cli.verbose,
+            ) {
+                Ok(config) => config,
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red().bold(), e);
+                    std::process::exit(EXIT_ERROR);
+                }
             };
 
-            run_analysis(path, config, format.into(), output);
+            let progress = ProgressMode::detect(cli.verbose, cli.quiet);
+            let compression = output.as_deref().map(Compression::from_path).unwrap_or_default();
+            let reporter = Reporter::new(format.into())
+                .with_group_by(group_by.into())
+                .with_compression(compression);
+            let baseline = baseline.map(|path| BaselineOptions {
+                path,
+                prune: prune_baseline,
+                update_on_pass: baseline_update_on_pass,
+                stale_after,
+            });
+            let output = match (output, output_dir) {
+                (Some(file), _) => ReportOutput::File(file),
+                (None, Some(dir)) => ReportOutput::Directory {
+                    dir,
+                    split_by: split_by.map(Into::into).unwrap_or_default(),
+                },
+                (None, None) => ReportOutput::Stdout,
+            };
+            let target = AnalyzeTarget { path, changed_since: changed };
+            let code = run_analysis(target, config, reporter, output, progress, baseline, fail_on.into());
+            if code != EXIT_CLEAN {
+                std::process::exit(code);
+            }
+        }
+        Commands::Version { format } => match format {
+            OutputFormatArg::Text => {
+                let info = VersionInfo::current();
+                println!("SAFT Enhanced v{}", info.version);
+                println!("Static Analysis for FRAME Toolkit");
+                println!("\nAuthors: {}", info.authors);
+                println!("Repository: {}", info.repository);
+            }
+            OutputFormatArg::Json => print_json(&VersionInfo::current()),
+        },
+        Commands::Rules { coverage, format } => {
+            let ruleset = load_rule_set(cli.config.as_deref());
+            if coverage {
+                if !show_coverage(&ruleset, &mut std::io::stdout().lock()) {
+                    std::process::exit(EXIT_FINDINGS);
+                }
+            } else if format == OutputFormatArg::Json {
+                print_json(&ruleset.enabled_rules());
+            } else {
+                show_rules(&ruleset);
+            }
+        }
+        Commands::Explain { rule_id } => {
+            let ruleset = load_rule_set(cli.config.as_deref());
+            let stdout = std::io::stdout();
+            if let Err(message) = explain_rule(&ruleset, &rule_id, &mut stdout.lock()) {
+                eprintln!("{} {}", "Error:".red().bold(), message);
+                std::process::exit(EXIT_ERROR);
+            }
+        }
+        Commands::Init { force } => match init_config(Path::new("."), force) {
+            Ok(path) => println!("{} Wrote {}", "Success:".green().bold(), path.display()),
+            Err(message) => {
+                eprintln!("{} {}", "Error:".red().bold(), message);
+                std::process::exit(EXIT_ERROR);
+            }
+        },
+    }
+}
+
+/// Write the default project config into `dir`, keeping an existing file unless `force`
+fn init_config(dir: &Path, force: bool) -> Result<PathBuf, String> {
+    let path = dir.join(DEFAULT_CONFIG_FILE);
+    if path.exists() && !force {
+        return Err(format!(
+            "{} already exists; use --force to overwrite it",
+            path.display()
+        ));
+    }
+
+    let contents = ProjectConfig::template(&AnalyzerConfig::default(), &RuleSet::default());
+    std::fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    Ok(path)
+}
+
+/// Rule set from the project config, or the built-in rules; exits on error
+fn load_rule_set(explicit: Option<&Path>) -> RuleSet {
+    match load_project_config(explicit, Path::new(".")).and_then(|project| match project {
+        Some(project) => project.rule_set(),
+        None => Ok(RuleSet::default()),
+    }) {
+        Ok(ruleset) => ruleset,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(EXIT_ERROR);
         }
-        Commands::Version => {
-            println!("SAFT Enhanced v{}", env!("CARGO_PKG_VERSION"));
-            println!("Static Analysis for FRAME Toolkit");
-            println!("\nAuthors: {}", env!("CARGO_PKG_AUTHORS"));
-            println!("Repository: {}", env!("CARGO_PKG_REPOSITORY"));
+    }
+}
+
+/// Load the explicit config file, or discover `.saft.toml` for the target
+fn load_project_config(
+    explicit: Option<&Path>,
+    target: &Path,
+) -> saft_enhanced::Result<Option<ProjectConfig>> {
+    match explicit {
+        Some(path) => ProjectConfig::from_file(path).map(Some),
+        None => Ok(ProjectConfig::discover(target)?.map(|(path, config)| {
+            tracing::debug!("Using config file {}", path.display());
+            config
+        })),
+    }
+}
+
+/// Build the analyzer config: file values first, then CLI flags on top
+fn build_config(
+    explicit: Option<&Path>,
+    target: &Path,
+    min_severity: Option<SeverityArg>,
+    max_file_size: Option<usize>,
+    timeout: Option<u64>,
+    verbose: bool,
+) -> saft_enhanced::Result<AnalyzerConfig> {
+    let mut config = load_project_config(explicit, target)?
+        .map(|project| project.analyzer)
+        .unwrap_or_default();
+
+    if let Some(min_severity) = min_severity {
+        config.min_severity = min_severity.into();
+    }
+    if let Some(max_file_size) = max_file_size {
+        config.max_file_size = max_file_size;
+    }
+    if let Some(timeout) = timeout {
+        config.per_file_timeout = Some(timeout);
+    }
+    if verbose {
+        config.verbose = true;
+    }
+
+    Ok(config)
+}
+
+/// How per-file progress is rendered on stderr
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum ProgressMode {
+    /// No progress output
+    Hidden,
+    /// Single line rewritten in place (interactive terminal)
+    Inline,
+    /// One line per file (non-TTY stderr with --verbose)
+    Lines,
+}
+
+impl ProgressMode {
+    fn detect(verbose: bool, quiet: bool) -> Self {
+        Self::select(verbose, quiet, std::io::stderr().is_terminal())
+    }
+
+    fn select(verbose: bool, quiet: bool, is_tty: bool) -> Self {
+        if quiet {
+            ProgressMode::Hidden
+        } else if is_tty {
+            ProgressMode::Inline
+        } else if verbose {
+            ProgressMode::Lines
+        } else {
+            ProgressMode::Hidden
         }
-        Commands::Rules => {
-            show_rules();
+    }
+
+    fn report(&self, current: usize, total: usize, path: &Path) {
+        let stderr = std::io::stderr();
+        let mut handle = stderr.lock();
+        match self {
+            ProgressMode::Hidden => {}
+            ProgressMode::Inline => {
+                let _ = write!(handle, "\r\x1b[2K[{}/{}] analyzing {}", current, total, path.display());
+                if current == total {
+                    let _ = writeln!(handle);
+                }
+                let _ = handle.flush();
+            }
+            ProgressMode::Lines => {
+                let _ = writeln!(handle, "[{}/{}] analyzing {}", current, total, path.display());
+            }
         }
     }
 }
 
+/// Analyze `target`, write the report and return the process exit code
+///
+/// [`EXIT_FINDINGS`] when a reported finding is at least `fail_on` severe,
+/// [`EXIT_ERROR`] when analysis or writing the report or baseline failed.
 fn run_analysis(
-    path: PathBuf,
+    target: AnalyzeTarget,
+    config: AnalyzerConfig,
+    reporter: Reporter,
+    output: ReportOutput,
+    progress: ProgressMode,
+    baseline_options: Option<BaselineOptions>,
+    fail_on: Severity,
+) -> i32 {
+    match analyze_and_report(target, config, reporter, output, progress, baseline_options, fail_on) {
+        Ok(true) => EXIT_FINDINGS,
+        Ok(false) => EXIT_CLEAN,
+        Err(message) => {
+            eprintln!("{} {}", "Error:".red().bold(), message);
+            EXIT_ERROR
+        }
+    }
+}
+
+/// Body of [`run_analysis`]; `Ok(true)` when findings reach `fail_on`
+fn analyze_and_report(
+    target: AnalyzeTarget,
     config: AnalyzerConfig,
-    format: ReportFormat,
-    output_path: Option<PathBuf>,
-) {
+    reporter: Reporter,
+    output: ReportOutput,
+    progress: ProgressMode,
+    baseline_options: Option<BaselineOptions>,
+    fail_on: Severity,
+) -> Result<bool, String> {
     println!("{}", "SAFT Enhanced - Security Analysis".bold());
     println!("{}", "=".repeat(50));
     println!();
 
     let analyzer = Analyzer::new(config);
+    let path = target.path;
 
-    let results = if path.is_file() {
+    let mut results = if !path.exists() {
+        return Err(format!("Path does not exist: {}", path.display()));
+    } else if let Some(base_ref) = &target.changed_since {
+        let files = changed::changed_files(&path, base_ref).map_err(|e| e.to_string())?;
+        println!("Analyzing {} file(s) changed since {} in: {}", files.len(), base_ref, path.display());
+        analyzer
+            .analyze_files_with_progress(&files, |current, total, file| progress.report(current, total, file))
+            .map_err(|e| e.to_string())?
+    } else if path.is_file() {
         println!("Analyzing file: {}", path.display());
-        match analyzer.analyze_file(&path) {
-            Ok(result) => vec![result],
-            Err(e) => {
-                eprintln!("{} {}", "Error:".red().bold(), e);
-                std::process::exit(1);
-            }
-        }
+        vec![analyzer.analyze_file(&path).map_err(|e| e.to_string())?]
     } else if path.is_dir() {
         println!("Analyzing directory: {}", path.display());
-        match analyzer.analyze_directory(&path) {
-            Ok(results) => results,
-            Err(e) => {
-                eprintln!("{} {}", "Error:".red().bold(), e);
-                std::process::exit(1);
-            }
-        }
+        analyzer
+            .analyze_directory_with_progress(&path, |current, total, file| {
+                progress.report(current, total, file)
+            })
+            .map_err(|e| e.to_string())?
     } else {
-        eprintln!("{} Path does not exist: {}", "Error:".red().bold(), path.display());
-        std::process::exit(1);
+        return Err(format!("Path does not exist: {}", path.display()));
     };
 
     if results.is_empty() {
         println!("{}", "No Rust files found to analyze.".yellow());
-        return;
+        return Ok(false);
     }
 
+    // Findings as analyzed, before the baseline hides accepted ones
+    let analyzed = baseline_options.as_ref().map(|_| results.clone());
+    let baseline = baseline_options
+        .as_ref()
+        .map(|options| apply_baseline(options, &mut results))
+        .transpose()?;
+
     // Generate report
-    let reporter = Reporter::new(format);
-
-    if let Some(output_path) = output_path {
-        // Write to file
-        match std::fs::File::create(&output_path) {
-            Ok(mut file) => {
-                if let Err(e) = reporter.generate(&results, &mut file) {
-                    eprintln!("{} Failed to write report: {}", "Error:".red().bold(), e);
-                    std::process::exit(1);
-                }
-                println!("\n{} Report written to: {}", "Success:".green().bold(), output_path.display());
-            }
-            Err(e) => {
-                eprintln!("{} Failed to create output file: {}", "Error:".red().bold(), e);
-                std::process::exit(1);
-            }
+    match output {
+        ReportOutput::File(output_path) => {
+            let mut file = std::fs::File::create(&output_path)
+                .map_err(|e| format!("Failed to create output file: {}", e))?;
+            reporter
+                .generate(&results, &mut file)
+                .map_err(|e| format!("Failed to write report: {}", e))?;
+            println!("\n{} Report written to: {}", "Success:".green().bold(), output_path.display());
         }
-    } else {
-        // Write to stdout
-        let stdout = std::io::stdout();
-        let mut handle = stdout.lock();
-        if let Err(e) = reporter.generate(&results, &mut handle) {
-            eprintln!("{} Failed to generate report: {}", "Error:".red().bold(), e);
-            std::process::exit(1);
+        ReportOutput::Directory { dir, split_by } => {
+            // Report names are relative to the analyzed directory
+            let root = if path.is_file() { path.parent().unwrap_or(Path::new("")) } else { path.as_path() };
+            let index = reporter
+                .write_split(&results, root, split_by, &dir)
+                .map_err(|e| format!("Failed to write reports: {}", e))?;
+            println!(
+                "\n{} {} report(s) written to: {} (summary in {})",
+                "Success:".green().bold(),
+                index.reports.len(),
+                dir.display(),
+                INDEX_FILE
+            );
+        }
+        ReportOutput::Stdout => {
+            let stdout = std::io::stdout();
+            let mut handle = stdout.lock();
+            reporter
+                .generate(&results, &mut handle)
+                .map_err(|e| format!("Failed to generate report: {}", e))?;
         }
     }
 
-    // Determine exit code based on findings
-    let has_critical = results.iter().any(|r| r.metadata.severity_counts.critical > 0);
-    let has_high = results.iter().any(|r| r.metadata.severity_counts.high > 0);
+    if fails(&results, fail_on) {
+        return Ok(true);
+    }
 
-    if has_critical || has_high {
-        std::process::exit(1);
+    if let (Some(options), Some(mut baseline), Some(analyzed)) = (baseline_options, baseline, analyzed) {
+        if options.update_on_pass {
+            let changes = baseline.record(&analyzed, unix_now());
+            save_baseline(&baseline, &options.path)?;
+            println!(
+                "{} Baseline {} updated: {} added, {} fixed removed",
+                "Success:".green().bold(),
+                options.path.display(),
+                changes.added,
+                changes.removed.len()
+            );
+        }
     }
+
+    Ok(false)
+}
+
+/// Whether any reported finding is at least `fail_on` severe
+fn fails(results: &[AnalysisResult], fail_on: Severity) -> bool {
+    results
+        .iter()
+        .flat_map(|r| &r.vulnerabilities)
+        .any(|vuln| vuln.severity >= fail_on)
 }
 
-fn show_rules() {
-    use saft_enhanced::rules::RuleSet;
+/// Load the baseline, prune and report stale entries as asked, and hide
+/// accepted findings from `results`
+fn apply_baseline(options: &BaselineOptions, results: &mut [AnalysisResult]) -> Result<Baseline, String> {
+    let mut baseline = Baseline::from_file_or_default(&options.path).map_err(|e| e.to_string())?;
 
+    if options.prune {
+        let removed = baseline.prune(results);
+        save_baseline(&baseline, &options.path)?;
+        println!("Pruned {} fixed finding(s) from {}", removed.len(), options.path.display());
+    }
+
+    if let Some(days) = options.stale_after {
+        let max_age = Duration::from_secs(days * 24 * 60 * 60);
+        for entry in baseline.stale(unix_now(), max_age) {
+            eprintln!(
+                "{} {} in {} accepted more than {} days ago: {}",
+                "Stale:".yellow().bold(),
+                entry.rule_id,
+                entry.file.display(),
+                days,
+                entry.message
+            );
+        }
+    }
+
+    let suppressed = baseline.filter(results);
+    if suppressed > 0 {
+        println!("{} finding(s) suppressed by baseline {}", suppressed, options.path.display());
+    }
+
+    Ok(baseline)
+}
+
+/// Write the baseline file
+fn save_baseline(baseline: &Baseline, path: &Path) -> Result<(), String> {
+    baseline
+        .save(path)
+        .map_err(|e| format!("Failed to write baseline: {}", e))
+}
+
+/// Write a value as pretty JSON followed by a newline
+fn write_json<T: Serialize, W: Write>(value: &T, out: &mut W) -> serde_json::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, value)?;
+    writeln!(out).map_err(serde_json::Error::io)
+}
+
+/// Print a value as pretty JSON on stdout; exits on error
+fn print_json<T: Serialize>(value: &T) {
+    if let Err(e) = write_json(value, &mut std::io::stdout().lock()) {
+        eprintln!("{} Failed to write JSON: {}", "Error:".red().bold(), e);
+        std::process::exit(EXIT_ERROR);
+    }
+}
+
+fn show_rules(ruleset: &RuleSet) {
     println!("{}", "Available Security Rules".bold());
     println!("{}", "=".repeat(50));
     println!();
 
-    let ruleset = RuleSet::default();
-
     for rule in ruleset.enabled_rules() {
         let severity_color = match rule.severity {
             Severity::Critical => rule.severity.to_string().red().bold(),
@@ -243,6 +726,82 @@ fn show_rules() {
     }
 }
 
+/// Print the analyzers behind each rule; returns whether every rule has one
+fn show_coverage<W: Write>(ruleset: &RuleSet, out: &mut W) -> bool {
+    let coverage = ruleset.coverage();
+
+    let write = |out: &mut W| -> std::io::Result<()> {
+        writeln!(out, "{}", "Rule Coverage".bold())?;
+        writeln!(out, "{}", "=".repeat(50))?;
+        for rule in &coverage.rules {
+            let analyzers = if rule.analyzers.is_empty() {
+                "no analyzer".red().bold().to_string()
+            } else {
+                rule.analyzers.join(", ").green().to_string()
+            };
+            writeln!(out, "{} - {}: {}", rule.rule.id.cyan().bold(), rule.rule.name, analyzers)?;
+        }
+        for analyzer in &coverage.unknown {
+            writeln!(
+                out,
+                "{} analyzer '{}' reports {}, which is not a defined rule",
+                "Warning:".yellow().bold(),
+                analyzer.name,
+                analyzer.rule_id
+            )?;
+        }
+        Ok(())
+    };
+
+    if let Err(e) = write(out) {
+        eprintln!("{} Failed to write coverage: {}", "Error:".red().bold(), e);
+        return false;
+    }
+    coverage.is_complete()
+}
+
+/// Print a rule's details and guidance; errors for unknown rule IDs
+fn explain_rule<W: Write>(ruleset: &RuleSet, rule_id: &str, out: &mut W) -> Result<(), String> {
+    let rule_id = rule_id.to_uppercase();
+    let Some(rule) = ruleset.get_rule(&rule_id) else {
+        let known: Vec<&str> = ruleset.enabled_rules().iter().map(|r| r.id.as_str()).collect();
+        return Err(format!(
+            "Unknown rule '{}'. Available rules: {} (see `saft rules`)",
+            rule_id,
+            known.join(", ")
+        ));
+    };
+
+    let write = |out: &mut W| -> std::io::Result<()> {
+        writeln!(out, "{} - {}", rule.id.cyan().bold(), rule.name.bold())?;
+        writeln!(out, "{}", "=".repeat(50))?;
+        writeln!(out, "Severity: {}", rule.severity)?;
+        writeln!(out, "Category: {:?}", rule.category)?;
+        writeln!(out, "\n{}", rule.description)?;
+
+        let Some(docs) = rules::rule_docs(&rule.id) else {
+            return Ok(());
+        };
+
+        writeln!(out, "\n{}", docs.guidance)?;
+        writeln!(out, "\n{}", "Vulnerable:".red().bold())?;
+        for line in docs.vulnerable_example.lines() {
+            writeln!(out, "    {}", line)?;
+        }
+        writeln!(out, "\n{}", "Fixed:".green().bold())?;
+        for line in docs.fixed_example.lines() {
+            writeln!(out, "    {}", line)?;
+        }
+        writeln!(out, "\nReferences:")?;
+        for reference in docs.references {
+            writeln!(out, "  - {}", reference)?;
+        }
+        Ok(())
+    };
+
+    write(out).map_err(|e| format!("Failed to write explanation: {}", e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,6 +812,340 @@ mod tests {
         assert!(cli.is_ok());
     }
 
+    #[test]
+    fn test_group_by_flag() {
+        let cli = Cli::try_parse_from(["saft", "analyze", "src", "--group-by", "severity"]).unwrap();
+        let Commands::Analyze { group_by, .. } = cli.command else {
+            panic!("expected analyze command");
+        };
+        assert_eq!(GroupBy::from(group_by), GroupBy::Severity);
+
+        let cli = Cli::try_parse_from(["saft", "analyze", "src"]).unwrap();
+        let Commands::Analyze { group_by, .. } = cli.command else {
+            panic!("expected analyze command");
+        };
+        assert_eq!(GroupBy::from(group_by), GroupBy::File);
+    }
+
+    #[test]
+    fn test_output_dir_flags() {
+        assert!(Cli::try_parse_from(["saft", "analyze", "src", "--split-by", "directory"]).is_err());
+        assert!(Cli::try_parse_from(["saft", "analyze", "src", "-o", "r.json", "--output-dir", "reports"]).is_err());
+
+        let cli = Cli::try_parse_from([
+            "saft",
+            "analyze",
+            "src",
+            "--output-dir",
+            "reports",
+            "--split-by",
+            "directory",
+        ])
+        .unwrap();
+        let Commands::Analyze { output_dir, split_by, .. } = cli.command else {
+            panic!("expected analyze command");
+        };
+        assert_eq!(output_dir, Some(PathBuf::from("reports")));
+        assert_eq!(split_by.map(SplitBy::from), Some(SplitBy::Directory));
+    }
+
+    /// Exit code of analyzing `source`, written to a fresh directory
+    fn exit_code_for(name: &str, source: Option<&str>, fail_on: Severity) -> i32 {
+        let dir = std::env::temp_dir().join(format!("saft-exit-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("lib.rs");
+        if let Some(source) = source {
+            std::fs::write(&file, source).unwrap();
+        }
+
+        let code = run_analysis(
+            AnalyzeTarget { path: file, changed_since: None },
+            AnalyzerConfig::default(),
+            Reporter::new(ReportFormat::Json),
+            ReportOutput::File(dir.join("report.json")),
+            ProgressMode::Hidden,
+            None,
+            fail_on,
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+        code
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let clean = "fn add(a: u32, b: u32) -> Option<u32> { a.checked_add(b) }";
+        let unchecked = "fn add(a: u32, b: u32) -> u32 { a + b }";
+
+        assert_eq!(exit_code_for("clean", Some(clean), Severity::High), EXIT_CLEAN);
+        assert_eq!(exit_code_for("findings", Some(unchecked), Severity::High), EXIT_FINDINGS);
+        // The unchecked addition is High, below a Critical fail-on
+        assert_eq!(exit_code_for("below", Some(unchecked), Severity::Critical), EXIT_CLEAN);
+        assert_eq!(exit_code_for("unparseable", Some("fn add( {"), Severity::High), EXIT_ERROR);
+        assert_eq!(exit_code_for("missing", None, Severity::High), EXIT_ERROR);
+    }
+
+    #[test]
+    fn test_fail_on_flag() {
+        let cli = Cli::try_parse_from(["saft", "analyze", "src", "--fail-on", "medium"]).unwrap();
+        let Commands::Analyze { fail_on, .. } = cli.command else {
+            panic!("expected analyze command");
+        };
+        assert_eq!(Severity::from(fail_on), Severity::Medium);
+
+        let cli = Cli::try_parse_from(["saft", "analyze", "src"]).unwrap();
+        let Commands::Analyze { fail_on, .. } = cli.command else {
+            panic!("expected analyze command");
+        };
+        assert_eq!(Severity::from(fail_on), Severity::High);
+    }
+
+    #[test]
+    fn test_changed_flag() {
+        let cli = Cli::try_parse_from(["saft", "analyze", "src", "--changed", "origin/main"]).unwrap();
+        let Commands::Analyze { changed, .. } = cli.command else {
+            panic!("expected analyze command");
+        };
+        assert_eq!(changed.as_deref(), Some("origin/main"));
+
+        // A partial run must not rewrite the baseline
+        assert!(Cli::try_parse_from([
+            "saft",
+            "analyze",
+            "src",
+            "--changed",
+            "origin/main",
+            "--baseline",
+            "saft-baseline.json",
+            "--prune-baseline",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_baseline_flags_require_baseline() {
+        assert!(Cli::try_parse_from(["saft", "analyze", "src", "--prune-baseline"]).is_err());
+
+        let cli = Cli::try_parse_from([
+            "saft",
+            "analyze",
+            "src",
+            "--baseline",
+            "saft-baseline.json",
+            "--prune-baseline",
+            "--baseline-update-on-pass",
+            "--stale-after",
+            "90",
+        ])
+        .unwrap();
+        let Commands::Analyze {
+            baseline,
+            prune_baseline,
+            baseline_update_on_pass,
+            stale_after,
+            ..
+        } = cli.command
+        else {
+            panic!("expected analyze command");
+        };
+        assert_eq!(baseline, Some(PathBuf::from("saft-baseline.json")));
+        assert!(prune_baseline && baseline_update_on_pass);
+        assert_eq!(stale_after, Some(90));
+    }
+
+    #[test]
+    fn test_progress_mode_selection() {
+        assert_eq!(ProgressMode::select(false, true, true), ProgressMode::Hidden);
+        assert_eq!(ProgressMode::select(false, false, true), ProgressMode::Inline);
+        assert_eq!(ProgressMode::select(true, false, false), ProgressMode::Lines);
+        assert_eq!(ProgressMode::select(false, false, false), ProgressMode::Hidden);
+    }
+
+    fn write_config(name: &str, contents: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("saft-cli-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(".saft.toml"), contents).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_config_file_is_honored() {
+        let dir = write_config("file", "min_severity = \"medium\"\nexclude_paths = [\"target\"]\n");
+
+        let config = build_config(None, &dir, None, None, None, false).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config.min_severity, Severity::Medium);
+        assert_eq!(config.exclude_paths, vec![dir.join("target")]);
+    }
+
+    #[test]
+    fn test_cli_flag_overrides_config_file() {
+        let dir = write_config("override", "min_severity = \"medium\"\nexclude_paths = [\"target\"]\n");
+        let config_path = dir.join(".saft.toml");
+
+        let cli = Cli::try_parse_from([
+            "saft",
+            "analyze",
+            dir.to_str().unwrap(),
+            "--config",
+            config_path.to_str().unwrap(),
+            "--min-severity",
+            "high",
+            "--timeout",
+            "20",
+        ])
+        .unwrap();
+
+        let Commands::Analyze { path, max_file_size, timeout, .. } = cli.command else {
+            panic!("expected analyze command");
+        };
+        let config = build_config(
+            cli.config.as_deref(),
+            &path,
+            cli.min_severity,
+            max_file_size,
+            timeout,
+            cli.verbose,
+        )
+        .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config.min_severity, Severity::High);
+        assert_eq!(config.per_file_timeout, Some(20));
+        // Values not given on the command line still come from the file
+        assert_eq!(config.exclude_paths, vec![dir.join("target")]);
+    }
+
+    #[test]
+    fn test_defaults_without_config_file() {
+        let dir = std::env::temp_dir().join(format!("saft-cli-none-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let config = build_config(None, &dir, None, None, None, false).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config.min_severity, Severity::Info);
+        assert!(config.exclude_paths.is_empty());
+    }
+
+    #[test]
+    fn test_init_writes_default_config() {
+        let dir = std::env::temp_dir().join(format!("saft-cli-init-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path = init_config(&dir, false).unwrap();
+        let project = ProjectConfig::from_file(&path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+
+        // A second init keeps the existing file unless forced
+        let refused = init_config(&dir, false);
+        let forced = init_config(&dir, true);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let default = AnalyzerConfig::default();
+        let parsed = project.analyzer;
+        assert_eq!(parsed.min_severity, default.min_severity);
+        assert_eq!(parsed.enabled_categories, default.enabled_categories);
+        assert_eq!(parsed.exclude_paths, default.exclude_paths);
+        assert_eq!(parsed.max_file_size, default.max_file_size);
+        assert_eq!(parsed.verbose, default.verbose);
+        assert_eq!(parsed.severity_overrides, default.severity_overrides);
+        assert!(project.rules.is_none());
+        for rule in RuleSet::default().enabled_rules() {
+            assert!(contents.contains(&rule.id), "{} missing from template", rule.id);
+        }
+
+        assert!(refused.unwrap_err().contains("--force"));
+        assert!(forced.is_ok());
+    }
+
+    #[test]
+    fn test_explain_known_rule() {
+        let cli = Cli::try_parse_from(["saft", "explain", "SAFT-002"]).unwrap();
+        let Commands::Explain { rule_id } = cli.command else {
+            panic!("expected explain command");
+        };
+
+        let mut output = Vec::new();
+        explain_rule(&RuleSet::default(), &rule_id, &mut output).unwrap();
+
+        let text = String::from_utf8(output).unwrap();
+        assert!(text.contains("Missing Origin Check"));
+        assert!(text.contains("AccessControl"));
+        assert!(text.contains("ensure_origin"));
+        assert!(text.contains("https://docs.substrate.io/build/origins/"));
+    }
+
+    #[test]
+    fn test_explain_unknown_rule() {
+        let mut output = Vec::new();
+        let err = explain_rule(&RuleSet::default(), "SAFT-999", &mut output).unwrap_err();
+
+        assert!(err.contains("SAFT-999"));
+        assert!(err.contains("SAFT-001"));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_rules_coverage() {
+        let cli = Cli::try_parse_from(["saft", "rules", "--coverage"]).unwrap();
+        assert!(matches!(cli.command, Commands::Rules { coverage: true, .. }));
+        assert!(Cli::try_parse_from(["saft", "rules", "--coverage", "--format", "json"]).is_err());
+
+        // The default rules include ones no analyzer implements yet
+        let mut output = Vec::new();
+        assert!(!show_coverage(&RuleSet::default(), &mut output));
+        let text = String::from_utf8(output).unwrap();
+        assert!(text.contains("overflow"));
+        assert!(text.contains("no analyzer"));
+        assert!(!text.contains("not a defined rule"));
+
+        let rules = RuleSet::default()
+            .enabled_rules()
+            .into_iter()
+            .filter(|rule| ["SAFT-001", "SAFT-005"].contains(&rule.id.as_str()))
+            .cloned()
+            .collect();
+        let mut output = Vec::new();
+        assert!(show_coverage(&RuleSet::from_rules(rules), &mut output));
+        assert!(!String::from_utf8(output).unwrap().contains("no analyzer"));
+
+        let mut rules = RuleSet::default().enabled_rules().into_iter().cloned().collect::<Vec<_>>();
+        rules[0].id = "CUSTOM-001".to_string();
+        let mut output = Vec::new();
+        assert!(!show_coverage(&RuleSet::from_rules(rules), &mut output));
+        let text = String::from_utf8(output).unwrap();
+        assert!(text.contains("no analyzer"));
+        assert!(text.contains("SAFT-001, which is not a defined rule"));
+    }
+
+    #[test]
+    fn test_rules_json_output() {
+        let cli = Cli::try_parse_from(["saft", "rules", "--format", "json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Rules { coverage: false, format: OutputFormatArg::Json }
+        ));
+
+        let mut output = Vec::new();
+        write_json(&RuleSet::default().enabled_rules(), &mut output).unwrap();
+        let rules: Vec<rules::SecurityRule> = serde_json::from_slice(&output).unwrap();
+        assert!(rules.iter().any(|rule| rule.id == "SAFT-001"));
+        assert_eq!(rules.len(), RuleSet::default().enabled_rules().len());
+    }
+
+    #[test]
+    fn test_version_json_output() {
+        let cli = Cli::try_parse_from(["saft", "version", "-f", "json"]).unwrap();
+        assert!(matches!(cli.command, Commands::Version { format: OutputFormatArg::Json }));
+
+        let mut output = Vec::new();
+        write_json(&VersionInfo::current(), &mut output).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(json["name"], "saft-enhanced");
+    }
+
     #[test]
     fn test_severity_conversion() {
         let severity: Severity = SeverityArg::High.into();
@@ -0,0 +1,313 @@
+//! Project configuration files
+//!
+//! A project can commit a `.saft.toml` next to its pallets so every
+//! contributor runs the analyzer with the same settings. The file holds a full
+//! [`AnalyzerConfig`] plus an optional reference to a rule set file:
+//!
+//! ```toml
+//! min_severity = "medium"
+//! exclude_paths = ["target", "benches"]
+//! rules = "saft-rules.toml"
+//!
+//! [severity_overrides]
+//! SAFT-004 = "high"
+//! ```
+//!
+//! Relative paths are resolved against the directory containing the file.
+//! JSON files (`.json` extension) are accepted as well. `saft init` writes a
+//! commented starting point built by [`ProjectConfig::template`].
+
+use crate::rules::{RuleSet, SecurityRule};
+use crate::{AnalyzerConfig, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Config file name discovered in the analyzed directory
+pub const DEFAULT_CONFIG_FILE: &str = ".saft.toml";
+
+/// Contents of a project configuration file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectConfig {
+    /// Analyzer settings
+    #[serde(flatten)]
+    pub analyzer: AnalyzerConfig,
+    /// Rule set file to use instead of the built-in rules
+    pub rules: Option<PathBuf>,
+}
+
+/// Rule set file layout
+#[derive(Debug, Deserialize)]
+struct RuleSetFile {
+    rules: Vec<SecurityRule>,
+}
+
+impl ProjectConfig {
+    /// Load a configuration file, selecting the format by extension
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut config: ProjectConfig = read_config_file(path)?;
+
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        config.analyzer.exclude_paths = config
+            .analyzer
+            .exclude_paths
+            .iter()
+            .map(|p| resolve(base, p))
+            .collect();
+        config.rules = config.rules.as_deref().map(|p| resolve(base, p));
+
+        Ok(config)
+    }
+
+    /// Find and load `.saft.toml` for an analysis target
+    ///
+    /// For a file target the file's directory is searched. Returns the path
+    /// of the loaded file along with its contents.
+    pub fn discover<P: AsRef<Path>>(target: P) -> Result<Option<(PathBuf, Self)>> {
+        let target = target.as_ref();
+        let dir = if target.is_file() {
+            target.parent().unwrap_or_else(|| Path::new(""))
+        } else {
+            target
+        };
+
+        let candidate = dir.join(DEFAULT_CONFIG_FILE);
+        if !candidate.is_file() {
+            return Ok(None);
+        }
+
+        let config = Self::from_file(&candidate)?;
+        Ok(Some((candidate, config)))
+    }
+
+    /// Commented TOML config capturing `analyzer` and listing `rules`
+    ///
+    /// Parses back into the same analyzer settings.
+    pub fn template(analyzer: &AnalyzerConfig, rules: &RuleSet) -> String {
+        let mut out = String::new();
+        // Writing to a String cannot fail
+        let _ = write_template(&mut out, analyzer, rules);
+        out
+    }
+
+    /// Rule set referenced by this configuration, or the built-in rules
+    pub fn rule_set(&self) -> Result<RuleSet> {
+        match &self.rules {
+            Some(path) => {
+                let file: RuleSetFile = read_config_file(path)?;
+                Ok(RuleSet::from_rules(file.rules))
+            }
+            None => Ok(RuleSet::default()),
+        }
+    }
+}
+
+fn write_template(out: &mut String, analyzer: &AnalyzerConfig, rules: &RuleSet) -> std::fmt::Result {
+    writeln!(out, "# SAFT project configuration")?;
+    writeln!(out, "#")?;
+    writeln!(out, "# Used automatically when analyzing this directory; pass another file")?;
+    writeln!(out, "# with --config. Command-line flags take precedence over these values.")?;
+    writeln!(out)?;
+
+    writeln!(out, "# Minimum severity to report: info, low, medium, high or critical")?;
+    writeln!(out, "min_severity = {}", toml_value(&analyzer.min_severity))?;
+    writeln!(out)?;
+
+    writeln!(out, "# Vulnerability categories to check; remove an entry to skip it")?;
+    writeln!(out, "enabled_categories = [")?;
+    for category in &analyzer.enabled_categories {
+        writeln!(out, "    {},", toml_value(category))?;
+    }
+    writeln!(out, "]")?;
+    writeln!(out)?;
+
+    writeln!(out, "# Paths to skip, relative to this file, e.g. [\"target\", \"benches\"]")?;
+    writeln!(out, "exclude_paths = {}", toml_value(&analyzer.exclude_paths))?;
+    writeln!(out)?;
+
+    writeln!(out, "# Files larger than this many bytes are not analyzed")?;
+    writeln!(out, "max_file_size = {}", analyzer.max_file_size)?;
+    writeln!(out)?;
+
+    writeln!(out, "# Give up on a file after this many seconds; unset means no limit")?;
+    match analyzer.per_file_timeout {
+        Some(secs) => writeln!(out, "per_file_timeout = {}", secs)?,
+        None => writeln!(out, "# per_file_timeout = 30")?,
+    }
+    writeln!(out)?;
+
+    writeln!(out, "verbose = {}", analyzer.verbose)?;
+    writeln!(out)?;
+
+    writeln!(out, "# Use a custom rule set instead of the built-in rules")?;
+    writeln!(out, "# rules = \"saft-rules.toml\"")?;
+    writeln!(out)?;
+
+    writeln!(out, "# Enabled rules (details: saft explain <id>):")?;
+    for rule in rules.enabled_rules() {
+        writeln!(out, "#   {}  {} [{}]", rule.id, rule.name, rule.severity)?;
+    }
+    writeln!(out, "#")?;
+    writeln!(out, "# Change the severity reported for a rule:")?;
+    if analyzer.severity_overrides.is_empty() {
+        writeln!(out, "# [severity_overrides]")?;
+        writeln!(out, "# SAFT-004 = \"high\"")?;
+    } else {
+        let mut overrides: Vec<_> = analyzer.severity_overrides.iter().collect();
+        overrides.sort();
+        writeln!(out, "[severity_overrides]")?;
+        for (rule_id, severity) in overrides {
+            writeln!(out, "{} = {}", rule_id, toml_value(severity))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Inline TOML representation of a value
+fn toml_value<T: Serialize>(value: &T) -> String {
+    toml::Value::try_from(value)
+        .map(|v| v.to_string())
+        .unwrap_or_default()
+}
+
+/// Deserialize a TOML or JSON file
+fn read_config_file<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        Error::ConfigError(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+
+    let is_json = path.extension().and_then(|e| e.to_str()) == Some("json");
+    if is_json {
+        serde_json::from_str(&contents)
+            .map_err(|e| Error::ConfigError(format!("Invalid config {}: {}", path.display(), e)))
+    } else {
+        toml::from_str(&contents)
+            .map_err(|e| Error::ConfigError(format!("Invalid config {}: {}", path.display(), e)))
+    }
+}
+
+/// Resolve a path from a config file against the file's directory
+fn resolve(base: &Path, path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base.join(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Severity, VulnerabilityCategory};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("saft-config-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_toml_config_resolves_relative_paths() {
+        let dir = temp_dir("toml");
+        let path = dir.join(DEFAULT_CONFIG_FILE);
+        std::fs::write(
+            &path,
+            r#"
+            min_severity = "medium"
+            exclude_paths = ["target"]
+            enabled_categories = ["integer_overflow"]
+            "#,
+        )
+        .unwrap();
+
+        let config = ProjectConfig::from_file(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config.analyzer.min_severity, Severity::Medium);
+        assert_eq!(config.analyzer.exclude_paths, vec![dir.join("target")]);
+        assert_eq!(
+            config.analyzer.enabled_categories,
+            vec![VulnerabilityCategory::IntegerOverflow]
+        );
+        // Unset fields keep their defaults
+        assert_eq!(config.analyzer.max_file_size, AnalyzerConfig::default().max_file_size);
+    }
+
+    #[test]
+    fn test_json_config() {
+        let dir = temp_dir("json");
+        let path = dir.join("saft.json");
+        std::fs::write(&path, r#"{"min_severity": "high", "verbose": true}"#).unwrap();
+
+        let config = ProjectConfig::from_file(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config.analyzer.min_severity, Severity::High);
+        assert!(config.analyzer.verbose);
+    }
+
+    #[test]
+    fn test_discover_and_rule_set() {
+        let dir = temp_dir("discover");
+        assert!(ProjectConfig::discover(&dir).unwrap().is_none());
+
+        std::fs::write(dir.join(DEFAULT_CONFIG_FILE), "rules = \"rules.toml\"").unwrap();
+        std::fs::write(
+            dir.join("rules.toml"),
+            r#"
+            [[rules]]
+            id = "CUSTOM-001"
+            name = "Custom Rule"
+            description = "Project specific check"
+            category = "best_practice"
+            severity = "low"
+            enabled = true
+            "#,
+        )
+        .unwrap();
+
+        let (found, config) = ProjectConfig::discover(&dir).unwrap().unwrap();
+        let rules = config.rule_set().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(found, dir.join(DEFAULT_CONFIG_FILE));
+        assert!(rules.get_rule("CUSTOM-001").is_some());
+        assert!(rules.get_rule("SAFT-001").is_none());
+    }
+
+    #[test]
+    fn test_template_round_trips_custom_settings() {
+        let mut analyzer = AnalyzerConfig {
+            min_severity: Severity::Low,
+            enabled_categories: vec![VulnerabilityCategory::Reentrancy],
+            ..AnalyzerConfig::default()
+        };
+        analyzer.severity_overrides.insert("SAFT-004".to_string(), Severity::High);
+        analyzer.per_file_timeout = Some(30);
+
+        let template = ProjectConfig::template(&analyzer, &RuleSet::default());
+        assert!(template.contains("#   SAFT-002  Missing Origin Check"));
+
+        let parsed: ProjectConfig = toml::from_str(&template).unwrap();
+        assert_eq!(parsed.analyzer.min_severity, Severity::Low);
+        assert_eq!(parsed.analyzer.enabled_categories, vec![VulnerabilityCategory::Reentrancy]);
+        assert_eq!(parsed.analyzer.severity_overrides.get("SAFT-004"), Some(&Severity::High));
+        assert_eq!(parsed.analyzer.per_file_timeout, Some(30));
+        assert!(parsed.rules.is_none());
+    }
+
+    #[test]
+    fn test_invalid_config_is_error() {
+        let dir = temp_dir("invalid");
+        let path = dir.join(DEFAULT_CONFIG_FILE);
+        std::fs::write(&path, "min_severity = \"extreme\"").unwrap();
+
+        let result = ProjectConfig::from_file(&path);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+    }
+}
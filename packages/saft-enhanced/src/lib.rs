@@ -27,12 +27,20 @@
 //! ```
 
 pub mod analyzers;
+pub mod baseline;
+pub mod changed;
+pub mod config;
 pub mod parser;
 pub mod reporter;
 pub mod rules;
 
+use analyzers::VulnerabilityAnalyzer;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Main error type for SAFT Enhanced
@@ -55,6 +63,9 @@ pub enum Error {
 
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+
+    #[error("Git error: {0}")]
+    GitError(String),
 }
 
 /// Result type alias for SAFT Enhanced operations
@@ -76,6 +87,19 @@ pub enum Severity {
     Critical,
 }
 
+impl Severity {
+    /// Numeric weight of a finding at this severity, used for risk scores
+    pub fn weight(&self) -> u32 {
+        match self {
+            Severity::Info => 0,
+            Severity::Low => 1,
+            Severity::Medium => 3,
+            Severity::High => 8,
+            Severity::Critical => 20,
+        }
+    }
+}
+
 impl std::fmt::Display for Severity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -123,12 +147,43 @@ pub enum VulnerabilityCategory {
     BestPractice,
     /// XCM decimal precision issues
     XcmDecimalPrecision,
+    /// Attack observed on a live chain by runtime monitoring
+    RuntimeAttack,
+}
+
+impl VulnerabilityCategory {
+    /// CWE identifiers (MITRE Common Weakness Enumeration) related to this category
+    pub fn cwe_ids(&self) -> &'static [u32] {
+        match self {
+            VulnerabilityCategory::IntegerOverflow => &[190, 191],
+            VulnerabilityCategory::Reentrancy => &[1265, 841],
+            VulnerabilityCategory::AccessControl => &[284, 862],
+            VulnerabilityCategory::UncheckedCall => &[252],
+            VulnerabilityCategory::ErrorHandling => &[755],
+            VulnerabilityCategory::StorageManipulation => &[471],
+            VulnerabilityCategory::TimestampDependence => &[829],
+            VulnerabilityCategory::WeakRandomness => &[330, 338],
+            VulnerabilityCategory::DenialOfService => &[400, 770],
+            VulnerabilityCategory::BestPractice => &[710],
+            VulnerabilityCategory::XcmDecimalPrecision => &[681, 682],
+            // The attack pattern carries the specific CWEs
+            VulnerabilityCategory::RuntimeAttack => &[693],
+        }
+    }
+
+    /// Reference URLs for the related CWE entries
+    pub fn references(&self) -> Vec<String> {
+        self.cwe_ids()
+            .iter()
+            .map(|id| format!("https://cwe.mitre.org/data/definitions/{}.html", id))
+            .collect()
+    }
 }
 
 /// A detected vulnerability
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vulnerability {
-    /// Unique identifier for this vulnerability type
+    /// ID of the [`rules::SecurityRule`] the finding is reported under (e.g. `SAFT-001`)
     pub id: String,
     /// Severity level
     pub severity: Severity,
@@ -157,6 +212,40 @@ pub struct AnalysisResult {
     pub metadata: AnalysisMetadata,
 }
 
+impl AnalysisResult {
+    /// Result for a file whose analysis did not finish, with no findings
+    fn incomplete(path: &Path, reason: &str, duration_ms: u64) -> Self {
+        Self {
+            file: path.to_path_buf(),
+            vulnerabilities: vec![],
+            metadata: AnalysisMetadata {
+                total_vulnerabilities: 0,
+                severity_counts: SeverityCounts::default(),
+                risk_score: 0,
+                duration_ms,
+                analyzer_version: env!("CARGO_PKG_VERSION").to_string(),
+                incomplete: Some(reason.to_string()),
+            },
+        }
+    }
+
+    /// Keep only the findings matching `keep`, recounting the metadata
+    pub fn retain_vulnerabilities<F>(&mut self, keep: F)
+    where
+        F: FnMut(&Vulnerability) -> bool,
+    {
+        self.vulnerabilities.retain(keep);
+
+        let mut severity_counts = SeverityCounts::default();
+        for vuln in &self.vulnerabilities {
+            severity_counts.increment(vuln.severity);
+        }
+        self.metadata.total_vulnerabilities = self.vulnerabilities.len();
+        self.metadata.risk_score = severity_counts.risk_score();
+        self.metadata.severity_counts = severity_counts;
+    }
+}
+
 /// Metadata about the analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisMetadata {
@@ -164,10 +253,16 @@ pub struct AnalysisMetadata {
     pub total_vulnerabilities: usize,
     /// Count by severity
     pub severity_counts: SeverityCounts,
+    /// Weighted sum of findings (see `Severity::weight`)
+    #[serde(default)]
+    pub risk_score: u32,
     /// Analysis duration in milliseconds
     pub duration_ms: u64,
     /// SAFT Enhanced version
     pub analyzer_version: String,
+    /// Why the file was not fully analyzed (e.g. `analysis timed out`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incomplete: Option<String>,
 }
 
 /// Count of vulnerabilities by severity
@@ -191,10 +286,25 @@ impl SeverityCounts {
             Severity::Info => self.info += 1,
         }
     }
+
+    /// Weighted sum of the counted findings
+    pub fn risk_score(&self) -> u32 {
+        [
+            (Severity::Critical, self.critical),
+            (Severity::High, self.high),
+            (Severity::Medium, self.medium),
+            (Severity::Low, self.low),
+            (Severity::Info, self.info),
+        ]
+        .iter()
+        .map(|(severity, count)| severity.weight() * *count as u32)
+        .sum()
+    }
 }
 
 /// Configuration for the analyzer
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AnalyzerConfig {
     /// Minimum severity level to report
     pub min_severity: Severity,
@@ -206,6 +316,11 @@ pub struct AnalyzerConfig {
     pub max_file_size: usize,
     /// Enable verbose output
     pub verbose: bool,
+    /// Severity overrides by rule id (e.g. `SAFT-004` -> `high`)
+    pub severity_overrides: HashMap<String, Severity>,
+    /// Seconds after which the analysis of a single file is abandoned (no limit when unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_file_timeout: Option<u64>,
 }
 
 impl Default for AnalyzerConfig {
@@ -228,19 +343,39 @@ impl Default for AnalyzerConfig {
             exclude_paths: vec![],
             max_file_size: 10 * 1024 * 1024, // 10 MB
             verbose: false,
+            severity_overrides: HashMap::new(),
+            per_file_timeout: None,
         }
     }
 }
 
+/// Note recorded in the metadata of a file whose analysis exceeded `per_file_timeout`
+pub const TIMED_OUT: &str = "analysis timed out";
+
 /// Main analyzer for FRAME pallets
+#[derive(Clone)]
 pub struct Analyzer {
     config: AnalyzerConfig,
+    /// Analyzers run after the built-in ones
+    extra_analyzers: Vec<Arc<dyn VulnerabilityAnalyzer + Send + Sync>>,
 }
 
 impl Analyzer {
     /// Create a new analyzer with the given configuration
     pub fn new(config: AnalyzerConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            extra_analyzers: Vec::new(),
+        }
+    }
+
+    /// Run an additional analyzer on every file, after the built-in ones
+    pub fn with_analyzer<A>(mut self, analyzer: A) -> Self
+    where
+        A: VulnerabilityAnalyzer + Send + Sync + 'static,
+    {
+        self.extra_analyzers.push(Arc::new(analyzer));
+        self
     }
 
     /// Create an analyzer with default configuration
@@ -249,8 +384,52 @@ impl Analyzer {
     }
 
     /// Analyze a single Rust file
+    ///
+    /// With `per_file_timeout` set, a file taking longer is reported with no
+    /// findings and [`TIMED_OUT`] in its metadata instead.
     pub fn analyze_file<P: AsRef<Path>>(&self, path: P) -> Result<AnalysisResult> {
         let path = path.as_ref();
+        match self.config.per_file_timeout {
+            Some(secs) => self.analyze_file_with_deadline(path, Duration::from_secs(secs)),
+            None => self.run_analysis(path),
+        }
+    }
+
+    /// Analyze on a worker thread, giving up once `timeout` has passed
+    ///
+    /// A running analysis can't be interrupted, so a timed out worker is left
+    /// to finish in the background and its result is discarded.
+    fn analyze_file_with_deadline(&self, path: &Path, timeout: Duration) -> Result<AnalysisResult> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let analyzer = self.clone();
+        let file = path.to_path_buf();
+
+        std::thread::Builder::new()
+            .name("saft-analysis".to_string())
+            .spawn(move || {
+                // The receiver is gone once the deadline has passed
+                let _ = tx.send(analyzer.run_analysis(&file));
+            })?;
+
+        match rx.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => {
+                tracing::warn!(
+                    "Analysis of {} timed out after {}s",
+                    path.display(),
+                    timeout.as_secs()
+                );
+                Ok(AnalysisResult::incomplete(path, TIMED_OUT, timeout.as_millis() as u64))
+            }
+            Err(RecvTimeoutError::Disconnected) => Err(Error::AnalysisError(format!(
+                "Analysis of {} panicked",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Parse and analyze a file on the current thread
+    fn run_analysis(&self, path: &Path) -> Result<AnalysisResult> {
         let start = std::time::Instant::now();
 
         tracing::info!("Analyzing file: {}", path.display());
@@ -275,33 +454,38 @@ impl Analyzer {
             tracing::debug!("Running vulnerability analyzers...");
         }
 
-        // Integer overflow/underflow analysis
-        if self.is_category_enabled(&VulnerabilityCategory::IntegerOverflow) {
-            let overflow_vulns = analyzers::overflow::analyze(&ast, path)?;
-            vulnerabilities.extend(overflow_vulns);
-        }
-
-        // Access control analysis
-        if self.is_category_enabled(&VulnerabilityCategory::AccessControl) {
-            let access_vulns = analyzers::access_control::analyze(&ast, path)?;
-            vulnerabilities.extend(access_vulns);
+        for builtin in analyzers::BUILTIN_ANALYZERS {
+            if self.is_category_enabled(&builtin.category) {
+                vulnerabilities.extend((builtin.analyze)(&ast, path)?);
+            }
         }
 
-        // Reentrancy analysis
-        if self.is_category_enabled(&VulnerabilityCategory::Reentrancy) {
-            let reentrancy_vulns = analyzers::reentrancy::analyze(&ast, path)?;
-            vulnerabilities.extend(reentrancy_vulns);
+        for analyzer in &self.extra_analyzers {
+            if self.config.verbose {
+                tracing::debug!("Running analyzer {}", analyzer.name());
+            }
+            vulnerabilities.extend(analyzer.analyze(&ast, path)?);
         }
 
-        // XCM decimal precision analysis
-        if self.is_category_enabled(&VulnerabilityCategory::XcmDecimalPrecision) {
-            let xcm_vulns = analyzers::xcm_precision::analyze(&ast, path)?;
-            vulnerabilities.extend(xcm_vulns);
+        // Apply per-rule severity overrides before filtering
+        for vuln in &mut vulnerabilities {
+            if let Some(severity) = self.config.severity_overrides.get(&vuln.id) {
+                vuln.severity = *severity;
+            }
         }
 
         // Filter by minimum severity
         vulnerabilities.retain(|v| v.severity >= self.config.min_severity);
 
+        // Attach CWE references for the vulnerability category
+        for vuln in &mut vulnerabilities {
+            for reference in vuln.category.references() {
+                if !vuln.references.contains(&reference) {
+                    vuln.references.push(reference);
+                }
+            }
+        }
+
         // Calculate metadata
         let mut severity_counts = SeverityCounts::default();
         for vuln in &vulnerabilities {
@@ -319,38 +503,73 @@ impl Analyzer {
                     + severity_counts.medium
                     + severity_counts.low
                     + severity_counts.info,
+                risk_score: severity_counts.risk_score(),
                 severity_counts,
                 duration_ms,
                 analyzer_version: env!("CARGO_PKG_VERSION").to_string(),
+                incomplete: None,
             },
         })
     }
 
     /// Analyze all Rust files in a directory recursively
     pub fn analyze_directory<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<AnalysisResult>> {
+        self.analyze_directory_with_progress(dir, |_, _, _| {})
+    }
+
+    /// Analyze all Rust files in a directory recursively, reporting progress
+    ///
+    /// `progress` is invoked once per `.rs` file, before it is analyzed, with the
+    /// 1-based index of the file, the total number of files and the file path.
+    pub fn analyze_directory_with_progress<P, F>(
+        &self,
+        dir: P,
+        progress: F,
+    ) -> Result<Vec<AnalysisResult>>
+    where
+        P: AsRef<Path>,
+        F: FnMut(usize, usize, &Path),
+    {
         let dir = dir.as_ref();
-        let mut results = Vec::new();
 
-        for entry in walkdir::WalkDir::new(dir)
+        // Collect files up front so the total is known for progress reporting
+        let files: Vec<PathBuf> = walkdir::WalkDir::new(dir)
             .into_iter()
             .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("rs"))
+            .collect();
 
-            // Skip excluded paths
-            if self.is_excluded(path) {
-                continue;
-            }
+        self.analyze_files_with_progress(&files, progress)
+    }
+
+    /// Analyze the given files, reporting progress as
+    /// [`Self::analyze_directory_with_progress`] does
+    ///
+    /// Excluded paths are skipped. Files that fail to analyze are logged and
+    /// left out of the results.
+    pub fn analyze_files_with_progress<F>(
+        &self,
+        files: &[PathBuf],
+        mut progress: F,
+    ) -> Result<Vec<AnalysisResult>>
+    where
+        F: FnMut(usize, usize, &Path),
+    {
+        let files: Vec<&PathBuf> = files.iter().filter(|path| !self.is_excluded(path)).collect();
+
+        let total = files.len();
+        let mut results = Vec::new();
+
+        for (index, path) in files.iter().enumerate() {
+            progress(index + 1, total, path);
 
-            // Only analyze .rs files
-            if path.extension().and_then(|s| s.to_str()) == Some("rs") {
-                match self.analyze_file(path) {
-                    Ok(result) => results.push(result),
-                    Err(e) => {
-                        tracing::warn!("Failed to analyze {}: {}", path.display(), e);
-                        if self.config.verbose {
-                            tracing::error!("Error details: {:?}", e);
-                        }
+            match self.analyze_file(path) {
+                Ok(result) => results.push(result),
+                Err(e) => {
+                    tracing::warn!("Failed to analyze {}: {}", path.display(), e);
+                    if self.config.verbose {
+                        tracing::error!("Error details: {:?}", e);
                     }
                 }
             }
@@ -397,6 +616,22 @@ mod tests {
         assert_eq!(counts.medium, 0);
     }
 
+    #[test]
+    fn test_risk_score_weights_severity() {
+        let mut one_critical = SeverityCounts::default();
+        one_critical.increment(Severity::Critical);
+
+        let mut three_medium = SeverityCounts::default();
+        for _ in 0..3 {
+            three_medium.increment(Severity::Medium);
+        }
+
+        assert_eq!(one_critical.risk_score(), 20);
+        assert_eq!(three_medium.risk_score(), 9);
+        assert!(one_critical.risk_score() > three_medium.risk_score());
+        assert_eq!(SeverityCounts::default().risk_score(), 0);
+    }
+
     #[test]
     fn test_default_config() {
         let config = AnalyzerConfig::default();
@@ -404,4 +639,150 @@ mod tests {
         assert_eq!(config.enabled_categories.len(), 11);
         assert!(config.max_file_size > 0);
     }
+
+    #[test]
+    fn test_directory_progress_callback() {
+        let dir = std::env::temp_dir().join(format!("saft-progress-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("a.rs"), "fn a() {}").unwrap();
+        std::fs::write(dir.join("nested/b.rs"), "fn b() {}").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not rust").unwrap();
+
+        let analyzer = Analyzer::new(AnalyzerConfig::default());
+        let mut calls = Vec::new();
+        let results = analyzer
+            .analyze_directory_with_progress(&dir, |current, total, _| calls.push((current, total)))
+            .unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(calls, vec![(1, 2), (2, 2)]);
+    }
+
+    /// Stalls on `slow.rs`, standing in for a pathological file
+    struct SlowAnalyzer;
+
+    impl VulnerabilityAnalyzer for SlowAnalyzer {
+        fn analyze(&self, _ast: &syn::File, file_path: &Path) -> Result<Vec<Vulnerability>> {
+            if file_path.ends_with("slow.rs") {
+                std::thread::sleep(Duration::from_secs(30));
+            }
+            Ok(vec![])
+        }
+
+        fn name(&self) -> &str {
+            "slow"
+        }
+    }
+
+    #[test]
+    fn test_slow_file_times_out_and_others_complete() {
+        let dir = std::env::temp_dir().join(format!("saft-timeout-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("slow.rs"), "fn slow() {}").unwrap();
+        std::fs::write(dir.join("fast.rs"), "fn transfer_amount(a: u32, b: u32) -> u32 { a + b }").unwrap();
+
+        let config = AnalyzerConfig {
+            per_file_timeout: Some(1),
+            ..AnalyzerConfig::default()
+        };
+        let results = Analyzer::new(config)
+            .with_analyzer(SlowAnalyzer)
+            .analyze_directory(&dir)
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(results.len(), 2);
+        let result = |name: &str| results.iter().find(|r| r.file.ends_with(name)).unwrap();
+
+        let slow = result("slow.rs");
+        assert_eq!(slow.metadata.incomplete.as_deref(), Some(TIMED_OUT));
+        assert!(slow.vulnerabilities.is_empty());
+
+        let fast = result("fast.rs");
+        assert!(fast.metadata.incomplete.is_none());
+        assert!(!fast.vulnerabilities.is_empty());
+    }
+
+    #[test]
+    fn test_every_category_has_cwe_mapping() {
+        // Exhaustive match: adding a variant fails to compile until it is listed here
+        fn listed(category: &VulnerabilityCategory) -> bool {
+            match category {
+                VulnerabilityCategory::IntegerOverflow
+                | VulnerabilityCategory::Reentrancy
+                | VulnerabilityCategory::AccessControl
+                | VulnerabilityCategory::UncheckedCall
+                | VulnerabilityCategory::ErrorHandling
+                | VulnerabilityCategory::StorageManipulation
+                | VulnerabilityCategory::TimestampDependence
+                | VulnerabilityCategory::WeakRandomness
+                | VulnerabilityCategory::DenialOfService
+                | VulnerabilityCategory::BestPractice
+                | VulnerabilityCategory::XcmDecimalPrecision
+                | VulnerabilityCategory::RuntimeAttack => true,
+            }
+        }
+
+        for category in AnalyzerConfig::default().enabled_categories.iter() {
+            assert!(listed(category));
+            assert!(!category.cwe_ids().is_empty(), "{:?} has no CWE mapping", category);
+        }
+        assert_eq!(
+            VulnerabilityCategory::IntegerOverflow.references()[0],
+            "https://cwe.mitre.org/data/definitions/190.html"
+        );
+    }
+
+    #[test]
+    fn test_analysis_attaches_cwe_references() {
+        let path = std::env::temp_dir().join(format!("saft-cwe-{}.rs", std::process::id()));
+        std::fs::write(&path, "fn transfer_amount(a: u32, b: u32) -> u32 { a + b }").unwrap();
+
+        let analyzer = Analyzer::new(AnalyzerConfig::default());
+        let result = analyzer.analyze_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let overflow: Vec<_> = result
+            .vulnerabilities
+            .iter()
+            .filter(|v| v.category == VulnerabilityCategory::IntegerOverflow)
+            .collect();
+
+        assert!(!overflow.is_empty());
+        for vuln in overflow {
+            assert!(vuln
+                .references
+                .contains(&"https://cwe.mitre.org/data/definitions/190.html".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_severity_overrides() {
+        let path = std::env::temp_dir().join(format!("saft-override-{}.rs", std::process::id()));
+        std::fs::write(&path, "fn transfer_amount(a: u32, b: u32) -> u32 { a + b }").unwrap();
+
+        let mut bumped = AnalyzerConfig::default();
+        bumped
+            .severity_overrides
+            .insert("SAFT-001".to_string(), Severity::Critical);
+        let result = Analyzer::new(bumped).analyze_file(&path).unwrap();
+
+        let overflow: Vec<_> = result.vulnerabilities.iter().filter(|v| v.id == "SAFT-001").collect();
+        assert!(!overflow.is_empty());
+        assert!(overflow.iter().all(|v| v.severity == Severity::Critical));
+        assert_eq!(result.metadata.severity_counts.critical, overflow.len());
+
+        // Dropped below min_severity, the findings are filtered out
+        let mut lowered = AnalyzerConfig {
+            min_severity: Severity::Medium,
+            ..AnalyzerConfig::default()
+        };
+        lowered.severity_overrides.insert("SAFT-001".to_string(), Severity::Low);
+        let result = Analyzer::new(lowered).analyze_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.vulnerabilities.iter().all(|v| v.id != "SAFT-001"));
+    }
 }
@@ -10,7 +10,9 @@ use syn::{visit::Visit, File, Item, ItemMod};
 pub mod pallet;
 pub mod visitors;
 
-pub use pallet::{FramePallet, PalletConfig, PalletStorage, PalletCall, PalletEvent, PalletError};
+pub use pallet::{
+    FramePallet, PalletConfig, PalletStorage, PalletCall, PalletEvent, PalletError, StorageKind,
+};
 
 /// Parse a Rust source file into an AST
 pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<File> {
@@ -86,6 +88,9 @@ impl<'ast> Visit<'ast> for PalletExtractor {
                     self.storage_items.push(PalletStorage {
                         name: item_struct.ident.to_string(),
                         ty: quote::quote!(#item_struct).to_string(),
+                        kind: StorageKind::Unknown,
+                        key_types: vec![],
+                        value_type: None,
                     });
                 }
 
@@ -96,6 +101,19 @@ impl<'ast> Visit<'ast> for PalletExtractor {
                     });
                 }
             }
+            // Storage items declared as type aliases, e.g.
+            // `pub type Balances<T> = StorageMap<_, Blake2_128Concat, T::AccountId, u128>;`
+            Item::Type(item_type) if has_storage_attribute(&item_type.attrs) => {
+                let ty = &item_type.ty;
+                let (kind, key_types, value_type) = extract_storage_shape(ty);
+                self.storage_items.push(PalletStorage {
+                    name: item_type.ident.to_string(),
+                    ty: quote::quote!(#ty).to_string(),
+                    kind,
+                    key_types,
+                    value_type,
+                });
+            }
             Item::Enum(item_enum) => {
                 // Check for Event enum
                 if has_event_attribute(&item_enum.attrs) {
@@ -149,6 +167,104 @@ fn has_storage_attribute(attrs: &[syn::Attribute]) -> bool {
     })
 }
 
+/// Classify a storage type and extract its key and value types
+///
+/// Handles both the positional form (`StorageMap<_, Hasher, Key, Value>`) and
+/// the named-generics form (`StorageMap<Hasher = .., Key = .., Value = ..>`).
+fn extract_storage_shape(ty: &syn::Type) -> (StorageKind, Vec<String>, Option<String>) {
+    let segment = match ty {
+        syn::Type::Path(type_path) => type_path.path.segments.last(),
+        _ => None,
+    };
+    let Some(segment) = segment else {
+        return (StorageKind::Unknown, vec![], None);
+    };
+
+    let kind = StorageKind::from_type_name(&segment.ident.to_string());
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return (kind, vec![], None);
+    };
+
+    let mut positional = Vec::new();
+    let mut named = std::collections::HashMap::new();
+    for arg in &args.args {
+        match arg {
+            syn::GenericArgument::Type(t) => positional.push(t),
+            syn::GenericArgument::AssocType(assoc) => {
+                named.insert(assoc.ident.to_string(), &assoc.ty);
+            }
+            _ => {}
+        }
+    }
+
+    let type_string = |t: &syn::Type| quote::quote!(#t).to_string();
+
+    if !named.is_empty() {
+        let named_key = |name: &str| named.get(name).map(|t| type_string(t));
+        let key_types = match kind {
+            StorageKind::Map | StorageKind::CountedMap => named_key("Key").into_iter().collect(),
+            StorageKind::DoubleMap => [named_key("Key1"), named_key("Key2")]
+                .into_iter()
+                .flatten()
+                .collect(),
+            StorageKind::NMap | StorageKind::CountedNMap => {
+                named.get("Key").map(|t| nmap_key_types(t)).unwrap_or_default()
+            }
+            StorageKind::Value | StorageKind::Unknown => vec![],
+        };
+        return (kind, key_types, named_key("Value"));
+    }
+
+    // The first positional argument is the storage prefix (`_`)
+    let rest = positional.get(1..).unwrap_or_default();
+    let at = |i: usize| rest.get(i).map(|t| type_string(t));
+
+    let (key_types, value_type) = match kind {
+        StorageKind::Value => (vec![], at(0)),
+        StorageKind::Map | StorageKind::CountedMap => (at(1).into_iter().collect(), at(2)),
+        StorageKind::DoubleMap => ([at(1), at(3)].into_iter().flatten().collect(), at(4)),
+        StorageKind::NMap | StorageKind::CountedNMap => (
+            rest.first().map(|t| nmap_key_types(t)).unwrap_or_default(),
+            at(1),
+        ),
+        StorageKind::Unknown => (vec![], None),
+    };
+
+    (kind, key_types, value_type)
+}
+
+/// Key types of an N-map key definition: `NMapKey<H, K>` or a tuple of them
+fn nmap_key_types(ty: &syn::Type) -> Vec<String> {
+    let keys: Vec<&syn::Type> = match ty {
+        syn::Type::Tuple(tuple) => tuple.elems.iter().collect(),
+        other => vec![other],
+    };
+
+    keys.into_iter()
+        .map(|key| {
+            // `NMapKey<Hasher, Key>` / `Key<Hasher, Key>`: keep the key type
+            let inner = match key {
+                syn::Type::Path(type_path) => type_path.path.segments.last().and_then(|seg| {
+                    match &seg.arguments {
+                        syn::PathArguments::AngleBracketed(args) => args
+                            .args
+                            .iter()
+                            .filter_map(|arg| match arg {
+                                syn::GenericArgument::Type(t) => Some(t),
+                                _ => None,
+                            })
+                            .nth(1),
+                        _ => None,
+                    }
+                }),
+                _ => None,
+            };
+            let key = inner.unwrap_or(key);
+            quote::quote!(#key).to_string()
+        })
+        .collect()
+}
+
 /// Check if attributes contain event macro
 fn has_event_attribute(attrs: &[syn::Attribute]) -> bool {
     attrs.iter().any(|attr| {
@@ -255,4 +371,105 @@ mod tests {
         let result = extract_pallet(&ast);
         assert!(result.is_err());
     }
+
+    /// Parse a single storage declaration inside a minimal pallet
+    fn parse_storage(declaration: &str) -> PalletStorage {
+        let source = format!(
+            r#"
+            #[frame_support::pallet]
+            pub mod pallet {{
+                #[pallet::storage]
+                {}
+            }}
+            "#,
+            declaration
+        );
+
+        let ast = parse_source(&source).unwrap();
+        let pallet = extract_pallet(&ast).unwrap();
+        assert_eq!(pallet.storage_items.len(), 1);
+        pallet.storage_items.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn test_storage_value() {
+        let storage = parse_storage("pub type TotalIssuance<T> = StorageValue<_, u128, ValueQuery>;");
+
+        assert_eq!(storage.name, "TotalIssuance");
+        assert_eq!(storage.kind, StorageKind::Value);
+        assert_eq!(storage.key_arity(), 0);
+        assert_eq!(storage.value_type.as_deref(), Some("u128"));
+        assert!(!storage.has_unbounded_count());
+    }
+
+    #[test]
+    fn test_storage_map() {
+        let storage = parse_storage(
+            "pub type Balances<T> = StorageMap<_, Blake2_128Concat, u64, u128, ValueQuery>;",
+        );
+
+        assert_eq!(storage.kind, StorageKind::Map);
+        assert_eq!(storage.key_types, vec!["u64"]);
+        assert_eq!(storage.value_type.as_deref(), Some("u128"));
+        assert!(storage.has_unbounded_count());
+    }
+
+    #[test]
+    fn test_storage_double_map() {
+        let storage = parse_storage(
+            "pub type Allowances<T> = StorageDoubleMap<_, Blake2_128Concat, u64, Twox64Concat, u32, u128>;",
+        );
+
+        assert_eq!(storage.kind, StorageKind::DoubleMap);
+        assert_eq!(storage.key_types, vec!["u64", "u32"]);
+        assert_eq!(storage.value_type.as_deref(), Some("u128"));
+    }
+
+    #[test]
+    fn test_storage_nmap() {
+        let storage = parse_storage(
+            "pub type Votes<T> = StorageNMap<_, (NMapKey<Blake2_128Concat, u32>, NMapKey<Twox64Concat, u64>, NMapKey<Identity, u8>), bool>;",
+        );
+
+        assert_eq!(storage.kind, StorageKind::NMap);
+        assert_eq!(storage.key_types, vec!["u32", "u64", "u8"]);
+        assert_eq!(storage.key_arity(), 3);
+        assert_eq!(storage.value_type.as_deref(), Some("bool"));
+        assert!(storage.has_unbounded_count());
+    }
+
+    #[test]
+    fn test_counted_storage_map() {
+        let storage = parse_storage(
+            "pub type Members<T> = CountedStorageMap<_, Blake2_128Concat, u64, bool>;",
+        );
+
+        assert_eq!(storage.kind, StorageKind::CountedMap);
+        assert_eq!(storage.key_types, vec!["u64"]);
+        assert_eq!(storage.value_type.as_deref(), Some("bool"));
+        assert!(!storage.has_unbounded_count());
+    }
+
+    #[test]
+    fn test_counted_storage_nmap() {
+        let storage = parse_storage(
+            "pub type Stakes<T> = CountedStorageNMap<_, NMapKey<Blake2_128Concat, u64>, u128>;",
+        );
+
+        assert_eq!(storage.kind, StorageKind::CountedNMap);
+        assert_eq!(storage.key_types, vec!["u64"]);
+        assert_eq!(storage.value_type.as_deref(), Some("u128"));
+        assert!(!storage.has_unbounded_count());
+    }
+
+    #[test]
+    fn test_storage_named_generics() {
+        let storage = parse_storage(
+            "pub type Balances<T> = StorageMap<Hasher = Blake2_128Concat, Key = u64, Value = u128>;",
+        );
+
+        assert_eq!(storage.kind, StorageKind::Map);
+        assert_eq!(storage.key_types, vec!["u64"]);
+        assert_eq!(storage.value_type.as_deref(), Some("u128"));
+    }
 }
@@ -33,6 +33,70 @@ pub struct PalletStorage {
     pub name: String,
     /// Type definition as string
     pub ty: String,
+    /// FRAME storage kind
+    pub kind: StorageKind,
+    /// Key types, in order (empty for `StorageValue`)
+    pub key_types: Vec<String>,
+    /// Value type, if it could be determined
+    pub value_type: Option<String>,
+}
+
+/// FRAME storage kinds, which differ in iteration and DoS characteristics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageKind {
+    /// `StorageValue`: a single value
+    Value,
+    /// `StorageMap`: one key
+    Map,
+    /// `StorageDoubleMap`: two keys
+    DoubleMap,
+    /// `StorageNMap`: a tuple of keys
+    NMap,
+    /// `CountedStorageMap`: one key, with a maintained item counter
+    CountedMap,
+    /// `CountedStorageNMap`: a tuple of keys, with a maintained item counter
+    CountedNMap,
+    /// Not a recognized FRAME storage type
+    Unknown,
+}
+
+impl StorageKind {
+    /// Classify a storage type by its name (e.g. `StorageDoubleMap`)
+    pub fn from_type_name(name: &str) -> Self {
+        match name {
+            "StorageValue" => StorageKind::Value,
+            "StorageMap" => StorageKind::Map,
+            "StorageDoubleMap" => StorageKind::DoubleMap,
+            "StorageNMap" => StorageKind::NMap,
+            "CountedStorageMap" => StorageKind::CountedMap,
+            "CountedStorageNMap" => StorageKind::CountedNMap,
+            _ => StorageKind::Unknown,
+        }
+    }
+
+    /// Whether the storage holds a keyed collection that can be iterated
+    pub fn is_map(&self) -> bool {
+        !matches!(self, StorageKind::Value | StorageKind::Unknown)
+    }
+
+    /// Whether the storage keeps a counter, making `.count()` O(1)
+    pub fn has_counter(&self) -> bool {
+        matches!(self, StorageKind::CountedMap | StorageKind::CountedNMap)
+    }
+}
+
+impl PalletStorage {
+    /// Number of keys needed to address a single entry
+    pub fn key_arity(&self) -> usize {
+        self.key_types.len()
+    }
+
+    /// Whether counting the entries requires iterating the whole map
+    ///
+    /// Plain maps have no counter, so `iter().count()` is unbounded work.
+    pub fn has_unbounded_count(&self) -> bool {
+        self.kind.is_map() && !self.kind.has_counter()
+    }
 }
 
 /// A dispatchable call
@@ -102,6 +166,9 @@ mod tests {
             storage_items: vec![PalletStorage {
                 name: "MyValue".to_string(),
                 ty: "u32".to_string(),
+                kind: StorageKind::Value,
+                key_types: vec![],
+                value_type: Some("u32".to_string()),
             }],
             calls: vec![],
             events: vec![],
@@ -112,6 +179,17 @@ mod tests {
         assert!(!pallet.has_storage("NonExistent"));
     }
 
+    #[test]
+    fn test_storage_kind_characteristics() {
+        assert!(!StorageKind::Value.is_map());
+        assert!(StorageKind::Map.is_map());
+        assert!(!StorageKind::Map.has_counter());
+        assert!(StorageKind::CountedMap.has_counter());
+        assert!(StorageKind::CountedNMap.has_counter());
+        assert_eq!(StorageKind::from_type_name("StorageNMap"), StorageKind::NMap);
+        assert_eq!(StorageKind::from_type_name("BoundedVec"), StorageKind::Unknown);
+    }
+
     #[test]
     fn test_pallet_call_names() {
         let pallet = FramePallet {
@@ -1,7 +1,89 @@
 //! Different output format implementations
 
 // Placeholder for future format implementations
-// - SARIF (Static Analysis Results Interchange Format)
 // - HTML with interactive visualization
 // - Markdown for documentation
-// - CSV for data analysis
+
+use crate::{Severity, Vulnerability};
+use serde_json::{json, Value};
+
+pub(crate) const SARIF_VERSION: &str = "2.1.0";
+
+pub(crate) const SARIF_SCHEMA: &str =
+    "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json";
+
+/// SARIF result level for a severity
+pub(crate) fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low | Severity::Info => "note",
+    }
+}
+
+/// `reportingDescriptor` for the rule a vulnerability was reported under
+pub(crate) fn sarif_rule(vuln: &Vulnerability) -> Value {
+    let mut rule = json!({
+        "id": vuln.id,
+        "shortDescription": { "text": vuln.message },
+        "fullDescription": { "text": vuln.description },
+        "defaultConfiguration": { "level": sarif_level(vuln.severity) },
+        "properties": {
+            "category": vuln.category,
+            "tags": vuln
+                .category
+                .cwe_ids()
+                .iter()
+                .map(|id| format!("CWE-{}", id))
+                .collect::<Vec<_>>(),
+        },
+    });
+    if let Some(remediation) = &vuln.remediation {
+        rule["help"] = json!({ "text": remediation });
+    }
+    if let Some(reference) = vuln.references.first() {
+        rule["helpUri"] = json!(reference);
+    }
+    rule
+}
+
+/// SARIF `result` for a vulnerability; SARIF lines and columns start at 1
+pub(crate) fn sarif_result(vuln: &Vulnerability, rule_index: usize) -> Value {
+    json!({
+        "ruleId": vuln.id,
+        "ruleIndex": rule_index,
+        "level": sarif_level(vuln.severity),
+        "message": { "text": vuln.message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": vuln.location.file.display().to_string() },
+                "region": {
+                    "startLine": vuln.location.line.max(1),
+                    "startColumn": vuln.location.column.max(1),
+                },
+            },
+        }],
+    })
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break (RFC 4180)
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+}
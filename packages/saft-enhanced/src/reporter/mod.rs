@@ -1,10 +1,20 @@
 //! Report generation for analysis results
 
-use crate::{AnalysisResult, Severity};
+use crate::{AnalysisResult, Severity, Vulnerability};
 use colored::Colorize;
-use std::io::Write;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::Path;
 
 pub mod formats;
+pub mod split;
+
+/// Number of files listed in the text summary's risk ranking
+const TOP_RISK_FILES: usize = 10;
+
+/// zstd level used for compressed reports
+const REPORT_COMPRESSION_LEVEL: i32 = 3;
 
 /// Format for the report output
 #[derive(Debug, Clone, Copy)]
@@ -17,17 +27,89 @@ pub enum ReportFormat {
     Html,
     /// SARIF format for integration with security tools
     Sarif,
+    /// CSV format, one row per vulnerability, for spreadsheets and ticketing systems
+    Csv,
+}
+
+impl ReportFormat {
+    /// File extension of reports in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Text => "txt",
+            ReportFormat::Json => "json",
+            ReportFormat::Html => "html",
+            ReportFormat::Sarif => "sarif",
+            ReportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// How findings are grouped in the text report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    /// One section per analyzed file
+    #[default]
+    File,
+    /// One section per severity, most severe first, across all files
+    Severity,
+    /// One section per rule ID, across all files
+    Rule,
+}
+
+/// Compression applied to the generated report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Plain output
+    #[default]
+    None,
+    /// A single zstd frame with a content checksum
+    Zstd,
+}
+
+impl Compression {
+    /// Infer the compression from an output path (`.zst`, e.g. `report.json.zst`, is zstd)
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+}
+
+/// Read a zstd-compressed report back, failing on corrupt or truncated input
+pub fn decompress_report<R: Read>(input: R) -> std::io::Result<Vec<u8>> {
+    let mut report = Vec::new();
+    zstd::stream::read::Decoder::new(input)?.read_to_end(&mut report)?;
+    Ok(report)
 }
 
 /// Reporter for analysis results
 pub struct Reporter {
     format: ReportFormat,
+    group_by: GroupBy,
+    compression: Compression,
 }
 
 impl Reporter {
     /// Create a new reporter with the specified format
     pub fn new(format: ReportFormat) -> Self {
-        Self { format }
+        Self {
+            format,
+            group_by: GroupBy::default(),
+            compression: Compression::default(),
+        }
+    }
+
+    /// Group findings by file (default), severity or rule in the text report
+    pub fn with_group_by(mut self, group_by: GroupBy) -> Self {
+        self.group_by = group_by;
+        self
+    }
+
+    /// Compress the report, e.g. for large CI artifacts
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
     }
 
     /// Generate a report from analysis results
@@ -35,12 +117,31 @@ impl Reporter {
         &self,
         results: &[AnalysisResult],
         output: &mut W,
+    ) -> std::io::Result<()> {
+        match self.compression {
+            Compression::None => self.generate_format(results, output),
+            Compression::Zstd => {
+                let mut encoder = zstd::stream::write::Encoder::new(output, REPORT_COMPRESSION_LEVEL)?;
+                encoder.include_checksum(true)?;
+                self.generate_format(results, &mut encoder)?;
+                encoder.finish()?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Write the report in the configured format, uncompressed
+    fn generate_format<W: Write>(
+        &self,
+        results: &[AnalysisResult],
+        output: &mut W,
     ) -> std::io::Result<()> {
         match self.format {
             ReportFormat::Text => self.generate_text(results, output),
             ReportFormat::Json => self.generate_json(results, output),
             ReportFormat::Html => self.generate_html(results, output),
             ReportFormat::Sarif => self.generate_sarif(results, output),
+            ReportFormat::Csv => self.generate_csv(results, output),
         }
     }
 
@@ -53,6 +154,22 @@ impl Reporter {
         writeln!(output, "\n{}", "SAFT Enhanced - Analysis Report".bold())?;
         writeln!(output, "{}", "=".repeat(50))?;
 
+        match self.group_by {
+            GroupBy::File => self.write_by_file(results, output)?,
+            GroupBy::Severity => self.write_by_severity(results, output)?,
+            GroupBy::Rule => self.write_by_rule(results, output)?,
+        }
+
+        writeln!(output, "\n{}", "=".repeat(50))?;
+        self.write_summary(results, output)
+    }
+
+    /// One section per file, in analysis order
+    fn write_by_file<W: Write>(
+        &self,
+        results: &[AnalysisResult],
+        output: &mut W,
+    ) -> std::io::Result<()> {
         for result in results {
             writeln!(output, "\nFile: {}", result.file.display().to_string().cyan())?;
             writeln!(
@@ -60,11 +177,16 @@ impl Reporter {
                 "Total vulnerabilities: {}",
                 result.metadata.total_vulnerabilities
             )?;
+            writeln!(output, "Risk score: {}", result.metadata.risk_score)?;
             writeln!(
                 output,
                 "Analysis time: {}ms",
                 result.metadata.duration_ms
             )?;
+            if let Some(reason) = &result.metadata.incomplete {
+                writeln!(output, "{}", format!("  Incomplete: {}", reason).yellow())?;
+                continue;
+            }
 
             if result.vulnerabilities.is_empty() {
                 writeln!(output, "{}", "  No vulnerabilities found!".green())?;
@@ -73,21 +195,91 @@ impl Reporter {
 
             writeln!(output, "\nVulnerabilities:")?;
             for vuln in &result.vulnerabilities {
-                let severity_str = self.colorize_severity(&vuln.severity);
-                writeln!(output, "\n  [{}] {}", severity_str, vuln.id)?;
-                writeln!(output, "  Location: {}:{}", vuln.location.file.display(), vuln.location.line)?;
-                writeln!(output, "  Message: {}", vuln.message)?;
-                writeln!(output, "  Category: {:?}", vuln.category)?;
-
-                if let Some(remediation) = &vuln.remediation {
-                    writeln!(output, "  Remediation: {}", remediation.yellow())?;
-                }
+                self.write_vulnerability(vuln, output)?;
             }
         }
 
-        writeln!(output, "\n{}", "=".repeat(50))?;
+        Ok(())
+    }
 
-        // Summary
+    /// Findings from all files, one section per severity (most severe first)
+    fn write_by_severity<W: Write>(
+        &self,
+        results: &[AnalysisResult],
+        output: &mut W,
+    ) -> std::io::Result<()> {
+        let mut groups: BTreeMap<Reverse<Severity>, Vec<&Vulnerability>> = BTreeMap::new();
+        for vuln in results.iter().flat_map(|r| &r.vulnerabilities) {
+            groups.entry(Reverse(vuln.severity)).or_default().push(vuln);
+        }
+
+        for (Reverse(severity), vulns) in groups {
+            let header = format!("{} ({})", self.colorize_severity(&severity), vulns.len());
+            self.write_group(&header, vulns, output)?;
+        }
+        Ok(())
+    }
+
+    /// Findings from all files, one section per rule ID
+    fn write_by_rule<W: Write>(
+        &self,
+        results: &[AnalysisResult],
+        output: &mut W,
+    ) -> std::io::Result<()> {
+        let mut groups: BTreeMap<&str, Vec<&Vulnerability>> = BTreeMap::new();
+        for vuln in results.iter().flat_map(|r| &r.vulnerabilities) {
+            groups.entry(vuln.id.as_str()).or_default().push(vuln);
+        }
+
+        for (rule_id, vulns) in groups {
+            let header = format!("{} - {:?} ({})", rule_id.cyan(), vulns[0].category, vulns.len());
+            self.write_group(&header, vulns, output)?;
+        }
+        Ok(())
+    }
+
+    /// A group header followed by its findings, ordered by location
+    fn write_group<W: Write>(
+        &self,
+        header: &str,
+        mut vulns: Vec<&Vulnerability>,
+        output: &mut W,
+    ) -> std::io::Result<()> {
+        vulns.sort_by(|a, b| {
+            (&a.location.file, a.location.line).cmp(&(&b.location.file, b.location.line))
+        });
+
+        writeln!(output, "\n{}", header.bold())?;
+        for vuln in vulns {
+            self.write_vulnerability(vuln, output)?;
+        }
+        Ok(())
+    }
+
+    /// A single finding in the text report
+    fn write_vulnerability<W: Write>(
+        &self,
+        vuln: &Vulnerability,
+        output: &mut W,
+    ) -> std::io::Result<()> {
+        let severity_str = self.colorize_severity(&vuln.severity);
+        writeln!(output, "\n  [{}] {}", severity_str, vuln.id)?;
+        writeln!(output, "  Location: {}:{}", vuln.location.file.display(), vuln.location.line)?;
+        writeln!(output, "  Message: {}", vuln.message)?;
+        writeln!(output, "  Category: {:?}", vuln.category)?;
+
+        if let Some(remediation) = &vuln.remediation {
+            writeln!(output, "  Remediation: {}", remediation.yellow())?;
+        }
+        Ok(())
+    }
+
+    /// Summary totals and the highest-risk files
+    fn write_summary<W: Write>(
+        &self,
+        results: &[AnalysisResult],
+        output: &mut W,
+    ) -> std::io::Result<()> {
         let total_vulns: usize = results.iter().map(|r| r.metadata.total_vulnerabilities).sum();
         let total_critical: usize = results.iter().map(|r| r.metadata.severity_counts.critical).sum();
         let total_high: usize = results.iter().map(|r| r.metadata.severity_counts.high).sum();
@@ -110,6 +302,25 @@ impl Reporter {
             writeln!(output, "  Low: {}", total_low.to_string().blue())?;
         }
 
+        // Highest-risk files first
+        let mut ranked: Vec<&AnalysisResult> = results
+            .iter()
+            .filter(|r| r.metadata.risk_score > 0)
+            .collect();
+        ranked.sort_by_key(|r| Reverse(r.metadata.risk_score));
+
+        if results.len() > 1 && !ranked.is_empty() {
+            writeln!(output, "\nHighest risk files:")?;
+            for result in ranked.iter().take(TOP_RISK_FILES) {
+                writeln!(
+                    output,
+                    "  {:>5}  {}",
+                    result.metadata.risk_score,
+                    result.file.display()
+                )?;
+            }
+        }
+
         Ok(())
     }
 
@@ -120,7 +331,7 @@ impl Reporter {
         output: &mut W,
     ) -> std::io::Result<()> {
         let json = serde_json::to_string_pretty(results)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+            .map_err(std::io::Error::other)?;
         writeln!(output, "{}", json)
     }
 
@@ -133,13 +344,68 @@ impl Reporter {
         writeln!(output, "<!-- HTML report not yet implemented -->")
     }
 
-    /// Generate SARIF report
+    /// Generate SARIF 2.1.0 report, one rule per vulnerability ID
     fn generate_sarif<W: Write>(
         &self,
-        _results: &[AnalysisResult],
+        results: &[AnalysisResult],
         output: &mut W,
     ) -> std::io::Result<()> {
-        writeln!(output, "{{\"version\": \"2.1.0\", \"$schema\": \"https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json\"}}")
+        let mut rules = Vec::new();
+        let mut rule_indices: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut sarif_results = Vec::new();
+
+        for vuln in results.iter().flat_map(|r| &r.vulnerabilities) {
+            let rule_index = *rule_indices.entry(vuln.id.as_str()).or_insert_with(|| {
+                rules.push(formats::sarif_rule(vuln));
+                rules.len() - 1
+            });
+            sarif_results.push(formats::sarif_result(vuln, rule_index));
+        }
+
+        let sarif = serde_json::json!({
+            "version": formats::SARIF_VERSION,
+            "$schema": formats::SARIF_SCHEMA,
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "SAFT Enhanced",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    }
+                },
+                "results": sarif_results,
+            }],
+        });
+
+        let json = serde_json::to_string_pretty(&sarif)
+            .map_err(std::io::Error::other)?;
+        writeln!(output, "{}", json)
+    }
+
+    /// Generate CSV report
+    fn generate_csv<W: Write>(
+        &self,
+        results: &[AnalysisResult],
+        output: &mut W,
+    ) -> std::io::Result<()> {
+        writeln!(output, "file,line,column,severity,category,id,message,remediation")?;
+
+        for vuln in results.iter().flat_map(|r| &r.vulnerabilities) {
+            let fields = [
+                vuln.location.file.display().to_string(),
+                vuln.location.line.to_string(),
+                vuln.location.column.to_string(),
+                vuln.severity.to_string(),
+                format!("{:?}", vuln.category),
+                vuln.id.clone(),
+                vuln.message.clone(),
+                vuln.remediation.clone().unwrap_or_default(),
+            ];
+            let row: Vec<String> = fields.iter().map(|f| formats::csv_field(f)).collect();
+            writeln!(output, "{}", row.join(","))?;
+        }
+
+        Ok(())
     }
 
     /// Colorize severity level for terminal output
@@ -188,8 +454,10 @@ mod tests {
                     low: 0,
                     info: 0,
                 },
+                risk_score: 8,
                 duration_ms: 100,
                 analyzer_version: "0.1.0".to_string(),
+                incomplete: None,
             },
         }];
 
@@ -210,8 +478,10 @@ mod tests {
             metadata: AnalysisMetadata {
                 total_vulnerabilities: 0,
                 severity_counts: SeverityCounts::default(),
+                risk_score: 0,
                 duration_ms: 50,
                 analyzer_version: "0.1.0".to_string(),
+                incomplete: None,
             },
         }];
 
@@ -220,6 +490,252 @@ mod tests {
         reporter.generate(&results, &mut output).unwrap();
 
         let report = String::from_utf8(output).unwrap();
-        assert!(serde_json::from_str::<serde_json::Value>(&report).is_ok());
+        let value: serde_json::Value = serde_json::from_str(&report).unwrap();
+        assert_eq!(value[0]["metadata"]["risk_score"], 0);
+    }
+
+    #[test]
+    fn test_compressed_json_report_round_trip() {
+        let results = vec![result_with_findings(
+            "src/lib.rs",
+            vec![finding("src/lib.rs", 3, "SAFT-001", Severity::High)],
+        )];
+
+        let mut plain = Vec::new();
+        Reporter::new(ReportFormat::Json).generate(&results, &mut plain).unwrap();
+
+        let mut compressed = Vec::new();
+        Reporter::new(ReportFormat::Json)
+            .with_compression(Compression::Zstd)
+            .generate(&results, &mut compressed)
+            .unwrap();
+        assert_ne!(compressed, plain);
+
+        let report = decompress_report(compressed.as_slice()).unwrap();
+        assert_eq!(report, plain);
+        let decoded: Vec<AnalysisResult> = serde_json::from_slice(&report).unwrap();
+        assert_eq!(decoded[0].vulnerabilities[0].id, "SAFT-001");
+    }
+
+    #[test]
+    fn test_corrupt_compressed_report_rejected() {
+        let mut compressed = Vec::new();
+        Reporter::new(ReportFormat::Json)
+            .with_compression(Compression::Zstd)
+            .generate(&[result_with_counts("src/lib.rs", SeverityCounts::default())], &mut compressed)
+            .unwrap();
+
+        let mut flipped = compressed.clone();
+        let middle = flipped.len() / 2;
+        flipped[middle] ^= 0xff;
+        let truncated = &compressed[..compressed.len() - 4];
+
+        assert!(decompress_report(flipped.as_slice()).is_err());
+        assert!(decompress_report(truncated).is_err());
+        assert!(decompress_report(&b"{\"plain\": true}"[..]).is_err());
+    }
+
+    #[test]
+    fn test_compression_inferred_from_extension() {
+        assert_eq!(Compression::from_path(Path::new("report.json.zst")), Compression::Zstd);
+        assert_eq!(Compression::from_path(Path::new("report.json")), Compression::None);
+        assert_eq!(Compression::from_path(Path::new("report")), Compression::None);
+    }
+
+    #[test]
+    fn test_csv_report_generation() {
+        let results = vec![AnalysisResult {
+            file: PathBuf::from("lib.rs"),
+            vulnerabilities: vec![Vulnerability {
+                id: "SAFT-002".to_string(),
+                severity: Severity::Medium,
+                category: VulnerabilityCategory::IntegerOverflow,
+                message: "Unchecked add, may overflow".to_string(),
+                description: "Test description".to_string(),
+                location: Location {
+                    file: PathBuf::from("lib.rs"),
+                    line: 42,
+                    column: 9,
+                    snippet: None,
+                },
+                remediation: None,
+                references: vec![],
+            }],
+            metadata: AnalysisMetadata {
+                total_vulnerabilities: 1,
+                severity_counts: SeverityCounts { medium: 1, ..Default::default() },
+                risk_score: 3,
+                duration_ms: 10,
+                analyzer_version: "0.1.0".to_string(),
+                incomplete: None,
+            },
+        }];
+
+        let reporter = Reporter::new(ReportFormat::Csv);
+        let mut output = Vec::new();
+        reporter.generate(&results, &mut output).unwrap();
+
+        let report = String::from_utf8(output).unwrap();
+        let mut lines = report.lines();
+        assert_eq!(
+            lines.next(),
+            Some("file,line,column,severity,category,id,message,remediation")
+        );
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("lib.rs,42,9,"));
+        assert!(row.contains(",SAFT-002,\"Unchecked add, may overflow\","));
+        assert_eq!(lines.next(), None);
+    }
+
+    fn result_with_counts(file: &str, severity_counts: SeverityCounts) -> AnalysisResult {
+        AnalysisResult {
+            file: PathBuf::from(file),
+            vulnerabilities: vec![],
+            metadata: AnalysisMetadata {
+                total_vulnerabilities: 0,
+                risk_score: severity_counts.risk_score(),
+                severity_counts,
+                duration_ms: 10,
+                analyzer_version: "0.1.0".to_string(),
+                incomplete: None,
+            },
+        }
+    }
+
+    fn finding(file: &str, line: usize, id: &str, severity: Severity) -> Vulnerability {
+        Vulnerability {
+            id: id.to_string(),
+            severity,
+            category: VulnerabilityCategory::IntegerOverflow,
+            message: format!("{} finding", severity),
+            description: "Test description".to_string(),
+            location: Location {
+                file: PathBuf::from(file),
+                line,
+                column: 1,
+                snippet: None,
+            },
+            remediation: None,
+            references: vec![],
+        }
+    }
+
+    fn result_with_findings(file: &str, vulnerabilities: Vec<Vulnerability>) -> AnalysisResult {
+        let mut result = result_with_counts(file, SeverityCounts::default());
+        result.metadata.total_vulnerabilities = vulnerabilities.len();
+        result.vulnerabilities = vulnerabilities;
+        result
+    }
+
+    #[test]
+    fn test_group_by_severity_orders_across_files() {
+        let results = vec![
+            result_with_findings(
+                "a.rs",
+                vec![
+                    finding("a.rs", 3, "SAFT-004", Severity::Low),
+                    finding("a.rs", 9, "SAFT-001", Severity::High),
+                ],
+            ),
+            result_with_findings(
+                "b.rs",
+                vec![
+                    finding("b.rs", 5, "SAFT-002", Severity::Critical),
+                    finding("b.rs", 7, "SAFT-003", Severity::Medium),
+                ],
+            ),
+        ];
+
+        let reporter = Reporter::new(ReportFormat::Text).with_group_by(GroupBy::Severity);
+        let mut output = Vec::new();
+        reporter.generate(&results, &mut output).unwrap();
+
+        let report = String::from_utf8(output).unwrap();
+        assert!(!report.contains("File: "));
+        let positions: Vec<usize> = ["b.rs:5", "a.rs:9", "b.rs:7", "a.rs:3"]
+            .iter()
+            .map(|location| report.find(location).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{}", report);
+    }
+
+    #[test]
+    fn test_group_by_rule() {
+        let results = vec![
+            result_with_findings("a.rs", vec![finding("a.rs", 3, "SAFT-002", Severity::Low)]),
+            result_with_findings(
+                "b.rs",
+                vec![
+                    finding("b.rs", 5, "SAFT-001", Severity::High),
+                    finding("b.rs", 8, "SAFT-002", Severity::Low),
+                ],
+            ),
+        ];
+
+        let reporter = Reporter::new(ReportFormat::Text).with_group_by(GroupBy::Rule);
+        let mut output = Vec::new();
+        reporter.generate(&results, &mut output).unwrap();
+
+        let report = String::from_utf8(output).unwrap();
+        let rule_one = report.find("b.rs:5").unwrap();
+        let rule_two = [report.find("a.rs:3").unwrap(), report.find("b.rs:8").unwrap()];
+        assert!(rule_one < rule_two[0] && rule_two[0] < rule_two[1]);
+    }
+
+    #[test]
+    fn test_summary_lists_highest_risk_first() {
+        let results = vec![
+            result_with_counts("medium.rs", SeverityCounts { medium: 3, ..Default::default() }),
+            result_with_counts("clean.rs", SeverityCounts::default()),
+            result_with_counts("critical.rs", SeverityCounts { critical: 1, ..Default::default() }),
+        ];
+
+        let reporter = Reporter::new(ReportFormat::Text);
+        let mut output = Vec::new();
+        reporter.generate(&results, &mut output).unwrap();
+
+        let report = String::from_utf8(output).unwrap();
+        let ranking = &report[report.find("Highest risk files:").unwrap()..];
+        let critical = ranking.find("critical.rs").unwrap();
+        let medium = ranking.find("medium.rs").unwrap();
+        assert!(critical < medium);
+        assert!(!ranking.contains("clean.rs"));
+    }
+
+    #[test]
+    fn test_sarif_report_shares_rules_across_results() {
+        let results = vec![
+            result_with_findings("a.rs", vec![finding("a.rs", 3, "SAFT-002", Severity::Low)]),
+            result_with_findings(
+                "b.rs",
+                vec![
+                    finding("b.rs", 5, "SAFT-001", Severity::High),
+                    finding("b.rs", 8, "SAFT-002", Severity::Low),
+                ],
+            ),
+        ];
+
+        let reporter = Reporter::new(ReportFormat::Sarif);
+        let mut output = Vec::new();
+        reporter.generate(&results, &mut output).unwrap();
+
+        let sarif: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "SAFT-002");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[1]["ruleId"], "SAFT-001");
+        assert_eq!(results[1]["ruleIndex"], 1);
+        assert_eq!(results[1]["level"], "error");
+        assert_eq!(results[2]["ruleIndex"], 0);
+        assert_eq!(results[2]["level"], "note");
+        assert_eq!(
+            results[2]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            8
+        );
     }
 }
@@ -0,0 +1,196 @@
+//! Reports split into one file per analyzed file or top-level directory
+
+use super::{Compression, Reporter};
+use crate::{AnalysisResult, SeverityCounts};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+/// Name of the summary written next to the split reports
+pub const INDEX_FILE: &str = "index.json";
+
+/// Separator replacing `/` when a source path becomes a report file name
+const PATH_SEPARATOR: &str = "__";
+
+/// How a report is split across files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplitBy {
+    /// One report per analyzed file
+    #[default]
+    File,
+    /// One report per top-level subdirectory of the analyzed directory;
+    /// files directly in it get a report of their own
+    Directory,
+}
+
+/// Summary of the split reports, written as [`INDEX_FILE`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportIndex {
+    /// Findings across all reports
+    pub total_vulnerabilities: usize,
+    pub severity_counts: SeverityCounts,
+    /// Reports, ordered by file name
+    pub reports: Vec<IndexEntry>,
+}
+
+/// One split report in the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Report file name, relative to the output directory
+    pub report: PathBuf,
+    /// Analyzed files included in the report
+    pub sources: Vec<PathBuf>,
+    pub total_vulnerabilities: usize,
+    pub severity_counts: SeverityCounts,
+    pub risk_score: u32,
+}
+
+impl IndexEntry {
+    fn new(report: PathBuf, results: &[AnalysisResult]) -> Self {
+        let mut severity_counts = SeverityCounts::default();
+        for vuln in results.iter().flat_map(|r| &r.vulnerabilities) {
+            severity_counts.increment(vuln.severity);
+        }
+
+        Self {
+            report,
+            sources: results.iter().map(|r| r.file.clone()).collect(),
+            total_vulnerabilities: results.iter().map(|r| r.vulnerabilities.len()).sum(),
+            risk_score: severity_counts.risk_score(),
+            severity_counts,
+        }
+    }
+}
+
+/// Report name for an analyzed file, from its path relative to `root`
+///
+/// `pallets/vault/src/lib.rs` becomes `pallets__vault__src__lib.rs` when
+/// splitting by file, and `pallets` when splitting by directory.
+pub fn report_name(file: &Path, root: &Path, split_by: SplitBy) -> String {
+    let relative = file.strip_prefix(root).unwrap_or(file);
+    let components: Vec<String> = relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+
+    match (split_by, components.as_slice()) {
+        // The analyzed path was the file itself
+        (_, []) => file
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "report".to_string()),
+        (SplitBy::Directory, [top, _, ..]) => top.clone(),
+        _ => components.join(PATH_SEPARATOR),
+    }
+}
+
+impl Reporter {
+    /// Write one report per group of results into `dir`, plus an [`INDEX_FILE`]
+    ///
+    /// Report names come from [`report_name`] with the format's extension,
+    /// and `.zst` when compressed. `root` is the analyzed directory (or the
+    /// analyzed file's parent) that source paths are made relative to.
+    pub fn write_split(
+        &self,
+        results: &[AnalysisResult],
+        root: &Path,
+        split_by: SplitBy,
+        dir: &Path,
+    ) -> std::io::Result<ReportIndex> {
+        let mut groups: BTreeMap<String, Vec<AnalysisResult>> = BTreeMap::new();
+        for result in results {
+            groups
+                .entry(report_name(&result.file, root, split_by))
+                .or_default()
+                .push(result.clone());
+        }
+
+        std::fs::create_dir_all(dir)?;
+
+        let mut reports = Vec::with_capacity(groups.len());
+        for (name, group) in &groups {
+            let mut file_name = format!("{}.{}", name, self.format.extension());
+            if self.compression == Compression::Zstd {
+                file_name.push_str(".zst");
+            }
+
+            let mut file = File::create(dir.join(&file_name))?;
+            self.generate(group, &mut file)?;
+            reports.push(IndexEntry::new(PathBuf::from(file_name), group));
+        }
+
+        let mut severity_counts = SeverityCounts::default();
+        for vuln in results.iter().flat_map(|r| &r.vulnerabilities) {
+            severity_counts.increment(vuln.severity);
+        }
+        let index = ReportIndex {
+            total_vulnerabilities: reports.iter().map(|r| r.total_vulnerabilities).sum(),
+            severity_counts,
+            reports,
+        };
+
+        let mut file = File::create(dir.join(INDEX_FILE))?;
+        serde_json::to_writer_pretty(&mut file, &index)?;
+        writeln!(file)?;
+
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::ReportFormat;
+    use crate::{Analyzer, AnalyzerConfig};
+
+    #[test]
+    fn test_report_names() {
+        let root = Path::new("/repo");
+        let file = Path::new("/repo/pallets/vault/src/lib.rs");
+        assert_eq!(report_name(file, root, SplitBy::File), "pallets__vault__src__lib.rs");
+        assert_eq!(report_name(file, root, SplitBy::Directory), "pallets");
+
+        let top_level = Path::new("/repo/build.rs");
+        assert_eq!(report_name(top_level, root, SplitBy::Directory), "build.rs");
+        assert_eq!(report_name(top_level, top_level, SplitBy::File), "build.rs");
+    }
+
+    #[test]
+    fn test_directory_split_into_reports_and_index() {
+        let dir = std::env::temp_dir().join(format!("saft-split-{}", std::process::id()));
+        let out = dir.join("reports");
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("a.rs"), "fn a() {}").unwrap();
+        std::fs::write(dir.join("nested/b.rs"), "fn b() {}").unwrap();
+
+        let results = Analyzer::new(AnalyzerConfig::default()).analyze_directory(&dir).unwrap();
+        let index = Reporter::new(ReportFormat::Json)
+            .write_split(&results, &dir, SplitBy::File, &out)
+            .unwrap();
+
+        let mut written: Vec<String> = std::fs::read_dir(&out)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        written.sort();
+        let report: Vec<AnalysisResult> =
+            serde_json::from_slice(&std::fs::read(out.join("nested__b.rs.json")).unwrap()).unwrap();
+        let stored: ReportIndex =
+            serde_json::from_slice(&std::fs::read(out.join(INDEX_FILE)).unwrap()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(written, vec!["a.rs.json", "index.json", "nested__b.rs.json"]);
+        assert_eq!(report.len(), 1);
+        assert!(report[0].file.ends_with("nested/b.rs"));
+
+        assert_eq!(index.reports.len(), 2);
+        assert_eq!(stored.reports.len(), 2);
+        assert_eq!(stored.reports[0].report, PathBuf::from("a.rs.json"));
+        assert_eq!(stored.reports[1].sources, vec![dir.join("nested/b.rs")]);
+    }
+}
@@ -1,5 +1,6 @@
 //! Security rules and patterns for FRAME pallets
 
+use crate::analyzers::{BuiltinAnalyzer, BUILTIN_ANALYZERS};
 use crate::{Severity, VulnerabilityCategory};
 use serde::{Deserialize, Serialize};
 
@@ -56,10 +57,23 @@ impl RuleSet {
                     severity: Severity::Medium,
                     enabled: true,
                 },
+                SecurityRule {
+                    id: "SAFT-005".to_string(),
+                    name: "XCM Decimal Precision".to_string(),
+                    description: "Cross-chain transfer amount not converted to the destination's decimals".to_string(),
+                    category: VulnerabilityCategory::XcmDecimalPrecision,
+                    severity: Severity::Critical,
+                    enabled: true,
+                },
             ],
         }
     }
 
+    /// Create a rule set from explicit rules
+    pub fn from_rules(rules: Vec<SecurityRule>) -> Self {
+        Self { rules }
+    }
+
     /// Get all enabled rules
     pub fn enabled_rules(&self) -> Vec<&SecurityRule> {
         self.rules.iter().filter(|r| r.enabled).collect()
@@ -69,6 +83,172 @@ impl RuleSet {
     pub fn get_rule(&self, id: &str) -> Option<&SecurityRule> {
         self.rules.iter().find(|r| r.id == id)
     }
+
+    /// Map the enabled rules to the built-in analyzers reporting them
+    pub fn coverage(&self) -> Coverage<'_> {
+        let rules = self
+            .enabled_rules()
+            .into_iter()
+            .map(|rule| RuleCoverage {
+                rule,
+                analyzers: BUILTIN_ANALYZERS
+                    .iter()
+                    .filter(|analyzer| analyzer.rule_id == rule.id)
+                    .map(|analyzer| analyzer.name)
+                    .collect(),
+            })
+            .collect();
+
+        let unknown = BUILTIN_ANALYZERS
+            .iter()
+            .filter(|analyzer| self.get_rule(analyzer.rule_id).is_none())
+            .collect();
+
+        Coverage { rules, unknown }
+    }
+}
+
+/// A rule and the built-in analyzers reporting it
+#[derive(Debug, Clone)]
+pub struct RuleCoverage<'a> {
+    pub rule: &'a SecurityRule,
+    /// Names of the analyzers whose findings carry the rule's ID
+    pub analyzers: Vec<&'static str>,
+}
+
+/// Which rules of a [`RuleSet`] are backed by a built-in analyzer
+#[derive(Debug, Clone)]
+pub struct Coverage<'a> {
+    pub rules: Vec<RuleCoverage<'a>>,
+    /// Analyzers reporting a rule the set does not define
+    pub unknown: Vec<&'static BuiltinAnalyzer>,
+}
+
+impl Coverage<'_> {
+    /// Rules no analyzer reports
+    pub fn unimplemented(&self) -> Vec<&SecurityRule> {
+        self.rules
+            .iter()
+            .filter(|coverage| coverage.analyzers.is_empty())
+            .map(|coverage| coverage.rule)
+            .collect()
+    }
+
+    /// Every rule has an analyzer and every analyzer a rule
+    pub fn is_complete(&self) -> bool {
+        self.unimplemented().is_empty() && self.unknown.is_empty()
+    }
+}
+
+/// Extended guidance for a built-in rule, shown by `saft explain`
+#[derive(Debug, Clone, Copy)]
+pub struct RuleDocs {
+    pub id: &'static str,
+    /// Why the pattern is dangerous and how to fix it
+    pub guidance: &'static str,
+    /// Code exhibiting the vulnerability
+    pub vulnerable_example: &'static str,
+    /// The same code with the vulnerability fixed
+    pub fixed_example: &'static str,
+    pub references: &'static [&'static str],
+}
+
+const RULE_DOCS: &[RuleDocs] = &[
+    RuleDocs {
+        id: "SAFT-001",
+        guidance: "Runtime arithmetic on balances, counters or indices must not wrap. \
+            In release builds `+`, `-` and `*` overflow silently, which can mint \
+            funds or bypass limits. Use `checked_*` and return an error, or \
+            `saturating_*` where clamping is the intended behaviour.",
+        vulnerable_example: "\
+let new_balance = balance + amount;
+Balances::<T>::insert(&who, new_balance);",
+        fixed_example: "\
+let new_balance = balance.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+Balances::<T>::insert(&who, new_balance);",
+        references: &[
+            "https://cwe.mitre.org/data/definitions/190.html",
+            "https://docs.substrate.io/build/troubleshoot-your-code/#arithmetic-overflow",
+        ],
+    },
+    RuleDocs {
+        id: "SAFT-002",
+        guidance: "Every dispatchable must verify its origin before touching state. \
+            Without `ensure_signed`, `ensure_root` or a custom `EnsureOrigin`, any \
+            account (or an unsigned extrinsic) can call privileged logic. Check the \
+            origin first and restrict administrative calls to root or a configured \
+            governance origin.",
+        vulnerable_example: "\
+#[pallet::call_index(0)]
+pub fn set_fee(origin: OriginFor<T>, fee: BalanceOf<T>) -> DispatchResult {
+    Fee::<T>::put(fee);
+    Ok(())
+}",
+        fixed_example: "\
+#[pallet::call_index(0)]
+pub fn set_fee(origin: OriginFor<T>, fee: BalanceOf<T>) -> DispatchResult {
+    T::AdminOrigin::ensure_origin(origin)?;
+    Fee::<T>::put(fee);
+    Ok(())
+}",
+        references: &[
+            "https://cwe.mitre.org/data/definitions/284.html",
+            "https://docs.substrate.io/build/origins/",
+        ],
+    },
+    RuleDocs {
+        id: "SAFT-003",
+        guidance: "Update storage before calling into other pallets or transferring \
+            funds (checks-effects-interactions). Hooks and currency implementations \
+            can call back into your pallet, observing stale state and repeating \
+            withdrawals.",
+        vulnerable_example: "\
+let amount = Deposits::<T>::get(&who);
+T::Currency::transfer(&Self::account_id(), &who, amount, AllowDeath)?;
+Deposits::<T>::remove(&who);",
+        fixed_example: "\
+let amount = Deposits::<T>::take(&who);
+T::Currency::transfer(&Self::account_id(), &who, amount, AllowDeath)?;",
+        references: &[
+            "https://cwe.mitre.org/data/definitions/1265.html",
+            "https://swcregistry.io/docs/SWC-107",
+        ],
+    },
+    RuleDocs {
+        id: "SAFT-004",
+        guidance: "A panic in runtime code aborts block production or makes the \
+            extrinsic unusable. Replace `unwrap()`/`expect()` with explicit error \
+            handling that returns a pallet `Error`, and reserve `expect` for \
+            invariants that are proven at compile time.",
+        vulnerable_example: "\
+let owner = Owners::<T>::get(id).unwrap();",
+        fixed_example: "\
+let owner = Owners::<T>::get(id).ok_or(Error::<T>::UnknownItem)?;",
+        references: &[
+            "https://cwe.mitre.org/data/definitions/755.html",
+            "https://docs.substrate.io/build/events-and-errors/",
+        ],
+    },
+    RuleDocs {
+        id: "SAFT-005",
+        guidance: "Parachains configure the same asset with different decimals, so an \
+            amount valid on one chain is off by orders of magnitude on another. \
+            Convert amounts to the destination's decimals before building the XCM \
+            transfer instead of hardcoding or forwarding raw balances.",
+        vulnerable_example: "\
+transfer_multiasset(dest, 10_000_000_000u128);",
+        fixed_example: "\
+transfer_multiasset(dest, convert_balance(amount, source_decimals, dest_decimals));",
+        references: &[
+            "https://cwe.mitre.org/data/definitions/682.html",
+            "https://docs.substrate.io/reference/xcm-reference/",
+        ],
+    },
+];
+
+/// Extended guidance for a built-in rule
+pub fn rule_docs(id: &str) -> Option<&'static RuleDocs> {
+    RULE_DOCS.iter().find(|docs| docs.id == id)
 }
 
 #[cfg(test)]
@@ -88,4 +268,52 @@ mod tests {
         let enabled = ruleset.enabled_rules();
         assert_eq!(enabled.len(), ruleset.rules.len());
     }
+
+    #[test]
+    fn test_every_default_rule_has_docs() {
+        let ruleset = RuleSet::default();
+        for rule in ruleset.enabled_rules() {
+            let docs = rule_docs(&rule.id).expect("built-in rule without docs");
+            assert!(!docs.vulnerable_example.is_empty());
+            assert!(!docs.references.is_empty());
+        }
+        assert!(rule_docs("SAFT-999").is_none());
+    }
+
+    #[test]
+    fn test_default_rules_match_analyzers() {
+        let ruleset = RuleSet::default();
+        let coverage = ruleset.coverage();
+
+        // Rules advertised without an analyzer behind them yet
+        let unimplemented: Vec<&str> = coverage.unimplemented().iter().map(|rule| rule.id.as_str()).collect();
+        assert_eq!(unimplemented, ["SAFT-002", "SAFT-003", "SAFT-004"]);
+
+        let unknown: Vec<&str> = coverage.unknown.iter().map(|analyzer| analyzer.rule_id).collect();
+        assert!(unknown.is_empty(), "analyzers reporting undefined rules: {:?}", unknown);
+
+        assert!(!coverage.is_complete());
+    }
+
+    #[test]
+    fn test_coverage_reports_gaps() {
+        let mut rules = RuleSet::default().rules;
+        rules.retain(|rule| rule.id != "SAFT-005");
+        rules.push(SecurityRule {
+            id: "CUSTOM-001".to_string(),
+            name: "Custom".to_string(),
+            description: "Project specific rule".to_string(),
+            category: VulnerabilityCategory::BestPractice,
+            severity: Severity::Low,
+            enabled: true,
+        });
+
+        let ruleset = RuleSet::from_rules(rules);
+        let coverage = ruleset.coverage();
+
+        assert!(!coverage.is_complete());
+        assert!(coverage.unimplemented().iter().any(|rule| rule.id == "CUSTOM-001"));
+        assert_eq!(coverage.unknown.len(), 1);
+        assert_eq!(coverage.unknown[0].name, "xcm_precision");
+    }
 }
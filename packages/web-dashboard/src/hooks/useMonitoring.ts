@@ -118,7 +118,7 @@ async function switchChain(request: SwitchChainRequest): Promise<SwitchChainResp
 
   if (!response.ok) {
     const error = await response.json()
-    throw new Error(error.error || 'Failed to switch chain')
+    throw new Error(error.error?.message || 'Failed to switch chain')
   }
 
   return response.json()
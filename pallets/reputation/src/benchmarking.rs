@@ -0,0 +1,86 @@
+//! Benchmarks for the reputation pallet
+//!
+//! `submit_proof` is dominated by the Groth16 check, which the runtime runs
+//! in wasm without host functions. The key and proof are built from the
+//! BN254 generators rather than a real circuit setup, so no prover is needed
+//! in the runtime, but they deserialize, subgroup-check and pair exactly like
+//! a real key and proof for one public input.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_groth16::{Proof, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_std::{vec, vec::Vec};
+
+/// Commitment proven in the benchmarks; a canonical field element
+const COMMITMENT: Commitment = [1u8; 32];
+
+fn compressed<S: CanonicalSerialize>(value: &S) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    value.serialize_compressed(&mut bytes).expect("writing to a Vec never fails");
+    bytes
+}
+
+/// Verifying key with every point at its group's generator
+fn verifying_key() -> Vec<u8> {
+    compressed(&VerifyingKey::<Bn254> {
+        alpha_g1: G1Affine::generator(),
+        beta_g2: G2Affine::generator(),
+        gamma_g2: G2Affine::generator(),
+        delta_g2: G2Affine::generator(),
+        // The commitment is the only public input
+        gamma_abc_g1: vec![G1Affine::generator(); 2],
+    })
+}
+
+/// Proof of `commitment` that holds against [`verifying_key`]
+///
+/// With every key point a generator, the check reduces to
+/// `a = 1 + (1 + commitment) + 1` in the exponent, with `b` and `c` generators.
+fn proof(commitment: &Commitment) -> Vec<u8> {
+    let input = Fr::deserialize_compressed(&commitment[..]).expect("commitment is a field element");
+    compressed(&Proof::<Bn254> {
+        a: (G1Affine::generator() * (Fr::from(3u64) + input)).into_affine(),
+        b: G2Affine::generator(),
+        c: G1Affine::generator(),
+    })
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn set_verifying_key() {
+        let key: BoundedVec<u8, T::MaxVerifyingKeyLen> =
+            verifying_key().try_into().expect("key fits MaxVerifyingKeyLen");
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, key);
+
+        assert!(ProofVerifyingKey::<T>::exists());
+    }
+
+    #[benchmark]
+    fn submit_proof() {
+        let key: BoundedVec<u8, T::MaxVerifyingKeyLen> =
+            verifying_key().try_into().expect("key fits MaxVerifyingKeyLen");
+        ProofVerifyingKey::<T>::put(key);
+        let proof: BoundedVec<u8, T::MaxProofLen> =
+            proof(&COMMITMENT).try_into().expect("proof fits MaxProofLen");
+        let caller: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), COMMITMENT, proof, Severity::Critical);
+
+        assert_eq!(UsedCommitments::<T>::get(COMMITMENT), Some(caller));
+        assert!(PendingFindings::<T>::contains_key(COMMITMENT));
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -1,15 +1,228 @@
 //! Reputation Pallet
 //!
 //! Manages reputation scores for auditors and security researchers
+//!
+//! Verified findings are credited to researchers through
+//! [`Pallet::note_verified_finding`] and stay claimable until the researcher
+//! calls `claim_reward`, which pays them from the pallet's treasury account
+//! according to the per-severity payouts in [`Config`].
+//!
+//! Researchers can also submit a finding without revealing it by calling
+//! `submit_proof` with a privacy-layer Groth16 proof (BN254) of their report
+//! commitment. The proof is checked against the verifying key set by root,
+//! and each commitment is accepted once. The commitment is the circuit's
+//! only public input: the proof binds neither the submitter nor the claimed
+//! severity, and since the proving key is public anyone can prove a made-up
+//! report. Accepted proofs are therefore only held in [`PendingFindings`]
+//! until the [`Config::ReportOrigin`], having reviewed the report disclosed
+//! to it, credits them with `acknowledge_finding` at the severity it assessed
+//! or drops them with `reject_finding` (e.g. a copy of another researcher's
+//! proof taken from the transaction pool). Rejecting a finding frees its
+//! commitment, so the researcher who actually holds the report can submit it.
+//!
+//! Each researcher has a [`ReputationRecord`] in [`ReputationScores`]. The
+//! configured [`Config::ReportOrigin`] credits reported vulnerabilities with
+//! `record_vulnerability`, weighted by severity (see
+//! [`Severity::reputation_points`]); acknowledged findings are credited the
+//! same way.
+//!
+//! Proofs verified outside the pallet, e.g. by a dedicated verifier pallet,
+//! are submitted through `submit_verified_report` once the configured
+//! [`VerifierProvider`] confirms them for the submitting account, which earns
+//! the reputation of the claimed severity on first acceptance. The severity
+//! is still only the submitter's claim, so no payout becomes claimable; that
+//! takes a `submit_proof` finding acknowledged by the report origin.
+//! Commitments accepted either way are accepted once.
+//!
+//! Scores of inactive researchers decay: every [`Config::DecayPeriod`] blocks
+//! a decay round starts, in which each researcher whose score hasn't changed
+//! for a full period loses [`Config::DecayRate`] of it (at least one point),
+//! never dropping below [`Config::ScoreFloor`]. A round walks
+//! [`ReputationScores`] at most [`Config::MaxDecayPerBlock`] records per
+//! block, resuming from the cursor kept in [`DecayRound`].
+//!
+//! The [`Config::SlashOrigin`] (e.g. governance) can take reputation from an
+//! auditor behind bogus reports with `slash_reputation`. The latest
+//! [`Config::MaxSlashHistory`] slashes of each account are kept in
+//! [`SlashHistory`]; older ones are dropped.
+//!
+//! Frontends and off-chain workers read reputation through
+//! [`runtime_api::ReputationApi`].
+//!
+//! Researchers can vouch for each other with `endorse`. An endorsement adds
+//! the square root of the endorser's own reputation to the target's, so
+//! accounts without reputation can't endorse and a single well-known
+//! endorser can't dominate. Each endorser may endorse a given target once per
+//! [`Config::EndorsePeriod`].
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod runtime_api;
+pub mod weights;
 
 #[frame_support::pallet]
 pub mod pallet {
+    use super::WeightInfo;
+    use ark_bn254::{Bn254, Fr};
+    use ark_groth16::{prepare_verifying_key, Groth16, Proof, VerifyingKey};
+    use ark_serialize::CanonicalDeserialize;
     use frame_support::pallet_prelude::*;
+    use frame_support::traits::{Currency, ExistenceRequirement};
+    use frame_support::PalletId;
     use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::{AccountIdConversion, IntegerSquareRoot, Saturating, Zero};
+    use sp_runtime::{PerThing, Percent};
+    use sp_std::vec::Vec;
+
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// Severity of a verified finding
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum Severity {
+        Low,
+        Medium,
+        High,
+        Critical,
+    }
+
+    impl Severity {
+        /// Reputation credited for a verified finding of this severity
+        pub fn reputation_points(&self) -> u32 {
+            match self {
+                Severity::Low => 1,
+                Severity::Medium => 3,
+                Severity::High => 7,
+                Severity::Critical => 15,
+            }
+        }
+    }
+
+    /// Severity from its index: 0 is low, 3 is critical
+    impl TryFrom<u8> for Severity {
+        type Error = ();
+
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            match value {
+                0 => Ok(Severity::Low),
+                1 => Ok(Severity::Medium),
+                2 => Ok(Severity::High),
+                3 => Ok(Severity::Critical),
+                _ => Err(()),
+            }
+        }
+    }
+
+    /// Reputation of a researcher
+    #[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ReputationRecord<BlockNumber> {
+        pub score: u32,
+        /// Vulnerabilities credited through reports or verified proofs
+        pub vulnerabilities_reported: u32,
+        /// Block the score last changed in
+        pub last_updated: BlockNumber,
+    }
+
+    /// Compressed BN254 scalar committing to a privacy-layer report
+    pub type Commitment = [u8; 32];
+
+    /// Confirms privacy-layer proofs verified outside this pallet
+    pub trait VerifierProvider<AccountId> {
+        /// Whether `who` proved the Groth16 statement behind `commitment`
+        fn is_verified(who: &AccountId, commitment: &Commitment) -> bool;
+    }
+
+    /// Verifies nothing, leaving `submit_proof` as the only way to credit proofs
+    impl<AccountId> VerifierProvider<AccountId> for () {
+        fn is_verified(_who: &AccountId, _commitment: &Commitment) -> bool {
+            false
+        }
+    }
+
+    /// Verified findings not yet paid out, per severity
+    #[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct FindingCounts {
+        pub low: u32,
+        pub medium: u32,
+        pub high: u32,
+        pub critical: u32,
+    }
+
+    impl FindingCounts {
+        pub fn is_empty(&self) -> bool {
+            *self == Self::default()
+        }
+
+        fn add(&mut self, severity: Severity) {
+            let count = match severity {
+                Severity::Low => &mut self.low,
+                Severity::Medium => &mut self.medium,
+                Severity::High => &mut self.high,
+                Severity::Critical => &mut self.critical,
+            };
+            *count = count.saturating_add(1);
+        }
+    }
+
+    /// Why reputation was slashed
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum SlashReason {
+        /// A reported vulnerability turned out not to exist
+        FalseReport,
+        /// A report copied an earlier finding
+        DuplicateReport,
+        /// Any other abuse, e.g. spamming reports
+        Misconduct,
+    }
+
+    /// A past slash of an account's reputation
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct SlashRecord<BlockNumber> {
+        /// Block the slash happened in
+        pub at: BlockNumber,
+        pub amount: u32,
+        pub reason: SlashReason,
+    }
+
+    /// A proven finding awaiting review by the report origin
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PendingFinding<AccountId, BlockNumber> {
+        /// Account that submitted the proof
+        pub who: AccountId,
+        /// Severity claimed by the submitter, not bound by the proof
+        pub claimed: Severity,
+        /// Block the proof was accepted in
+        pub submitted_at: BlockNumber,
+    }
+
+    /// Progress of a decay round through [`ReputationScores`]
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct DecayProgress<BlockNumber> {
+        /// Block the round started in
+        pub started: BlockNumber,
+        /// Raw storage key of the last record processed, `None` before the first batch
+        pub last_key: Option<Vec<u8>>,
+    }
+
+    /// An endorsement of one researcher by another
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Endorsement<BlockNumber> {
+        /// Reputation the endorsement added to the target
+        pub weight: u32,
+        /// Block the endorsement was made in
+        pub at: BlockNumber,
+    }
 
     #[pallet::pallet]
     pub struct Pallet<T>(_);
@@ -17,21 +230,594 @@ pub mod pallet {
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Currency rewards are paid in
+        type Currency: Currency<Self::AccountId>;
+
+        /// Derives the treasury account rewards are paid from
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// Reward per verified low severity finding
+        #[pallet::constant]
+        type LowPayout: Get<BalanceOf<Self>>;
+
+        /// Reward per verified medium severity finding
+        #[pallet::constant]
+        type MediumPayout: Get<BalanceOf<Self>>;
+
+        /// Reward per verified high severity finding
+        #[pallet::constant]
+        type HighPayout: Get<BalanceOf<Self>>;
+
+        /// Reward per verified critical finding
+        #[pallet::constant]
+        type CriticalPayout: Get<BalanceOf<Self>>;
+
+        /// Maximum size of the compressed Groth16 verifying key
+        #[pallet::constant]
+        type MaxVerifyingKeyLen: Get<u32>;
+
+        /// Maximum size of a compressed Groth16 proof
+        #[pallet::constant]
+        type MaxProofLen: Get<u32>;
+
+        /// Blocks before an endorser may endorse the same target again
+        #[pallet::constant]
+        type EndorsePeriod: Get<BlockNumberFor<Self>>;
+
+        /// Blocks between reputation decay rounds; zero disables decay
+        #[pallet::constant]
+        type DecayPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Share of an inactive researcher's score lost each decay round
+        #[pallet::constant]
+        type DecayRate: Get<Percent>;
+
+        /// Score below which decay never takes a researcher
+        #[pallet::constant]
+        type ScoreFloor: Get<u32>;
+
+        /// Reputation records a decay round processes per block
+        #[pallet::constant]
+        type MaxDecayPerBlock: Get<u32>;
+
+        /// Confirms proofs for `submit_verified_report`
+        type Verifier: VerifierProvider<Self::AccountId>;
+
+        /// Origin allowed to record reported vulnerabilities
+        type ReportOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to slash reputation
+        type SlashOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Slashes kept per account; older ones are dropped
+        #[pallet::constant]
+        type MaxSlashHistory: Get<u32>;
+
+        type WeightInfo: WeightInfo;
     }
 
+    /// Verified findings each researcher can still claim a reward for
+    #[pallet::storage]
+    pub type UnclaimedFindings<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, FindingCounts, ValueQuery>;
+
+    /// Reputation of each researcher
+    #[pallet::storage]
+    pub type ReputationScores<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ReputationRecord<BlockNumberFor<T>>, ValueQuery>;
+
+    /// Compressed verifying key for privacy-layer vulnerability proofs
+    #[pallet::storage]
+    pub type ProofVerifyingKey<T: Config> =
+        StorageValue<_, BoundedVec<u8, T::MaxVerifyingKeyLen>, OptionQuery>;
+
+    /// Accepted proof commitments and who submitted them; each is accepted once
+    #[pallet::storage]
+    pub type UsedCommitments<T: Config> =
+        StorageMap<_, Blake2_128Concat, Commitment, T::AccountId, OptionQuery>;
+
+    /// Commitments accepted through `submit_verified_report` and who submitted them
+    #[pallet::storage]
+    pub type VerifiedCommitments<T: Config> =
+        StorageMap<_, Blake2_128Concat, Commitment, T::AccountId, OptionQuery>;
+
+    /// Proven findings not yet acknowledged or rejected by the report origin
+    #[pallet::storage]
+    pub type PendingFindings<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Commitment,
+        PendingFinding<T::AccountId, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Latest endorsement of a target (first key) by an endorser (second key)
+    #[pallet::storage]
+    pub type Endorsements<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        Endorsement<BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// The running decay round, if any
+    ///
+    /// Unbounded only in the length of the raw key, which the account type bounds.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type DecayRound<T: Config> = StorageValue<_, DecayProgress<BlockNumberFor<T>>, OptionQuery>;
+
+    /// Latest slashes of each account, oldest first
+    #[pallet::storage]
+    pub type SlashHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<SlashRecord<BlockNumberFor<T>>, T::MaxSlashHistory>,
+        ValueQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        // TODO: Add events
+        /// A verified finding was credited to a researcher
+        FindingVerified { who: T::AccountId, severity: Severity },
+        /// A researcher was paid for their unclaimed findings
+        RewardClaimed { who: T::AccountId, amount: BalanceOf<T> },
+        /// The verifying key for vulnerability proofs was replaced
+        VerifyingKeySet,
+        /// A vulnerability proof was verified; `submit_proof` findings await acknowledgement
+        ProofAccepted {
+            who: T::AccountId,
+            commitment: Commitment,
+            severity: Severity,
+        },
+        /// A researcher endorsed another, adding `weight` to their reputation
+        Endorsed {
+            by: T::AccountId,
+            target: T::AccountId,
+            weight: u32,
+        },
+        /// A researcher's reputation score changed
+        ScoreUpdated { who: T::AccountId, new_score: u32 },
+        /// Scores of `count` inactive researchers decayed in this block's batch
+        ScoresDecayed { count: u32 },
+        /// Up to `amount` was taken from an account's reputation
+        ReputationSlashed { who: T::AccountId, amount: u32 },
+        /// The report origin credited a pending finding at `severity`
+        FindingAcknowledged {
+            who: T::AccountId,
+            commitment: Commitment,
+            severity: Severity,
+        },
+        /// The report origin dropped a pending finding
+        FindingRejected { who: T::AccountId, commitment: Commitment },
     }
 
     #[pallet::error]
     pub enum Error<T> {
-        // TODO: Add errors
+        /// The caller has no verified findings left to claim
+        NothingToClaim,
+        /// The treasury can't cover the reward
+        InsufficientTreasuryBalance,
+        /// No verifying key has been set for vulnerability proofs
+        VerifyingKeyNotSet,
+        /// The verifying key is not a valid compressed Groth16 key
+        InvalidVerifyingKey,
+        /// A proof for this commitment was already accepted
+        DuplicateCommitment,
+        /// The proof is malformed or does not verify against the commitment
+        InvalidProof,
+        /// Researchers can't endorse themselves
+        SelfEndorsement,
+        /// The endorser has no reputation to lend
+        InsufficientReputation,
+        /// The endorser already endorsed this target within the endorsement period
+        AlreadyEndorsed,
+        /// Severity is not 0 (low) to 3 (critical)
+        InvalidSeverity,
+        /// The account has no reputation record to slash
+        NoReputation,
+        /// The verifier has not confirmed a proof for this commitment
+        UnverifiedCommitment,
+        /// No finding is pending for this commitment
+        UnknownFinding,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let period = T::DecayPeriod::get();
+            if period.is_zero() {
+                return Weight::zero();
+            }
+
+            // A round still running when the next is due finishes first
+            let round = match DecayRound::<T>::get() {
+                Some(round) => round,
+                None if (n % period).is_zero() => DecayProgress { started: n, last_key: None },
+                None => return T::DbWeight::get().reads(1),
+            };
+
+            let (scanned, decayed) = Self::decay_scores(round);
+            T::WeightInfo::decay_scores(scanned, decayed)
+        }
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        // TODO: Add dispatchable functions
+        /// Claim the reward for all of the caller's unclaimed verified findings
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::claim_reward())]
+        pub fn claim_reward(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let findings = UnclaimedFindings::<T>::get(&who);
+            ensure!(!findings.is_empty(), Error::<T>::NothingToClaim);
+
+            let amount = Self::reward_for(&findings);
+            let treasury = Self::account_id();
+            ensure!(
+                T::Currency::free_balance(&treasury) >= amount,
+                Error::<T>::InsufficientTreasuryBalance
+            );
+
+            // Zero the claimable findings first so they can't be paid twice
+            UnclaimedFindings::<T>::remove(&who);
+            T::Currency::transfer(&treasury, &who, amount, ExistenceRequirement::AllowDeath)?;
+
+            Self::deposit_event(Event::RewardClaimed { who, amount });
+            Ok(())
+        }
+
+        /// Set the verifying key `submit_proof` checks proofs against
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::set_verifying_key())]
+        pub fn set_verifying_key(
+            origin: OriginFor<T>,
+            key: BoundedVec<u8, T::MaxVerifyingKeyLen>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::decode_verifying_key(&key)?;
+
+            ProofVerifyingKey::<T>::put(key);
+            Self::deposit_event(Event::VerifyingKeySet);
+            Ok(())
+        }
+
+        /// Prove knowledge of a vulnerability behind `commitment`
+        ///
+        /// On success the finding is pending until the report origin
+        /// acknowledges it with `acknowledge_finding`; `severity` is only the
+        /// caller's claim.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::submit_proof())]
+        pub fn submit_proof(
+            origin: OriginFor<T>,
+            commitment: Commitment,
+            proof_bytes: BoundedVec<u8, T::MaxProofLen>,
+            severity: Severity,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!Self::commitment_used(&commitment), Error::<T>::DuplicateCommitment);
+            Self::verify_proof(&commitment, &proof_bytes)?;
+
+            UsedCommitments::<T>::insert(commitment, &who);
+            Self::hold_finding(who, commitment, severity);
+            Ok(())
+        }
+
+        /// Vouch for `target`, adding weight scaled by the caller's own reputation
+        ///
+        /// The weight is the integer square root of the caller's score.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::endorse())]
+        pub fn endorse(origin: OriginFor<T>, target: T::AccountId) -> DispatchResult {
+            let by = ensure_signed(origin)?;
+            ensure!(by != target, Error::<T>::SelfEndorsement);
+
+            let now = frame_system::Pallet::<T>::block_number();
+            if let Some(previous) = Endorsements::<T>::get(&target, &by) {
+                ensure!(
+                    now >= previous.at.saturating_add(T::EndorsePeriod::get()),
+                    Error::<T>::AlreadyEndorsed
+                );
+            }
+
+            let weight = Self::endorsement_weight(&by);
+            ensure!(weight > 0, Error::<T>::InsufficientReputation);
+
+            Endorsements::<T>::insert(&target, &by, Endorsement { weight, at: now });
+            Self::add_score(&target, weight, false);
+
+            Self::deposit_event(Event::Endorsed { by, target, weight });
+            Ok(())
+        }
+
+        /// Credit `researcher` with a reported vulnerability
+        ///
+        /// `severity` runs from 0 (low) to 3 (critical). The researcher's
+        /// score grows by the severity's points; no reward becomes claimable.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::record_vulnerability())]
+        pub fn record_vulnerability(
+            origin: OriginFor<T>,
+            researcher: T::AccountId,
+            severity: u8,
+        ) -> DispatchResult {
+            T::ReportOrigin::ensure_origin(origin)?;
+            let severity = Severity::try_from(severity).map_err(|_| Error::<T>::InvalidSeverity)?;
+
+            Self::credit_vulnerability(&researcher, severity);
+            Ok(())
+        }
+
+        /// Take up to `amount` from `who`'s reputation, e.g. for a false report
+        ///
+        /// The slash is recorded in [`SlashHistory`], dropping the oldest
+        /// entry once [`Config::MaxSlashHistory`] are kept.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::slash_reputation())]
+        pub fn slash_reputation(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            amount: u32,
+            reason: SlashReason,
+        ) -> DispatchResult {
+            T::SlashOrigin::ensure_origin(origin)?;
+            ensure!(ReputationScores::<T>::contains_key(&who), Error::<T>::NoReputation);
+
+            ReputationScores::<T>::mutate(&who, |record| record.score = record.score.saturating_sub(amount));
+
+            let at = frame_system::Pallet::<T>::block_number();
+            SlashHistory::<T>::mutate(&who, |history| {
+                let end = history.len();
+                // Keeps the newest entries; only fails if MaxSlashHistory is zero
+                let _ = history.force_insert_keep_right(end, SlashRecord { at, amount, reason });
+            });
+
+            Self::deposit_event(Event::ReputationSlashed { who, amount });
+            Ok(())
+        }
+
+        /// Submit a report whose proof the configured verifier confirmed
+        ///
+        /// `severity` runs from 0 (low) to 3 (critical). The verifier confirms
+        /// the proof for the caller, so their reputation grows by the
+        /// severity's points right away; no reward becomes claimable.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::submit_verified_report())]
+        pub fn submit_verified_report(
+            origin: OriginFor<T>,
+            proof_commitment: Commitment,
+            severity: u8,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let severity = Severity::try_from(severity).map_err(|_| Error::<T>::InvalidSeverity)?;
+
+            ensure!(!Self::commitment_used(&proof_commitment), Error::<T>::DuplicateCommitment);
+            ensure!(
+                T::Verifier::is_verified(&who, &proof_commitment),
+                Error::<T>::UnverifiedCommitment
+            );
+
+            VerifiedCommitments::<T>::insert(proof_commitment, &who);
+            Self::credit_vulnerability(&who, severity);
+
+            Self::deposit_event(Event::ProofAccepted { who, commitment: proof_commitment, severity });
+            Ok(())
+        }
+
+        /// Credit a pending finding to its submitter at the assessed `severity`
+        ///
+        /// `severity` runs from 0 (low) to 3 (critical) and replaces the
+        /// submitter's claim. The finding becomes claimable and the
+        /// submitter's reputation grows by the severity's points.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::acknowledge_finding())]
+        pub fn acknowledge_finding(
+            origin: OriginFor<T>,
+            commitment: Commitment,
+            severity: u8,
+        ) -> DispatchResult {
+            T::ReportOrigin::ensure_origin(origin)?;
+            let severity = Severity::try_from(severity).map_err(|_| Error::<T>::InvalidSeverity)?;
+            let finding = PendingFindings::<T>::take(commitment).ok_or(Error::<T>::UnknownFinding)?;
+
+            Self::credit_vulnerability(&finding.who, severity);
+            Self::note_verified_finding(&finding.who, severity);
+
+            Self::deposit_event(Event::FindingAcknowledged { who: finding.who, commitment, severity });
+            Ok(())
+        }
+
+        /// Drop a pending finding without crediting it
+        ///
+        /// The commitment is freed from both proof paths, so whoever actually
+        /// holds the report can still submit its proof either way.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::reject_finding())]
+        pub fn reject_finding(origin: OriginFor<T>, commitment: Commitment) -> DispatchResult {
+            T::ReportOrigin::ensure_origin(origin)?;
+            let finding = PendingFindings::<T>::take(commitment).ok_or(Error::<T>::UnknownFinding)?;
+            UsedCommitments::<T>::remove(commitment);
+            VerifiedCommitments::<T>::remove(commitment);
+
+            Self::deposit_event(Event::FindingRejected { who: finding.who, commitment });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Account holding the reward treasury
+        pub fn account_id() -> T::AccountId {
+            T::PalletId::get().into_account_truncating()
+        }
+
+        /// Credit a verified finding to `who`, claimable through `claim_reward`
+        pub fn note_verified_finding(who: &T::AccountId, severity: Severity) {
+            UnclaimedFindings::<T>::mutate(who, |findings| findings.add(severity));
+            Self::deposit_event(Event::FindingVerified { who: who.clone(), severity });
+        }
+
+        /// Hold a proven finding until the report origin reviews it
+        fn hold_finding(who: T::AccountId, commitment: Commitment, claimed: Severity) {
+            let submitted_at = frame_system::Pallet::<T>::block_number();
+            PendingFindings::<T>::insert(
+                commitment,
+                PendingFinding { who: who.clone(), claimed, submitted_at },
+            );
+            Self::deposit_event(Event::ProofAccepted { who, commitment, severity: claimed });
+        }
+
+        /// Whether `commitment` was already accepted, by either proof path
+        fn commitment_used(commitment: &Commitment) -> bool {
+            UsedCommitments::<T>::contains_key(commitment) || VerifiedCommitments::<T>::contains_key(commitment)
+        }
+
+        /// Reputation record of `who`, if it has one
+        pub fn reputation_of(who: &T::AccountId) -> Option<ReputationRecord<BlockNumberFor<T>>> {
+            ReputationScores::<T>::try_get(who).ok()
+        }
+
+        /// Up to `limit` researchers with the highest scores, highest first
+        ///
+        /// Scans every record but only ever holds the best `limit` of them.
+        pub fn top_researchers(limit: u32) -> Vec<(T::AccountId, u32)> {
+            let limit = limit as usize;
+            let mut top: Vec<(T::AccountId, u32)> = Vec::new();
+            if limit == 0 {
+                return top;
+            }
+
+            for (who, record) in ReputationScores::<T>::iter() {
+                if top.len() == limit && top.last().is_some_and(|(_, lowest)| record.score <= *lowest) {
+                    continue;
+                }
+                // After any equal scores, so earlier entries win ties
+                let position = top.partition_point(|(_, score)| *score >= record.score);
+                top.insert(position, (who, record.score));
+                top.truncate(limit);
+            }
+            top
+        }
+
+        /// Reputation an endorsement by `endorser` adds to its target
+        pub fn endorsement_weight(endorser: &T::AccountId) -> u32 {
+            ReputationScores::<T>::get(endorser).score.integer_sqrt()
+        }
+
+        /// Run the next batch of a decay round
+        ///
+        /// Scans up to [`Config::MaxDecayPerBlock`] records after the round's
+        /// cursor and decays those inactive for a full period before the
+        /// round started. The round ends once every record was scanned.
+        /// Returns how many records were scanned and how many decayed.
+        fn decay_scores(round: DecayProgress<BlockNumberFor<T>>) -> (u32, u32) {
+            let (rate, floor) = (T::DecayRate::get(), T::ScoreFloor::get());
+            let inactive_since = round.started.saturating_sub(T::DecayPeriod::get());
+            let batch = T::MaxDecayPerBlock::get().max(1);
+
+            let mut records = match round.last_key {
+                Some(key) => ReputationScores::<T>::iter_from(key),
+                None => ReputationScores::<T>::iter(),
+            };
+
+            let mut scanned = 0u32;
+            let mut updates = Vec::new();
+            for (who, mut record) in records.by_ref().take(batch as usize) {
+                scanned = scanned.saturating_add(1);
+                if record.last_updated > inactive_since || record.score <= floor {
+                    continue;
+                }
+
+                let loss = rate.mul_ceil(record.score);
+                let score = record.score.saturating_sub(loss).max(floor);
+                if score != record.score {
+                    record.score = score;
+                    updates.push((who, record));
+                }
+            }
+
+            if scanned < batch {
+                DecayRound::<T>::kill();
+            } else {
+                DecayRound::<T>::put(DecayProgress {
+                    started: round.started,
+                    last_key: Some(records.last_raw_key().to_vec()),
+                });
+            }
+
+            // Written after iterating, since changing the map mid-iteration is undefined
+            let decayed = updates.len() as u32;
+            for (who, record) in updates {
+                ReputationScores::<T>::insert(who, record);
+            }
+
+            if decayed > 0 {
+                Self::deposit_event(Event::ScoresDecayed { count: decayed });
+            }
+            (scanned, decayed)
+        }
+
+        /// Count a vulnerability of `severity` towards `who`'s reputation
+        fn credit_vulnerability(who: &T::AccountId, severity: Severity) {
+            Self::add_score(who, severity.reputation_points(), true);
+        }
+
+        /// Raise `who`'s score by `points`, counting a vulnerability if `vulnerability`
+        fn add_score(who: &T::AccountId, points: u32, vulnerability: bool) {
+            let now = frame_system::Pallet::<T>::block_number();
+            let new_score = ReputationScores::<T>::mutate(who, |record| {
+                record.score = record.score.saturating_add(points);
+                if vulnerability {
+                    record.vulnerabilities_reported = record.vulnerabilities_reported.saturating_add(1);
+                }
+                record.last_updated = now;
+                record.score
+            });
+
+            Self::deposit_event(Event::ScoreUpdated { who: who.clone(), new_score });
+        }
+
+        fn decode_verifying_key(key: &[u8]) -> Result<VerifyingKey<Bn254>, Error<T>> {
+            VerifyingKey::<Bn254>::deserialize_compressed(key).map_err(|_| Error::<T>::InvalidVerifyingKey)
+        }
+
+        /// Check a Groth16 proof whose only public input is the commitment
+        fn verify_proof(commitment: &Commitment, proof_bytes: &[u8]) -> DispatchResult {
+            let key = ProofVerifyingKey::<T>::get().ok_or(Error::<T>::VerifyingKeyNotSet)?;
+            let key = Self::decode_verifying_key(&key)?;
+
+            let proof = Proof::<Bn254>::deserialize_compressed(proof_bytes)
+                .map_err(|_| Error::<T>::InvalidProof)?;
+            let commitment = Fr::deserialize_compressed(&commitment[..])
+                .map_err(|_| Error::<T>::InvalidProof)?;
+
+            let valid = Groth16::<Bn254>::verify_proof(&prepare_verifying_key(&key), &proof, &[commitment])
+                .map_err(|_| Error::<T>::InvalidProof)?;
+            ensure!(valid, Error::<T>::InvalidProof);
+            Ok(())
+        }
+
+        /// Total payout for a set of findings
+        pub fn reward_for(findings: &FindingCounts) -> BalanceOf<T> {
+            [
+                (findings.low, T::LowPayout::get()),
+                (findings.medium, T::MediumPayout::get()),
+                (findings.high, T::HighPayout::get()),
+                (findings.critical, T::CriticalPayout::get()),
+            ]
+            .into_iter()
+            .fold(BalanceOf::<T>::default(), |total, (count, payout)| {
+                total.saturating_add(payout.saturating_mul(count.into()))
+            })
+        }
     }
 }
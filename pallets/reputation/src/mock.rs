@@ -0,0 +1,93 @@
+use crate as pallet_reputation;
+use crate::{Commitment, VerifierProvider};
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU32, ConstU64},
+    PalletId,
+};
+use sp_runtime::{BuildStorage, Percent};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const TREASURY_BALANCE: u64 = 1_000;
+
+pub const ENDORSE_PERIOD: u64 = 100;
+
+pub const DECAY_PERIOD: u64 = 50;
+
+pub const SCORE_FLOOR: u32 = 5;
+
+pub const MAX_DECAY_PER_BLOCK: u32 = 10;
+
+pub const MAX_SLASH_HISTORY: u32 = 3;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Reputation: pallet_reputation,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+}
+
+/// Treats commitments starting with a zero byte as unverified
+pub struct MockVerifier;
+
+impl VerifierProvider<u64> for MockVerifier {
+    fn is_verified(_who: &u64, commitment: &Commitment) -> bool {
+        commitment[0] != 0
+    }
+}
+
+parameter_types! {
+    pub const ReputationPalletId: PalletId = PalletId(*b"sn/reput");
+    pub const ScoreDecayRate: Percent = Percent::from_percent(10);
+}
+
+impl pallet_reputation::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type PalletId = ReputationPalletId;
+    type LowPayout = ConstU64<5>;
+    type MediumPayout = ConstU64<20>;
+    type HighPayout = ConstU64<50>;
+    type CriticalPayout = ConstU64<200>;
+    type MaxVerifyingKeyLen = ConstU32<1024>;
+    type MaxProofLen = ConstU32<256>;
+    type EndorsePeriod = ConstU64<ENDORSE_PERIOD>;
+    type DecayPeriod = ConstU64<DECAY_PERIOD>;
+    type DecayRate = ScoreDecayRate;
+    type ScoreFloor = ConstU32<SCORE_FLOOR>;
+    type MaxDecayPerBlock = ConstU32<MAX_DECAY_PER_BLOCK>;
+    type Verifier = MockVerifier;
+    type ReportOrigin = frame_system::EnsureRoot<u64>;
+    type SlashOrigin = frame_system::EnsureRoot<u64>;
+    type MaxSlashHistory = ConstU32<MAX_SLASH_HISTORY>;
+    type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(Reputation::account_id(), TREASURY_BALANCE)],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    // Events are only recorded from block 1
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
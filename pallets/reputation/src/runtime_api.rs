@@ -0,0 +1,20 @@
+//! Runtime API for reading reputation without decoding raw storage
+
+use crate::ReputationRecord;
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// Reputation queries for frontends and off-chain workers
+    pub trait ReputationApi<AccountId, BlockNumber>
+    where
+        AccountId: Codec,
+        BlockNumber: Codec,
+    {
+        /// Reputation record of `account`, if it has one
+        fn reputation_of(account: AccountId) -> Option<ReputationRecord<BlockNumber>>;
+
+        /// Up to `limit` researchers with the highest scores, highest first
+        fn top_researchers(limit: u32) -> Vec<(AccountId, u32)>;
+    }
+}
@@ -0,0 +1,637 @@
+use crate::{
+    mock::*, Commitment, DecayRound, Endorsement, Endorsements, Error, Event, PendingFinding, PendingFindings,
+    ReputationRecord, ReputationScores, Severity, SlashHistory, SlashReason, SlashRecord,
+    UnclaimedFindings, UsedCommitments, VerifiedCommitments,
+};
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::Groth16;
+use ark_serialize::CanonicalSerialize;
+use ark_snark::SNARK;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use crate::weights::WeightInfo;
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
+use sp_runtime::DispatchError;
+use privacy_layer::circuits::{compute_commitment, VulnerabilityCircuit};
+
+const RESEARCHER: u64 = 1;
+
+/// Compressed verifying key plus a proof for one commitment
+struct ProofFixture {
+    key: Vec<u8>,
+    commitment: Commitment,
+    proof: Vec<u8>,
+}
+
+fn compressed<S: CanonicalSerialize>(value: &S) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    value.serialize_compressed(&mut bytes).unwrap();
+    bytes
+}
+
+fn commitment_of(value: Fr) -> Commitment {
+    compressed(&value).try_into().unwrap()
+}
+
+/// Prove a high severity report with the privacy-layer circuit
+fn proof_fixture() -> ProofFixture {
+    let mut rng = StdRng::seed_from_u64(7);
+    let (proving_key, verifying_key) =
+        Groth16::<Bn254>::circuit_specific_setup(VulnerabilityCircuit::<Fr>::empty(), &mut rng)
+            .unwrap();
+
+    let (severity, category_hash, description_hash, affected_code_hash, blinding) =
+        (Fr::from(2u64), Fr::from(56u64), Fr::from(1234u64), Fr::from(78u64), Fr::from(99u64));
+    let commitment =
+        compute_commitment(severity, category_hash, description_hash, affected_code_hash, blinding);
+    let circuit = VulnerabilityCircuit::new(
+        severity,
+        category_hash,
+        description_hash,
+        affected_code_hash,
+        blinding,
+        commitment,
+    );
+    let proof = Groth16::<Bn254>::prove(&proving_key, circuit, &mut rng).unwrap();
+
+    ProofFixture {
+        key: compressed(&verifying_key),
+        commitment: commitment_of(commitment),
+        proof: compressed(&proof),
+    }
+}
+
+fn score_of(who: u64) -> u32 {
+    ReputationScores::<Test>::get(who).score
+}
+
+fn set_score(who: u64, score: u32) {
+    ReputationScores::<Test>::mutate(who, |record| record.score = score);
+}
+
+fn set_key(fixture: &ProofFixture) {
+    assert_ok!(Reputation::set_verifying_key(
+        RuntimeOrigin::root(),
+        fixture.key.clone().try_into().unwrap()
+    ));
+}
+
+#[test]
+fn claim_reward_pays_unclaimed_findings() {
+    new_test_ext().execute_with(|| {
+        Reputation::note_verified_finding(&RESEARCHER, Severity::High);
+        Reputation::note_verified_finding(&RESEARCHER, Severity::Low);
+        Reputation::note_verified_finding(&RESEARCHER, Severity::Low);
+
+        assert_ok!(Reputation::claim_reward(RuntimeOrigin::signed(RESEARCHER)));
+
+        // One high (50) and two low (5 each)
+        assert_eq!(Balances::free_balance(RESEARCHER), 60);
+        assert_eq!(Balances::free_balance(Reputation::account_id()), TREASURY_BALANCE - 60);
+        assert!(UnclaimedFindings::<Test>::get(RESEARCHER).is_empty());
+        System::assert_last_event(Event::RewardClaimed { who: RESEARCHER, amount: 60 }.into());
+
+        // The same findings can't be claimed twice
+        assert_noop!(
+            Reputation::claim_reward(RuntimeOrigin::signed(RESEARCHER)),
+            Error::<Test>::NothingToClaim
+        );
+    });
+}
+
+#[test]
+fn claim_reward_rejects_empty_claimable() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Reputation::claim_reward(RuntimeOrigin::signed(RESEARCHER)),
+            Error::<Test>::NothingToClaim
+        );
+    });
+}
+
+#[test]
+fn claim_reward_rejects_insufficient_treasury() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..6 {
+            Reputation::note_verified_finding(&RESEARCHER, Severity::Critical);
+        }
+
+        assert_noop!(
+            Reputation::claim_reward(RuntimeOrigin::signed(RESEARCHER)),
+            Error::<Test>::InsufficientTreasuryBalance
+        );
+        // Findings stay claimable once the treasury is topped up
+        assert_eq!(UnclaimedFindings::<Test>::get(RESEARCHER).critical, 6);
+    });
+}
+
+#[test]
+fn submit_proof_holds_finding_until_acknowledged() {
+    new_test_ext().execute_with(|| {
+        let fixture = proof_fixture();
+        set_key(&fixture);
+
+        assert_ok!(Reputation::submit_proof(
+            RuntimeOrigin::signed(RESEARCHER),
+            fixture.commitment,
+            fixture.proof.clone().try_into().unwrap(),
+            Severity::Critical
+        ));
+
+        // The claimed severity isn't bound by the proof, so nothing is credited yet
+        assert_eq!(score_of(RESEARCHER), 0);
+        assert!(UnclaimedFindings::<Test>::get(RESEARCHER).is_empty());
+        assert_eq!(UsedCommitments::<Test>::get(fixture.commitment), Some(RESEARCHER));
+        assert_eq!(
+            PendingFindings::<Test>::get(fixture.commitment),
+            Some(PendingFinding { who: RESEARCHER, claimed: Severity::Critical, submitted_at: 1 })
+        );
+        System::assert_last_event(
+            Event::ProofAccepted {
+                who: RESEARCHER,
+                commitment: fixture.commitment,
+                severity: Severity::Critical,
+            }
+            .into(),
+        );
+
+        // The report origin assessed it as high
+        assert_noop!(
+            Reputation::acknowledge_finding(RuntimeOrigin::signed(RESEARCHER), fixture.commitment, 3),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Reputation::acknowledge_finding(RuntimeOrigin::root(), fixture.commitment, 2));
+        System::assert_last_event(
+            Event::FindingAcknowledged {
+                who: RESEARCHER,
+                commitment: fixture.commitment,
+                severity: Severity::High,
+            }
+            .into(),
+        );
+        assert_eq!(score_of(RESEARCHER), Severity::High.reputation_points());
+        assert_eq!(ReputationScores::<Test>::get(RESEARCHER).vulnerabilities_reported, 1);
+        assert_eq!(UnclaimedFindings::<Test>::get(RESEARCHER).high, 1);
+        assert!(PendingFindings::<Test>::get(fixture.commitment).is_none());
+        assert_noop!(
+            Reputation::acknowledge_finding(RuntimeOrigin::root(), fixture.commitment, 2),
+            Error::<Test>::UnknownFinding
+        );
+
+        // The commitment acts as a nullifier
+        assert_noop!(
+            Reputation::submit_proof(
+                RuntimeOrigin::signed(2),
+                fixture.commitment,
+                fixture.proof.try_into().unwrap(),
+                Severity::High
+            ),
+            Error::<Test>::DuplicateCommitment
+        );
+    });
+}
+
+#[test]
+fn submit_proof_rejects_invalid_proof() {
+    new_test_ext().execute_with(|| {
+        let fixture = proof_fixture();
+        set_key(&fixture);
+
+        // A valid proof does not verify against another commitment
+        assert_noop!(
+            Reputation::submit_proof(
+                RuntimeOrigin::signed(RESEARCHER),
+                commitment_of(Fr::from(42u64)),
+                fixture.proof.clone().try_into().unwrap(),
+                Severity::Critical
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        // Nor does garbage
+        assert_noop!(
+            Reputation::submit_proof(
+                RuntimeOrigin::signed(RESEARCHER),
+                fixture.commitment,
+                vec![0u8; fixture.proof.len()].try_into().unwrap(),
+                Severity::Critical
+            ),
+            Error::<Test>::InvalidProof
+        );
+        assert_eq!(score_of(RESEARCHER), 0);
+    });
+}
+
+#[test]
+fn submit_proof_requires_verifying_key() {
+    new_test_ext().execute_with(|| {
+        let fixture = proof_fixture();
+
+        assert_noop!(
+            Reputation::submit_proof(
+                RuntimeOrigin::signed(RESEARCHER),
+                fixture.commitment,
+                fixture.proof.try_into().unwrap(),
+                Severity::Low
+            ),
+            Error::<Test>::VerifyingKeyNotSet
+        );
+        assert_noop!(
+            Reputation::set_verifying_key(RuntimeOrigin::root(), vec![1u8; 64].try_into().unwrap()),
+            Error::<Test>::InvalidVerifyingKey
+        );
+    });
+}
+
+#[test]
+fn endorse_weight_scales_with_endorser_reputation() {
+    new_test_ext().execute_with(|| {
+        let (veteran, newcomer, target) = (10, 11, RESEARCHER);
+        set_score(veteran, 100);
+        set_score(newcomer, 4);
+
+        assert_ok!(Reputation::endorse(RuntimeOrigin::signed(veteran), target));
+        System::assert_last_event(Event::Endorsed { by: veteran, target, weight: 10 }.into());
+
+        assert_ok!(Reputation::endorse(RuntimeOrigin::signed(newcomer), target));
+        System::assert_last_event(Event::Endorsed { by: newcomer, target, weight: 2 }.into());
+
+        assert_eq!(score_of(target), 12);
+        assert_eq!(
+            Endorsements::<Test>::get(target, veteran),
+            Some(Endorsement { weight: 10, at: 1 })
+        );
+    });
+}
+
+#[test]
+fn endorse_once_per_period() {
+    new_test_ext().execute_with(|| {
+        set_score(10, 25);
+        assert_ok!(Reputation::endorse(RuntimeOrigin::signed(10), RESEARCHER));
+
+        System::set_block_number(ENDORSE_PERIOD);
+        assert_noop!(
+            Reputation::endorse(RuntimeOrigin::signed(10), RESEARCHER),
+            Error::<Test>::AlreadyEndorsed
+        );
+
+        // Another target is fine, and so is the same one once the period is over
+        assert_ok!(Reputation::endorse(RuntimeOrigin::signed(10), 2));
+        System::set_block_number(1 + ENDORSE_PERIOD);
+        assert_ok!(Reputation::endorse(RuntimeOrigin::signed(10), RESEARCHER));
+        assert_eq!(score_of(RESEARCHER), 10);
+    });
+}
+
+#[test]
+fn endorse_requires_reputation_and_another_account() {
+    new_test_ext().execute_with(|| {
+        // A fresh (e.g. sybil) account carries no weight
+        assert_noop!(
+            Reputation::endorse(RuntimeOrigin::signed(10), RESEARCHER),
+            Error::<Test>::InsufficientReputation
+        );
+
+        set_score(RESEARCHER, 50);
+        assert_noop!(
+            Reputation::endorse(RuntimeOrigin::signed(RESEARCHER), RESEARCHER),
+            Error::<Test>::SelfEndorsement
+        );
+    });
+}
+
+#[test]
+fn record_vulnerability_weights_score_by_severity() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Reputation::record_vulnerability(RuntimeOrigin::root(), RESEARCHER, 3));
+        System::assert_last_event(Event::ScoreUpdated { who: RESEARCHER, new_score: 15 }.into());
+
+        System::set_block_number(5);
+        assert_ok!(Reputation::record_vulnerability(RuntimeOrigin::root(), RESEARCHER, 2));
+        assert_ok!(Reputation::record_vulnerability(RuntimeOrigin::root(), RESEARCHER, 1));
+        assert_ok!(Reputation::record_vulnerability(RuntimeOrigin::root(), RESEARCHER, 0));
+        System::assert_last_event(Event::ScoreUpdated { who: RESEARCHER, new_score: 26 }.into());
+
+        // Critical 15, high 7, medium 3 and low 1
+        assert_eq!(
+            ReputationScores::<Test>::get(RESEARCHER),
+            ReputationRecord { score: 26, vulnerabilities_reported: 4, last_updated: 5 }
+        );
+        // Reports build reputation but are not claimable
+        assert!(UnclaimedFindings::<Test>::get(RESEARCHER).is_empty());
+    });
+}
+
+#[test]
+fn record_vulnerability_rejects_bad_origin_and_severity() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Reputation::record_vulnerability(RuntimeOrigin::signed(RESEARCHER), RESEARCHER, 3),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Reputation::record_vulnerability(RuntimeOrigin::root(), RESEARCHER, 4),
+            Error::<Test>::InvalidSeverity
+        );
+        assert_eq!(ReputationScores::<Test>::get(RESEARCHER), ReputationRecord::default());
+    });
+}
+
+fn record(score: u32, last_updated: u64) -> ReputationRecord<u64> {
+    ReputationRecord { score, vulnerabilities_reported: 1, last_updated }
+}
+
+#[test]
+fn inactive_scores_decay_every_period() {
+    new_test_ext().execute_with(|| {
+        let (idle, near_floor, at_floor, active) = (1, 2, 3, 4);
+        ReputationScores::<Test>::insert(idle, record(100, 1));
+        ReputationScores::<Test>::insert(near_floor, record(SCORE_FLOOR + 1, 1));
+        ReputationScores::<Test>::insert(at_floor, record(SCORE_FLOOR, 1));
+        ReputationScores::<Test>::insert(active, record(100, DECAY_PERIOD + 10));
+
+        // Only every DECAY_PERIOD blocks
+        assert_eq!(Reputation::on_initialize(DECAY_PERIOD - 1), Weight::zero());
+
+        // Everyone was active within the last period
+        System::set_block_number(DECAY_PERIOD);
+        assert_eq!(Reputation::on_initialize(DECAY_PERIOD), <() as WeightInfo>::decay_scores(4, 0));
+        assert_eq!(score_of(idle), 100);
+        assert!(System::events().is_empty());
+
+        System::set_block_number(2 * DECAY_PERIOD);
+        assert_eq!(
+            Reputation::on_initialize(2 * DECAY_PERIOD),
+            <() as WeightInfo>::decay_scores(4, 2)
+        );
+        System::assert_last_event(Event::ScoresDecayed { count: 2 }.into());
+        // 10% off, at least one point, never below the floor
+        assert_eq!(score_of(idle), 90);
+        assert_eq!(score_of(near_floor), SCORE_FLOOR);
+        assert_eq!(score_of(at_floor), SCORE_FLOOR);
+        assert_eq!(score_of(active), 100);
+        // Decay is not activity
+        assert_eq!(ReputationScores::<Test>::get(idle), record(90, 1));
+
+        System::set_block_number(3 * DECAY_PERIOD);
+        Reputation::on_initialize(3 * DECAY_PERIOD);
+        System::assert_last_event(Event::ScoresDecayed { count: 2 }.into());
+        assert_eq!(score_of(idle), 81);
+        assert_eq!(score_of(active), 90);
+    });
+}
+
+#[test]
+fn decay_round_is_spread_over_blocks() {
+    new_test_ext().execute_with(|| {
+        let researchers = 2 * MAX_DECAY_PER_BLOCK + 5;
+        for who in 0..researchers as u64 {
+            ReputationScores::<Test>::insert(who, record(100, 1));
+        }
+
+        let start = 2 * DECAY_PERIOD;
+        let batch = MAX_DECAY_PER_BLOCK;
+        for (offset, scanned) in [(0, batch), (1, batch), (2, 5)] {
+            System::set_block_number(start + offset);
+            assert_eq!(
+                Reputation::on_initialize(start + offset),
+                <() as WeightInfo>::decay_scores(scanned, scanned)
+            );
+            System::assert_last_event(Event::ScoresDecayed { count: scanned }.into());
+        }
+        assert!(DecayRound::<Test>::get().is_none());
+
+        // Every record decayed exactly once
+        assert!((0..researchers as u64).all(|who| score_of(who) == 90));
+
+        // Nothing to do until the next round
+        System::set_block_number(start + 3);
+        assert_eq!(Reputation::on_initialize(start + 3), Weight::zero());
+    });
+}
+
+#[test]
+fn slash_reputation_saturates_and_records_history() {
+    new_test_ext().execute_with(|| {
+        ReputationScores::<Test>::insert(RESEARCHER, record(20, 1));
+
+        assert_ok!(Reputation::slash_reputation(
+            RuntimeOrigin::root(),
+            RESEARCHER,
+            15,
+            SlashReason::FalseReport
+        ));
+        System::assert_last_event(Event::ReputationSlashed { who: RESEARCHER, amount: 15 }.into());
+        assert_eq!(score_of(RESEARCHER), 5);
+
+        System::set_block_number(2);
+        assert_ok!(Reputation::slash_reputation(
+            RuntimeOrigin::root(),
+            RESEARCHER,
+            100,
+            SlashReason::Misconduct
+        ));
+        assert_eq!(score_of(RESEARCHER), 0);
+        assert_eq!(
+            SlashHistory::<Test>::get(RESEARCHER).into_inner(),
+            vec![
+                SlashRecord { at: 1, amount: 15, reason: SlashReason::FalseReport },
+                SlashRecord { at: 2, amount: 100, reason: SlashReason::Misconduct },
+            ]
+        );
+    });
+}
+
+#[test]
+fn slash_history_keeps_only_latest_slashes() {
+    new_test_ext().execute_with(|| {
+        ReputationScores::<Test>::insert(RESEARCHER, record(100, 1));
+
+        for amount in 1..=(MAX_SLASH_HISTORY + 2) {
+            assert_ok!(Reputation::slash_reputation(
+                RuntimeOrigin::root(),
+                RESEARCHER,
+                amount,
+                SlashReason::DuplicateReport
+            ));
+        }
+
+        // The two oldest slashes were dropped once the history was full
+        let history = SlashHistory::<Test>::get(RESEARCHER);
+        assert_eq!(history.len() as u32, MAX_SLASH_HISTORY);
+        let amounts: Vec<u32> = history.iter().map(|slash| slash.amount).collect();
+        assert_eq!(amounts, vec![3, 4, 5]);
+        // Every slash still took effect
+        assert_eq!(score_of(RESEARCHER), 100 - 15);
+    });
+}
+
+#[test]
+fn slash_reputation_requires_origin_and_record() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Reputation::slash_reputation(RuntimeOrigin::root(), RESEARCHER, 1, SlashReason::FalseReport),
+            Error::<Test>::NoReputation
+        );
+
+        ReputationScores::<Test>::insert(RESEARCHER, record(10, 1));
+        assert_noop!(
+            Reputation::slash_reputation(
+                RuntimeOrigin::signed(2),
+                RESEARCHER,
+                1,
+                SlashReason::FalseReport
+            ),
+            DispatchError::BadOrigin
+        );
+        assert!(SlashHistory::<Test>::get(RESEARCHER).is_empty());
+    });
+}
+
+#[test]
+fn top_researchers_ordered_by_descending_score() {
+    new_test_ext().execute_with(|| {
+        for (who, score) in [(1, 40), (2, 90), (3, 10), (4, 70), (5, 55)] {
+            ReputationScores::<Test>::insert(who, record(score, 1));
+        }
+
+        assert_eq!(Reputation::top_researchers(3), vec![(2, 90), (4, 70), (5, 55)]);
+        assert_eq!(
+            Reputation::top_researchers(10),
+            vec![(2, 90), (4, 70), (5, 55), (1, 40), (3, 10)]
+        );
+        assert!(Reputation::top_researchers(0).is_empty());
+    });
+}
+
+#[test]
+fn reputation_of_reads_stored_record() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Reputation::reputation_of(&RESEARCHER), None);
+
+        assert_ok!(Reputation::record_vulnerability(RuntimeOrigin::root(), RESEARCHER, 1));
+        assert_eq!(
+            Reputation::reputation_of(&RESEARCHER),
+            Some(ReputationRecord { score: 3, vulnerabilities_reported: 1, last_updated: 1 })
+        );
+    });
+}
+
+const VERIFIED: Commitment = [7u8; 32];
+
+#[test]
+fn submit_verified_report_credits_reputation_on_first_acceptance() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Reputation::submit_verified_report(RuntimeOrigin::signed(RESEARCHER), VERIFIED, 2));
+        assert_eq!(VerifiedCommitments::<Test>::get(VERIFIED), Some(RESEARCHER));
+        System::assert_last_event(
+            Event::ProofAccepted { who: RESEARCHER, commitment: VERIFIED, severity: Severity::High }
+                .into(),
+        );
+        assert_eq!(score_of(RESEARCHER), Severity::High.reputation_points());
+
+        // The severity is only claimed: nothing is paid out and nothing awaits review
+        assert!(UnclaimedFindings::<Test>::get(RESEARCHER).is_empty());
+        assert!(PendingFindings::<Test>::get(VERIFIED).is_none());
+
+        // Replaying the commitment, even from another account, is rejected
+        assert_noop!(
+            Reputation::submit_verified_report(RuntimeOrigin::signed(RESEARCHER), VERIFIED, 2),
+            Error::<Test>::DuplicateCommitment
+        );
+        assert_noop!(
+            Reputation::submit_verified_report(RuntimeOrigin::signed(2), VERIFIED, 3),
+            Error::<Test>::DuplicateCommitment
+        );
+        assert_eq!(score_of(RESEARCHER), Severity::High.reputation_points());
+    });
+}
+
+#[test]
+fn rejected_verified_report_can_be_resubmitted() {
+    new_test_ext().execute_with(|| {
+        // A verifier-confirmed report still pending review, e.g. held by an earlier runtime
+        VerifiedCommitments::<Test>::insert(VERIFIED, 2);
+        PendingFindings::<Test>::insert(
+            VERIFIED,
+            PendingFinding { who: 2, claimed: Severity::Critical, submitted_at: 1 },
+        );
+
+        assert_ok!(Reputation::reject_finding(RuntimeOrigin::root(), VERIFIED));
+        assert!(VerifiedCommitments::<Test>::get(VERIFIED).is_none());
+        assert_eq!(score_of(2), 0);
+
+        assert_ok!(Reputation::submit_verified_report(RuntimeOrigin::signed(RESEARCHER), VERIFIED, 1));
+        assert_eq!(VerifiedCommitments::<Test>::get(VERIFIED), Some(RESEARCHER));
+        assert_eq!(score_of(RESEARCHER), Severity::Medium.reputation_points());
+    });
+}
+
+#[test]
+fn submit_verified_report_rejects_unverified_and_reused_commitments() {
+    new_test_ext().execute_with(|| {
+        let unverified = [0u8; 32];
+        assert_noop!(
+            Reputation::submit_verified_report(RuntimeOrigin::signed(RESEARCHER), unverified, 1),
+            Error::<Test>::UnverifiedCommitment
+        );
+        assert_noop!(
+            Reputation::submit_verified_report(RuntimeOrigin::signed(RESEARCHER), VERIFIED, 4),
+            Error::<Test>::InvalidSeverity
+        );
+
+        // A commitment already credited through submit_proof can't be credited again
+        UsedCommitments::<Test>::insert(VERIFIED, 2);
+        assert_noop!(
+            Reputation::submit_verified_report(RuntimeOrigin::signed(RESEARCHER), VERIFIED, 1),
+            Error::<Test>::DuplicateCommitment
+        );
+        assert_eq!(score_of(RESEARCHER), 0);
+    });
+}
+
+#[test]
+fn rejected_finding_is_never_credited() {
+    new_test_ext().execute_with(|| {
+        let fixture = proof_fixture();
+        set_key(&fixture);
+        assert_noop!(
+            Reputation::reject_finding(RuntimeOrigin::root(), fixture.commitment),
+            Error::<Test>::UnknownFinding
+        );
+
+        // e.g. a proof copied from the transaction pool, or a made-up report
+        assert_ok!(Reputation::submit_proof(
+            RuntimeOrigin::signed(2),
+            fixture.commitment,
+            fixture.proof.clone().try_into().unwrap(),
+            Severity::Critical
+        ));
+        assert_noop!(
+            Reputation::reject_finding(RuntimeOrigin::signed(RESEARCHER), fixture.commitment),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Reputation::reject_finding(RuntimeOrigin::root(), fixture.commitment));
+        System::assert_last_event(
+            Event::FindingRejected { who: 2, commitment: fixture.commitment }.into(),
+        );
+
+        assert_noop!(
+            Reputation::acknowledge_finding(RuntimeOrigin::root(), fixture.commitment, 3),
+            Error::<Test>::UnknownFinding
+        );
+        assert_eq!(score_of(2), 0);
+        assert!(UnclaimedFindings::<Test>::get(2).is_empty());
+
+        // The commitment is freed for the researcher who holds the report
+        assert!(UsedCommitments::<Test>::get(fixture.commitment).is_none());
+        assert_ok!(Reputation::submit_proof(
+            RuntimeOrigin::signed(RESEARCHER),
+            fixture.commitment,
+            fixture.proof.clone().try_into().unwrap(),
+            Severity::High
+        ));
+        assert_ok!(Reputation::acknowledge_finding(RuntimeOrigin::root(), fixture.commitment, 2));
+        assert_eq!(score_of(RESEARCHER), Severity::High.reputation_points());
+    });
+}
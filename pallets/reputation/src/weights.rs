@@ -0,0 +1,104 @@
+//! Weights for the reputation pallet
+//!
+//! The `()` weights are hand estimates. `submit_proof` and
+//! `set_verifying_key`, the calls that run BN254 curve arithmetic in wasm,
+//! have benchmarks in `benchmarking.rs`; regenerate them on reference
+//! hardware with
+//!
+//! ```text
+//! cargo build --release --features runtime-benchmarks
+//! ./target/release/security-nexus-node benchmark pallet --pallet pallet_reputation \
+//!     --extrinsic '*' --steps 50 --repeat 20 --output pallets/reputation/src/weights.rs
+//! ```
+
+use frame_support::weights::{constants::RocksDbWeight, Weight};
+
+pub trait WeightInfo {
+    fn claim_reward() -> Weight;
+    fn set_verifying_key() -> Weight;
+    fn submit_proof() -> Weight;
+    fn endorse() -> Weight;
+    fn record_vulnerability() -> Weight;
+    fn decay_scores(scanned: u32, decayed: u32) -> Weight;
+    fn slash_reputation() -> Weight;
+    fn submit_verified_report() -> Weight;
+    fn acknowledge_finding() -> Weight;
+    fn reject_finding() -> Weight;
+}
+
+impl WeightInfo for () {
+    /// Reads the claimable findings and both balances, writes all three
+    fn claim_reward() -> Weight {
+        Weight::from_parts(50_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+
+    /// Deserializes and subgroup-checks the key (three G2 points), writes it
+    ///
+    /// Conservative upper bound of 20 ms until benchmarked.
+    fn set_verifying_key() -> Weight {
+        Weight::from_parts(20_000_000_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+    }
+
+    /// Dominated by the Groth16 check: decoding the key, preparing it (one
+    /// pairing) and three more pairings, all in wasm without host functions
+    ///
+    /// Conservative upper bound of 100 ms until benchmarked, so signed
+    /// callers can't fill blocks with underpriced verification.
+    fn submit_proof() -> Weight {
+        Weight::from_parts(100_000_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+
+    /// Reads the previous endorsement and both scores, writes the endorsement and target score
+    fn endorse() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+
+    /// Reads and writes the researcher's reputation record
+    fn record_vulnerability() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+
+    /// Reads the round and one batch of reputation records, writes the round and the decayed ones
+    fn decay_scores(scanned: u32, decayed: u32) -> Weight {
+        Weight::from_parts(5_000_000, 0)
+            .saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(scanned.into()))
+            .saturating_add(RocksDbWeight::get().reads(1 + scanned as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 + decayed as u64))
+    }
+
+    /// Reads and writes the reputation record and slash history
+    fn slash_reputation() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+
+    /// Reads both commitment maps and the score, writes the commitment and score
+    fn submit_verified_report() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+
+    /// Takes the pending finding, reads and writes the score and claimable findings
+    fn acknowledge_finding() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+
+    /// Takes the pending finding and frees its commitment in both maps
+    fn reject_finding() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+}
@@ -16,7 +16,7 @@ use sp_runtime::{
 };
 
 pub use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-pub use sp_runtime::{MultiAddress, Perbill, Permill};
+pub use sp_runtime::{MultiAddress, Perbill, Percent, Permill};
 
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
@@ -545,9 +545,37 @@ impl pallet_security_registry::Config for Runtime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const ReputationPalletId: frame_support::PalletId = frame_support::PalletId(*b"sn/reput");
+    pub const LowFindingPayout: Balance = 10 * UNIT;
+    pub const MediumFindingPayout: Balance = 50 * UNIT;
+    pub const HighFindingPayout: Balance = 200 * UNIT;
+    pub const CriticalFindingPayout: Balance = 1_000 * UNIT;
+    pub const EndorsementPeriod: BlockNumber = 30 * DAYS;
+    pub const ReputationDecayPeriod: BlockNumber = 30 * DAYS;
+    pub const ReputationDecayRate: Percent = Percent::from_percent(5);
+    pub const ReputationScoreFloor: u32 = 10;
+}
+
 impl pallet_reputation::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type Currency = Balances;
+    type PalletId = ReputationPalletId;
+    type LowPayout = LowFindingPayout;
+    type MediumPayout = MediumFindingPayout;
+    type HighPayout = HighFindingPayout;
+    type CriticalPayout = CriticalFindingPayout;
+    type MaxVerifyingKeyLen = ConstU32<1024>;
+    type MaxProofLen = ConstU32<256>;
+    type EndorsePeriod = EndorsementPeriod;
+    type DecayPeriod = ReputationDecayPeriod;
+    type DecayRate = ReputationDecayRate;
+    type ScoreFloor = ReputationScoreFloor;
+    type MaxDecayPerBlock = ConstU32<200>;
+    type Verifier = ();
+    type ReportOrigin = EnsureRoot<AccountId>;
+    type SlashOrigin = EnsureRoot<AccountId>;
+    type MaxSlashHistory = ConstU32<32>;
     type WeightInfo = ();
 }
 
@@ -691,6 +719,16 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_reputation::runtime_api::ReputationApi<Block, AccountId, BlockNumber> for Runtime {
+        fn reputation_of(account: AccountId) -> Option<pallet_reputation::ReputationRecord<BlockNumber>> {
+            Reputation::reputation_of(&account)
+        }
+
+        fn top_researchers(limit: u32) -> Vec<(AccountId, u32)> {
+            Reputation::top_researchers(limit)
+        }
+    }
+
     impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
         fn query_info(
             uxt: <Block as BlockT>::Extrinsic,